@@ -2,14 +2,14 @@
 
 use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
 use luxor::{
-    Color, ColorSystem, Console, ConsoleOptions, Measurable, Measurement, Renderable, Segment,
-    Style, Text,
+    Color, ColorSystem, Console, ConsoleOptions, Measurable, Measurement, RenderOptions,
+    Renderable, Segment, Style, Text,
 };
 
 /// Benchmark text rendering performance.
 fn benchmark_text_rendering(c: &mut Criterion) {
     let console = Console::new();
-    let options = ConsoleOptions::new();
+    let options = RenderOptions::new();
 
     // Benchmark simple text rendering
     c.bench_function("render simple text", |b| {
@@ -21,8 +21,8 @@ fn benchmark_text_rendering(c: &mut Criterion) {
 
     // Benchmark styled text rendering
     c.bench_function("render styled text", |b| {
-        let text =
-            Text::new("Hello, world!").with_style(Style::new().bold().color(Color::rgb(255, 0, 0)));
+        let text = Text::new("Hello, world!")
+            .with_style(Style::new().bold().with_color(Color::rgb(255, 0, 0)));
         b.iter(|| {
             let _segments = text.render(&console, &options).unwrap();
         });
@@ -35,8 +35,8 @@ fn benchmark_text_rendering(c: &mut Criterion) {
                 .bold()
                 .italic()
                 .underline()
-                .color(Color::rgb(255, 128, 64))
-                .background(Color::rgb(64, 128, 255)),
+                .with_color(Color::rgb(255, 128, 64))
+                .with_background(Color::rgb(64, 128, 255)),
         );
         b.iter(|| {
             let _segments = text.render(&console, &options).unwrap();
@@ -58,7 +58,7 @@ fn benchmark_style_operations(c: &mut Criterion) {
     // Benchmark style creation
     c.bench_function("create simple style", |b| {
         b.iter(|| {
-            let _style = Style::new().bold().color(Color::rgb(255, 0, 0));
+            let _style = Style::new().bold().with_color(Color::rgb(255, 0, 0));
         });
     });
 
@@ -73,15 +73,15 @@ fn benchmark_style_operations(c: &mut Criterion) {
                 .reverse()
                 .blink()
                 .hidden()
-                .color(Color::rgb(255, 128, 64))
-                .background(Color::rgb(64, 128, 255));
+                .with_color(Color::rgb(255, 128, 64))
+                .with_background(Color::rgb(64, 128, 255));
         });
     });
 
     // Benchmark style combination
     c.bench_function("combine styles", |b| {
-        let base = Style::new().bold().color(Color::rgb(255, 0, 0));
-        let overlay = Style::new().italic().background(Color::rgb(0, 255, 0));
+        let base = Style::new().bold().with_color(Color::rgb(255, 0, 0));
+        let overlay = Style::new().italic().with_background(Color::rgb(0, 255, 0));
         b.iter(|| {
             let _combined = base.clone().combine(overlay.clone());
         });
@@ -142,7 +142,7 @@ fn benchmark_color_operations(c: &mut Criterion) {
 
 /// Benchmark segment operations.
 fn benchmark_segment_operations(c: &mut Criterion) {
-    let style = Style::new().bold().color(Color::rgb(255, 0, 0));
+    let style = Style::new().bold().with_color(Color::rgb(255, 0, 0));
 
     // Benchmark segment creation
     c.bench_function("create segment", |b| {
@@ -223,7 +223,7 @@ fn benchmark_ansi_operations(c: &mut Criterion) {
 
     // Benchmark ANSI escape generation
     c.bench_function("generate ansi escape", |b| {
-        let style = Style::new().bold().color(Color::rgb(255, 0, 0));
+        let style = Style::new().bold().with_color(Color::rgb(255, 0, 0));
         b.iter(|| {
             let _ansi = style_to_ansi(&style, ColorSystem::TrueColor);
         });
@@ -255,9 +255,8 @@ fn benchmark_ansi_operations(c: &mut Criterion) {
 
 /// Benchmark different color systems.
 fn benchmark_color_systems(c: &mut Criterion) {
-    let console = Console::new();
-    let text =
-        Text::new("Benchmark text").with_style(Style::new().bold().color(Color::rgb(255, 128, 64)));
+    let text = Text::new("Benchmark text")
+        .with_style(Style::new().bold().with_color(Color::rgb(255, 128, 64)));
 
     let mut group = c.benchmark_group("color_systems");
 
@@ -266,7 +265,8 @@ fn benchmark_color_systems(c: &mut Criterion) {
         ColorSystem::EightBit,
         ColorSystem::TrueColor,
     ] {
-        let options = ConsoleOptions::new().with_color_system(color_system);
+        let console = Console::with_options(ConsoleOptions::new().with_color_system(color_system));
+        let options = console.get_render_options();
 
         group.bench_with_input(
             BenchmarkId::new("render", format!("{:?}", color_system)),
@@ -285,7 +285,7 @@ fn benchmark_color_systems(c: &mut Criterion) {
 /// Benchmark scaling with text size.
 fn benchmark_text_scaling(c: &mut Criterion) {
     let console = Console::new();
-    let options = ConsoleOptions::new();
+    let options = RenderOptions::new();
 
     let mut group = c.benchmark_group("text_scaling");
 
@@ -318,7 +318,7 @@ fn benchmark_allocation_patterns(c: &mut Criterion) {
             |_| {
                 let _styles: Vec<Style> = (0..1000)
                     .map(|i| {
-                        Style::new().bold().color(Color::rgb(
+                        Style::new().bold().with_color(Color::rgb(
                             (i % 256) as u8,
                             ((i / 256) % 256) as u8,
                             ((i / 65536) % 256) as u8,
@@ -1,9 +1,12 @@
 use criterion::{Criterion, criterion_group, criterion_main};
-use luxor::{Color, Console, ConsoleOptions, Renderable, Style, Text};
+use luxor::{
+    CacheKey, Cached, Color, ColorSystem, Console, Justify, RenderOptions, Renderable, Segment,
+    Segments, Style, Text,
+};
 
 fn benchmark_text_rendering(c: &mut Criterion) {
     let console = Console::new();
-    let options = ConsoleOptions::new();
+    let options = RenderOptions::new();
 
     c.bench_function("render simple text", |b| {
         let text = Text::new("Hello, world!");
@@ -13,37 +16,161 @@ fn benchmark_text_rendering(c: &mut Criterion) {
     });
 
     c.bench_function("render styled text", |b| {
-        let text =
-            Text::new("Hello, world!").with_style(Style::new().bold().color(Color::rgb(255, 0, 0)));
+        let text = Text::new("Hello, world!")
+            .with_style(Style::new().bold().with_color(Color::rgb(255, 0, 0)));
         b.iter(|| {
             let _segments = text.render(&console, &options).unwrap();
         });
     });
 }
 
+fn benchmark_text_to_segments(c: &mut Criterion) {
+    // 10k characters with 2k overlapping spans, like a highlighted log file
+    let content: String = (0..10_000)
+        .map(|index| if index % 7 == 6 { ' ' } else { 'x' })
+        .collect();
+    let mut text = Text::new(&content);
+    let styles = [
+        Style::new().bold(),
+        Style::new().with_color(Color::rgb(255, 0, 0)),
+        Style::new().italic().with_background(Color::rgb(0, 0, 80)),
+        Style::new().underline(),
+    ];
+    for index in 0..2_000 {
+        let start = (index * 5) % 9_990;
+        let length = 3 + index % 17;
+        let end = (start + length).min(content.len());
+        text.stylize_range(start..end, styles[index % styles.len()].clone())
+            .unwrap();
+    }
+
+    c.bench_function("to_segments 10k chars 2k spans", |b| {
+        b.iter(|| {
+            let _segments = text.to_segments();
+        });
+    });
+}
+
 fn benchmark_style_operations(c: &mut Criterion) {
     c.bench_function("style creation", |b| {
         b.iter(|| {
             let _style = Style::new()
                 .bold()
                 .italic()
-                .color(Color::rgb(255, 0, 0))
-                .background(Color::rgb(0, 255, 0));
+                .with_color(Color::rgb(255, 0, 0))
+                .with_background(Color::rgb(0, 255, 0));
         });
     });
 
     c.bench_function("style combination", |b| {
-        let base = Style::new().bold().color(Color::rgb(255, 0, 0));
-        let overlay = Style::new().italic().background(Color::rgb(0, 255, 0));
+        let base = Style::new().bold().with_color(Color::rgb(255, 0, 0));
+        let overlay = Style::new().italic().with_background(Color::rgb(0, 255, 0));
         b.iter(|| {
             let _combined = base.clone().combine(overlay.clone());
         });
     });
 }
 
+/// A dashboard panel that builds its body from markup at render time.
+#[derive(Clone)]
+struct StatusPanel {
+    version: u64,
+    rows: Vec<String>,
+}
+
+impl StatusPanel {
+    fn new(version: u64) -> Self {
+        let rows = (0..20)
+            .map(|row| format!("[bold]row {row}[/bold] [green]ok[/green] [dim]v{version}[/dim]"))
+            .collect();
+        Self { version, rows }
+    }
+}
+
+impl Renderable for StatusPanel {
+    fn render(&self, console: &Console, options: &RenderOptions) -> luxor::Result<Vec<Segment>> {
+        Text::from_markup(&self.rows.join("\n"))?
+            .with_justify(Justify::Center)
+            .render(console, options)
+    }
+}
+
+impl CacheKey for StatusPanel {
+    fn cache_key(&self) -> u64 {
+        self.version
+    }
+}
+
+fn benchmark_cached_rendering(c: &mut Criterion) {
+    let console = Console::new();
+    let options = RenderOptions::new().with_max_width(80);
+
+    // Six panels, one of which changes every frame. Updates alternate between
+    // two prebuilt panels so that only rendering is measured.
+    let updates = [StatusPanel::new(1), StatusPanel::new(2)];
+
+    c.bench_function("render 6 panels uncached", |b| {
+        let mut panels: Vec<StatusPanel> = (0..6).map(|_| StatusPanel::new(0)).collect();
+        let mut frame = 0;
+        b.iter(|| {
+            frame += 1;
+            panels[frame % 6] = updates[frame % 2].clone();
+            for panel in &panels {
+                let _segments = panel.render(&console, &options).unwrap();
+            }
+        });
+    });
+
+    c.bench_function("render 6 panels cached", |b| {
+        let mut panels: Vec<Cached<StatusPanel>> =
+            (0..6).map(|_| Cached::new(StatusPanel::new(0))).collect();
+        let mut frame = 0;
+        b.iter(|| {
+            frame += 1;
+            *panels[frame % 6].get_mut() = updates[frame % 2].clone();
+            for panel in &panels {
+                let _segments = panel.render(&console, &options).unwrap();
+            }
+        });
+    });
+}
+
+fn benchmark_frame_output(c: &mut Criterion) {
+    // A 100k-segment frame, like a full-screen dashboard redraw
+    let styles = [
+        Style::new().bold(),
+        Style::new().with_color(Color::rgb(255, 128, 0)),
+        Style::new(),
+    ];
+    let frame: Segments = (0..100_000)
+        .map(|index| {
+            if index % 20 == 19 {
+                Segment::line()
+            } else {
+                Segment::new("cell ", styles[index % styles.len()].clone())
+            }
+        })
+        .collect();
+
+    c.bench_function("frame render to string", |b| {
+        b.iter(|| frame.render(ColorSystem::TrueColor).len());
+    });
+
+    c.bench_function("frame write to sink", |b| {
+        b.iter(|| {
+            frame
+                .write_to(&mut std::io::sink(), ColorSystem::TrueColor)
+                .unwrap()
+        });
+    });
+}
+
 criterion_group!(
     benches,
     benchmark_text_rendering,
-    benchmark_style_operations
+    benchmark_text_to_segments,
+    benchmark_style_operations,
+    benchmark_cached_rendering,
+    benchmark_frame_output
 );
 criterion_main!(benches);
@@ -46,8 +46,8 @@ fn demonstrate_styled_alignment(console: &Console) -> Result<(), LuxorError> {
     let width = 40;
     let style = Style::new()
         .bold()
-        .color(Color::rgb(255, 255, 255))
-        .background(Color::rgb(0, 100, 200));
+        .with_color(Color::rgb(255, 255, 255))
+        .with_background(Color::rgb(0, 100, 200));
 
     // Left aligned with style
     let text = format!("{:<width$}", "LEFT", width = width);
@@ -97,7 +97,8 @@ fn demonstrate_centered_titles(console: &Console) -> Result<(), LuxorError> {
 fn create_centered_title(console: &Console, title: &str, width: usize) -> Result<(), LuxorError> {
     // Create decorative border
     let border = "═".repeat(width);
-    console.print(Text::new(&border).with_style(Style::new().color(Color::rgb(100, 200, 255))))?;
+    console
+        .print(Text::new(&border).with_style(Style::new().with_color(Color::rgb(100, 200, 255))))?;
 
     // Center the title
     let centered = format!("{:^width$}", title, width = width);
@@ -105,13 +106,14 @@ fn create_centered_title(console: &Console, title: &str, width: usize) -> Result
     title_text = title_text.with_style(
         Style::new()
             .bold()
-            .color(Color::rgb(255, 255, 255))
-            .background(Color::rgb(50, 100, 200)),
+            .with_color(Color::rgb(255, 255, 255))
+            .with_background(Color::rgb(50, 100, 200)),
     );
     console.print(title_text)?;
 
     // Bottom border
-    console.print(Text::new(&border).with_style(Style::new().color(Color::rgb(100, 200, 255))))?;
+    console
+        .print(Text::new(&border).with_style(Style::new().with_color(Color::rgb(100, 200, 255))))?;
 
     Ok(())
 }
@@ -123,7 +125,7 @@ fn create_section_header(console: &Console, header: &str, width: usize) -> Resul
         Style::new()
             .bold()
             .underline()
-            .color(Color::rgb(255, 200, 100)),
+            .with_color(Color::rgb(255, 200, 100)),
     );
     console.print(header_text)?;
 
@@ -0,0 +1,75 @@
+//! Markup, Justification and Spacers
+//!
+//! Shows markup parsing with strict and lossy error handling, justified
+//! banners with full-width backgrounds, and spacers laying out a status
+//! line.
+//!
+//! Run with `--capture` to render into a buffer instead of the terminal,
+//! which is how CI exercises this example without a TTY.
+
+use luxor::prelude::*;
+
+const WIDTH: usize = 60;
+
+fn main() -> Result<()> {
+    let console = Console::with_options(ConsoleOptions::new().with_max_width(WIDTH));
+
+    if std::env::args().any(|arg| arg == "--capture") {
+        let output = console.capture(run)?;
+        assert!(!output.is_empty(), "demo produced no output");
+        println!("captured {} bytes", output.len());
+        return Ok(());
+    }
+
+    run(&console)
+}
+
+fn run(console: &Console) -> Result<()> {
+    let banner = Style::new()
+        .bold()
+        .with_color(Color::Standard(StandardColor::BrightWhite))
+        .with_background(Color::rgb(40, 80, 160));
+    console.println(
+        Text::new("LUXOR MARKUP")
+            .with_style(banner)
+            .with_justify(Justify::Center)
+            .with_full_width_background(true),
+    )?;
+    console.println(Text::new(""))?;
+
+    // Well-formed markup renders with styles applied
+    console.println(Text::from_markup(
+        "[bold]Bold[/bold], [italic green]italic green[/italic green] and [red on white]red on white[/]",
+    )?)?;
+
+    // Malformed markup fails in strict mode with the position of the problem...
+    let broken = "Deploy [bold]finished[/italic] in [cyan]3s";
+    if let Err(error) = Text::from_markup(broken) {
+        console.println(Text::new(&format!("strict: {}", error)).with_style(Style::new().dim()))?;
+    }
+
+    // ...and renders anyway in lossy mode, reporting what was recovered
+    let (text, warnings) = Text::from_markup_lossy(broken);
+    console.println(text)?;
+    for warning in warnings {
+        console.println(
+            Text::new(&format!("  warning: {}", warning)).with_style(Style::new().dim()),
+        )?;
+    }
+    console.println(Text::new(""))?;
+
+    // A status line: left and right text with a fill spacer between them
+    let options = console.render_options().clone();
+    let left = Text::from_markup("[bold]build[/bold] #1042")?;
+    let right = Text::from_markup("[green]passed[/green]")?;
+    let used =
+        left.measure(console, &options)?.maximum() + right.measure(console, &options)?.maximum();
+    let fill_options = options.clone().with_max_width(WIDTH.saturating_sub(used));
+
+    let mut line = console.render(left, &options)?;
+    line.extend(console.render(Spacer::fill(), &fill_options)?);
+    line.extend(console.render(right, &options)?);
+    console.println(Segments::from_vec(line))?;
+
+    Ok(())
+}
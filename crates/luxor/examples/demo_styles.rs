@@ -0,0 +1,85 @@
+//! Color Systems and Swatch Grid
+//!
+//! Renders the 16 standard colors, a slice of the 256-color palette and a
+//! true color gradient, then shows how each downgrades to the standard
+//! color system.
+//!
+//! Run with `--capture` to render into a buffer instead of the terminal,
+//! which is how CI exercises this example without a TTY.
+
+use luxor::prelude::*;
+
+fn main() -> Result<()> {
+    let console = Console::with_options(ConsoleOptions::new().with_max_width(80));
+
+    if std::env::args().any(|arg| arg == "--capture") {
+        let output = console.capture(run)?;
+        assert!(!output.is_empty(), "demo produced no output");
+        println!("captured {} bytes", output.len());
+        return Ok(());
+    }
+
+    run(&console)
+}
+
+fn run(console: &Console) -> Result<()> {
+    heading(console, "Standard colors")?;
+    let swatches: Vec<Color> = StandardColor::ALL
+        .iter()
+        .map(|&c| Color::Standard(c))
+        .collect();
+    swatch_rows(console, &swatches, 8)?;
+
+    heading(console, "256-color palette (16-63)")?;
+    let swatches: Vec<Color> = (16..64).map(Color::EightBit).collect();
+    swatch_rows(console, &swatches, 24)?;
+
+    heading(console, "True color gradient")?;
+    let gradient: Vec<Color> = (0..48)
+        .map(|i| {
+            let t = i as f64 / 47.0;
+            Color::rgb((255.0 * (1.0 - t)) as u8, 64, (255.0 * t) as u8)
+        })
+        .collect();
+    swatch_rows(console, &gradient, 48)?;
+
+    heading(console, "Gradient downgraded to standard colors")?;
+    let downgraded: Vec<Color> = gradient
+        .iter()
+        .map(|color| color.downgrade(ColorSystem::Standard))
+        .collect();
+    swatch_rows(console, &downgraded, 48)?;
+
+    heading(console, "Readable labels on every standard background")?;
+    for &color in &StandardColor::ALL {
+        let background = Color::Standard(color);
+        let style = Style::new()
+            .with_color(Color::Standard(StandardColor::BrightBlack))
+            .ensure_contrast(background)
+            .with_background(background);
+        console
+            .println(Text::new(&format!(" {:<14}", format!("{:?}", color))).with_style(style))?;
+    }
+
+    Ok(())
+}
+
+fn heading(console: &Console, title: &str) -> Result<()> {
+    console.println(Text::new(""))?;
+    console.println(Text::new(title).with_style(Style::new().bold().underline()))
+}
+
+/// Print colors as two-cell background swatches, `per_row` to a line.
+fn swatch_rows(console: &Console, colors: &[Color], per_row: usize) -> Result<()> {
+    for row in colors.chunks(per_row) {
+        let mut line = Text::new(&"  ".repeat(row.len()));
+        for (index, &color) in row.iter().enumerate() {
+            line.stylize_range(
+                index * 2..index * 2 + 2,
+                Style::new().with_background(color),
+            )?;
+        }
+        console.println(line)?;
+    }
+    Ok(())
+}
@@ -62,7 +62,7 @@ fn demonstrate_number_highlighter(console: &Console) -> Result<(), LuxorError> {
 /// Simple email highlighter using basic pattern matching
 fn highlight_emails(text: &str) -> Result<Text, LuxorError> {
     let mut result = Text::new(text);
-    let email_style = Style::new().bold().color(Color::rgb(255, 0, 255)); // Magenta
+    let email_style = Style::new().bold().with_color(Color::rgb(255, 0, 255)); // Magenta
 
     // Simple email pattern: word@word.word
     let chars: Vec<char> = text.chars().collect();
@@ -85,7 +85,7 @@ fn highlight_emails(text: &str) -> Result<Text, LuxorError> {
 /// Simple URL highlighter
 fn highlight_urls(text: &str) -> Result<Text, LuxorError> {
     let mut result = Text::new(text);
-    let url_style = Style::new().underline().color(Color::rgb(0, 100, 255)); // Blue
+    let url_style = Style::new().underline().with_color(Color::rgb(0, 100, 255)); // Blue
 
     // Look for http:// or https://
     if let Some(start) = text.find("http://") {
@@ -137,8 +137,10 @@ fn highlight_rust_keywords(text: &str) -> Result<Text, LuxorError> {
                     || !text.chars().nth(keyword_end).unwrap().is_alphanumeric());
 
             if is_word_boundary {
-                result
-                    .stylize_range(absolute_pos..keyword_end, Style::new().bold().color(*color))?;
+                result.stylize_range(
+                    absolute_pos..keyword_end,
+                    Style::new().bold().with_color(*color),
+                )?;
             }
 
             start = absolute_pos + 1;
@@ -151,7 +153,7 @@ fn highlight_rust_keywords(text: &str) -> Result<Text, LuxorError> {
 /// Number highlighter
 fn highlight_numbers(text: &str) -> Result<Text, LuxorError> {
     let mut result = Text::new(text);
-    let number_style = Style::new().color(Color::rgb(100, 255, 100)); // Light green
+    let number_style = Style::new().with_color(Color::rgb(100, 255, 100)); // Light green
 
     let chars: Vec<char> = text.chars().collect();
     let mut i = 0;
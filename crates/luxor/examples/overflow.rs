@@ -34,11 +34,13 @@ fn demonstrate_crop_overflow(
     let cropped = crop_text(text, width);
     let border = "─".repeat(width);
 
-    console.print(Text::new(&border).with_style(Style::new().color(Color::rgb(100, 100, 100))))?;
+    console
+        .print(Text::new(&border).with_style(Style::new().with_color(Color::rgb(100, 100, 100))))?;
     console.print(
-        Text::new(&cropped).with_style(Style::new().bold().color(Color::rgb(0, 100, 255))),
+        Text::new(&cropped).with_style(Style::new().bold().with_color(Color::rgb(0, 100, 255))),
     )?;
-    console.print(Text::new(&border).with_style(Style::new().color(Color::rgb(100, 100, 100))))?;
+    console
+        .print(Text::new(&border).with_style(Style::new().with_color(Color::rgb(100, 100, 100))))?;
     println!();
     Ok(())
 }
@@ -53,11 +55,14 @@ fn demonstrate_ellipsis_overflow(
     let ellipsis_text = add_ellipsis(text, width);
     let border = "─".repeat(width);
 
-    console.print(Text::new(&border).with_style(Style::new().color(Color::rgb(100, 100, 100))))?;
+    console
+        .print(Text::new(&border).with_style(Style::new().with_color(Color::rgb(100, 100, 100))))?;
     console.print(
-        Text::new(&ellipsis_text).with_style(Style::new().bold().color(Color::rgb(255, 100, 0))),
+        Text::new(&ellipsis_text)
+            .with_style(Style::new().bold().with_color(Color::rgb(255, 100, 0))),
     )?;
-    console.print(Text::new(&border).with_style(Style::new().color(Color::rgb(100, 100, 100))))?;
+    console
+        .print(Text::new(&border).with_style(Style::new().with_color(Color::rgb(100, 100, 100))))?;
     println!();
     Ok(())
 }
@@ -72,13 +77,15 @@ fn demonstrate_wrap_overflow(
     let wrapped_lines = wrap_text(text, width);
     let border = "─".repeat(width);
 
-    console.print(Text::new(&border).with_style(Style::new().color(Color::rgb(100, 100, 100))))?;
+    console
+        .print(Text::new(&border).with_style(Style::new().with_color(Color::rgb(100, 100, 100))))?;
     for line in wrapped_lines {
         console.print(
-            Text::new(&line).with_style(Style::new().bold().color(Color::rgb(0, 255, 100))),
+            Text::new(&line).with_style(Style::new().bold().with_color(Color::rgb(0, 255, 100))),
         )?;
     }
-    console.print(Text::new(&border).with_style(Style::new().color(Color::rgb(100, 100, 100))))?;
+    console
+        .print(Text::new(&border).with_style(Style::new().with_color(Color::rgb(100, 100, 100))))?;
     println!();
     Ok(())
 }
@@ -98,14 +105,15 @@ fn demonstrate_styled_overflow(console: &Console) -> Result<(), LuxorError> {
         // Crop with style
         let cropped = crop_text(text, width);
         let mut crop_text_obj = Text::new(&format!("Crop: {}", cropped));
-        crop_text_obj = crop_text_obj.with_style(Style::new().color(Color::rgb(255, 100, 100)));
+        crop_text_obj =
+            crop_text_obj.with_style(Style::new().with_color(Color::rgb(255, 100, 100)));
         console.print(crop_text_obj)?;
 
         // Ellipsis with style
         let ellipsis = add_ellipsis(text, width);
         let mut ellipsis_text_obj = Text::new(&format!("Ellipsis: {}", ellipsis));
         ellipsis_text_obj =
-            ellipsis_text_obj.with_style(Style::new().color(Color::rgb(100, 255, 100)));
+            ellipsis_text_obj.with_style(Style::new().with_color(Color::rgb(100, 255, 100)));
         console.print(ellipsis_text_obj)?;
 
         println!();
@@ -42,7 +42,7 @@ fn create_rainbow_sentence(console: &Console) -> Result<(), LuxorError> {
             .map(|(pos, _)| pos)
             .unwrap_or(text.len());
         let color = colors[i % color_count];
-        rainbow_text.stylize_range(i..next_pos, Style::new().color(color))?;
+        rainbow_text.stylize_range(i..next_pos, Style::new().with_color(color))?;
     }
 
     console.print(rainbow_text)?;
@@ -71,7 +71,7 @@ fn create_gradient_text(console: &Console) -> Result<(), LuxorError> {
         let blue = (ratio * 255.0) as u8;
         let color = Color::rgb(red, 0, blue);
 
-        gradient_text.stylize_range(byte_pos..next_pos, Style::new().color(color))?;
+        gradient_text.stylize_range(byte_pos..next_pos, Style::new().with_color(color))?;
     }
 
     console.print(gradient_text)?;
@@ -98,7 +98,7 @@ fn create_hue_cycle_text(console: &Console) -> Result<(), LuxorError> {
         let hue = (i as f32 / char_count as f32) * 360.0;
         let color = hsv_to_rgb(hue, 1.0, 1.0);
 
-        hue_text.stylize_range(byte_pos..next_pos, Style::new().color(color))?;
+        hue_text.stylize_range(byte_pos..next_pos, Style::new().with_color(color))?;
     }
 
     console.print(hue_text)?;
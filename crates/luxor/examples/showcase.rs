@@ -13,7 +13,7 @@ fn main() -> Result<(), LuxorError> {
 
     // 1. Basic text styling
     let mut basic_text = Text::new("Hello, Luxor!");
-    basic_text = basic_text.with_style(Style::new().bold().color(Color::rgb(0, 255, 0)));
+    basic_text = basic_text.with_style(Style::new().bold().with_color(Color::rgb(0, 255, 0)));
     console.print(basic_text)?;
 
     // 2. Rich markup parsing
@@ -27,11 +27,14 @@ fn main() -> Result<(), LuxorError> {
 
     // 3. Style ranges
     let mut range_text = Text::new("This text has multiple styles applied");
-    range_text.stylize_range(0..4, Style::new().bold().color(Color::rgb(255, 0, 0)))?; // "This"
-    range_text.stylize_range(5..9, Style::new().italic().color(Color::rgb(0, 255, 0)))?; // "text"
+    range_text.stylize_range(0..4, Style::new().bold().with_color(Color::rgb(255, 0, 0)))?; // "This"
+    range_text.stylize_range(
+        5..9,
+        Style::new().italic().with_color(Color::rgb(0, 255, 0)),
+    )?; // "text"
     range_text.stylize_range(
         10..13,
-        Style::new().underline().color(Color::rgb(0, 0, 255)),
+        Style::new().underline().with_color(Color::rgb(0, 0, 255)),
     )?; // "has"
     console.print(range_text)?;
 
@@ -46,7 +49,7 @@ fn main() -> Result<(), LuxorError> {
 
     // RGB colors (using manual construction since rgb() parsing not implemented yet)
     let mut rgb_demo = Text::new("RGB Color (255,100,50)");
-    rgb_demo = rgb_demo.with_style(Style::new().color(Color::rgb(255, 100, 50)));
+    rgb_demo = rgb_demo.with_style(Style::new().with_color(Color::rgb(255, 100, 50)));
     console.print(rgb_demo)?;
 
     // Standard colors
@@ -92,7 +95,7 @@ fn main() -> Result<(), LuxorError> {
     println!("\n=== Performance Test ===");
     let large_text = "Lorem ipsum dolor sit amet ".repeat(50);
     let mut perf_text = Text::new(&large_text);
-    perf_text = perf_text.with_style(Style::new().italic().color(Color::rgb(128, 128, 128)));
+    perf_text = perf_text.with_style(Style::new().italic().with_color(Color::rgb(128, 128, 128)));
 
     let start = std::time::Instant::now();
     console.print(perf_text)?;
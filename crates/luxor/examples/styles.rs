@@ -58,7 +58,7 @@ fn demonstrate_color_variations(console: &Console) -> Result<(), LuxorError> {
 
     for (name, color) in standard_colors {
         let mut text = Text::new(&format!("   {}", name));
-        text = text.with_style(Style::new().color(color));
+        text = text.with_style(Style::new().with_color(color));
         console.print(text)?;
     }
 
@@ -77,7 +77,7 @@ fn demonstrate_color_variations(console: &Console) -> Result<(), LuxorError> {
         let red = ((1.0 - ratio) * 255.0) as u8;
         let blue = (ratio * 255.0) as u8;
         let color = Color::rgb(red, 0, blue);
-        gradient_text.stylize_range(pos..next_pos, Style::new().color(color))?;
+        gradient_text.stylize_range(pos..next_pos, Style::new().with_color(color))?;
     }
 
     console.print(gradient_text)?;
@@ -91,26 +91,29 @@ fn demonstrate_style_combinations(console: &Console) -> Result<(), LuxorError> {
     let combinations = [
         (
             "Bold + Red",
-            Style::new().bold().color(Color::rgb(255, 0, 0)),
+            Style::new().bold().with_color(Color::rgb(255, 0, 0)),
         ),
         (
             "Italic + Green",
-            Style::new().italic().color(Color::rgb(0, 255, 0)),
+            Style::new().italic().with_color(Color::rgb(0, 255, 0)),
         ),
         (
             "Underline + Blue",
-            Style::new().underline().color(Color::rgb(0, 0, 255)),
+            Style::new().underline().with_color(Color::rgb(0, 0, 255)),
         ),
         (
             "Bold + Italic + Yellow",
-            Style::new().bold().italic().color(Color::rgb(255, 255, 0)),
+            Style::new()
+                .bold()
+                .italic()
+                .with_color(Color::rgb(255, 255, 0)),
         ),
         (
             "Bold + Underline + Magenta",
             Style::new()
                 .bold()
                 .underline()
-                .color(Color::rgb(255, 0, 255)),
+                .with_color(Color::rgb(255, 0, 255)),
         ),
         (
             "All attributes + Cyan",
@@ -119,7 +122,7 @@ fn demonstrate_style_combinations(console: &Console) -> Result<(), LuxorError> {
                 .italic()
                 .underline()
                 .strikethrough()
-                .color(Color::rgb(0, 255, 255)),
+                .with_color(Color::rgb(0, 255, 255)),
         ),
     ];
 
@@ -161,7 +164,7 @@ fn demonstrate_background_colors(console: &Console) -> Result<(), LuxorError> {
 
     for (description, fg_color, bg_color) in backgrounds {
         let mut text = Text::new(&format!("   {}", description));
-        text = text.with_style(Style::new().color(fg_color).background(bg_color));
+        text = text.with_style(Style::new().with_color(fg_color).with_background(bg_color));
         console.print(text)?;
     }
     println!();
@@ -172,7 +175,7 @@ fn demonstrate_style_inheritance(console: &Console) -> Result<(), LuxorError> {
     println!("5. Style composition and inheritance:");
 
     // Create a base style
-    let base_style = Style::new().bold().color(Color::rgb(100, 100, 255));
+    let base_style = Style::new().bold().with_color(Color::rgb(100, 100, 255));
 
     // Combine with other styles
     let mut text = Text::new("This text demonstrates style composition:");
@@ -232,7 +235,7 @@ fn demonstrate_color_palette(console: &Console) -> Result<(), LuxorError> {
     for (i, color) in palette.iter().enumerate() {
         let start = i;
         let end = i + 1;
-        colored_palette.stylize_range(start..end, Style::new().color(*color))?;
+        colored_palette.stylize_range(start..end, Style::new().with_color(*color))?;
     }
 
     console.print(Text::new("Color palette: "))?;
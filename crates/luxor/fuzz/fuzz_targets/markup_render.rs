@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use luxor::markup::{render, render_lossy};
+
+fuzz_target!(|data: &str| {
+    let input_chars = data.chars().count();
+
+    // Strict rendering may fail, but must not panic
+    if let Ok(text) = render(data, None) {
+        assert!(text.plain().chars().count() <= input_chars);
+        let _ = text.to_segments();
+    }
+
+    // Lossy rendering never fails and never grows the text beyond the input
+    let (text, warnings) = render_lossy(data, None);
+    assert!(text.plain().chars().count() <= input_chars);
+    assert!(warnings.len() <= input_chars);
+    let _ = text.to_segments();
+
+    // Input without tags comes back unchanged
+    if !data.contains('[') {
+        assert_eq!(text.plain(), data);
+        assert!(warnings.is_empty());
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use luxor::ansi::strip_ansi;
+
+fuzz_target!(|data: &str| {
+    let stripped = strip_ansi(data);
+
+    // Stripping only removes input and leaves no escapes behind
+    assert!(stripped.len() <= data.len());
+    assert!(!stripped.contains('\x1b'));
+    assert_eq!(strip_ansi(&stripped), stripped);
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use luxor::{ColorSystem, Style, ansi::style_to_ansi};
+
+fuzz_target!(|data: &str| {
+    if let Ok(style) = Style::parse(data) {
+        // Any parsed style must render for every color system
+        for color_system in [
+            ColorSystem::Standard,
+            ColorSystem::EightBit,
+            ColorSystem::TrueColor,
+        ] {
+            let _ = style_to_ansi(&style, color_system);
+        }
+    }
+});
@@ -1,6 +1,6 @@
 //! ANSI escape sequence generation for terminal control.
 
-use crate::{Color, ColorSystem, Style};
+use crate::{ColorSystem, Style, StyleDelta};
 
 /// ANSI escape sequence builder for styling text.
 pub struct AnsiBuilder {
@@ -18,106 +18,12 @@ impl AnsiBuilder {
     /// Add a style to the builder.
     ///
     /// This will generate the appropriate ANSI escape sequences for the given style
-    /// and color system capability.
+    /// and color system capability: those of the [`StyleDelta`] from the
+    /// empty style, so attributes set to `false` are turned off explicitly.
     pub fn add_style(&mut self, style: &Style, color_system: ColorSystem) {
-        // Foreground color
-        if let Some(color) = style.color {
-            self.add_color(color, false, color_system);
-        }
-
-        // Background color
-        if let Some(color) = style.background {
-            self.add_color(color, true, color_system);
-        }
-
-        // Text attributes
-        if let Some(true) = style.bold {
-            self.sequences.push("1".to_string());
-        }
-        if let Some(true) = style.dim {
-            self.sequences.push("2".to_string());
-        }
-        if let Some(true) = style.italic {
-            self.sequences.push("3".to_string());
-        }
-        if let Some(true) = style.underline {
-            self.sequences.push("4".to_string());
-        }
-        if let Some(true) = style.blink {
-            self.sequences.push("5".to_string());
-        }
-        if let Some(true) = style.reverse {
-            self.sequences.push("7".to_string());
-        }
-        if let Some(true) = style.hidden {
-            self.sequences.push("8".to_string());
-        }
-        if let Some(true) = style.strikethrough {
-            self.sequences.push("9".to_string());
-        }
-
-        // Turn off attributes explicitly set to false
-        if let Some(false) = style.bold {
-            self.sequences.push("22".to_string()); // Normal intensity
-        }
-        if let Some(false) = style.dim {
-            self.sequences.push("22".to_string()); // Normal intensity
-        }
-        if let Some(false) = style.italic {
-            self.sequences.push("23".to_string());
-        }
-        if let Some(false) = style.underline {
-            self.sequences.push("24".to_string());
-        }
-        if let Some(false) = style.blink {
-            self.sequences.push("25".to_string());
-        }
-        if let Some(false) = style.reverse {
-            self.sequences.push("27".to_string());
-        }
-        if let Some(false) = style.hidden {
-            self.sequences.push("28".to_string());
-        }
-        if let Some(false) = style.strikethrough {
-            self.sequences.push("29".to_string());
-        }
-    }
-
-    /// Add a color escape sequence.
-    fn add_color(&mut self, color: Color, is_background: bool, color_system: ColorSystem) {
-        let downgraded = color.downgrade(color_system);
-
-        match downgraded {
-            Color::Default => {
-                if is_background {
-                    self.sequences.push("49".to_string()); // Default background
-                } else {
-                    self.sequences.push("39".to_string()); // Default foreground
-                }
-            }
-            Color::Standard(std_color) => {
-                let code = if is_background {
-                    std_color.ansi_bg_code()
-                } else {
-                    std_color.ansi_fg_code()
-                };
-                self.sequences.push(code.to_string());
-            }
-            Color::EightBit(index) => {
-                if is_background {
-                    self.sequences.push(format!("48;5;{}", index));
-                } else {
-                    self.sequences.push(format!("38;5;{}", index));
-                }
-            }
-            Color::TrueColor { r, g, b } => {
-                if is_background {
-                    self.sequences.push(format!("48;2;{};{};{}", r, g, b));
-                } else {
-                    self.sequences.push(format!("38;2;{};{};{}", r, g, b));
-                }
-            }
-        }
+        let codes = StyleDelta::between(&Style::new(), style).apply_to_sgr(color_system);
+        self.sequences
+            .extend(codes.into_iter().map(|code| code.to_string()));
     }
 
     /// Build the final ANSI escape sequence.
@@ -153,7 +59,7 @@ impl Default for AnsiBuilder {
 /// ```rust
 /// use luxor::{ansi::style_to_ansi, Style, Color, ColorSystem};
 ///
-/// let style = Style::new().bold().color(Color::rgb(255, 0, 0));
+/// let style = Style::new().bold().with_color(Color::rgb(255, 0, 0));
 /// let ansi = style_to_ansi(&style, ColorSystem::TrueColor);
 /// println!("{}Bold red text\x1b[0m", ansi);
 /// ```
@@ -223,6 +129,72 @@ pub mod cursor {
     }
 }
 
+/// Generate OSC 8 hyperlink escape sequences.
+pub mod link {
+    use std::{borrow::Cow, fmt::Write};
+
+    /// End the current hyperlink.
+    pub const CLOSE: &str = "\x1b]8;;\x1b\\";
+
+    /// Start a hyperlink to `url`.
+    ///
+    /// Terminals treat runs of text with the same `id` as one link, for
+    /// example when a link is wrapped across lines. The URL is
+    /// percent-encoded with [`encode_url`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::ansi::link;
+    ///
+    /// assert_eq!(link::open("https://a.b/c d", None), "\x1b]8;;https://a.b/c%20d\x1b\\");
+    /// assert_eq!(link::open("https://a.b", Some("7")), "\x1b]8;id=7;https://a.b\x1b\\");
+    /// ```
+    pub fn open(url: &str, id: Option<&str>) -> String {
+        let url = encode_url(url);
+        match id {
+            Some(id) => format!("\x1b]8;id={};{}\x1b\\", id, url),
+            None => format!("\x1b]8;;{}\x1b\\", url),
+        }
+    }
+
+    /// Percent-encode the characters of a URL that would break an escape
+    /// sequence: spaces and control characters.
+    ///
+    /// Existing `%` escapes are left alone, so encoding an encoded URL
+    /// changes nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::ansi::link::encode_url;
+    ///
+    /// assert_eq!(encode_url("https://a.b/my file"), "https://a.b/my%20file");
+    /// assert_eq!(encode_url("https://a.b/my%20file"), "https://a.b/my%20file");
+    /// ```
+    pub fn encode_url(url: &str) -> Cow<'_, str> {
+        if !url.chars().any(needs_encoding) {
+            return Cow::Borrowed(url);
+        }
+        let mut encoded = String::with_capacity(url.len() + 8);
+        for ch in url.chars() {
+            if needs_encoding(ch) {
+                let mut buffer = [0; 4];
+                for byte in ch.encode_utf8(&mut buffer).bytes() {
+                    let _ = write!(encoded, "%{:02X}", byte);
+                }
+            } else {
+                encoded.push(ch);
+            }
+        }
+        Cow::Owned(encoded)
+    }
+
+    fn needs_encoding(ch: char) -> bool {
+        ch == ' ' || ch.is_control()
+    }
+}
+
 /// Strip ANSI escape sequences from a string.
 ///
 /// This function removes all ANSI escape sequences from the input string,
@@ -238,42 +210,199 @@ pub mod cursor {
 /// assert_eq!(plain, "Hello World");
 /// ```
 pub fn strip_ansi(text: &str) -> String {
-    let mut result = String::new();
-    let mut chars = text.chars().peekable();
-
-    while let Some(ch) = chars.next() {
-        if ch == '\x1b' {
-            // Start of escape sequence
-            if chars.peek() == Some(&'[') {
-                chars.next(); // Consume '['
-
-                // Skip until we find the end of the sequence
-                for ch in chars.by_ref() {
-                    if ch.is_ascii_alphabetic() {
-                        break; // End of sequence - found terminator
-                    }
-                    // If we encounter another escape or end of string without terminator,
-                    // this was an incomplete sequence
-                    if ch == '\x1b' {
-                        // Put back the escape character for next iteration
-                        result.push(ch);
-                        break;
-                    }
+    AnsiPieces::new(text)
+        .filter_map(|piece| match piece {
+            AnsiPiece::Char(ch) => Some(ch),
+            AnsiPiece::Escape(_) => None,
+        })
+        .collect()
+}
+
+/// A piece of a string that may contain ANSI escape sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AnsiPiece<'a> {
+    /// A visible character.
+    Char(char),
+    /// A complete, incomplete or bare escape sequence.
+    Escape(&'a str),
+}
+
+/// Iterator splitting a string into visible characters and escape sequences.
+///
+/// A CSI sequence (`ESC [`) runs to its alphabetic terminator, and an OSC
+/// sequence (`ESC ]`), such as a hyperlink, to BEL or `ESC \\`. One cut
+/// short by another escape or by the end of input is still a single escape
+/// piece, as is an escape not followed by `[` or `]`.
+struct AnsiPieces<'a> {
+    text: &'a str,
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+}
+
+impl<'a> AnsiPieces<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            chars: text.char_indices().peekable(),
+        }
+    }
+}
+
+impl<'a> Iterator for AnsiPieces<'a> {
+    type Item = AnsiPiece<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, ch) = self.chars.next()?;
+        if ch != '\x1b' {
+            return Some(AnsiPiece::Char(ch));
+        }
+
+        let mut end = start + 1;
+        if self.chars.next_if(|&(_, ch)| ch == '[').is_some() {
+            end += 1;
+            // Another escape means this sequence was incomplete; leave the
+            // escape in the stream so the next piece starts with it
+            while let Some((index, ch)) = self.chars.next_if(|&(_, ch)| ch != '\x1b') {
+                end = index + ch.len_utf8();
+                if ch.is_ascii_alphabetic() {
+                    break; // End of sequence - found terminator
+                }
+            }
+        } else if self.chars.next_if(|&(_, ch)| ch == ']').is_some() {
+            end += 1;
+            let mut terminated = false;
+            while let Some((index, ch)) = self.chars.next_if(|&(_, ch)| ch != '\x1b') {
+                end = index + ch.len_utf8();
+                if ch == '\x07' {
+                    terminated = true;
+                    break;
                 }
+            }
+            // String terminator `ESC \\`
+            if !terminated && self.text[end..].starts_with("\x1b\\") {
+                self.chars.next();
+                self.chars.next();
+                end += 2;
+            }
+        }
+        Some(AnsiPiece::Escape(&self.text[start..end]))
+    }
+}
 
-                // If we didn't find a proper terminator, this was likely an incomplete
-                // or malformed sequence. In that case, we've already consumed it.
-                // For incomplete sequences like "\x1b[", we just remove them entirely.
-            } else {
-                // Not an ANSI sequence, keep the character
-                result.push(ch);
+/// Track whether SGR attributes are active after an escape sequence.
+///
+/// Returns the new state: an SGR sequence whose last effective parameter is
+/// a reset (`0` or empty) closes styling, any other SGR opens it, and
+/// non-SGR sequences leave it unchanged.
+fn sgr_open_after(escape: &str, open: bool) -> bool {
+    let Some(params) = escape
+        .strip_prefix("\x1b[")
+        .and_then(|rest| rest.strip_suffix('m'))
+    else {
+        return open;
+    };
+
+    let params: Vec<&str> = params.split(';').collect();
+    let mut open = open;
+    let mut index = 0;
+    while index < params.len() {
+        match params[index] {
+            "" | "0" => open = false,
+            // Extended colors carry their arguments as further parameters
+            "38" | "48" | "58" => {
+                open = true;
+                index += match params.get(index + 1) {
+                    Some(&"5") => 2,
+                    Some(&"2") => 4,
+                    _ => 0,
+                };
+            }
+            _ => open = true,
+        }
+        index += 1;
+    }
+    open
+}
+
+/// Truncate a string that may contain ANSI escapes to a visible width.
+///
+/// Escape sequences are never cut and don't count toward the width. The
+/// string is cut after the last character that fits, leaving room for
+/// `ellipsis` when one is given and fits at all. If styling opened before
+/// the cut is still active, a reset is appended so it can't leak into
+/// whatever is printed next; the ellipsis takes the style of the text it
+/// replaces. Strings that already fit are returned as-is.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::ansi::truncate_visible;
+///
+/// let red = "\x1b[38;2;255;0;0mHello world\x1b[0m";
+/// assert_eq!(truncate_visible(red, 5, None), "\x1b[38;2;255;0;0mHello\x1b[0m");
+/// assert_eq!(truncate_visible(red, 5, Some("…")), "\x1b[38;2;255;0;0mHell…\x1b[0m");
+/// assert_eq!(truncate_visible("short", 10, Some("…")), "short");
+/// ```
+pub fn truncate_visible(input: &str, max_cells: usize, ellipsis: Option<&str>) -> String {
+    use unicode_width::UnicodeWidthChar;
+
+    if text_width(input) <= max_cells {
+        return input.to_string();
+    }
+
+    let ellipsis = ellipsis.filter(|ellipsis| text_width(ellipsis) <= max_cells);
+    let budget = max_cells - ellipsis.map_or(0, text_width);
+
+    let mut output = String::with_capacity(input.len());
+    let mut used = 0;
+    let mut sgr_open = false;
+    // Escapes are held back until a character follows them, so none are
+    // emitted for text that was cut off
+    let mut pending = Vec::new();
+    for piece in AnsiPieces::new(input) {
+        match piece {
+            AnsiPiece::Escape(escape) => pending.push(escape),
+            AnsiPiece::Char(ch) => {
+                let width = ch.width().unwrap_or(0);
+                if used + width > budget {
+                    break;
+                }
+                used += width;
+                for escape in pending.drain(..) {
+                    sgr_open = sgr_open_after(escape, sgr_open);
+                    output.push_str(escape);
+                }
+                output.push(ch);
             }
-        } else {
-            result.push(ch);
         }
     }
 
-    result
+    if let Some(ellipsis) = ellipsis {
+        output.push_str(ellipsis);
+    }
+    if sgr_open {
+        output.push_str(codes::RESET);
+    }
+    output
+}
+
+/// Pad a string that may contain ANSI escapes with spaces to a visible width.
+///
+/// Strings already at least `cells` wide are returned unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::ansi::pad_visible;
+///
+/// assert_eq!(pad_visible("\x1b[1mab\x1b[0m", 4), "\x1b[1mab\x1b[0m  ");
+/// assert_eq!(pad_visible("abcdef", 4), "abcdef");
+/// ```
+pub fn pad_visible(input: &str, cells: usize) -> String {
+    let padding = cells.saturating_sub(text_width(input));
+    let mut output = String::with_capacity(input.len() + padding);
+    output.push_str(input);
+    output.extend(std::iter::repeat(' ').take(padding));
+    output
 }
 
 /// Calculate the display width of text, ignoring ANSI escape sequences.
@@ -313,10 +442,21 @@ mod tests {
         assert_eq!(builder.build(), "\x1b[1m");
     }
 
+    #[test]
+    fn test_ansi_builder_decorations() {
+        let style = Style::parse("underline2 overline frame encircle").unwrap();
+        assert_eq!(
+            style_to_ansi(&style, ColorSystem::Standard),
+            "\x1b[21;51;52;53m"
+        );
+        let off = Style::parse("not underline2 not frame not overline").unwrap();
+        assert_eq!(style_to_ansi(&off, ColorSystem::Standard), "\x1b[55;24;54m");
+    }
+
     #[test]
     fn test_ansi_builder_color() {
         let mut builder = AnsiBuilder::new();
-        let style = Style::new().color(Color::Standard(StandardColor::Red));
+        let style = Style::new().with_color(Color::Standard(StandardColor::Red));
         builder.add_style(&style, ColorSystem::Standard);
         assert_eq!(builder.build(), "\x1b[31m");
     }
@@ -324,7 +464,7 @@ mod tests {
     #[test]
     fn test_ansi_builder_true_color() {
         let mut builder = AnsiBuilder::new();
-        let style = Style::new().color(Color::rgb(255, 128, 64));
+        let style = Style::new().with_color(Color::rgb(255, 128, 64));
         builder.add_style(&style, ColorSystem::TrueColor);
         assert_eq!(builder.build(), "\x1b[38;2;255;128;64m");
     }
@@ -335,8 +475,8 @@ mod tests {
         let style = Style::new()
             .bold()
             .italic()
-            .color(Color::Standard(StandardColor::Red))
-            .background(Color::Standard(StandardColor::Blue));
+            .with_color(Color::Standard(StandardColor::Red))
+            .with_background(Color::Standard(StandardColor::Blue));
         builder.add_style(&style, ColorSystem::Standard);
 
         let result = builder.build();
@@ -348,7 +488,7 @@ mod tests {
 
     #[test]
     fn test_style_to_ansi() {
-        let style = Style::new().bold().color(Color::rgb(255, 0, 0));
+        let style = Style::new().bold().with_color(Color::rgb(255, 0, 0));
         let ansi = style_to_ansi(&style, ColorSystem::TrueColor);
         assert!(ansi.contains("38;2;255;0;0")); // True color red
         assert!(ansi.contains("1")); // Bold
@@ -384,6 +524,75 @@ mod tests {
         assert_eq!(text_width(plain), 5);
     }
 
+    #[test]
+    fn test_strip_ansi_malformed_sequences() {
+        // Incomplete sequence interrupted by another escape
+        assert_eq!(strip_ansi("\x1b[1\x1b[31mRed"), "Red");
+        assert_eq!(strip_ansi("\x1b[\x1b[m"), "");
+        // Bare escapes are dropped
+        assert_eq!(strip_ansi("a\x1b\x1b[mb"), "ab");
+        assert_eq!(strip_ansi("\x1b\x1b[m["), "[");
+        // Unterminated sequence at the end of input
+        assert_eq!(strip_ansi("Hello\x1b[1;3"), "Hello");
+        // Hyperlinks end at ST or BEL
+        assert_eq!(
+            strip_ansi("\x1b]8;;https://a.b\x1b\\link\x1b]8;;\x07!"),
+            "link!"
+        );
+
+        for input in ["\x1b[\x1b[m", "\x1b\x1b[m[", "\x1b[1\x1b[31mRed"] {
+            let once = strip_ansi(input);
+            assert_eq!(strip_ansi(&once), once);
+        }
+    }
+
+    #[test]
+    fn test_ansi_pieces() {
+        let pieces: Vec<_> = AnsiPieces::new("a\x1b[1mb\x1b[3\x1bc\x1b[").collect();
+        assert_eq!(
+            pieces,
+            vec![
+                AnsiPiece::Char('a'),
+                AnsiPiece::Escape("\x1b[1m"),
+                AnsiPiece::Char('b'),
+                AnsiPiece::Escape("\x1b[3"),
+                AnsiPiece::Escape("\x1b"),
+                AnsiPiece::Char('c'),
+                AnsiPiece::Escape("\x1b["),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_truncate_visible_truecolor_straddling_cut() {
+        let input = "ab\x1b[38;2;10;200;30mcdef\x1b[0m";
+        assert_eq!(truncate_visible(input, 2, None), "ab");
+        assert_eq!(
+            truncate_visible(input, 3, None),
+            "ab\x1b[38;2;10;200;30mc\x1b[0m"
+        );
+        // Color arguments of 0 are not resets
+        let input = "\x1b[38;2;0;0;0mabc";
+        assert_eq!(truncate_visible(input, 1, None), "\x1b[38;2;0;0;0ma\x1b[0m");
+    }
+
+    #[test]
+    fn test_truncate_visible_already_reset() {
+        let input = "\x1b[1mab\x1b[0mcdef";
+        assert_eq!(truncate_visible(input, 3, None), "\x1b[1mab\x1b[0mc");
+        assert_eq!(truncate_visible(input, 3, Some("…")), "\x1b[1mab…\x1b[0m");
+        assert_eq!(truncate_visible("\x1b[31;0mabc", 2, None), "\x1b[31;0mab");
+    }
+
+    #[test]
+    fn test_truncate_visible_wide_and_ellipsis() {
+        assert_eq!(truncate_visible("a漢字", 2, None), "a");
+        assert_eq!(truncate_visible("漢字漢", 5, Some("...")), "漢...");
+        // An ellipsis that doesn't fit is dropped
+        assert_eq!(truncate_visible("abcdef", 2, Some("...")), "ab");
+        assert_eq!(truncate_visible("abc", 0, None), "");
+    }
+
     #[test]
     fn test_strip_ansi_no_escape() {
         let plain = "Hello World";
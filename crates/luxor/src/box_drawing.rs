@@ -0,0 +1,286 @@
+//! Box drawing - frames around lines of content.
+//!
+//! A [`BoxConfig`] holds the characters of a frame, and [`BorderStyle`]
+//! names the usual sets. [`draw_box`] puts a frame around lines already
+//! fitted to a width, with optional labels set into the top and bottom
+//! edges, as [`Panel`](crate::components::Panel) does with its title.
+
+use crate::{Segment, Style, layout::Align, render_utils, rendered::Line};
+
+/// A named set of frame characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BorderStyle {
+    /// Light lines with rounded corners: `╭─╮`.
+    #[default]
+    Rounded,
+    /// Light lines with square corners: `┌─┐`.
+    Square,
+    /// Heavy lines: `┏━┓`.
+    Heavy,
+    /// Double lines: `╔═╗`.
+    Double,
+    /// Plus signs, dashes and bars, for terminals without Unicode.
+    Ascii,
+}
+
+/// The characters of a frame.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::box_drawing::{BorderStyle, BoxConfig};
+///
+/// let config = BoxConfig::from(BorderStyle::Heavy);
+/// assert_eq!(config.top_left, '┏');
+/// assert!(!config.is_ascii());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoxConfig {
+    /// The top left corner.
+    pub top_left: char,
+    /// The top edge.
+    pub top: char,
+    /// The top right corner.
+    pub top_right: char,
+    /// The left edge.
+    pub left: char,
+    /// The right edge.
+    pub right: char,
+    /// The bottom left corner.
+    pub bottom_left: char,
+    /// The bottom edge.
+    pub bottom: char,
+    /// The bottom right corner.
+    pub bottom_right: char,
+}
+
+impl BoxConfig {
+    /// Create a frame from its corners, clockwise from the top left, and
+    /// its horizontal and vertical edges.
+    pub const fn new(corners: [char; 4], horizontal: char, vertical: char) -> Self {
+        Self {
+            top_left: corners[0],
+            top: horizontal,
+            top_right: corners[1],
+            left: vertical,
+            right: vertical,
+            bottom_left: corners[3],
+            bottom: horizontal,
+            bottom_right: corners[2],
+        }
+    }
+
+    /// Check if every character is ASCII.
+    pub fn is_ascii(&self) -> bool {
+        [
+            self.top_left,
+            self.top,
+            self.top_right,
+            self.left,
+            self.right,
+            self.bottom_left,
+            self.bottom,
+            self.bottom_right,
+        ]
+        .iter()
+        .all(char::is_ascii)
+    }
+}
+
+impl Default for BoxConfig {
+    fn default() -> Self {
+        BorderStyle::default().into()
+    }
+}
+
+impl From<BorderStyle> for BoxConfig {
+    fn from(style: BorderStyle) -> Self {
+        match style {
+            BorderStyle::Rounded => Self::new(['╭', '╮', '╯', '╰'], '─', '│'),
+            BorderStyle::Square => Self::new(['┌', '┐', '┘', '└'], '─', '│'),
+            BorderStyle::Heavy => Self::new(['┏', '┓', '┛', '┗'], '━', '┃'),
+            BorderStyle::Double => Self::new(['╔', '╗', '╝', '╚'], '═', '║'),
+            BorderStyle::Ascii => Self::new(['+', '+', '+', '+'], '-', '|'),
+        }
+    }
+}
+
+/// A label set into an edge of a frame, such as a title.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxLabel {
+    /// The label, on one line.
+    pub line: Line,
+    /// Where along the edge it goes.
+    pub align: Align,
+}
+
+/// Draw `config` around `lines`, each exactly `width` cells wide, with
+/// the frame in `style`.
+///
+/// Labels are set into the top and bottom edges with a space on either
+/// side, and cropped to fit. Left and right aligned labels keep one edge
+/// character between them and the corner when there is room. The frame is
+/// `width + 2` cells wide; nothing is drawn for a zero width.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Segment, Style, box_drawing::{BoxConfig, BoxLabel, draw_box}, layout::Align};
+///
+/// let lines = vec![vec![Segment::new("hello   ", Style::new())]];
+/// let title = BoxLabel { line: vec![Segment::new("Info", Style::new())], align: Align::Left };
+/// let segments = draw_box(&BoxConfig::default(), lines, 8, &Style::new(), Some(&title), None);
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, "╭─ Info ─╮\n│hello   │\n╰────────╯");
+/// ```
+pub fn draw_box(
+    config: &BoxConfig,
+    lines: Vec<Line>,
+    width: usize,
+    style: &Style,
+    title: Option<&BoxLabel>,
+    subtitle: Option<&BoxLabel>,
+) -> Vec<Segment> {
+    if width == 0 {
+        return Vec::new();
+    }
+    let mut framed = Vec::with_capacity(lines.len() + 2);
+    framed.push(edge(
+        config.top_left,
+        config.top,
+        config.top_right,
+        width,
+        style,
+        title,
+    ));
+    for line in lines {
+        let mut framed_line = vec![Segment::new(config.left.to_string(), style.clone())];
+        framed_line.extend(line);
+        framed_line.push(Segment::new(config.right.to_string(), style.clone()));
+        framed.push(framed_line);
+    }
+    framed.push(edge(
+        config.bottom_left,
+        config.bottom,
+        config.bottom_right,
+        width,
+        style,
+        subtitle,
+    ));
+    render_utils::frame_lines(framed)
+}
+
+/// Draw one horizontal edge `width` cells wide between its corners, with
+/// `label` set into it.
+fn edge(
+    left: char,
+    fill: char,
+    right: char,
+    width: usize,
+    style: &Style,
+    label: Option<&BoxLabel>,
+) -> Line {
+    let run = |cells: usize| Segment::new(fill.to_string().repeat(cells), style.clone());
+    let mut line = vec![Segment::new(left.to_string(), style.clone())];
+    // A label needs a cell for itself and one for a space on each side
+    match label.filter(|_| width >= 3) {
+        Some(label) => {
+            let shown = render_utils::line_width(&label.line).min(width - 2);
+            let text = render_utils::adjust_line_length(label.line.clone(), shown, Style::new());
+            let extra = width - shown - 2;
+            let (before, after) = match label.align {
+                Align::Left if extra > 0 => (1, extra - 1),
+                Align::Right if extra > 0 => (extra - 1, 1),
+                align => align.split(extra),
+            };
+            line.push(run(before));
+            line.push(Segment::new(" ", style.clone()));
+            line.extend(text);
+            line.push(Segment::new(" ", style.clone()));
+            line.push(run(after));
+        }
+        None => line.push(run(width)),
+    }
+    line.push(Segment::new(right.to_string(), style.clone()));
+    line.retain(|segment| !segment.text().is_empty());
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::render_lines;
+
+    fn label(text: &str, align: Align) -> BoxLabel {
+        BoxLabel {
+            line: vec![Segment::new(text, Style::new())],
+            align,
+        }
+    }
+
+    fn boxed(width: usize, title: Option<&BoxLabel>, subtitle: Option<&BoxLabel>) -> String {
+        let lines = vec![vec![Segment::new(" ".repeat(width), Style::new())]];
+        let config = BoxConfig::default();
+        render_lines(&draw_box(
+            &config,
+            lines,
+            width,
+            &Style::new(),
+            title,
+            subtitle,
+        ))
+        .join("\n")
+    }
+
+    #[test]
+    fn test_label_alignment() {
+        let top = |align| boxed(10, Some(&label("ab", align)), None);
+        assert!(top(Align::Left).starts_with("╭─ ab ─────╮"));
+        assert!(top(Align::Center).starts_with("╭─── ab ───╮"));
+        assert!(top(Align::Right).starts_with("╭───── ab ─╮"));
+        let bottom = boxed(6, None, Some(&label("end", Align::Center)));
+        assert!(bottom.ends_with("╰ end ─╯"), "{bottom}");
+    }
+
+    #[test]
+    fn test_labels_are_cropped() {
+        let title = label("a long title", Align::Left);
+        assert_eq!(boxed(6, Some(&title), None), "╭ a lo ╮\n│      │\n╰──────╯");
+        // Too narrow for a label at all
+        assert_eq!(boxed(2, Some(&title), None), "╭──╮\n│  │\n╰──╯");
+        assert!(
+            draw_box(
+                &BoxConfig::default(),
+                Vec::new(),
+                0,
+                &Style::new(),
+                None,
+                None
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_border_styles() {
+        for (style, corner) in [
+            (BorderStyle::Rounded, '╭'),
+            (BorderStyle::Square, '┌'),
+            (BorderStyle::Heavy, '┏'),
+            (BorderStyle::Double, '╔'),
+            (BorderStyle::Ascii, '+'),
+        ] {
+            let config = BoxConfig::from(style);
+            assert_eq!(config.top_left, corner);
+            assert_eq!(config.is_ascii(), style == BorderStyle::Ascii);
+        }
+        let border = Style::parse("blue").unwrap();
+        let segments = draw_box(&BoxConfig::default(), Vec::new(), 1, &border, None, None);
+        assert!(
+            segments
+                .iter()
+                .filter(|segment| !segment.text().contains('\n'))
+                .all(|segment| segment.style() == &border)
+        );
+    }
+}
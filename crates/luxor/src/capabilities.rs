@@ -0,0 +1,584 @@
+//! Capabilities - a summary of what the console detected about its output.
+//!
+//! When output looks wrong on some terminal, the first question is what
+//! luxor decided about it. [`Console::capabilities`] answers that in one
+//! value that can be inspected in code or printed for a bug report:
+//!
+//! ```rust,no_run
+//! use luxor::Console;
+//!
+//! let console = Console::new();
+//! console.println(console.capabilities()).unwrap();
+//! ```
+//!
+//! Detection reads the environment and queries the terminal once per
+//! process: [`Capabilities::probe_once`] caches the result, and every
+//! console's defaults come from it. Color is enabled when stdout is a
+//! terminal other than `TERM=dumb`, so output piped to a file is plain
+//! text. A non-empty `NO_COLOR` disables color, and otherwise
+//! `FORCE_COLOR` enables it and treats output as a terminal. `LUXOR_*`
+//! variables are applied last, see [`Capabilities::override_from_env`]. Tests and embedders can skip
+//! detection entirely with [`Console::with_capabilities`].
+
+use crate::{
+    ColorSystem, Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment,
+    Style, WidthSource, console::is_enabled_flag, options, render_utils,
+};
+use std::{
+    fmt,
+    io::IsTerminal,
+    sync::{OnceLock, RwLock},
+};
+
+/// Environment variables that affect how luxor renders.
+const ENVIRONMENT_VARIABLES: &[&str] = &[
+    "TERM",
+    "COLORTERM",
+    "COLUMNS",
+    "NO_COLOR",
+    "FORCE_COLOR",
+    "LUXOR_ACCESSIBLE",
+    "LUXOR_ASCII_ONLY",
+    "LUXOR_COLOR_SYSTEM",
+    "LUXOR_FORCE_TERMINAL",
+];
+
+/// The process-wide detection result shared by all consoles.
+static PROBED: OnceLock<RwLock<Capabilities>> = OnceLock::new();
+
+/// Number of detection passes run, for tests of the cache.
+#[cfg(test)]
+static PROBE_COUNT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// An environment variable as a `(name, value)` pair.
+pub type EnvironmentVariable = (String, String);
+
+/// A terminal size as `(width, height)`.
+type Size = (usize, usize);
+
+/// A `(key, value)` row of the rendered summary.
+type Row = (String, String);
+
+/// What a console detected about its output.
+///
+/// Fields may be added in minor releases as detection improves.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{ColorSystem, Console, ConsoleOptions};
+///
+/// let options = ConsoleOptions::new()
+///     .with_max_width(100)
+///     .with_color_system(ColorSystem::TrueColor);
+/// let capabilities = Console::with_options(options).capabilities();
+/// assert!(capabilities.truecolor);
+/// assert_eq!(capabilities.width, 100);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// The color system detected or configured, even if color is disabled.
+    pub color_system: ColorSystem,
+    /// Whether color output is enabled at all.
+    pub color_enabled: bool,
+    /// Whether 24-bit colors are rendered as-is.
+    pub truecolor: bool,
+    /// Whether decorations may use non-ASCII characters.
+    pub unicode: bool,
+    /// Whether output goes to a terminal, as opposed to a pipe, file or capture.
+    pub is_terminal: bool,
+    /// Render width in cells.
+    pub width: usize,
+    /// Where the render width came from.
+    pub width_source: WidthSource,
+    /// Terminal height in lines.
+    pub height: usize,
+    /// Whether accessible output is enabled.
+    pub accessible: bool,
+    /// The environment variables luxor reads that are set, as `(name, value)`.
+    pub environment: Vec<EnvironmentVariable>,
+}
+
+impl Capabilities {
+    /// Get the capabilities of the process's terminal, detecting them on
+    /// first use.
+    ///
+    /// Later calls, including those made by every new [`ConsoleOptions`](crate::ConsoleOptions),
+    /// return the cached result without reading the environment again. Use
+    /// [`Capabilities::refresh`] to detect again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Capabilities, ConsoleOptions};
+    ///
+    /// let capabilities = Capabilities::probe_once();
+    /// assert_eq!(ConsoleOptions::new().color_system, capabilities.color_system);
+    /// ```
+    pub fn probe_once() -> Self {
+        probed()
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Detect the capabilities again, replacing the cached result, e.g.
+    /// after changing environment variables.
+    ///
+    /// Consoles and options created earlier keep what they were created
+    /// with.
+    pub fn refresh() -> Self {
+        let capabilities = Self::probe();
+        match PROBED.get() {
+            Some(probed) => {
+                *probed
+                    .write()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()) = capabilities.clone();
+            }
+            None => {
+                // Losing a race with `probe_once` leaves an equally fresh result
+                let _ = PROBED.set(RwLock::new(capabilities.clone()));
+            }
+        }
+        capabilities
+    }
+
+    /// Apply the `LUXOR_*` environment variables over detected values.
+    ///
+    /// - `LUXOR_COLOR_SYSTEM`: `standard`, `256`, `truecolor`, or `none` to
+    ///   disable color.
+    /// - `LUXOR_ASCII_ONLY`: restrict decorations to ASCII.
+    /// - `LUXOR_ACCESSIBLE`: enable accessible output.
+    /// - `LUXOR_FORCE_TERMINAL`: treat output as a terminal.
+    ///
+    /// Flags are enabled by any value other than an empty string or `0`.
+    /// Detection already applies these, so this is only needed for values
+    /// built by hand.
+    pub fn override_from_env(self) -> Self {
+        self.override_with(|name| std::env::var(name).ok())
+    }
+
+    fn override_with(mut self, env: impl Fn(&str) -> Option<String>) -> Self {
+        if let Some(value) = env("LUXOR_COLOR_SYSTEM") {
+            match value.trim().to_lowercase().as_str() {
+                "none" => self.color_enabled = false,
+                "standard" | "16" => self.color_system = ColorSystem::Standard,
+                "256" | "eight_bit" => self.color_system = ColorSystem::EightBit,
+                "truecolor" | "24bit" => self.color_system = ColorSystem::TrueColor,
+                _ => {}
+            }
+            self.truecolor = self.color_enabled && self.color_system == ColorSystem::TrueColor;
+        }
+        if let Some(value) = env("LUXOR_ASCII_ONLY") {
+            self.unicode = !is_enabled_flag(&value);
+        }
+        if let Some(value) = env("LUXOR_ACCESSIBLE") {
+            self.accessible = is_enabled_flag(&value);
+        }
+        if let Some(value) = env("LUXOR_FORCE_TERMINAL") {
+            self.is_terminal = is_enabled_flag(&value);
+        }
+        self
+    }
+
+    /// Get the value of `COLUMNS` seen by detection.
+    fn columns(&self) -> Option<&str> {
+        self.environment
+            .iter()
+            .find(|(name, _)| name == "COLUMNS")
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Detect the capabilities of the process's terminal.
+    fn probe() -> Self {
+        #[cfg(test)]
+        PROBE_COUNT.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+        // Without terminal support, output is treated as going to a pipe
+        let is_terminal = cfg!(feature = "terminal") && std::io::stdout().is_terminal();
+        Self::probe_with(is_terminal, terminal_size(), |name| {
+            std::env::var(name).ok()
+        })
+    }
+
+    /// Detect capabilities from explicit terminal and environment state.
+    fn probe_with(
+        is_terminal: bool,
+        size: Option<Size>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Self {
+        let color_system = ColorSystem::from_env(&env);
+        let force_color = env("FORCE_COLOR").is_some_and(|value| is_enabled_flag(&value));
+        let no_color = env("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let dumb = env("TERM").is_some_and(|term| term.trim() == "dumb");
+        let is_terminal = force_color
+            || env("LUXOR_FORCE_TERMINAL").map_or(is_terminal, |value| is_enabled_flag(&value));
+        let color_enabled = !no_color && (force_color || (is_terminal && !dumb));
+        let (width, width_source) = options::resolve_width(
+            None,
+            env("COLUMNS").as_deref(),
+            || size.map(|(width, _)| width),
+            options::DEFAULT_FALLBACK_WIDTH,
+        );
+        Self {
+            color_system,
+            color_enabled,
+            truecolor: color_enabled && color_system == ColorSystem::TrueColor,
+            unicode: true,
+            is_terminal,
+            width,
+            width_source,
+            height: size.map_or(24, |(_, height)| height),
+            accessible: false,
+            environment: ENVIRONMENT_VARIABLES
+                .iter()
+                .filter_map(|&name| env(name).map(|value| (name.to_string(), value)))
+                .collect(),
+        }
+        .override_with(env)
+    }
+
+    /// Summarize a console from the cached detection and its options.
+    pub(crate) fn detect(console: &Console) -> Self {
+        let probed = Self::probe_once();
+        Self::from_parts(console, console.is_terminal(), |name| {
+            probed
+                .environment
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+        })
+    }
+
+    /// Summarize a console with explicit terminal and environment state.
+    fn from_parts(
+        console: &Console,
+        is_terminal: bool,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Self {
+        let options = console.options();
+        let color_system = options.color_system;
+        Self {
+            color_system,
+            color_enabled: options.enable_color,
+            truecolor: options.enable_color && color_system == ColorSystem::TrueColor,
+            unicode: !options.render.ascii_only,
+            is_terminal,
+            width: console.width(),
+            width_source: console.width_source(),
+            height: console.height(),
+            accessible: options.render.accessible,
+            environment: ENVIRONMENT_VARIABLES
+                .iter()
+                .filter_map(|&name| env(name).map(|value| (name.to_string(), value)))
+                .collect(),
+        }
+    }
+
+    /// Get the summary as `(key, value)` rows.
+    fn rows(&self) -> Vec<Row> {
+        let color_system = match self.color_system {
+            ColorSystem::Standard => "16 colors",
+            ColorSystem::EightBit => "256 colors",
+            ColorSystem::TrueColor => "truecolor",
+        };
+        let color = if self.color_enabled {
+            color_system.to_string()
+        } else {
+            format!("disabled ({})", color_system)
+        };
+        let width_source = match self.width_source {
+            WidthSource::Explicit => "set explicitly",
+            WidthSource::Environment => "from COLUMNS",
+            WidthSource::Terminal => "from terminal",
+            WidthSource::Fallback => "fallback",
+        };
+        let yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+
+        let mut rows = vec![
+            ("color".to_string(), color),
+            ("unicode".to_string(), yes_no(self.unicode)),
+            ("terminal".to_string(), yes_no(self.is_terminal)),
+            (
+                "size".to_string(),
+                format!("{}x{} ({})", self.width, self.height, width_source),
+            ),
+            ("accessible".to_string(), yes_no(self.accessible)),
+        ];
+        rows.extend(
+            self.environment
+                .iter()
+                .map(|(name, value)| (format!("${}", name), value.clone())),
+        );
+        rows
+    }
+}
+
+/// Get the cached detection result, detecting on first use.
+fn probed() -> &'static RwLock<Capabilities> {
+    PROBED.get_or_init(|| RwLock::new(Capabilities::probe()))
+}
+
+/// Get the value of `COLUMNS` seen by [`Capabilities::probe_once`], without
+/// reading the environment again.
+pub(crate) fn probed_columns() -> Option<String> {
+    probed()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .columns()
+        .map(str::to_string)
+}
+
+/// Get the width of the key column, which fits the longest key.
+fn key_width(rows: &[Row]) -> usize {
+    rows.iter().map(|(key, _)| key.len()).max().unwrap_or(0)
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.rows();
+        let width = key_width(&rows);
+        write!(f, "Terminal capabilities")?;
+        for (key, value) in rows {
+            write!(f, "\n  {:<width$}  {}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Renderable for Capabilities {
+    fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let key_style = Style::new().bold();
+        let mut lines = vec![vec![Segment::new(
+            "Terminal capabilities",
+            Style::new().bold().underline(),
+        )]];
+        let rows = self.rows();
+        let width = key_width(&rows);
+        for (key, value) in rows {
+            lines.push(vec![
+                Segment::new(format!("  {:<width$}  ", key), key_style.clone()),
+                Segment::new(value, Style::default()),
+            ]);
+        }
+        let segments = render_utils::frame_lines(lines);
+        Ok(render_utils::fold(segments, options.get_max_width()))
+    }
+}
+
+impl Measurable for Capabilities {
+    fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+        let width = self
+            .to_string()
+            .lines()
+            .map(render_utils::visible_width)
+            .max()
+            .unwrap_or(0);
+        Ok(Measurement::new(1, width))
+    }
+}
+
+/// Query the size of the terminal as (width, height) in cells.
+#[cfg(feature = "terminal")]
+pub(crate) fn terminal_size() -> Option<Size> {
+    let (width, height) = crossterm::terminal::size().ok()?;
+    Some((width as usize, height as usize))
+}
+
+/// Query the size of the terminal as (width, height) in cells.
+///
+/// Always `None` without the `terminal` feature.
+#[cfg(not(feature = "terminal"))]
+pub(crate) fn terminal_size() -> Option<Size> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConsoleOptions;
+    use crate::testing::render_lines;
+    use std::sync::atomic::Ordering;
+
+    fn console() -> Console {
+        Console::with_options(
+            ConsoleOptions::new()
+                .with_max_width(72)
+                .with_color_system(ColorSystem::EightBit)
+                .with_accessible(false),
+        )
+    }
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "TERM" => Some("xterm-256color".to_string()),
+            "LUXOR_ACCESSIBLE" => Some("0".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_capabilities_reports_options() {
+        let console = Console::with_options(
+            ConsoleOptions::new()
+                .with_max_width(40)
+                .with_color_system(ColorSystem::TrueColor)
+                .with_color(false)
+                .with_ascii_only(true)
+                .with_accessible(true),
+        );
+        let capabilities = Capabilities::from_parts(&console, false, |_| None);
+        assert_eq!(capabilities.color_system, ColorSystem::TrueColor);
+        assert!(!capabilities.color_enabled);
+        assert!(!capabilities.truecolor);
+        assert!(!capabilities.unicode);
+        assert!(capabilities.accessible);
+        assert_eq!(capabilities.width, 40);
+        assert_eq!(capabilities.width_source, WidthSource::Explicit);
+        assert!(capabilities.environment.is_empty());
+    }
+
+    #[test]
+    fn test_capabilities_capture_is_not_terminal() {
+        let console = console();
+        console.begin_capture();
+        assert!(!console.capabilities().is_terminal);
+        console.end_capture();
+    }
+
+    #[test]
+    fn test_probe_once_runs_one_detection() {
+        let first = Capabilities::probe_once();
+        let count = PROBE_COUNT.load(Ordering::SeqCst);
+        let _ = Console::new();
+        let _ = ConsoleOptions::new();
+        let _ = ColorSystem::detect();
+        assert_eq!(Capabilities::probe_once(), first);
+        assert_eq!(PROBE_COUNT.load(Ordering::SeqCst), count);
+
+        let _ = Capabilities::refresh();
+        assert_eq!(PROBE_COUNT.load(Ordering::SeqCst), count + 1);
+    }
+
+    #[test]
+    fn test_probe_applies_luxor_variables_last() {
+        let env = |name: &str| match name {
+            "COLORTERM" => Some("truecolor".to_string()),
+            "COLUMNS" => Some("132".to_string()),
+            "LUXOR_COLOR_SYSTEM" => Some("standard".to_string()),
+            "LUXOR_ASCII_ONLY" => Some("1".to_string()),
+            "LUXOR_FORCE_TERMINAL" => Some("yes".to_string()),
+            _ => None,
+        };
+        let capabilities = Capabilities::probe_with(false, Some((100, 40)), env);
+        assert_eq!(capabilities.color_system, ColorSystem::Standard);
+        assert!(!capabilities.truecolor);
+        assert!(!capabilities.unicode);
+        assert!(capabilities.is_terminal);
+        assert_eq!(capabilities.width, 132);
+        assert_eq!(capabilities.width_source, WidthSource::Environment);
+        assert_eq!(capabilities.columns(), Some("132"));
+        assert_eq!(capabilities.height, 40);
+
+        let disabled = capabilities
+            .override_with(|name| (name == "LUXOR_COLOR_SYSTEM").then(|| "none".to_string()));
+        assert!(!disabled.color_enabled);
+    }
+
+    #[test]
+    fn test_probe_color_matrix() {
+        // (terminal, TERM, NO_COLOR, FORCE_COLOR, color enabled, terminal)
+        let cases = [
+            (true, "xterm", None, None, true, true),
+            (false, "xterm", None, None, false, false),
+            (true, "dumb", None, None, false, true),
+            (true, "xterm", Some("1"), None, false, true),
+            (true, "xterm", Some(""), None, true, true),
+            (false, "xterm", None, Some("1"), true, true),
+            (false, "dumb", None, Some("1"), true, true),
+            (false, "xterm", None, Some("0"), false, false),
+            (true, "xterm", Some("1"), Some("1"), false, true),
+        ];
+        for (terminal, term, no_color, force_color, color_enabled, is_terminal) in cases {
+            let env = |name: &str| match name {
+                "TERM" => Some(term.to_string()),
+                "NO_COLOR" => no_color.map(str::to_string),
+                "FORCE_COLOR" => force_color.map(str::to_string),
+                _ => None,
+            };
+            let capabilities = Capabilities::probe_with(terminal, None, env);
+            let case = (terminal, term, no_color, force_color);
+            assert_eq!(capabilities.color_enabled, color_enabled, "{case:?}");
+            assert_eq!(capabilities.is_terminal, is_terminal, "{case:?}");
+        }
+    }
+
+    #[test]
+    fn test_console_is_terminal() {
+        let terminal = Capabilities::probe_with(true, Some((80, 24)), |_| None);
+        let console = Console::with_capabilities(terminal);
+        assert!(console.is_terminal());
+        assert!(console.options().enable_color);
+        console.begin_capture();
+        assert!(!console.is_terminal());
+        console.end_capture();
+        console.set_writer(Box::new(Vec::new()));
+        assert!(!console.is_terminal());
+
+        let forced = Console::with_options(ConsoleOptions::new().with_force_terminal(false));
+        assert!(!forced.is_terminal());
+        assert!(!forced.options().enable_color);
+    }
+
+    #[test]
+    fn test_injected_capabilities_take_precedence() {
+        let mut injected = Capabilities::probe_with(true, Some((60, 20)), |_| None);
+        injected.color_system = ColorSystem::Standard;
+        injected.unicode = false;
+        injected.accessible = true;
+
+        let console = Console::with_capabilities(injected.clone());
+        assert_eq!(console.options().color_system, ColorSystem::Standard);
+        assert!(console.render_options().ascii_only);
+        assert!(console.render_options().accessible);
+        assert_eq!(console.size(), (60, 20));
+        assert_eq!(console.capabilities(), injected);
+    }
+
+    #[test]
+    fn test_capabilities_display() {
+        let mut capabilities = Capabilities::from_parts(&console(), true, env);
+        capabilities.height = 24;
+        assert_eq!(
+            capabilities.to_string(),
+            "Terminal capabilities\n  \
+             color              256 colors\n  \
+             unicode            yes\n  \
+             terminal           yes\n  \
+             size               72x24 (set explicitly)\n  \
+             accessible         no\n  \
+             $TERM              xterm-256color\n  \
+             $LUXOR_ACCESSIBLE  0"
+        );
+    }
+
+    #[test]
+    fn test_capabilities_render() {
+        let mut capabilities = Capabilities::from_parts(&console(), false, |_| None);
+        capabilities.height = 24;
+        let options = ConsoleOptions::new().with_max_width(72);
+        let segments = capabilities.render(&console(), &options.render).unwrap();
+        crate::assert_render_eq!(
+            render_lines(&segments),
+            "Terminal capabilities\n  \
+             color       256 colors\n  \
+             unicode     yes\n  \
+             terminal    no\n  \
+             size        72x24 (set explicitly)\n  \
+             accessible  no"
+        );
+        assert_eq!(segments[0].style(), &Style::new().bold().underline());
+        assert_eq!(segments[2].style(), &Style::new().bold());
+    }
+}
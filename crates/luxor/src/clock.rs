@@ -0,0 +1,294 @@
+//! Clock - time sources, timers and redraw deadlines for animations.
+//!
+//! Animated components read the time from a [`Clock`] instead of
+//! [`Instant::now`], so tests can drive them with a [`ManualClock`] and
+//! assert exact frames. Each animation reports when it next changes as an
+//! [`Animation::next_frame_deadline`]; [`next_deadline`] combines several,
+//! so an event loop can sleep until the earliest one instead of polling:
+//!
+//! ```rust,no_run
+//! use luxor::{Animation, Console, Spinner, clock};
+//! use std::time::Instant;
+//!
+//! let console = Console::new();
+//! let spinner = Spinner::new().with_text("working");
+//! loop {
+//!     console.print(spinner.clone()).unwrap();
+//!     let deadline = clock::next_deadline([&spinner as &dyn Animation]);
+//!     if let Some(wait) = deadline.map(|deadline| deadline - Instant::now()) {
+//!         std::thread::sleep(wait);
+//!     }
+//! }
+//! ```
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A source of the current time.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Get the current time.
+    fn now(&self) -> Instant;
+}
+
+/// A clock shared between the components that read it.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The real time, from [`Instant::now`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl SystemClock {
+    /// Get the system clock as a [`SharedClock`].
+    pub fn shared() -> SharedClock {
+        Arc::new(SystemClock)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to, for tests.
+///
+/// Clones share the same time, so a test can keep one handle and give
+/// another to the component under test.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Clock, ManualClock};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// let start = clock.now();
+/// clock.advance(Duration::from_millis(250));
+/// assert_eq!(clock.now() - start, Duration::from_millis(250));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// Create a clock stopped at the current time.
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Get a handle to this clock as a [`SharedClock`].
+    pub fn shared(&self) -> SharedClock {
+        Arc::new(self.clone())
+    }
+
+    /// Move the clock forward.
+    pub fn advance(&self, duration: Duration) {
+        *self.lock() += duration;
+    }
+
+    /// Set the clock to `now`.
+    pub fn set(&self, now: Instant) {
+        *self.lock() = now;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Instant> {
+        self.now
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.lock()
+    }
+}
+
+/// Something that changes over time and knows when it next changes.
+pub trait Animation {
+    /// Get the time of the next visible change, or `None` if the
+    /// animation is finished or never changes on its own.
+    fn next_frame_deadline(&self) -> Option<Instant>;
+}
+
+/// Get the earliest deadline of `animations`.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Animation, ManualClock, Timer, clock::next_deadline};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// let fast = Timer::new(clock.shared()).with_interval(Duration::from_millis(80));
+/// let slow = Timer::new(clock.shared()).with_interval(Duration::from_secs(1));
+/// let deadline = next_deadline([&fast as &dyn Animation, &slow]);
+/// assert_eq!(deadline, fast.deadline());
+/// ```
+pub fn next_deadline<'a>(
+    animations: impl IntoIterator<Item = &'a dyn Animation>,
+) -> Option<Instant> {
+    animations
+        .into_iter()
+        .filter_map(|animation| animation.next_frame_deadline())
+        .min()
+}
+
+/// Elapsed time since a start, read from a [`Clock`].
+///
+/// With an interval, the timer ticks at every whole multiple of it since
+/// the start, and [`Timer::deadline`] is the next tick.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    clock: SharedClock,
+    started: Instant,
+    interval: Option<Duration>,
+}
+
+impl Timer {
+    /// Start a timer now, without an interval.
+    pub fn new(clock: SharedClock) -> Self {
+        let started = clock.now();
+        Self {
+            clock,
+            started,
+            interval: None,
+        }
+    }
+
+    /// Set the tick interval. A zero interval is ignored.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval).filter(|interval| !interval.is_zero());
+        self
+    }
+
+    /// Restart the timer from the current time.
+    pub fn restart(&mut self) {
+        self.started = self.clock.now();
+    }
+
+    /// Get the time the timer was started.
+    pub fn started(&self) -> Instant {
+        self.started
+    }
+
+    /// Get the tick interval.
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// Get the time since the timer was started.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.now().saturating_duration_since(self.started)
+    }
+
+    /// Get the number of whole intervals since the start, or 0 without an
+    /// interval.
+    pub fn ticks(&self) -> u128 {
+        match self.interval {
+            Some(interval) => self.elapsed().as_nanos() / interval.as_nanos(),
+            None => 0,
+        }
+    }
+
+    /// Get the time of the next tick, or `None` without an interval.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{ManualClock, Timer};
+    /// use std::time::Duration;
+    ///
+    /// let clock = ManualClock::new();
+    /// let timer = Timer::new(clock.shared()).with_interval(Duration::from_millis(100));
+    /// clock.advance(Duration::from_millis(250));
+    /// assert_eq!(timer.deadline(), Some(timer.started() + Duration::from_millis(300)));
+    /// ```
+    pub fn deadline(&self) -> Option<Instant> {
+        let interval = self.interval?;
+        let next = self.ticks() + 1;
+        let nanos = interval.as_nanos().saturating_mul(next);
+        let offset = Duration::new(
+            u64::try_from(nanos / 1_000_000_000).unwrap_or(u64::MAX),
+            (nanos % 1_000_000_000) as u32,
+        );
+        self.started.checked_add(offset)
+    }
+
+    /// Get the clock the timer reads.
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+}
+
+impl Animation for Timer {
+    fn next_frame_deadline(&self) -> Option<Instant> {
+        self.deadline()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer_ticks_and_deadline() {
+        let clock = ManualClock::new();
+        let timer = Timer::new(clock.shared()).with_interval(Duration::from_millis(100));
+        let start = timer.started();
+
+        assert_eq!(timer.ticks(), 0);
+        assert_eq!(timer.deadline(), Some(start + Duration::from_millis(100)));
+
+        // Exactly on a tick, the deadline is the following one
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(timer.ticks(), 1);
+        assert_eq!(timer.deadline(), Some(start + Duration::from_millis(200)));
+
+        clock.advance(Duration::from_millis(1_050));
+        assert_eq!(timer.elapsed(), Duration::from_millis(1_150));
+        assert_eq!(timer.deadline(), Some(start + Duration::from_millis(1_200)));
+
+        let plain = Timer::new(clock.shared()).with_interval(Duration::ZERO);
+        assert_eq!(plain.interval(), None);
+        assert_eq!(plain.deadline(), None);
+    }
+
+    #[test]
+    fn test_next_deadline_follows_earliest() {
+        let clock = ManualClock::new();
+        let fast = Timer::new(clock.shared()).with_interval(Duration::from_millis(80));
+        let slow = Timer::new(clock.shared()).with_interval(Duration::from_millis(200));
+        let idle = Timer::new(clock.shared());
+        let start = fast.started();
+        let animations = [&fast as &dyn Animation, &slow, &idle];
+
+        assert_eq!(
+            next_deadline(animations),
+            Some(start + Duration::from_millis(80))
+        );
+        clock.advance(Duration::from_millis(170));
+        assert_eq!(
+            next_deadline(animations),
+            Some(start + Duration::from_millis(200))
+        );
+        clock.advance(Duration::from_millis(40));
+        assert_eq!(
+            next_deadline(animations),
+            Some(start + Duration::from_millis(240))
+        );
+        assert_eq!(next_deadline([&idle as &dyn Animation]), None);
+    }
+}
@@ -1,6 +1,7 @@
 //! Color representation and color system support.
 
 use crate::{LuxorError, Result};
+use std::fmt;
 
 /// RGB color tuple type for convenience.
 pub type Rgb = (u8, u8, u8);
@@ -27,6 +28,65 @@ pub enum StandardColor {
 }
 
 impl StandardColor {
+    /// All 16 standard colors in ANSI index order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::StandardColor;
+    ///
+    /// assert_eq!(StandardColor::ALL.len(), 16);
+    /// assert_eq!(StandardColor::ALL[1], StandardColor::Red);
+    /// ```
+    pub const ALL: [StandardColor; 16] = [
+        StandardColor::Black,
+        StandardColor::Red,
+        StandardColor::Green,
+        StandardColor::Yellow,
+        StandardColor::Blue,
+        StandardColor::Magenta,
+        StandardColor::Cyan,
+        StandardColor::White,
+        StandardColor::BrightBlack,
+        StandardColor::BrightRed,
+        StandardColor::BrightGreen,
+        StandardColor::BrightYellow,
+        StandardColor::BrightBlue,
+        StandardColor::BrightMagenta,
+        StandardColor::BrightCyan,
+        StandardColor::BrightWhite,
+    ];
+
+    /// Get the name of this color as accepted by [`Style::parse`](crate::Style::parse).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::StandardColor;
+    ///
+    /// assert_eq!(StandardColor::BrightRed.name(), "bright_red");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            StandardColor::Black => "black",
+            StandardColor::Red => "red",
+            StandardColor::Green => "green",
+            StandardColor::Yellow => "yellow",
+            StandardColor::Blue => "blue",
+            StandardColor::Magenta => "magenta",
+            StandardColor::Cyan => "cyan",
+            StandardColor::White => "white",
+            StandardColor::BrightBlack => "bright_black",
+            StandardColor::BrightRed => "bright_red",
+            StandardColor::BrightGreen => "bright_green",
+            StandardColor::BrightYellow => "bright_yellow",
+            StandardColor::BrightBlue => "bright_blue",
+            StandardColor::BrightMagenta => "bright_magenta",
+            StandardColor::BrightCyan => "bright_cyan",
+            StandardColor::BrightWhite => "bright_white",
+        }
+    }
+
     /// Get the ANSI color code for this standard color as foreground.
     pub fn ansi_fg_code(self) -> u8 {
         match self {
@@ -120,7 +180,7 @@ impl Color {
     /// let green = Color::rgb(0, 255, 0);
     /// let blue = Color::rgb(0, 0, 255);
     /// ```
-    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
         Self::TrueColor { r, g, b }
     }
 
@@ -139,6 +199,10 @@ impl Color {
     /// ```
     pub fn from_hex(hex: &str) -> Result<Self> {
         let hex = hex.trim_start_matches('#');
+        // Checked up front so the byte slicing below stays on char boundaries
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(LuxorError::color("Invalid hex digit"));
+        }
 
         let (r, g, b) = match hex.len() {
             3 => {
@@ -187,6 +251,69 @@ impl Color {
         matches!(self, Color::Default)
     }
 
+    /// Get the canonical representation of this color.
+    ///
+    /// The first 16 entries of the 8-bit palette are the standard colors, so
+    /// they are represented as [`Color::Standard`]. All other colors are
+    /// already canonical.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Color, StandardColor};
+    ///
+    /// assert_eq!(Color::EightBit(1).canonicalize(), Color::Standard(StandardColor::Red));
+    /// assert_eq!(Color::EightBit(200).canonicalize(), Color::EightBit(200));
+    /// ```
+    pub fn canonicalize(self) -> Self {
+        match self {
+            Color::EightBit(index) if index < 16 => Color::Standard(standard_from_index(index)),
+            other => other,
+        }
+    }
+
+    /// Get the relative luminance of this color, from 0.0 (black) to 1.0 (white).
+    ///
+    /// Uses the WCAG 2.x definition on the color's RGB approximation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Color;
+    ///
+    /// assert_eq!(Color::rgb(0, 0, 0).luminance(), 0.0);
+    /// assert!((Color::rgb(255, 255, 255).luminance() - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn luminance(self) -> f64 {
+        fn linear(channel: u8) -> f64 {
+            let c = f64::from(channel) / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+
+        let (r, g, b) = self.to_rgb();
+        0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+    }
+
+    /// Get the WCAG contrast ratio between this color and another, from 1.0
+    /// (identical) to 21.0 (black on white).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Color;
+    ///
+    /// let ratio = Color::rgb(0, 0, 0).contrast_ratio(Color::rgb(255, 255, 255));
+    /// assert!((ratio - 21.0).abs() < 1e-9);
+    /// ```
+    pub fn contrast_ratio(self, other: Color) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
     /// Convert RGB color to the closest 8-bit color index.
     pub fn rgb_to_eight_bit(r: u8, g: u8, b: u8) -> u8 {
         // Standard colors (0-15)
@@ -247,6 +374,20 @@ impl Color {
     }
 }
 
+/// Formats the color in the canonical syntax accepted by
+/// [`Style::parse`](crate::Style::parse): `default`, a standard color name,
+/// `color(N)` or `#rrggbb`.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.canonicalize() {
+            Color::Default => write!(f, "default"),
+            Color::Standard(color) => write!(f, "{}", color.name()),
+            Color::EightBit(index) => write!(f, "color({})", index),
+            Color::TrueColor { r, g, b } => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+        }
+    }
+}
+
 /// Terminal color system capabilities.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColorSystem {
@@ -260,15 +401,25 @@ pub enum ColorSystem {
 
 impl ColorSystem {
     /// Detect the color system capability of the current terminal.
+    ///
+    /// This is the color system of [`Capabilities::probe_once`], so the
+    /// environment is only read once per process.
+    ///
+    /// [`Capabilities::probe_once`]: crate::Capabilities::probe_once
     pub fn detect() -> Self {
+        crate::Capabilities::probe_once().color_system
+    }
+
+    /// Detect the color system from `COLORTERM` and `TERM`.
+    pub(crate) fn from_env(env: impl Fn(&str) -> Option<String>) -> Self {
         // Check environment variables for color support
-        if let Ok(colorterm) = std::env::var("COLORTERM") {
+        if let Some(colorterm) = env("COLORTERM") {
             if colorterm == "truecolor" || colorterm == "24bit" {
                 return ColorSystem::TrueColor;
             }
         }
 
-        if let Ok(term) = std::env::var("TERM") {
+        if let Some(term) = env("TERM") {
             if term.contains("256color") || term.contains("256") {
                 return ColorSystem::EightBit;
             }
@@ -312,25 +463,10 @@ fn eight_bit_to_rgb(index: u8) -> Rgb {
 
 /// Convert an index (0-15) to a standard color.
 fn standard_from_index(index: u8) -> StandardColor {
-    match index {
-        0 => StandardColor::Black,
-        1 => StandardColor::Red,
-        2 => StandardColor::Green,
-        3 => StandardColor::Yellow,
-        4 => StandardColor::Blue,
-        5 => StandardColor::Magenta,
-        6 => StandardColor::Cyan,
-        7 => StandardColor::White,
-        8 => StandardColor::BrightBlack,
-        9 => StandardColor::BrightRed,
-        10 => StandardColor::BrightGreen,
-        11 => StandardColor::BrightYellow,
-        12 => StandardColor::BrightBlue,
-        13 => StandardColor::BrightMagenta,
-        14 => StandardColor::BrightCyan,
-        15 => StandardColor::BrightWhite,
-        _ => StandardColor::White, // Fallback
-    }
+    StandardColor::ALL
+        .get(index as usize)
+        .copied()
+        .unwrap_or(StandardColor::White) // Fallback
 }
 
 /// Find the closest standard color to the given RGB values.
@@ -398,10 +534,39 @@ mod tests {
         assert_eq!(Color::from_hex("#00F").unwrap(), Color::rgb(0, 0, 255));
     }
 
+    #[test]
+    fn test_color_luminance_and_contrast() {
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+
+        assert_eq!(black.luminance(), 0.0);
+        assert!((white.luminance() - 1.0).abs() < 1e-9);
+        assert!(Color::rgb(0, 255, 0).luminance() > Color::rgb(255, 0, 0).luminance());
+
+        assert!((black.contrast_ratio(white) - 21.0).abs() < 1e-9);
+        assert_eq!(black.contrast_ratio(white), white.contrast_ratio(black));
+        assert!((white.contrast_ratio(white) - 1.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_color_from_hex_invalid() {
         assert!(Color::from_hex("#GG0000").is_err());
         assert!(Color::from_hex("#FF00").is_err());
+        // Multi-byte input whose byte length matches a valid hex color
+        assert!(Color::from_hex("漢 ").is_err());
+        assert!(Color::from_hex("#é1234").is_err());
+    }
+
+    #[test]
+    fn test_color_display() {
+        assert_eq!(Color::Default.to_string(), "default");
+        assert_eq!(
+            Color::Standard(StandardColor::BrightCyan).to_string(),
+            "bright_cyan"
+        );
+        assert_eq!(Color::EightBit(3).to_string(), "yellow");
+        assert_eq!(Color::EightBit(208).to_string(), "color(208)");
+        assert_eq!(Color::rgb(255, 8, 0).to_string(), "#ff0800");
     }
 
     #[test]
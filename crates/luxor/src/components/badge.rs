@@ -0,0 +1,397 @@
+//! Badge - a short inline label on a colored background.
+//!
+//! Badges mark status in line with other output, as test runners do with
+//! ` PASS ` and ` FAIL `. A badge is a single line of fixed width, so it
+//! composes with text by collecting both into [`Segments`](crate::Segments):
+//!
+//! ```rust
+//! use luxor::{Console, RenderOptions, Renderable, Segments, Text, components::Badge};
+//!
+//! let console = Console::new();
+//! let options = RenderOptions::new().with_max_width(40);
+//! let mut line = Segments::new();
+//! line.extend(Badge::success("PASS").render(&console, &options).unwrap());
+//! line.extend(Text::new(" tests/render.rs").render(&console, &options).unwrap());
+//! assert_eq!(line.plain_text(), " PASS  tests/render.rs");
+//! ```
+
+use crate::{
+    Color, Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment,
+    StandardColor, Style, render_utils,
+};
+
+/// Cap characters for pill badges as `(left, right)`.
+const PILL_CAPS: (&str, &str) = ("▐", "▌");
+/// ASCII replacements for [`PILL_CAPS`].
+const ASCII_PILL_CAPS: (&str, &str) = ("(", ")");
+
+/// The outcome a preset badge marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Success,
+    Warning,
+    Error,
+    Info,
+}
+
+impl Kind {
+    /// Get the name of the theme style for the outcome.
+    fn theme_key(self) -> &'static str {
+        match self {
+            Kind::Success => "badge.success",
+            Kind::Warning => "badge.warning",
+            Kind::Error => "badge.error",
+            Kind::Info => "badge.info",
+        }
+    }
+
+    /// Get the marker put before the label in accessible mode.
+    fn marker(self, ascii: bool) -> &'static str {
+        match (self, ascii) {
+            (Kind::Success, false) => "✓",
+            (Kind::Warning, false) => "!",
+            (Kind::Error, false) => "✗",
+            (Kind::Info, false) => "i",
+            (Kind::Success, true) => "OK",
+            (Kind::Warning, true) => "WARN",
+            (Kind::Error, true) => "ERR",
+            (Kind::Info, true) => "INFO",
+        }
+    }
+}
+
+/// A short label padded by one space on each side.
+///
+/// The padding carries the badge style, so a background color forms a solid
+/// block around the label. Pill badges add rounded half-block caps, or
+/// parentheses when [`RenderOptions::ascii_only`] is set. With
+/// [`RenderOptions::accessible`], the presets put a marker before the
+/// label, `✓`, `!`, `✗` or `i`, or with ASCII only `OK`, `WARN`, `ERR` or
+/// `INFO`, so the outcome does not rest on the color.
+///
+/// The presets take their style from the theme of the options they are
+/// rendered with, or else of the console, as `badge.success`,
+/// `badge.warning`, `badge.error` and `badge.info`. Without a theme or
+/// that style they use their own colors, and a style set with
+/// [`Badge::with_style`] always wins.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, Style, components::Badge};
+///
+/// let badge = Badge::new("PASS").with_style(Style::parse("black on green").unwrap());
+/// let options = RenderOptions::new().with_max_width(40);
+/// let segments = badge.render(&Console::new(), &options).unwrap();
+/// assert_eq!(segments[0].text(), " PASS ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    label: String,
+    style: Style,
+    pill: bool,
+    kind: Option<Kind>,
+    /// Whether the style is the preset's, which the theme may replace.
+    themed: bool,
+}
+
+impl Badge {
+    /// Create an unstyled badge.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            style: Style::default(),
+            pill: false,
+            kind: None,
+            themed: false,
+        }
+    }
+
+    /// Create a badge for a successful outcome: the theme's
+    /// `badge.success` style, or bold black on green.
+    pub fn success(label: impl Into<String>) -> Self {
+        Self::preset(
+            label,
+            Kind::Success,
+            StandardColor::Black,
+            StandardColor::Green,
+        )
+    }
+
+    /// Create a badge for a warning: the theme's `badge.warning` style, or
+    /// bold black on yellow.
+    pub fn warning(label: impl Into<String>) -> Self {
+        Self::preset(
+            label,
+            Kind::Warning,
+            StandardColor::Black,
+            StandardColor::Yellow,
+        )
+    }
+
+    /// Create a badge for an error: the theme's `badge.error` style, or
+    /// bold white on red.
+    pub fn error(label: impl Into<String>) -> Self {
+        Self::preset(
+            label,
+            Kind::Error,
+            StandardColor::BrightWhite,
+            StandardColor::Red,
+        )
+    }
+
+    /// Create an informational badge: the theme's `badge.info` style, or
+    /// bold white on blue.
+    pub fn info(label: impl Into<String>) -> Self {
+        Self::preset(
+            label,
+            Kind::Info,
+            StandardColor::BrightWhite,
+            StandardColor::Blue,
+        )
+    }
+
+    fn preset(
+        label: impl Into<String>,
+        kind: Kind,
+        color: StandardColor,
+        background: StandardColor,
+    ) -> Self {
+        let style = Style::new()
+            .bold()
+            .with_color(Color::Standard(color))
+            .with_background(Color::Standard(background));
+        Self {
+            kind: Some(kind),
+            themed: true,
+            ..Self::new(label).with_style(style)
+        }
+    }
+
+    /// Set the style of the label and its padding, in place of any theme
+    /// style.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self.themed = false;
+        self
+    }
+
+    /// Enable or disable rounded pill ends.
+    ///
+    /// The caps are drawn in the badge's background color, so they only
+    /// look rounded when the style has a background.
+    pub fn with_pill(mut self, pill: bool) -> Self {
+        self.pill = pill;
+        self
+    }
+
+    /// Get the label text.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the style of the label and its padding, as used without a
+    /// theme.
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Check if the badge has pill ends.
+    pub fn is_pill(&self) -> bool {
+        self.pill
+    }
+
+    /// Get the width of the badge in cells, without the marker added in
+    /// accessible mode.
+    pub fn width(&self) -> usize {
+        let caps = if self.pill { 2 } else { 0 };
+        render_utils::visible_width(&self.label) + 2 + caps
+    }
+
+    /// Get the style drawn with: the theme's style for a preset, if there
+    /// is one, or else the badge's own.
+    fn resolved_style(&self, console: &Console, options: &RenderOptions) -> Style {
+        let Some(kind) = self.kind.filter(|_| self.themed) else {
+            return self.style.clone();
+        };
+        let theme = options
+            .resolved_theme()
+            .or_else(|| console.render_options().resolved_theme());
+        theme
+            .as_ref()
+            .and_then(|theme| theme.get(kind.theme_key()))
+            .unwrap_or(&self.style)
+            .clone()
+    }
+
+    /// Get the marker drawn before the label with `options`, if any.
+    fn marker(&self, options: &RenderOptions) -> Option<&'static str> {
+        let kind = self.kind.filter(|_| options.accessible)?;
+        Some(kind.marker(options.ascii_only))
+    }
+
+    /// Get the width of the badge in cells as drawn with `options`.
+    fn width_with(&self, options: &RenderOptions) -> usize {
+        let marker = self
+            .marker(options)
+            .map_or(0, |marker| render_utils::visible_width(marker) + 1);
+        self.width() + marker
+    }
+}
+
+impl Renderable for Badge {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let style = self.resolved_style(console, options);
+        let width = self.width_with(options).min(options.get_max_width());
+        let body = match self.marker(options) {
+            Some(marker) => format!(" {} {} ", marker, self.label),
+            None => format!(" {} ", self.label),
+        };
+        let body = Segment::new(body, style.clone());
+        if !self.pill {
+            return Ok(render_utils::adjust_line_length(
+                vec![body],
+                width,
+                style.clone(),
+            ));
+        }
+
+        let (left, right, cap_style) = if options.ascii_only {
+            (ASCII_PILL_CAPS.0, ASCII_PILL_CAPS.1, style.clone())
+        } else {
+            let cap_style = match style.background {
+                Some(background) => Style::new().with_color(background),
+                None => Style::default(),
+            };
+            (PILL_CAPS.0, PILL_CAPS.1, cap_style)
+        };
+        let line = vec![
+            Segment::new(left, cap_style.clone()),
+            body,
+            Segment::new(right, cap_style),
+        ];
+        Ok(render_utils::adjust_line_length(line, width, style.clone()))
+    }
+}
+
+impl Measurable for Badge {
+    fn measure(&self, _console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        Ok(Measurement::fixed(self.width_with(options)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Theme, testing::render_lines};
+
+    fn render(badge: &Badge, options: &RenderOptions) -> Vec<Segment> {
+        badge.render(&Console::new(), options).unwrap()
+    }
+
+    #[test]
+    fn test_badge_padding_carries_style() {
+        let style = Style::parse("black on green").unwrap();
+        let badge = Badge::new("PASS").with_style(style.clone());
+        let segments = render(&badge, &RenderOptions::new().with_max_width(40));
+        assert_eq!(segments, vec![Segment::new(" PASS ", style)]);
+    }
+
+    #[test]
+    fn test_badge_pill_caps() {
+        let style = Style::parse("black on green").unwrap();
+        let badge = Badge::new("PASS").with_style(style.clone()).with_pill(true);
+        let segments = render(&badge, &RenderOptions::new().with_max_width(40));
+        let cap = Style::new().with_color(Color::Standard(StandardColor::Green));
+        assert_eq!(
+            segments,
+            vec![
+                Segment::new("▐", cap.clone()),
+                Segment::new(" PASS ", style),
+                Segment::new("▌", cap),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_badge_ascii_fallback() {
+        let badge = Badge::error("FAIL").with_pill(true);
+        let options = RenderOptions::new()
+            .with_max_width(40)
+            .with_ascii_only(true);
+        let segments = render(&badge, &options);
+        crate::assert_render_eq!(render_lines(&segments), "( FAIL )");
+        assert!(segments.iter().all(|s| s.style() == badge.style()));
+    }
+
+    #[test]
+    fn test_badge_presets() {
+        let red = Some(Color::Standard(StandardColor::Red));
+        assert_eq!(Badge::error("x").style().background, red);
+        assert_eq!(Badge::success("x").style().bold, Some(true));
+        assert_ne!(Badge::warning("x").style(), Badge::info("x").style());
+    }
+
+    #[test]
+    fn test_badge_accessible_markers() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(40);
+        let accessible = options.clone().with_accessible(true);
+        let plain = |badge: &Badge, options: &RenderOptions| render_lines(&render(badge, options));
+        let badge = Badge::success("PASS").with_pill(true);
+        assert_eq!(plain(&badge, &options), ["▐ PASS ▌"]);
+        assert_eq!(plain(&badge, &accessible), ["▐ ✓ PASS ▌"]);
+        assert_eq!(badge.measure(&console, &accessible).unwrap().maximum(), 10);
+        assert_eq!(plain(&Badge::warning("slow"), &accessible), [" ! slow "]);
+        assert_eq!(plain(&Badge::error("FAIL"), &accessible), [" ✗ FAIL "]);
+        assert_eq!(plain(&Badge::info("note"), &accessible), [" i note "]);
+
+        let ascii = accessible.with_ascii_only(true);
+        assert_eq!(plain(&Badge::error("FAIL"), &ascii), [" ERR FAIL "]);
+        assert_eq!(plain(&Badge::info("note"), &ascii), [" INFO note "]);
+        // Badges of the caller's own style have no outcome to mark
+        assert_eq!(plain(&Badge::new("v1.2"), &ascii), [" v1.2 "]);
+    }
+
+    #[test]
+    fn test_badge_presets_use_theme() {
+        let options = RenderOptions::new().with_max_width(40);
+        let magenta = Style::parse("bold white on magenta").unwrap();
+        let theme = Theme::new().with_style("badge.error", magenta.clone());
+        let themed = options.clone().with_theme(theme.clone());
+        let style = |badge: &Badge, console: &Console, options: &RenderOptions| {
+            badge.render(console, options).unwrap()[0].style().clone()
+        };
+        let console = Console::new();
+        let badge = Badge::error("FAIL");
+        assert_eq!(style(&badge, &console, &themed), magenta);
+        // The default theme has the preset colors
+        let default = options.clone().with_theme(Theme::new());
+        assert_eq!(&style(&badge, &console, &default), badge.style());
+        // Without the style in the theme, or without a theme
+        let empty = options.clone().with_theme(Theme::empty());
+        assert_eq!(&style(&badge, &console, &empty), badge.style());
+        assert_eq!(&style(&badge, &console, &options), badge.style());
+        // The console's theme when the options have none
+        let console = Console::with_theme(theme);
+        assert_eq!(style(&badge, &console, &options), magenta);
+        // A style set on the badge wins
+        let own = Style::parse("bold red").unwrap();
+        let badge = badge.with_style(own.clone());
+        assert_eq!(style(&badge, &console, &themed), own);
+        let info = Badge::info("note");
+        assert_eq!(&style(&info, &console, &themed), info.style());
+    }
+
+    #[test]
+    fn test_badge_measure_and_crop() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(40);
+        let badge = Badge::new("漢字").with_pill(true);
+        assert_eq!(badge.measure(&console, &options).unwrap().maximum(), 8);
+
+        let narrow = RenderOptions::new().with_max_width(5);
+        let segments = render(&badge, &narrow);
+        assert_eq!(render_utils::line_width(&segments), 5);
+    }
+}
@@ -0,0 +1,932 @@
+//! BigText - short strings drawn in large block letters.
+//!
+//! Splash headers and banners without shelling out to figlet. Letters come
+//! from an embedded 5x7 bitmap font covering ASCII letters, digits and
+//! common punctuation; lowercase letters are drawn as capitals.
+//!
+//! ```rust
+//! use luxor::{Console, RenderOptions, Renderable, components::BigText};
+//!
+//! let options = RenderOptions::new().with_max_width(80);
+//! let banner = BigText::new("hi").with_half_height(true);
+//! let segments = banner.render(&Console::new(), &options).unwrap();
+//! let plain: String = segments.iter().map(|s| s.text()).collect();
+//! assert_eq!(plain.lines().next(), Some("█   █  ▀█▀ "));
+//! ```
+
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style,
+    diagnostics::{self, Warning},
+    rendered::Line,
+};
+use unicode_width::UnicodeWidthChar;
+
+/// Width of a glyph in pixels.
+const GLYPH_WIDTH: usize = 5;
+/// Height of a glyph in pixels.
+const GLYPH_HEIGHT: usize = 7;
+
+/// A glyph as rows of pixels, `#` for set ones.
+type Glyph = [&'static str; GLYPH_HEIGHT];
+/// A character and its glyph.
+type FontEntry = (char, Glyph);
+
+/// Text drawn in large block letters.
+///
+/// Each glyph is 5 pixels wide and 7 tall, and glyphs are separated by one
+/// column of spacing. A pixel is one `pixel` character, `█` by default. In
+/// half-height mode two rows of pixels share a line, drawn with `▀`, `▄`
+/// and `█`, so letters are 4 lines tall. With
+/// [`RenderOptions::ascii_only`], pixels are drawn as `#` at full height.
+///
+/// Characters without a glyph are drawn as a blank glyph, and reported as a
+/// [`Warning::MissingGlyph`]. Text wider than
+/// the available width loses whole glyphs from the end, replaced by an
+/// ellipsis glyph.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Measurable, components::BigText};
+///
+/// let console = Console::new();
+/// let options = RenderOptions::new().with_max_width(80);
+/// let measurement = BigText::new("OK").measure(&console, &options).unwrap();
+/// assert_eq!(measurement.maximum(), 11);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigText {
+    text: String,
+    pixel: char,
+    style: Style,
+    half_height: bool,
+    spacing: usize,
+}
+
+impl BigText {
+    /// Create big text drawn with `█` pixels.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            pixel: '█',
+            style: Style::default(),
+            half_height: false,
+            spacing: 1,
+        }
+    }
+
+    /// Set the character drawn for each pixel at full height.
+    pub fn with_pixel(mut self, pixel: char) -> Self {
+        self.pixel = pixel;
+        self
+    }
+
+    /// Set the style of the pixels.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Draw two rows of pixels per line with half blocks.
+    pub fn with_half_height(mut self, half_height: bool) -> Self {
+        self.half_height = half_height;
+        self
+    }
+
+    /// Set the number of pixel columns between glyphs.
+    pub fn with_spacing(mut self, spacing: usize) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Get the text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the width in cells when drawn with the given options.
+    pub fn width(&self, options: &RenderOptions) -> usize {
+        columns(self.text.chars().count(), self.spacing) * self.pixel_width(options)
+    }
+
+    /// Get the character drawn for full pixels.
+    fn pixel(&self, options: &RenderOptions) -> char {
+        if options.ascii_only && !self.pixel.is_ascii() {
+            diagnostics::warn(|| Warning::AsciiFallback {
+                component: "BigText",
+            });
+            '#'
+        } else {
+            self.pixel
+        }
+    }
+
+    /// Check if two rows of pixels share a line.
+    fn is_half_height(&self, options: &RenderOptions) -> bool {
+        self.half_height && !options.ascii_only
+    }
+
+    /// Get the width of a pixel in cells.
+    fn pixel_width(&self, options: &RenderOptions) -> usize {
+        if self.is_half_height(options) {
+            1
+        } else {
+            self.pixel(options).width().unwrap_or(1).max(1)
+        }
+    }
+
+    /// Get the glyphs to draw within `max_width` cells.
+    fn glyphs(&self, options: &RenderOptions, max_width: usize) -> Vec<&'static Glyph> {
+        let mut glyphs: Vec<&Glyph> = self.text.chars().map(glyph).collect();
+        let pixel_width = self.pixel_width(options);
+        let fits = |count: usize| columns(count, self.spacing) * pixel_width <= max_width;
+        if !fits(glyphs.len()) {
+            // Keep room for the ellipsis glyph, if it fits at all
+            let count = (0..glyphs.len()).rev().find(|&count| fits(count + 1));
+            match count {
+                Some(count) => {
+                    glyphs.truncate(count);
+                    glyphs.push(&ELLIPSIS);
+                }
+                None => glyphs.clear(),
+            }
+        }
+        glyphs
+    }
+
+    /// Lay the glyphs out as rows of pixels.
+    fn pixels(&self, glyphs: &[&Glyph]) -> Vec<Vec<bool>> {
+        (0..GLYPH_HEIGHT)
+            .map(|row| {
+                let mut pixels = Vec::new();
+                for (index, glyph) in glyphs.iter().enumerate() {
+                    if index > 0 {
+                        pixels.extend(std::iter::repeat(false).take(self.spacing));
+                    }
+                    pixels.extend(glyph[row].bytes().map(|byte| byte == b'#'));
+                }
+                pixels
+            })
+            .collect()
+    }
+
+    /// Draw a line of cells, each `None` for a blank or the cell's text.
+    fn line(&self, cells: impl Iterator<Item = Option<String>>, blank: &str) -> Line {
+        let mut line = Line::new();
+        let mut run = String::new();
+        let mut run_set = false;
+        for cell in cells {
+            let set = cell.is_some();
+            if set != run_set && !run.is_empty() {
+                line.push(self.run(std::mem::take(&mut run), run_set));
+            }
+            run_set = set;
+            run.push_str(cell.as_deref().unwrap_or(blank));
+        }
+        if !run.is_empty() {
+            line.push(self.run(run, run_set));
+        }
+        line
+    }
+
+    fn run(&self, text: String, set: bool) -> Segment {
+        let style = if set {
+            self.style.clone()
+        } else {
+            Style::default()
+        };
+        Segment::new(text, style)
+    }
+}
+
+/// Get the number of pixel columns of `count` glyphs.
+fn columns(count: usize, spacing: usize) -> usize {
+    match count {
+        0 => 0,
+        count => count * GLYPH_WIDTH + (count - 1) * spacing,
+    }
+}
+
+/// Get the glyph for a character, warning if there is none.
+fn glyph(ch: char) -> &'static Glyph {
+    let ch = ch.to_ascii_uppercase();
+    match FONT.iter().find(|(glyph_char, _)| *glyph_char == ch) {
+        Some((_, glyph)) => glyph,
+        None => {
+            diagnostics::warn(|| Warning::MissingGlyph { character: ch });
+            &FONT[0].1
+        }
+    }
+}
+
+impl Renderable for BigText {
+    fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let glyphs = self.glyphs(options, options.get_max_width());
+        if glyphs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows = self.pixels(&glyphs);
+        let lines: Vec<Line> = if self.is_half_height(options) {
+            rows.chunks(2)
+                .map(|pair| {
+                    let cells = (0..pair[0].len()).map(|column| {
+                        let top = pair[0][column];
+                        let bottom = pair.get(1).is_some_and(|row| row[column]);
+                        match (top, bottom) {
+                            (true, true) => Some("█".to_string()),
+                            (true, false) => Some("▀".to_string()),
+                            (false, true) => Some("▄".to_string()),
+                            (false, false) => None,
+                        }
+                    });
+                    self.line(cells, " ")
+                })
+                .collect()
+        } else {
+            let pixel = self.pixel(options).to_string();
+            let blank = " ".repeat(self.pixel_width(options));
+            rows.iter()
+                .map(|row| {
+                    let cells = row.iter().map(|&set| set.then(|| pixel.clone()));
+                    self.line(cells, &blank)
+                })
+                .collect()
+        };
+        Ok(crate::render_utils::frame_lines(lines))
+    }
+}
+
+impl Measurable for BigText {
+    fn measure(&self, _console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        Ok(Measurement::fixed(self.width(options)))
+    }
+}
+
+/// Drawn in place of glyphs cut off to fit the width.
+#[rustfmt::skip]
+const ELLIPSIS: Glyph = [
+    "     ",
+    "     ",
+    "     ",
+    "     ",
+    "     ",
+    "     ",
+    "# # #",
+];
+
+/// The font, starting with the blank glyph for space.
+#[rustfmt::skip]
+const FONT: &[FontEntry] = &[
+    (' ', [
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+    ]),
+    ('!', [
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "     ",
+        "  #  ",
+    ]),
+    ('"', [
+        " # # ",
+        " # # ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+    ]),
+    ('#', [
+        " # # ",
+        " # # ",
+        "#####",
+        " # # ",
+        "#####",
+        " # # ",
+        " # # ",
+    ]),
+    ('$', [
+        "  #  ",
+        " ####",
+        "# #  ",
+        " ### ",
+        "  # #",
+        "#### ",
+        "  #  ",
+    ]),
+    ('%', [
+        "##   ",
+        "##  #",
+        "   # ",
+        "  #  ",
+        " #   ",
+        "#  ##",
+        "   ##",
+    ]),
+    ('&', [
+        " ##  ",
+        "#  # ",
+        "# #  ",
+        " #   ",
+        "# # #",
+        "#  # ",
+        " ## #",
+    ]),
+    ('\'', [
+        "  #  ",
+        "  #  ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+    ]),
+    ('(', [
+        "   # ",
+        "  #  ",
+        " #   ",
+        " #   ",
+        " #   ",
+        "  #  ",
+        "   # ",
+    ]),
+    (')', [
+        " #   ",
+        "  #  ",
+        "   # ",
+        "   # ",
+        "   # ",
+        "  #  ",
+        " #   ",
+    ]),
+    ('*', [
+        "     ",
+        "  #  ",
+        "# # #",
+        " ### ",
+        "# # #",
+        "  #  ",
+        "     ",
+    ]),
+    ('+', [
+        "     ",
+        "  #  ",
+        "  #  ",
+        "#####",
+        "  #  ",
+        "  #  ",
+        "     ",
+    ]),
+    (',', [
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "  #  ",
+        " #   ",
+    ]),
+    ('-', [
+        "     ",
+        "     ",
+        "     ",
+        " ### ",
+        "     ",
+        "     ",
+        "     ",
+    ]),
+    ('.', [
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "  #  ",
+    ]),
+    ('/', [
+        "    #",
+        "    #",
+        "   # ",
+        "  #  ",
+        " #   ",
+        "#    ",
+        "#    ",
+    ]),
+    ('0', [
+        " ### ",
+        "#   #",
+        "#  ##",
+        "# # #",
+        "##  #",
+        "#   #",
+        " ### ",
+    ]),
+    ('1', [
+        "  #  ",
+        " ##  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        " ### ",
+    ]),
+    ('2', [
+        " ### ",
+        "#   #",
+        "    #",
+        "   # ",
+        "  #  ",
+        " #   ",
+        "#####",
+    ]),
+    ('3', [
+        "#####",
+        "   # ",
+        "  #  ",
+        "   # ",
+        "    #",
+        "#   #",
+        " ### ",
+    ]),
+    ('4', [
+        "   # ",
+        "  ## ",
+        " # # ",
+        "#  # ",
+        "#####",
+        "   # ",
+        "   # ",
+    ]),
+    ('5', [
+        "#####",
+        "#    ",
+        "#### ",
+        "    #",
+        "    #",
+        "#   #",
+        " ### ",
+    ]),
+    ('6', [
+        "  ## ",
+        " #   ",
+        "#    ",
+        "#### ",
+        "#   #",
+        "#   #",
+        " ### ",
+    ]),
+    ('7', [
+        "#####",
+        "    #",
+        "   # ",
+        "  #  ",
+        " #   ",
+        " #   ",
+        " #   ",
+    ]),
+    ('8', [
+        " ### ",
+        "#   #",
+        "#   #",
+        " ### ",
+        "#   #",
+        "#   #",
+        " ### ",
+    ]),
+    ('9', [
+        " ### ",
+        "#   #",
+        "#   #",
+        " ####",
+        "    #",
+        "   # ",
+        " ##  ",
+    ]),
+    (':', [
+        "     ",
+        "     ",
+        "  #  ",
+        "     ",
+        "     ",
+        "  #  ",
+        "     ",
+    ]),
+    (';', [
+        "     ",
+        "     ",
+        "  #  ",
+        "     ",
+        "     ",
+        "  #  ",
+        " #   ",
+    ]),
+    ('<', [
+        "   # ",
+        "  #  ",
+        " #   ",
+        "#    ",
+        " #   ",
+        "  #  ",
+        "   # ",
+    ]),
+    ('=', [
+        "     ",
+        "     ",
+        "#####",
+        "     ",
+        "#####",
+        "     ",
+        "     ",
+    ]),
+    ('>', [
+        " #   ",
+        "  #  ",
+        "   # ",
+        "    #",
+        "   # ",
+        "  #  ",
+        " #   ",
+    ]),
+    ('?', [
+        " ### ",
+        "#   #",
+        "    #",
+        "   # ",
+        "  #  ",
+        "     ",
+        "  #  ",
+    ]),
+    ('@', [
+        " ### ",
+        "#   #",
+        "# ###",
+        "# # #",
+        "# ###",
+        "#    ",
+        " ####",
+    ]),
+    ('A', [
+        " ### ",
+        "#   #",
+        "#   #",
+        "#####",
+        "#   #",
+        "#   #",
+        "#   #",
+    ]),
+    ('B', [
+        "#### ",
+        "#   #",
+        "#   #",
+        "#### ",
+        "#   #",
+        "#   #",
+        "#### ",
+    ]),
+    ('C', [
+        " ### ",
+        "#   #",
+        "#    ",
+        "#    ",
+        "#    ",
+        "#   #",
+        " ### ",
+    ]),
+    ('D', [
+        "#### ",
+        "#   #",
+        "#   #",
+        "#   #",
+        "#   #",
+        "#   #",
+        "#### ",
+    ]),
+    ('E', [
+        "#####",
+        "#    ",
+        "#    ",
+        "#### ",
+        "#    ",
+        "#    ",
+        "#####",
+    ]),
+    ('F', [
+        "#####",
+        "#    ",
+        "#    ",
+        "#### ",
+        "#    ",
+        "#    ",
+        "#    ",
+    ]),
+    ('G', [
+        " ### ",
+        "#   #",
+        "#    ",
+        "# ###",
+        "#   #",
+        "#   #",
+        " ####",
+    ]),
+    ('H', [
+        "#   #",
+        "#   #",
+        "#   #",
+        "#####",
+        "#   #",
+        "#   #",
+        "#   #",
+    ]),
+    ('I', [
+        " ### ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        " ### ",
+    ]),
+    ('J', [
+        "  ###",
+        "   # ",
+        "   # ",
+        "   # ",
+        "   # ",
+        "#  # ",
+        " ##  ",
+    ]),
+    ('K', [
+        "#   #",
+        "#  # ",
+        "# #  ",
+        "##   ",
+        "# #  ",
+        "#  # ",
+        "#   #",
+    ]),
+    ('L', [
+        "#    ",
+        "#    ",
+        "#    ",
+        "#    ",
+        "#    ",
+        "#    ",
+        "#####",
+    ]),
+    ('M', [
+        "#   #",
+        "## ##",
+        "# # #",
+        "# # #",
+        "#   #",
+        "#   #",
+        "#   #",
+    ]),
+    ('N', [
+        "#   #",
+        "#   #",
+        "##  #",
+        "# # #",
+        "#  ##",
+        "#   #",
+        "#   #",
+    ]),
+    ('O', [
+        " ### ",
+        "#   #",
+        "#   #",
+        "#   #",
+        "#   #",
+        "#   #",
+        " ### ",
+    ]),
+    ('P', [
+        "#### ",
+        "#   #",
+        "#   #",
+        "#### ",
+        "#    ",
+        "#    ",
+        "#    ",
+    ]),
+    ('Q', [
+        " ### ",
+        "#   #",
+        "#   #",
+        "#   #",
+        "# # #",
+        "#  # ",
+        " ## #",
+    ]),
+    ('R', [
+        "#### ",
+        "#   #",
+        "#   #",
+        "#### ",
+        "# #  ",
+        "#  # ",
+        "#   #",
+    ]),
+    ('S', [
+        " ####",
+        "#    ",
+        "#    ",
+        " ### ",
+        "    #",
+        "    #",
+        "#### ",
+    ]),
+    ('T', [
+        "#####",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+    ]),
+    ('U', [
+        "#   #",
+        "#   #",
+        "#   #",
+        "#   #",
+        "#   #",
+        "#   #",
+        " ### ",
+    ]),
+    ('V', [
+        "#   #",
+        "#   #",
+        "#   #",
+        "#   #",
+        "#   #",
+        " # # ",
+        "  #  ",
+    ]),
+    ('W', [
+        "#   #",
+        "#   #",
+        "#   #",
+        "# # #",
+        "# # #",
+        "# # #",
+        " # # ",
+    ]),
+    ('X', [
+        "#   #",
+        "#   #",
+        " # # ",
+        "  #  ",
+        " # # ",
+        "#   #",
+        "#   #",
+    ]),
+    ('Y', [
+        "#   #",
+        "#   #",
+        " # # ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+    ]),
+    ('Z', [
+        "#####",
+        "    #",
+        "   # ",
+        "  #  ",
+        " #   ",
+        "#    ",
+        "#####",
+    ]),
+    ('[', [
+        " ### ",
+        " #   ",
+        " #   ",
+        " #   ",
+        " #   ",
+        " #   ",
+        " ### ",
+    ]),
+    ('\\', [
+        "#    ",
+        "#    ",
+        " #   ",
+        "  #  ",
+        "   # ",
+        "    #",
+        "    #",
+    ]),
+    (']', [
+        " ### ",
+        "   # ",
+        "   # ",
+        "   # ",
+        "   # ",
+        "   # ",
+        " ### ",
+    ]),
+    ('^', [
+        "  #  ",
+        " # # ",
+        "#   #",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+    ]),
+    ('_', [
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "     ",
+        "#####",
+    ]),
+    ('|', [
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+        "  #  ",
+    ]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::render_lines;
+
+    fn render(text: &BigText, width: usize) -> Vec<String> {
+        let options = RenderOptions::new().with_max_width(width);
+        render_lines(&text.render(&Console::new(), &options).unwrap())
+    }
+
+    #[test]
+    fn test_big_text_ok() {
+        crate::assert_render_eq!(
+            render(&BigText::new("OK").with_pixel('#'), 80),
+            " ###  #   #\n\
+             #   # #  # \n\
+             #   # # #  \n\
+             #   # ##   \n\
+             #   # # #  \n\
+             #   # #  # \n\
+             \x20###  #   #"
+        );
+    }
+
+    #[test]
+    fn test_big_text_half_height() {
+        crate::assert_render_eq!(
+            render(&BigText::new("ok").with_half_height(true), 80),
+            "▄▀▀▀▄ █  ▄▀\n\
+             █   █ █▄▀  \n\
+             █   █ █ ▀▄ \n\
+             \x20▀▀▀  ▀   ▀"
+        );
+    }
+
+    #[test]
+    fn test_big_text_truncates_whole_glyphs() {
+        let text = BigText::new("OK!").with_pixel('#');
+        // Three glyphs need 17 cells; two fit with the ellipsis in 11
+        let lines = render(&text, 16);
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], " ###       ");
+        assert_eq!(lines[6], " ###  # # #");
+        // Not even the ellipsis fits
+        let options = RenderOptions::new().with_max_width(4);
+        assert!(text.render(&Console::new(), &options).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_big_text_unknown_character() {
+        let text = BigText::new("a~").with_pixel('#');
+        let warnings = diagnostics::collect_warnings(|| {
+            let lines = render(&text, 80);
+            assert!(lines.iter().all(|line| line.ends_with("      ")));
+        });
+        let missing = Warning::MissingGlyph { character: '~' };
+        assert!(warnings.contains(&missing));
+        assert!(!warnings.contains(&Warning::MissingGlyph { character: 'A' }));
+        assert_eq!(
+            missing.to_string(),
+            "no big text glyph for '~'; drawn blank"
+        );
+        let options = RenderOptions::new().with_max_width(80);
+        assert_eq!(text.width(&options), 11);
+    }
+}
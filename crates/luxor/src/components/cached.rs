@@ -0,0 +1,303 @@
+//! Cached - memoized rendering for components that rarely change.
+//!
+//! In a frequently refreshed display most components render the same lines
+//! every frame. Wrapping them in [`Cached`] keeps the last rendered segments
+//! and reuses them until the content key or the render width changes.
+
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Text,
+    diagnostics, render_utils,
+};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::Mutex,
+};
+
+/// A fingerprint of everything that affects how a value renders.
+///
+/// Implementations can hash their content or return a version counter that
+/// is bumped on every change. Two values with the same key must render
+/// identically at the same width.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::components::CacheKey;
+///
+/// struct Status {
+///     version: u64,
+///     message: String,
+/// }
+///
+/// impl CacheKey for Status {
+///     fn cache_key(&self) -> u64 {
+///         self.version
+///     }
+/// }
+/// ```
+pub trait CacheKey {
+    /// Get the key identifying the current content.
+    fn cache_key(&self) -> u64;
+}
+
+impl CacheKey for str {
+    fn cache_key(&self) -> u64 {
+        hash_of(self)
+    }
+}
+
+impl CacheKey for String {
+    fn cache_key(&self) -> u64 {
+        hash_of(self)
+    }
+}
+
+impl CacheKey for Text {
+    fn cache_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.plain().hash(&mut hasher);
+        self.base_style().hash(&mut hasher);
+        self.spans().hash(&mut hasher);
+        self.justify().hash(&mut hasher);
+        self.full_width_background().hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Hash a value with the standard hasher.
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The last render of a [`Cached`] value and the inputs it was made from.
+#[derive(Debug)]
+struct CacheEntry {
+    key: u64,
+    width: usize,
+    segments: Vec<Segment>,
+}
+
+/// A renderable wrapper that memoizes the rendered segments.
+///
+/// The cache is reused while both the inner value's [`CacheKey`] and the
+/// effective render width are unchanged. Mutating the value through
+/// [`Cached::get_mut`] or calling [`Cached::invalidate`] discards it.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, Text, components::Cached};
+///
+/// let console = Console::new();
+/// let options = RenderOptions::new().with_max_width(40);
+/// let mut cached = Cached::new(Text::new("CPU 12%"));
+///
+/// let first = cached.render(&console, &options).unwrap();
+/// assert!(cached.is_cached());
+///
+/// *cached.get_mut() = Text::new("CPU 87%");
+/// let second = cached.render(&console, &options).unwrap();
+/// assert_ne!(first, second);
+/// ```
+#[derive(Debug)]
+pub struct Cached<R> {
+    inner: R,
+    cache: Mutex<Option<CacheEntry>>,
+}
+
+impl<R: Renderable + CacheKey> Cached<R> {
+    /// Wrap a renderable with an empty cache.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Get the wrapped renderable.
+    pub fn get(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get the wrapped renderable for modification, discarding the cache.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.invalidate();
+        &mut self.inner
+    }
+
+    /// Unwrap the renderable.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Discard the cached render so the next render is fresh.
+    pub fn invalidate(&self) {
+        *self.lock() = None;
+    }
+
+    /// Check whether a cached render is currently held.
+    pub fn is_cached(&self) -> bool {
+        self.lock().is_some()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<CacheEntry>> {
+        // A panic while holding the lock cannot leave a partial entry behind,
+        // since entries are only ever replaced whole
+        self.cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<R: Renderable + CacheKey> Renderable for Cached<R> {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let key = self.inner.cache_key();
+        let width = options.get_max_width();
+
+        if let Some(entry) = self.lock().as_ref() {
+            if entry.key == key && entry.width == width {
+                diagnostics::breadcrumb!(key, width, "cache hit");
+                return Ok(entry.segments.clone());
+            }
+        }
+        diagnostics::breadcrumb!(key, width, "cache miss");
+
+        let segments = options.render_child(&self.inner, console)?;
+        *self.lock() = Some(CacheEntry {
+            key,
+            width,
+            segments: segments.clone(),
+        });
+        Ok(segments)
+    }
+}
+
+impl<R: Renderable + Measurable + CacheKey> Measurable for Cached<R> {
+    /// Measure the inner value. Past the measure limit of the pass, its
+    /// last measurement in the pass is used instead, or else the width of
+    /// the cached render, or the whole width without one.
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        options.measure_child(&self.inner, console, || {
+            let width = options.get_max_width();
+            match self.lock().as_ref() {
+                Some(entry) => {
+                    let lines = render_utils::split_lines(entry.segments.clone());
+                    let widest = lines
+                        .iter()
+                        .map(|line| render_utils::line_width(line))
+                        .max();
+                    Measurement::fixed(widest.unwrap_or(0).min(width))
+                }
+                None => Measurement::span(width),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Justify, Style, testing::render_lines};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A renderable that counts how often it is actually rendered.
+    struct Counting {
+        version: u64,
+        renders: AtomicUsize,
+    }
+
+    impl Counting {
+        fn new() -> Self {
+            Self {
+                version: 0,
+                renders: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl Renderable for Counting {
+        fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+            self.renders.fetch_add(1, Ordering::SeqCst);
+            let line = format!("v{} w{}", self.version, options.get_max_width());
+            Ok(vec![Segment::new(line, Style::default())])
+        }
+    }
+
+    impl CacheKey for Counting {
+        fn cache_key(&self) -> u64 {
+            self.version
+        }
+    }
+
+    fn renders(cached: &Cached<Counting>) -> usize {
+        cached.get().renders.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_cached_reuses_render() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(20);
+        let cached = Cached::new(Counting::new());
+
+        let first = cached.render(&console, &options).unwrap();
+        let second = cached.render(&console, &options).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(renders(&cached), 1);
+    }
+
+    #[test]
+    fn test_cached_width_change() {
+        let console = Console::new();
+        let cached = Cached::new(Counting::new());
+
+        let narrow = RenderOptions::new().with_max_width(20);
+        let wide = RenderOptions::new().with_max_width(40);
+        cached.render(&console, &narrow).unwrap();
+        let lines = render_lines(&cached.render(&console, &wide).unwrap());
+        crate::assert_render_eq!(lines, "v0 w40");
+        assert_eq!(renders(&cached), 2);
+    }
+
+    #[test]
+    fn test_cached_invalidate() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(20);
+        let cached = Cached::new(Counting::new());
+
+        cached.render(&console, &options).unwrap();
+        cached.invalidate();
+        assert!(!cached.is_cached());
+        cached.render(&console, &options).unwrap();
+        assert_eq!(renders(&cached), 2);
+    }
+
+    #[test]
+    fn test_cached_key_change() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(20);
+        let mut cached = Cached::new(Counting::new());
+
+        cached.render(&console, &options).unwrap();
+        cached.get_mut().version = 1;
+        let lines = render_lines(&cached.render(&console, &options).unwrap());
+        crate::assert_render_eq!(lines, "v1 w20");
+    }
+
+    #[test]
+    fn test_text_cache_key() {
+        let text = Text::new("hello");
+        assert_eq!(text.cache_key(), Text::new("hello").cache_key());
+        assert_ne!(text.cache_key(), Text::new("hallo").cache_key());
+        assert_ne!(
+            text.cache_key(),
+            text.clone().with_justify(Justify::Center).cache_key()
+        );
+        assert_ne!(
+            text.cache_key(),
+            Text::from_markup("[bold]hello[/bold]").unwrap().cache_key()
+        );
+    }
+}
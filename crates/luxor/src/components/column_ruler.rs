@@ -0,0 +1,216 @@
+//! ColumnRuler - column numbers for debugging layout.
+//!
+//! The ruler numbers every cell of the width it is given, so printing it
+//! above some output, or inside a container, shows which column each
+//! character lands in without counting by hand.
+
+use crate::{Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style};
+use std::collections::BTreeSet;
+
+/// A ruler numbering the columns across the available width.
+///
+/// Each column's number is written downwards, one digit per line: the
+/// last line has the units, the line above the tens, and so on, with the
+/// higher digits left blank until the column reaches them. There are as
+/// many lines as the widest column number has digits. Columns are counted
+/// from zero.
+///
+/// Marked columns are drawn in a contrasting style, to check where a
+/// border or edge is expected to fall.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, components::ColumnRuler};
+///
+/// let options = RenderOptions::new().with_max_width(14);
+/// let segments = ColumnRuler::new().render(&Console::new(), &options).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, "          1111\n01234567890123");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnRuler {
+    markers: BTreeSet<usize>,
+    style: Style,
+    marker_style: Style,
+}
+
+impl ColumnRuler {
+    /// Create a dim ruler with no marked columns.
+    pub fn new() -> Self {
+        Self {
+            markers: BTreeSet::new(),
+            style: Style::new().dim(),
+            marker_style: Style::new().reverse(),
+        }
+    }
+
+    /// Mark `columns`, counted from zero. Columns past the width are not
+    /// drawn.
+    pub fn with_markers(mut self, columns: impl IntoIterator<Item = usize>) -> Self {
+        self.markers.extend(columns);
+        self
+    }
+
+    /// Set the style of the digits.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the digits in marked columns.
+    pub fn with_marker_style(mut self, style: Style) -> Self {
+        self.marker_style = style;
+        self
+    }
+
+    /// Get the marked columns, in ascending order.
+    pub fn markers(&self) -> impl Iterator<Item = usize> + '_ {
+        self.markers.iter().copied()
+    }
+
+    /// Render the line of the digits worth `place` for columns below
+    /// `width`, in runs of the same style.
+    fn render_line(&self, place: usize, width: usize) -> Vec<Segment> {
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut run = String::new();
+        let mut marked = false;
+        for column in 0..width {
+            let digit = match column >= place || place == 1 {
+                true => char::from(b'0' + (column / place % 10) as u8),
+                false => ' ',
+            };
+            let is_marker = self.markers.contains(&column);
+            if is_marker != marked && !run.is_empty() {
+                segments.push(self.segment(std::mem::take(&mut run), marked));
+            }
+            marked = is_marker;
+            run.push(digit);
+        }
+        if !run.is_empty() {
+            segments.push(self.segment(run, marked));
+        }
+        segments
+    }
+
+    fn segment(&self, run: String, marked: bool) -> Segment {
+        let style = match marked {
+            true => self.marker_style.clone(),
+            false => self.style.clone(),
+        };
+        Segment::new(run, style)
+    }
+}
+
+impl Default for ColumnRuler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderable for ColumnRuler {
+    fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let width = options.get_max_width();
+        if width == 0 {
+            return Ok(Vec::new());
+        }
+        // The place value of the highest digit of the last column
+        let mut place = 1;
+        while (width - 1) / place >= 10 {
+            place *= 10;
+        }
+        let mut segments = Vec::new();
+        loop {
+            segments.extend(self.render_line(place, width));
+            if place == 1 {
+                return Ok(segments);
+            }
+            segments.push(Segment::line());
+            place /= 10;
+        }
+    }
+}
+
+impl Measurable for ColumnRuler {
+    fn measure(&self, _console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        Ok(Measurement::span(options.get_max_width()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_render_eq, testing::render_lines};
+
+    const MARKERS: [usize; 3] = [0, 34, 119];
+
+    fn render(width: usize) -> Vec<Segment> {
+        let options = RenderOptions::new().with_max_width(width);
+        ColumnRuler::new()
+            .with_markers(MARKERS)
+            .render(&Console::new(), &options)
+            .unwrap()
+    }
+
+    /// The columns drawn in the marker style.
+    fn marked(segments: &[Segment]) -> Vec<usize> {
+        let reversed = Style::new().reverse();
+        let mut columns = Vec::new();
+        let mut column = 0;
+        for segment in segments {
+            if segment.text() == "\n" {
+                column = 0;
+                continue;
+            }
+            if segment.style() == &reversed {
+                columns.extend(column..column + segment.cell_length());
+            }
+            column += segment.cell_length();
+        }
+        columns.dedup();
+        columns
+    }
+
+    #[test]
+    fn test_ruler_width_9() {
+        let segments = render(9);
+        assert_render_eq!(render_lines(&segments), "012345678");
+        assert_eq!(marked(&segments), [0]);
+        assert_eq!(segments[1].style(), &Style::new().dim());
+    }
+
+    #[test]
+    fn test_ruler_width_35() {
+        let segments = render(35);
+        assert_render_eq!(
+            render_lines(&segments),
+            "          1111111111222222222233333\n\
+             01234567890123456789012345678901234"
+        );
+        assert_eq!(marked(&segments), [0, 34, 0, 34]);
+    }
+
+    #[test]
+    fn test_ruler_width_120() {
+        let segments = render(120);
+        let hundreds = format!("{}{}", " ".repeat(100), "1".repeat(20));
+        let tens: String = (0..120)
+            .map(|column| match column {
+                0..=9 => ' ',
+                _ => char::from(b'0' + (column / 10 % 10) as u8),
+            })
+            .collect();
+        assert_eq!(
+            &tens[..30],
+            "          11111111112222222222",
+            "tens start at column 10"
+        );
+        assert_eq!(&tens[95..115], "99999000000000011111");
+        let units = "0123456789".repeat(12);
+        assert_render_eq!(
+            render_lines(&segments),
+            format!("{hundreds}\n{tens}\n{units}")
+        );
+        assert_eq!(marked(&segments), [0, 34, 119, 0, 34, 119, 0, 34, 119]);
+    }
+}
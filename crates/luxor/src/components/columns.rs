@@ -0,0 +1,414 @@
+//! Columns - renderables flowed into as many columns as fit.
+//!
+//! [`Columns`] lays a list of items out in a grid, like a terminal `ls`.
+//! By default it picks the most columns the available width allows from
+//! the measured width of each item; [`Columns::with_column_count`] fixes
+//! the count instead:
+//!
+//! ```rust
+//! use luxor::{Console, RenderOptions, Renderable, components::Columns};
+//!
+//! let columns = Columns::from_items(["alpha", "beta", "gamma", "delta"]);
+//! let options = RenderOptions::new().with_max_width(14);
+//! let segments = columns.render(&Console::new(), &options).unwrap();
+//! let plain: String = segments.iter().map(|s| s.text()).collect();
+//! assert_eq!(plain, "alpha beta \ngamma delta");
+//! ```
+
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style,
+    layout::{Padding, WidthSpec, resolve_widths},
+    protocol::RenderableMeasurable,
+    render_utils,
+    rendered::Line,
+};
+
+/// An item of a [`Columns`].
+type Item = Box<dyn RenderableMeasurable>;
+
+/// The rendered lines of one item.
+type Cell = Vec<Line>;
+
+/// Renderables laid out in a grid of columns.
+///
+/// Items are placed row by row, or column by column with
+/// [`Columns::with_column_first`]. Each column is as wide as its widest
+/// item, or every column as wide as the widest item overall with
+/// [`Columns::with_equal`]. Cells are padded to the width of their column
+/// and to the height of their row.
+///
+/// [`Padding`] separates cells but is not added around the grid: columns
+/// are `max(left, right)` cells apart and rows `max(top, bottom)` lines
+/// apart. The default is one cell between columns.
+///
+/// Without a fixed count, the grid has the most columns whose widths and
+/// gaps fit the available width, and one column when even a single item
+/// is wider than that. Columns that do not fit, whether an item is too
+/// wide or the fixed count too high, are narrowed in proportion to their
+/// width and their items wrapped to it. No items render nothing.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, components::Columns};
+///
+/// let columns = Columns::from_items(["a", "bb", "ccc", "d", "e"])
+///     .with_column_count(2)
+///     .with_column_first(true);
+/// let options = RenderOptions::new().with_max_width(40);
+/// let segments = columns.render(&Console::new(), &options).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, "a   d\nbb  e\nccc  ");
+/// ```
+pub struct Columns {
+    items: Vec<Item>,
+    column_count: Option<usize>,
+    padding: Padding,
+    equal: bool,
+    column_first: bool,
+}
+
+impl Columns {
+    /// Create an empty grid, fitting as many columns as it can.
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            column_count: None,
+            padding: Padding::symmetric(0, 1),
+            equal: false,
+            column_first: false,
+        }
+    }
+
+    /// Create a grid of `items`.
+    pub fn from_items<R>(items: impl IntoIterator<Item = R>) -> Self
+    where
+        R: Renderable + Measurable + 'static,
+    {
+        let mut columns = Self::new();
+        columns.extend(items);
+        columns
+    }
+
+    /// Add an item after the last one.
+    pub fn push(&mut self, item: impl Renderable + Measurable + 'static) {
+        self.items.push(Box::new(item));
+    }
+
+    /// Add an item after the last one.
+    pub fn with_item(mut self, item: impl Renderable + Measurable + 'static) -> Self {
+        self.push(item);
+        self
+    }
+
+    /// Always use `count` columns, or as many as there are items if that
+    /// is fewer. A count of zero is taken as one.
+    pub fn with_column_count(mut self, count: usize) -> Self {
+        self.column_count = Some(count.max(1));
+        self
+    }
+
+    /// Set the space between cells. See [`Columns`] for how it collapses.
+    pub fn with_padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    /// Make every column as wide as the widest item.
+    pub fn with_equal(mut self, equal: bool) -> Self {
+        self.equal = equal;
+        self
+    }
+
+    /// Fill each column top to bottom before the next, instead of each
+    /// row left to right.
+    pub fn with_column_first(mut self, column_first: bool) -> Self {
+        self.column_first = column_first;
+        self
+    }
+
+    /// Get the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check whether there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get the cells between columns.
+    fn column_gap(&self) -> usize {
+        self.padding.left.max(self.padding.right)
+    }
+
+    /// Get the lines between rows.
+    fn row_gap(&self) -> usize {
+        self.padding.top.max(self.padding.bottom)
+    }
+
+    /// Get the number of rows and columns needed to place every item in at
+    /// most `columns` columns. Filling column first can leave fewer.
+    fn grid(&self, columns: usize) -> (usize, usize) {
+        let count = self.items.len();
+        let rows = (count + columns - 1) / columns;
+        match self.column_first {
+            true => (rows, (count + rows - 1) / rows),
+            false => (rows, columns),
+        }
+    }
+
+    /// Get the column of the item at `index` in a grid of `rows` by
+    /// `columns`.
+    fn column_of(&self, index: usize, rows: usize, columns: usize) -> usize {
+        match self.column_first {
+            true => index / rows,
+            false => index % columns,
+        }
+    }
+
+    /// Get the natural width of each column of a grid with at most
+    /// `columns` columns.
+    fn column_widths(&self, measurements: &[Measurement], columns: usize) -> Vec<Measurement> {
+        let (rows, columns) = self.grid(columns);
+        let mut widths = vec![Measurement::fixed(0); columns];
+        for (index, measurement) in measurements.iter().enumerate() {
+            let column = self.column_of(index, rows, columns);
+            widths[column] = widths[column].max_with(*measurement);
+        }
+        if self.equal {
+            let widest = widths.iter().fold(Measurement::fixed(0), |widest, width| {
+                widest.max_with(*width)
+            });
+            widths.fill(widest);
+        }
+        widths
+    }
+
+    /// Get the total width of columns of the given widths, gaps included.
+    fn total_width(&self, widths: &[Measurement]) -> usize {
+        let gaps = self
+            .column_gap()
+            .saturating_mul(widths.len().saturating_sub(1));
+        widths
+            .iter()
+            .fold(gaps, |total, width| total.saturating_add(width.maximum()))
+    }
+
+    /// Measure every item.
+    fn measure_items(
+        &self,
+        console: &Console,
+        options: &RenderOptions,
+    ) -> Result<Vec<Measurement>> {
+        let available = options.get_max_width();
+        self.items
+            .iter()
+            .map(|item| {
+                options
+                    .measure_child(item.as_ref(), console, || Measurement::span(available))
+                    .map(|measurement| measurement.clamp(0, available))
+            })
+            .collect()
+    }
+
+    /// Get the natural widths of the columns to lay the items out in: the
+    /// fixed count, or the most columns that fit in `available` cells.
+    fn layout(&self, measurements: &[Measurement], available: usize) -> Vec<Measurement> {
+        let count = self.items.len();
+        if let Some(columns) = self.column_count {
+            return self.column_widths(measurements, columns.min(count));
+        }
+        (1..=count)
+            .rev()
+            .map(|columns| self.column_widths(measurements, columns))
+            .find(|widths| widths.len() == 1 || self.total_width(widths) <= available)
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Renderable + Measurable + 'static> Extend<R> for Columns {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, items: I) {
+        for item in items {
+            self.push(item);
+        }
+    }
+}
+
+impl Renderable for Columns {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        if self.items.is_empty() {
+            return Ok(Vec::new());
+        }
+        let available = options.get_max_width();
+        let measurements = self.measure_items(console, options)?;
+        let natural = self.layout(&measurements, available);
+        let (rows, columns) = self.grid(natural.len());
+
+        // Narrow columns that do not fit in proportion to their width
+        let gaps = self.column_gap().saturating_mul(columns - 1);
+        let specs = vec![WidthSpec::Auto; columns];
+        let widths = resolve_widths(&specs, available.saturating_sub(gaps), &natural);
+
+        let mut cells: Vec<Vec<Cell>> = vec![vec![Vec::new(); columns]; rows];
+        for (index, item) in self.items.iter().enumerate() {
+            let column = self.column_of(index, rows, columns);
+            let row = match self.column_first {
+                true => index % rows,
+                false => index / columns,
+            };
+            let width = widths[column];
+            let lines = match width {
+                0 => vec![Line::new()],
+                _ => {
+                    let inner = render_utils::narrow(options, available.saturating_sub(width));
+                    render_utils::split_lines(inner.render_child(item.as_ref(), console)?)
+                }
+            };
+            cells[row][column] = lines;
+        }
+
+        let gap = render_utils::fill(self.column_gap(), Style::new());
+        let total = widths.iter().sum::<usize>() + gaps;
+        let mut output: Vec<Line> = Vec::new();
+        for (row_index, row) in cells.into_iter().enumerate() {
+            if row_index > 0 {
+                output.extend((0..self.row_gap()).map(|_| {
+                    render_utils::fill(total, Style::new())
+                        .into_iter()
+                        .collect()
+                }));
+            }
+            let height = row.iter().map(Vec::len).max().unwrap_or(0).max(1);
+            let mut row: Vec<_> = row.into_iter().map(Vec::into_iter).collect();
+            for _ in 0..height {
+                let mut line = Line::new();
+                for (column, cell) in row.iter_mut().enumerate() {
+                    if column > 0 {
+                        line.extend(gap.clone());
+                    }
+                    let cell_line = cell.next().unwrap_or_default();
+                    line.extend(render_utils::adjust_line_length(
+                        cell_line,
+                        widths[column],
+                        Style::new(),
+                    ));
+                }
+                output.push(line);
+            }
+        }
+        Ok(render_utils::frame_lines(output))
+    }
+}
+
+impl Measurable for Columns {
+    /// Measure the widest item as the minimum, for a single column, and
+    /// the grid the available width fits as the maximum.
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        if self.items.is_empty() {
+            return Ok(Measurement::fixed(0));
+        }
+        let available = options.get_max_width();
+        let measurements = self.measure_items(console, options)?;
+        let minimum = measurements
+            .iter()
+            .map(Measurement::minimum)
+            .max()
+            .unwrap_or(0);
+        let maximum = self
+            .total_width(&self.layout(&measurements, available))
+            .min(available);
+        Ok(Measurement::new(minimum.min(maximum), maximum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Text, assert_render_eq, render_utils::checked_render, testing::render_lines};
+
+    fn lines(columns: &Columns, width: usize) -> Vec<String> {
+        let options = RenderOptions::new().with_max_width(width);
+        render_lines(&checked_render(columns, &Console::new(), &options).unwrap())
+    }
+
+    #[test]
+    fn test_auto_fit_column_count() {
+        let columns = Columns::from_items(["one", "two", "three", "four", "five"]);
+        assert_render_eq!(lines(&columns, 80), "one two three four five");
+        assert_render_eq!(lines(&columns, 16), "one  two  three\nfour five      ");
+        assert_render_eq!(lines(&columns, 5), "one  \ntwo  \nthree\nfour \nfive ");
+    }
+
+    #[test]
+    fn test_equal_and_column_first() {
+        let columns = Columns::from_items(["a", "bbbb", "c", "d"]).with_equal(true);
+        assert_render_eq!(lines(&columns, 10), "a    bbbb\nc    d   ");
+
+        let columns = Columns::from_items(["1", "2", "3", "4", "5"])
+            .with_column_count(3)
+            .with_column_first(true);
+        assert_render_eq!(lines(&columns, 20), "1 3 5\n2 4  ");
+    }
+
+    #[test]
+    fn test_padding_between_cells_and_rows() {
+        let columns = Columns::from_items(["a", "b", "c"])
+            .with_column_count(2)
+            .with_padding((1, 2));
+        assert_render_eq!(lines(&columns, 20), "a  b\n    \nc   ");
+    }
+
+    #[test]
+    fn test_multi_line_cells_share_row_height() {
+        let stacked = Columns::from_items(["a", "b", "c"]).with_column_count(1);
+        let columns = Columns::new().with_item(stacked).with_item(Text::new("d"));
+        assert_render_eq!(lines(&columns, 20), "a d\nb  \nc  ");
+    }
+
+    #[test]
+    fn test_wide_item_falls_back_to_one_column() {
+        let columns = Columns::from_items(["abcdefgh", "x"]);
+        assert_render_eq!(lines(&columns, 4), "abcd\nefgh\nx   ");
+        // A fixed count too wide for the line narrows the columns
+        let columns = Columns::from_items(["aaaa", "bbbb"]).with_column_count(2);
+        assert_render_eq!(lines(&columns, 7), "aaa bbb\na   b  ");
+    }
+
+    #[test]
+    fn test_empty_renders_nothing() {
+        let columns = Columns::new();
+        let options = RenderOptions::new().with_max_width(20);
+        assert!(
+            columns
+                .render(&Console::new(), &options)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            columns.measure(&Console::new(), &options).unwrap(),
+            Measurement::fixed(0)
+        );
+    }
+
+    #[test]
+    fn test_measure() {
+        let columns = Columns::from_items(["ab", "cdef", "g"]);
+        let options = RenderOptions::new().with_max_width(80);
+        assert_eq!(
+            columns.measure(&Console::new(), &options).unwrap(),
+            Measurement::new(4, 9)
+        );
+        let options = RenderOptions::new().with_max_width(6);
+        assert_eq!(
+            columns.measure(&Console::new(), &options).unwrap(),
+            Measurement::fixed(4)
+        );
+    }
+}
@@ -0,0 +1,543 @@
+//! Diff - inline and line-by-line views of how text changed.
+//!
+//! [`DiffText`] shows the changes between two strings inline, word by word
+//! or character by character: deletions struck through in red, insertions
+//! in green. [`DiffLines`] shows whole lines with `-` and `+` gutters, as
+//! unified diffs do. Both build a [`Text`], so they wrap and nest like any
+//! other content.
+//!
+//! The diff is a longest common subsequence, which takes time and memory
+//! proportional to the product of the two lengths. After skipping the
+//! common prefix and suffix, inputs whose product is above the limit fall
+//! back to a coarser diff: characters to words, words to lines, and lines
+//! to replacing the old text with the new one.
+
+use crate::{
+    Color, Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment,
+    StandardColor, Style, Text,
+};
+use unicode_width::UnicodeWidthChar;
+
+/// The default limit on the work of one diff, in table cells.
+pub const DEFAULT_DIFF_LIMIT: usize = 1 << 20;
+
+/// How a piece of text changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// A token and how it changed.
+type Edit<'a> = (Change, &'a str);
+/// A run of text and how it changed.
+type Run = (Change, String);
+/// Splits text into the units to compare.
+type Tokenizer = fn(&str) -> Vec<&str>;
+
+/// The units [`DiffText`] compares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiffGranularity {
+    /// Words and the whitespace between them.
+    #[default]
+    Word,
+    /// Characters, with combining marks kept on the character before them.
+    Character,
+}
+
+/// An inline diff of two strings.
+///
+/// Unchanged text is drawn in the unchanged style, which is empty by
+/// default. Where text was replaced, the deleted text comes first. With
+/// [`RenderOptions::accessible`] set, changes are also marked in text as
+/// `git diff --word-diff` does: `[-deleted-]` and `{+inserted+}`.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::components::DiffText;
+///
+/// let diff = DiffText::new("retries = 3", "retries = 5");
+/// let text = diff.to_text();
+/// assert_eq!(text.plain(), "retries = 35");
+/// assert_eq!(text.style_at(10).strikethrough, Some(true));
+/// assert_eq!(text.style_at(11).strikethrough, None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffText {
+    old: String,
+    new: String,
+    granularity: DiffGranularity,
+    limit: usize,
+    styles: DiffStyles,
+}
+
+/// The styles of the parts of a diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiffStyles {
+    deleted: Style,
+    inserted: Style,
+    unchanged: Style,
+}
+
+impl Default for DiffStyles {
+    fn default() -> Self {
+        Self {
+            deleted: Style::new()
+                .with_color(Color::Standard(StandardColor::Red))
+                .strikethrough(),
+            inserted: Style::new().with_color(Color::Standard(StandardColor::Green)),
+            unchanged: Style::new(),
+        }
+    }
+}
+
+impl DiffStyles {
+    fn style(&self, change: Change) -> &Style {
+        match change {
+            Change::Equal => &self.unchanged,
+            Change::Delete => &self.deleted,
+            Change::Insert => &self.inserted,
+        }
+    }
+}
+
+impl DiffText {
+    /// Create a word diff from `old` to `new`.
+    pub fn new(old: impl Into<String>, new: impl Into<String>) -> Self {
+        Self {
+            old: old.into(),
+            new: new.into(),
+            granularity: DiffGranularity::default(),
+            limit: DEFAULT_DIFF_LIMIT,
+            styles: DiffStyles::default(),
+        }
+    }
+
+    /// Set the units compared.
+    pub fn with_granularity(mut self, granularity: DiffGranularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    /// Set the limit on the work of the diff, in table cells: the product
+    /// of the numbers of units left after the common prefix and suffix.
+    /// Above it, the diff falls back to coarser units.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set the style of deleted text.
+    pub fn with_deleted_style(mut self, style: Style) -> Self {
+        self.styles.deleted = style;
+        self
+    }
+
+    /// Set the style of inserted text.
+    pub fn with_inserted_style(mut self, style: Style) -> Self {
+        self.styles.inserted = style;
+        self
+    }
+
+    /// Set the style of unchanged text, for example dim to make the
+    /// changes stand out.
+    pub fn with_unchanged_style(mut self, style: Style) -> Self {
+        self.styles.unchanged = style;
+        self
+    }
+
+    /// Build the diff as styled text.
+    pub fn to_text(&self) -> Text {
+        self.build(false)
+    }
+
+    /// Build the diff as styled text, with changes between textual
+    /// markers if `markers` is set.
+    fn build(&self, markers: bool) -> Text {
+        let tokenizers: &[Tokenizer] = match self.granularity {
+            DiffGranularity::Character => &[characters, words, lines],
+            DiffGranularity::Word => &[words, lines],
+        };
+        let edits = tokenizers
+            .iter()
+            .find_map(|tokenize| diff(&tokenize(&self.old), &tokenize(&self.new), self.limit))
+            .unwrap_or_else(|| replace(&self.old, &self.new));
+
+        let mut text = Text::new("");
+        for (change, run) in group(edits) {
+            let run = match (change, markers) {
+                (Change::Delete, true) => format!("[-{}-]", run),
+                (Change::Insert, true) => format!("{{+{}+}}", run),
+                _ => run,
+            };
+            text.append_text(Text::new(&run).with_style(self.styles.style(change).clone()));
+        }
+        text
+    }
+}
+
+impl Renderable for DiffText {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        self.build(options.accessible).render(console, options)
+    }
+}
+
+impl Measurable for DiffText {
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        self.build(options.accessible).measure(console, options)
+    }
+}
+
+/// A line-by-line diff with `-`, `+` and blank gutters.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::components::DiffLines;
+///
+/// let diff = DiffLines::new("a\nb\nc", "a\nB\nc");
+/// assert_eq!(diff.to_text().plain(), "  a\n- b\n+ B\n  c");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLines {
+    old: String,
+    new: String,
+    limit: usize,
+    styles: DiffStyles,
+}
+
+impl DiffLines {
+    /// Create a line diff from `old` to `new`.
+    pub fn new(old: impl Into<String>, new: impl Into<String>) -> Self {
+        Self {
+            old: old.into(),
+            new: new.into(),
+            limit: DEFAULT_DIFF_LIMIT,
+            styles: DiffStyles {
+                deleted: Style::new().with_color(Color::Standard(StandardColor::Red)),
+                ..DiffStyles::default()
+            },
+        }
+    }
+
+    /// Set the limit on the work of the diff, in table cells: the product
+    /// of the numbers of lines left after the common prefix and suffix.
+    /// Above it, every old line is shown deleted and every new one
+    /// inserted.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set the style of deleted lines, including their gutter.
+    pub fn with_deleted_style(mut self, style: Style) -> Self {
+        self.styles.deleted = style;
+        self
+    }
+
+    /// Set the style of inserted lines, including their gutter.
+    pub fn with_inserted_style(mut self, style: Style) -> Self {
+        self.styles.inserted = style;
+        self
+    }
+
+    /// Set the style of unchanged lines.
+    pub fn with_unchanged_style(mut self, style: Style) -> Self {
+        self.styles.unchanged = style;
+        self
+    }
+
+    /// Build the diff as styled text, one line per line of either input.
+    pub fn to_text(&self) -> Text {
+        let old: Vec<&str> = self.old.lines().collect();
+        let new: Vec<&str> = self.new.lines().collect();
+        let edits = diff(&old, &new, self.limit).unwrap_or_else(|| {
+            old.iter()
+                .map(|line| (Change::Delete, *line))
+                .chain(new.iter().map(|line| (Change::Insert, *line)))
+                .collect()
+        });
+
+        let mut text = Text::new("");
+        for (index, (change, line)) in ordered(edits).into_iter().enumerate() {
+            if index > 0 {
+                text.append("\n");
+            }
+            let gutter = match change {
+                Change::Equal => "  ",
+                Change::Delete => "- ",
+                Change::Insert => "+ ",
+            };
+            let line = format!("{}{}", gutter, line);
+            text.append_text(Text::new(&line).with_style(self.styles.style(change).clone()));
+        }
+        text
+    }
+}
+
+impl Renderable for DiffLines {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        self.to_text().render(console, options)
+    }
+}
+
+impl Measurable for DiffLines {
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        self.to_text().measure(console, options)
+    }
+}
+
+/// Split `text` into words and the runs of whitespace between them.
+fn words(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut in_space = None;
+    for (index, ch) in text.char_indices() {
+        let space = ch.is_whitespace();
+        if in_space.is_some_and(|in_space| in_space != space) {
+            tokens.push(&text[start..index]);
+            start = index;
+        }
+        in_space = Some(space);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// Split `text` into characters, each with the zero-width characters after
+/// it.
+fn characters(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    for (index, ch) in text.char_indices().skip(1) {
+        if ch.width() != Some(0) || ch == '\n' {
+            tokens.push(&text[start..index]);
+            start = index;
+        }
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// Split `text` into lines, each with its newline.
+fn lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// Diff as replacing all of `old` with all of `new`.
+fn replace<'a>(old: &'a str, new: &'a str) -> Vec<Edit<'a>> {
+    vec![(Change::Delete, old), (Change::Insert, new)]
+}
+
+/// Diff two lists of tokens, or `None` if that takes more than `limit`
+/// cells of work.
+fn diff<'a>(old: &[&'a str], new: &[&'a str], limit: usize) -> Option<Vec<Edit<'a>>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let (old_rest, new_rest) = (&old[prefix..], &new[prefix..]);
+    let suffix = old_rest
+        .iter()
+        .rev()
+        .zip(new_rest.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old_rest[..old_rest.len() - suffix];
+    let new_middle = &new_rest[..new_rest.len() - suffix];
+    let cells = old_middle.len().saturating_mul(new_middle.len());
+    if cells > limit {
+        return None;
+    }
+
+    let mut edits = Vec::with_capacity(old.len() + new_middle.len());
+    edits.extend(old[..prefix].iter().map(|token| (Change::Equal, *token)));
+    edits.extend(longest_common_subsequence(old_middle, new_middle));
+    edits.extend(
+        old_rest[old_rest.len() - suffix..]
+            .iter()
+            .map(|token| (Change::Equal, *token)),
+    );
+    Some(edits)
+}
+
+/// Diff two lists of tokens by their longest common subsequence.
+fn longest_common_subsequence<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Edit<'a>> {
+    let width = new.len() + 1;
+    // lengths[i * width + j] is the length of the LCS of old[i..] and new[j..]
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            edits.push((Change::Equal, old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            edits.push((Change::Delete, old[i]));
+            i += 1;
+        } else {
+            edits.push((Change::Insert, new[j]));
+            j += 1;
+        }
+    }
+    edits.extend(old[i..].iter().map(|token| (Change::Delete, *token)));
+    edits.extend(new[j..].iter().map(|token| (Change::Insert, *token)));
+    edits
+}
+
+/// Reorder `edits` so each run of changes lists its deletions before its
+/// insertions.
+fn ordered(edits: Vec<Edit<'_>>) -> Vec<Edit<'_>> {
+    let mut output = Vec::with_capacity(edits.len());
+    let mut inserted = Vec::new();
+    for (change, token) in edits {
+        match change {
+            Change::Delete => output.push((change, token)),
+            Change::Insert => inserted.push((change, token)),
+            Change::Equal => {
+                output.append(&mut inserted);
+                output.push((change, token));
+            }
+        }
+    }
+    output.append(&mut inserted);
+    output
+}
+
+/// Join adjacent tokens with the same change, deletions first.
+fn group(edits: Vec<Edit<'_>>) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    for (change, token) in ordered(edits) {
+        match runs.last_mut() {
+            Some((last, run)) if *last == change => run.push_str(token),
+            _ if token.is_empty() => {}
+            _ => runs.push((change, token.to_string())),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{markup::Span, testing::render_lines};
+    use std::time::{Duration, Instant};
+
+    fn red() -> Style {
+        Style::new().with_color(Color::Standard(StandardColor::Red))
+    }
+
+    fn green() -> Style {
+        Style::new().with_color(Color::Standard(StandardColor::Green))
+    }
+
+    #[test]
+    fn test_word_diff_spans() {
+        let text = DiffText::new("the quick brown fox", "the slow brown dog").to_text();
+        assert_eq!(text.plain(), "the quickslow brown foxdog");
+        assert_eq!(
+            text.spans(),
+            [
+                Span::new(4, 9, red().strikethrough()),
+                Span::new(9, 13, green()),
+                Span::new(20, 23, red().strikethrough()),
+                Span::new(23, 26, green()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_character_diff_keeps_combining_marks() {
+        let dim = Style::new().dim();
+        let text = DiffText::new("cafe\u{301} bar", "cafe baz")
+            .with_granularity(DiffGranularity::Character)
+            .with_unchanged_style(dim.clone())
+            .to_text();
+        assert_eq!(text.plain(), "cafe\u{301}e barz");
+        assert_eq!(
+            text.spans(),
+            [
+                Span::new(0, 3, dim.clone()),
+                Span::new(3, 5, red().strikethrough()),
+                Span::new(5, 6, green()),
+                Span::new(6, 9, dim),
+                Span::new(9, 10, red().strikethrough()),
+                Span::new(10, 11, green()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_accessible_marks_changes() {
+        let diff = DiffText::new("retries = 3", "retries = 5");
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(40);
+        let plain = |options: &RenderOptions| {
+            render_lines(&diff.render(&console, options).unwrap()).join("\n")
+        };
+        assert_eq!(plain(&options), "retries = 35");
+        let accessible = options.with_accessible(true);
+        assert_eq!(plain(&accessible), "retries = [-3-]{+5+}");
+        let segments = diff.render(&console, &accessible).unwrap();
+        let deleted = segments.iter().find(|s| s.text() == "[-3-]").unwrap();
+        assert_eq!(deleted.style().strikethrough, Some(true));
+        assert_eq!(diff.measure(&console, &accessible).unwrap().maximum(), 20);
+    }
+
+    #[test]
+    fn test_line_diff_gutters() {
+        let diff = DiffLines::new("one\ntwo\nthree\n", "one\n2\nthree\nfour\n");
+        let text = diff.to_text();
+        crate::assert_render_eq!(
+            render_lines(&text.render(&Console::new(), &RenderOptions::new()).unwrap()),
+            "  one\n- two\n+ 2\n  three\n+ four"
+        );
+        assert_eq!(text.style_at(6), red());
+        assert_eq!(text.style_at(12), green());
+        assert_eq!(text.style_at(0), Style::new());
+    }
+
+    #[test]
+    fn test_limit_falls_back_without_hanging() {
+        let old: String = (0..20_000).map(|n| format!("w{} ", n)).collect();
+        let new: String = (0..20_000)
+            .map(|n| format!("w{} ", n * 7 % 20_000))
+            .collect();
+        let started = Instant::now();
+
+        // Too many words and a single line, so the whole text is replaced
+        let text = DiffText::new(old.as_str(), new.as_str()).to_text();
+        assert_eq!(text.plain(), format!("{}{}", old, new));
+        assert_eq!(text.spans().len(), 2);
+
+        // Lines get a line-level fallback before replacement
+        let old_lines = old.replace(' ', "\n");
+        let new_lines = new.replace(' ', "\n");
+        let text = DiffText::new(old_lines.as_str(), new_lines.as_str())
+            .with_granularity(DiffGranularity::Character)
+            .to_text();
+        assert_eq!(text.plain().len(), old_lines.len() + new_lines.len());
+        let lines = DiffLines::new(old_lines.as_str(), new_lines.as_str()).to_text();
+        assert_eq!(lines.plain().lines().count(), 40_000);
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        // Lowering the limit replaces what would be diffed by words
+        let small = DiffText::new("a b c d", "a c d b");
+        assert_eq!(small.to_text().plain(), "a b c d b");
+        assert_eq!(small.with_limit(4).to_text().plain(), "a b c da c d b");
+    }
+}
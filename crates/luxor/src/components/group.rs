@@ -0,0 +1,250 @@
+//! Group - renderables one after another, as a single renderable.
+//!
+//! A [`Group`] renders each of its items on the lines after the previous
+//! one, so compound output can be printed at once or put inside a
+//! container such as a [`Panel`](crate::components::Panel):
+//!
+//! ```rust
+//! use luxor::{Console, RenderOptions, Renderable, Text, components::{Group, Panel}};
+//!
+//! let group = Group::new().with_item("Status").with_item(Text::new("ready"));
+//! let panel = Panel::fit(group);
+//! let options = RenderOptions::new().with_max_width(40);
+//! let segments = panel.render(&Console::new(), &options).unwrap();
+//! let plain: String = segments.iter().map(|s| s.text()).collect();
+//! assert_eq!(plain, "╭────────╮\n│ Status │\n│ ready  │\n╰────────╯");
+//! ```
+
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment,
+    protocol::RenderableMeasurable, render_utils,
+};
+
+/// An item of a [`Group`].
+type Item = Box<dyn RenderableMeasurable>;
+
+/// Renderables stacked vertically.
+///
+/// Each item starts on a new line: a newline segment goes between the
+/// output of one item and the next, and none after the last. By default
+/// every item is rendered at the whole available width; with
+/// [`Group::with_fit`] each is rendered at its own measured width
+/// instead. An error rendering any item is returned as the error of the
+/// group. No items render nothing.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, components::Group};
+///
+/// let group = Group::from_items(["one", "two"]);
+/// let options = RenderOptions::new().with_max_width(40);
+/// let segments = group.render(&Console::new(), &options).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, "one\ntwo");
+/// ```
+pub struct Group {
+    items: Vec<Item>,
+    fit: bool,
+}
+
+impl Group {
+    /// Create an empty group.
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            fit: false,
+        }
+    }
+
+    /// Create a group of `items`, in order.
+    pub fn from_items<R>(items: impl IntoIterator<Item = R>) -> Self
+    where
+        R: Renderable + Measurable + 'static,
+    {
+        let mut group = Self::new();
+        group.extend(items);
+        group
+    }
+
+    /// Add an item after the last one.
+    pub fn push(&mut self, item: impl Renderable + Measurable + 'static) {
+        self.items.push(Box::new(item));
+    }
+
+    /// Add an item after the last one.
+    pub fn with_item(mut self, item: impl Renderable + Measurable + 'static) -> Self {
+        self.push(item);
+        self
+    }
+
+    /// Render each item at its measured width rather than the whole
+    /// available width.
+    pub fn with_fit(mut self, fit: bool) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Get the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check whether there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Renderable + Measurable + 'static> Extend<R> for Group {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, items: I) {
+        for item in items {
+            self.push(item);
+        }
+    }
+}
+
+impl Renderable for Group {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let available = options.get_max_width();
+        let mut output = Vec::new();
+        for (index, item) in self.items.iter().enumerate() {
+            if index > 0 {
+                output.push(Segment::line());
+            }
+            let width = match self.fit {
+                true => options
+                    .measure_child(item.as_ref(), console, || Measurement::span(available))?
+                    .maximum()
+                    .min(available),
+                false => available,
+            };
+            if width == 0 {
+                continue;
+            }
+            let inner = render_utils::narrow(options, available - width);
+            output.extend(inner.render_child(item.as_ref(), console)?);
+        }
+        Ok(output)
+    }
+}
+
+impl Measurable for Group {
+    /// Measure the widest item, taking the largest minimum and maximum of
+    /// the items.
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        let available = options.get_max_width();
+        self.items
+            .iter()
+            .try_fold(Measurement::fixed(0), |widest, item| {
+                let measurement = options
+                    .measure_child(item.as_ref(), console, || Measurement::span(available))?;
+                Ok(widest.max_with(measurement.clamp(0, available)))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        LuxorError, Style, Text, assert_render_eq, render_utils::checked_render,
+        testing::render_lines,
+    };
+
+    fn lines(group: &Group, width: usize) -> Vec<String> {
+        let options = RenderOptions::new().with_max_width(width);
+        render_lines(&group.render(&Console::new(), &options).unwrap())
+    }
+
+    struct Failing;
+
+    impl Renderable for Failing {
+        fn render(&self, _console: &Console, _options: &RenderOptions) -> Result<Vec<Segment>> {
+            Err(LuxorError::rendering("broken"))
+        }
+    }
+
+    impl Measurable for Failing {
+        fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+            Ok(Measurement::fixed(3))
+        }
+    }
+
+    /// Fills the width it is given, but measures `self.0` wide.
+    struct Rule(usize);
+
+    impl Renderable for Rule {
+        fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+            let line = "-".repeat(options.get_max_width());
+            Ok(vec![Segment::new(line, Style::new())])
+        }
+    }
+
+    impl Measurable for Rule {
+        fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+            Ok(Measurement::fixed(self.0))
+        }
+    }
+
+    #[test]
+    fn test_nested_groups_keep_order() {
+        let inner = Group::new()
+            .with_item(Text::new("b").with_style(Style::new().bold()))
+            .with_item("c");
+        let group = Group::new().with_item("a").with_item(inner).with_item("d");
+        let options = RenderOptions::new().with_max_width(10);
+        let segments = group.render(&Console::new(), &options).unwrap();
+        let texts: Vec<&str> = segments.iter().map(|s| s.text()).collect();
+        assert_eq!(texts, ["a", "\n", "b", "\n", "c", "\n", "d"]);
+        assert_eq!(segments[2].style().bold, Some(true));
+        assert!(
+            Group::new()
+                .render(&Console::new(), &options)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_fit_renders_items_at_measured_width() {
+        let group = Group::new()
+            .with_item(Rule(3))
+            .with_item("abcdefghijk")
+            .with_item(Rule(20));
+        assert_render_eq!(lines(&group, 8), "--------\nabcdefgh\nijk\n--------");
+        let group = group.with_fit(true);
+        assert_render_eq!(lines(&group, 8), "---\nabcdefgh\nijk\n--------");
+    }
+
+    #[test]
+    fn test_measure_widest_item() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(20);
+        let group = Group::from_items(["ab cd", "abcdef", "a b c d e f g h i j k l m n"]);
+        assert_eq!(
+            group.measure(&console, &options).unwrap(),
+            Measurement::new(6, 20)
+        );
+        assert_eq!(
+            Group::new().measure(&console, &options).unwrap(),
+            Measurement::fixed(0)
+        );
+        checked_render(&group, &console, &options).unwrap();
+    }
+
+    #[test]
+    fn test_failing_item_fails_group() {
+        let inner = Group::new().with_item("ok").with_item(Failing);
+        let group = Group::new().with_item("first").with_item(inner);
+        let options = RenderOptions::new().with_max_width(10);
+        let error = group.render(&Console::new(), &options).unwrap_err();
+        assert!(error.to_string().contains("broken"), "{error}");
+    }
+}
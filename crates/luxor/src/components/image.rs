@@ -0,0 +1,420 @@
+//! Image - pixel art and logos drawn with half-block characters.
+//!
+//! Luxor does not decode image files: callers bring decoded RGB or RGBA
+//! pixels, or a function sampling them. Each cell shows two pixels, the top
+//! one as the foreground of `▀` and the bottom one as its background, so an
+//! image of `w` by `h` pixels takes `w` columns and `h / 2` lines, rounded
+//! up. Larger images are scaled down with nearest-neighbor sampling.
+//!
+//! ```rust
+//! use luxor::{Color, ColorSystem, Console, ConsoleOptions, RenderOptions, Renderable};
+//! use luxor::components::Image;
+//!
+//! // Red above blue, one cell
+//! let image = Image::from_rgb(1, 2, vec![255, 0, 0, 0, 0, 255]).unwrap();
+//! let console = Console::with_options(ConsoleOptions::new().with_color_system(ColorSystem::TrueColor));
+//! let options = RenderOptions::new().with_max_width(80).with_ascii_only(false);
+//! let segments = image.render(&console, &options).unwrap();
+//! assert_eq!(segments[0].text(), "▀");
+//! assert_eq!(segments[0].style().color, Some(Color::rgb(255, 0, 0)));
+//! assert_eq!(segments[0].style().background, Some(Color::rgb(0, 0, 255)));
+//! ```
+
+use crate::{
+    Color, ColorSystem, Console, LuxorError, Measurable, Measurement, RenderOptions, Renderable,
+    Result, Segment, Style,
+    diagnostics::{self, Warning},
+    render_utils,
+    rendered::Line,
+};
+
+/// Characters of increasing brightness drawn in ASCII-only mode.
+const ASCII_RAMP: &[u8] = b" .:-=+*#%@";
+
+/// A 4x4 Bayer matrix, the thresholds of the ordered dither.
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Spread of the dither in each channel, about one step of the 256-color
+/// palette's color cube.
+const DITHER_SPREAD: f64 = 40.0;
+
+/// A pixel as red, green, blue and alpha.
+type Rgba = [u8; 4];
+
+/// An image drawn with half-block characters.
+///
+/// The image is drawn at its own size, one column per pixel, unless that
+/// is wider than the available width or taller than the maximum height;
+/// then it is scaled down, keeping its aspect ratio. Pixels with an alpha
+/// below the threshold are transparent and show the terminal's default
+/// background.
+///
+/// When the console has 256 colors, pixels are dithered to the palette so
+/// gradients do not band. With [`RenderOptions::ascii_only`], the image is
+/// drawn in shades of ASCII characters by brightness, without color.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, Measurable, RenderOptions, components::Image};
+///
+/// let image = Image::from_fn(40, 20, |x, y| [x as u8 * 6, y as u8 * 12, 128, 255]);
+/// let options = RenderOptions::new().with_max_width(20);
+/// assert_eq!(image.size(&options), (20, 5));
+/// assert_eq!(image.measure(&Console::new(), &options).unwrap().maximum(), 20);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgba>,
+    max_height: Option<usize>,
+    alpha_threshold: u8,
+    dither: bool,
+}
+
+impl Image {
+    /// Create an image from rows of RGB pixels, three bytes each.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pixels` does not hold `width * height` pixels.
+    pub fn from_rgb(width: usize, height: usize, pixels: Vec<u8>) -> Result<Self> {
+        Self::from_bytes(width, height, &pixels, 3)
+    }
+
+    /// Create an image from rows of RGBA pixels, four bytes each.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pixels` does not hold `width * height` pixels.
+    pub fn from_rgba(width: usize, height: usize, pixels: Vec<u8>) -> Result<Self> {
+        Self::from_bytes(width, height, &pixels, 4)
+    }
+
+    /// Create an image by sampling `pixel(x, y)`, which returns RGBA, for
+    /// each pixel.
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut pixel: impl FnMut(usize, usize) -> [u8; 4],
+    ) -> Self {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| pixel(x, y))
+            .collect();
+        Self::with_pixels(width, height, pixels)
+    }
+
+    fn from_bytes(width: usize, height: usize, bytes: &[u8], channels: usize) -> Result<Self> {
+        let expected = width.saturating_mul(height).saturating_mul(channels);
+        if bytes.len() != expected {
+            return Err(LuxorError::rendering(format!(
+                "a {}x{} image needs {} bytes of pixels, got {}",
+                width,
+                height,
+                expected,
+                bytes.len()
+            )));
+        }
+        let pixels = bytes
+            .chunks_exact(channels)
+            .map(|pixel| {
+                [
+                    pixel[0],
+                    pixel[1],
+                    pixel[2],
+                    pixel.get(3).copied().unwrap_or(255),
+                ]
+            })
+            .collect();
+        Ok(Self::with_pixels(width, height, pixels))
+    }
+
+    fn with_pixels(width: usize, height: usize, pixels: Vec<Rgba>) -> Self {
+        Self {
+            width,
+            height,
+            pixels,
+            max_height: None,
+            alpha_threshold: 128,
+            dither: true,
+        }
+    }
+
+    /// Set the maximum height in lines, each two rows of pixels.
+    pub fn with_max_height(mut self, lines: usize) -> Self {
+        self.max_height = Some(lines);
+        self
+    }
+
+    /// Set the alpha below which pixels are transparent. The default is
+    /// 128; 0 makes every pixel opaque.
+    pub fn with_alpha_threshold(mut self, threshold: u8) -> Self {
+        self.alpha_threshold = threshold;
+        self
+    }
+
+    /// Enable or disable dithering on 256-color consoles. It is enabled by
+    /// default.
+    pub fn with_dither(mut self, dither: bool) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    /// Get the size in pixels.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Get the size drawn with `options`, in columns and lines.
+    pub fn size(&self, options: &RenderOptions) -> (usize, usize) {
+        let (width, height) = self.scaled(options);
+        (width, (height + 1) / 2)
+    }
+
+    /// Get the size in pixels after scaling down to fit `options`.
+    fn scaled(&self, options: &RenderOptions) -> (usize, usize) {
+        if self.width == 0 || self.height == 0 {
+            return (0, 0);
+        }
+        let max_width = options.get_max_width();
+        let max_height = self
+            .max_height
+            .map_or(usize::MAX, |lines| lines.saturating_mul(2));
+        if self.width <= max_width && self.height <= max_height {
+            return (self.width, self.height);
+        }
+        let scale =
+            (max_width as f64 / self.width as f64).min(max_height as f64 / self.height as f64);
+        let fit = |size: usize, max: usize| ((size as f64 * scale) as usize).clamp(1, max.max(1));
+        (fit(self.width, max_width), fit(self.height, max_height))
+    }
+
+    /// Get the pixel drawn at `(x, y)` of an image scaled to `width` by
+    /// `height`, or `None` if it is transparent.
+    fn sample(&self, x: usize, y: usize, width: usize, height: usize) -> Option<Rgba> {
+        if y >= height {
+            return None;
+        }
+        let source = self.pixels[y * self.height / height * self.width + x * self.width / width];
+        (source[3] >= self.alpha_threshold).then_some(source)
+    }
+
+    /// Get the color of a pixel drawn at `(x, y)` for `color_system`.
+    fn color(&self, pixel: Rgba, x: usize, y: usize, color_system: ColorSystem) -> Color {
+        let [r, g, b, _] = pixel;
+        if !(self.dither && color_system == ColorSystem::EightBit) {
+            return Color::rgb(r, g, b);
+        }
+        let threshold = (f64::from(BAYER[y % 4][x % 4]) + 0.5) / 16.0 - 0.5;
+        let dither = |channel: u8| {
+            (f64::from(channel) + threshold * DITHER_SPREAD)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+        Color::EightBit(Color::rgb_to_eight_bit(dither(r), dither(g), dither(b)))
+    }
+
+    /// Draw the cell showing the pixels `top` and `bottom` in color.
+    fn block(
+        &self,
+        top: Option<Rgba>,
+        bottom: Option<Rgba>,
+        x: usize,
+        y: usize,
+        color_system: ColorSystem,
+    ) -> Segment {
+        let color = |pixel, y| self.color(pixel, x, y, color_system);
+        match (top, bottom) {
+            (Some(top), Some(bottom)) => Segment::new(
+                "▀",
+                Style::new()
+                    .with_color(color(top, y))
+                    .with_background(color(bottom, y + 1)),
+            ),
+            (Some(top), None) => Segment::new("▀", Style::new().with_color(color(top, y))),
+            (None, Some(bottom)) => {
+                Segment::new("▄", Style::new().with_color(color(bottom, y + 1)))
+            }
+            (None, None) => Segment::new(" ", Style::default()),
+        }
+    }
+
+    /// Draw the cell showing the pixels `top` and `bottom` as a shade of
+    /// ASCII.
+    fn shade(top: Option<Rgba>, bottom: Option<Rgba>) -> Segment {
+        let lit: Vec<f64> = [top, bottom]
+            .into_iter()
+            .flatten()
+            .map(|[r, g, b, _]| Color::rgb(r, g, b).luminance())
+            .collect();
+        if lit.is_empty() {
+            return Segment::new(" ", Style::default());
+        }
+        let luminance = lit.iter().sum::<f64>() / lit.len() as f64;
+        let index = (luminance * (ASCII_RAMP.len() - 1) as f64).round() as usize;
+        let ch = char::from(ASCII_RAMP[index.min(ASCII_RAMP.len() - 1)]);
+        Segment::new(ch.to_string(), Style::default())
+    }
+}
+
+impl Renderable for Image {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let (width, height) = self.scaled(options);
+        if width == 0 || height == 0 {
+            return Ok(Vec::new());
+        }
+        if options.ascii_only {
+            diagnostics::warn(|| Warning::AsciiFallback { component: "Image" });
+        }
+        let color_system = console.color_system();
+        let lines: Vec<Line> = (0..height)
+            .step_by(2)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        let top = self.sample(x, y, width, height);
+                        let bottom = self.sample(x, y + 1, width, height);
+                        if options.ascii_only {
+                            Self::shade(top, bottom)
+                        } else {
+                            self.block(top, bottom, x, y, color_system)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(render_utils::frame_lines(lines))
+    }
+}
+
+impl Measurable for Image {
+    fn measure(&self, _console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        Ok(Measurement::fixed(self.size(options).0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConsoleOptions, testing::render_lines};
+
+    const RED: Rgba = [255, 0, 0, 255];
+    const GREEN: Rgba = [0, 255, 0, 255];
+    const BLUE: Rgba = [0, 0, 255, 255];
+    const CLEAR: Rgba = [0, 0, 0, 0];
+
+    /// Four quadrants, red, green, blue and transparent, two pixels each.
+    fn quadrants() -> Image {
+        Image::from_fn(4, 4, |x, y| match (x < 2, y < 2) {
+            (true, true) => RED,
+            (false, true) => GREEN,
+            (true, false) => BLUE,
+            (false, false) => CLEAR,
+        })
+    }
+
+    /// A cell's text, foreground and background.
+    type Cell = (String, Option<Color>, Option<Color>);
+
+    fn cells(segments: &[Segment]) -> Vec<Cell> {
+        segments
+            .iter()
+            .map(|segment| {
+                let style = segment.style();
+                (segment.text().to_string(), style.color, style.background)
+            })
+            .collect()
+    }
+
+    fn rgb([r, g, b, _]: Rgba) -> Option<Color> {
+        Some(Color::rgb(r, g, b))
+    }
+
+    #[test]
+    fn test_image_half_blocks_full_size_and_downscaled() {
+        let console =
+            Console::with_options(ConsoleOptions::new().with_color_system(ColorSystem::TrueColor));
+        let options = RenderOptions::new()
+            .with_max_width(80)
+            .with_ascii_only(false);
+        let segments = quadrants().render(&console, &options).unwrap();
+        crate::assert_render_eq!(render_lines(&segments), "▀▀▀▀\n▀▀  ");
+        let red = ("▀".to_string(), rgb(RED), rgb(RED));
+        let green = ("▀".to_string(), rgb(GREEN), rgb(GREEN));
+        let blue = ("▀".to_string(), rgb(BLUE), rgb(BLUE));
+        let clear = (" ".to_string(), None, None);
+        let newline = ("\n".to_string(), None, None);
+        assert_eq!(
+            cells(&segments),
+            [
+                red.clone(),
+                red.clone(),
+                green.clone(),
+                green.clone(),
+                newline,
+                blue.clone(),
+                blue,
+                clear.clone(),
+                clear.clone(),
+            ]
+        );
+
+        // Half the size: one pixel per quadrant, so one line of two cells
+        let options = options.with_max_width(2);
+        let segments = quadrants().render(&console, &options).unwrap();
+        assert_eq!(
+            cells(&segments),
+            [
+                ("▀".to_string(), rgb(RED), rgb(BLUE)),
+                ("▀".to_string(), rgb(GREEN), None),
+            ]
+        );
+        assert_eq!(quadrants().size(&options), (2, 1));
+        let measurement = quadrants().measure(&console, &options).unwrap();
+        assert_eq!(measurement.maximum(), 2);
+    }
+
+    #[test]
+    fn test_image_dither_ascii_and_errors() {
+        let gray = Image::from_fn(4, 2, |_, _| [120, 120, 120, 255]);
+        let eight_bit =
+            Console::with_options(ConsoleOptions::new().with_color_system(ColorSystem::EightBit));
+        let options = RenderOptions::new()
+            .with_max_width(80)
+            .with_ascii_only(false);
+        let colors: Vec<Option<Color>> = gray
+            .render(&eight_bit, &options)
+            .unwrap()
+            .iter()
+            .map(|segment| segment.style().color)
+            .collect();
+        assert!(
+            colors
+                .iter()
+                .all(|color| matches!(color, Some(Color::EightBit(_))))
+        );
+        assert!(colors.windows(2).any(|pair| pair[0] != pair[1]));
+        let flat = gray
+            .clone()
+            .with_dither(false)
+            .render(&eight_bit, &options)
+            .unwrap();
+        assert_eq!(flat[0].style().color, Some(Color::rgb(120, 120, 120)));
+
+        let ascii = options.clone().with_ascii_only(true);
+        let segments = quadrants().render(&eight_bit, &ascii).unwrap();
+        crate::assert_render_eq!(render_lines(&segments), "::**\n..  ");
+
+        assert!(Image::from_rgb(2, 2, vec![0; 11]).is_err());
+        assert!(Image::from_rgba(2, 2, vec![0; 16]).is_ok());
+        assert!(
+            Image::from_rgb(0, 0, Vec::new())
+                .unwrap()
+                .render(&eight_bit, &options)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! LineLayout - a left and a right part sharing one line.
+//!
+//! Lines such as `name ....... 42 MB`, or a message with a right-aligned
+//! timestamp, need the width of both parts before the gap between them is
+//! known. [`LineLayout`] measures both, truncates one if they collide, and
+//! fills the gap.
+
+use crate::{
+    Console, EllipsisConfig, Measurable, Measurement, RenderOptions, Renderable, Result, Segment,
+    Style, Text, render_utils, rendered::Line,
+};
+use std::fmt;
+use unicode_width::UnicodeWidthChar;
+
+/// One side of a [`LineLayout`].
+type Part = Box<dyn Renderable>;
+
+/// Which part of a [`LineLayout`] keeps its width when both do not fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LinePriority {
+    /// The left part is kept; the right part is truncated.
+    Left,
+    /// The right part is kept; the left part is truncated.
+    #[default]
+    Right,
+}
+
+/// A single line with a left part, a right part and a filled gap between.
+///
+/// Each part is rendered without wrapping and only its first line is used.
+/// When the parts and the minimum gap do not fit, the part without
+/// [`LinePriority`] is truncated with the console's ellipsis. If the
+/// priority part alone is wider than the line, it is truncated too and the
+/// other part is dropped. The line is always exactly the layout width, or
+/// the console width if that is smaller.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, Style, components::LineLayout};
+///
+/// let console = Console::new();
+/// let options = RenderOptions::new().with_max_width(80);
+/// let line = LineLayout::new(20)
+///     .with_left("report.pdf ")
+///     .with_right(" 42 MB")
+///     .with_fill('.', Style::new().dim());
+/// let plain: String = line
+///     .render(&console, &options)
+///     .unwrap()
+///     .iter()
+///     .map(|s| s.text())
+///     .collect();
+/// assert_eq!(plain, "report.pdf ... 42 MB");
+/// ```
+pub struct LineLayout {
+    width: usize,
+    left: Option<Part>,
+    right: Option<Part>,
+    fill: char,
+    fill_style: Style,
+    priority: LinePriority,
+    min_gap: usize,
+    ellipsis: Option<EllipsisConfig>,
+}
+
+impl LineLayout {
+    /// Create an empty line of `width` cells, with a space-filled gap of
+    /// at least one cell and the right part taking priority.
+    pub fn new(width: usize) -> Self {
+        Self {
+            width,
+            left: None,
+            right: None,
+            fill: ' ',
+            fill_style: Style::default(),
+            priority: LinePriority::default(),
+            min_gap: 1,
+            ellipsis: None,
+        }
+    }
+
+    /// Set the left-aligned part.
+    pub fn with_left(mut self, left: impl Renderable + 'static) -> Self {
+        self.left = Some(Box::new(left));
+        self
+    }
+
+    /// Set the right-aligned part.
+    pub fn with_right(mut self, right: impl Renderable + 'static) -> Self {
+        self.right = Some(Box::new(right));
+        self
+    }
+
+    /// Fill the gap with `fill` in `style` instead of unstyled spaces.
+    ///
+    /// If a double-width fill character does not divide the gap evenly,
+    /// the last cell is a space in the same style.
+    pub fn with_fill(mut self, fill: char, style: Style) -> Self {
+        self.fill = fill;
+        self.fill_style = style;
+        self
+    }
+
+    /// Set which part keeps its width when both do not fit.
+    pub fn with_priority(mut self, priority: LinePriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set the smallest gap kept between the parts when one is truncated.
+    pub fn with_min_gap(mut self, min_gap: usize) -> Self {
+        self.min_gap = min_gap;
+        self
+    }
+
+    /// Set the marker for truncated parts, instead of
+    /// [`RenderOptions::ellipsis`].
+    pub fn with_ellipsis(mut self, ellipsis: EllipsisConfig) -> Self {
+        self.ellipsis = Some(ellipsis);
+        self
+    }
+
+    /// Get the width of the line in cells.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Render one part as a single unwrapped line.
+    fn render_part(
+        part: Option<&dyn Renderable>,
+        console: &Console,
+        options: &RenderOptions,
+    ) -> Result<Line> {
+        let Some(part) = part else {
+            return Ok(Line::new());
+        };
+        let options = options.clone().with_soft_wrap(true);
+        let segments = part.render(console, &options)?;
+        let mut lines = render_utils::split_lines(segments);
+        Ok(if lines.is_empty() {
+            Line::new()
+        } else {
+            lines.swap_remove(0)
+        })
+    }
+
+    /// Fill `width` cells of gap.
+    fn gap(&self, width: usize) -> Option<Segment> {
+        let fill_width = self.fill.width().unwrap_or(0);
+        if width == 0 {
+            return None;
+        }
+        if fill_width == 0 {
+            return render_utils::fill(width, self.fill_style.clone());
+        }
+        let mut gap: String = std::iter::repeat(self.fill)
+            .take(width / fill_width)
+            .collect();
+        gap.extend(std::iter::repeat(' ').take(width % fill_width));
+        Some(Segment::new(gap, self.fill_style.clone()))
+    }
+}
+
+/// Truncate a line to `width` cells with `ellipsis`.
+fn truncate(line: Line, width: usize, ellipsis: &EllipsisConfig) -> Line {
+    if render_utils::line_width(&line) <= width {
+        return line;
+    }
+    let pieces = line
+        .iter()
+        .filter(|segment| !segment.is_control())
+        .map(|segment| Text::new(segment.text()).with_style(segment.style().clone()));
+    let mut text = Text::assemble(pieces, "");
+    text.truncate(width, ellipsis);
+    text.to_segments()
+}
+
+impl Renderable for LineLayout {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let width = self.width.min(options.get_max_width());
+        let ellipsis = self.ellipsis.as_ref().unwrap_or(&options.ellipsis);
+        let left = Self::render_part(self.left.as_deref(), console, options)?;
+        let right = Self::render_part(self.right.as_deref(), console, options)?;
+
+        let (first, second) = match self.priority {
+            LinePriority::Left => (left, right),
+            LinePriority::Right => (right, left),
+        };
+        let first = truncate(first, width, ellipsis);
+        let first_width = render_utils::line_width(&first);
+        let second = if first_width + render_utils::line_width(&second) <= width {
+            second
+        } else {
+            let budget = width.saturating_sub(first_width.saturating_add(self.min_gap));
+            truncate(second, budget, ellipsis)
+        };
+        let (left, right) = match self.priority {
+            LinePriority::Left => (first, second),
+            LinePriority::Right => (second, first),
+        };
+
+        let used = render_utils::line_width(&left) + render_utils::line_width(&right);
+        let mut line = left;
+        line.extend(self.gap(width.saturating_sub(used)));
+        line.extend(right);
+        Ok(line)
+    }
+}
+
+impl Measurable for LineLayout {
+    fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+        Ok(Measurement::fixed(self.width))
+    }
+}
+
+impl fmt::Debug for LineLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LineLayout")
+            .field("width", &self.width)
+            .field("fill", &self.fill)
+            .field("fill_style", &self.fill_style)
+            .field("priority", &self.priority)
+            .field("min_gap", &self.min_gap)
+            .field("ellipsis", &self.ellipsis)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::render_lines;
+
+    fn render(layout: &LineLayout) -> Vec<String> {
+        let options = RenderOptions::new().with_max_width(80);
+        render_lines(&layout.render(&Console::new(), &options).unwrap())
+    }
+
+    fn dotted(width: usize, left: &'static str, right: &'static str) -> LineLayout {
+        LineLayout::new(width)
+            .with_left(left)
+            .with_right(right)
+            .with_fill('.', Style::new())
+    }
+
+    #[test]
+    fn test_line_layout_fills_gap() {
+        crate::assert_render_eq!(render(&dotted(16, "name", "42 MB")), "name.......42 MB");
+        crate::assert_render_eq!(
+            render(&LineLayout::new(12).with_right("12:00")),
+            "       12:00"
+        );
+        // Exact fit leaves no gap
+        crate::assert_render_eq!(render(&dotted(9, "name", "42 MB")), "name42 MB");
+    }
+
+    #[test]
+    fn test_line_layout_collision_truncates_lower_priority() {
+        crate::assert_render_eq!(render(&dotted(8, "name", "42 MB")), "n….42 MB");
+        let left_first = dotted(8, "name", "42 MB").with_priority(LinePriority::Left);
+        crate::assert_render_eq!(render(&left_first), "name.42…");
+        let no_gap = dotted(8, "name", "42 MB").with_min_gap(0);
+        crate::assert_render_eq!(render(&no_gap), "na…42 MB");
+    }
+
+    #[test]
+    fn test_line_layout_priority_part_wider_than_line() {
+        crate::assert_render_eq!(render(&dotted(4, "name", "42 MB")), "42 …");
+        crate::assert_render_eq!(render(&dotted(0, "name", "42 MB")), "");
+    }
+
+    #[test]
+    fn test_line_layout_double_width_fill() {
+        let layout = LineLayout::new(9)
+            .with_left("a")
+            .with_right("b")
+            .with_fill('・', Style::new());
+        crate::assert_render_eq!(render(&layout), "a・・・ b");
+        let segments = layout
+            .render(&Console::new(), &RenderOptions::new().with_max_width(80))
+            .unwrap();
+        assert_eq!(render_utils::line_width(&segments), 9);
+    }
+}
@@ -0,0 +1,44 @@
+//! Built-in renderable components.
+//!
+//! Components are higher-level building blocks composed from segments, such as
+//! spacers used to separate content inside containers.
+
+pub mod badge;
+pub mod big_text;
+pub mod cached;
+pub mod column_ruler;
+pub mod columns;
+pub mod diff;
+pub mod group;
+pub mod image;
+pub mod line_layout;
+pub mod numbered_text;
+pub mod numeric_column;
+pub mod padded;
+pub mod panel;
+pub mod progress_bar;
+pub mod spacer;
+pub mod spinner;
+pub mod task_list;
+pub mod tree;
+pub mod vertical_bar;
+
+pub use badge::Badge;
+pub use big_text::BigText;
+pub use cached::{CacheKey, Cached};
+pub use column_ruler::ColumnRuler;
+pub use columns::Columns;
+pub use diff::{DiffGranularity, DiffLines, DiffText};
+pub use group::Group;
+pub use image::Image;
+pub use line_layout::{LineLayout, LinePriority};
+pub use numbered_text::NumberedText;
+pub use numeric_column::{NumericColumn, NumericOverflow};
+pub use padded::Padded;
+pub use panel::Panel;
+pub use progress_bar::{BarGlyphs, Direction, ProgressBar};
+pub use spacer::{Blank, Spacer};
+pub use spinner::Spinner;
+pub use task_list::{TaskId, TaskList, TaskState};
+pub use tree::{Tree, TreeGuides};
+pub use vertical_bar::VerticalBar;
@@ -0,0 +1,250 @@
+//! NumberedText - text with a line-number gutter.
+//!
+//! For file excerpts and other text where line numbers matter. Lines too
+//! wide for the space left by the gutter are folded, and the rows they
+//! continue on keep the separator but leave the number out, so the content
+//! stays in one column.
+
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style, Text,
+    render_utils,
+};
+use std::collections::BTreeSet;
+
+/// The separator between the gutter and the content.
+const SEPARATOR: &str = "│ ";
+/// [`SEPARATOR`] when only ASCII may be drawn.
+const ASCII_SEPARATOR: &str = "| ";
+/// The marker in front of marked lines.
+const MARKER: &str = "> ";
+
+/// Text drawn with the number of each line in front of it.
+///
+/// The gutter is as wide as the largest line number, with the numbers
+/// right-aligned. A trailing newline does not get a numbered empty line.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, Text, components::NumberedText};
+///
+/// let numbered = NumberedText::new(Text::new("fn main() {\n}\n")).with_start_line(9);
+/// let options = RenderOptions::new().with_max_width(40).with_ascii_only(false);
+/// let segments = numbered.render(&Console::new(), &options).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, " 9 │ fn main() {\n10 │ }");
+/// ```
+#[derive(Debug, Clone)]
+pub struct NumberedText {
+    text: Text,
+    start_line: usize,
+    gutter_style: Style,
+    highlighted: BTreeSet<usize>,
+    highlight_style: Style,
+    marked: BTreeSet<usize>,
+}
+
+impl NumberedText {
+    /// Number the lines of `text` from one.
+    pub fn new(text: impl Into<Text>) -> Self {
+        Self {
+            text: text.into(),
+            start_line: 1,
+            gutter_style: Style::new().dim(),
+            highlighted: BTreeSet::new(),
+            highlight_style: Style::new().bold(),
+            marked: BTreeSet::new(),
+        }
+    }
+
+    /// Set the number of the first line.
+    pub fn with_start_line(mut self, line: usize) -> Self {
+        self.start_line = line;
+        self
+    }
+
+    /// Set the style of the line numbers and separator.
+    pub fn with_gutter_style(mut self, style: Style) -> Self {
+        self.gutter_style = style;
+        self
+    }
+
+    /// Highlight the lines numbered `lines`, both gutter and content.
+    pub fn with_highlighted_lines(mut self, lines: impl IntoIterator<Item = usize>) -> Self {
+        self.highlighted.extend(lines);
+        self
+    }
+
+    /// Set the style laid over highlighted lines.
+    pub fn with_highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Draw a `>` in front of the lines numbered `lines`. Once any line is
+    /// marked, every line gets a marker column.
+    pub fn with_marked_lines(mut self, lines: impl IntoIterator<Item = usize>) -> Self {
+        self.marked.extend(lines);
+        self
+    }
+
+    /// Get the numbered text.
+    pub fn text(&self) -> &Text {
+        &self.text
+    }
+
+    /// The lines to number, without the empty line after a trailing
+    /// newline.
+    fn lines(&self) -> Vec<Text> {
+        let mut lines = self.text.split_lines();
+        if lines.len() > 1 && lines.last().is_some_and(Text::is_empty) {
+            lines.pop();
+        }
+        lines
+    }
+
+    /// The number of cells taken up by the marker column, the numbers and
+    /// the separator, for `count` lines.
+    fn gutter_width(&self, count: usize) -> usize {
+        let last = self.start_line + count.saturating_sub(1);
+        let marker = if self.marked.is_empty() {
+            0
+        } else {
+            MARKER.len()
+        };
+        marker + last.to_string().len() + 1 + SEPARATOR.chars().count()
+    }
+
+    /// Render the gutter of a row of line `number`, or of a continuation
+    /// row without one.
+    fn gutter(&self, number: Option<usize>, digits: usize, separator: &str) -> String {
+        let mut gutter = String::new();
+        if !self.marked.is_empty() {
+            let marked = number.is_some_and(|number| self.marked.contains(&number));
+            gutter.push_str(if marked { MARKER } else { "  " });
+        }
+        match number {
+            Some(number) => gutter.push_str(&format!("{number:>digits$} ")),
+            None => gutter.push_str(&" ".repeat(digits + 1)),
+        }
+        gutter.push_str(separator);
+        gutter
+    }
+}
+
+impl Renderable for NumberedText {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let lines = self.lines();
+        let digits = (self.start_line + lines.len().saturating_sub(1))
+            .to_string()
+            .len();
+        let separator = if options.ascii_only {
+            ASCII_SEPARATOR
+        } else {
+            SEPARATOR
+        };
+        let inner = render_utils::narrow(options, self.gutter_width(lines.len()));
+
+        let mut rows = Vec::new();
+        for (index, line) in lines.into_iter().enumerate() {
+            let number = self.start_line + index;
+            let highlight = self.highlighted.contains(&number);
+            let rendered = render_utils::split_lines(line.render(console, &inner)?);
+            for (row, content) in rendered.into_iter().enumerate() {
+                let gutter = self.gutter((row == 0).then_some(number), digits, separator);
+                let mut output = vec![Segment::new(gutter, self.gutter_style.clone())];
+                output.extend(content);
+                if highlight {
+                    output = output
+                        .into_iter()
+                        .map(|segment| {
+                            let style = segment.style().clone();
+                            let style = style.combine(self.highlight_style.clone());
+                            Segment::new(segment.text(), style)
+                        })
+                        .collect();
+                }
+                rows.push(output);
+            }
+        }
+        Ok(render_utils::frame_lines(rows))
+    }
+}
+
+impl Measurable for NumberedText {
+    fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+        let lines = self.lines();
+        let widest = lines.iter().map(Text::width).max().unwrap_or(0);
+        Ok(Measurement::fixed(self.gutter_width(lines.len()) + widest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_render_eq, testing::render_lines};
+
+    fn sample() -> NumberedText {
+        let text = Text::new("first\nabcdefghijklmnopqrstuvwxy\nlast\n");
+        NumberedText::new(text).with_start_line(98)
+    }
+
+    fn render(numbered: &NumberedText, width: usize) -> Vec<Segment> {
+        let options = RenderOptions::new()
+            .with_max_width(width)
+            .with_ascii_only(false);
+        numbered.render(&Console::new(), &options).unwrap()
+    }
+
+    #[test]
+    fn test_wrapped_line_keeps_gutter_aligned() {
+        // 98..=100 needs three digits, leaving ten cells of content
+        let segments = render(&sample(), 16);
+        assert_render_eq!(
+            render_lines(&segments),
+            " 98 │ first\n \
+             99 │ abcdefghij\n    \
+             │ klmnopqrst\n    \
+             │ uvwxy\n\
+             100 │ last"
+        );
+        let gutters: Vec<&str> = segments
+            .iter()
+            .filter(|segment| segment.style() == &Style::new().dim())
+            .map(Segment::text)
+            .collect();
+        assert_eq!(gutters, [" 98 │ ", " 99 │ ", "    │ ", "    │ ", "100 │ "]);
+    }
+
+    #[test]
+    fn test_gutter_width_follows_last_number() {
+        let below = NumberedText::new(Text::new("a\nb")).with_start_line(98);
+        assert_render_eq!(render_lines(&render(&below, 10)), "98 │ a\n99 │ b");
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(40);
+        let measurement = sample().measure(&console, &options).unwrap();
+        assert_eq!(measurement, Measurement::fixed(6 + 25));
+    }
+
+    #[test]
+    fn test_highlighted_and_marked_lines() {
+        let numbered = sample()
+            .with_highlighted_lines([99])
+            .with_marked_lines([99, 100]);
+        let segments = render(&numbered, 18);
+        assert_render_eq!(
+            render_lines(&segments),
+            "   98 │ first\n\
+             >  99 │ abcdefghij\n      \
+             │ klmnopqrst\n      \
+             │ uvwxy\n\
+             > 100 │ last"
+        );
+        let bold: String = segments
+            .iter()
+            .filter(|segment| segment.style().bold == Some(true))
+            .map(Segment::text)
+            .collect();
+        assert_eq!(bold, ">  99 │ abcdefghij      │ klmnopqrst      │ uvwxy");
+    }
+}
@@ -0,0 +1,381 @@
+//! NumericColumn - right-aligned numbers that stay readable when narrow.
+//!
+//! Truncating a number with an ellipsis, as text is truncated, produces
+//! something like `1…45` that reads as a different number. A numeric
+//! column instead keeps decimal points aligned and, when the longest value
+//! does not fit, either scales every value to a unit suffix (`98.8M`) or
+//! clips the most significant digits behind a visible marker (`>4,567`).
+//!
+//! ```rust
+//! use luxor::components::NumericColumn;
+//!
+//! let column = NumericColumn::new([3.5, 1234.25, -42.0]).with_thousands_separator(',');
+//! assert_eq!(column.lines(10), ["      3.5 ", "  1,234.25", "    -42   "]);
+//! ```
+
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style,
+    render_utils,
+};
+use unicode_width::UnicodeWidthChar;
+
+/// Unit suffixes for each power of 1000, starting with a space for
+/// unscaled values so decimal points stay aligned.
+const SUFFIXES: [char; 7] = [' ', 'k', 'M', 'G', 'T', 'P', 'E'];
+/// The integer and fraction digits of a formatted number.
+type NumberParts = (String, String);
+
+/// Marker for digits clipped when even scaled values do not fit.
+const CLIP_MARKER: char = '>';
+
+/// What a [`NumericColumn`] does when its longest value does not fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumericOverflow {
+    /// Scale every value by powers of 1000 with a `k`, `M`, `G`, ...
+    /// suffix, with one decimal place or none. If even that does not fit,
+    /// values are clipped as with [`NumericOverflow::ClipLeft`] with a `>`
+    /// marker.
+    #[default]
+    Scale,
+    /// Drop the most significant digits of each value that does not fit,
+    /// and show `marker` in their place.
+    ClipLeft {
+        /// The character drawn in place of the clipped digits.
+        marker: char,
+    },
+}
+
+/// A column of numbers, right-aligned with their decimal points lined up.
+///
+/// Without a fixed precision, each value shows the digits it has and
+/// shorter fractions are padded with spaces to the longest one. Rendering
+/// draws one line per value, each exactly as wide as the maximum width.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::components::{NumericColumn, NumericOverflow};
+///
+/// let column = NumericColumn::new([1_500_000.0, 250.0]);
+/// assert_eq!(column.lines(6), ["  1.5M", "250.0 "]);
+///
+/// let clipped = column.with_overflow(NumericOverflow::ClipLeft { marker: '>' });
+/// assert_eq!(clipped.lines(6), [">00000", "   250"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumericColumn {
+    values: Vec<f64>,
+    precision: Option<usize>,
+    separator: Option<char>,
+    overflow: NumericOverflow,
+    style: Style,
+}
+
+impl NumericColumn {
+    /// Create a column of `values`.
+    ///
+    /// Integers wider than 32 bits convert to `f64` with `as`; values above
+    /// 2^53 lose their last digits.
+    pub fn new<T: Into<f64>>(values: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            values: values.into_iter().map(Into::into).collect(),
+            precision: None,
+            separator: None,
+            overflow: NumericOverflow::default(),
+            style: Style::default(),
+        }
+    }
+
+    /// Show every value with exactly `precision` decimal places.
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Group the digits before the decimal point in threes with
+    /// `separator`.
+    pub fn with_thousands_separator(mut self, separator: char) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// Set what happens when the longest value does not fit.
+    pub fn with_overflow(mut self, overflow: NumericOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Set the style of the numbers.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Get the values.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Format the values as lines exactly `width` cells wide.
+    pub fn lines(&self, width: usize) -> Vec<String> {
+        let natural = self.natural_lines();
+        let fitted = if longest(&natural) <= width {
+            natural
+        } else {
+            match self.overflow {
+                NumericOverflow::Scale => {
+                    let scaled = [1, 0]
+                        .into_iter()
+                        .map(|decimals| self.scaled_lines(decimals))
+                        .find(|lines| longest(lines) <= width);
+                    match scaled {
+                        Some(lines) => lines,
+                        None => clip_lines(self.scaled_lines(0), width, CLIP_MARKER),
+                    }
+                }
+                NumericOverflow::ClipLeft { marker } => clip_lines(natural, width, marker),
+            }
+        };
+        fitted
+            .into_iter()
+            .map(|line| {
+                let pad = width.saturating_sub(render_utils::visible_width(&line));
+                format!("{}{}", " ".repeat(pad), line)
+            })
+            .collect()
+    }
+
+    /// Format the values as they are, decimal points aligned.
+    fn natural_lines(&self) -> Vec<String> {
+        let parts: Vec<NumberParts> = self
+            .values
+            .iter()
+            .map(|&value| split_number(value, self.precision))
+            .collect();
+        let precision = parts
+            .iter()
+            .map(|(_, fraction)| fraction.len())
+            .max()
+            .unwrap_or(0);
+        parts
+            .into_iter()
+            .map(|(integer, fraction)| {
+                let mut line = self.group(&integer);
+                if !fraction.is_empty() {
+                    line.push('.');
+                    line.push_str(&fraction);
+                    line.push_str(&" ".repeat(precision - fraction.len()));
+                } else if precision > 0 {
+                    line.push_str(&" ".repeat(precision + 1));
+                }
+                line
+            })
+            .collect()
+    }
+
+    /// Format the values scaled to a unit suffix with `decimals` places.
+    fn scaled_lines(&self, decimals: usize) -> Vec<String> {
+        self.values
+            .iter()
+            .map(|&value| {
+                if !value.is_finite() {
+                    return format!("{} ", value);
+                }
+                let mut power = 0;
+                let mut scaled = value;
+                while scaled.abs() >= 1000.0 && power < SUFFIXES.len() - 1 {
+                    scaled /= 1000.0;
+                    power += 1;
+                }
+                let mut text = format!("{:.*}", decimals, scaled);
+                // Rounding can carry into the next unit, as 999.96 to 1000.0
+                if power < SUFFIXES.len() - 1 && text.trim_start_matches('-').starts_with("1000") {
+                    scaled /= 1000.0;
+                    power += 1;
+                    text = format!("{:.*}", decimals, scaled);
+                }
+                let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+                let mut line = self.group(integer);
+                if !fraction.is_empty() {
+                    line.push('.');
+                    line.push_str(fraction);
+                }
+                line.push(SUFFIXES[power]);
+                line
+            })
+            .collect()
+    }
+
+    /// Group the digits of `integer` with the separator, if there is one.
+    fn group(&self, integer: &str) -> String {
+        let (sign, digits) = match integer.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", integer),
+        };
+        let separator = match self.separator {
+            Some(separator) if digits.bytes().all(|byte| byte.is_ascii_digit()) => separator,
+            _ => return integer.to_string(),
+        };
+        let mut grouped = String::from(sign);
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index) % 3 == 0 {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+}
+
+/// Split the formatted `value` into its integer and fraction digits.
+fn split_number(value: f64, precision: Option<usize>) -> NumberParts {
+    if !value.is_finite() {
+        return (value.to_string(), String::new());
+    }
+    let text = match precision {
+        Some(precision) => format!("{:.*}", precision, value),
+        None => value.to_string(),
+    };
+    match text.split_once('.') {
+        Some((integer, fraction)) => (integer.to_string(), fraction.to_string()),
+        None => (text, String::new()),
+    }
+}
+
+/// Get the width of the widest line.
+fn longest(lines: &[String]) -> usize {
+    lines
+        .iter()
+        .map(|line| render_utils::visible_width(line))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Keep the last cells of each line wider than `width`, behind `marker`.
+fn clip_lines(lines: Vec<String>, width: usize, marker: char) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|line| {
+            if render_utils::visible_width(&line) <= width {
+                return line;
+            }
+            let budget = width.saturating_sub(marker.width().unwrap_or(1));
+            let mut cells = 0;
+            let mut start = line.len();
+            for (index, ch) in line.char_indices().rev() {
+                cells += ch.width().unwrap_or(0);
+                if cells > budget {
+                    break;
+                }
+                start = index;
+            }
+            if width == 0 {
+                String::new()
+            } else {
+                format!("{}{}", marker, &line[start..])
+            }
+        })
+        .collect()
+}
+
+impl Renderable for NumericColumn {
+    fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let lines = self
+            .lines(options.get_max_width())
+            .into_iter()
+            .map(|line| {
+                let number = line.trim();
+                let leading = line.len() - line.trim_start().len();
+                let trailing = line.len() - line.trim_end().len();
+                let mut segments = Vec::with_capacity(3);
+                segments.extend(render_utils::fill(leading, Style::default()));
+                if !number.is_empty() {
+                    segments.push(Segment::new(number, self.style.clone()));
+                }
+                segments.extend(render_utils::fill(trailing, Style::default()));
+                segments
+            })
+            .collect();
+        Ok(render_utils::frame_lines(lines))
+    }
+}
+
+impl Measurable for NumericColumn {
+    fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+        let maximum = longest(&self.natural_lines());
+        let minimum = match self.overflow {
+            NumericOverflow::Scale => longest(&self.scaled_lines(0)),
+            NumericOverflow::ClipLeft { .. } => 2,
+        };
+        Ok(Measurement::new(minimum.min(maximum), maximum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::render_lines;
+
+    fn mixed() -> NumericColumn {
+        NumericColumn::new([3.5, 1234.25, 98_765_432.0, -42.0]).with_thousands_separator(',')
+    }
+
+    #[test]
+    fn test_mixed_magnitudes_at_widths() {
+        assert_eq!(
+            mixed().lines(14),
+            [
+                "          3.5 ",
+                "      1,234.25",
+                " 98,765,432   ",
+                "        -42   ",
+            ]
+        );
+        // The grouped values need 13 cells, so these scale to units
+        assert_eq!(
+            mixed().lines(10),
+            ["      3.5 ", "      1.2k", "     98.8M", "    -42.0 "]
+        );
+        assert_eq!(mixed().lines(6), ["  3.5 ", "  1.2k", " 98.8M", "-42.0 "]);
+        // Too narrow for one decimal place, so whole units
+        assert_eq!(mixed().lines(4), ["  4 ", "  1k", " 99M", "-42 "]);
+    }
+
+    #[test]
+    fn test_clip_left_keeps_least_significant_digits() {
+        let column = mixed().with_overflow(NumericOverflow::ClipLeft { marker: '>' });
+        assert_eq!(column.lines(6), ["  3.5 ", ">34.25", ">32   ", "-42   "]);
+        assert_eq!(column.lines(1), [">", ">", ">", ">"]);
+        assert_eq!(column.lines(0), ["", "", "", ""]);
+    }
+
+    #[test]
+    fn test_precision_and_rounding_carry() {
+        let column = NumericColumn::new([2.0, 0.126, 10.0]).with_precision(2);
+        assert_eq!(column.lines(6), ["  2.00", "  0.13", " 10.00"]);
+
+        let carry = NumericColumn::new([999_960.0, f64::NAN]);
+        assert_eq!(carry.lines(5), [" 1.0M", " NaN "]);
+    }
+
+    #[test]
+    fn test_render_and_measure() {
+        let console = Console::new();
+        let column = mixed().with_style(Style::new().bold());
+        let options = RenderOptions::new().with_max_width(10);
+        let segments = column.render(&console, &options).unwrap();
+        crate::assert_render_eq!(
+            render_lines(&segments),
+            "      3.5 \n      1.2k\n     98.8M\n    -42.0 "
+        );
+        assert!(
+            segments
+                .iter()
+                .filter(|segment| segment.text().trim().is_empty() && segment.text() != "\n")
+                .all(Segment::is_padding)
+        );
+
+        let measurement = column.measure(&console, &options).unwrap();
+        assert_eq!(measurement, Measurement::new(4, 13));
+    }
+}
@@ -0,0 +1,271 @@
+//! Padded - a renderable with space around it.
+//!
+//! [`Padding`] only describes the space; [`Padded`] draws it, as blank
+//! lines above and below the content and spaces either side of every
+//! line. With a background style the padding and content form one colored
+//! block:
+//!
+//! ```rust
+//! use luxor::{Console, RenderOptions, Renderable, Text, components::Padded, layout::Padding};
+//!
+//! let padded = Padded::new(Text::new("hi"), Padding::symmetric(1, 2));
+//! let options = RenderOptions::new().with_max_width(40);
+//! let segments = padded.render(&Console::new(), &options).unwrap();
+//! let plain: String = segments.iter().map(|s| s.text()).collect();
+//! assert_eq!(plain, "      \n  hi  \n      ");
+//! ```
+
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style,
+    layout::Padding, render_utils, rendered::Line,
+};
+
+/// A renderable surrounded by padding.
+///
+/// The content is rendered narrower by the left and right padding, so the
+/// padded output still fits the available width, and every line is filled
+/// to the width of the widest one, or to the whole available width with
+/// [`Padded::with_expand`]. Padding wider than the available width leaves
+/// no room for the content, which then renders as a blank line.
+///
+/// The style, typically a background, colors the padding and is the base
+/// style of the content: the content's own styles are combined over it.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Color, Console, RenderOptions, Renderable, Style, Text, components::Padded};
+///
+/// let padded = Padded::new(Text::new("ok"), (0, 1))
+///     .with_style(Style::new().with_background(Color::rgb(0, 0, 128)));
+/// let options = RenderOptions::new().with_max_width(40);
+/// let segments = padded.render(&Console::new(), &options).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, " ok ");
+/// assert!(segments.iter().all(|s| s.style().background.is_some()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Padded<R> {
+    content: R,
+    padding: Padding,
+    style: Style,
+    expand: bool,
+}
+
+impl<R: Renderable> Padded<R> {
+    /// Create `content` surrounded by `padding`, as wide as the content.
+    pub fn new(content: R, padding: impl Into<Padding>) -> Self {
+        Self {
+            content,
+            padding: padding.into(),
+            style: Style::default(),
+            expand: false,
+        }
+    }
+
+    /// Set the style of the padding and the base style of the content.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Fill every line to the whole available width.
+    pub fn with_expand(mut self, expand: bool) -> Self {
+        self.expand = expand;
+        self
+    }
+
+    /// Get the content.
+    pub fn content(&self) -> &R {
+        &self.content
+    }
+
+    /// Get the padding.
+    pub fn padding(&self) -> Padding {
+        self.padding
+    }
+
+    /// Put the style under a line of content, keeping controls and the
+    /// padding mark as they were.
+    fn restyle(&self, line: Line) -> Line {
+        if self.style.is_empty() {
+            return line;
+        }
+        line.into_iter()
+            .map(|segment| {
+                if segment.is_control() {
+                    return segment;
+                }
+                let style = self.style.clone().combine(segment.style().clone());
+                match segment.is_padding() {
+                    true => Segment::pad(segment.text(), style),
+                    false => Segment::new(segment.text(), style),
+                }
+            })
+            .collect()
+    }
+}
+
+impl<R: Renderable> Renderable for Padded<R> {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let available = options.get_max_width();
+        let inner_width = available.saturating_sub(self.padding.horizontal());
+        let lines = match inner_width {
+            0 => vec![Line::new()],
+            _ => {
+                let inner = render_utils::narrow(options, self.padding.horizontal());
+                render_utils::split_lines(inner.render_child(&self.content, console)?)
+            }
+        };
+        let content_width = match self.expand {
+            true => inner_width,
+            false => lines
+                .iter()
+                .map(|line| render_utils::line_width(line))
+                .max()
+                .unwrap_or(0)
+                .min(inner_width),
+        };
+
+        // Padding is cut from the right, then the left, when it does not fit
+        let left = self.padding.left.min(available);
+        let right = self.padding.right.min(available - left);
+        let width = left + content_width + right;
+        let blank = || -> Line {
+            render_utils::fill(width, self.style.clone())
+                .into_iter()
+                .collect()
+        };
+
+        let mut output = Vec::with_capacity(lines.len() + self.padding.vertical());
+        output.extend((0..self.padding.top).map(|_| blank()));
+        for line in lines {
+            let mut padded: Line = render_utils::fill(left, self.style.clone())
+                .into_iter()
+                .collect();
+            padded.extend(render_utils::adjust_line_length(
+                self.restyle(line),
+                content_width,
+                self.style.clone(),
+            ));
+            padded.extend(render_utils::fill(right, self.style.clone()));
+            output.push(padded);
+        }
+        output.extend((0..self.padding.bottom).map(|_| blank()));
+        Ok(render_utils::frame_lines(output))
+    }
+
+    fn component_name(&self) -> String {
+        self.content.component_name()
+    }
+}
+
+impl<R: Renderable + Measurable> Measurable for Padded<R> {
+    /// Measure the content plus the left and right padding, or the whole
+    /// available width when expanding.
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        let available = options.get_max_width();
+        if self.expand {
+            return Ok(Measurement::fixed(available));
+        }
+        let inner = render_utils::narrow(options, self.padding.horizontal());
+        let content = inner.measure_child(&self.content, console, || {
+            Measurement::span(inner.get_max_width())
+        })?;
+        Ok(content
+            .add_width(self.padding.horizontal())
+            .clamp(0, available))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Color, Text, assert_render_eq, render_utils::checked_render, testing::render_lines,
+    };
+
+    fn lines<R: Renderable>(padded: &Padded<R>, width: usize) -> Vec<String> {
+        let options = RenderOptions::new().with_max_width(width);
+        render_lines(&padded.render(&Console::new(), &options).unwrap())
+    }
+
+    #[test]
+    fn test_multi_line_content() {
+        let padded = Padded::new(Text::new("one\nthree"), Padding::new(1, 2, 0, 1));
+        assert_render_eq!(lines(&padded, 20), "        \n one    \n three  ");
+        let padded = padded.with_expand(true);
+        assert_render_eq!(lines(&padded, 10), "          \n one      \n three    ");
+    }
+
+    #[test]
+    fn test_zero_padding_is_a_no_op() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(20);
+        for content in [
+            "hello",
+            "ab\ncd",
+            "wrapping text that is longer than twenty",
+        ] {
+            let text = Text::new(content);
+            let padded = Padded::new(text.clone(), 0);
+            // Lines are only evened out to the widest one
+            let lines = render_lines(&text.render(&console, &options).unwrap());
+            let width = lines.iter().map(|line| line.len()).max().unwrap();
+            let expected: Vec<String> = lines
+                .iter()
+                .map(|line| format!("{:<width$}", line))
+                .collect();
+            assert_eq!(
+                render_lines(&padded.render(&console, &options).unwrap()),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_content_narrowed_to_fit() {
+        let padded = Padded::new(Text::new("abcdefgh"), (0, 2));
+        assert_render_eq!(lines(&padded, 8), "  abcd  \n  efgh  ");
+        // Padding wider than the line leaves a blank line
+        let padded = Padded::new(Text::new("x"), (0, 3, 0, 4));
+        assert_render_eq!(lines(&padded, 5), "     ");
+    }
+
+    #[test]
+    fn test_style_under_content() {
+        let background = Style::new().with_background(Color::rgb(0, 0, 128));
+        let content = Text::from_markup("[bold]a[/bold]b").unwrap();
+        let padded = Padded::new(content, 1).with_style(background);
+        let options = RenderOptions::new().with_max_width(10);
+        let segments = padded.render(&Console::new(), &options).unwrap();
+        assert_render_eq!(render_lines(&segments), "    \n ab \n    ");
+        for segment in segments
+            .iter()
+            .filter(|s| !s.is_control() && s.text() != "\n")
+        {
+            assert_eq!(segment.style().background, Some(Color::rgb(0, 0, 128)));
+        }
+        let bold = segments.iter().find(|s| s.text() == "a").unwrap();
+        assert_eq!(bold.style().bold, Some(true));
+    }
+
+    #[test]
+    fn test_measure() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(10);
+        let padded = Padded::new(Text::new("abc"), (0, 2));
+        assert_eq!(
+            padded.measure(&console, &options).unwrap(),
+            Measurement::fixed(7)
+        );
+        let padded = Padded::new(Text::new("abcdefghij"), (0, 2));
+        assert_eq!(padded.measure(&console, &options).unwrap().maximum(), 10);
+        checked_render(&padded, &console, &options).unwrap();
+        let padded = padded.with_expand(true);
+        assert_eq!(
+            padded.measure(&console, &options).unwrap(),
+            Measurement::fixed(10)
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! Panel - a renderable framed by a box, with an optional title.
+//!
+//! A [`Panel`] draws its content inside a [`BoxConfig`] border, with the
+//! title set into the top edge and the subtitle into the bottom. By
+//! default a panel takes the whole width; [`Panel::fit`] shrinks it to
+//! its content:
+//!
+//! ```rust
+//! use luxor::{Console, RenderOptions, Renderable, Text, components::Panel};
+//!
+//! let panel = Panel::fit(Text::new("hello")).with_title("Info");
+//! let options = RenderOptions::new().with_max_width(40);
+//! let segments = panel.render(&Console::new(), &options).unwrap();
+//! let plain: String = segments.iter().map(|s| s.text()).collect();
+//! assert_eq!(plain, "╭ Info ─╮\n│ hello │\n╰───────╯");
+//! ```
+
+use crate::{
+    BidiPolicy, Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment,
+    Style, Text,
+    box_drawing::{self, BorderStyle, BoxConfig, BoxLabel},
+    diagnostics::{self, Warning},
+    layout::{Align, Padding, Region},
+    render_utils,
+    rendered::Line,
+};
+
+/// Cells taken by the left and right edges of the frame.
+const FRAME_CELLS: usize = 2;
+
+/// A renderable drawn inside a border.
+///
+/// Content wider than the panel is cropped at its right edge; text wraps
+/// to the inner width before it gets there. A panel narrower than its
+/// two edges renders nothing. With [`RenderOptions::ascii_only`] set, a
+/// border with non-ASCII characters is drawn with [`BorderStyle::Ascii`].
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, Text, components::Panel, layout::Align};
+///
+/// let panel = Panel::new(Text::new("ready"))
+///     .with_title("Status")
+///     .with_title_align(Align::Left)
+///     .with_padding(0);
+/// let options = RenderOptions::new().with_max_width(14);
+/// let segments = panel.render(&Console::new(), &options).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, "╭─ Status ───╮\n│ready       │\n╰────────────╯");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Panel<R> {
+    content: R,
+    box_config: BoxConfig,
+    border_style: Style,
+    title: Option<Text>,
+    title_align: Align,
+    subtitle: Option<Text>,
+    subtitle_align: Align,
+    padding: Padding,
+    expand: bool,
+    width: Option<usize>,
+}
+
+impl<R: Renderable> Panel<R> {
+    /// Create a panel taking the whole available width.
+    pub fn new(content: R) -> Self {
+        Self {
+            content,
+            box_config: BoxConfig::default(),
+            border_style: Style::default(),
+            title: None,
+            title_align: Align::Center,
+            subtitle: None,
+            subtitle_align: Align::Center,
+            padding: Padding::symmetric(0, 1),
+            expand: true,
+            width: None,
+        }
+    }
+
+    /// Create a panel just wide enough for its content and title.
+    pub fn fit(content: R) -> Self {
+        Self {
+            expand: false,
+            ..Self::new(content)
+        }
+    }
+
+    /// Set the border characters, from a [`BorderStyle`] or a custom
+    /// [`BoxConfig`].
+    pub fn with_box(mut self, config: impl Into<BoxConfig>) -> Self {
+        self.box_config = config.into();
+        self
+    }
+
+    /// Set the style of the border.
+    pub fn with_border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    /// Set the title shown in the top border.
+    pub fn with_title(mut self, title: impl Into<Text>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set where the title goes along the top border.
+    pub fn with_title_align(mut self, align: Align) -> Self {
+        self.title_align = align;
+        self
+    }
+
+    /// Set the subtitle shown in the bottom border.
+    pub fn with_subtitle(mut self, subtitle: impl Into<Text>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Set where the subtitle goes along the bottom border.
+    pub fn with_subtitle_align(mut self, align: Align) -> Self {
+        self.subtitle_align = align;
+        self
+    }
+
+    /// Set the space between the border and the content. The default is
+    /// one cell on the left and right.
+    pub fn with_padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    /// Set a fixed width, border included. The panel is still narrowed to
+    /// the available width.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Get the content.
+    pub fn content(&self) -> &R {
+        &self.content
+    }
+
+    /// Render a title or subtitle onto one line, joining any lines of it
+    /// with spaces.
+    fn label(
+        &self,
+        text: Option<&Text>,
+        align: Align,
+        console: &Console,
+        options: &RenderOptions,
+    ) -> Result<Option<BoxLabel>> {
+        let Some(text) = text else {
+            return Ok(None);
+        };
+        let segments = text.render(console, &options.clone().with_soft_wrap(true))?;
+        let mut line = Line::new();
+        for (index, part) in render_utils::split_lines(segments).into_iter().enumerate() {
+            if index > 0 {
+                line.push(Segment::new(" ", Style::new()));
+            }
+            line.extend(part);
+        }
+        Ok(Some(BoxLabel { line, align }))
+    }
+
+    /// Render the content at most `width` cells wide, or a single blank
+    /// line when there is no room for it.
+    fn content_lines(
+        &self,
+        width: usize,
+        console: &Console,
+        options: &RenderOptions,
+    ) -> Result<Vec<Line>> {
+        if width == 0 {
+            return Ok(vec![Line::new()]);
+        }
+        let cells = options.get_max_width().saturating_sub(width);
+        let segments = render_utils::narrow(options, cells).render_child(&self.content, console)?;
+        // Keep right-to-left content from being reordered against the border
+        let policy = options.bidi_policy.unwrap_or(BidiPolicy::Isolate);
+        Ok(render_utils::split_lines(segments)
+            .into_iter()
+            .map(|line| render_utils::apply_bidi_policy(line, policy))
+            .collect())
+    }
+}
+
+impl<R: Renderable> Renderable for Panel<R> {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let available = options.get_max_width();
+        let width = self.width.map_or(available, |width| width.min(available));
+        if width < FRAME_CELLS {
+            return Ok(Vec::new());
+        }
+        let config = if options.ascii_only && !self.box_config.is_ascii() {
+            diagnostics::warn(|| Warning::AsciiFallback { component: "Panel" });
+            BoxConfig::from(BorderStyle::Ascii)
+        } else {
+            self.box_config
+        };
+
+        let title = self.label(self.title.as_ref(), self.title_align, console, options)?;
+        let subtitle = self.label(
+            self.subtitle.as_ref(),
+            self.subtitle_align,
+            console,
+            options,
+        )?;
+
+        let mut inner_width = width - FRAME_CELLS;
+        let region = self
+            .padding
+            .apply_to_region(Region::new(0, 0, inner_width, 0));
+        let mut lines = self.content_lines(region.width, console, options)?;
+        if !self.expand && self.width.is_none() {
+            // Shrink to the widest of the content and the labels
+            let content = lines.iter().map(|line| render_utils::line_width(line));
+            let labels = [&title, &subtitle]
+                .into_iter()
+                .flatten()
+                .map(|label| render_utils::line_width(&label.line) + 2);
+            let widest = content.max().unwrap_or(0) + self.padding.horizontal();
+            inner_width = labels.fold(widest, usize::max).min(inner_width);
+        }
+
+        let region = self
+            .padding
+            .apply_to_region(Region::new(0, 0, inner_width, 0));
+        let left = render_utils::fill(region.x, Style::new());
+        let right = render_utils::fill(inner_width - region.x - region.width, Style::new());
+        let blank = || render_utils::adjust_line_length(Vec::new(), inner_width, Style::new());
+
+        let mut body = Vec::with_capacity(lines.len() + self.padding.vertical());
+        body.extend((0..self.padding.top).map(|_| blank()));
+        for line in lines.drain(..) {
+            let mut padded: Line = left.iter().cloned().collect();
+            padded.extend(render_utils::adjust_line_length(
+                line,
+                region.width,
+                Style::new(),
+            ));
+            padded.extend(right.iter().cloned());
+            body.push(padded);
+        }
+        body.extend((0..self.padding.bottom).map(|_| blank()));
+
+        Ok(box_drawing::draw_box(
+            &config,
+            body,
+            inner_width,
+            &self.border_style,
+            title.as_ref(),
+            subtitle.as_ref(),
+        ))
+    }
+}
+
+impl<R: Renderable + Measurable> Measurable for Panel<R> {
+    /// Measure the content plus the border and padding, widened to fit
+    /// the title. A panel with a set width measures as that width.
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        let available = options.get_max_width();
+        if let Some(width) = self.width {
+            return Ok(Measurement::fixed(width.min(available)));
+        }
+        let decoration = FRAME_CELLS + self.padding.horizontal();
+        let inner = render_utils::narrow(options, decoration);
+        let content = inner.measure_child(&self.content, console, || {
+            Measurement::span(inner.get_max_width())
+        })?;
+        let labels = [&self.title, &self.subtitle]
+            .into_iter()
+            .flatten()
+            .map(|label| label.width() + 2 + FRAME_CELLS)
+            .fold(0, usize::max);
+        Ok(content
+            .add_width(decoration)
+            .max_with(Measurement::fixed(labels))
+            .clamp(0, available))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_render_eq, testing::render_lines};
+
+    fn lines<R: Renderable>(panel: &Panel<R>, width: usize) -> Vec<String> {
+        let options = RenderOptions::new().with_max_width(width);
+        render_lines(&panel.render(&Console::new(), &options).unwrap())
+    }
+
+    #[test]
+    fn test_expand_and_fit() {
+        let panel = Panel::new(Text::new("hi"));
+        assert_render_eq!(lines(&panel, 8), "╭──────╮\n│ hi   │\n╰──────╯");
+        let panel = Panel::fit(Text::new("hi")).with_padding((1, 2));
+        assert_render_eq!(
+            lines(&panel, 20),
+            "╭──────╮\n│      │\n│  hi  │\n│      │\n╰──────╯"
+        );
+        // A fitted panel still widens for its title
+        let panel = Panel::fit(Text::new("hi"))
+            .with_subtitle("done")
+            .with_padding(0);
+        assert_render_eq!(lines(&panel, 20), "╭──────╮\n│hi    │\n╰ done ╯");
+        let panel = Panel::new(Text::new("hi")).with_width(6);
+        assert_render_eq!(lines(&panel, 20), "╭────╮\n│ hi │\n╰────╯");
+    }
+
+    #[test]
+    fn test_title_alignment() {
+        let panel = |align| {
+            Panel::new(Text::new(""))
+                .with_title("ab")
+                .with_title_align(align)
+                .with_padding(0)
+        };
+        assert_eq!(lines(&panel(Align::Left), 12)[0], "╭─ ab ─────╮");
+        assert_eq!(lines(&panel(Align::Center), 12)[0], "╭─── ab ───╮");
+        assert_eq!(lines(&panel(Align::Right), 12)[0], "╭───── ab ─╮");
+    }
+
+    #[test]
+    fn test_markup_title_and_content() {
+        let panel = Panel::new(Text::from_markup("[bold]hi[/bold]").unwrap()).with_title("Info");
+        let options = RenderOptions::new().with_max_width(10);
+        let segments = panel.render(&Console::new(), &options).unwrap();
+        assert_render_eq!(
+            render_lines(&segments),
+            "╭─ Info ─╮\n│ hi     │\n╰────────╯"
+        );
+        let bold = segments
+            .iter()
+            .find(|segment| segment.text() == "hi")
+            .unwrap();
+        assert_eq!(bold.style().bold, Some(true));
+    }
+
+    #[test]
+    fn test_narrow_widths() {
+        // Text folds to the inner width
+        let panel = Panel::new(Text::new("abcdef")).with_padding(0);
+        assert_render_eq!(lines(&panel, 5), "╭───╮\n│abc│\n│def│\n╰───╯");
+        for width in [0, 1] {
+            let options = RenderOptions::new().with_max_width(width);
+            let panel = Panel::new(Text::new("x")).with_title("t");
+            assert!(panel.render(&Console::new(), &options).unwrap().is_empty());
+        }
+        // Padding wider than the panel leaves no room for the content
+        let panel = Panel::new(Text::new("x")).with_padding((0, 4));
+        assert_render_eq!(lines(&panel, 6), "╭────╮\n│    │\n╰────╯");
+    }
+
+    #[test]
+    fn test_ascii_border_and_measure() {
+        let panel = Panel::fit(Text::new("ok")).with_box(BorderStyle::Double);
+        let options = RenderOptions::new()
+            .with_max_width(20)
+            .with_ascii_only(true);
+        let segments = panel.render(&Console::new(), &options).unwrap();
+        assert_render_eq!(render_lines(&segments), "+----+\n| ok |\n+----+");
+
+        let text = Text::new("ok");
+        let expected = text
+            .measure(&Console::new(), &options)
+            .unwrap()
+            .add_width(4);
+        let measurement = Panel::new(text).measure(&Console::new(), &options).unwrap();
+        assert_eq!(measurement, expected);
+        let panel = Panel::new(Text::new("ok")).with_title("a long title");
+        let measurement = panel.measure(&Console::new(), &options).unwrap();
+        assert_eq!(measurement.minimum(), 16);
+        let panel = Panel::new(Text::new("ok")).with_width(30);
+        assert_eq!(
+            panel.measure(&Console::new(), &options).unwrap(),
+            Measurement::fixed(20)
+        );
+    }
+
+    #[test]
+    fn test_fit_wraps_content_between_words() {
+        let panel = Panel::fit(Text::new("hello world"));
+        assert_render_eq!(
+            lines(&panel, 12),
+            "╭───────╮\n│ hello │\n│ world │\n╰───────╯"
+        );
+    }
+
+    #[test]
+    fn test_isolates_right_to_left_content() {
+        let panel = Panel::fit(Text::new("שלום world\nabc"));
+        let rendered = lines(&panel, 20);
+        assert_render_eq!(
+            &rendered,
+            "╭────────────╮\n│ \u{2068}שלום\u{2069} world │\n│ abc        │\n╰────────────╯"
+        );
+        for line in &rendered {
+            let right = line.rfind(['│', '╮', '╯']).unwrap();
+            assert_eq!(render_utils::visible_width(&line[..right]), 13);
+        }
+
+        // Content that isolated itself is not isolated again
+        let options = RenderOptions::new()
+            .with_max_width(20)
+            .with_bidi_policy(BidiPolicy::Isolate);
+        let segments = panel.render(&Console::new(), &options).unwrap();
+        assert_eq!(render_lines(&segments), rendered);
+
+        let options = RenderOptions::new()
+            .with_max_width(20)
+            .with_bidi_policy(BidiPolicy::Ignore);
+        let segments = panel.render(&Console::new(), &options).unwrap();
+        assert_eq!(render_lines(&segments)[1], "│ שלום world │");
+    }
+}
@@ -0,0 +1,599 @@
+//! Progress bar - a horizontal bar showing how much of a task is done.
+//!
+//! The bar's characters come from a [`BarGlyphs`] set. Sets with partial
+//! glyphs advance in fractions of a cell, so a slow task still moves the bar
+//! visibly: [`BarGlyphs::blocks`] draws the leading edge with eighth blocks.
+//! A bar fills from the left unless [`ProgressBar::with_direction`] turns
+//! it around, as for a countdown.
+//!
+//! ```rust
+//! use luxor::{Console, RenderOptions, Renderable, components::{BarGlyphs, ProgressBar}};
+//!
+//! let bar = ProgressBar::new(100.0)
+//!     .with_completed(50.0)
+//!     .with_width(12)
+//!     .with_glyphs(BarGlyphs::ascii());
+//! let options = RenderOptions::new().with_max_width(80);
+//! let segments = bar.render(&Console::new(), &options).unwrap();
+//! let plain: String = segments.iter().map(|s| s.text()).collect();
+//! assert_eq!(plain, "[#####-----]");
+//! ```
+
+use crate::{
+    Color, Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style,
+    diagnostics::{self, Warning},
+    render_utils,
+};
+
+/// Eighth blocks from one to seven eighths of a cell, filled from the left.
+const EIGHTHS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Eighth blocks from one to seven eighths of a cell, filled from the
+/// right. Only the one-eighth and half blocks are in the Block Elements;
+/// the rest come from Symbols for Legacy Computing.
+const RIGHT_EIGHTHS: [char; 7] = [
+    '▕',
+    '\u{1fb87}',
+    '\u{1fb88}',
+    '▐',
+    '\u{1fb89}',
+    '\u{1fb8a}',
+    '\u{1fb8b}',
+];
+
+/// Width of the percentage after a bar in accessible mode, as in ` 100%`.
+const PERCENTAGE_WIDTH: usize = 5;
+
+/// A character and its mirror image.
+type MirrorPair = (char, char);
+
+/// Characters and their mirror images, for bars filling from the right.
+const MIRRORED: [MirrorPair; 6] = [
+    ('[', ']'),
+    ('(', ')'),
+    ('<', '>'),
+    ('▏', '▕'),
+    ('▌', '▐'),
+    ('╸', '╺'),
+];
+
+/// Split the fill of a bar `extent` cells long at `ratio` into whole cells
+/// and the steps of the cell after them, with `steps` steps to a cell.
+///
+/// The fill is truncated, so a bar only looks full when the ratio is one.
+pub(crate) fn bar_math(ratio: f64, extent: usize, steps: usize) -> (usize, usize) {
+    let steps = steps.max(1);
+    let filled = (ratio.clamp(0.0, 1.0) * (extent * steps) as f64) as usize;
+    (filled / steps, filled % steps)
+}
+
+/// Get the mirror image of `ch`, or `ch` if it looks the same either way.
+fn mirror(ch: char) -> char {
+    if let Some(index) = EIGHTHS.iter().position(|&eighth| eighth == ch) {
+        return RIGHT_EIGHTHS[index];
+    }
+    if let Some(index) = RIGHT_EIGHTHS.iter().position(|&eighth| eighth == ch) {
+        return EIGHTHS[index];
+    }
+    MIRRORED
+        .iter()
+        .find_map(|&(a, b)| match ch {
+            _ if ch == a => Some(b),
+            _ if ch == b => Some(a),
+            _ => None,
+        })
+        .unwrap_or(ch)
+}
+
+/// The way a [`ProgressBar`] fills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Direction {
+    /// From the left edge toward the right.
+    #[default]
+    LeftToRight,
+    /// From the right edge toward the left.
+    RightToLeft,
+}
+
+/// The characters a [`ProgressBar`] is drawn with.
+///
+/// A cell of the bar is either fully `filled`, `empty`, or at the leading
+/// edge partly filled. With `n` partial glyphs a cell has `n + 1` steps, the
+/// partial glyph for `k` steps being `partial[k - 1]`; without any the bar
+/// advances a whole cell at a time. The caps are drawn outside the cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BarGlyphs {
+    /// Drawn before the first cell; may be empty.
+    pub left_cap: String,
+    /// A completed cell.
+    pub filled: char,
+    /// Partly completed cells, from least to most filled.
+    pub partial: Vec<char>,
+    /// A remaining cell.
+    pub empty: char,
+    /// Drawn after the last cell; may be empty.
+    pub right_cap: String,
+}
+
+impl BarGlyphs {
+    /// Full blocks with eighth-block precision, between thin edges.
+    pub fn blocks() -> Self {
+        Self {
+            left_cap: "▕".to_string(),
+            filled: '█',
+            partial: EIGHTHS.to_vec(),
+            empty: ' ',
+            right_cap: "▏".to_string(),
+        }
+    }
+
+    /// A heavy line over a light one, with half-cell precision.
+    pub fn simple() -> Self {
+        Self {
+            left_cap: String::new(),
+            filled: '━',
+            partial: vec!['╸'],
+            empty: '─',
+            right_cap: String::new(),
+        }
+    }
+
+    /// Hashes and dashes in brackets, for terminals without Unicode.
+    pub fn ascii() -> Self {
+        Self {
+            left_cap: "[".to_string(),
+            filled: '#',
+            partial: Vec::new(),
+            empty: '-',
+            right_cap: "]".to_string(),
+        }
+    }
+
+    /// Check if every glyph is ASCII.
+    pub fn is_ascii(&self) -> bool {
+        self.left_cap.is_ascii()
+            && self.right_cap.is_ascii()
+            && self.filled.is_ascii()
+            && self.empty.is_ascii()
+            && self.partial.iter().all(char::is_ascii)
+    }
+
+    /// Get the mirror image of the set, for a bar filling from the right:
+    /// the caps swap sides and partial glyphs fill from the right.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::components::BarGlyphs;
+    ///
+    /// let glyphs = BarGlyphs::simple().mirrored();
+    /// assert_eq!(glyphs.partial, vec!['╺']);
+    /// assert_eq!(BarGlyphs::ascii().mirrored(), BarGlyphs::ascii());
+    /// ```
+    pub fn mirrored(&self) -> Self {
+        let mirror_str = |s: &str| s.chars().rev().map(mirror).collect();
+        Self {
+            left_cap: mirror_str(&self.right_cap),
+            filled: mirror(self.filled),
+            partial: self.partial.iter().copied().map(mirror).collect(),
+            empty: mirror(self.empty),
+            right_cap: mirror_str(&self.left_cap),
+        }
+    }
+
+    /// Get the width of both caps in cells.
+    fn caps_width(&self) -> usize {
+        render_utils::visible_width(&self.left_cap) + render_utils::visible_width(&self.right_cap)
+    }
+}
+
+impl Default for BarGlyphs {
+    fn default() -> Self {
+        Self::simple()
+    }
+}
+
+/// A single-line bar showing the completed fraction of a total.
+///
+/// The completed part, the remaining part and the bar once finished each
+/// have their own style. The defaults follow Rich: pink while in progress,
+/// green when finished, over dark grey. With
+/// [`RenderOptions::ascii_only`] set, glyph sets using other characters
+/// are replaced by [`BarGlyphs::ascii`]. With [`RenderOptions::accessible`]
+/// set, the completed percentage follows the bar, within its width.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, components::ProgressBar};
+///
+/// let bar = ProgressBar::new(3.0).with_completed(1.0);
+/// let options = RenderOptions::new().with_max_width(9);
+/// let segments = bar.render(&Console::new(), &options).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, "━━━──────");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressBar {
+    total: f64,
+    completed: f64,
+    width: Option<usize>,
+    glyphs: BarGlyphs,
+    direction: Direction,
+    complete_style: Style,
+    finished_style: Style,
+    remaining_style: Style,
+}
+
+impl ProgressBar {
+    /// Create an empty bar for a task of size `total`.
+    pub fn new(total: f64) -> Self {
+        Self {
+            total,
+            completed: 0.0,
+            width: None,
+            glyphs: BarGlyphs::default(),
+            direction: Direction::default(),
+            complete_style: Style::new().with_color(Color::rgb(249, 38, 114)),
+            finished_style: Style::new().with_color(Color::rgb(114, 156, 31)),
+            remaining_style: Style::new().with_color(Color::EightBit(237)),
+        }
+    }
+
+    /// Set how much of the total is done.
+    pub fn with_completed(mut self, completed: f64) -> Self {
+        self.completed = completed;
+        self
+    }
+
+    /// Set the width of the bar in cells, including caps.
+    ///
+    /// Without a width the bar fills the available width.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Set the characters the bar is drawn with.
+    pub fn with_glyphs(mut self, glyphs: BarGlyphs) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Set the way the bar fills, from the left by default.
+    ///
+    /// A bar filling from the right draws its glyphs mirrored, as
+    /// [`BarGlyphs::mirrored`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, RenderOptions, Renderable};
+    /// use luxor::components::{BarGlyphs, Direction, ProgressBar};
+    ///
+    /// let bar = ProgressBar::new(4.0)
+    ///     .with_completed(1.0)
+    ///     .with_width(10)
+    ///     .with_glyphs(BarGlyphs::ascii())
+    ///     .with_direction(Direction::RightToLeft);
+    /// let segments = bar.render(&Console::new(), &RenderOptions::new()).unwrap();
+    /// let plain: String = segments.iter().map(|s| s.text()).collect();
+    /// assert_eq!(plain, "[------##]");
+    /// ```
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set the style of the completed part while in progress.
+    pub fn with_complete_style(mut self, style: Style) -> Self {
+        self.complete_style = style;
+        self
+    }
+
+    /// Set the style of the completed part once finished.
+    pub fn with_finished_style(mut self, style: Style) -> Self {
+        self.finished_style = style;
+        self
+    }
+
+    /// Set the style of the remaining part.
+    pub fn with_remaining_style(mut self, style: Style) -> Self {
+        self.remaining_style = style;
+        self
+    }
+
+    /// Get the completed fraction, between 0 and 1.
+    ///
+    /// A total of zero or less counts as no progress.
+    pub fn ratio(&self) -> f64 {
+        if self.total > 0.0 {
+            (self.completed / self.total).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Check if the whole total is done.
+    pub fn is_finished(&self) -> bool {
+        self.ratio() >= 1.0
+    }
+
+    /// Draw the cells of the bar as completed and remaining text, the
+    /// completed part starting from the edge it fills from.
+    fn cells(&self, glyphs: &BarGlyphs, cells: usize) -> (String, String) {
+        let (filled, remainder) = bar_math(self.ratio(), cells, glyphs.partial.len() + 1);
+
+        let mut completed: String = std::iter::repeat(glyphs.filled).take(filled).collect();
+        let mut used = filled;
+        if remainder > 0 && used < cells {
+            completed.push(glyphs.partial[remainder - 1]);
+            used += 1;
+        }
+        let remaining = std::iter::repeat(glyphs.empty).take(cells - used).collect();
+        (completed, remaining)
+    }
+}
+
+impl Renderable for ProgressBar {
+    fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let glyphs = if options.ascii_only && !self.glyphs.is_ascii() {
+            diagnostics::warn(|| Warning::AsciiFallback {
+                component: "ProgressBar",
+            });
+            BarGlyphs::ascii()
+        } else {
+            self.glyphs.clone()
+        };
+        let glyphs = match self.direction {
+            Direction::LeftToRight => glyphs,
+            Direction::RightToLeft => glyphs.mirrored(),
+        };
+        let width = self
+            .width
+            .unwrap_or(usize::MAX)
+            .min(options.get_max_width());
+        let percentage = options.accessible.then(|| {
+            let percent = (self.ratio() * 100.0) as usize;
+            format!(" {:>3}%", percent)
+        });
+        let bar_width = width.saturating_sub(percentage.as_ref().map_or(0, |_| PERCENTAGE_WIDTH));
+        let cells = bar_width.saturating_sub(glyphs.caps_width());
+
+        let (completed, remaining) = self.cells(&glyphs, cells);
+        let complete_style = if self.is_finished() {
+            self.finished_style.clone()
+        } else {
+            self.complete_style.clone()
+        };
+        // The cap at the filling edge joins the completed part, the other
+        // one the remaining part until nothing remains
+        let leading_cap_style = if completed.is_empty() {
+            self.remaining_style.clone()
+        } else {
+            complete_style.clone()
+        };
+        let trailing_cap_style = if remaining.is_empty() && cells > 0 {
+            complete_style.clone()
+        } else {
+            self.remaining_style.clone()
+        };
+
+        let parts = match self.direction {
+            Direction::LeftToRight => [
+                Segment::new(glyphs.left_cap, leading_cap_style),
+                Segment::new(completed, complete_style),
+                Segment::new(remaining, self.remaining_style.clone()),
+                Segment::new(glyphs.right_cap, trailing_cap_style),
+            ],
+            Direction::RightToLeft => [
+                Segment::new(glyphs.left_cap, trailing_cap_style),
+                Segment::new(remaining, self.remaining_style.clone()),
+                Segment::new(completed.chars().rev().collect::<String>(), complete_style),
+                Segment::new(glyphs.right_cap, leading_cap_style),
+            ],
+        };
+        let line = parts
+            .into_iter()
+            .chain(percentage.map(|percentage| Segment::new(percentage, Style::new())))
+            .filter(|segment| !segment.text().is_empty())
+            .collect();
+        // Caps wider than the bar are cropped
+        Ok(render_utils::adjust_line_length(
+            line,
+            width,
+            Style::default(),
+        ))
+    }
+}
+
+impl Measurable for ProgressBar {
+    fn measure(&self, _console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        Ok(match self.width {
+            Some(width) => Measurement::fixed(width),
+            None => {
+                let percentage = if options.accessible {
+                    PERCENTAGE_WIDTH
+                } else {
+                    0
+                };
+                let minimum = self.glyphs.caps_width() + 1 + percentage;
+                Measurement::new(minimum, options.get_max_width())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::render_lines;
+
+    fn render(bar: &ProgressBar, options: &RenderOptions) -> Vec<Segment> {
+        bar.render(&Console::new(), options).unwrap()
+    }
+
+    fn plain(glyphs: BarGlyphs, ratio: f64) -> String {
+        let bar = ProgressBar::new(1.0)
+            .with_completed(ratio)
+            .with_width(10)
+            .with_glyphs(glyphs);
+        let segments = render(&bar, &RenderOptions::new().with_max_width(80));
+        segments.iter().map(|s| s.text()).collect()
+    }
+
+    #[test]
+    fn test_blocks_glyphs() {
+        assert_eq!(plain(BarGlyphs::blocks(), 0.0), "▕        ▏");
+        assert_eq!(plain(BarGlyphs::blocks(), 0.333), "▕██▋     ▏");
+        assert_eq!(plain(BarGlyphs::blocks(), 0.5), "▕████    ▏");
+        assert_eq!(plain(BarGlyphs::blocks(), 1.0), "▕████████▏");
+    }
+
+    #[test]
+    fn test_simple_glyphs() {
+        assert_eq!(plain(BarGlyphs::simple(), 0.0), "──────────");
+        assert_eq!(plain(BarGlyphs::simple(), 0.25), "━━╸───────");
+        assert_eq!(plain(BarGlyphs::simple(), 0.333), "━━━───────");
+        assert_eq!(plain(BarGlyphs::simple(), 0.5), "━━━━━─────");
+        assert_eq!(plain(BarGlyphs::simple(), 1.0), "━━━━━━━━━━");
+    }
+
+    #[test]
+    fn test_ascii_glyphs() {
+        assert_eq!(plain(BarGlyphs::ascii(), 0.0), "[--------]");
+        assert_eq!(plain(BarGlyphs::ascii(), 0.333), "[##------]");
+        assert_eq!(plain(BarGlyphs::ascii(), 0.5), "[####----]");
+        assert_eq!(plain(BarGlyphs::ascii(), 1.0), "[########]");
+    }
+
+    #[test]
+    fn test_eighth_steps() {
+        // Each eighth of a cell advances the leading edge
+        let edges: Vec<String> = (0..=8)
+            .map(|eighths| plain(BarGlyphs::blocks(), eighths as f64 / 64.0))
+            .collect();
+        assert_eq!(edges[0], "▕        ▏");
+        assert_eq!(edges[3], "▕▍       ▏");
+        assert_eq!(edges[7], "▕▉       ▏");
+        assert_eq!(edges[8], "▕█       ▏");
+    }
+
+    fn right_to_left(glyphs: BarGlyphs, ratio: f64) -> String {
+        let bar = ProgressBar::new(1.0)
+            .with_completed(ratio)
+            .with_width(10)
+            .with_glyphs(glyphs)
+            .with_direction(Direction::RightToLeft);
+        let segments = render(&bar, &RenderOptions::new().with_max_width(80));
+        segments.iter().map(|s| s.text()).collect()
+    }
+
+    #[test]
+    fn test_right_to_left() {
+        assert_eq!(right_to_left(BarGlyphs::blocks(), 0.0), "▕        ▏");
+        assert_eq!(
+            right_to_left(BarGlyphs::blocks(), 0.33),
+            "▕     \u{1fb89}██▏"
+        );
+        assert_eq!(right_to_left(BarGlyphs::blocks(), 1.0), "▕████████▏");
+        assert_eq!(right_to_left(BarGlyphs::simple(), 0.0), "──────────");
+        assert_eq!(right_to_left(BarGlyphs::simple(), 0.25), "───────╺━━");
+        assert_eq!(right_to_left(BarGlyphs::simple(), 1.0), "━━━━━━━━━━");
+        assert_eq!(right_to_left(BarGlyphs::ascii(), 0.33), "[------##]");
+
+        let left = BarGlyphs {
+            left_cap: "<(".to_string(),
+            right_cap: "▌".to_string(),
+            ..BarGlyphs::blocks()
+        };
+        let mirrored = left.mirrored();
+        assert_eq!(
+            (mirrored.left_cap.as_str(), mirrored.right_cap.as_str()),
+            ("▐", ")>")
+        );
+        assert_eq!(mirrored.mirrored(), left);
+        assert_eq!(bar_math(0.33, 5, 8), (1, 5));
+        assert_eq!(bar_math(2.0, 5, 8), (5, 0));
+    }
+
+    #[test]
+    fn test_right_to_left_cap_styles() {
+        let complete = Style::parse("magenta").unwrap();
+        let remaining = Style::parse("bright_black").unwrap();
+        let bar = ProgressBar::new(4.0)
+            .with_completed(2.0)
+            .with_width(6)
+            .with_glyphs(BarGlyphs::ascii())
+            .with_complete_style(complete.clone())
+            .with_remaining_style(remaining.clone())
+            .with_direction(Direction::RightToLeft);
+        let segments = render(&bar, &RenderOptions::new().with_max_width(80));
+        assert_eq!(
+            segments,
+            vec![
+                Segment::new("[", remaining.clone()),
+                Segment::new("--", remaining),
+                Segment::new("##", complete.clone()),
+                Segment::new("]", complete),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_styles_and_finished() {
+        let complete = Style::parse("magenta").unwrap();
+        let finished = Style::parse("green").unwrap();
+        let remaining = Style::parse("bright_black").unwrap();
+        let bar = ProgressBar::new(4.0)
+            .with_width(4)
+            .with_complete_style(complete.clone())
+            .with_finished_style(finished.clone())
+            .with_remaining_style(remaining.clone());
+        let options = RenderOptions::new().with_max_width(80);
+
+        let segments = render(&bar.clone().with_completed(2.0), &options);
+        assert_eq!(
+            segments,
+            vec![Segment::new("━━", complete), Segment::new("──", remaining),]
+        );
+
+        let done = bar.with_completed(5.0);
+        assert!(done.is_finished());
+        assert_eq!(
+            render(&done, &options),
+            vec![Segment::new("━━━━", finished)]
+        );
+    }
+
+    #[test]
+    fn test_accessible_shows_percentage() {
+        let bar = ProgressBar::new(3.0).with_completed(1.0).with_width(14);
+        let options = RenderOptions::new().with_max_width(80);
+        crate::assert_render_eq!(render_lines(&render(&bar, &options)), "━━━━╸─────────");
+        let accessible = options.with_accessible(true);
+        crate::assert_render_eq!(render_lines(&render(&bar, &accessible)), "━━━──────  33%");
+        let done = bar.with_completed(3.0);
+        crate::assert_render_eq!(render_lines(&render(&done, &accessible)), "━━━━━━━━━ 100%");
+
+        let bar = ProgressBar::new(1.0).with_glyphs(BarGlyphs::ascii());
+        let measurement = bar.measure(&Console::new(), &accessible).unwrap();
+        assert_eq!(measurement.minimum(), 8);
+    }
+
+    #[test]
+    fn test_ascii_fallback_and_width() {
+        let bar = ProgressBar::new(2.0)
+            .with_completed(1.0)
+            .with_glyphs(BarGlyphs::blocks());
+        let options = RenderOptions::new()
+            .with_max_width(10)
+            .with_ascii_only(true);
+        crate::assert_render_eq!(render_lines(&render(&bar, &options)), "[####----]");
+
+        let console = Console::new();
+        let measurement = bar.measure(&console, &options).unwrap();
+        assert_eq!((measurement.minimum(), measurement.maximum()), (3, 10));
+        assert_eq!(ProgressBar::new(0.0).ratio(), 0.0);
+    }
+}
@@ -0,0 +1,245 @@
+//! Spacer and Blank - placeholder renderables for gaps between content.
+//!
+//! Containers insert these instead of ad-hoc space strings so that the cell
+//! width and background style of every gap is decided in one place.
+
+use crate::{Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style};
+
+/// The kind of space a [`Spacer`] occupies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpacerKind {
+    /// A fixed number of cells on the current line.
+    Width(usize),
+    /// A fixed number of blank lines.
+    Lines(usize),
+    /// All remaining width on the current line.
+    Fill,
+}
+
+/// A renderable that occupies empty space.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, components::Spacer};
+///
+/// let console = Console::new();
+/// let options = RenderOptions::new().with_max_width(10);
+/// let segments = Spacer::width(3).render(&console, &options).unwrap();
+/// assert_eq!(segments[0].text(), "   ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spacer {
+    kind: SpacerKind,
+    style: Style,
+}
+
+impl Spacer {
+    /// Create a spacer that renders `width` unstyled spaces.
+    pub fn width(width: usize) -> Self {
+        Self {
+            kind: SpacerKind::Width(width),
+            style: Style::default(),
+        }
+    }
+
+    /// Create a spacer that renders `count` blank lines.
+    pub fn lines(count: usize) -> Self {
+        Self {
+            kind: SpacerKind::Lines(count),
+            style: Style::default(),
+        }
+    }
+
+    /// Create a spacer that expands to consume the remaining width.
+    ///
+    /// The fill spacer measures as `0..max_width`, so containers can hand it
+    /// whatever width is left over after sizing their other children.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, RenderOptions, Measurable, components::Spacer};
+    ///
+    /// let console = Console::new();
+    /// let options = RenderOptions::new().with_max_width(30);
+    /// let measurement = Spacer::fill().measure(&console, &options).unwrap();
+    /// assert_eq!(measurement.minimum(), 0);
+    /// assert_eq!(measurement.maximum(), 30);
+    /// ```
+    pub fn fill() -> Self {
+        Self {
+            kind: SpacerKind::Fill,
+            style: Style::default(),
+        }
+    }
+
+    /// Set the style applied to the spaces, typically a background color.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Get the style applied to the spaces.
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Check if this spacer expands to fill the remaining width.
+    pub fn is_fill(&self) -> bool {
+        self.kind == SpacerKind::Fill
+    }
+
+    fn spaces(&self, width: usize) -> Vec<Segment> {
+        if width == 0 {
+            Vec::new()
+        } else {
+            vec![Segment::pad(" ".repeat(width), self.style.clone())]
+        }
+    }
+}
+
+impl Renderable for Spacer {
+    fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        match self.kind {
+            SpacerKind::Width(width) => Ok(self.spaces(width.min(options.get_max_width()))),
+            SpacerKind::Lines(count) => Ok((0..count).map(|_| Segment::line()).collect()),
+            SpacerKind::Fill => Ok(self.spaces(options.get_max_width())),
+        }
+    }
+}
+
+impl Measurable for Spacer {
+    fn measure(&self, _console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        match self.kind {
+            SpacerKind::Width(width) => Ok(Measurement::fixed(width)),
+            SpacerKind::Lines(_) => Ok(Measurement::fixed(0)),
+            SpacerKind::Fill => Ok(Measurement::span(options.get_max_width())),
+        }
+    }
+}
+
+/// A renderable that produces no output and measures as zero width.
+///
+/// Useful as a placeholder child where a container requires a renderable but
+/// nothing should be shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Blank;
+
+impl Renderable for Blank {
+    fn render(&self, _console: &Console, _options: &RenderOptions) -> Result<Vec<Segment>> {
+        Ok(Vec::new())
+    }
+}
+
+impl Measurable for Blank {
+    fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+        Ok(Measurement::fixed(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Text, testing::render_lines};
+
+    #[test]
+    fn test_spacer_width() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(80);
+        let spacer = Spacer::width(4);
+
+        let segments = spacer.render(&console, &options).unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text(), "    ");
+        assert!(segments[0].style().is_empty());
+        assert_eq!(
+            spacer.measure(&console, &options).unwrap(),
+            Measurement::fixed(4)
+        );
+    }
+
+    #[test]
+    fn test_spacer_zero_width() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(80);
+        assert!(
+            Spacer::width(0)
+                .render(&console, &options)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_spacer_lines() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(80);
+        let spacer = Spacer::lines(3);
+
+        let segments = spacer.render(&console, &options).unwrap();
+        assert_eq!(segments.len(), 3);
+        assert!(segments.iter().all(|s| s.text() == "\n"));
+        assert_eq!(
+            spacer.measure(&console, &options).unwrap(),
+            Measurement::fixed(0)
+        );
+    }
+
+    #[test]
+    fn test_spacer_style() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(80);
+        let style = Style::new().with_background(Color::rgb(0, 0, 255));
+        let segments = Spacer::width(2)
+            .with_style(style.clone())
+            .render(&console, &options)
+            .unwrap();
+        assert_eq!(segments[0].style(), &style);
+    }
+
+    #[test]
+    fn test_spacer_fill_between_texts() {
+        let console = Console::new();
+        let width = 30;
+        let options = RenderOptions::new().with_max_width(width);
+        let left = Text::new("left");
+        let right = Text::new("right");
+
+        let used = left.measure(&console, &options).unwrap().maximum()
+            + right.measure(&console, &options).unwrap().maximum();
+        let fill_options = options.clone().with_max_width(width - used);
+
+        let mut line = left.render(&console, &options).unwrap();
+        line.extend(Spacer::fill().render(&console, &fill_options).unwrap());
+        line.extend(right.render(&console, &options).unwrap());
+
+        let cells: usize = line.iter().map(|s| s.cell_length()).sum();
+        assert_eq!(cells, width);
+        crate::assert_render_eq!(render_lines(&line), format!("left{}right", " ".repeat(21)));
+    }
+
+    #[test]
+    fn test_spacer_fill_measure() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(30);
+        let spacer = Spacer::fill();
+
+        assert!(spacer.is_fill());
+        assert_eq!(
+            spacer.measure(&console, &options).unwrap(),
+            Measurement::span(30)
+        );
+    }
+
+    #[test]
+    fn test_blank() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(80);
+        assert!(Blank.render(&console, &options).unwrap().is_empty());
+        assert_eq!(
+            Blank.measure(&console, &options).unwrap(),
+            Measurement::fixed(0)
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! Spinner - an animated indicator for work of unknown length.
+//!
+//! A spinner shows the frame for the current time each time it is
+//! rendered, so redrawing it at [`Animation::next_frame_deadline`] is all
+//! the animation needs.
+
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Segments, Style,
+    Text,
+    clock::{Animation, SharedClock, SystemClock, Timer},
+    diagnostics::{self, Warning},
+    render_utils,
+};
+use std::time::{Duration, Instant};
+
+/// Braille dots, the default frames.
+const DOTS: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+/// Frame interval for [`DOTS`].
+const DOTS_INTERVAL: Duration = Duration::from_millis(80);
+/// A rotating line, used when only ASCII may be drawn.
+const LINE: &[&str] = &["-", "\\", "|", "/"];
+/// Frame interval for [`LINE`].
+const LINE_INTERVAL: Duration = Duration::from_millis(130);
+
+/// An animated spinner with optional text after it.
+///
+/// The frame is chosen from the time since the spinner was created, read
+/// from its clock. When [`RenderOptions::ascii_only`] is set and the frames
+/// are not ASCII, a rotating line is drawn instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, ManualClock, RenderOptions, Renderable, Spinner};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// let spinner = Spinner::new().with_clock(clock.shared()).with_text("loading");
+/// let options = RenderOptions::new().with_max_width(40).with_ascii_only(false);
+/// let plain = |spinner: &Spinner| -> String {
+///     let segments = spinner.render(&Console::new(), &options).unwrap();
+///     segments.iter().map(|s| s.text()).collect()
+/// };
+/// assert_eq!(plain(&spinner), "⠋ loading");
+/// clock.advance(Duration::from_millis(80));
+/// assert_eq!(plain(&spinner), "⠙ loading");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spinner {
+    frames: Vec<String>,
+    timer: Timer,
+    text: Option<Text>,
+    style: Style,
+}
+
+impl Spinner {
+    /// Create a braille-dot spinner on the system clock.
+    pub fn new() -> Self {
+        Self::with_frames_on(DOTS, DOTS_INTERVAL, SystemClock::shared())
+    }
+
+    /// Create a rotating-line spinner, drawn with ASCII only.
+    pub fn line() -> Self {
+        Self::with_frames_on(LINE, LINE_INTERVAL, SystemClock::shared())
+    }
+
+    fn with_frames_on(frames: &[&str], interval: Duration, clock: SharedClock) -> Self {
+        Self {
+            frames: frames.iter().map(|frame| frame.to_string()).collect(),
+            timer: Timer::new(clock).with_interval(interval),
+            text: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Use custom frames, each shown for `interval`.
+    ///
+    /// An empty list of frames draws nothing; a zero interval shows the
+    /// first frame only.
+    pub fn with_frames<S: Into<String>>(
+        mut self,
+        frames: impl IntoIterator<Item = S>,
+        interval: Duration,
+    ) -> Self {
+        self.frames = frames.into_iter().map(Into::into).collect();
+        self.timer = Timer::new(self.timer.clock().clone()).with_interval(interval);
+        self
+    }
+
+    /// Read the time from `clock`, starting the animation now.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        let interval = self.timer.interval().unwrap_or_default();
+        self.timer = Timer::new(clock).with_interval(interval);
+        self
+    }
+
+    /// Set the text shown after the spinner.
+    pub fn with_text(mut self, text: impl Into<Text>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Set the style of the spinner frames.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Restart the animation from the first frame.
+    pub fn reset(&mut self) {
+        self.timer.restart();
+    }
+
+    /// Get the frame for the current time.
+    pub fn frame(&self) -> &str {
+        self.frame_in(self.frames.iter().map(String::as_str))
+            .unwrap_or("")
+    }
+
+    /// Get the time since the spinner started.
+    pub fn elapsed(&self) -> Duration {
+        self.timer.elapsed()
+    }
+
+    fn frame_in<'a>(&self, frames: impl ExactSizeIterator<Item = &'a str>) -> Option<&'a str> {
+        let mut frames = frames;
+        let count = frames.len();
+        if count == 0 {
+            return None;
+        }
+        let index = (self.timer.ticks() % count as u128) as usize;
+        frames.nth(index)
+    }
+
+    /// Get the frame to draw with `options`.
+    fn frame_for(&self, options: &RenderOptions) -> &str {
+        if options.ascii_only && !self.frames.iter().all(|frame| frame.is_ascii()) {
+            diagnostics::warn(|| Warning::AsciiFallback {
+                component: "Spinner",
+            });
+            return self.frame_in(LINE.iter().copied()).unwrap_or("");
+        }
+        self.frame()
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Animation for Spinner {
+    fn next_frame_deadline(&self) -> Option<Instant> {
+        if self.frames.len() < 2 {
+            return None;
+        }
+        self.timer.deadline()
+    }
+}
+
+impl Renderable for Spinner {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let mut segments = vec![Segment::new(self.frame_for(options), self.style.clone())];
+        if let Some(text) = &self.text {
+            segments.push(Segment::new(" ", Style::default()));
+            let options = options.clone().with_soft_wrap(true);
+            segments.extend(text.render(console, &options)?);
+        }
+        let mut line: Segments = render_utils::split_lines(segments)
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        line.truncate_cells(options.get_max_width());
+        Ok(line.into_vec())
+    }
+}
+
+impl Measurable for Spinner {
+    fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+        let frame = self
+            .frames
+            .iter()
+            .map(|frame| render_utils::visible_width(frame))
+            .max()
+            .unwrap_or(0);
+        let text = self
+            .text
+            .as_ref()
+            .map_or(0, |text| render_utils::visible_width(text.plain()) + 1);
+        Ok(Measurement::fixed(frame + text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ManualClock,
+        clock::{Clock, next_deadline},
+        testing::render_lines,
+    };
+
+    fn render(spinner: &Spinner, ascii_only: bool) -> Vec<String> {
+        let options = RenderOptions::new()
+            .with_max_width(20)
+            .with_ascii_only(ascii_only);
+        render_lines(&spinner.render(&Console::new(), &options).unwrap())
+    }
+
+    #[test]
+    fn test_spinner_frames_follow_clock() {
+        let clock = ManualClock::new();
+        let spinner = Spinner::new().with_clock(clock.shared()).with_text("build");
+        crate::assert_render_eq!(render(&spinner, false), "⠋ build");
+        crate::assert_render_eq!(render(&spinner, true), "- build");
+
+        clock.advance(Duration::from_millis(80 * 11));
+        crate::assert_render_eq!(render(&spinner, false), "⠙ build");
+        // The ASCII fallback steps at the spinner's interval, cycling in four
+        crate::assert_render_eq!(render(&spinner, true), "/ build");
+
+        let empty = Spinner::new().with_frames(Vec::<String>::new(), DOTS_INTERVAL);
+        assert_eq!(empty.frame(), "");
+        assert_eq!(empty.next_frame_deadline(), None);
+    }
+
+    #[test]
+    fn test_spinner_deadlines_with_manual_clock() {
+        let clock = ManualClock::new();
+        let fast = Spinner::new().with_clock(clock.shared());
+        let slow = Spinner::line().with_clock(clock.shared());
+        let start = clock.now();
+        let spinners = [&fast as &dyn Animation, &slow];
+
+        assert_eq!(
+            next_deadline(spinners),
+            Some(start + Duration::from_millis(80))
+        );
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(
+            next_deadline(spinners),
+            Some(start + Duration::from_millis(130))
+        );
+        clock.advance(Duration::from_millis(30));
+        assert_eq!(
+            next_deadline(spinners),
+            Some(start + Duration::from_millis(160))
+        );
+    }
+
+    #[test]
+    fn test_spinner_truncates_to_width() {
+        let spinner = Spinner::line()
+            .with_clock(ManualClock::new().shared())
+            .with_text("a very long status message");
+        crate::assert_render_eq!(render(&spinner, true), "- a very long status");
+        let measurement = spinner
+            .measure(&Console::new(), &RenderOptions::new())
+            .unwrap();
+        assert_eq!(measurement.maximum(), 28);
+    }
+}
@@ -0,0 +1,488 @@
+//! TaskList - build-tool style progress through a list of steps.
+//!
+//! Each task is a line: waiting tasks are dim, the running one spins and
+//! counts up its elapsed time, and finished ones show a mark and how long
+//! they took. A failed task is followed by the first lines of its error,
+//! indented under it.
+//!
+//! ```text
+//! ✓ fetch (1.2s)
+//! ⠋ compile (3.4s)
+//! · link
+//! ```
+//!
+//! The list is redrawn by printing it again, or by whatever drives the
+//! other animations; [`Animation::next_frame_deadline`] says when.
+
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Segments, Style,
+    Text,
+    clock::{Animation, SharedClock, SystemClock},
+    color::{Color, StandardColor},
+    components::Spinner,
+    render_utils,
+    rendered::Line,
+};
+use std::time::{Duration, Instant};
+
+/// The indent of error excerpts under a failed task.
+const ERROR_INDENT: usize = 4;
+/// The number of error lines shown by default.
+const DEFAULT_ERROR_LINES: usize = 5;
+
+/// A handle to a task in a [`TaskList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(usize);
+
+/// Where a task is in its lifecycle.
+#[derive(Debug, Clone)]
+pub enum TaskState {
+    /// Not started yet.
+    Pending,
+    /// Started, with the spinner drawn in front of it; the spinner's
+    /// elapsed time is the task's.
+    Running(Spinner),
+    /// Finished successfully after the given time.
+    Done(Duration),
+    /// Finished with the given error.
+    Failed(Text),
+    /// Not run.
+    Skipped,
+}
+
+impl TaskState {
+    /// Check whether the task has finished, one way or another.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done(_) | Self::Failed(_) | Self::Skipped)
+    }
+
+    /// Get the word for the state drawn after the mark in accessible mode.
+    fn status(&self) -> &'static str {
+        match self {
+            Self::Pending => "[WAIT]",
+            Self::Running(_) => "[RUN]",
+            Self::Done(_) => "[OK]",
+            Self::Failed(_) => "[FAIL]",
+            Self::Skipped => "[SKIP]",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Task {
+    name: Text,
+    state: TaskState,
+}
+
+/// An ordered list of tasks, each pending, running or finished.
+///
+/// With [`RenderOptions::accessible`] set, the mark of each task is
+/// followed by its state in words, such as `[OK]` or `[FAIL]`.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, ManualClock, RenderOptions, Renderable, components::TaskList};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// let mut tasks = TaskList::new().with_clock(clock.shared());
+/// let fetch = tasks.add_task("fetch");
+/// let build = tasks.add_task("build");
+/// tasks.start(fetch);
+/// clock.advance(Duration::from_millis(1200));
+/// tasks.complete(fetch);
+/// tasks.start(build);
+///
+/// let options = RenderOptions::new().with_max_width(40).with_ascii_only(false);
+/// let segments = tasks.render(&Console::new(), &options).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, "✓ fetch (1.2s)\n⠋ build (0ms)");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TaskList {
+    tasks: Vec<Task>,
+    clock: SharedClock,
+    error_lines: usize,
+}
+
+impl TaskList {
+    /// Create an empty list on the system clock.
+    pub fn new() -> Self {
+        Self {
+            tasks: Vec::new(),
+            clock: SystemClock::shared(),
+            error_lines: DEFAULT_ERROR_LINES,
+        }
+    }
+
+    /// Time the tasks with `clock`.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Show at most `lines` lines of each error, five by default.
+    pub fn with_error_lines(mut self, lines: usize) -> Self {
+        self.error_lines = lines;
+        self
+    }
+
+    /// Add a pending task at the end of the list.
+    pub fn add_task(&mut self, name: impl Into<Text>) -> TaskId {
+        self.tasks.push(Task {
+            name: name.into(),
+            state: TaskState::Pending,
+        });
+        TaskId(self.tasks.len() - 1)
+    }
+
+    /// Start the task `id`, restarting its time if it was running.
+    pub fn start(&mut self, id: TaskId) {
+        let spinner = Spinner::new().with_clock(self.clock.clone());
+        self.set_state(id, TaskState::Running(spinner));
+    }
+
+    /// Finish the task `id` successfully. A task that never started took
+    /// no time.
+    pub fn complete(&mut self, id: TaskId) {
+        let elapsed = self.elapsed(id);
+        self.set_state(id, TaskState::Done(elapsed));
+    }
+
+    /// Finish the task `id` with `error`, shown under it.
+    pub fn fail(&mut self, id: TaskId, error: impl Into<Text>) {
+        self.set_state(id, TaskState::Failed(error.into()));
+    }
+
+    /// Mark the task `id` as not run.
+    pub fn skip(&mut self, id: TaskId) {
+        self.set_state(id, TaskState::Skipped);
+    }
+
+    /// Get the state of the task `id`.
+    pub fn state(&self, id: TaskId) -> Option<&TaskState> {
+        self.tasks.get(id.0).map(|task| &task.state)
+    }
+
+    /// Check whether every task has finished.
+    pub fn is_finished(&self) -> bool {
+        self.tasks.iter().all(|task| task.state.is_finished())
+    }
+
+    fn set_state(&mut self, id: TaskId, state: TaskState) {
+        if let Some(task) = self.tasks.get_mut(id.0) {
+            task.state = state;
+        }
+    }
+
+    fn elapsed(&self, id: TaskId) -> Duration {
+        match self.state(id) {
+            Some(TaskState::Running(spinner)) => spinner.elapsed(),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Render the line of `task`, without its error.
+    fn task_line(
+        &self,
+        task: &Task,
+        console: &Console,
+        options: &RenderOptions,
+    ) -> Result<Vec<Segment>> {
+        let ascii = options.ascii_only;
+        let (mark, style, elapsed) = match &task.state {
+            TaskState::Pending => {
+                let mark = Segment::new(if ascii { "." } else { "·" }, Style::new());
+                (vec![mark], Style::new().dim(), None)
+            }
+            TaskState::Running(spinner) => {
+                let mark = options.render_child(spinner, console)?;
+                (mark, Style::new(), Some(spinner.elapsed()))
+            }
+            TaskState::Done(elapsed) => {
+                let style = Style::new().with_color(Color::Standard(StandardColor::Green));
+                let mark = Segment::new(if ascii { "+" } else { "✓" }, style);
+                (vec![mark], Style::new(), Some(*elapsed))
+            }
+            TaskState::Failed(_) => {
+                let style = Style::new().with_color(Color::Standard(StandardColor::Red));
+                let mark = Segment::new(if ascii { "x" } else { "✗" }, style);
+                (vec![mark], Style::new(), None)
+            }
+            TaskState::Skipped => {
+                let mark = Segment::new(if ascii { "-" } else { "–" }, Style::new());
+                (vec![mark], Style::new().dim(), None)
+            }
+        };
+
+        let mut line = mark;
+        line.push(Segment::new(" ", Style::new()));
+        if options.accessible {
+            line.push(Segment::new(task.state.status(), Style::new()));
+            line.push(Segment::new(" ", Style::new()));
+        }
+        line.extend(task.name.to_segments());
+        if let Some(elapsed) = elapsed {
+            let label = format!(" ({})", format_duration(elapsed));
+            line.push(Segment::new(label, Style::new().dim()));
+        }
+        let mut line: Segments = line
+            .into_iter()
+            .map(|segment| {
+                let combined = style.clone().combine(segment.style().clone());
+                Segment::new(segment.text(), combined)
+            })
+            .collect();
+        line.truncate_cells(options.get_max_width());
+        Ok(line.into_vec())
+    }
+
+    /// Render the first lines of `error`, indented.
+    fn error_lines(
+        &self,
+        error: &Text,
+        console: &Console,
+        options: &RenderOptions,
+    ) -> Result<Vec<Line>> {
+        let inner = render_utils::narrow(options, ERROR_INDENT);
+        let mut rendered = render_utils::split_lines(error.render(console, &inner)?);
+        if rendered.len() > 1 && rendered.last().is_some_and(Vec::is_empty) {
+            rendered.pop();
+        }
+        let hidden = rendered.len().saturating_sub(self.error_lines);
+        let indent = " ".repeat(ERROR_INDENT.min(options.get_max_width()));
+        let mut lines: Vec<Line> = rendered
+            .into_iter()
+            .take(self.error_lines)
+            .map(|content| {
+                let mut line = vec![Segment::new(indent.clone(), Style::new())];
+                line.extend(content);
+                line
+            })
+            .collect();
+        if hidden > 0 {
+            let more = match hidden {
+                1 => "… 1 more line".to_string(),
+                _ => format!("… {hidden} more lines"),
+            };
+            let more = Text::new(&more).with_style(Style::new().dim());
+            let mut line = vec![Segment::new(indent, Style::new())];
+            line.extend(more.render(console, &inner.with_soft_wrap(true))?);
+            let mut line: Segments = line.into_iter().collect();
+            line.truncate_cells(options.get_max_width());
+            lines.push(line.into_vec());
+        }
+        Ok(lines)
+    }
+}
+
+impl Default for TaskList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Animation for TaskList {
+    fn next_frame_deadline(&self) -> Option<Instant> {
+        self.tasks
+            .iter()
+            .filter_map(|task| match &task.state {
+                TaskState::Running(spinner) => spinner.next_frame_deadline(),
+                _ => None,
+            })
+            .min()
+    }
+}
+
+impl Renderable for TaskList {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let mut lines = Vec::new();
+        for task in &self.tasks {
+            options.check_cancelled()?;
+            lines.push(self.task_line(task, console, options)?);
+            if let TaskState::Failed(error) = &task.state {
+                lines.extend(self.error_lines(error, console, options)?);
+            }
+        }
+        Ok(render_utils::frame_lines(lines))
+    }
+}
+
+impl Measurable for TaskList {
+    fn measure(&self, _console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        let widest = self
+            .tasks
+            .iter()
+            .map(|task| {
+                let elapsed = match &task.state {
+                    TaskState::Running(spinner) => Some(spinner.elapsed()),
+                    TaskState::Done(elapsed) => Some(*elapsed),
+                    _ => None,
+                };
+                let label = elapsed.map_or(0, |elapsed| format_duration(elapsed).len() + 3);
+                let status = match options.accessible {
+                    true => task.state.status().len() + 1,
+                    false => 0,
+                };
+                let line = 2 + status + task.name.width() + label;
+                let error = match &task.state {
+                    TaskState::Failed(error) => error
+                        .split_lines()
+                        .iter()
+                        .take(self.error_lines)
+                        .map(|line| ERROR_INDENT + line.width())
+                        .max()
+                        .unwrap_or(0),
+                    _ => 0,
+                };
+                line.max(error)
+            })
+            .max()
+            .unwrap_or(0);
+        Ok(Measurement::fixed(widest))
+    }
+}
+
+/// Format `duration` for people: `850ms`, `1.2s` or `2m 05s`.
+fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    if millis < 1000 {
+        format!("{millis}ms")
+    } else if millis < 60_000 {
+        format!("{}.{}s", millis / 1000, millis % 1000 / 100)
+    } else {
+        let seconds = millis / 1000;
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Clock, ManualClock, assert_render_eq, testing::render_lines};
+
+    fn render(tasks: &TaskList) -> Vec<String> {
+        let options = RenderOptions::new()
+            .with_max_width(30)
+            .with_ascii_only(false);
+        render_lines(&tasks.render(&Console::new(), &options).unwrap())
+    }
+
+    #[test]
+    fn test_three_task_lifecycle() {
+        let clock = ManualClock::new();
+        let mut tasks = TaskList::new()
+            .with_clock(clock.shared())
+            .with_error_lines(2);
+        let fetch = tasks.add_task("fetch");
+        let compile = tasks.add_task("compile");
+        let test = tasks.add_task("test");
+        assert_render_eq!(render(&tasks), "· fetch\n· compile\n· test");
+        assert_eq!(tasks.next_frame_deadline(), None);
+
+        tasks.start(fetch);
+        clock.advance(Duration::from_millis(80 * 3 + 10));
+        assert_render_eq!(render(&tasks), "⠸ fetch (250ms)\n· compile\n· test");
+        assert_eq!(
+            tasks.next_frame_deadline(),
+            Some(clock.now() + Duration::from_millis(70))
+        );
+
+        clock.advance(Duration::from_millis(950));
+        tasks.complete(fetch);
+        tasks.start(compile);
+        clock.advance(Duration::from_secs(120));
+        assert_render_eq!(render(&tasks), "✓ fetch (1.2s)\n⠋ compile (2m 00s)\n· test");
+
+        tasks.fail(
+            compile,
+            "error[E0308]: mismatched types\n  --> src/lib.rs:4:5\n   |\n",
+        );
+        tasks.skip(test);
+        assert!(tasks.is_finished());
+        assert_render_eq!(
+            render(&tasks),
+            "✓ fetch (1.2s)\n\
+             ✗ compile\n    \
+             error[E0308]: mismatched\n    \
+             types\n    \
+             … 2 more lines\n\
+             – test"
+        );
+        assert_eq!(tasks.next_frame_deadline(), None);
+    }
+
+    #[test]
+    fn test_ascii_marks_and_styles() {
+        let clock = ManualClock::new();
+        let mut tasks = TaskList::new().with_clock(clock.shared());
+        let done = tasks.add_task("done");
+        let failed = tasks.add_task("failed");
+        tasks.add_task("pending");
+        tasks.complete(done);
+        tasks.fail(failed, "boom");
+        let options = RenderOptions::new()
+            .with_max_width(20)
+            .with_ascii_only(true);
+        let segments = tasks.render(&Console::new(), &options).unwrap();
+        assert_render_eq!(
+            render_lines(&segments),
+            "+ done (0ms)\nx failed\n    boom\n. pending"
+        );
+        let red = Style::new().with_color(Color::Standard(StandardColor::Red));
+        assert!(
+            segments
+                .iter()
+                .any(|s| s.text() == "x" && s.style() == &red)
+        );
+        let pending = segments.iter().find(|s| s.text() == "pending").unwrap();
+        assert_eq!(pending.style().dim, Some(true));
+
+        let measurement = tasks.measure(&Console::new(), &options).unwrap();
+        assert_eq!(measurement, Measurement::fixed(12));
+    }
+
+    #[test]
+    fn test_accessible_states_in_words() {
+        let clock = ManualClock::new();
+        let mut tasks = TaskList::new().with_clock(clock.shared());
+        let done = tasks.add_task("done");
+        let failed = tasks.add_task("failed");
+        let running = tasks.add_task("running");
+        let skipped = tasks.add_task("skipped");
+        tasks.add_task("pending");
+        tasks.complete(done);
+        tasks.fail(failed, "boom");
+        tasks.start(running);
+        tasks.skip(skipped);
+        let options = RenderOptions::new()
+            .with_max_width(30)
+            .with_ascii_only(false)
+            .with_accessible(true);
+        let segments = tasks.render(&Console::new(), &options).unwrap();
+        assert_render_eq!(
+            render_lines(&segments),
+            "✓ [OK] done (0ms)\n\
+             ✗ [FAIL] failed\n    \
+             boom\n\
+             ⠋ [RUN] running (0ms)\n\
+             – [SKIP] skipped\n\
+             · [WAIT] pending"
+        );
+        let measurement = tasks.measure(&Console::new(), &options).unwrap();
+        assert_eq!(measurement, Measurement::fixed(21));
+        // Without the flag there are marks only
+        assert_render_eq!(
+            render(&tasks),
+            "✓ done (0ms)\n✗ failed\n    boom\n⠋ running (0ms)\n– skipped\n· pending"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(999)), "999ms");
+        assert_eq!(format_duration(Duration::from_millis(59_999)), "59.9s");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "60m 00s");
+    }
+}
@@ -0,0 +1,501 @@
+//! Tree - a hierarchy drawn with guide lines.
+//!
+//! Each node has a text label and any number of children. Nodes can be
+//! collapsed, in which case a summary line such as `+ 12 items` stands in for
+//! their children, so an interactive file browser can toggle nodes between
+//! renders without rebuilding the tree.
+//!
+//! ```rust
+//! use luxor::{Console, RenderOptions, Renderable, components::Tree};
+//!
+//! let mut tree = Tree::new("src");
+//! tree.add("lib.rs");
+//! tree.add("components").add("tree.rs");
+//!
+//! let options = RenderOptions::new().with_max_width(40);
+//! let segments = tree.render(&Console::new(), &options).unwrap();
+//! let plain: String = segments.iter().map(|s| s.text()).collect();
+//! assert_eq!(plain, "src\n├── lib.rs\n└── components\n    └── tree.rs");
+//! ```
+
+use crate::{
+    CANCEL_CHECK_INTERVAL, Console, Measurable, Measurement, RenderOptions, RenderState,
+    Renderable, Result, Segment, Style, Text,
+    diagnostics::{self, Warning},
+    render_utils,
+    rendered::Line,
+};
+use std::cell::Cell;
+
+/// The guide strings drawn in front of tree nodes, each four cells wide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeGuides {
+    /// Below a node with no further siblings.
+    pub space: String,
+    /// Below a node with further siblings.
+    pub vertical: String,
+    /// In front of a node with further siblings.
+    pub branch: String,
+    /// In front of the last of its siblings.
+    pub last: String,
+}
+
+impl TreeGuides {
+    /// Light box-drawing lines: `├──`.
+    pub fn normal() -> Self {
+        Self::from_parts("│   ", "├── ", "└── ")
+    }
+
+    /// Heavy box-drawing lines: `┣━━`.
+    pub fn bold() -> Self {
+        Self::from_parts("┃   ", "┣━━ ", "┗━━ ")
+    }
+
+    /// Double box-drawing lines: `╠══`.
+    pub fn double() -> Self {
+        Self::from_parts("║   ", "╠══ ", "╚══ ")
+    }
+
+    /// ASCII lines for terminals without Unicode: `+--`.
+    pub fn ascii() -> Self {
+        Self::from_parts("|   ", "+-- ", "`-- ")
+    }
+
+    fn from_parts(vertical: &str, branch: &str, last: &str) -> Self {
+        Self {
+            space: "    ".to_string(),
+            vertical: vertical.to_string(),
+            branch: branch.to_string(),
+            last: last.to_string(),
+        }
+    }
+
+    /// Check if every guide is ASCII.
+    fn is_ascii(&self) -> bool {
+        [&self.space, &self.vertical, &self.branch, &self.last]
+            .iter()
+            .all(|guide| guide.is_ascii())
+    }
+}
+
+impl Default for TreeGuides {
+    fn default() -> Self {
+        Self::normal()
+    }
+}
+
+/// A node of a tree with its label and children.
+///
+/// The root's settings for guides, styles, depth and root visibility apply
+/// to the whole tree; those of nested nodes are ignored. With
+/// [`RenderOptions::ascii_only`] set, non-ASCII guides are replaced by
+/// [`TreeGuides::ascii`].
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, components::Tree};
+///
+/// let tree = Tree::new("target")
+///     .with_child(Tree::new("debug").with_child(Tree::new("build")).with_expanded(false));
+///
+/// let options = RenderOptions::new().with_max_width(40);
+/// let segments = tree.render(&Console::new(), &options).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, "target\n└── debug\n    └── + 1 item");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tree {
+    label: Text,
+    children: Vec<Tree>,
+    expanded: bool,
+    hide_root: bool,
+    max_depth: Option<usize>,
+    guides: TreeGuides,
+    guide_style: Style,
+    summary_style: Style,
+}
+
+/// Whether each guide column in front of a node continues below it.
+type Levels = Vec<bool>;
+
+impl Tree {
+    /// Create an expanded node without children.
+    pub fn new(label: impl Into<Text>) -> Self {
+        Self {
+            label: label.into(),
+            children: Vec::new(),
+            expanded: true,
+            hide_root: false,
+            max_depth: None,
+            guides: TreeGuides::default(),
+            guide_style: Style::default(),
+            summary_style: Style::new().dim(),
+        }
+    }
+
+    /// Add a child node.
+    pub fn with_child(mut self, child: Tree) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Add a child with the given label and get it for adding its own
+    /// children.
+    pub fn add(&mut self, label: impl Into<Text>) -> &mut Tree {
+        self.children.push(Tree::new(label));
+        self.children.last_mut().expect("a child was just added")
+    }
+
+    /// Set whether the children are shown, or a summary line instead.
+    pub fn with_expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// Show or hide the children between renders.
+    pub fn set_expanded(&mut self, expanded: bool) {
+        self.expanded = expanded;
+    }
+
+    /// Set whether the root's label is hidden, moving its children to the
+    /// top level.
+    pub fn with_hide_root(mut self, hide_root: bool) -> Self {
+        self.hide_root = hide_root;
+        self
+    }
+
+    /// Show at most `depth` levels below the root, summarizing deeper ones.
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Set the guide lines.
+    pub fn with_guides(mut self, guides: TreeGuides) -> Self {
+        self.guides = guides;
+        self
+    }
+
+    /// Set the style of the guide lines.
+    pub fn with_guide_style(mut self, style: Style) -> Self {
+        self.guide_style = style;
+        self
+    }
+
+    /// Set the style of the summary lines of collapsed nodes. Dim by
+    /// default.
+    pub fn with_summary_style(mut self, style: Style) -> Self {
+        self.summary_style = style;
+        self
+    }
+
+    /// Get the label.
+    pub fn label(&self) -> &Text {
+        &self.label
+    }
+
+    /// Get the children.
+    pub fn children(&self) -> &[Tree] {
+        &self.children
+    }
+
+    /// Get the children for modification.
+    pub fn children_mut(&mut self) -> &mut Vec<Tree> {
+        &mut self.children
+    }
+
+    /// Check if the children are shown.
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    /// Get the guides to draw with, given the options.
+    fn effective_guides(&self, options: &RenderOptions) -> TreeGuides {
+        if options.ascii_only && !self.guides.is_ascii() {
+            diagnostics::warn(|| Warning::AsciiFallback { component: "Tree" });
+            TreeGuides::ascii()
+        } else {
+            self.guides.clone()
+        }
+    }
+
+    /// Build the guide prefix for a line of a node at `levels`.
+    ///
+    /// The last level is drawn as a branch on the node's first line and as
+    /// a continuation on the others.
+    fn prefix(guides: &TreeGuides, levels: &[bool], first_line: bool) -> String {
+        let mut prefix = String::new();
+        for (index, &continues) in levels.iter().enumerate() {
+            let own = first_line && index + 1 == levels.len();
+            prefix.push_str(match (own, continues) {
+                (true, true) => &guides.branch,
+                (true, false) => &guides.last,
+                (false, true) => &guides.vertical,
+                (false, false) => &guides.space,
+            });
+        }
+        prefix
+    }
+
+    /// Render the label of `node`, the next row, with the render state.
+    fn render_label(
+        node: &Tree,
+        context: &RenderContext<'_>,
+        options: &RenderOptions,
+    ) -> Result<Vec<Segment>> {
+        let row = context.rows.get();
+        context.rows.set(row + 1);
+        if row % CANCEL_CHECK_INTERVAL == 0 {
+            context.options.check_cancelled()?;
+        }
+        let state = &context.options.state;
+        if state.is_empty() {
+            return node.label.render(context.console, options);
+        }
+        let mut label = node.label.clone();
+        state.highlight(&mut label);
+        let segments = label.render(context.console, options)?;
+        if !state.is_selected(row) {
+            return Ok(segments);
+        }
+        Ok(segments
+            .into_iter()
+            .map(|segment| {
+                let style = state.selection_style(segment.style().clone());
+                Segment::new(segment.text(), style)
+            })
+            .collect())
+    }
+
+    /// Render `node` and its visible descendants as lines.
+    fn render_node(
+        &self,
+        node: &Tree,
+        context: &RenderContext<'_>,
+        levels: &mut Levels,
+        depth: usize,
+        lines: &mut Vec<Line>,
+    ) -> Result<()> {
+        if depth > 0 || !self.hide_root {
+            let first = Self::prefix(&context.guides, levels, true);
+            let rest = Self::prefix(&context.guides, levels, false);
+            let inner = render_utils::narrow(context.options, render_utils::visible_width(&first))
+                .with_state(RenderState::default());
+            let label = Self::render_label(node, context, &inner)?;
+            for (index, line) in render_utils::split_lines(label).into_iter().enumerate() {
+                let prefix = if index == 0 { &first } else { &rest };
+                let mut output = vec![Segment::new(prefix.clone(), self.guide_style.clone())];
+                output.extend(line);
+                lines.push(output);
+            }
+        }
+
+        if node.children.is_empty() {
+            return Ok(());
+        }
+        let collapsed = !node.expanded || self.max_depth.is_some_and(|max| depth >= max);
+        // A hidden root has no guide column of its own
+        let nested = depth > 0 || !self.hide_root;
+        if collapsed {
+            if nested {
+                levels.push(false);
+            }
+            let prefix = Self::prefix(&context.guides, levels, true);
+            let count = node.children.len();
+            let summary = format!("+ {} item{}", count, if count == 1 { "" } else { "s" });
+            lines.push(vec![
+                Segment::new(prefix, self.guide_style.clone()),
+                Segment::new(summary, self.summary_style.clone()),
+            ]);
+            if nested {
+                levels.pop();
+            }
+            return Ok(());
+        }
+
+        for (index, child) in node.children.iter().enumerate() {
+            let continues = index + 1 < node.children.len();
+            if nested {
+                levels.push(continues);
+            }
+            self.render_node(child, context, levels, depth + 1, lines)?;
+            if nested {
+                levels.pop();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What every node of one render shares.
+struct RenderContext<'a> {
+    console: &'a Console,
+    options: &'a RenderOptions,
+    guides: TreeGuides,
+    /// Labels rendered so far, the row number of the next one.
+    rows: Cell<usize>,
+}
+
+impl Renderable for Tree {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let context = RenderContext {
+            console,
+            options,
+            guides: self.effective_guides(options),
+            rows: Cell::new(0),
+        };
+        let mut lines = Vec::new();
+        self.render_node(self, &context, &mut Vec::new(), 0, &mut lines)?;
+
+        let width = options.get_max_width();
+        let lines = lines
+            .into_iter()
+            .map(|line| {
+                if render_utils::line_width(&line) > width {
+                    render_utils::adjust_line_length(line, width, Style::default())
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Ok(render_utils::frame_lines(lines))
+    }
+}
+
+impl Measurable for Tree {
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        let segments = self.render(console, options)?;
+        let width = render_utils::split_lines(segments)
+            .iter()
+            .map(|line| render_utils::line_width(line))
+            .max()
+            .unwrap_or(0);
+        Ok(Measurement::new(width, width).clamp(options.min_width, options.get_max_width()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::render_lines;
+
+    fn render(tree: &Tree, width: usize) -> Vec<String> {
+        let options = RenderOptions::new().with_max_width(width);
+        render_lines(&tree.render(&Console::new(), &options).unwrap())
+    }
+
+    fn sample() -> Tree {
+        let mut tree = Tree::new("root");
+        let docs = tree.add("docs");
+        docs.add("guide.md");
+        docs.add("api.md");
+        tree.add("README.md");
+        tree
+    }
+
+    #[test]
+    fn test_tree_guides() {
+        crate::assert_render_eq!(
+            render(&sample(), 40),
+            "root\n├── docs\n│   ├── guide.md\n│   └── api.md\n└── README.md"
+        );
+        crate::assert_render_eq!(
+            render(&sample().with_guides(TreeGuides::bold()), 40),
+            "root\n┣━━ docs\n┃   ┣━━ guide.md\n┃   ┗━━ api.md\n┗━━ README.md"
+        );
+        crate::assert_render_eq!(
+            render(&sample().with_guides(TreeGuides::double()), 40),
+            "root\n╠══ docs\n║   ╠══ guide.md\n║   ╚══ api.md\n╚══ README.md"
+        );
+        crate::assert_render_eq!(
+            render(&sample().with_guides(TreeGuides::ascii()), 40),
+            "root\n+-- docs\n|   +-- guide.md\n|   `-- api.md\n`-- README.md"
+        );
+    }
+
+    #[test]
+    fn test_tree_collapsed_node() {
+        let mut tree = sample();
+        tree.children_mut()[0].set_expanded(false);
+        let output = render(&tree, 40);
+        crate::assert_render_eq!(&output, "root\n├── docs\n│   └── + 2 items\n└── README.md");
+        assert!(!output.concat().contains("guide.md"));
+
+        let options = RenderOptions::new().with_max_width(40);
+        let segments = tree.render(&Console::new(), &options).unwrap();
+        let summary = segments.iter().find(|s| s.text() == "+ 2 items").unwrap();
+        assert_eq!(summary.style(), &Style::new().dim());
+    }
+
+    #[test]
+    fn test_tree_hide_root_and_max_depth() {
+        crate::assert_render_eq!(
+            render(&sample().with_hide_root(true), 40),
+            "docs\n├── guide.md\n└── api.md\nREADME.md"
+        );
+        crate::assert_render_eq!(
+            render(&sample().with_max_depth(1), 40),
+            "root\n├── docs\n│   └── + 2 items\n└── README.md"
+        );
+        crate::assert_render_eq!(
+            render(&sample().with_max_depth(0).with_hide_root(true), 40),
+            "+ 2 items"
+        );
+    }
+
+    #[test]
+    fn test_tree_multiline_label_and_ascii_fallback() {
+        let tree = Tree::new("root")
+            .with_child(Tree::new("two\nlines").with_child(Tree::new("leaf")))
+            .with_child(Tree::new("overflowing"));
+        crate::assert_render_eq!(
+            render(&tree, 12),
+            "root\n├── two\n│   lines\n│   └── leaf\n└── overflow\n    ing"
+        );
+
+        let options = RenderOptions::new()
+            .with_max_width(20)
+            .with_ascii_only(true);
+        let segments = sample().render(&Console::new(), &options).unwrap();
+        assert!(render_lines(&segments).iter().all(|line| line.is_ascii()));
+        let measurement = sample().measure(&Console::new(), &options).unwrap();
+        assert_eq!(measurement.maximum(), 16);
+    }
+
+    #[test]
+    fn test_tree_render_state_selection_and_search() {
+        let guide = Style::new().dim();
+        let state = RenderState::new().with_selected([1]).with_search("MD");
+        let options = RenderOptions::new().with_max_width(40).with_state(state);
+        let segments = sample()
+            .with_guide_style(guide.clone())
+            .render(&Console::new(), &options)
+            .unwrap();
+        crate::assert_render_eq!(
+            render_lines(&segments),
+            "root\n├── docs\n│   ├── guide.md\n│   └── api.md\n└── README.md"
+        );
+
+        // Row 1 is "docs", inverted; its guide keeps the guide style
+        let docs = segments.iter().find(|s| s.text() == "docs").unwrap();
+        assert_eq!(docs.style(), &Style::new().reverse());
+        let prefix = segments.iter().find(|s| s.text() == "├── ").unwrap();
+        assert_eq!(prefix.style(), &guide);
+
+        // Every "md" is highlighted, ignoring case, and nothing else
+        let matches: Vec<&str> = segments
+            .iter()
+            .filter(|s| s.style().underline == Some(true))
+            .map(|s| s.text())
+            .collect();
+        assert_eq!(matches, ["md", "md", "md"]);
+
+        let unfocused = options
+            .clone()
+            .with_state(options.state.clone().with_focused(false));
+        let segments = sample().render(&Console::new(), &unfocused).unwrap();
+        let docs = segments.iter().find(|s| s.text() == "docs").unwrap();
+        assert_eq!(docs.style(), &Style::new().reverse().dim());
+    }
+}
@@ -0,0 +1,169 @@
+//! Vertical bar - a one-cell-wide gauge filling from the bottom.
+//!
+//! A [`VerticalBar`] fits in a table cell or next to a line of a dashboard
+//! where a [`ProgressBar`](super::ProgressBar) would not. The top of the
+//! fill is drawn with the lower eighth blocks, so a bar five lines high
+//! still shows forty steps.
+
+use super::progress_bar::bar_math;
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style,
+    diagnostics::{self, Warning},
+};
+
+/// Lower blocks from one to seven eighths of a cell.
+const LOWER_EIGHTHS: [char; 7] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇'];
+
+/// A single-column bar showing a fraction as its filled height.
+///
+/// With [`RenderOptions::ascii_only`] set, the bar is drawn with `#` and
+/// advances a whole line at a time.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, components::VerticalBar};
+///
+/// let bar = VerticalBar::new(3).with_ratio(0.5);
+/// let segments = bar.render(&Console::new(), &RenderOptions::new()).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, " \n▄\n█");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerticalBar {
+    height: usize,
+    ratio: f64,
+    style: Style,
+    empty_style: Style,
+}
+
+impl VerticalBar {
+    /// Create an empty bar `height` lines high.
+    pub fn new(height: usize) -> Self {
+        Self {
+            height,
+            ratio: 0.0,
+            style: Style::default(),
+            empty_style: Style::default(),
+        }
+    }
+
+    /// Set the filled fraction, clamped between 0 and 1.
+    pub fn with_ratio(mut self, ratio: f64) -> Self {
+        self.ratio = if ratio.is_nan() {
+            0.0
+        } else {
+            ratio.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// Set the style of the filled cells.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the empty cells, typically a background color.
+    pub fn with_empty_style(mut self, style: Style) -> Self {
+        self.empty_style = style;
+        self
+    }
+
+    /// Get the filled fraction.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+}
+
+impl Renderable for VerticalBar {
+    fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let partial: &[char] = if options.ascii_only {
+            diagnostics::warn(|| Warning::AsciiFallback {
+                component: "VerticalBar",
+            });
+            &[]
+        } else {
+            &LOWER_EIGHTHS
+        };
+        let filled = if options.ascii_only { '#' } else { '█' };
+        let (full, remainder) = bar_math(self.ratio, self.height, partial.len() + 1);
+
+        let mut segments = Vec::with_capacity(self.height * 2);
+        // Lines from the top down; line 0 is the bottom
+        for line in (0..self.height).rev() {
+            let cell = if line < full {
+                Segment::new(filled.to_string(), self.style.clone())
+            } else if line == full && remainder > 0 {
+                Segment::new(partial[remainder - 1].to_string(), self.style.clone())
+            } else {
+                Segment::new(" ", self.empty_style.clone())
+            };
+            segments.push(cell);
+            if line > 0 {
+                segments.push(Segment::line());
+            }
+        }
+        Ok(segments)
+    }
+}
+
+impl Measurable for VerticalBar {
+    fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+        Ok(Measurement::fixed(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_render_eq, testing::render_lines};
+
+    fn lines(bar: &VerticalBar, options: &RenderOptions) -> Vec<String> {
+        render_lines(&bar.render(&Console::new(), options).unwrap())
+    }
+
+    #[test]
+    fn test_ratios() {
+        let options = RenderOptions::new();
+        let bar = VerticalBar::new(5);
+        assert_render_eq!(
+            lines(&bar.clone().with_ratio(0.0), &options),
+            " \n \n \n \n "
+        );
+        assert_render_eq!(
+            lines(&bar.clone().with_ratio(0.33), &options),
+            " \n \n \n▅\n█"
+        );
+        assert_render_eq!(
+            lines(&bar.clone().with_ratio(1.0), &options),
+            "█\n█\n█\n█\n█"
+        );
+        assert_eq!(bar.with_ratio(f64::NAN).ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_ascii_and_styles() {
+        let options = RenderOptions::new().with_ascii_only(true);
+        let bar = VerticalBar::new(4).with_ratio(0.6);
+        assert_render_eq!(lines(&bar, &options), " \n \n#\n#");
+
+        let filled = Style::parse("green").unwrap();
+        let empty = Style::parse("on bright_black").unwrap();
+        let bar = VerticalBar::new(2)
+            .with_ratio(0.5)
+            .with_style(filled.clone())
+            .with_empty_style(empty.clone());
+        let segments = bar.render(&Console::new(), &RenderOptions::new()).unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                Segment::new(" ", empty),
+                Segment::line(),
+                Segment::new("█", filled),
+            ]
+        );
+        let measurement = bar.measure(&Console::new(), &options).unwrap();
+        assert_eq!(measurement, Measurement::fixed(1));
+    }
+}
@@ -1,49 +1,146 @@
 //! Console - the central rendering engine for rich text output.
 
 use crate::{
-    ColorSystem, LuxorError, Measurable, Measurement, Renderable, Result, Segment, Style, ansi,
+    Capabilities, Color, ColorSystem, LuxorError, Measurable, Measurement, Renderable, Rendered,
+    Result, Segment, Segments, StandardColor, Style, Text, ansi, capabilities,
+    components::CacheKey,
+    diagnostics,
+    highlighter::{BoxedHighlighter, Highlighter, ReprHighlighter},
+    markup::{LossyText, MarkupWarning},
+    profile::{Profiler, RenderProfile},
+    render_utils,
+    sanitize::Policy,
+    segment::{self, ControlCode},
+    sink::{BoxedSink, ConsoleSink, FrameSink, FrameSize},
+    tee::{PlainTee, TeeMode, TeeWriter},
+    text::{BidiPolicy, EllipsisConfig},
+    theme::Theme,
 };
-use crossterm::terminal;
-use std::io::{self, Write};
 
-/// Options that control how rendering is performed.
+pub use crate::options::WidthSource;
+use crate::options::{DEFAULT_FALLBACK_HEIGHT, RenderOptions};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    io::{self, Write},
+    sync::{
+        Mutex, MutexGuard,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+/// Options that control a console and the defaults it renders with.
+///
+/// Settings for a single render live in [`RenderOptions`], available as
+/// [`ConsoleOptions::render`]; the `with_*` builders for them set those
+/// defaults. See the [`options`](crate::options) module for how this
+/// type maps onto the options it used to carry.
 #[derive(Debug, Clone)]
 pub struct ConsoleOptions {
-    /// Maximum width for rendering (None = use terminal width).
+    /// Options used for each render by this console.
+    pub render: RenderOptions,
+    /// Mirror of `render.max_width`. When set to a different value, it
+    /// replaces `render.max_width` once the options are used.
+    #[deprecated(since = "0.2.0", note = "use `render.max_width`")]
     pub max_width: Option<usize>,
-    /// Minimum width for rendering.
+    /// Mirror of `render.min_width`. When set to a different value, it
+    /// replaces `render.min_width` once the options are used.
+    #[deprecated(since = "0.2.0", note = "use `render.min_width`")]
     pub min_width: usize,
     /// Whether to use ANSI color codes.
     pub enable_color: bool,
     /// Color system capability.
     pub color_system: ColorSystem,
-    /// Whether to use alternative screen buffer.
+    /// Never read; use [`Console::enable_alt_screen`] instead.
+    #[deprecated(note = "use Console::enable_alt_screen and Console::is_alt_screen")]
     pub alt_screen: bool,
     /// Legacy Windows mode (for compatibility).
     pub legacy_windows: bool,
+    /// Whether printed output shows its whitespace, for finding alignment
+    /// problems. See [`render_utils::show_whitespace`].
+    pub debug_whitespace: bool,
+    /// Whether output goes to a fixed-width block of plain text rather
+    /// than a terminal, so cursor control is refused. See
+    /// [`ConsoleOptions::plain_fixed`].
+    pub plain_fixed: bool,
+    /// Whether to treat the output as a terminal, or not, regardless of
+    /// detection. See [`Console::is_terminal`].
+    pub force_terminal: Option<bool>,
 }
 
 impl ConsoleOptions {
     /// Create new console options with default settings.
+    ///
+    /// Color, ASCII-only and accessibility defaults come from
+    /// [`Capabilities::probe_once`].
+    #[allow(deprecated)]
     pub fn new() -> Self {
+        let probed = Capabilities::probe_once();
+        let render = RenderOptions::new();
         Self {
-            max_width: None,
-            min_width: 0,
-            enable_color: true,
-            color_system: ColorSystem::detect(),
+            max_width: render.max_width,
+            min_width: render.min_width,
+            render,
+            enable_color: probed.color_enabled,
+            color_system: probed.color_system,
             alt_screen: false,
             legacy_windows: false,
+            debug_whitespace: false,
+            plain_fixed: false,
+            force_terminal: None,
         }
     }
 
-    /// Set the maximum width.
+    /// Create options for plain text at exactly `width` cells, for chat
+    /// code blocks and log files: color off, Unicode on, text sanitized
+    /// with [`Policy::Strict`] and soft wrapping on, with nothing taken
+    /// from the environment. Cursor control, such as
+    /// [`Console::clear`], fails on a console with these options. Turn
+    /// Unicode off with [`ConsoleOptions::with_ascii_only`].
+    ///
+    /// See [`Console::render_block`].
+    pub fn plain_fixed(width: usize) -> Self {
+        let mut options = Self::new()
+            .with_max_width(width)
+            .with_min_width(0)
+            .with_fallback_width(width)
+            .with_color(false)
+            .with_ascii_only(false)
+            .with_accessible(false)
+            .with_soft_wrap(true)
+            .with_sanitize(Policy::Strict)
+            .with_force_terminal(false);
+        options.plain_fixed = true;
+        options
+    }
+
+    /// Replace the options used for each render.
+    #[allow(deprecated)]
+    pub fn with_render(mut self, render: RenderOptions) -> Self {
+        self.max_width = render.max_width;
+        self.min_width = render.min_width;
+        self.render = render;
+        self
+    }
+
+    /// Set the maximum width. See [`RenderOptions::with_max_width`].
+    #[allow(deprecated)]
     pub fn with_max_width(mut self, width: usize) -> Self {
-        self.max_width = Some(width);
+        self.render = self.render.with_max_width(width);
+        self.max_width = self.render.max_width;
+        self
+    }
+
+    /// Set the maximum height. See [`RenderOptions::with_max_height`].
+    pub fn with_max_height(mut self, height: usize) -> Self {
+        self.render = self.render.with_max_height(height);
         self
     }
 
-    /// Set the minimum width.
+    /// Set the minimum width. See [`RenderOptions::with_min_width`].
+    #[allow(deprecated)]
     pub fn with_min_width(mut self, width: usize) -> Self {
+        self.render = self.render.with_min_width(width);
         self.min_width = width;
         self
     }
@@ -54,16 +151,160 @@ impl ConsoleOptions {
         self
     }
 
-    /// Set the color system.
+    /// Enable or disable accessible output. See
+    /// [`RenderOptions::with_accessible`].
+    pub fn with_accessible(mut self, accessible: bool) -> Self {
+        self.render = self.render.with_accessible(accessible);
+        self
+    }
+
+    /// Set the width used when no other width source is available. See
+    /// [`RenderOptions::with_fallback_width`].
+    pub fn with_fallback_width(mut self, width: usize) -> Self {
+        self.render = self.render.with_fallback_width(width);
+        self
+    }
+
+    /// Enable or disable soft wrapping. See
+    /// [`RenderOptions::with_soft_wrap`].
+    pub fn with_soft_wrap(mut self, soft_wrap: bool) -> Self {
+        self.render = self.render.with_soft_wrap(soft_wrap);
+        self
+    }
+
+    /// Enable or disable ASCII-only decorations. See
+    /// [`RenderOptions::with_ascii_only`].
+    pub fn with_ascii_only(mut self, ascii_only: bool) -> Self {
+        self.render = self.render.with_ascii_only(ascii_only);
+        self
+    }
+
+    /// Set how right-to-left text is handled. See
+    /// [`RenderOptions::with_bidi_policy`].
+    pub fn with_bidi_policy(mut self, policy: BidiPolicy) -> Self {
+        self.render = self.render.with_bidi_policy(policy);
+        self
+    }
+
+    /// Set the default truncation marker. See
+    /// [`RenderOptions::with_ellipsis`].
+    pub fn with_ellipsis(mut self, ellipsis: EllipsisConfig) -> Self {
+        self.render = self.render.with_ellipsis(ellipsis);
+        self
+    }
+
+    /// Enable or disable markup in plain strings. See
+    /// [`RenderOptions::with_markup`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions};
+    ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_markup(false));
+    /// let output = console.capture(|console| console.print("[red]x[/red]")).unwrap();
+    /// assert_eq!(output, "[red]x[/red]");
+    /// ```
+    pub fn with_markup(mut self, markup: bool) -> Self {
+        self.render = self.render.with_markup(markup);
+        self
+    }
+
+    /// Look up markup tags in `theme`. See [`RenderOptions::with_theme`].
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.render = self.render.with_theme(theme);
+        self
+    }
+
+    /// Highlight plain strings with the console's highlighter. See
+    /// [`RenderOptions::with_highlight`].
+    pub fn with_highlight(mut self, highlight: bool) -> Self {
+        self.render = self.render.with_highlight(highlight);
+        self
+    }
+
+    /// Sanitize printed text, for a console showing untrusted content. See
+    /// [`RenderOptions::with_sanitize`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions, sanitize::Policy};
+    ///
+    /// let options = ConsoleOptions::new().with_sanitize(Policy::Strict);
+    /// let console = Console::with_options(options);
+    /// let output = console.capture(|console| console.print("a\x1b]52;c;eA==\x07b")).unwrap();
+    /// assert_eq!(output, "ab");
+    /// ```
+    pub fn with_sanitize(mut self, policy: Policy) -> Self {
+        self.render = self.render.with_sanitize(policy);
+        self
+    }
+
+    /// Set the measure call limit of one pass. See
+    /// [`RenderOptions::with_measure_limit`].
+    pub fn with_measure_limit(mut self, limit: usize) -> Self {
+        self.render = self.render.with_measure_limit(limit);
+        self
+    }
+
+    /// Time the renders of each print, to find out where a slow frame
+    /// spends its time; see [`Console::render_profile`]. Without
+    /// profiling, renders are not timed at all.
+    pub fn with_profiling(mut self, enabled: bool) -> Self {
+        self.render.profiler = enabled.then(Profiler::new);
+        self
+    }
+
+    /// Show whitespace in printed output: spaces as `·`, tabs as `→`,
+    /// padding dimmed, line ends as `¶` and wide characters underlined.
+    /// See [`render_utils::show_whitespace`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions, Text, Justify};
+    ///
+    /// let options = ConsoleOptions::new()
+    ///     .with_max_width(7)
+    ///     .with_color(false)
+    ///     .with_debug_whitespace(true);
+    /// let console = Console::with_options(options);
+    /// let text = Text::new("a b").with_justify(Justify::Center);
+    /// let output = console.capture(|console| console.print(text)).unwrap();
+    /// assert_eq!(luxor::ansi::strip_ansi(&output), "··a·b··¶");
+    /// ```
+    pub fn with_debug_whitespace(mut self, debug_whitespace: bool) -> Self {
+        self.debug_whitespace = debug_whitespace;
+        self
+    }
+
+    /// Set the color system, which also enables color.
     pub fn with_color_system(mut self, color_system: ColorSystem) -> Self {
         self.color_system = color_system;
+        self.enable_color = true;
         self
     }
 
-    /// Get the effective maximum width, using terminal width if not set.
-    pub fn get_max_width(&self) -> usize {
-        self.max_width
-            .unwrap_or_else(|| terminal::size().map(|(w, _)| w as usize).unwrap_or(80))
+    /// Treat the output as a terminal, or not, instead of detecting it.
+    ///
+    /// Color follows: forcing a terminal enables it and forcing a pipe
+    /// disables it, as detection would. Call [`ConsoleOptions::with_color`]
+    /// afterwards to choose otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions};
+    ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_force_terminal(true));
+    /// assert!(console.is_terminal());
+    /// assert!(console.options().enable_color);
+    /// ```
+    pub fn with_force_terminal(mut self, force: bool) -> Self {
+        self.force_terminal = Some(force);
+        self.enable_color = force;
+        self
     }
 
     /// Get the effective color system, respecting the enable_color setting.
@@ -74,6 +315,66 @@ impl ConsoleOptions {
             ColorSystem::Standard // Minimal color support when disabled
         }
     }
+
+    /// Apply the deprecated width fields to the render options, where
+    /// they were assigned a value of their own.
+    #[allow(deprecated)]
+    pub(crate) fn synced(mut self) -> Self {
+        if self.max_width.is_some() && self.max_width != self.render.max_width {
+            self.render.max_width = self.max_width;
+        }
+        if self.min_width != 0 && self.min_width != self.render.min_width {
+            self.render.min_width = self.min_width;
+        }
+        self.max_width = self.render.max_width;
+        self.min_width = self.render.min_width;
+        self
+    }
+
+    /// Get the effective maximum width of the default render options.
+    #[deprecated(note = "use `options.render.get_max_width()`")]
+    pub fn get_max_width(&self) -> usize {
+        self.render.get_max_width()
+    }
+
+    /// Get where the maximum width of the default render options comes
+    /// from.
+    #[deprecated(note = "use `options.render.width_source()`")]
+    pub fn width_source(&self) -> WidthSource {
+        self.render.width_source()
+    }
+
+    /// Clamp a measured width to the default render options.
+    #[deprecated(note = "use `options.render.clamp_width(measured)`")]
+    pub fn clamp_width(&self, measured: usize) -> usize {
+        self.render.clamp_width(measured)
+    }
+
+    /// Validate the default render options.
+    ///
+    /// # Errors
+    ///
+    /// See [`RenderOptions::validate`].
+    #[deprecated(note = "use `options.render.validate()`")]
+    pub fn validate(&self) -> Result<()> {
+        self.render.validate()
+    }
+}
+
+/// Fail with [`LuxorError::UnsupportedFeature`] unless the `terminal`
+/// feature is enabled.
+fn require_terminal(operation: &'static str) -> Result<()> {
+    if cfg!(feature = "terminal") {
+        Ok(())
+    } else {
+        Err(LuxorError::unsupported_feature("terminal", operation))
+    }
+}
+
+/// Interpret an environment flag value: anything but empty or `0` enables it.
+pub(crate) fn is_enabled_flag(value: &str) -> bool {
+    let value = value.trim();
+    !value.is_empty() && value != "0"
 }
 
 impl Default for ConsoleOptions {
@@ -89,6 +390,24 @@ impl Default for ConsoleOptions {
 /// - Coordinating the rendering of complex objects
 /// - Handling output streams and buffering
 /// - Providing measurement and layout services
+///
+/// # Thread safety
+///
+/// `Console` is `Send` and `Sync`. Each print is written to the output in
+/// one piece, so prints from several threads through `&Console` never
+/// interleave within a print, though their order is unspecified. Methods
+/// that change the console take `&mut self`; to call them from worker
+/// threads, or to keep several prints together, share the console as a
+/// [`SharedConsole`](crate::SharedConsole).
+///
+/// Printing takes `&self`: the output, capture buffer and sink sit behind
+/// locks, so a console can print while it is borrowed elsewhere.
+///
+/// # Output
+///
+/// Output goes to stdout unless the console was created with
+/// [`Console::with_writer`]. While capturing, it goes to the capture buffer
+/// instead, and with a sink set, to the sink; capturing wins over both.
 #[derive(Debug)]
 pub struct Console {
     /// Console options and settings.
@@ -99,6 +418,67 @@ pub struct Console {
     height: Option<usize>,
     /// Whether we're in alternative screen mode.
     in_alt_screen: bool,
+    /// Whether the cursor was hidden and not shown again.
+    cursor_hidden: AtomicBool,
+    /// Buffer receiving output instead of stdout while capturing.
+    capture: Mutex<Option<String>>,
+    /// Secondary writer receiving a plain-text transcript.
+    tee: Mutex<Option<PlainTee>>,
+    /// Capabilities injected in place of detection.
+    capabilities: Option<Capabilities>,
+    /// Keys of the messages printed once, with their suppressed repeats.
+    once: Mutex<OnceCounts>,
+    /// Display receiving output in frames instead of stdout.
+    sink: Mutex<Option<ConsoleSink>>,
+    /// Writer receiving output in place of stdout.
+    writer: Mutex<Option<ConsoleWriter>>,
+    /// Highlighter for plain strings, when highlighting is enabled.
+    highlighter: Option<ConsoleHighlighter>,
+}
+
+/// A writer of any type, as output for a console.
+pub type BoxedWriter = Box<dyn Write + Send>;
+
+/// The writer of a console.
+struct ConsoleWriter(BoxedWriter);
+
+impl std::fmt::Debug for ConsoleWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ConsoleWriter")
+    }
+}
+
+/// The highlighter of a console.
+struct ConsoleHighlighter(BoxedHighlighter);
+
+impl std::fmt::Debug for ConsoleHighlighter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ConsoleHighlighter")
+    }
+}
+
+/// Keys of messages printed once, with the number of repeats suppressed.
+type OnceCounts = HashMap<String, usize>;
+/// A marker for a message, its ASCII replacement and its color.
+type OnceMarker = (&'static str, &'static str, StandardColor);
+
+/// The kinds of message printed once per key.
+#[derive(Debug, Clone, Copy)]
+enum OnceKind {
+    Note,
+    Warning,
+    Error,
+}
+
+impl OnceKind {
+    /// Get the marker, its ASCII replacement and its color.
+    fn marker(self) -> OnceMarker {
+        match self {
+            OnceKind::Note => ("ℹ", "i", StandardColor::Blue),
+            OnceKind::Warning => ("⚠", "!", StandardColor::Yellow),
+            OnceKind::Error => ("✖", "x", StandardColor::Red),
+        }
+    }
 }
 
 impl Console {
@@ -117,6 +497,14 @@ impl Console {
             width: None,
             height: None,
             in_alt_screen: false,
+            cursor_hidden: AtomicBool::new(false),
+            capture: Mutex::new(None),
+            tee: Mutex::new(None),
+            capabilities: None,
+            once: Mutex::new(HashMap::new()),
+            sink: Mutex::new(None),
+            writer: Mutex::new(None),
+            highlighter: Some(ConsoleHighlighter(Box::new(ReprHighlighter::new()))),
         }
     }
 
@@ -133,19 +521,173 @@ impl Console {
     /// let console = Console::with_options(options);
     /// ```
     pub fn with_options(options: ConsoleOptions) -> Self {
+        let options = options.synced();
+        let highlighter = match options.render.theme.as_deref() {
+            Some(theme) => ReprHighlighter::new().with_theme(theme.clone()),
+            None => ReprHighlighter::new(),
+        };
         Self {
             options,
             width: None,
             height: None,
             in_alt_screen: false,
+            cursor_hidden: AtomicBool::new(false),
+            capture: Mutex::new(None),
+            tee: Mutex::new(None),
+            capabilities: None,
+            once: Mutex::new(HashMap::new()),
+            sink: Mutex::new(None),
+            writer: Mutex::new(None),
+            highlighter: Some(ConsoleHighlighter(Box::new(highlighter))),
         }
     }
 
+    /// Create a console rendering plain text at exactly `width` cells. See
+    /// [`ConsoleOptions::plain_fixed`] and [`Console::render_block`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Console;
+    ///
+    /// let console = Console::plain_fixed(6);
+    /// assert!(console.clear().is_err());
+    /// ```
+    pub fn plain_fixed(width: usize) -> Self {
+        Self::with_options(ConsoleOptions::plain_fixed(width))
+    }
+
+    /// Create a console for the given capabilities instead of detecting them.
+    ///
+    /// The options are derived from `capabilities`, with its width made
+    /// explicit, and [`Console::capabilities`] returns it unchanged. This
+    /// makes output independent of the environment, for tests and for
+    /// embedders that know their display better than detection does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Capabilities, ColorSystem, Console};
+    ///
+    /// let mut capabilities = Capabilities::probe_once();
+    /// capabilities.color_system = ColorSystem::Standard;
+    /// capabilities.width = 60;
+    /// let console = Console::with_capabilities(capabilities);
+    /// assert_eq!(console.color_system(), ColorSystem::Standard);
+    /// assert_eq!(console.width(), 60);
+    /// ```
+    pub fn with_capabilities(capabilities: Capabilities) -> Self {
+        let options = ConsoleOptions::new()
+            .with_max_width(capabilities.width)
+            .with_color(capabilities.color_enabled)
+            .with_color_system(capabilities.color_system)
+            .with_ascii_only(!capabilities.unicode)
+            .with_accessible(capabilities.accessible);
+        let mut console = Self::with_options(options);
+        console.height = Some(capabilities.height);
+        console.capabilities = Some(capabilities);
+        console
+    }
+
+    /// Create a console writing to `writer` instead of stdout.
+    ///
+    /// Everything the console outputs goes to the writer, including cursor
+    /// movement, clearing and the alternative screen. Each print is
+    /// written and flushed in one piece.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions};
+    ///
+    /// let console = Console::with_writer(Box::new(std::io::sink()));
+    /// console.println("discarded").unwrap();
+    /// ```
+    ///
+    /// To set options as well, use [`Console::set_writer`]:
+    ///
+    /// ```rust
+    /// use luxor::{ColorSystem, Console, ConsoleOptions, Style};
+    ///
+    /// let options = ConsoleOptions::new().with_color_system(ColorSystem::Standard);
+    /// let console = Console::with_options(options);
+    /// console.set_writer(Box::new(Vec::new()));
+    /// console.print(console.styled("Hi", Style::new().bold())).unwrap();
+    /// ```
+    pub fn with_writer(writer: BoxedWriter) -> Self {
+        let console = Self::new();
+        console.set_writer(writer);
+        console
+    }
+
+    /// Create a console whose markup can use the named styles of `theme`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, Theme};
+    ///
+    /// let console = Console::with_theme(Theme::new());
+    /// let output = console.capture(|console| console.print_markup("[info]ok[/info]")).unwrap();
+    /// assert_eq!(luxor::ansi::strip_ansi(&output), "ok");
+    /// assert!(console.print_markup("[infoo]ok[/]").is_err());
+    /// ```
+    pub fn with_theme(theme: Theme) -> Self {
+        Self::with_options(ConsoleOptions::new().with_theme(theme))
+    }
+
     /// Get the console options.
     pub fn options(&self) -> &ConsoleOptions {
         &self.options
     }
 
+    /// Get the options this console renders with.
+    ///
+    /// These are [`ConsoleOptions::render`]. Components that render a child
+    /// with different settings start from a clone of them.
+    pub fn render_options(&self) -> &RenderOptions {
+        &self.options.render
+    }
+
+    /// Summarize what this console detected about its output.
+    ///
+    /// The result can be printed directly, e.g. behind a
+    /// `--diagnose-terminal` flag, to explain unexpected output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions};
+    ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_ascii_only(true));
+    /// assert!(!console.capabilities().unicode);
+    /// ```
+    pub fn capabilities(&self) -> Capabilities {
+        match &self.capabilities {
+            Some(capabilities) => capabilities.clone(),
+            None => Capabilities::detect(self),
+        }
+    }
+
+    /// Check whether output goes to a terminal.
+    ///
+    /// [`ConsoleOptions::with_force_terminal`] decides if set. Otherwise
+    /// output captured, or sent to a writer or sink, is not a terminal,
+    /// and stdout is one if detection found it was, by
+    /// [`std::io::IsTerminal`] or `LUXOR_FORCE_TERMINAL`.
+    pub fn is_terminal(&self) -> bool {
+        if let Some(forced) = self.options.force_terminal {
+            return forced;
+        }
+        if self.is_capturing() || self.lock_writer().is_some() || self.lock_sink().is_some() {
+            return false;
+        }
+        match &self.capabilities {
+            Some(capabilities) => capabilities.is_terminal,
+            None => Capabilities::probe_once().is_terminal,
+        }
+    }
+
     /// Get the terminal width, detecting it if not cached.
     ///
     /// # Examples
@@ -158,11 +700,33 @@ impl Console {
     /// println!("Terminal width: {}", width);
     /// ```
     pub fn width(&self) -> usize {
-        self.width.unwrap_or_else(|| {
-            self.options
-                .max_width
-                .unwrap_or_else(|| terminal::size().map(|(w, _)| w as usize).unwrap_or(80))
-        })
+        match self.sink_size() {
+            Some((width, _)) => width,
+            None => self
+                .width
+                .unwrap_or_else(|| self.options.render.get_max_width()),
+        }
+    }
+
+    /// Get where the console width comes from.
+    ///
+    /// A size cached by [`Console::update_size`] or [`Console::resize`], or
+    /// known to the [`FrameSink`], counts as the terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions, WidthSource};
+    ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_max_width(60));
+    /// assert_eq!(console.width_source(), WidthSource::Explicit);
+    /// ```
+    pub fn width_source(&self) -> WidthSource {
+        if self.width.is_some() || self.sink_size().is_some() {
+            WidthSource::Terminal
+        } else {
+            self.options.render.width_source()
+        }
     }
 
     /// Get the terminal height, detecting it if not cached.
@@ -177,8 +741,12 @@ impl Console {
     /// println!("Terminal height: {}", height);
     /// ```
     pub fn height(&self) -> usize {
-        self.height
-            .unwrap_or_else(|| terminal::size().map(|(_, h)| h as usize).unwrap_or(24))
+        if let Some((_, height)) = self.sink_size() {
+            return height;
+        }
+        self.height.unwrap_or_else(|| {
+            capabilities::terminal_size().map_or(DEFAULT_FALLBACK_HEIGHT, |(_, height)| height)
+        })
     }
 
     /// Get the size of the terminal as (width, height).
@@ -186,11 +754,35 @@ impl Console {
         (self.width(), self.height())
     }
 
+    /// Set the size of the display, for a console whose output does not
+    /// go to a terminal it can ask.
+    ///
+    /// The size is cached like [`Console::update_size`] does, and passed on
+    /// to the [`FrameSink`] if one is set.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = Some(width);
+        self.height = Some(height);
+        if let Some(sink) = self.lock_sink().as_mut() {
+            sink.0.resize(width, height);
+        }
+    }
+
     /// Update the cached terminal size.
     ///
-    /// This should be called when the terminal is resized.
+    /// This should be called when the terminal is resized. With a
+    /// [`FrameSink`] that knows its size, that size is used instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LuxorError::UnsupportedFeature`] without the `terminal`
+    /// feature.
+    #[cfg(feature = "terminal")]
     pub fn update_size(&mut self) -> Result<()> {
-        match terminal::size() {
+        if let Some((width, height)) = self.sink_size() {
+            (self.width, self.height) = (Some(width), Some(height));
+            return Ok(());
+        }
+        match crossterm::terminal::size() {
             Ok((w, h)) => {
                 self.width = Some(w as usize);
                 self.height = Some(h as usize);
@@ -203,26 +795,59 @@ impl Console {
         }
     }
 
+    /// Update the cached terminal size.
+    ///
+    /// Without the `terminal` feature, this only works with a
+    /// [`FrameSink`] that knows its size.
+    #[cfg(not(feature = "terminal"))]
+    pub fn update_size(&mut self) -> Result<()> {
+        if let Some((width, height)) = self.sink_size() {
+            (self.width, self.height) = (Some(width), Some(height));
+            return Ok(());
+        }
+        Err(LuxorError::unsupported_feature(
+            "terminal",
+            "terminal size detection",
+        ))
+    }
+
     /// Enable alternative screen buffer.
     ///
     /// This switches to an alternative screen buffer, allowing you to draw
     /// full-screen applications without affecting the terminal history.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LuxorError::UnsupportedFeature`] without the `terminal`
+    /// feature.
     pub fn enable_alt_screen(&mut self) -> Result<()> {
+        require_terminal("the alternate screen")?;
+        self.require_cursor_control("the alternate screen")?;
         if !self.in_alt_screen {
-            print!("{}", ansi::codes::ALT_SCREEN_ENABLE);
-            io::stdout().flush()?;
+            self.write_output(ansi::codes::ALT_SCREEN_ENABLE)?;
             self.in_alt_screen = true;
         }
         Ok(())
     }
 
+    /// Check whether the alternate screen buffer is active.
+    pub fn is_alt_screen(&self) -> bool {
+        self.in_alt_screen
+    }
+
     /// Disable alternative screen buffer.
     ///
     /// This returns to the normal screen buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LuxorError::UnsupportedFeature`] without the `terminal`
+    /// feature.
     pub fn disable_alt_screen(&mut self) -> Result<()> {
+        require_terminal("the alternate screen")?;
+        self.require_cursor_control("the alternate screen")?;
         if self.in_alt_screen {
-            print!("{}", ansi::codes::ALT_SCREEN_DISABLE);
-            io::stdout().flush()?;
+            self.write_output(ansi::codes::ALT_SCREEN_DISABLE)?;
             self.in_alt_screen = false;
         }
         Ok(())
@@ -230,25 +855,68 @@ impl Console {
 
     /// Clear the screen.
     pub fn clear(&self) -> Result<()> {
-        print!("{}{}", ansi::codes::CLEAR_SCREEN, ansi::codes::CURSOR_HOME);
-        io::stdout().flush()?;
-        Ok(())
+        self.require_cursor_control("clearing the screen")?;
+        self.write_segments(&[
+            Segment::control(ControlCode::Clear),
+            Segment::control(ControlCode::Home),
+        ])
     }
 
     /// Hide the cursor.
+    ///
+    /// The cursor is shown again by [`Console::restore_terminal`], or when
+    /// the console is dropped.
     pub fn hide_cursor(&self) -> Result<()> {
-        print!("{}", ansi::codes::CURSOR_HIDE);
-        io::stdout().flush()?;
+        self.require_cursor_control("hiding the cursor")?;
+        self.write_output(ansi::codes::CURSOR_HIDE)?;
+        self.cursor_hidden.store(true, Ordering::Relaxed);
         Ok(())
     }
 
     /// Show the cursor.
     pub fn show_cursor(&self) -> Result<()> {
-        print!("{}", ansi::codes::CURSOR_SHOW);
-        io::stdout().flush()?;
+        self.require_cursor_control("showing the cursor")?;
+        self.write_output(ansi::codes::CURSOR_SHOW)?;
+        self.cursor_hidden.store(false, Ordering::Relaxed);
         Ok(())
     }
 
+    /// Undo the terminal changes this console made and has not undone.
+    ///
+    /// Shows the cursor if it was hidden with [`Console::hide_cursor`] and
+    /// leaves the alternate screen if it was entered, writing nothing for
+    /// states that were never changed. Styles need no restoring, since
+    /// every styled segment ends with a reset. The console does this when
+    /// it is dropped as well, ignoring errors, so a panic or early return
+    /// does not leave the terminal without a cursor. Calling it first
+    /// means the drop writes nothing further.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Console;
+    ///
+    /// let mut console = Console::new();
+    /// console.begin_capture();
+    /// console.hide_cursor().unwrap();
+    /// console.restore_terminal().unwrap();
+    /// console.restore_terminal().unwrap();
+    /// assert_eq!(console.end_capture(), "\x1b[?25l\x1b[?25h");
+    /// ```
+    pub fn restore_terminal(&mut self) -> Result<()> {
+        let mut restore = String::new();
+        if self.cursor_hidden.swap(false, Ordering::Relaxed) {
+            restore.push_str(ansi::codes::CURSOR_SHOW);
+        }
+        if std::mem::take(&mut self.in_alt_screen) {
+            restore.push_str(ansi::codes::ALT_SCREEN_DISABLE);
+        }
+        if restore.is_empty() {
+            return Ok(());
+        }
+        self.write_output(&restore)
+    }
+
     /// Print a renderable object to the console.
     ///
     /// # Examples
@@ -261,9 +929,10 @@ impl Console {
     /// console.print(styled_text).unwrap();
     /// ```
     pub fn print<R: Renderable>(&self, renderable: R) -> Result<()> {
-        let segments = renderable.render(self, &self.options)?;
-        self.write_segments(&segments)?;
-        Ok(())
+        let options = self.print_options();
+        options.validate()?;
+        let segments = self.render_frame(&renderable, &options)?;
+        self.write_printed(segments)
     }
 
     /// Print a renderable object followed by a newline.
@@ -277,60 +946,719 @@ impl Console {
     /// console.println("Hello, world!").unwrap();
     /// ```
     pub fn println<R: Renderable>(&self, renderable: R) -> Result<()> {
-        self.print(renderable)?;
-        println!();
-        Ok(())
+        let options = self.print_options();
+        options.validate()?;
+        let mut segments = self.render_frame(&renderable, &options)?;
+        segments.push(Segment::line());
+        self.write_printed(segments)
     }
 
-    /// Render a renderable object to segments without printing.
+    /// Print any [`Display`](std::fmt::Display) value, such as a number,
+    /// as formatted by `{}`.
+    ///
+    /// The formatted string is printed as plain text, never parsed as
+    /// markup, so brackets in it are kept. It is highlighted like a plain
+    /// string when [`ConsoleOptions::with_highlight`] is set.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use luxor::{Console, ConsoleOptions};
     ///
-    /// let console = Console::new();
-    /// let options = ConsoleOptions::new();
-    /// let segments = console.render("Hello, world!", &options).unwrap();
+    /// let console = Console::with_options(ConsoleOptions::new().with_color(false));
+    /// let output = console.capture(|console| console.print_display(3.14)).unwrap();
+    /// assert_eq!(output, "3.14");
     /// ```
-    pub fn render<R: Renderable>(
-        &self,
-        renderable: R,
-        options: &ConsoleOptions,
-    ) -> Result<Vec<Segment>> {
-        renderable.render(self, options)
+    pub fn print_display<T: std::fmt::Display>(&self, value: T) -> Result<()> {
+        self.print(self.display_text(value))
     }
 
-    /// Measure a renderable object.
+    /// Print any [`Display`](std::fmt::Display) value followed by a
+    /// newline. See [`Console::print_display`].
+    pub fn println_display<T: std::fmt::Display>(&self, value: T) -> Result<()> {
+        self.println(self.display_text(value))
+    }
+
+    /// Format `value` as text, highlighted if highlighting is enabled.
+    fn display_text<T: std::fmt::Display>(&self, value: T) -> Text {
+        let mut text = Text::new(&value.to_string());
+        if let Some(highlighter) = self.highlighter().filter(|_| self.options.render.highlight) {
+            highlighter.highlight(&mut text);
+        }
+        text
+    }
+
+    /// Parse `markup` and print it, whether or not markup is enabled for
+    /// plain strings.
     ///
-    /// # Examples
+    /// Unlike printing a `&str`, which prints invalid markup literally,
+    /// this prints nothing and returns the error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LuxorError::MarkupError`]
+    /// if the markup is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions};
+    ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_markup(false));
+    /// let output = console.capture(|console| console.print_markup("[red]x[/]")).unwrap();
+    /// assert_eq!(luxor::ansi::strip_ansi(&output), "x");
+    /// assert!(console.print_markup("[red]x[/blue]").is_err());
+    /// ```
+    pub fn print_markup(&self, markup: &str) -> Result<()> {
+        self.print(self.parse_markup(markup)?)
+    }
+
+    /// Parse `markup` and print it followed by a newline. See
+    /// [`Console::print_markup`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LuxorError::MarkupError`]
+    /// if the markup is malformed.
+    pub fn println_markup(&self, markup: &str) -> Result<()> {
+        self.println(self.parse_markup(markup)?)
+    }
+
+    /// Parse `markup` and print it, repairing it where it is malformed
+    /// rather than failing.
+    ///
+    /// The markup is repaired as by
+    /// [`render_lossy`](crate::markup::render_lossy), and each repair is
+    /// reported as a
+    /// [`Warning::LossyMarkup`](crate::diagnostics::Warning::LossyMarkup)
+    /// and returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if printing fails.
+    ///
+    /// # Examples
     ///
     /// ```rust
     /// use luxor::{Console, ConsoleOptions};
     ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_color(false));
+    /// let mut warnings = Vec::new();
+    /// let output = console
+    ///     .capture(|console| {
+    ///         warnings = console.print_markup_lossy("[bold]x[/blue]")?;
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(luxor::ansi::strip_ansi(&output), "x");
+    /// assert_eq!(warnings.len(), 2);
+    /// ```
+    pub fn print_markup_lossy(&self, markup: &str) -> Result<Vec<MarkupWarning>> {
+        let (text, warnings) = self.parse_markup_lossy(markup);
+        self.print(text)?;
+        Ok(warnings)
+    }
+
+    /// Parse `markup` and print it followed by a newline, repairing it
+    /// where it is malformed. See [`Console::print_markup_lossy`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if printing fails.
+    pub fn println_markup_lossy(&self, markup: &str) -> Result<Vec<MarkupWarning>> {
+        let (text, warnings) = self.parse_markup_lossy(markup);
+        self.println(text)?;
+        Ok(warnings)
+    }
+
+    /// Parse `markup` with the console's theme, if it has one.
+    fn parse_markup(&self, markup: &str) -> Result<Text> {
+        match self.options.render.resolved_theme().as_deref() {
+            Some(theme) => crate::markup::render_with_theme(markup, None, theme),
+            None => crate::markup::render(markup, None),
+        }
+    }
+
+    /// Parse `markup` lossily with the console's theme, if it has one.
+    fn parse_markup_lossy(&self, markup: &str) -> LossyText {
+        match self.options.render.resolved_theme().as_deref() {
+            Some(theme) => crate::markup::render_lossy_with_theme(markup, None, theme),
+            None => crate::markup::render_lossy(markup, None),
+        }
+    }
+
+    /// Print a [`ColumnRuler`](crate::components::ColumnRuler) across the
+    /// console width, to see which column output lands in.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions};
+    ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_max_width(12));
+    /// let output = console.capture(|console| console.debug_ruler()).unwrap();
+    /// assert!(luxor::ansi::strip_ansi(&output).ends_with("\n012345678901\n"));
+    /// ```
+    pub fn debug_ruler(&self) -> Result<()> {
+        self.println(crate::components::ColumnRuler::new())
+    }
+
+    /// Print a warning the first time `key` is seen, and count it as
+    /// suppressed after that. Returns whether it was printed.
+    ///
+    /// The warning is drawn after a yellow `⚠`, or `!` when only ASCII may
+    /// be drawn. Keys are shared with [`Console::note_once`] and
+    /// [`Console::error_once`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions};
+    ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_color(false));
+    /// let output = console
+    ///     .capture(|console| {
+    ///         for _ in 0..3 {
+    ///             console.warn_once("legacy-config", "config.ini is deprecated")?;
+    ///         }
+    ///         console.flush_once_summary().map(|_| ())
+    ///     })
+    ///     .unwrap();
+    /// let output = luxor::ansi::strip_ansi(&output);
+    /// assert!(output.ends_with("config.ini is deprecated\n(2 similar messages suppressed)\n"));
+    /// ```
+    pub fn warn_once<R: Renderable>(&self, key: &str, renderable: R) -> Result<bool> {
+        self.print_once(OnceKind::Warning, key, renderable)
+    }
+
+    /// Print a note after a blue `ℹ` the first time `key` is seen. See
+    /// [`Console::warn_once`].
+    pub fn note_once<R: Renderable>(&self, key: &str, renderable: R) -> Result<bool> {
+        self.print_once(OnceKind::Note, key, renderable)
+    }
+
+    /// Print an error after a red `✖` the first time `key` is seen. See
+    /// [`Console::warn_once`].
+    pub fn error_once<R: Renderable>(&self, key: &str, renderable: R) -> Result<bool> {
+        self.print_once(OnceKind::Error, key, renderable)
+    }
+
+    /// Get the keys seen by the `*_once` methods with the number of times
+    /// each was suppressed.
+    pub fn once_counts(&self) -> HashMap<String, usize> {
+        self.lock_once().clone()
+    }
+
+    /// Forget the keys seen by the `*_once` methods, so each prints again.
+    pub fn reset_once_flags(&self) {
+        self.lock_once().clear();
+    }
+
+    /// Print how many messages the `*_once` methods suppressed, as a dim
+    /// line such as `(7 similar messages suppressed)`, and start counting
+    /// from zero again. Prints nothing if none were. Returns the number.
+    pub fn flush_once_summary(&self) -> Result<usize> {
+        let suppressed = {
+            let mut once = self.lock_once();
+            once.values_mut().map(std::mem::take).sum::<usize>()
+        };
+        if suppressed > 0 {
+            let summary = format!(
+                "({} similar message{} suppressed)",
+                suppressed,
+                if suppressed == 1 { "" } else { "s" }
+            );
+            self.write_printed(vec![
+                Segment::new(summary, Style::new().dim()),
+                Segment::line(),
+            ])?;
+        }
+        Ok(suppressed)
+    }
+
+    fn print_once<R: Renderable>(&self, kind: OnceKind, key: &str, renderable: R) -> Result<bool> {
+        {
+            let mut once = self.lock_once();
+            if let Some(suppressed) = once.get_mut(key) {
+                *suppressed += 1;
+                return Ok(false);
+            }
+            once.insert(key.to_string(), 0);
+        }
+
+        let options = self.print_options();
+        options.validate()?;
+        let (marker, ascii, color) = kind.marker();
+        let marker = if options.ascii_only { ascii } else { marker };
+        let prefix = format!("{} ", marker);
+        let indent = render_utils::visible_width(&prefix);
+        let inner = render_utils::narrow(&options, indent);
+        let content = self.render_timed(&renderable, &inner)?;
+        let style = Style::new().bold().with_color(Color::Standard(color));
+        let lines = render_utils::split_lines(content)
+            .into_iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let mut output = Vec::with_capacity(line.len() + 1);
+                if index == 0 {
+                    output.push(Segment::new(prefix.as_str(), style.clone()));
+                } else {
+                    output.extend(render_utils::fill(indent, Style::default()));
+                }
+                output.extend(line);
+                output
+            })
+            .collect();
+        let mut segments = render_utils::frame_lines(lines);
+        segments.push(Segment::line());
+        self.write_printed(segments)?;
+        Ok(true)
+    }
+
+    /// Render a renderable object to segments without printing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, RenderOptions};
+    ///
+    /// let console = Console::new();
+    /// let options = RenderOptions::new();
+    /// let segments = console.render("Hello, world!", &options).unwrap();
+    /// ```
+    pub fn render<R: Renderable>(
+        &self,
+        renderable: R,
+        options: &RenderOptions,
+    ) -> Result<Vec<Segment>> {
+        options.validate()?;
+        self.render_timed(&renderable, options)
+    }
+
+    /// Render a printed frame, starting a new profile when profiling.
+    fn render_frame<R: Renderable>(
+        &self,
+        renderable: &R,
+        options: &RenderOptions,
+    ) -> Result<Vec<Segment>> {
+        if let Some(profiler) = &options.profiler {
+            profiler.reset();
+        }
+        self.render_timed(renderable, options)
+    }
+
+    /// Get the render timings of the last print, if profiling is enabled
+    /// with [`ConsoleOptions::with_profiling`].
+    pub fn render_profile(&self) -> Option<RenderProfile> {
+        self.options
+            .render
+            .profiler
+            .as_ref()
+            .map(Profiler::snapshot)
+    }
+
+    /// Render, leaving a breadcrumb with the time taken when tracing.
+    fn render_timed<R: Renderable>(
+        &self,
+        renderable: &R,
+        options: &RenderOptions,
+    ) -> Result<Vec<Segment>> {
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+        let segments = options.render_child(renderable, self)?;
+        diagnostics::breadcrumb!(
+            renderable = std::any::type_name::<R>(),
+            elapsed = ?started.elapsed(),
+            segments = segments.len(),
+            "rendered"
+        );
+        Ok(segments)
+    }
+
+    /// Render a renderable at `width` into lines that can be re-rendered
+    /// cheaply when the width changes.
+    ///
+    /// The console's options are used with the maximum width set to
+    /// `width`. See [`Rendered`] for reusing the result.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, Text};
+    ///
     /// let console = Console::new();
-    /// let options = ConsoleOptions::new();
+    /// let rendered = console.render_lines(Text::new("left pane"), 4).unwrap();
+    /// assert_eq!(rendered.height(), 2);
+    /// ```
+    pub fn render_lines<R: Renderable + CacheKey>(
+        &self,
+        renderable: R,
+        width: usize,
+    ) -> Result<Rendered<R>> {
+        Rendered::new(renderable, self, width)
+    }
+
+    /// Render a renderable to a block of plain text at the console's
+    /// width, for monospace targets without a terminal, such as chat code
+    /// blocks and log files.
+    ///
+    /// Styles and control codes are dropped, text is sanitized with
+    /// [`Policy::Strict`] and tabs become spaces, so the block holds
+    /// nothing but printable characters. Box-drawing characters become
+    /// ASCII when the console is [ASCII-only](ConsoleOptions::with_ascii_only).
+    /// Every line is padded or truncated to exactly the width, or with
+    /// `ragged` only truncated, with trailing spaces removed.
+    ///
+    /// This is usually called on a [`Console::plain_fixed`] console.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, components::Tree};
+    ///
+    /// let mut tree = Tree::new("src");
+    /// tree.add("main.rs");
+    /// let console = Console::plain_fixed(12);
+    /// assert_eq!(
+    ///     console.render_block(tree, false).unwrap(),
+    ///     "src         \n└── main.rs "
+    /// );
+    /// ```
+    pub fn render_block<R: Renderable>(&self, renderable: R, ragged: bool) -> Result<String> {
+        let options = self.print_options();
+        let width = options.get_max_width();
+        let segments = self.render(renderable, &options)?;
+        let plain: Vec<Segment> = segments
+            .iter()
+            .filter(|segment| !segment.is_control())
+            .map(|segment| {
+                let text = crate::sanitize::sanitize(segment.text(), Policy::Strict);
+                let text = match options.ascii_only {
+                    true => render_utils::ascii_box_drawing(&text).into_owned(),
+                    false => text.into_owned(),
+                };
+                Segment::new(text.replace('\t', " "), Style::new())
+            })
+            .collect();
+
+        let lines: Vec<String> = render_utils::split_lines(plain)
+            .into_iter()
+            .map(|line| {
+                if ragged {
+                    let mut line: Segments = line.into_iter().collect();
+                    line.truncate_cells(width);
+                    line.plain_text().trim_end().to_string()
+                } else {
+                    render_utils::adjust_line_length(line, width, Style::new())
+                        .iter()
+                        .map(Segment::text)
+                        .collect()
+                }
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    /// Measure a renderable object.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, RenderOptions};
+    ///
+    /// let console = Console::new();
+    /// let options = RenderOptions::new();
     /// let measurement = console.measure("Hello, world!", &options).unwrap();
     /// println!("Width: {} - {}", measurement.minimum(), measurement.maximum());
     /// ```
     pub fn measure<M: Measurable>(
         &self,
         measurable: M,
-        options: &ConsoleOptions,
+        options: &RenderOptions,
     ) -> Result<Measurement> {
         measurable.measure(self, options)
     }
 
+    /// Write the output of [`Console::print`], showing its whitespace if
+    /// asked to.
+    fn write_printed(&self, segments: Vec<Segment>) -> Result<()> {
+        if self.options.debug_whitespace {
+            self.write_segments(&render_utils::show_whitespace(segments))
+        } else {
+            self.write_segments(&segments)
+        }
+    }
+
     /// Write segments directly to the output.
     fn write_segments(&self, segments: &[Segment]) -> Result<()> {
-        for segment in segments {
-            let output = segment.render(self.options.get_color_system());
-            print!("{}", output);
+        if segments.iter().any(Segment::is_control) {
+            self.require_cursor_control("writing control codes")?;
+        }
+        if let Some(tee) = self.lock_tee().as_mut() {
+            tee.write_segments(segments);
+        }
+        let color_system = self.options.get_color_system();
+        if let Some(buffer) = self.lock_capture().as_mut() {
+            segment::render_segments_to(segments, buffer, color_system)
+                .map_err(|_| LuxorError::rendering("failed to render segments"))?;
+            return Ok(());
+        }
+        if let Some(sink) = self.lock_sink().as_mut() {
+            let frame = segment::render_segments(segments, color_system);
+            sink.0.frame(frame.as_bytes());
+            return Ok(());
+        }
+        if let Some(writer) = self.lock_writer().as_mut() {
+            let mut buffered = io::BufWriter::new(&mut writer.0);
+            segment::write_segments_to(segments, &mut buffered, color_system)?;
+            buffered.flush()?;
+            return Ok(());
+        }
+        let mut stdout = io::BufWriter::new(io::stdout().lock());
+        segment::write_segments_to(segments, &mut stdout, color_system)?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    /// Fail with [`LuxorError::Terminal`] on a console writing plain text
+    /// to a fixed-width block, which has no cursor to control.
+    fn require_cursor_control(&self, operation: &str) -> Result<()> {
+        if self.options.plain_fixed {
+            return Err(LuxorError::terminal(format!(
+                "{operation} is not available on a plain fixed-width console"
+            )));
         }
-        io::stdout().flush()?;
         Ok(())
     }
 
+    /// Write raw output to the capture buffer if capturing, otherwise the
+    /// sink, the writer or stdout.
+    pub(crate) fn write_output(&self, output: &str) -> Result<()> {
+        if let Some(buffer) = self.lock_capture().as_mut() {
+            buffer.push_str(output);
+            return Ok(());
+        }
+        if let Some(sink) = self.lock_sink().as_mut() {
+            sink.0.frame(output.as_bytes());
+            return Ok(());
+        }
+        if let Some(writer) = self.lock_writer().as_mut() {
+            writer.0.write_all(output.as_bytes())?;
+            writer.0.flush()?;
+            return Ok(());
+        }
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(output.as_bytes())?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    fn lock_capture(&self) -> MutexGuard<'_, Option<String>> {
+        self.capture
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock_once(&self) -> MutexGuard<'_, OnceCounts> {
+        self.once
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock_writer(&self) -> MutexGuard<'_, Option<ConsoleWriter>> {
+        self.writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock_sink(&self) -> MutexGuard<'_, Option<ConsoleSink>> {
+        self.sink
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// The render options for one print, in a measure pass of its own.
+    fn print_options(&self) -> RenderOptions {
+        let mut options = self.sized_options().into_owned();
+        options.measure_pass = Some(Default::default());
+        options
+    }
+
+    /// The render options to print with, taking the width and height from
+    /// the sink or the cached terminal size where they set none.
+    fn sized_options(&self) -> Cow<'_, RenderOptions> {
+        let render = &self.options.render;
+        let size = self.sink_size();
+        let width = size
+            .map(|(width, _)| width)
+            .or(self.width)
+            .filter(|_| render.max_width.is_none());
+        let height = size
+            .map(|(_, height)| height)
+            .or(self.height)
+            .filter(|_| render.max_height.is_none());
+        if width.is_none() && height.is_none() {
+            return Cow::Borrowed(render);
+        }
+        let mut options = render.clone();
+        options.max_width = width.or(options.max_width);
+        options.max_height = height.or(options.max_height);
+        Cow::Owned(options)
+    }
+
+    fn sink_size(&self) -> Option<FrameSize> {
+        self.lock_sink().as_ref().and_then(|sink| sink.0.size())
+    }
+
+    fn lock_tee(&self) -> MutexGuard<'_, Option<PlainTee>> {
+        self.tee
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Also write a plain-text transcript of all output to `writer`.
+    ///
+    /// The transcript has no styles or escape codes. In-place redraws are
+    /// resolved with [`TeeMode::Final`], so only their last state is kept.
+    /// Writes are flushed line by line. If writing fails, the tee is
+    /// detached and a
+    /// [`Warning::TeeDetached`](crate::diagnostics::Warning::TeeDetached) is
+    /// reported; console output carries on regardless. Attaching a tee replaces any previous one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions, Style};
+    ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_max_width(40));
+    /// console.tee_plain(Box::new(std::io::sink()));
+    /// console.capture(|console| console.println(console.styled("saved", Style::new().bold())))
+    ///     .unwrap();
+    /// assert!(console.end_tee().is_some());
+    /// ```
+    pub fn tee_plain(&self, writer: TeeWriter) {
+        self.tee_plain_with_mode(writer, TeeMode::default());
+    }
+
+    /// Also write a plain-text transcript of all output to `writer`,
+    /// resolving in-place redraws according to `mode`.
+    ///
+    /// See [`Console::tee_plain`].
+    pub fn tee_plain_with_mode(&self, writer: TeeWriter, mode: TeeMode) {
+        let previous = self.lock_tee().replace(PlainTee::new(writer, mode));
+        drop(previous);
+    }
+
+    /// Stop the plain-text transcript, writing any lines still held back,
+    /// and return its writer.
+    ///
+    /// Returns `None` if no tee was attached or it was detached after a
+    /// write error.
+    pub fn end_tee(&self) -> Option<TeeWriter> {
+        let tee = self.lock_tee().take();
+        tee.and_then(PlainTee::finish)
+    }
+
+    /// Send output to `sink` instead of stdout, one frame per print.
+    ///
+    /// Capturing still takes precedence over the sink. Setting a sink
+    /// replaces the one set before.
+    pub fn set_sink(&self, sink: impl FrameSink + 'static) {
+        *self.lock_sink() = Some(ConsoleSink(Box::new(sink)));
+    }
+
+    /// Stop sending output to the sink, and return it.
+    pub fn take_sink(&self) -> Option<BoxedSink> {
+        self.lock_sink().take().map(|sink| sink.0)
+    }
+
+    /// Write output to `writer` instead of stdout, replacing the writer set
+    /// before. Capturing and the sink still take precedence.
+    pub fn set_writer(&self, writer: BoxedWriter) {
+        *self.lock_writer() = Some(ConsoleWriter(writer));
+    }
+
+    /// Go back to writing to stdout, and return the writer.
+    pub fn take_writer(&self) -> Option<BoxedWriter> {
+        self.lock_writer().take().map(|writer| writer.0)
+    }
+
+    /// Highlight plain strings with `highlighter`, replacing the one set
+    /// before, when highlighting is enabled with
+    /// [`ConsoleOptions::with_highlight`].
+    ///
+    /// The default is a [`ReprHighlighter`] using the console's theme.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions, highlighter::NullHighlighter};
+    ///
+    /// let mut console = Console::with_options(ConsoleOptions::new().with_highlight(true));
+    /// console.set_highlighter(NullHighlighter);
+    /// let output = console.capture(|console| console.print("took 12 ms")).unwrap();
+    /// assert_eq!(output, "took 12 ms");
+    /// ```
+    pub fn set_highlighter(&mut self, highlighter: impl Highlighter + 'static) {
+        self.highlighter = Some(ConsoleHighlighter(Box::new(highlighter)));
+    }
+
+    /// Stop highlighting plain strings, and return the highlighter.
+    pub fn take_highlighter(&mut self) -> Option<BoxedHighlighter> {
+        self.highlighter.take().map(|highlighter| highlighter.0)
+    }
+
+    /// Get the highlighter for plain strings, if there is one.
+    pub fn highlighter(&self) -> Option<&dyn Highlighter> {
+        self.highlighter
+            .as_ref()
+            .map(|highlighter| highlighter.0.as_ref())
+    }
+
+    /// Start capturing output instead of writing it to stdout.
+    ///
+    /// Everything printed until [`Console::end_capture`] is collected in a
+    /// buffer, including ANSI escape sequences. Starting a capture while
+    /// one is active keeps the existing buffer.
+    pub fn begin_capture(&self) {
+        self.lock_capture().get_or_insert_with(String::new);
+    }
+
+    /// Stop capturing and return the captured output.
+    ///
+    /// Returns an empty string if no capture was active.
+    pub fn end_capture(&self) -> String {
+        self.lock_capture().take().unwrap_or_default()
+    }
+
+    /// Check whether output is currently being captured.
+    pub fn is_capturing(&self) -> bool {
+        self.lock_capture().is_some()
+    }
+
+    /// Run a closure with output captured, returning what it printed.
+    ///
+    /// Useful for tests and headless runs where there is no terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions, ColorSystem};
+    ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_color(false));
+    /// let output = console.capture(|console| console.println("Hello")).unwrap();
+    /// assert_eq!(output, "Hello\n");
+    /// ```
+    pub fn capture<F>(&self, f: F) -> Result<String>
+    where
+        F: FnOnce(&Console) -> Result<()>,
+    {
+        self.begin_capture();
+        let result = f(self);
+        let output = self.end_capture();
+        result.map(|_| output)
+    }
+
     /// Create a styled string with the given style.
     ///
     /// This is a convenience method for applying a style to a string.
@@ -341,7 +1669,7 @@ impl Console {
     /// use luxor::{Console, Style, Color};
     ///
     /// let console = Console::new();
-    /// let styled = console.styled("Hello", Style::new().color(Color::rgb(255, 0, 0)));
+    /// let styled = console.styled("Hello", Style::new().with_color(Color::rgb(255, 0, 0)));
     /// console.print(styled).unwrap();
     /// ```
     pub fn styled(&self, text: &str, style: Style) -> StyledText {
@@ -351,9 +1679,11 @@ impl Console {
         }
     }
 
-    /// Get console options suitable for the current terminal.
-    pub fn get_render_options(&self) -> ConsoleOptions {
-        self.options.clone()
+    /// Get a copy of the options this console prints with: its render
+    /// options, with the size of the [`FrameSink`] or the cached terminal
+    /// size if they set none.
+    pub fn get_render_options(&self) -> RenderOptions {
+        self.sized_options().into_owned()
     }
 
     /// Check if color output is enabled and supported.
@@ -367,6 +1697,13 @@ impl Console {
     }
 }
 
+impl Drop for Console {
+    fn drop(&mut self) {
+        // Best effort: there is nobody left to report a failure to
+        let _ = self.restore_terminal();
+    }
+}
+
 impl Default for Console {
     fn default() -> Self {
         Self::new()
@@ -374,6 +1711,10 @@ impl Default for Console {
 }
 
 /// A styled text object that can be rendered by the console.
+///
+/// It renders and measures as a [`Text`] of the same content
+/// and style, so wrapping, justification and overflow work the same for
+/// both.
 #[derive(Debug, Clone)]
 pub struct StyledText {
     text: String,
@@ -397,17 +1738,21 @@ impl StyledText {
     }
 }
 
+impl From<StyledText> for Text {
+    fn from(styled: StyledText) -> Self {
+        Text::new(&styled.text).with_style(styled.style)
+    }
+}
+
 impl Renderable for StyledText {
-    fn render(&self, _console: &Console, _options: &ConsoleOptions) -> Result<Vec<Segment>> {
-        Ok(vec![Segment::new(self.text.clone(), self.style.clone())])
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        Text::from(self.clone()).render(console, options)
     }
 }
 
 impl Measurable for StyledText {
-    fn measure(&self, _console: &Console, _options: &ConsoleOptions) -> Result<Measurement> {
-        use unicode_width::UnicodeWidthStr;
-        let width = self.text.width();
-        Ok(Measurement::fixed(width))
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        Text::from(self.clone()).measure(console, options)
     }
 }
 
@@ -423,6 +1768,336 @@ mod tests {
         assert!(console.height() > 0);
     }
 
+    #[test]
+    fn test_console_capture() {
+        let console = Console::with_options(ConsoleOptions::new().with_color(false));
+        assert!(!console.is_capturing());
+
+        let output = console
+            .capture(|console| {
+                assert!(console.is_capturing());
+                console.print("Hello")?;
+                console.println(", world")
+            })
+            .unwrap();
+        assert_eq!(output, "Hello, world\n");
+        assert!(!console.is_capturing());
+    }
+
+    #[test]
+    // 3.14 is the value being printed, not an approximation of pi
+    #[allow(clippy::approx_constant)]
+    fn test_print_display() {
+        let options = ConsoleOptions::new().with_max_width(20).with_color(false);
+        let console = Console::with_options(options);
+
+        let output = console.capture(|console| console.print_display(42));
+        assert_eq!(output.unwrap(), "42");
+        let output = console.capture(|console| console.println_display(3.14));
+        assert_eq!(output.unwrap(), "3.14\n");
+        let output = console.capture(|console| console.println_display("one\ntwo three"));
+        assert_eq!(output.unwrap(), "one\ntwo three\n");
+        // Never markup, even when plain strings are parsed
+        let output = console.capture(|console| console.print_display("[bold]x"));
+        assert_eq!(output.unwrap(), "[bold]x");
+        // Long values fold to the width like any text
+        let long = "9".repeat(50);
+        let output = console
+            .capture(|console| console.print_display(&long))
+            .unwrap();
+        assert!(output.lines().all(|line| line.len() <= 20), "{output:?}");
+        assert_eq!(output.replace('\n', ""), long);
+    }
+
+    #[test]
+    fn test_print_highlights_plain_strings() {
+        let options = ConsoleOptions::new()
+            .with_color_system(ColorSystem::Standard)
+            .with_highlight(true);
+        let mut console = Console::with_options(options);
+        let line = r#"Got 200 from https://x.dev in 3.5s "ok" at 2024-01-01"#;
+        let rendered = |console: &Console| line.render(console, console.render_options()).unwrap();
+
+        let segments = rendered(&console);
+        let plain: String = segments.iter().map(|s| s.text()).collect();
+        assert_eq!(plain, line);
+        let theme = Theme::new();
+        for (value, name) in [
+            ("200", "repr.number"),
+            ("https://x.dev", "repr.url"),
+            ("\"ok\"", "repr.str"),
+            ("2024", "repr.number"),
+        ] {
+            let segment = segments.iter().find(|s| s.text() == value).unwrap();
+            assert_eq!(segment.style(), theme.get(name).unwrap(), "{value}");
+        }
+        // Display values are highlighted too, but Text is left alone
+        let output = console.capture(|console| console.print_display(7)).unwrap();
+        assert_eq!(output, "\x1b[36;1m7\x1b[0m");
+        let output = console.capture(|console| console.print(Text::new("7")));
+        assert_eq!(output.unwrap(), "7");
+
+        console.take_highlighter();
+        assert_eq!(rendered(&console).len(), 1);
+    }
+
+    #[test]
+    fn test_print_markup() {
+        let options = ConsoleOptions::new().with_color_system(ColorSystem::Standard);
+        let console = Console::with_options(options.with_markup(false));
+        let printed = |markup: &str| console.capture(|console| console.println_markup(markup));
+        let expected = |text: Text| console.capture(|console| console.println(text)).unwrap();
+
+        // Nested tags, and an implicit close of the innermost one
+        let nested = Text::from_markup("[bold]a [italic]b[/italic] c[/bold]").unwrap();
+        let output = printed("[bold]a [italic]b[/] c[/bold]").unwrap();
+        assert_eq!(output, expected(nested));
+        assert!(output.contains("\x1b[1;3m"), "{output:?}");
+        assert_eq!(printed("[[not a tag]").unwrap(), "[not a tag]\n");
+        // Without tags the text is printed as is
+        assert_eq!(printed("plain").unwrap(), "plain\n");
+
+        let error = printed("[bold]oops[/italic]").unwrap_err();
+        assert!(matches!(error, crate::LuxorError::MarkupError { .. }));
+        assert_eq!(
+            printed("[red]x[/]").unwrap(),
+            expected(Text::from_markup("[red]x[/red]").unwrap())
+        );
+        // Plain strings still follow the console setting
+        assert_eq!(
+            console
+                .capture(|console| console.print("[red]x[/]"))
+                .unwrap(),
+            "[red]x[/]"
+        );
+    }
+
+    #[test]
+    fn test_print_markup_lossy() {
+        use crate::{
+            diagnostics::{Warning, collect_warnings},
+            markup::MarkupWarningKind,
+        };
+
+        let options = ConsoleOptions::new()
+            .with_color_system(ColorSystem::Standard)
+            .with_theme(Theme::new());
+        let console = Console::with_options(options.with_markup(false));
+        let mut repaired = Vec::new();
+        let warnings = collect_warnings(|| {
+            let output = console
+                .capture(|console| {
+                    repaired = console.println_markup_lossy("[warning]low[/] [bold]disk[/red]")?;
+                    Ok(())
+                })
+                .unwrap();
+            let expected = crate::markup::render_with_theme(
+                "[warning]low[/] [bold]disk[/]",
+                None,
+                &Theme::new(),
+            )
+            .unwrap();
+            assert_eq!(
+                output,
+                console
+                    .capture(|console| console.println(expected))
+                    .unwrap()
+            );
+        });
+        let kinds: Vec<_> = repaired.iter().map(|warning| warning.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                MarkupWarningKind::UnmatchedClosingTag,
+                MarkupWarningKind::UnclosedTag
+            ]
+        );
+        for warning in repaired {
+            assert!(warnings.contains(&Warning::LossyMarkup(warning)));
+        }
+        // Well-formed markup needs no repair
+        let mut repaired = None;
+        console
+            .capture(|console| {
+                repaired = Some(console.print_markup_lossy("[red]x[/]")?);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(repaired, Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_console_capture_styled_and_manual() {
+        let options = ConsoleOptions::new().with_color_system(ColorSystem::Standard);
+        let console = Console::with_options(options);
+
+        console.begin_capture();
+        console
+            .print(Text::new("Hi").with_style(Style::new().bold()))
+            .unwrap();
+        let output = console.end_capture();
+        assert!(output.contains("\x1b[1m"));
+        assert!(output.contains("Hi"));
+
+        assert_eq!(console.end_capture(), "");
+    }
+
+    #[test]
+    fn test_once_messages_print_once_and_count_repeats() {
+        let options = ConsoleOptions::new()
+            .with_max_width(20)
+            .with_ascii_only(false)
+            .with_color(false);
+        let console = Console::with_options(options);
+        let output = console
+            .capture(|console| {
+                let message = Text::new("disk is slow to respond");
+                assert!(console.warn_once("slow", message.clone())?);
+                assert!(!console.warn_once("slow", message)?);
+                assert!(console.error_once("auth", Text::new("denied"))?);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(
+            ansi::strip_ansi(&output),
+            "⚠ disk is slow to\n  respond\n✖ denied\n"
+        );
+        assert_eq!(console.once_counts()["slow"], 1);
+        assert_eq!(console.once_counts()["auth"], 0);
+
+        // Notes share keys with warnings
+        let summary = console
+            .capture(|console| {
+                assert!(!console.note_once("auth", "denied")?);
+                assert_eq!(console.flush_once_summary()?, 2);
+                assert_eq!(console.flush_once_summary()?, 0);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(
+            ansi::strip_ansi(&summary),
+            "(2 similar messages suppressed)\n"
+        );
+
+        console.reset_once_flags();
+        assert!(console.once_counts().is_empty());
+        let again = console
+            .capture(|console| console.note_once("auth", "retrying").map(|_| ()))
+            .unwrap();
+        assert_eq!(ansi::strip_ansi(&again), "ℹ retrying\n");
+    }
+
+    #[test]
+    fn test_console_capture_error_ends_capture() {
+        let console = Console::new();
+        let result = console.capture(|_| Err(LuxorError::rendering("boom")));
+        assert!(result.is_err());
+        assert!(!console.is_capturing());
+    }
+
+    type Bytes = std::sync::Arc<Mutex<Vec<u8>>>;
+
+    /// A writer whose contents stay readable after it is handed over.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Bytes);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    /// Print a line, a three-frame live display and a closing line.
+    fn print_with_live_display(console: &Console) -> Result<()> {
+        console.println(console.styled("start", Style::new().bold()))?;
+        for step in 1..=3 {
+            let mut frame = Vec::new();
+            if step > 1 {
+                frame.push(Segment::control(ControlCode::CarriageReturn));
+                frame.push(Segment::control(ControlCode::CursorUp(1)));
+            }
+            frame.push(Segment::new(
+                format!("progress {}/3\nstep {}", step, step),
+                Style::new(),
+            ));
+            console.write_segments(&frame)?;
+        }
+        console.println("")?;
+        console.println("done")
+    }
+
+    fn transcript(mode: TeeMode) -> String {
+        let console = Console::with_options(ConsoleOptions::new().with_max_width(40));
+        let buffer = SharedBuffer::default();
+        console.tee_plain_with_mode(Box::new(buffer.clone()), mode);
+        let output = console.capture(print_with_live_display).unwrap();
+        assert!(output.contains("\x1b[1mstart"));
+        assert!(console.end_tee().is_some());
+        buffer.contents()
+    }
+
+    #[test]
+    fn test_tee_final_keeps_last_frame() {
+        assert_eq!(
+            transcript(TeeMode::Final),
+            "start\nprogress 3/3\nstep 3\ndone\n"
+        );
+    }
+
+    #[test]
+    fn test_tee_sequential_keeps_every_frame() {
+        assert_eq!(
+            transcript(TeeMode::Sequential),
+            "start\nprogress 1/3\nstep 1\nprogress 2/3\nstep 2\nprogress 3/3\nstep 3\ndone\n"
+        );
+    }
+
+    #[test]
+    fn test_tee_write_error_detaches() {
+        struct Broken;
+
+        impl Write for Broken {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "gone"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let console = Console::with_options(ConsoleOptions::new().with_color(false));
+        console.tee_plain_with_mode(Box::new(Broken), TeeMode::Sequential);
+        let warnings = crate::diagnostics::collect_warnings(|| {
+            let output = console
+                .capture(|console| {
+                    console.println("one")?;
+                    console.println("two")
+                })
+                .unwrap();
+            assert_eq!(output, "one\ntwo\n");
+        });
+        assert!(console.end_tee().is_none());
+        let detached = crate::diagnostics::Warning::TeeDetached {
+            error: "gone".to_string(),
+        };
+        assert!(warnings.contains(&detached));
+        assert_eq!(
+            detached.to_string(),
+            "plain tee detached after write error: gone"
+        );
+    }
+
     #[test]
     fn test_console_options() {
         let options = ConsoleOptions::new()
@@ -437,7 +2112,7 @@ mod tests {
 
     #[test]
     fn test_styled_text() {
-        let style = Style::new().bold().color(Color::rgb(255, 0, 0));
+        let style = Style::new().bold().with_color(Color::rgb(255, 0, 0));
         let styled = StyledText::new("Hello".to_string(), style.clone());
 
         assert_eq!(styled.text(), "Hello");
@@ -447,7 +2122,7 @@ mod tests {
     #[test]
     fn test_styled_text_renderable() {
         let console = Console::new();
-        let options = ConsoleOptions::default();
+        let options = RenderOptions::default();
         let styled = StyledText::new("Hello".to_string(), Style::new().bold());
 
         let segments = styled.render(&console, &options).unwrap();
@@ -466,7 +2141,7 @@ mod tests {
     #[test]
     fn test_text_renderable() {
         let console = Console::new();
-        let options = ConsoleOptions::default();
+        let options = RenderOptions::default();
         let text = Text::new("Hello").with_style(Style::new().bold());
 
         let segments = text.render(&console, &options).unwrap();
@@ -477,7 +2152,7 @@ mod tests {
     #[test]
     fn test_text_measurable() {
         let console = Console::new();
-        let options = ConsoleOptions::default();
+        let options = RenderOptions::default();
         let text = Text::new("Hello");
 
         let measurement = text.measure(&console, &options).unwrap();
@@ -496,12 +2171,364 @@ mod tests {
     }
 
     #[test]
-    fn test_console_options_get_max_width() {
-        let options = ConsoleOptions::new().with_max_width(100);
-        assert_eq!(options.get_max_width(), 100);
+    fn test_simple_text_paths_render_alike() {
+        let console = Console::new();
+        let content = "a long line of text that folds\nthen a 漢字 line\n";
+        let style = Style::new().bold();
+        let text = Text::new(content).with_style(style.clone());
+        let styled = console.styled(content, style);
+        let plain = Text::new(content);
+
+        for options in [
+            RenderOptions::new().with_max_width(7),
+            RenderOptions::new().with_max_width(80),
+            RenderOptions::new().with_max_width(7).with_soft_wrap(true),
+            RenderOptions::new()
+                .with_max_width(9)
+                .with_bidi_policy(BidiPolicy::Isolate),
+        ] {
+            let expected = text.render(&console, &options).unwrap();
+            assert_eq!(styled.render(&console, &options).unwrap(), expected);
+            assert_eq!(
+                content.render(&console, &options).unwrap(),
+                plain.render(&console, &options).unwrap()
+            );
+            assert_eq!(
+                styled.measure(&console, &options).unwrap(),
+                text.measure(&console, &options).unwrap()
+            );
+        }
+    }
+
+    /// A column of `|` filling the height available to it.
+    struct FullHeight;
+
+    impl Renderable for FullHeight {
+        fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+            let lines = vec![vec![Segment::new("|", Style::new())]; options.get_max_height()];
+            Ok(render_utils::frame_lines(lines))
+        }
+    }
+
+    #[test]
+    fn test_max_height() {
+        let console = Console::with_options(ConsoleOptions::new().with_max_height(3));
+        let segments = console
+            .render(FullHeight, console.render_options())
+            .unwrap();
+        let lines = segments.iter().filter(|segment| segment.text() == "\n");
+        assert_eq!(lines.count(), 2);
+        assert_eq!(render_utils::split_lines(segments).len(), 3);
+
+        // Printing takes the height the console knows unless one is set
+        let mut console = Console::with_options(ConsoleOptions::new().with_color(false));
+        console.resize(10, 4);
+        let output = console
+            .capture(|console| console.print(FullHeight))
+            .unwrap();
+        assert_eq!(output, "|\n|\n|\n|");
+        console.options.render = console.options.render.clone().with_max_height(2);
+        let output = console
+            .capture(|console| console.print(FullHeight))
+            .unwrap();
+        assert_eq!(output, "|\n|");
+    }
+
+    #[test]
+    fn test_print_uses_resized_width() {
+        let mut console = Console::with_options(ConsoleOptions::new().with_color(false));
+        console.resize(20, 5);
+        assert_eq!(console.width(), 20);
+        let sentence = "the quick brown fox jumps over the lazy dog and back again";
+        let output = console
+            .capture(|console| console.println(Text::new(sentence)))
+            .unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.chars().count() <= 20));
+
+        // An explicit width still wins
+        let mut console =
+            Console::with_options(ConsoleOptions::new().with_color(false).with_max_width(30));
+        console.resize(20, 5);
+        let output = console
+            .capture(|console| console.println(Text::new(sentence)))
+            .unwrap();
+        assert!(output.lines().any(|line| line.chars().count() > 20));
+    }
+
+    #[test]
+    fn test_console_options_pass_through() {
+        let mut options = ConsoleOptions::new().with_max_width(30).with_markup(false);
+        assert_eq!(options.render.max_width, Some(30));
+        assert!(!options.render.markup);
+        // Deprecated pass-through to the render options
+        #[allow(deprecated)]
+        let width = options.get_max_width();
+        assert_eq!(width, 30);
+        options.render.soft_wrap = true;
+        assert!(options.render.soft_wrap);
+        assert!(RenderOptions::from(options.clone()).soft_wrap);
+
+        // The deprecated fields mirror the render options, and win once
+        // assigned
+        let mut options = ConsoleOptions::new().with_max_width(30).with_color(false);
+        #[allow(deprecated)]
+        {
+            assert_eq!(options.max_width, Some(30));
+            assert_eq!(options.min_width, 0);
+            options.max_width = Some(12);
+            options.min_width = 4;
+        }
+        let render = RenderOptions::from(options.clone());
+        assert_eq!((render.max_width, render.min_width), (Some(12), 4));
+        let console = Console::with_options(options);
+        assert_eq!(console.width(), 12);
+        #[allow(deprecated)]
+        let mirrored = console.options().max_width;
+        assert_eq!(mirrored, Some(12));
+        let output = console
+            .capture(|console| console.print(Text::new("hello world again")))
+            .unwrap();
+        assert_eq!(output, "hello world\nagain");
+    }
+
+    #[test]
+    fn test_per_render_override_leaves_console_options() {
+        let console = Console::with_options(ConsoleOptions::new().with_max_width(20));
+        let narrow = console.render_options().clone().with_max_width(5);
+        let segments = console.render(Text::new("hello world"), &narrow).unwrap();
+        let lines = crate::render_utils::split_lines(segments);
+        assert!(lines.len() > 1);
+        assert!(
+            lines
+                .iter()
+                .all(|line| crate::render_utils::line_width(line) <= 5)
+        );
 
-        let options = ConsoleOptions::new();
-        assert!(options.get_max_width() > 0); // Should use terminal width
+        assert_eq!(console.render_options().max_width, Some(20));
+        assert_eq!(console.get_render_options().max_width, Some(20));
+        let output = console
+            .capture(|console| console.print(Text::new("hello world")))
+            .unwrap();
+        assert_eq!(output, "hello world");
+    }
+
+    #[test]
+    fn test_console_options_accessible() {
+        assert!(
+            ConsoleOptions::new()
+                .with_accessible(true)
+                .render
+                .accessible
+        );
+        assert!(
+            !ConsoleOptions::new()
+                .with_accessible(false)
+                .render
+                .accessible
+        );
+
+        assert!(is_enabled_flag("1"));
+        assert!(is_enabled_flag("true"));
+        assert!(!is_enabled_flag(""));
+        assert!(!is_enabled_flag("0"));
+        assert!(!is_enabled_flag(" 0 "));
+    }
+
+    #[test]
+    fn test_width_source() {
+        let options = ConsoleOptions::new().with_max_width(42);
+        assert_eq!(options.render.width_source(), WidthSource::Explicit);
+        assert!(options.render.width_source().is_known());
+        assert!(!WidthSource::Fallback.is_known());
+
+        let console = Console::with_options(options);
+        assert_eq!(console.width(), 42);
+        assert_eq!(console.width_source(), WidthSource::Explicit);
+
+        let options = ConsoleOptions::new().with_fallback_width(100);
+        assert_eq!(options.render.fallback_width, 100);
+    }
+
+    #[cfg(not(feature = "terminal"))]
+    #[test]
+    fn test_without_terminal_feature() {
+        let mut console = Console::with_options(ConsoleOptions::new().with_fallback_width(72));
+        if std::env::var_os("COLUMNS").is_none() {
+            assert_eq!(console.width_source(), WidthSource::Fallback);
+            assert_eq!(console.width(), 72);
+        }
+        assert!(!Capabilities::probe_once().is_terminal);
+
+        for error in [
+            console.enable_alt_screen().unwrap_err(),
+            console.disable_alt_screen().unwrap_err(),
+            console.update_size().unwrap_err(),
+        ] {
+            assert!(matches!(
+                error,
+                LuxorError::UnsupportedFeature {
+                    feature: "terminal",
+                    ..
+                }
+            ));
+        }
+
+        // Rendering at an explicit width still works
+        let console = Console::with_options(ConsoleOptions::new().with_max_width(10));
+        console.begin_capture();
+        console.println(Text::new("plain output")).unwrap();
+        assert_eq!(console.end_capture(), "plain\noutput\n");
+    }
+
+    #[test]
+    fn test_wrapped_link_keeps_one_id() {
+        let options = ConsoleOptions::new()
+            .with_max_width(10)
+            .with_color_system(ColorSystem::TrueColor);
+        let console = Console::with_options(options);
+        let text = Text::from_markup("[link=https://example.com]click here please[/link]").unwrap();
+        let output = console.capture(|console| console.println(text)).unwrap();
+
+        let ids: Vec<&str> = output
+            .split("\x1b]8;id=")
+            .skip(1)
+            .map(|rest| rest.split(';').next().unwrap())
+            .collect();
+        assert_eq!(ids.len(), 2, "{:?}", output);
+        assert_eq!(ids[0], ids[1]);
+        assert_eq!(ansi::strip_ansi(&output), "click here\nplease\n");
+    }
+
+    #[test]
+    fn test_restore_terminal_only_undoes_changes() {
+        let mut console = Console::new();
+        console.begin_capture();
+        console.restore_terminal().unwrap();
+        assert_eq!(console.end_capture(), "");
+
+        console.begin_capture();
+        console.hide_cursor().unwrap();
+        console.print("working").unwrap();
+        console.restore_terminal().unwrap();
+        console.restore_terminal().unwrap();
+        let output = console.end_capture();
+        assert_eq!(output.matches(ansi::codes::CURSOR_SHOW).count(), 1);
+        assert!(output.ends_with(ansi::codes::CURSOR_SHOW));
+
+        // Showing the cursor explicitly leaves nothing to restore
+        console.begin_capture();
+        console.hide_cursor().unwrap();
+        console.show_cursor().unwrap();
+        console.restore_terminal().unwrap();
+        assert_eq!(
+            console.end_capture(),
+            format!("{}{}", ansi::codes::CURSOR_HIDE, ansi::codes::CURSOR_SHOW)
+        );
+    }
+
+    #[cfg(feature = "terminal")]
+    #[test]
+    fn test_restore_terminal_leaves_alt_screen() {
+        let mut console = Console::new();
+        console.begin_capture();
+        assert!(!console.is_alt_screen());
+        console.enable_alt_screen().unwrap();
+        assert!(console.is_alt_screen());
+        console.hide_cursor().unwrap();
+        console.restore_terminal().unwrap();
+        assert!(!console.is_alt_screen());
+        console.disable_alt_screen().unwrap();
+        assert_eq!(
+            console.end_capture(),
+            format!(
+                "{}{}{}{}",
+                ansi::codes::ALT_SCREEN_ENABLE,
+                ansi::codes::CURSOR_HIDE,
+                ansi::codes::CURSOR_SHOW,
+                ansi::codes::ALT_SCREEN_DISABLE
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_block_pastes_cleanly() {
+        let mut tree = crate::components::Tree::new("deploy");
+        tree.add("build\x1b]52;c;eA==\x07 ok");
+        tree.add("a step with a name too long\tfor the block");
+        let console = Console::plain_fixed(20);
+        let block = console.render_block(tree.clone(), false).unwrap();
+        assert_eq!(
+            block,
+            "deploy              \n\
+             ├── build ok        \n\
+             └── a step with a na"
+        );
+        assert!(block.chars().all(|ch| ch == '\n' || !ch.is_control()));
+        assert!(block.lines().all(|line| line.chars().count() == 20));
+
+        let ragged = console.render_block(tree, true).unwrap();
+        assert_eq!(ragged.lines().next(), Some("deploy"));
+
+        let boxed = "╭────╮\n│ ok │\n╰────╯";
+        let ascii = Console::with_options(ConsoleOptions::plain_fixed(8).with_ascii_only(true));
+        assert_eq!(
+            ascii.render_block(boxed, true).unwrap(),
+            "+----+\n| ok |\n+----+"
+        );
+    }
+
+    #[test]
+    fn test_writer_gets_all_output() {
+        let options = ConsoleOptions::new().with_color_system(ColorSystem::Standard);
+        let console = Console::with_options(options);
+        let buffer = SharedBuffer::default();
+        console.set_writer(Box::new(buffer.clone()));
+
+        console
+            .print(
+                console.styled(
+                    "Hi",
+                    Style::new()
+                        .bold()
+                        .with_color(Color::Standard(StandardColor::Red)),
+                ),
+            )
+            .unwrap();
+        console.hide_cursor().unwrap();
+        console.show_cursor().unwrap();
+        console.clear().unwrap();
+        assert_eq!(
+            buffer.contents(),
+            "\x1b[31;1mHi\x1b[0m\x1b[?25l\x1b[?25h\x1b[2J\x1b[H"
+        );
+
+        // Capturing takes the output until it ends
+        console.begin_capture();
+        console.print("captured").unwrap();
+        assert_eq!(console.end_capture(), "captured");
+        assert!(console.take_writer().is_some());
+        assert!(!buffer.contents().contains("captured"));
+    }
+
+    #[test]
+    fn test_plain_fixed_refuses_cursor_control() {
+        let mut console = Console::plain_fixed(10);
+        console.begin_capture();
+        assert!(matches!(console.clear(), Err(LuxorError::Terminal { .. })));
+        assert!(console.hide_cursor().is_err());
+        assert!(console.show_cursor().is_err());
+        if cfg!(feature = "terminal") {
+            assert!(console.enable_alt_screen().is_err());
+        }
+        assert!(
+            console
+                .print(Segments::from(vec![Segment::control(ControlCode::Home)]))
+                .is_err()
+        );
+        console.print("text").unwrap();
+        assert_eq!(console.end_capture(), "text");
     }
 
     #[test]
@@ -0,0 +1,278 @@
+//! Diagnostics - reporting problems that do not stop a render.
+//!
+//! Some problems are recovered from instead of being returned as errors:
+//! markup repaired by [`render_lossy`](crate::markup::render_lossy), glyphs
+//! replaced with ASCII, colors approximated for the terminal. Each is
+//! reported as a [`Warning`] to the handler set with
+//! [`set_warning_handler`]. With the `tracing` feature, warnings are also
+//! emitted as `tracing` events at the warn level, next to debug-level
+//! breadcrumbs such as render timings and cache hits.
+//!
+//! With no handler and without the feature, warnings are never built:
+//! reporting one costs a single atomic load.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use luxor::diagnostics::{self, Warning};
+//! use luxor::markup::render_lossy;
+//! use std::sync::{Arc, Mutex};
+//!
+//! let seen = Arc::new(Mutex::new(Vec::new()));
+//! let sink = Arc::clone(&seen);
+//! diagnostics::set_warning_handler(Box::new(move |warning| {
+//!     sink.lock().unwrap().push(warning);
+//! }));
+//!
+//! render_lossy("[bold]unclosed", None);
+//! diagnostics::clear_warning_handler();
+//! assert!(seen
+//!     .lock()
+//!     .unwrap()
+//!     .iter()
+//!     .any(|warning| matches!(warning, Warning::LossyMarkup(_))));
+//! ```
+
+use crate::{Color, ColorSystem, MarkupWarning};
+use std::{
+    fmt,
+    sync::{
+        RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+/// A problem that was recovered from while rendering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// Markup was repaired while rendering it lossily.
+    LossyMarkup(MarkupWarning),
+    /// A string printed with markup enabled had invalid markup, so it was
+    /// printed literally.
+    InvalidMarkup {
+        /// The string as printed.
+        markup: String,
+        /// Why its markup is invalid.
+        message: String,
+    },
+    /// A component drew ASCII in place of its glyphs, because only ASCII
+    /// may be drawn.
+    AsciiFallback {
+        /// The type name of the component.
+        component: &'static str,
+    },
+    /// A color was approximated for a terminal with fewer colors.
+    ColorDowngrade {
+        /// The color asked for.
+        color: Color,
+        /// The color written instead.
+        rendered: Color,
+        /// The color system of the output.
+        color_system: ColorSystem,
+    },
+    /// A measure pass made more calls than its limit, so containers
+    /// estimated the size of their children from there on.
+    MeasureBudget {
+        /// How many containers deep the first estimated call was.
+        depth: usize,
+        /// The limit that was reached.
+        limit: usize,
+    },
+    /// [`BigText`](crate::components::BigText) has no glyph for a
+    /// character, so it was drawn blank.
+    MissingGlyph {
+        /// The character, upper-cased as it was looked up.
+        character: char,
+    },
+    /// Right-to-left text was rendered with
+    /// [`BidiPolicy::Warn`](crate::BidiPolicy::Warn).
+    RightToLeft {
+        /// The rendered text containing it.
+        text: String,
+    },
+    /// Writing the plain transcript set with
+    /// [`Console::tee_plain`](crate::Console::tee_plain) failed, so the
+    /// tee was detached.
+    TeeDetached {
+        /// Why the write failed.
+        error: String,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::LossyMarkup(warning) => write!(f, "repaired markup: {}", warning),
+            Warning::InvalidMarkup { message, .. } => {
+                write!(f, "printing invalid markup as plain text: {}", message)
+            }
+            Warning::AsciiFallback { component } => {
+                write!(f, "{} drawn with ASCII glyphs", component)
+            }
+            Warning::ColorDowngrade {
+                color,
+                rendered,
+                color_system,
+            } => write!(
+                f,
+                "{:?} approximated as {:?} for {:?}",
+                color, rendered, color_system
+            ),
+            Warning::MeasureBudget { depth, limit } => write!(
+                f,
+                "more than {} measure calls, estimating from depth {}",
+                limit, depth
+            ),
+            Warning::MissingGlyph { character } => {
+                write!(f, "no big text glyph for {:?}; drawn blank", character)
+            }
+            Warning::RightToLeft { .. } => write!(
+                f,
+                "right-to-left text rendered; borders and alignment around it may be displaced"
+            ),
+            Warning::TeeDetached { error } => {
+                write!(f, "plain tee detached after write error: {}", error)
+            }
+        }
+    }
+}
+
+/// A function receiving every [`Warning`].
+pub type WarningHandler = Box<dyn Fn(Warning) + Send + Sync>;
+
+/// Whether a handler is set, checked before a warning is built.
+static HAS_HANDLER: AtomicBool = AtomicBool::new(false);
+/// Receiver of warnings.
+static HANDLER: RwLock<Option<WarningHandler>> = RwLock::new(None);
+
+/// Set the function that receives every [`Warning`], replacing any
+/// previous one.
+///
+/// The handler is called on the rendering thread, so it should be quick;
+/// it must not set or clear the handler itself.
+pub fn set_warning_handler(handler: WarningHandler) {
+    *HANDLER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(handler);
+    HAS_HANDLER.store(true, Ordering::Release);
+}
+
+/// Remove the warning handler, returning it. Warnings are ignored again.
+pub fn clear_warning_handler() -> Option<WarningHandler> {
+    HAS_HANDLER.store(false, Ordering::Release);
+    HANDLER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take()
+}
+
+/// Report the warning built by `warning`, building it only if someone is
+/// listening.
+pub(crate) fn warn(warning: impl FnOnce() -> Warning) {
+    let handled = HAS_HANDLER.load(Ordering::Acquire);
+    #[cfg(feature = "tracing")]
+    let traced = tracing::enabled!(tracing::Level::WARN);
+    #[cfg(not(feature = "tracing"))]
+    let traced = false;
+    if !handled && !traced {
+        return;
+    }
+
+    let warning = warning();
+    #[cfg(feature = "tracing")]
+    if traced {
+        tracing::warn!("{}", warning);
+    }
+    if handled {
+        let handler = HANDLER
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(handler) = handler.as_ref() {
+            handler(warning);
+        }
+    }
+}
+
+/// Record a debug-level `tracing` event with the `tracing` feature;
+/// expands to nothing without it.
+macro_rules! breadcrumb {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)+);
+    };
+}
+
+pub(crate) use breadcrumb;
+
+/// Serializes the tests that set the global handler.
+#[cfg(test)]
+static HANDLER_TESTS: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Collect the warnings reported while running `f`.
+///
+/// Other tests may render at the same time, so callers look for the
+/// warnings they expect rather than comparing the whole list.
+#[cfg(test)]
+pub(crate) fn collect_warnings(f: impl FnOnce()) -> Vec<Warning> {
+    use std::sync::{Arc, Mutex};
+
+    let _guard = HANDLER_TESTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    set_warning_handler(Box::new(move |warning| {
+        sink.lock().unwrap().push(warning);
+    }));
+    f();
+    clear_warning_handler();
+    let warnings = seen.lock().unwrap().clone();
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Console, ProgressBar, RenderOptions, Renderable, markup::render_lossy};
+    #[test]
+    fn test_lossy_markup_warns() {
+        let warnings = collect_warnings(|| {
+            let (text, _) = render_lossy("[bold]ok[/italic]", None);
+            assert_eq!(text.plain(), "ok");
+        });
+        let closing = warnings.iter().find_map(|warning| match warning {
+            Warning::LossyMarkup(warning) if warning.tag == "[/italic]" => Some(warning),
+            _ => None,
+        });
+        assert_eq!(closing.map(|warning| warning.position), Some(8));
+    }
+
+    #[test]
+    fn test_unicode_fallback_warns() {
+        let bar = ProgressBar::new(10.0).with_completed(5.0).with_width(10);
+        let warnings = collect_warnings(|| {
+            let options = RenderOptions::new().with_ascii_only(true);
+            bar.render(&Console::new(), &options).unwrap();
+        });
+        let fallback = Warning::AsciiFallback {
+            component: "ProgressBar",
+        };
+        assert!(warnings.contains(&fallback));
+        assert_eq!(fallback.to_string(), "ProgressBar drawn with ASCII glyphs");
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    #[test]
+    fn test_warning_not_built_without_handler() {
+        let _guard = HANDLER_TESTS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let built = AtomicBool::new(false);
+        warn(|| {
+            built.store(true, Ordering::SeqCst);
+            Warning::AsciiFallback { component: "Test" }
+        });
+        assert!(!built.load(Ordering::SeqCst));
+    }
+}
@@ -36,9 +36,34 @@ pub enum LuxorError {
     #[error("Terminal error: {message}")]
     Terminal { message: String },
 
-    /// Markup parsing errors.
-    #[error("Markup error: {0}")]
-    MarkupError(String),
+    /// Markup parsing errors, annotated with the character position of the
+    /// offending tag and a snippet of its source.
+    #[error("Markup error at position {position}: {message} (near `{snippet}`)")]
+    MarkupError {
+        message: String,
+        position: usize,
+        snippet: String,
+    },
+
+    /// Layout errors, such as invalid padding or size specifications.
+    #[error("Layout error: {message}")]
+    Layout { message: String },
+
+    /// Serialization or deserialization errors.
+    #[error("Serialization error: {message}")]
+    Serialization { message: String },
+
+    /// An operation that needs a crate feature which is not enabled.
+    #[error("Unsupported feature: {operation} requires the `{feature}` feature of luxor")]
+    UnsupportedFeature {
+        feature: &'static str,
+        operation: &'static str,
+    },
+
+    /// A render stopped because its cancel flag was set. See
+    /// [`RenderOptions::with_cancel`](crate::RenderOptions::with_cancel).
+    #[error("Rendering cancelled")]
+    Cancelled,
 
     /// Invalid range errors for text operations.
     #[error("Invalid range: {0}")]
@@ -81,6 +106,34 @@ impl LuxorError {
         }
     }
 
+    /// Create a new layout error.
+    pub fn layout(message: impl Into<String>) -> Self {
+        Self::Layout {
+            message: message.into(),
+        }
+    }
+
+    /// Create a new markup error at the given character position.
+    pub fn markup(message: impl Into<String>, position: usize, snippet: impl Into<String>) -> Self {
+        Self::MarkupError {
+            message: message.into(),
+            position,
+            snippet: snippet.into(),
+        }
+    }
+
+    /// Create a new serialization error.
+    pub fn serialization(message: impl Into<String>) -> Self {
+        Self::Serialization {
+            message: message.into(),
+        }
+    }
+
+    /// Create an error for an operation that needs a disabled feature.
+    pub fn unsupported_feature(feature: &'static str, operation: &'static str) -> Self {
+        Self::UnsupportedFeature { feature, operation }
+    }
+
     /// Create a new terminal error.
     pub fn terminal(message: impl Into<String>) -> Self {
         Self::Terminal {
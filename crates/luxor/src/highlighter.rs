@@ -0,0 +1,467 @@
+//! Highlighters - automatic styling of patterns in text.
+//!
+//! A [`Highlighter`] adds style spans to a [`Text`] wherever it finds
+//! something worth picking out. [`ReprHighlighter`] colors the values
+//! that turn up in logs and debug output: numbers, quoted strings, URLs,
+//! UUIDs, booleans and `None`/`null`, in the `repr.*` styles of a
+//! [`Theme`].
+//!
+//! Highlighting plain strings when they are printed is off by default;
+//! turn it on with [`ConsoleOptions::with_highlight`], and change the
+//! highlighter with [`Console::set_highlighter`]:
+//!
+//! ```rust
+//! use luxor::{ColorSystem, Console, ConsoleOptions};
+//!
+//! let options = ConsoleOptions::new()
+//!     .with_color_system(ColorSystem::Standard)
+//!     .with_highlight(true);
+//! let console = Console::with_options(options);
+//! let output = console.capture(|console| console.print("took 12 ms")).unwrap();
+//! assert_eq!(output, "took \x1b[36;1m12\x1b[0m ms");
+//! ```
+//!
+//! [`ConsoleOptions::with_highlight`]: crate::ConsoleOptions::with_highlight
+//! [`Console::set_highlighter`]: crate::Console::set_highlighter
+
+use crate::{Text, Theme};
+use std::ops::Range;
+
+/// Something that styles parts of a text.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Style, Text, highlighter::Highlighter};
+///
+/// /// Makes every "TODO" bold.
+/// struct Todo;
+///
+/// impl Highlighter for Todo {
+///     fn highlight(&self, text: &mut Text) {
+///         text.highlight_words(&["TODO"], Style::new().bold(), true);
+///     }
+/// }
+///
+/// let mut text = Text::new("TODO: tests");
+/// Todo.highlight(&mut text);
+/// assert_eq!(text.style_at(0).bold, Some(true));
+/// ```
+pub trait Highlighter: Send + Sync {
+    /// Add style spans to `text`. Spans added here win over the styles
+    /// the text already has where they overlap.
+    fn highlight(&self, text: &mut Text);
+}
+
+/// A highlighter of any type.
+pub type BoxedHighlighter = Box<dyn Highlighter>;
+
+/// A highlighter that leaves text as it is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullHighlighter;
+
+impl Highlighter for NullHighlighter {
+    fn highlight(&self, _text: &mut Text) {}
+}
+
+/// The kinds of value [`ReprHighlighter`] picks out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Number,
+    Str,
+    BoolTrue,
+    BoolFalse,
+    None,
+    Url,
+    Uuid,
+}
+
+impl Kind {
+    /// Get the name of the theme style for this kind.
+    fn style_name(self) -> &'static str {
+        match self {
+            Kind::Number => "repr.number",
+            Kind::Str => "repr.str",
+            Kind::BoolTrue => "repr.bool_true",
+            Kind::BoolFalse => "repr.bool_false",
+            Kind::None => "repr.none",
+            Kind::Url => "repr.url",
+            Kind::Uuid => "repr.uuid",
+        }
+    }
+}
+
+/// A match of one kind, as a character range.
+type Match = (Range<usize>, Kind);
+
+/// Where a match ends, and its kind.
+type Found = Option<(usize, Kind)>;
+
+/// A function finding a match that starts at an index.
+type Scanner = fn(&[char], usize) -> Found;
+
+/// A keyword and its kind.
+type Keyword = (&'static str, Kind);
+
+/// The scanners for every kind.
+const SCANNERS: [Scanner; 5] = [scan_number, scan_string, scan_url, scan_uuid, scan_keyword];
+
+/// Keywords, as whole words, and their kinds.
+const KEYWORDS: [Keyword; 6] = [
+    ("True", Kind::BoolTrue),
+    ("true", Kind::BoolTrue),
+    ("False", Kind::BoolFalse),
+    ("false", Kind::BoolFalse),
+    ("None", Kind::None),
+    ("null", Kind::None),
+];
+
+/// URL schemes that start a URL.
+const URL_SCHEMES: [&str; 5] = ["https://", "http://", "file://", "wss://", "ws://"];
+
+/// A highlighter for values in logs and debug output.
+///
+/// It picks out:
+///
+/// - numbers, such as `42`, `-3.5`, `1e-9` and `0xff`, including those
+///   followed by a unit (`200ms`) but not those in words (`v2`),
+///   versions or addresses (`1.2.3`), in `repr.number`;
+/// - strings in single or double quotes, with backslash escapes, on one
+///   line, in `repr.str`;
+/// - `http`, `https`, `file`, `ws` and `wss` URLs, in `repr.url`;
+/// - UUIDs, in `repr.uuid`;
+/// - `True`, `true`, `False` and `false`, in `repr.bool_true` and
+///   `repr.bool_false`, and `None` and `null`, in `repr.none`.
+///
+/// Where matches overlap the longest wins, so a URL or a string keeps its
+/// own style over any numbers in it. The styles are looked up in the
+/// default [`Theme`], over which [`ReprHighlighter::with_theme`] sets
+/// others; kinds without a style are left plain.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Style, Text, Theme, highlighter::{Highlighter, ReprHighlighter}};
+///
+/// let highlighter = ReprHighlighter::new()
+///     .with_theme(Theme::empty().with_style("repr.number", Style::new().underline()));
+/// let mut text = Text::new("retry 3 of 5");
+/// highlighter.highlight(&mut text);
+/// assert_eq!(text.style_at(6).underline, Some(true));
+/// assert_eq!(text.style_at(0), Style::new());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReprHighlighter {
+    theme: Theme,
+}
+
+impl ReprHighlighter {
+    /// Create a highlighter with the styles of the default theme.
+    pub fn new() -> Self {
+        Self {
+            theme: Theme::new(),
+        }
+    }
+
+    /// Use the `repr.*` styles of `theme`, keeping the default style of
+    /// any it does not name.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = self.theme.merge(theme);
+        self
+    }
+
+    /// Find the values in `content`, as character ranges in order.
+    fn find(&self, content: &[char]) -> Vec<Match> {
+        let mut candidates: Vec<Match> = Vec::new();
+        for start in 0..content.len() {
+            for scan in SCANNERS {
+                if let Some((end, kind)) = scan(content, start) {
+                    candidates.push((start..end, kind));
+                }
+            }
+        }
+
+        // The longest match wins, then the earliest
+        candidates.sort_by_key(|(range, _)| (std::cmp::Reverse(range.len()), range.start));
+        let mut taken = vec![false; content.len()];
+        let mut matches: Vec<Match> = Vec::new();
+        for (range, kind) in candidates {
+            if taken[range.clone()].iter().any(|&taken| taken) {
+                continue;
+            }
+            taken[range.clone()].fill(true);
+            matches.push((range, kind));
+        }
+        matches.sort_by_key(|(range, _)| range.start);
+        matches
+    }
+}
+
+impl Default for ReprHighlighter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Highlighter for ReprHighlighter {
+    fn highlight(&self, text: &mut Text) {
+        let content: Vec<char> = text.plain().chars().collect();
+        for (range, kind) in self.find(&content) {
+            if let Some(style) = self.theme.get(kind.style_name()) {
+                // Matches are found in the text itself, so are in range
+                text.stylize_range(range, style.clone())
+                    .expect("match is within the text");
+            }
+        }
+    }
+}
+
+/// Check whether `c` is part of a word.
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Check whether the character before `index` is part of a word.
+fn after_word(content: &[char], index: usize) -> bool {
+    index > 0 && is_word(content[index - 1])
+}
+
+/// Check whether `content` has `prefix` at `index`.
+fn starts_with(content: &[char], index: usize, prefix: &str) -> bool {
+    let mut chars = content[index..].iter();
+    prefix.chars().all(|c| chars.next() == Some(&c))
+}
+
+/// Get the index after the run of characters from `index` that pass
+/// `test`.
+fn skip_while(content: &[char], index: usize, test: impl Fn(char) -> bool) -> usize {
+    index + content[index..].iter().take_while(|&&c| test(c)).count()
+}
+
+/// Scan a decimal or hexadecimal number, which may have a unit of
+/// letters after it but is not otherwise part of a word.
+fn scan_number(content: &[char], start: usize) -> Found {
+    if after_word(content, start) || (start > 0 && content[start - 1] == '.') {
+        return None;
+    }
+    let digits = |index| skip_while(content, index, |c| c.is_ascii_digit());
+    let mut end = start;
+    if content.get(end) == Some(&'-') {
+        end += 1;
+    }
+    if starts_with(content, end, "0x") {
+        let hex_end = skip_while(content, end + 2, |c| c.is_ascii_hexdigit());
+        if hex_end > end + 2 && !content.get(hex_end).is_some_and(|&c| is_word(c)) {
+            return Some((hex_end, Kind::Number));
+        }
+        return None;
+    }
+    let integer_end = digits(end);
+    if integer_end == end {
+        return None;
+    }
+    end = integer_end;
+    if content.get(end) == Some(&'.') && content.get(end + 1).is_some_and(char::is_ascii_digit) {
+        end = digits(end + 1);
+    }
+    if matches!(content.get(end), Some('e' | 'E')) {
+        let sign = usize::from(matches!(content.get(end + 1), Some('-' | '+')));
+        let exponent_end = digits(end + 1 + sign);
+        if exponent_end > end + 1 + sign {
+            end = exponent_end;
+        }
+    }
+
+    // A version or address such as 1.2.3 is not a number
+    if content.get(end) == Some(&'.') && content.get(end + 1).is_some_and(char::is_ascii_digit) {
+        return None;
+    }
+    // Letters after the number are a unit, unless the word goes on, as
+    // in a hash
+    let unit_end = skip_while(content, end, char::is_alphabetic);
+    if content.get(unit_end).is_some_and(|&c| is_word(c)) {
+        return None;
+    }
+    Some((end, Kind::Number))
+}
+
+/// Scan a string in single or double quotes on one line, with
+/// backslash escapes.
+fn scan_string(content: &[char], start: usize) -> Found {
+    let quote = content[start];
+    if !matches!(quote, '"' | '\'') || after_word(content, start) {
+        return None;
+    }
+    let mut index = start + 1;
+    while let Some(&c) = content.get(index) {
+        match c {
+            '\\' => index += 2,
+            '\n' => return None,
+            c if c == quote => return Some((index + 1, Kind::Str)),
+            _ => index += 1,
+        }
+    }
+    None
+}
+
+/// Scan a URL, leaving off punctuation that ends a sentence.
+fn scan_url(content: &[char], start: usize) -> Found {
+    if after_word(content, start) {
+        return None;
+    }
+    let scheme = URL_SCHEMES
+        .iter()
+        .find(|scheme| starts_with(content, start, scheme))?;
+    let rest = start + scheme.len();
+    let mut end = skip_while(content, rest, |c| {
+        c.is_ascii_alphanumeric() || "-$_+!`(),.?/;:&=%#~@".contains(c)
+    });
+    let opens = content[rest..end].iter().filter(|&&c| c == '(').count();
+    while end > rest {
+        match content[end - 1] {
+            '.' | ',' | ';' | ':' | '!' | '?' => end -= 1,
+            ')' if opens == 0 => end -= 1,
+            _ => break,
+        }
+    }
+    (end > rest).then_some((end, Kind::Url))
+}
+
+/// Scan a UUID: groups of 8, 4, 4, 4 and 12 hex digits joined by `-`.
+fn scan_uuid(content: &[char], start: usize) -> Found {
+    if after_word(content, start) {
+        return None;
+    }
+    let mut end = start;
+    for (group, length) in [8, 4, 4, 4, 12].into_iter().enumerate() {
+        if group > 0 {
+            if content.get(end) != Some(&'-') {
+                return None;
+            }
+            end += 1;
+        }
+        let group_end = skip_while(content, end, |c| c.is_ascii_hexdigit());
+        if group_end - end < length {
+            return None;
+        }
+        end += length;
+    }
+    match content.get(end) {
+        Some(&c) if is_word(c) => None,
+        _ => Some((end, Kind::Uuid)),
+    }
+}
+
+/// Scan a keyword as a whole word.
+fn scan_keyword(content: &[char], start: usize) -> Found {
+    if after_word(content, start) {
+        return None;
+    }
+    KEYWORDS.iter().find_map(|&(keyword, kind)| {
+        let end = start + keyword.chars().count();
+        let whole =
+            starts_with(content, start, keyword) && !content.get(end).is_some_and(|&c| is_word(c));
+        whole.then_some((end, kind))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Style;
+
+    /// Get the values found in `content`, each after the name of its
+    /// style.
+    fn found(content: &str) -> Vec<String> {
+        let chars: Vec<char> = content.chars().collect();
+        ReprHighlighter::new()
+            .find(&chars)
+            .into_iter()
+            .map(|(range, kind)| {
+                let value: String = chars[range].iter().collect();
+                format!("{} {}", kind.style_name(), value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_log_line() {
+        let line = r#"Got 200 from https://x.dev in 3.5s "ok" at 2024-01-01"#;
+        assert_eq!(
+            found(line),
+            [
+                "repr.number 200",
+                "repr.url https://x.dev",
+                "repr.number 3.5",
+                "repr.str \"ok\"",
+                "repr.number 2024",
+                "repr.number 01",
+                "repr.number 01",
+            ]
+        );
+
+        let mut text = Text::new(line);
+        ReprHighlighter::new().highlight(&mut text);
+        let theme = Theme::new();
+        assert_eq!(&text.style_at(4), theme.get("repr.number").unwrap());
+        assert_eq!(&text.style_at(13), theme.get("repr.url").unwrap());
+        assert_eq!(&text.style_at(36), theme.get("repr.str").unwrap());
+        assert_eq!(text.style_at(0), Style::new());
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            found("-4 1e-9 0xff 200ms x2 1.2.3 1a2b (7)"),
+            [
+                "repr.number -4",
+                "repr.number 1e-9",
+                "repr.number 0xff",
+                "repr.number 200",
+                "repr.number 7",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strings_keywords_and_uuids() {
+        assert_eq!(
+            found(r#"don't 'a \' b' "x"y true None nullable False"#),
+            [
+                r"repr.str 'a \' b'",
+                "repr.str \"x\"",
+                "repr.bool_true true",
+                "repr.none None",
+                "repr.bool_false False",
+            ]
+        );
+        assert_eq!(
+            found("id=123e4567-e89b-12d3-a456-426614174000."),
+            ["repr.uuid 123e4567-e89b-12d3-a456-426614174000"]
+        );
+    }
+
+    #[test]
+    fn test_longest_match_wins() {
+        // The URL holds a number, and the string a URL
+        assert_eq!(
+            found("(see http://a.io/v/2?x=1), 'http://b.io'"),
+            ["repr.url http://a.io/v/2?x=1", "repr.str 'http://b.io'",]
+        );
+    }
+
+    #[test]
+    fn test_null_highlighter_and_missing_styles() {
+        let mut text = Text::new("42 true");
+        NullHighlighter.highlight(&mut text);
+        assert!(text.spans().is_empty());
+
+        // Only the styles the theme names apply
+        let mut theme = Theme::empty();
+        theme.insert("repr.bool_true", Style::new().italic());
+        let highlighter = ReprHighlighter { theme };
+        highlighter.highlight(&mut text);
+        assert_eq!(text.spans().len(), 1);
+        assert_eq!(text.style_at(3).italic, Some(true));
+    }
+}
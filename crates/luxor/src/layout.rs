@@ -0,0 +1,857 @@
+//! Layout - spacing values and width constraints shared by components
+//! that place content.
+
+use crate::{LuxorError, Measurement, Result};
+use std::fmt;
+
+/// Space around content in cells, as top, right, bottom and left.
+///
+/// Padding can be written like the CSS shorthand, with one to four values:
+/// all sides; vertical and horizontal; top, horizontal and bottom; or each
+/// side clockwise from the top. The same forms convert from integers and
+/// tuples, so builders taking `impl Into<Padding>` accept `1`, `(1, 2)` or
+/// `(1, 2, 1, 2)`.
+///
+/// To draw padding around a renderable, wrap it in a
+/// [`Padded`](crate::components::Padded).
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::layout::Padding;
+///
+/// let padding = Padding::parse("1 2").unwrap();
+/// assert_eq!(padding, Padding::new(1, 2, 1, 2));
+/// assert_eq!(padding, Padding::from((1, 2)));
+/// assert_eq!(padding.to_string(), "1 2");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Padding {
+    /// Lines above the content.
+    pub top: usize,
+    /// Cells to the right of the content.
+    pub right: usize,
+    /// Lines below the content.
+    pub bottom: usize,
+    /// Cells to the left of the content.
+    pub left: usize,
+}
+
+impl Padding {
+    /// Create padding with each side given explicitly, in CSS order.
+    pub const fn new(top: usize, right: usize, bottom: usize, left: usize) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Create the same padding on all sides.
+    pub const fn uniform(size: usize) -> Self {
+        Self::new(size, size, size, size)
+    }
+
+    /// Create padding from vertical (top and bottom) and horizontal (left
+    /// and right) sizes.
+    pub const fn symmetric(vertical: usize, horizontal: usize) -> Self {
+        Self::new(vertical, horizontal, vertical, horizontal)
+    }
+
+    /// Parse padding from one to four values separated by whitespace or
+    /// commas, expanded as in CSS.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::layout::Padding;
+    ///
+    /// assert_eq!(Padding::parse("1").unwrap(), Padding::uniform(1));
+    /// assert_eq!(Padding::parse("0, 1, 2").unwrap(), Padding::new(0, 1, 2, 1));
+    /// assert!(Padding::parse("1 -2").is_err());
+    /// ```
+    pub fn parse(input: &str) -> Result<Self> {
+        const FORMS: &str = "expected 1 to 4 non-negative integers, as in \"1\", \"1 2\", \
+                             \"1 2 1\" or \"1 2 1 2\"";
+
+        let values = input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                token.parse::<usize>().map_err(|_| {
+                    LuxorError::layout(format!("invalid padding value `{}`: {}", token, FORMS))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match values[..] {
+            [all] => Ok(Self::uniform(all)),
+            [vertical, horizontal] => Ok(Self::symmetric(vertical, horizontal)),
+            [top, horizontal, bottom] => Ok(Self::new(top, horizontal, bottom, horizontal)),
+            [top, right, bottom, left] => Ok(Self::new(top, right, bottom, left)),
+            _ => Err(LuxorError::layout(format!(
+                "invalid padding `{}`: {}",
+                input.trim(),
+                FORMS
+            ))),
+        }
+    }
+
+    /// Get the total of the left and right padding, saturating at
+    /// `usize::MAX`.
+    pub const fn horizontal(&self) -> usize {
+        self.left.saturating_add(self.right)
+    }
+
+    /// Get the total of the top and bottom padding, saturating at
+    /// `usize::MAX`.
+    pub const fn vertical(&self) -> usize {
+        self.top.saturating_add(self.bottom)
+    }
+
+    /// Get the part of `region` left inside the padding. Padding larger
+    /// than the region leaves an empty region, never a negative one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::layout::{Padding, Region};
+    ///
+    /// let inner = Padding::symmetric(1, 2).apply_to_region(Region::new(0, 0, 10, 5));
+    /// assert_eq!(inner, Region::new(2, 1, 6, 3));
+    /// assert_eq!(Padding::uniform(4).apply_to_region(Region::new(0, 0, 6, 6)).width, 0);
+    /// ```
+    pub fn apply_to_region(&self, region: Region) -> Region {
+        let left = self.left.min(region.width);
+        let top = self.top.min(region.height);
+        Region {
+            x: region.x + left,
+            y: region.y + top,
+            width: region.width.saturating_sub(self.horizontal()),
+            height: region.height.saturating_sub(self.vertical()),
+        }
+    }
+}
+
+/// A rectangle of cells, from its top left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Region {
+    /// The column of the left edge.
+    pub x: usize,
+    /// The line of the top edge.
+    pub y: usize,
+    /// The width in cells.
+    pub width: usize,
+    /// The height in lines.
+    pub height: usize,
+}
+
+impl Region {
+    /// Create a region from its corner and size.
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Where to place content narrower than the space for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Align {
+    /// Against the left edge.
+    Left,
+    /// In the middle, with any odd cell on the right.
+    #[default]
+    Center,
+    /// Against the right edge.
+    Right,
+}
+
+impl Align {
+    /// Split `extra` cells into the space before and after the content.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::layout::Align;
+    ///
+    /// assert_eq!(Align::Center.split(5), (2, 3));
+    /// assert_eq!(Align::Right.split(5), (5, 0));
+    /// ```
+    pub fn split(self, extra: usize) -> (usize, usize) {
+        match self {
+            Self::Left => (0, extra),
+            Self::Center => (extra / 2, extra - extra / 2),
+            Self::Right => (extra, 0),
+        }
+    }
+}
+
+impl From<usize> for Padding {
+    fn from(size: usize) -> Self {
+        Self::uniform(size)
+    }
+}
+
+impl From<(usize, usize)> for Padding {
+    fn from((vertical, horizontal): (usize, usize)) -> Self {
+        Self::symmetric(vertical, horizontal)
+    }
+}
+
+impl From<(usize, usize, usize, usize)> for Padding {
+    fn from((top, right, bottom, left): (usize, usize, usize, usize)) -> Self {
+        Self::new(top, right, bottom, left)
+    }
+}
+
+impl std::str::FromStr for Padding {
+    type Err = LuxorError;
+
+    fn from_str(input: &str) -> Result<Self> {
+        Self::parse(input)
+    }
+}
+
+/// Formats the shortest shorthand that parses back to the same padding.
+impl fmt::Display for Padding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            top,
+            right,
+            bottom,
+            left,
+        } = *self;
+        if right != left {
+            write!(f, "{} {} {} {}", top, right, bottom, left)
+        } else if top != bottom {
+            write!(f, "{} {} {}", top, right, bottom)
+        } else if top != right {
+            write!(f, "{} {}", top, right)
+        } else {
+            write!(f, "{}", top)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Padding {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes from the shorthand string, or from an integer for uniform
+/// padding.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Padding {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        struct PaddingVisitor;
+
+        impl serde::de::Visitor<'_> for PaddingVisitor {
+            type Value = Padding;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("padding as an integer or a string like \"1 2\"")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, size: u64) -> std::result::Result<Padding, E> {
+                usize::try_from(size)
+                    .map(Padding::uniform)
+                    .map_err(|_| E::custom(format!("padding {} is too large", size)))
+            }
+
+            fn visit_str<E: serde::de::Error>(
+                self,
+                input: &str,
+            ) -> std::result::Result<Padding, E> {
+                Padding::parse(input).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(PaddingVisitor)
+    }
+}
+
+/// The lower and upper bounds on a measured width.
+type Bounds = (usize, usize);
+
+/// A constraint on the width of a column or split, as written in
+/// configuration.
+///
+/// | Written | Spec | Width |
+/// |---------|------|-------|
+/// | `12` | [`WidthSpec::Fixed`] | exactly 12 cells |
+/// | `min:8` | [`WidthSpec::Min`] | the measured width, at least 8 |
+/// | `max:20` | [`WidthSpec::Max`] | the measured width, at most 20 |
+/// | `8..20` | [`WidthSpec::Range`] | the measured width, between 8 and 20 |
+/// | `2fr` | [`WidthSpec::Fraction`] | two shares of what is left |
+/// | `auto` | [`WidthSpec::Auto`] | the measured width |
+///
+/// See [`resolve_widths`] for how a set of specs shares the width.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::layout::WidthSpec;
+///
+/// assert_eq!(WidthSpec::parse("min:8").unwrap(), WidthSpec::Min(8));
+/// assert_eq!(WidthSpec::parse("8..20").unwrap(), WidthSpec::Range(8, 20));
+/// assert_eq!(WidthSpec::Fraction(2).to_string(), "2fr");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WidthSpec {
+    /// Exactly this many cells.
+    Fixed(usize),
+    /// The measured width, but at least this many cells.
+    Min(usize),
+    /// The measured width, but at most this many cells.
+    Max(usize),
+    /// The measured width, kept between the two bounds.
+    Range(usize, usize),
+    /// This many shares of the width left after every other spec.
+    Fraction(u32),
+    /// The measured width.
+    #[default]
+    Auto,
+}
+
+impl WidthSpec {
+    /// Parse a spec written as in the table on [`WidthSpec`]. Whitespace
+    /// around the spec and its numbers is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::layout::WidthSpec;
+    ///
+    /// assert_eq!(WidthSpec::parse(" 12 ").unwrap(), WidthSpec::Fixed(12));
+    /// assert_eq!(WidthSpec::parse("1fr").unwrap(), WidthSpec::Fraction(1));
+    /// assert_eq!(WidthSpec::parse("auto").unwrap(), WidthSpec::Auto);
+    /// assert!(WidthSpec::parse("20..8").is_err());
+    /// ```
+    pub fn parse(input: &str) -> Result<Self> {
+        const FORMS: &str = "expected a width like \"12\", \"min:8\", \"max:20\", \"8..20\", \
+                             \"1fr\" or \"auto\"";
+
+        let spec = input.trim();
+        let number = |token: &str| {
+            token
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| LuxorError::layout(format!("invalid width `{}`: {}", spec, FORMS)))
+        };
+
+        if spec.eq_ignore_ascii_case("auto") {
+            Ok(Self::Auto)
+        } else if let Some(minimum) = spec.strip_prefix("min:") {
+            Ok(Self::Min(number(minimum)?))
+        } else if let Some(maximum) = spec.strip_prefix("max:") {
+            Ok(Self::Max(number(maximum)?))
+        } else if let Some((low, high)) = spec.split_once("..") {
+            let (low, high) = (number(low)?, number(high)?);
+            if low > high {
+                return Err(LuxorError::layout(format!(
+                    "invalid width `{}`: the lower bound is above the upper one",
+                    spec
+                )));
+            }
+            Ok(Self::Range(low, high))
+        } else if let Some(shares) = spec.strip_suffix("fr") {
+            match shares.trim().parse::<u32>() {
+                Ok(0) => Err(LuxorError::layout(format!(
+                    "invalid width `{}`: a fraction needs at least one share",
+                    spec
+                ))),
+                Ok(shares) => Ok(Self::Fraction(shares)),
+                Err(_) => Err(LuxorError::layout(format!(
+                    "invalid width `{}`: {}",
+                    spec, FORMS
+                ))),
+            }
+        } else {
+            Ok(Self::Fixed(number(spec)?))
+        }
+    }
+
+    /// Get the bounds this spec puts on a measured width, or `None` for
+    /// fixed widths and fractions.
+    fn bounds(self) -> Option<Bounds> {
+        match self {
+            Self::Min(minimum) => Some((minimum, usize::MAX)),
+            Self::Max(maximum) => Some((0, maximum)),
+            Self::Range(low, high) => Some((low, high)),
+            Self::Auto => Some((0, usize::MAX)),
+            Self::Fixed(_) | Self::Fraction(_) => None,
+        }
+    }
+}
+
+impl From<usize> for WidthSpec {
+    fn from(width: usize) -> Self {
+        Self::Fixed(width)
+    }
+}
+
+impl std::str::FromStr for WidthSpec {
+    type Err = LuxorError;
+
+    fn from_str(input: &str) -> Result<Self> {
+        Self::parse(input)
+    }
+}
+
+/// Formats the spec as it is parsed.
+impl fmt::Display for WidthSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed(width) => write!(f, "{}", width),
+            Self::Min(minimum) => write!(f, "min:{}", minimum),
+            Self::Max(maximum) => write!(f, "max:{}", maximum),
+            Self::Range(low, high) => write!(f, "{}..{}", low, high),
+            Self::Fraction(shares) => write!(f, "{}fr", shares),
+            Self::Auto => f.write_str("auto"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for WidthSpec {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes from the written form, or from an integer for a fixed
+/// width.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WidthSpec {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        struct WidthSpecVisitor;
+
+        impl serde::de::Visitor<'_> for WidthSpecVisitor {
+            type Value = WidthSpec;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a width as an integer or a string like \"min:8\" or \"1fr\"")
+            }
+
+            fn visit_u64<E: serde::de::Error>(
+                self,
+                width: u64,
+            ) -> std::result::Result<WidthSpec, E> {
+                usize::try_from(width)
+                    .map(WidthSpec::Fixed)
+                    .map_err(|_| E::custom(format!("width {} is too large", width)))
+            }
+
+            fn visit_str<E: serde::de::Error>(
+                self,
+                input: &str,
+            ) -> std::result::Result<WidthSpec, E> {
+                WidthSpec::parse(input).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(WidthSpecVisitor)
+    }
+}
+
+/// Split `total` cells in proportion to `ratios`, so the parts add up to
+/// exactly `total`. Rounding favors earlier parts. With no ratio above
+/// zero, every part is zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::layout::ratio_distribute;
+///
+/// assert_eq!(ratio_distribute(10, &[1, 1, 1]), vec![4, 3, 3]);
+/// assert_eq!(ratio_distribute(10, &[3, 0, 1]), vec![8, 0, 2]);
+/// ```
+pub fn ratio_distribute(total: usize, ratios: &[usize]) -> Vec<usize> {
+    let mut ratio_left: u128 = ratios.iter().map(|&ratio| ratio as u128).sum();
+    let mut remaining = total as u128;
+    ratios
+        .iter()
+        .map(|&ratio| {
+            if ratio_left == 0 {
+                return 0;
+            }
+            // Round up, so earlier parts take the odd cells
+            let share = (ratio as u128 * remaining + ratio_left - 1) / ratio_left;
+            ratio_left -= ratio as u128;
+            remaining -= share;
+            share as usize
+        })
+        .collect()
+}
+
+/// Share `available` cells between columns with the given `specs`, using
+/// the `measured` width of each column's content.
+///
+/// Widths are settled in order of precedence:
+///
+/// 1. Fixed widths. If together they exceed `available`, they are shrunk
+///    in proportion to their size, and nothing is left for other columns.
+/// 2. Measured widths: [`WidthSpec::Auto`], [`WidthSpec::Min`],
+///    [`WidthSpec::Max`] and [`WidthSpec::Range`] columns take their
+///    content's maximum width kept within their bounds. If they do not
+///    fit in what fixed widths leave, each first gets its lower bound, and
+///    what is left is shared in proportion to how much more each wants.
+///    Lower bounds that do not fit are themselves shrunk in proportion.
+/// 3. Fractions share what is left, with [`ratio_distribute`].
+///
+/// The widths never add up to more than `available`, and add up to
+/// exactly `available` when there is a fraction. A column without a
+/// measurement in `measured` is measured as empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Measurement, layout::{WidthSpec, resolve_widths}};
+///
+/// let specs = [WidthSpec::Fixed(10), WidthSpec::Auto, WidthSpec::Fraction(1)];
+/// let measured = [Measurement::fixed(3), Measurement::new(4, 12), Measurement::fixed(1)];
+/// assert_eq!(resolve_widths(&specs, 40, &measured), vec![10, 12, 18]);
+/// ```
+pub fn resolve_widths(
+    specs: &[WidthSpec],
+    available: usize,
+    measured: &[Measurement],
+) -> Vec<usize> {
+    let mut widths = vec![0; specs.len()];
+
+    // Fixed widths first, shrunk together if they cannot all fit
+    let fixed: Vec<usize> = specs
+        .iter()
+        .map(|spec| match spec {
+            WidthSpec::Fixed(width) => *width,
+            _ => 0,
+        })
+        .collect();
+    let fixed_total: usize = fixed
+        .iter()
+        .fold(0, |total, &width| total.saturating_add(width));
+    let fixed = if fixed_total > available {
+        ratio_distribute(available, &fixed)
+    } else {
+        fixed
+    };
+    for (width, fixed) in widths.iter_mut().zip(&fixed) {
+        *width += fixed;
+    }
+    let mut remaining = available - fixed.iter().sum::<usize>();
+
+    // Then measured widths within their bounds
+    let mut lower = vec![0; specs.len()];
+    let mut growth = vec![0; specs.len()];
+    for (index, spec) in specs.iter().enumerate() {
+        if let Some((low, high)) = spec.bounds() {
+            let maximum = measured
+                .get(index)
+                .map_or(0, |measurement| measurement.maximum());
+            lower[index] = low;
+            growth[index] = maximum.clamp(low, high) - low;
+        }
+    }
+    let lower_total: usize = lower
+        .iter()
+        .fold(0, |total, &width| total.saturating_add(width));
+    let growth_total: usize = growth
+        .iter()
+        .fold(0, |total, &width| total.saturating_add(width));
+    if lower_total > remaining {
+        lower = ratio_distribute(remaining, &lower);
+        growth = vec![0; specs.len()];
+    } else if growth_total > remaining - lower_total {
+        growth = ratio_distribute(remaining - lower_total, &growth);
+    }
+    for (index, width) in widths.iter_mut().enumerate() {
+        *width += lower[index] + growth[index];
+        remaining -= lower[index] + growth[index];
+    }
+
+    // Fractions share the rest
+    let shares: Vec<usize> = specs
+        .iter()
+        .map(|spec| match spec {
+            WidthSpec::Fraction(shares) => *shares as usize,
+            _ => 0,
+        })
+        .collect();
+    for (width, share) in widths.iter_mut().zip(ratio_distribute(remaining, &shares)) {
+        *width += share;
+    }
+    widths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_padding_parse_arities() {
+        assert_eq!(Padding::parse("1").unwrap(), Padding::new(1, 1, 1, 1));
+        assert_eq!(Padding::parse("1 2").unwrap(), Padding::new(1, 2, 1, 2));
+        assert_eq!(Padding::parse("1 2 3").unwrap(), Padding::new(1, 2, 3, 2));
+        assert_eq!(Padding::parse("1 2 3 4").unwrap(), Padding::new(1, 2, 3, 4));
+        assert_eq!(
+            Padding::parse(" 1,2 , 3\t4 ").unwrap(),
+            Padding::new(1, 2, 3, 4)
+        );
+    }
+
+    #[test]
+    fn test_padding_parse_errors() {
+        let error = Padding::parse("1 -2").unwrap_err().to_string();
+        assert!(error.contains("`-2`"), "{}", error);
+        assert!(error.contains("\"1 2 1 2\""), "{}", error);
+
+        let error = Padding::parse("wide").unwrap_err().to_string();
+        assert!(error.contains("`wide`"), "{}", error);
+
+        for input in ["", "  ,", "1 2 3 4 5"] {
+            let error = Padding::parse(input).unwrap_err().to_string();
+            assert!(error.contains("expected 1 to 4"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_padding_from_in_builder_position() {
+        fn with_padding(padding: impl Into<Padding>) -> Padding {
+            padding.into()
+        }
+
+        assert_eq!(with_padding(2), Padding::uniform(2));
+        assert_eq!(with_padding((1, 2)), Padding::symmetric(1, 2));
+        assert_eq!(with_padding((1, 2, 3, 4)), Padding::new(1, 2, 3, 4));
+        assert_eq!(with_padding(Padding::default()).horizontal(), 0);
+        assert_eq!("0 1".parse::<Padding>().unwrap().horizontal(), 2);
+    }
+
+    #[test]
+    fn test_padding_apply_to_region() {
+        let region = Region::new(3, 1, 20, 6);
+        assert_eq!(
+            Padding::new(1, 2, 3, 4).apply_to_region(region),
+            Region::new(7, 2, 14, 2)
+        );
+        // Padding wider than the region leaves nothing, at its far edge
+        let inner = Padding::symmetric(5, 30).apply_to_region(region);
+        assert_eq!(inner, Region::new(23, 6, 0, 0));
+        assert_eq!(Padding::default().apply_to_region(region), region);
+        assert_eq!(Align::Left.split(3), (0, 3));
+        assert_eq!(Align::default().split(0), (0, 0));
+    }
+
+    #[test]
+    fn test_padding_display_round_trip() {
+        for (padding, shorthand) in [
+            (Padding::uniform(1), "1"),
+            (Padding::symmetric(0, 2), "0 2"),
+            (Padding::new(1, 2, 3, 2), "1 2 3"),
+            (Padding::new(1, 2, 1, 4), "1 2 1 4"),
+        ] {
+            assert_eq!(padding.to_string(), shorthand);
+            assert_eq!(Padding::parse(shorthand).unwrap(), padding);
+        }
+    }
+
+    fn measured(widths: &[Bounds]) -> Vec<Measurement> {
+        widths
+            .iter()
+            .map(|&(minimum, maximum)| Measurement::new(minimum, maximum))
+            .collect()
+    }
+
+    #[test]
+    fn test_width_spec_parse() {
+        for (input, spec) in [
+            ("12", WidthSpec::Fixed(12)),
+            (" min: 8 ", WidthSpec::Min(8)),
+            ("max:20", WidthSpec::Max(20)),
+            ("8..20", WidthSpec::Range(8, 20)),
+            ("5..5", WidthSpec::Range(5, 5)),
+            ("1fr", WidthSpec::Fraction(1)),
+            ("3 fr", WidthSpec::Fraction(3)),
+            ("auto", WidthSpec::Auto),
+            ("AUTO", WidthSpec::Auto),
+        ] {
+            assert_eq!(WidthSpec::parse(input).unwrap(), spec, "{:?}", input);
+            assert_eq!(WidthSpec::parse(&spec.to_string()).unwrap(), spec);
+        }
+        assert_eq!("7".parse::<WidthSpec>().unwrap(), WidthSpec::from(7));
+        assert_eq!(WidthSpec::default(), WidthSpec::Auto);
+    }
+
+    #[test]
+    fn test_width_spec_parse_errors() {
+        for (input, message) in [
+            ("", "expected a width"),
+            ("wide", "`wide`"),
+            ("-3", "`-3`"),
+            ("min:", "`min:`"),
+            ("max:x", "`max:x`"),
+            ("20..8", "lower bound is above"),
+            ("8..", "`8..`"),
+            ("0fr", "at least one share"),
+            ("1.5fr", "`1.5fr`"),
+            ("fr", "`fr`"),
+        ] {
+            let error = WidthSpec::parse(input).unwrap_err().to_string();
+            assert!(error.contains(message), "{:?}: {}", input, error);
+        }
+    }
+
+    #[test]
+    fn test_ratio_distribute() {
+        assert_eq!(ratio_distribute(10, &[1, 1, 1]), vec![4, 3, 3]);
+        assert_eq!(ratio_distribute(7, &[1, 2]), vec![3, 4]);
+        assert_eq!(ratio_distribute(0, &[1, 2]), vec![0, 0]);
+        assert_eq!(ratio_distribute(5, &[0, 0]), vec![0, 0]);
+        assert_eq!(ratio_distribute(5, &[]), Vec::<usize>::new());
+        assert_eq!(
+            ratio_distribute(100, &[usize::MAX, usize::MAX]),
+            vec![50, 50]
+        );
+        for total in 0..40 {
+            let parts = ratio_distribute(total, &[3, 5, 7, 1]);
+            assert_eq!(parts.iter().sum::<usize>(), total);
+        }
+    }
+
+    #[test]
+    fn test_resolve_fixed_and_fractions() {
+        use WidthSpec::*;
+
+        let specs = [Fixed(10), Fraction(1), Fraction(2)];
+        assert_eq!(resolve_widths(&specs, 40, &[]), vec![10, 10, 20]);
+        // Odd cells go to the earlier fraction
+        assert_eq!(resolve_widths(&specs, 41, &[]), vec![10, 11, 20]);
+        // Fractions take nothing when nothing is left
+        assert_eq!(resolve_widths(&specs, 10, &[]), vec![10, 0, 0]);
+        assert_eq!(resolve_widths(&[], 10, &[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_resolve_measured_widths() {
+        use WidthSpec::*;
+
+        let content = measured(&[(2, 30), (2, 30), (2, 3), (2, 30), (2, 30)]);
+        let specs = [Auto, Min(40), Min(8), Max(12), Range(5, 15)];
+        assert_eq!(
+            resolve_widths(&specs, 200, &content),
+            vec![30, 40, 8, 12, 15]
+        );
+        // Narrow content stays narrow, but not below a lower bound
+        let narrow = measured(&[(1, 4), (1, 4)]);
+        assert_eq!(
+            resolve_widths(&[Range(5, 15), Auto], 80, &narrow),
+            vec![5, 4]
+        );
+        // Missing measurements count as empty content
+        assert_eq!(resolve_widths(&[Min(3), Auto], 80, &[]), vec![3, 0]);
+    }
+
+    #[test]
+    fn test_resolve_shrinks_measured_widths_proportionally() {
+        use WidthSpec::*;
+
+        // Lower bounds 10 and 0 fit; the rest is shared by how much more
+        // each column wants: 20 and 40
+        let content = measured(&[(0, 30), (0, 40)]);
+        let specs = [Min(10), Auto];
+        assert_eq!(resolve_widths(&specs, 40, &content), vec![20, 20]);
+        assert_eq!(
+            resolve_widths(
+                &[Fixed(10), Min(10), Auto, Fraction(1)],
+                50,
+                &measured(&[(0, 0), (0, 30), (0, 40), (0, 0)])
+            ),
+            vec![10, 20, 20, 0]
+        );
+        // Lower bounds that do not fit are shrunk in proportion
+        let specs = [Min(30), Range(10, 20)];
+        assert_eq!(
+            resolve_widths(&specs, 20, &measured(&[(0, 50), (0, 50)])),
+            vec![15, 5]
+        );
+    }
+
+    #[test]
+    fn test_resolve_infeasible_fixed_widths() {
+        use WidthSpec::*;
+
+        // Fixed widths that do not fit shrink in proportion to their size
+        let specs = [Fixed(30), Fixed(10), Auto, Fraction(1)];
+        let content = measured(&[(0, 0), (0, 0), (5, 20), (0, 0)]);
+        assert_eq!(resolve_widths(&specs, 20, &content), vec![15, 5, 0, 0]);
+        assert_eq!(
+            resolve_widths(&[Fixed(usize::MAX), Fixed(1)], 10, &[]),
+            vec![10, 0]
+        );
+        for available in 0..60 {
+            let total: usize = resolve_widths(&specs, available, &content).iter().sum();
+            assert_eq!(total, available);
+        }
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_width_spec_serde() {
+        let specs = vec![
+            WidthSpec::Fixed(4),
+            WidthSpec::Range(2, 9),
+            WidthSpec::Fraction(2),
+        ];
+        let json = serde_json::to_string(&specs).unwrap();
+        assert_eq!(json, r#"["4","2..9","2fr"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<WidthSpec>>(&json).unwrap(),
+            specs
+        );
+        assert_eq!(
+            serde_json::from_str::<WidthSpec>("12").unwrap(),
+            WidthSpec::Fixed(12)
+        );
+        let error = serde_json::from_str::<WidthSpec>(r#""9..2""#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("lower bound"), "{}", error);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_padding_serde() {
+        let padding = Padding::symmetric(1, 2);
+        assert_eq!(serde_json::to_string(&padding).unwrap(), r#""1 2""#);
+        assert_eq!(
+            serde_json::from_str::<Padding>(r#""1 2""#).unwrap(),
+            padding
+        );
+        assert_eq!(
+            serde_json::from_str::<Padding>("3").unwrap(),
+            Padding::uniform(3)
+        );
+
+        let error = serde_json::from_str::<Padding>(r#""1 x""#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("`x`"), "{}", error);
+    }
+}
@@ -8,10 +8,11 @@
 //! ## Quick Start
 //!
 //! ```rust
-//! use luxor::{Console, Style, Color};
+//! use luxor::prelude::*;
 //!
 //! let console = Console::new();
-//! // Basic usage will be available after Phase 1 implementation
+//! let text = Text::from_markup("[bold red]Hello[/bold red], world!").unwrap();
+//! console.println(text).unwrap();
 //! ```
 //!
 //! ## Architecture
@@ -25,23 +26,77 @@
 //! - **Color**: Color representation supporting standard, 8-bit, and 24-bit colors
 
 pub mod ansi;
+pub mod box_drawing;
+pub mod capabilities;
+pub mod clock;
 pub mod color;
+pub mod components;
 pub mod console;
+pub mod diagnostics;
 pub mod error;
+pub mod highlighter;
+pub mod layout;
+pub mod live;
 pub mod markup;
 pub mod measure;
+pub mod options;
+pub mod playback;
+pub mod prelude;
+pub mod profile;
 pub mod protocol;
+pub mod render_string;
+pub mod render_utils;
+pub mod rendered;
+pub mod sanitize;
 pub mod segment;
+pub mod select;
+pub mod shared;
+pub mod sink;
 pub mod style;
+pub mod style_layers;
+pub mod tee;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
 pub mod text;
+pub mod theme;
+pub mod units;
+pub mod wrap;
 
 // Re-export core types for convenient access
+pub use box_drawing::BorderStyle;
+pub use capabilities::Capabilities;
+pub use clock::{Animation, Clock, ManualClock, SystemClock, Timer};
 pub use color::{Color, ColorSystem, StandardColor};
+pub use components::*;
 pub use console::{Console, ConsoleOptions, StyledText};
+pub use diagnostics::Warning;
 pub use error::{LuxorError, Result};
-pub use markup::{Span, Tag, escape as escape_markup, render as render_markup};
+pub use layout::{Align, Padding};
+pub use live::Live;
+pub use markup::{
+    MarkupStream, MarkupWarning, MarkupWarningKind, Span, Tag, escape as escape_markup,
+    render as render_markup,
+};
 pub use measure::Measurement;
+#[cfg(feature = "terminal")]
+pub use options::ctrlc_cancel_flag;
+pub use options::{CANCEL_CHECK_INTERVAL, MeasurePass, RenderOptions, RenderState, WidthSource};
 pub use protocol::{Measurable, Renderable};
-pub use segment::{ControlCode, Segment, Segments};
-pub use style::Style;
-pub use text::Text;
+pub use render_string::{render_block, render_markup_to_string, render_plain, render_to_string};
+pub use rendered::Rendered;
+pub use segment::{ControlCode, Segment, Segments, WidthSplit};
+#[cfg(feature = "terminal")]
+pub use select::SelectPrompt;
+pub use select::{SelectKey, SelectOutcome, SelectState};
+pub use shared::SharedConsole;
+pub use sink::FrameSink;
+pub use style::{Style, transitions::StyleDelta};
+pub use style_layers::StyleLayers;
+pub use tee::TeeMode;
+pub use text::{BidiPolicy, EllipsisConfig, Justify, MatchContext, OverflowPosition, Text};
+pub use theme::Theme;
+
+/// Compile the README examples as doctests so they stay in sync with the API.
+#[cfg(doctest)]
+#[doc = include_str!("../../../README.md")]
+pub struct ReadmeDoctests;
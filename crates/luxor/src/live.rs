@@ -0,0 +1,275 @@
+//! Live - a region of the terminal redrawn in place.
+//!
+//! A [`Live`] display draws a renderable, and each
+//! [`refresh`](Live::refresh) moves the cursor back to the top of what it
+//! drew and draws the renderable again over it, so a dashboard or progress
+//! report updates without scrolling. Every line is padded to the full
+//! width, which overwrites what was there before without clearing the
+//! screen, so there is no flicker.
+//!
+//! ```rust
+//! use luxor::{Console, ConsoleOptions, Live, Text};
+//!
+//! let mut console = Console::with_options(ConsoleOptions::new().with_color(false));
+//! console.begin_capture();
+//! let mut live = Live::new(Text::new("0 done"));
+//! live.start(&mut console).unwrap();
+//! for done in 1..=3 {
+//!     live.set_renderable(Text::new(&format!("{done} done")));
+//!     live.refresh(&mut console).unwrap();
+//! }
+//! live.stop(&mut console).unwrap();
+//! let output = console.end_capture();
+//! assert!(output.contains("\r3 done"));
+//! assert!(output.ends_with("\n\x1b[?25h"));
+//! ```
+
+use crate::{
+    Console, ControlCode, Renderable, Result, Segment, Segments, Style, render_utils,
+    rendered::Line,
+};
+
+/// A renderable drawn in place, and redrawn on every refresh.
+///
+/// The display borrows the console for each call rather than holding it,
+/// so the console can still be used between refreshes, though printing
+/// while the display is started draws over it.
+///
+/// A display dropped without [`Live::stop`] leaves its region as last
+/// drawn; the console shows the cursor again when it is dropped.
+#[derive(Debug)]
+pub struct Live<R> {
+    renderable: R,
+    alt_screen: bool,
+    started: bool,
+    /// The number of lines the last refresh drew, which the next one goes
+    /// back over.
+    drawn: usize,
+}
+
+impl<R: Renderable> Live<R> {
+    /// Create a display of `renderable`.
+    pub fn new(renderable: R) -> Self {
+        Self {
+            renderable,
+            alt_screen: false,
+            started: false,
+            drawn: 0,
+        }
+    }
+
+    /// Draw on the alternate screen, leaving the terminal history as it
+    /// was when the display stops.
+    ///
+    /// The alternate screen needs the `terminal` feature.
+    pub fn with_alt_screen(mut self, alt_screen: bool) -> Self {
+        self.alt_screen = alt_screen;
+        self
+    }
+
+    /// Get the renderable being displayed.
+    pub fn renderable(&self) -> &R {
+        &self.renderable
+    }
+
+    /// Replace the renderable being displayed. It is drawn by the next
+    /// refresh.
+    pub fn set_renderable(&mut self, renderable: R) {
+        self.renderable = renderable;
+    }
+
+    /// Check whether the display is started.
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+
+    /// Start the display: hide the cursor, enter the alternate screen if
+    /// asked to, and draw the renderable.
+    ///
+    /// Starting a started display only refreshes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cursor cannot be controlled or the
+    /// renderable fails to render. The display counts as started either
+    /// way, so [`Live::stop`] puts the terminal back.
+    pub fn start(&mut self, console: &mut Console) -> Result<()> {
+        if !self.started {
+            self.started = true;
+            self.drawn = 0;
+            console.hide_cursor()?;
+            if self.alt_screen {
+                console.enable_alt_screen()?;
+            }
+        }
+        self.refresh(console)
+    }
+
+    /// Draw the renderable again over the lines drawn before.
+    ///
+    /// The terminal size is queried again first, so a resize between
+    /// refreshes is picked up. Content taller than the terminal is cut
+    /// short with an ellipsis on the last line, since the cursor cannot go
+    /// back above the top of the screen. Lines left over from a taller
+    /// frame are blanked, as far as they are still on screen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the renderable fails to render, in which case
+    /// nothing is drawn.
+    pub fn refresh(&mut self, console: &mut Console) -> Result<()> {
+        if console.is_terminal() {
+            console.update_size()?;
+        }
+        // One cell spare, so a full line never wraps
+        let width = console.width().saturating_sub(1);
+        let height = console.height().max(1);
+        let options = console.render_options().clone().with_max_width(width);
+        let mut lines = render_utils::split_lines(options.render_child(&self.renderable, console)?);
+        if lines.len() > height {
+            lines.truncate(height - 1);
+            let ellipsis = if console.render_options().ascii_only {
+                "..."
+            } else {
+                "…"
+            };
+            lines.push(vec![Segment::new(ellipsis, Style::new())]);
+        }
+
+        // After the terminal shrinks, lines above its top are out of reach
+        let reachable = self.drawn.min(height);
+        let mut frame = Vec::new();
+        if self.alt_screen {
+            frame.push(Segment::control(ControlCode::Home));
+        } else {
+            if reachable > 1 {
+                frame.push(Segment::control(ControlCode::CursorUp(reachable - 1)));
+            }
+            frame.push(Segment::control(ControlCode::CarriageReturn));
+        }
+        let count = lines.len();
+        let leftover = reachable.saturating_sub(count);
+        let blank = std::iter::repeat_with(Line::new).take(leftover);
+        let padded = lines
+            .into_iter()
+            .chain(blank)
+            .map(|line| render_utils::adjust_line_length(line, width, Style::new()))
+            .collect();
+        self.drawn = count;
+        frame.extend(render_utils::frame_lines(padded));
+        if leftover > 0 {
+            // Back to the end of the last line drawn
+            frame.push(Segment::control(ControlCode::CursorUp(leftover)));
+            frame.push(Segment::control(ControlCode::CursorMoveToColumn(width + 1)));
+        }
+        console.print(Segments::from_vec(frame))
+    }
+
+    /// Stop the display: draw it one last time, show the cursor and leave
+    /// the alternate screen if it was entered.
+    ///
+    /// Outside the alternate screen the last frame stays, and the cursor
+    /// moves to the line below it. Stopping a display that is not started
+    /// does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error met. The cursor is shown and the alternate
+    /// screen left even if the last refresh fails.
+    pub fn stop(&mut self, console: &mut Console) -> Result<()> {
+        if !self.started {
+            return Ok(());
+        }
+        self.started = false;
+        let drawn = if self.alt_screen {
+            Ok(())
+        } else {
+            self.refresh(console).and_then(|_| console.print("\n"))
+        };
+        let shown = console.show_cursor();
+        let left = if self.alt_screen {
+            console.disable_alt_screen()
+        } else {
+            Ok(())
+        };
+        self.drawn = 0;
+        drawn.and(shown).and(left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConsoleOptions, LuxorError, RenderOptions, Text};
+
+    fn console(width: usize, height: usize) -> Console {
+        let mut console = Console::with_options(ConsoleOptions::new().with_color(false));
+        console.resize(width, height);
+        console.begin_capture();
+        console
+    }
+
+    #[test]
+    fn test_refresh_redraws_in_place() {
+        let mut console = console(6, 10);
+        let mut live = Live::new(Text::new("a\nbb"));
+        live.start(&mut console).unwrap();
+        live.set_renderable(Text::new("ccc\nd\ne"));
+        live.refresh(&mut console).unwrap();
+        assert_eq!(
+            console.end_capture(),
+            "\x1b[?25l\ra    \nbb   \x1b[1A\rccc  \nd    \ne    "
+        );
+
+        // A shorter frame blanks the leftover lines and goes back up
+        console.begin_capture();
+        live.set_renderable(Text::new("f"));
+        live.stop(&mut console).unwrap();
+        assert_eq!(
+            console.end_capture(),
+            "\x1b[2A\rf    \n     \n     \x1b[2A\x1b[6G\n\x1b[?25h"
+        );
+        assert!(!live.is_started());
+    }
+
+    #[test]
+    fn test_content_taller_than_terminal() {
+        let mut console = console(6, 3);
+        let mut live = Live::new(Text::new("1\n2\n3\n4\n5"));
+        live.start(&mut console).unwrap();
+        assert_eq!(console.end_capture(), "\x1b[?25l\r1    \n2    \n…    ");
+
+        // The next frame goes back over the three lines drawn
+        console.begin_capture();
+        console.resize(6, 4);
+        live.refresh(&mut console).unwrap();
+        assert_eq!(console.end_capture(), "\x1b[2A\r1    \n2    \n3    \n…    ");
+
+        // After shrinking, the cursor goes no higher than the top row
+        console.begin_capture();
+        console.resize(6, 2);
+        live.refresh(&mut console).unwrap();
+        assert_eq!(console.end_capture(), "\x1b[1A\r1    \n…    ");
+    }
+
+    #[derive(Debug)]
+    struct Failing;
+
+    impl Renderable for Failing {
+        fn render(&self, _console: &Console, _options: &RenderOptions) -> Result<Vec<Segment>> {
+            Err(LuxorError::rendering("broken"))
+        }
+    }
+
+    #[test]
+    fn test_stop_shows_cursor_after_error() {
+        let mut console = console(6, 10);
+        let mut live = Live::new(Failing);
+        assert!(live.start(&mut console).is_err());
+        assert!(live.is_started());
+        assert!(live.stop(&mut console).is_err());
+        assert_eq!(console.end_capture(), "\x1b[?25l\x1b[?25h");
+        // Stopping again does nothing
+        live.stop(&mut console).unwrap();
+    }
+}
@@ -5,15 +5,56 @@
 //! - `[red]text[/red]` - Apply red color
 //! - `[bold red]text[/bold red]` - Combine styles
 //! - `[/]` - Close the most recent tag
-//! - `\[` - Escape square brackets
+//! - `[link=https://example.com]text[/link]` - Make a hyperlink
+//! - `[warning]text[/warning]` - Apply a named style from a [`Theme`], when
+//!   one is set with [`RenderOptions::with_theme`](crate::RenderOptions::with_theme)
+//! - `\[`, `\]` and `\\` - Escape square brackets and backslashes; see
+//!   [`escape`]. `[[` also escapes an opening bracket
 //!
 //! The parser converts markup strings into `Text` instances with appropriate style spans.
-
-use crate::{error::LuxorError, style::Style, text::Text};
-use std::collections::VecDeque;
-
-/// A tuple of (start_position, Tag, Style) for tracking open style tags.
-type StyleStackEntry = (usize, Tag, Style);
+//!
+//! # When strings are parsed as markup
+//!
+//! Only plain strings printed or rendered as they are (`&str` and
+//! `String`) consult [`RenderOptions::markup`](crate::RenderOptions::markup),
+//! which defaults to on as in Rich. A [`Text`] is never parsed unless it
+//! was built with [`Text::from_markup`], and `Text::from("a [b] c")` is
+//! always literal.
+//!
+//! | `markup` option | String markup | Output                                   |
+//! |-----------------|---------------|------------------------------------------|
+//! | on              | valid         | styled by the markup                     |
+//! | on              | invalid       | the literal string, and a warning        |
+//! | off             | valid         | the literal string                       |
+//! | off             | invalid       | the literal string                       |
+//!
+//! The warning is reported as a [`Warning::InvalidMarkup`]. The print
+//! itself never fails because of markup.
+//!
+//! ```rust
+//! use luxor::{ColorSystem, Console, ConsoleOptions};
+//!
+//! let options = ConsoleOptions::new().with_color_system(ColorSystem::Standard);
+//! let console = Console::with_options(options);
+//! let output = console.capture(|console| console.println("[bold]x[/bold] y")).unwrap();
+//! assert_eq!(output, "\x1b[1mx\x1b[0m y\n");
+//!
+//! let console = Console::with_options(ConsoleOptions::new().with_markup(false));
+//! let output = console.capture(|console| console.println("[bold]x[/bold]")).unwrap();
+//! assert_eq!(output, "[bold]x[/bold]\n");
+//! ```
+
+use crate::{
+    diagnostics::{self, Warning},
+    error::LuxorError,
+    style::Style,
+    text::Text,
+    theme::Theme,
+};
+use std::{collections::VecDeque, ops::Range};
+
+/// A tuple of (span index, Tag, tag source range) for tracking open style tags.
+type StyleStackEntry = (usize, Tag, Range<usize>);
 
 /// Result type for token parsing operations.
 type ParseResult = Result<Vec<Token>, LuxorError>;
@@ -21,6 +62,12 @@ type ParseResult = Result<Vec<Token>, LuxorError>;
 /// Result type for span split operations.
 type SplitResult = (Span, Option<Span>);
 
+/// Optional sink for warnings; `Some` enables lossy rendering.
+type WarningSink<'a> = Option<&'a mut Vec<MarkupWarning>>;
+
+/// Rendered text together with the warnings produced while recovering.
+pub type LossyText = (Text, Vec<MarkupWarning>);
+
 /// A markup tag with optional parameters.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Tag {
@@ -80,7 +127,7 @@ impl std::fmt::Display for Tag {
 }
 
 /// A span of styled text within markup.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Span {
     /// Start position in the text
     pub start: usize,
@@ -121,19 +168,17 @@ impl Span {
         (left, Some(right))
     }
 
-    /// Move this span by the given offset.
+    /// Move this span by the given offset, saturating at the ends of
+    /// `usize`.
     pub fn move_by(&self, offset: isize) -> Self {
-        let start = if offset < 0 {
-            self.start.saturating_sub((-offset) as usize)
-        } else {
-            self.start + (offset as usize)
-        };
-        let end = if offset < 0 {
-            self.end.saturating_sub((-offset) as usize)
-        } else {
-            self.end + (offset as usize)
+        let shift = |position: usize| {
+            if offset < 0 {
+                position.saturating_sub(offset.unsigned_abs())
+            } else {
+                position.saturating_add(offset.unsigned_abs())
+            }
         };
-        Span::new(start, end, self.style.clone())
+        Span::new(shift(self.start), shift(self.end), self.style.clone())
     }
 }
 
@@ -142,8 +187,45 @@ impl Span {
 enum Token {
     /// Plain text content
     Text(String),
-    /// An opening or closing tag
-    Tag(Tag),
+    /// An opening or closing tag, with the byte range of its source in the markup
+    Tag(Tag, Range<usize>),
+}
+
+/// The kind of recovery performed by [`render_lossy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkupWarningKind {
+    /// A closing tag had no matching opening tag and was ignored.
+    UnmatchedClosingTag,
+    /// A tag did not name a known style and was rendered as literal text.
+    UnknownTag,
+    /// An opening tag was never closed and was closed at the end of the text.
+    UnclosedTag,
+}
+
+/// A non-fatal problem found while rendering markup in lossy mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupWarning {
+    /// What went wrong and how it was recovered.
+    pub kind: MarkupWarningKind,
+    /// Character position of the offending tag in the markup string.
+    pub position: usize,
+    /// The offending tag as it appeared in the markup (e.g. `[/italic]`).
+    pub tag: String,
+}
+
+impl std::fmt::Display for MarkupWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self.kind {
+            MarkupWarningKind::UnmatchedClosingTag => "closing tag has no matching opening tag",
+            MarkupWarningKind::UnknownTag => "unknown style, rendered as text",
+            MarkupWarningKind::UnclosedTag => "tag was never closed",
+        };
+        write!(
+            f,
+            "{} at position {}: {}",
+            self.tag, self.position, description
+        )
+    }
 }
 
 /// Parse markup tokens from a string.
@@ -153,6 +235,19 @@ fn parse_tokens(markup: &str) -> ParseResult {
     let mut current_pos = 0;
 
     while let Some((pos, ch)) = chars.next() {
+        if ch == '\\' {
+            // A backslash escapes a bracket or another backslash; before
+            // anything else it is literal
+            if let Some((_, next_ch @ ('[' | ']' | '\\'))) = chars.peek().copied() {
+                if pos > current_pos {
+                    tokens.push(Token::Text(markup[current_pos..pos].to_string()));
+                }
+                tokens.push(Token::Text(next_ch.to_string()));
+                chars.next();
+                current_pos = pos + 2;
+            }
+            continue;
+        }
         if ch == '[' {
             // Check for escaped bracket
             if let Some((_, next_ch)) = chars.peek() {
@@ -168,17 +263,14 @@ fn parse_tokens(markup: &str) -> ParseResult {
                 }
             }
 
-            // Find the closing bracket
+            // Find the closing bracket without consuming the character stream,
+            // so an unterminated `[` leaves the rest of the input to be scanned.
+            // Tags cannot contain `[`, so another one first also means unterminated.
             let tag_start = pos + 1;
-            let mut tag_end = None;
-
-            for (bracket_idx, bracket_ch) in chars.by_ref() {
-                if bracket_ch == ']' {
-                    tag_end = Some(bracket_idx);
-                    break;
-                }
-            }
-
+            let tag_end = markup[tag_start..]
+                .find(['[', ']'])
+                .map(|offset| tag_start + offset)
+                .filter(|&end_pos| markup.as_bytes()[end_pos] == b']');
             if let Some(end_pos) = tag_end {
                 // Add any text before this tag
                 if pos > current_pos {
@@ -189,15 +281,14 @@ fn parse_tokens(markup: &str) -> ParseResult {
                 let tag_content = &markup[tag_start..end_pos];
                 if !tag_content.is_empty() {
                     let tag = parse_tag(tag_content)?;
-                    tokens.push(Token::Tag(tag));
+                    tokens.push(Token::Tag(tag, pos..end_pos + 1));
                 }
 
                 current_pos = end_pos + 1;
-            } else {
-                // No closing bracket found, treat as regular text
-                // Continue to next character
-                current_pos = pos;
+                while chars.next_if(|&(index, _)| index <= end_pos).is_some() {}
             }
+            // With no closing bracket the `[` is plain text, picked up later
+            // from `current_pos`
         }
     }
 
@@ -220,43 +311,260 @@ fn parse_tag(content: &str) -> Result<Tag, LuxorError> {
     }
 }
 
-/// Escape markup syntax in plain text.
+/// Parse a string printed with markup enabled, falling back to `None`
+/// (print it literally) with a warning if the markup is invalid.
+pub(crate) fn render_or_warn(markup: &str, theme: Option<&Theme>) -> Option<Text> {
+    match render_parsed(markup, None, theme) {
+        Ok(text) => Some(text),
+        Err(error) => {
+            diagnostics::warn(|| Warning::InvalidMarkup {
+                markup: markup.to_string(),
+                message: error.to_string(),
+            });
+            None
+        }
+    }
+}
+
+/// Get the style an opening tag applies.
+///
+/// `[link=URL]` links to `URL`; every other tag is a style definition, or
+/// the name of a style in `theme`.
+fn tag_style(tag: &Tag, theme: Option<&Theme>) -> Result<Style, LuxorError> {
+    match (tag.name.as_str(), &tag.parameters, theme) {
+        ("link", Some(url), _) => Ok(Style::new().with_link(url.as_str())),
+        (name, _, Some(theme)) => theme.resolve(name),
+        (name, _, None) => Style::parse(name),
+    }
+}
+
+/// Escape markup syntax in plain text, so that [`render`] gives back
+/// `text` unchanged.
+///
+/// Brackets are escaped with a backslash, and so is a backslash that
+/// would otherwise escape the character after it. Other backslashes, such
+/// as those in Windows paths, are left alone.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::markup::{escape, render};
+///
+/// assert_eq!(escape("[bold]"), "\\[bold\\]");
+/// assert_eq!(escape("C:\\dir\\[x]"), "C:\\dir\\\\\\[x\\]");
+/// let text = "C:\\dir\\[x]";
+/// assert_eq!(render(&escape(text), None).unwrap().plain(), text);
+/// ```
 pub fn escape(text: &str) -> String {
-    text.replace('[', "\\[").replace(']', "\\]")
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '[' | ']' => escaped.push('\\'),
+            '\\' if matches!(chars.peek(), Some('[' | ']' | '\\')) => escaped.push('\\'),
+            _ => {}
+        }
+        escaped.push(ch);
+    }
+    escaped
 }
 
 /// Parse markup and render it into a `Text` instance.
+///
+/// Malformed markup produces a [`LuxorError::MarkupError`] carrying the
+/// character position of the offending tag, so callers can point at it.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{LuxorError, markup::render};
+///
+/// match render("[bold]oops[/italic]", None) {
+///     Err(LuxorError::MarkupError { position, snippet, .. }) => {
+///         assert_eq!(position, 10);
+///         assert_eq!(snippet, "[/italic]");
+///     }
+///     other => panic!("expected a markup error, got {:?}", other),
+/// }
+/// ```
 pub fn render(markup: &str, base_style: Option<Style>) -> Result<Text, LuxorError> {
-    // If no markup tags are present, return simple text
-    if !markup.contains('[') {
+    render_parsed(markup, base_style, None)
+}
+
+/// Parse markup like [`render`], looking up tags that are not style
+/// definitions in `theme`.
+///
+/// # Errors
+///
+/// Returns [`LuxorError::MarkupError`] for malformed markup, including a
+/// tag that is neither a style definition nor a name in `theme`.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Style, Theme, markup::render_with_theme};
+///
+/// let theme = Theme::new().with_style("path", Style::new().underline());
+/// let text = render_with_theme("[path]/tmp[/path] [bold]ok[/]", None, &theme).unwrap();
+/// assert_eq!(text.style_at(0).underline, Some(true));
+/// assert_eq!(text.style_at(5).bold, Some(true));
+///
+/// let error = render_with_theme("[pth]/tmp[/]", None, &theme).unwrap_err();
+/// assert!(error.to_string().contains("[pth]"));
+/// ```
+pub fn render_with_theme(
+    markup: &str,
+    base_style: Option<Style>,
+    theme: &Theme,
+) -> Result<Text, LuxorError> {
+    render_parsed(markup, base_style, Some(theme))
+}
+
+/// Parse markup, failing on the first problem.
+fn render_parsed(
+    markup: &str,
+    base_style: Option<Style>,
+    theme: Option<&Theme>,
+) -> Result<Text, LuxorError> {
+    // If no markup tags or escapes are present, return simple text
+    if !markup.contains(['[', '\\']) {
         return Ok(Text::new(markup).with_style(base_style.unwrap_or_default()));
     }
 
+    render_tokens(markup, base_style, theme, None)
+}
+
+/// Parse markup and render it into a `Text` instance without ever failing.
+///
+/// Problems are recovered from and reported as warnings instead:
+/// unmatched closing tags are ignored, unknown tags are rendered as literal
+/// text, and unclosed tags are closed at the end of the text.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::markup::{MarkupWarningKind, render_lossy};
+///
+/// let (text, warnings) = render_lossy("[bold]oops[/italic]", None);
+/// assert_eq!(text.plain(), "oops");
+/// assert_eq!(warnings.len(), 2);
+/// assert_eq!(warnings[0].kind, MarkupWarningKind::UnmatchedClosingTag);
+/// assert_eq!(warnings[1].kind, MarkupWarningKind::UnclosedTag);
+/// ```
+pub fn render_lossy(markup: &str, base_style: Option<Style>) -> LossyText {
+    render_recovered(markup, base_style, None)
+}
+
+/// Parse markup like [`render_lossy`], resolving tags that are not style
+/// definitions in `theme`.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Theme, markup::render_lossy_with_theme};
+///
+/// let (text, warnings) = render_lossy_with_theme("[warning]disk[/] [pth]x[/]", None, &Theme::new());
+/// assert_eq!(text.plain(), "disk [pth]x");
+/// assert_eq!(warnings.len(), 2);
+/// ```
+pub fn render_lossy_with_theme(
+    markup: &str,
+    base_style: Option<Style>,
+    theme: &Theme,
+) -> LossyText {
+    render_recovered(markup, base_style, Some(theme))
+}
+
+/// Parse markup, recovering from and reporting every problem.
+fn render_recovered(markup: &str, base_style: Option<Style>, theme: Option<&Theme>) -> LossyText {
+    let mut warnings = Vec::new();
+    let result = render_tokens(markup, base_style.clone(), theme, Some(&mut warnings));
+    for warning in &warnings {
+        diagnostics::warn(|| Warning::LossyMarkup(warning.clone()));
+    }
+    match result {
+        Ok(text) => (text, warnings),
+        // Lossy rendering recovers from every markup problem, so only
+        // unexpected internal errors reach this point.
+        Err(_) => (
+            Text::new(markup).with_style(base_style.unwrap_or_default()),
+            warnings,
+        ),
+    }
+}
+
+/// Converts byte offsets in a string to character offsets, counting from
+/// the previous offset converted.
+///
+/// Tags are reported in order, apart from unclosed tags reported last to
+/// first at the end, so moving in either direction keeps the total work
+/// linear in the length of the markup.
+struct CharCursor<'a> {
+    text: &'a str,
+    byte: usize,
+    chars: usize,
+}
+
+impl<'a> CharCursor<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            byte: 0,
+            chars: 0,
+        }
+    }
+
+    /// Get the character offset of the byte offset `byte`.
+    fn position(&mut self, byte: usize) -> usize {
+        if byte >= self.byte {
+            self.chars += self.text[self.byte..byte].chars().count();
+        } else {
+            self.chars -= self.text[byte..self.byte].chars().count();
+        }
+        self.byte = byte;
+        self.chars
+    }
+}
+
+/// Render markup tokens, recording recoveries in `warnings` when lossy.
+fn render_tokens(
+    markup: &str,
+    base_style: Option<Style>,
+    theme: Option<&Theme>,
+    mut warnings: WarningSink<'_>,
+) -> Result<Text, LuxorError> {
     let tokens = parse_tokens(markup)?;
     let mut text_content = String::new();
-    let mut spans = Vec::new();
+    // Span positions are character offsets into the rendered text. Each
+    // span is added when its tag opens, so inner tags come later and win
+    let mut text_len = 0;
+    let mut spans: Vec<Span> = Vec::new();
     let mut style_stack: VecDeque<StyleStackEntry> = VecDeque::new();
 
-    // Helper to create style from tag
-    let create_style_from_tag = |tag: &Tag| -> Result<Style, LuxorError> {
-        // For now, parse basic styles. This will be expanded.
-        Style::parse(&tag.name)
-    };
+    let mut cursor = CharCursor::new(markup);
+    let mut char_position = |byte_range: &Range<usize>| cursor.position(byte_range.start);
 
     for token in tokens {
         match token {
             Token::Text(text) => {
                 // Add the text content
+                text_len += text.chars().count();
                 text_content.push_str(&text);
             }
-            Token::Tag(tag) => {
+            Token::Tag(tag, source) => {
                 if tag.is_closing() {
                     let style_name = tag.closing_name();
 
                     if style_name.is_empty() {
                         // Implicit close - close the most recent tag
-                        if let Some((start_pos, _open_tag, style)) = style_stack.pop_back() {
-                            spans.push(Span::new(start_pos, text_content.len(), style));
+                        if let Some((index, _open_tag, _)) = style_stack.pop_back() {
+                            spans[index].end = text_len;
+                        } else if let Some(warnings) = warnings.as_deref_mut() {
+                            warnings.push(MarkupWarning {
+                                kind: MarkupWarningKind::UnmatchedClosingTag,
+                                position: char_position(&source),
+                                tag: markup[source].to_string(),
+                            });
                         }
                     } else {
                         // Explicit close - find matching tag
@@ -270,29 +578,64 @@ pub fn render(markup: &str, base_style: Option<Style>) -> Result<Text, LuxorErro
 
                         if let Some(index) = found_index {
                             // Close all tags from this point to the end
-                            let removed_tags: Vec<_> = style_stack.drain(index..).collect();
-                            for (start_pos, _tag, style) in removed_tags {
-                                spans.push(Span::new(start_pos, text_content.len(), style));
+                            for (span_index, _tag, _) in style_stack.drain(index..) {
+                                spans[span_index].end = text_len;
                             }
+                        } else if let Some(warnings) = warnings.as_deref_mut() {
+                            warnings.push(MarkupWarning {
+                                kind: MarkupWarningKind::UnmatchedClosingTag,
+                                position: char_position(&source),
+                                tag: markup[source].to_string(),
+                            });
                         } else {
-                            return Err(LuxorError::MarkupError(format!(
-                                "Closing tag '{}' has no matching opening tag",
-                                tag.name
-                            )));
+                            return Err(LuxorError::markup(
+                                format!("closing tag '{}' has no matching opening tag", tag.name),
+                                char_position(&source),
+                                &markup[source],
+                            ));
                         }
                     }
                 } else {
                     // Opening tag
-                    let style = create_style_from_tag(&tag)?;
-                    style_stack.push_back((text_content.len(), tag, style));
+                    match tag_style(&tag, theme) {
+                        Ok(style) => {
+                            style_stack.push_back((spans.len(), tag, source));
+                            spans.push(Span::new(text_len, text_len, style));
+                        }
+                        Err(error) => {
+                            if let Some(warnings) = warnings.as_deref_mut() {
+                                let literal = &markup[source.clone()];
+                                warnings.push(MarkupWarning {
+                                    kind: MarkupWarningKind::UnknownTag,
+                                    position: char_position(&source),
+                                    tag: literal.to_string(),
+                                });
+                                text_len += literal.chars().count();
+                                text_content.push_str(literal);
+                            } else {
+                                return Err(LuxorError::markup(
+                                    format!("invalid style in tag '{}': {}", tag.name, error),
+                                    char_position(&source),
+                                    &markup[source],
+                                ));
+                            }
+                        }
+                    }
                 }
             }
         }
     }
 
     // Close any remaining open tags
-    while let Some((start_pos, _tag, style)) = style_stack.pop_back() {
-        spans.push(Span::new(start_pos, text_content.len(), style));
+    while let Some((index, _tag, source)) = style_stack.pop_back() {
+        if let Some(warnings) = warnings.as_deref_mut() {
+            warnings.push(MarkupWarning {
+                kind: MarkupWarningKind::UnclosedTag,
+                position: char_position(&source),
+                tag: markup[source].to_string(),
+            });
+        }
+        spans[index].end = text_len;
     }
 
     // Create the text with spans
@@ -301,14 +644,210 @@ pub fn render(markup: &str, base_style: Option<Style>) -> Result<Text, LuxorErro
         text = text.with_style(base);
     }
 
-    // Apply all spans
-    for span in spans {
-        if !span.is_empty() {
-            text.stylize_range(span.start..span.end, span.style)?;
+    // Apply all spans. They end within the text by construction, so skip
+    // the bounds check of `stylize_range`, which counts the characters of
+    // the whole text for every span
+    text.spans_mut()
+        .extend(spans.into_iter().filter(|span| !span.is_empty()));
+
+    Ok(text)
+}
+
+/// A parser for markup that arrives in pieces, such as a log being tailed.
+///
+/// Each [`feed`](MarkupStream::feed) returns the text completed so far,
+/// styled by the tags open at that point, including tags opened by earlier
+/// feeds. A chunk may end anywhere, even inside a tag: whatever might still
+/// turn out to be a tag is held back until the next feed or
+/// [`finish`](MarkupStream::finish). Joining the returned texts gives the
+/// same plain text and styles as rendering the whole markup at once.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::markup::MarkupStream;
+///
+/// let mut stream = MarkupStream::new();
+/// let first = stream.feed("[bold]starting [re").unwrap();
+/// assert_eq!(first.plain(), "starting ");
+/// assert_eq!(stream.open_tags().count(), 1);
+///
+/// let second = stream.feed("d]up[/red] done").unwrap();
+/// assert_eq!(second.plain(), "up done");
+/// assert_eq!(second.style_at(0).bold, Some(true));
+/// assert!(second.style_at(0).color.is_some());
+///
+/// let tail = stream.finish();
+/// assert!(tail.is_empty());
+/// assert_eq!(stream.open_tags().count(), 0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MarkupStream {
+    base_style: Option<Style>,
+    /// Markup received but not parsed yet: a possible tag at the end.
+    pending: String,
+    /// Tags opened and not yet closed, outermost first.
+    open: Vec<OpenTag>,
+    /// Characters of markup parsed so far, for error positions.
+    position: usize,
+}
+
+/// A tag left open by a feed, with the style it applies.
+type OpenTag = (Tag, Style);
+
+impl MarkupStream {
+    /// Create a stream with no tags open.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the style all returned text is drawn over.
+    pub fn with_base_style(mut self, style: Style) -> Self {
+        self.base_style = Some(style);
+        self
+    }
+
+    /// Parse `chunk` after the markup fed before it, returning the text it
+    /// completes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LuxorError::MarkupError`] for the same markup that
+    /// [`render`] rejects, positioned in characters from the start of the
+    /// stream. The stream is left as it was before the failed feed.
+    pub fn feed(&mut self, chunk: &str) -> Result<Text, LuxorError> {
+        self.pending.push_str(chunk);
+        let complete = complete_markup_len(&self.pending);
+        let markup = self.pending[..complete].to_string();
+        let text = self.render(&markup)?;
+        self.pending.drain(..complete);
+        Ok(text)
+    }
+
+    /// Return the text held back at the end of the stream, as plain text,
+    /// and close the tags left open.
+    ///
+    /// Only an opening bracket that was never closed can be held back, so
+    /// this cannot fail. The stream can be reused afterwards.
+    pub fn finish(&mut self) -> Text {
+        let markup = std::mem::take(&mut self.pending);
+        let text = self.render(&markup).unwrap_or_else(|_| {
+            Text::new(&markup).with_style(self.base_style.clone().unwrap_or_default())
+        });
+        self.open.clear();
+        self.position = 0;
+        text
+    }
+
+    /// Get the tags opened and not yet closed, outermost first.
+    pub fn open_tags(&self) -> impl Iterator<Item = &Tag> + '_ {
+        self.open.iter().map(|(tag, _)| tag)
+    }
+
+    /// Render complete markup with the open tags, updating them and the
+    /// position only on success.
+    fn render(&mut self, markup: &str) -> Result<Text, LuxorError> {
+        let tokens = parse_tokens(markup)?;
+        let mut open = self.open.clone();
+        let mut content = String::new();
+        let mut text_len = 0;
+        // Tags still open from earlier feeds style this one from its start
+        let mut spans: Vec<Span> = open
+            .iter()
+            .map(|(_, style)| Span::new(0, 0, style.clone()))
+            .collect();
+        let mut stack: Vec<usize> = (0..spans.len()).collect();
+
+        let mut cursor = CharCursor::new(markup);
+        let offset = self.position;
+        let mut char_position = |source: &Range<usize>| offset + cursor.position(source.start);
+
+        for token in tokens {
+            match token {
+                Token::Text(text) => {
+                    text_len += text.chars().count();
+                    content.push_str(&text);
+                }
+                Token::Tag(tag, source) if tag.is_closing() => {
+                    let name = tag.closing_name();
+                    let found = if name.is_empty() {
+                        open.len().checked_sub(1)
+                    } else {
+                        open.iter().rposition(|(open_tag, _)| open_tag.name == name)
+                    };
+                    match found {
+                        Some(index) => {
+                            open.truncate(index);
+                            for span_index in stack.drain(index..) {
+                                spans[span_index].end = text_len;
+                            }
+                        }
+                        // Like `render`, a stray `[/]` is ignored
+                        None if name.is_empty() => {}
+                        None => {
+                            return Err(LuxorError::markup(
+                                format!("closing tag '{}' has no matching opening tag", tag.name),
+                                char_position(&source),
+                                &markup[source],
+                            ));
+                        }
+                    }
+                }
+                Token::Tag(tag, source) => match tag_style(&tag, None) {
+                    Ok(style) => {
+                        stack.push(spans.len());
+                        spans.push(Span::new(text_len, text_len, style.clone()));
+                        open.push((tag, style));
+                    }
+                    Err(error) => {
+                        return Err(LuxorError::markup(
+                            format!("invalid style in tag '{}': {}", tag.name, error),
+                            char_position(&source),
+                            &markup[source],
+                        ));
+                    }
+                },
+            }
+        }
+        for span_index in stack {
+            spans[span_index].end = text_len;
+        }
+
+        self.open = open;
+        self.position += markup.chars().count();
+        let mut text = Text::new(&content);
+        if let Some(base) = &self.base_style {
+            text = text.with_style(base.clone());
         }
+        text.spans_mut()
+            .extend(spans.into_iter().filter(|span| !span.is_empty()));
+        Ok(text)
     }
+}
 
-    Ok(text)
+/// Get the length in bytes of the start of `markup` that parses the same
+/// whatever follows it: everything before a `[` that may still become a
+/// tag or an escape, or a `\\` that may still escape the next character.
+/// Mirrors how [`parse_tokens`] scans.
+fn complete_markup_len(markup: &str) -> usize {
+    let bytes = markup.as_bytes();
+    let mut index = 0;
+    while let Some(offset) = markup[index..].find(['[', '\\']) {
+        let open = index + offset;
+        match (bytes[open], bytes.get(open + 1)) {
+            (_, None) => return open,
+            (b'\\', Some(b'[' | b']' | b'\\')) => index = open + 2,
+            (b'\\', Some(_)) => index = open + 1,
+            (_, Some(b'[')) => index = open + 2,
+            (_, Some(_)) => match markup[open + 1..].find(['[', ']']) {
+                Some(end) if bytes[open + 1 + end] == b']' => index = open + 1 + end + 1,
+                // Another `[` first: this one is literal text
+                Some(_) => index = open + 1,
+                None => return open,
+            },
+        }
+    }
+    markup.len()
 }
 
 #[cfg(test)]
@@ -375,7 +914,7 @@ mod tests {
         }
 
         match &tokens[1] {
-            Token::Tag(tag) => {
+            Token::Tag(tag, _) => {
                 assert_eq!(tag.name, "bold");
                 assert!(!tag.is_closing());
             }
@@ -388,7 +927,7 @@ mod tests {
         }
 
         match &tokens[3] {
-            Token::Tag(tag) => {
+            Token::Tag(tag, _) => {
                 assert_eq!(tag.name, "/bold");
                 assert!(tag.is_closing());
             }
@@ -430,6 +969,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_render_palette_and_rgb_tags() {
+        let text = render(
+            "[color(196)]a[/] [rgb(0, 128, 255) on 17]b[/rgb(0, 128, 255) on 17]",
+            None,
+        )
+        .unwrap();
+        assert_eq!(text.plain(), "a b");
+        assert_eq!(text.style_at(0).color, Some(Color::EightBit(196)));
+        assert_eq!(text.style_at(2).color, Some(Color::rgb(0, 128, 255)));
+        assert_eq!(text.style_at(2).background, Some(Color::EightBit(17)));
+
+        let error = error_message("[color(300)]x[/]");
+        assert!(error.contains("'color(300)'"), "{}", error);
+    }
+
+    #[test]
+    fn test_render_link_tag() {
+        let text = render("see [link=https://example.com/a b]docs[/link]", None).unwrap();
+        assert_eq!(text.plain(), "see docs");
+        assert_eq!(text.style_at(5).link_url(), Some("https://example.com/a b"));
+        assert_eq!(text.style_at(0).link_url(), None);
+        assert!(render("[link]docs[/link]", None).is_err());
+    }
+
     #[test]
     fn test_render_nested_tags() {
         let text = render("[bold]Hello [red]world[/red][/bold]", None).unwrap();
@@ -467,12 +1031,259 @@ mod tests {
         assert_eq!(span.style.bold, Some(true));
     }
 
+    #[test]
+    fn test_render_unmatched_closing_tag_error() {
+        let error = render("ok [bold]ab[/italic]", None).unwrap_err();
+        match error {
+            LuxorError::MarkupError {
+                position, snippet, ..
+            } => {
+                assert_eq!(position, 11);
+                assert_eq!(snippet, "[/italic]");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_render_unknown_tag_error() {
+        let error = render("héllo [nosuchstyle]x", None).unwrap_err();
+        match error {
+            LuxorError::MarkupError {
+                position, snippet, ..
+            } => {
+                // Position counts characters, not bytes
+                assert_eq!(position, 6);
+                assert_eq!(snippet, "[nosuchstyle]");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(error_message("[nosuchstyle]").contains("position 0"));
+    }
+
+    #[test]
+    fn test_render_with_theme() {
+        let theme = Theme::new().with_style("bold", Style::new().italic());
+        let text =
+            render_with_theme("[warning]a [danger]b[/danger][/] [bold]c[/]", None, &theme).unwrap();
+        assert_eq!(text.plain(), "a b c");
+        assert_eq!(text.style_at(0), theme.get("warning").unwrap().clone());
+        assert_eq!(text.style_at(2).bold, Some(true));
+        // Theme names win over style definitions of the same name
+        assert_eq!(text.style_at(4), Style::new().italic());
+
+        let error = render_with_theme("ok [warnign]x[/]", None, &theme).unwrap_err();
+        match error {
+            LuxorError::MarkupError {
+                position, snippet, ..
+            } => {
+                assert_eq!(position, 3);
+                assert_eq!(snippet, "[warnign]");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(render("[warning]x[/]", None).is_err());
+    }
+
+    fn error_message(markup: &str) -> String {
+        render(markup, None).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_render_multibyte_span_positions() {
+        let text = render("héllo [bold]wörld[/bold]", None).unwrap();
+        assert_eq!(text.plain(), "héllo wörld");
+        assert_eq!(text.spans()[0].start, 6);
+        assert_eq!(text.spans()[0].end, 11);
+    }
+
+    #[test]
+    fn test_render_lossy_clean_markup() {
+        let (text, warnings) = render_lossy("[bold]Hello[/bold]", None);
+        assert_eq!(text.plain(), "Hello");
+        assert_eq!(text.spans().len(), 1);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_render_lossy_unmatched_closing_tag() {
+        let (text, warnings) = render_lossy("a[/bold]b", None);
+        assert_eq!(text.plain(), "ab");
+        assert!(text.spans().is_empty());
+        assert_eq!(
+            warnings,
+            vec![MarkupWarning {
+                kind: MarkupWarningKind::UnmatchedClosingTag,
+                position: 1,
+                tag: "[/bold]".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_render_lossy_implicit_close_without_open() {
+        let (text, warnings) = render_lossy("a[/]b", None);
+        assert_eq!(text.plain(), "ab");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, MarkupWarningKind::UnmatchedClosingTag);
+        assert_eq!(warnings[0].tag, "[/]");
+    }
+
+    #[test]
+    fn test_render_lossy_unknown_tag() {
+        let (text, warnings) = render_lossy("a[nosuchstyle]b", None);
+        assert_eq!(text.plain(), "a[nosuchstyle]b");
+        assert!(text.spans().is_empty());
+        assert_eq!(
+            warnings,
+            vec![MarkupWarning {
+                kind: MarkupWarningKind::UnknownTag,
+                position: 1,
+                tag: "[nosuchstyle]".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_render_lossy_unclosed_tag() {
+        let (text, warnings) = render_lossy("[red]ab[bold]cd", None);
+        assert_eq!(text.plain(), "abcd");
+        assert_eq!(text.spans().len(), 2);
+        assert_eq!(
+            warnings,
+            vec![
+                MarkupWarning {
+                    kind: MarkupWarningKind::UnclosedTag,
+                    position: 7,
+                    tag: "[bold]".to_string(),
+                },
+                MarkupWarning {
+                    kind: MarkupWarningKind::UnclosedTag,
+                    position: 0,
+                    tag: "[red]".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "[bold] at position 7: tag was never closed"
+        );
+    }
+
+    #[test]
+    fn test_render_unterminated_tag_keeps_text() {
+        let text = render("before [after", None).unwrap();
+        assert_eq!(text.plain(), "before [after");
+        assert_eq!(render("end [", None).unwrap().plain(), "end [");
+
+        let text = render("[bold]a[/bold] b [c", None).unwrap();
+        assert_eq!(text.plain(), "a b [c");
+        assert_eq!(text.spans().len(), 1);
+    }
+
+    #[test]
+    fn test_render_unterminated_tag_then_escape() {
+        // The escape after an unterminated `[` is still recognized
+        let text = render("[é[[", None).unwrap();
+        assert_eq!(text.plain(), "[é[");
+        let text = render("漢[字[bold]x[/bold]", None).unwrap();
+        assert_eq!(text.plain(), "漢[字x");
+        assert_eq!(text.spans()[0].start, 3);
+    }
+
+    #[test]
+    fn test_markup_stream_matches_one_shot_at_every_split() {
+        let markup = "[bold]héllo [red on blue]wörld[/red on blue][[not a tag] \
+                      [link=https://example.com]site[/link] [italic]a [b stray [/][/bold] end [";
+        let expected = render(markup, None).unwrap();
+        let styles = |text: &Text| -> Vec<Style> {
+            (0..text.len()).map(|index| text.style_at(index)).collect()
+        };
+
+        for split in (0..=markup.len()).filter(|&split| markup.is_char_boundary(split)) {
+            for second in (split..=markup.len()).filter(|&index| markup.is_char_boundary(index)) {
+                let mut stream = MarkupStream::new();
+                let mut text = stream.feed(&markup[..split]).unwrap();
+                text.append_text(stream.feed(&markup[split..second]).unwrap());
+                text.append_text(stream.feed(&markup[second..]).unwrap());
+                text.append_text(stream.finish());
+                assert_eq!(
+                    text.plain(),
+                    expected.plain(),
+                    "split at {} and {}",
+                    split,
+                    second
+                );
+                assert_eq!(
+                    styles(&text),
+                    styles(&expected),
+                    "split at {} and {}",
+                    split,
+                    second
+                );
+            }
+        }
+
+        let mut stream = MarkupStream::new();
+        stream.feed("[bold]a[ita").unwrap();
+        assert_eq!(
+            stream
+                .open_tags()
+                .map(|tag| tag.name.as_str())
+                .collect::<Vec<_>>(),
+            ["bold"]
+        );
+        let error = stream.feed("lic]b[/red]").unwrap_err();
+        assert!(matches!(
+            error,
+            LuxorError::MarkupError { position: 16, .. }
+        ));
+        assert_eq!(stream.open_tags().count(), 1);
+    }
+
     #[test]
     fn test_escape_markup() {
         assert_eq!(escape("Hello [world]"), "Hello \\[world\\]");
         assert_eq!(escape("No markup here"), "No markup here");
     }
 
+    #[test]
+    fn test_backslash_escapes() {
+        let text = render("\\[bold]x\\[/bold]", None).unwrap();
+        assert_eq!(text.plain(), "[bold]x[/bold]");
+        assert!(text.spans().is_empty());
+        // An escaped backslash leaves the tag after it a tag
+        let text = render("a\\\\[bold]b[/bold]", None).unwrap();
+        assert_eq!(text.plain(), "a\\b");
+        assert_eq!(text.style_at(2).bold, Some(true));
+        // Other backslashes are literal
+        assert_eq!(render("C:\\dir\\", None).unwrap().plain(), "C:\\dir\\");
+        assert_eq!(render("x\\]", None).unwrap().plain(), "x]");
+    }
+
+    #[test]
+    fn test_escape_round_trips() {
+        for text in [
+            "[bold]not a tag[/bold]",
+            "a\\[b",
+            "trailing \\",
+            "\\\\[x]\\",
+            "[[",
+            "漢[字]\\é",
+            "unclosed [tag",
+        ] {
+            let escaped = escape(text);
+            assert_eq!(render(&escaped, None).unwrap().plain(), text, "{}", escaped);
+            let mut stream = MarkupStream::new();
+            let mut streamed = Text::new("");
+            for ch in escaped.chars() {
+                streamed.append_text(stream.feed(&ch.to_string()).unwrap());
+            }
+            streamed.append_text(stream.finish());
+            assert_eq!(streamed.plain(), text);
+        }
+    }
+
     #[test]
     fn test_span_operations() {
         let style = Style::new().bold();
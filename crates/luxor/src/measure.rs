@@ -1,5 +1,7 @@
 //! Measurement utilities for layout calculations.
 
+use crate::units::Cells;
+
 /// Represents the measurement requirements for a renderable object.
 ///
 /// Measurements are used by the layout system to determine how much space
@@ -72,6 +74,11 @@ impl Measurement {
         Self::new(0, width)
     }
 
+    /// Create a measurement from typed widths. See [`Measurement::new`].
+    pub fn from_cells(minimum: Cells, maximum: Cells) -> Self {
+        Self::new(minimum.get(), maximum.get())
+    }
+
     /// Get the minimum width.
     pub fn minimum(&self) -> usize {
         self.minimum
@@ -82,6 +89,16 @@ impl Measurement {
         self.maximum
     }
 
+    /// Get the minimum width, as [`Cells`].
+    pub fn minimum_cells(&self) -> Cells {
+        Cells(self.minimum)
+    }
+
+    /// Get the maximum width, as [`Cells`].
+    pub fn maximum_cells(&self) -> Cells {
+        Cells(self.maximum)
+    }
+
     /// Check if this is a fixed width measurement.
     ///
     /// Returns `true` if the minimum and maximum widths are equal.
@@ -119,7 +136,8 @@ impl Measurement {
         )
     }
 
-    /// Add a fixed width to both minimum and maximum.
+    /// Add a fixed width to both minimum and maximum, saturating at
+    /// `usize::MAX`.
     ///
     /// # Examples
     ///
@@ -132,7 +150,10 @@ impl Measurement {
     /// assert_eq!(expanded.maximum(), 25);
     /// ```
     pub fn add_width(self, width: usize) -> Self {
-        Self::new(self.minimum + width, self.maximum + width)
+        Self::new(
+            self.minimum.saturating_add(width),
+            self.maximum.saturating_add(width),
+        )
     }
 
     /// Subtract a width from both minimum and maximum.
@@ -196,7 +217,10 @@ impl Measurement {
     /// assert_eq!(combined.maximum(), 35);
     /// ```
     pub fn add_with(self, other: Self) -> Self {
-        Self::new(self.minimum + other.minimum, self.maximum + other.maximum)
+        Self::new(
+            self.minimum.saturating_add(other.minimum),
+            self.maximum.saturating_add(other.maximum),
+        )
     }
 }
 
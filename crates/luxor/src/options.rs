@@ -0,0 +1,1246 @@
+//! Options - the settings a single render is performed with.
+//!
+//! [`RenderOptions`] is what [`Renderable::render`] and
+//! [`Measurable::measure`] receive: the available width and the settings
+//! that change how content is laid out. Settings that belong to the
+//! console as a whole, such as the color system and the alternate screen,
+//! stay on [`ConsoleOptions`] and [`Console`]. A console renders with the
+//! defaults in [`ConsoleOptions::render`]; a component rendering a child
+//! with different settings clones and adjusts the options it was given.
+//!
+//! # Migrating from `ConsoleOptions`
+//!
+//! Before this split, `ConsoleOptions` was passed to every render. The
+//! mapping is:
+//!
+//! | Before | After |
+//! |--------|-------|
+//! | `render(&self, &Console, &ConsoleOptions)` | `render(&self, &Console, &RenderOptions)` |
+//! | `measure(&self, &Console, &ConsoleOptions)` | `measure(&self, &Console, &RenderOptions)` |
+//! | `options.max_width`, `min_width` | deprecated fields mirroring `options.render.max_width` and `min_width`, until 0.3 |
+//! | `options.get_max_width()`, `width_source()`, `clamp_width()`, `validate()` | deprecated methods passing through to `options.render`, until 0.3 |
+//! | `options.fallback_width`, `soft_wrap`, `ascii_only`, `accessible`, `bidi_policy`, `ellipsis`, `markup` | the same fields of [`RenderOptions`], or `ConsoleOptions::render` |
+//! | `options.enable_color`, `color_system`, `legacy_windows` | unchanged, on [`ConsoleOptions`] |
+//! | `options.alt_screen` | [`Console::enable_alt_screen`](crate::Console::enable_alt_screen) and [`Console::is_alt_screen`](crate::Console::is_alt_screen) |
+//! | `console.get_render_options()` returning `ConsoleOptions` | returns `RenderOptions`; [`Console::render_options`](crate::Console::render_options) borrows it |
+//! | `console.options().clone().with_max_width(w)` | `console.render_options().clone().with_max_width(w)` |
+//!
+//! The `with_*` builders for render settings remain on `ConsoleOptions`
+//! and set its defaults. Render settings are read through
+//! `ConsoleOptions::render`, as in `options.render.max_width`. The
+//! deprecated `max_width` and `min_width` fields are kept for one release:
+//! builders set them along with the render options, and a value assigned
+//! to one of them directly replaces the render setting when the options
+//! are used. The `ConsoleOptions::alt_screen` field was never read and is
+//! deprecated.
+
+use crate::{
+    Capabilities, LuxorError, Measurable, Measurement, Renderable, Result, Segment, Style, Text,
+    capabilities,
+    console::{Console, ConsoleOptions},
+    diagnostics::{self, Warning},
+    profile::{self, Profiler},
+    sanitize::Policy,
+    text::{BidiPolicy, EllipsisConfig},
+    theme::Theme,
+};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+/// The number of measure calls in one pass after which containers
+/// estimate the size of their children. See
+/// [`RenderOptions::with_measure_limit`].
+pub const DEFAULT_MEASURE_LIMIT: usize = 10_000;
+
+/// Where the effective render width came from.
+///
+/// Components can use this to pick layouts that do not depend on a real
+/// terminal, for example not wrapping when output is piped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WidthSource {
+    /// Set explicitly with [`RenderOptions::with_max_width`].
+    Explicit,
+    /// Read from the `COLUMNS` environment variable.
+    Environment,
+    /// Queried from the terminal.
+    Terminal,
+    /// No width was available, so [`RenderOptions::fallback_width`] was used.
+    Fallback,
+}
+
+impl WidthSource {
+    /// Check whether the width reflects a real display rather than a guess.
+    pub fn is_known(self) -> bool {
+        self != WidthSource::Fallback
+    }
+}
+
+/// A width and where it was detected.
+type DetectedWidth = (usize, WidthSource);
+
+/// A child of a container: its address and type.
+type ChildKey = (usize, &'static str);
+
+/// The last measurement of each child in a pass.
+type LastMeasured = HashMap<ChildKey, Measured>;
+
+/// A measurement of a child and the call and widths it was made for.
+#[derive(Debug, Clone, Copy)]
+struct Measured {
+    call: usize,
+    max_width: usize,
+    min_width: usize,
+    measurement: Measurement,
+}
+
+/// The measure calls of one print or render pass, shared by the options
+/// of every call nested in it. See [`RenderOptions::measure_child`].
+///
+/// The width detected for options without an explicit one is resolved
+/// once per pass too.
+#[derive(Debug, Default)]
+pub struct MeasurePass {
+    calls: AtomicUsize,
+    last: Mutex<LastMeasured>,
+    detected_width: OnceLock<Option<DetectedWidth>>,
+}
+
+impl MeasurePass {
+    /// Create a pass with no measure calls made yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of measure calls made in the pass.
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LastMeasured> {
+        self.last
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Settings for a single render.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, Text};
+///
+/// let options = RenderOptions::new().with_max_width(7);
+/// let segments = Text::new("wrapped text").render(&Console::new(), &options).unwrap();
+/// assert_eq!(segments[0].text(), "wrapped");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// Maximum width for rendering (None = use terminal width).
+    pub max_width: Option<usize>,
+    /// Minimum width for rendering.
+    pub min_width: usize,
+    /// Maximum height for rendering (None = use terminal height).
+    pub max_height: Option<usize>,
+    /// Accessible output: components add textual signals alongside color.
+    pub accessible: bool,
+    /// Width used when neither an explicit width, `COLUMNS` nor the
+    /// terminal size is available.
+    pub fallback_width: usize,
+    /// Skip width-dependent layout (wrapping and padding) of text.
+    pub soft_wrap: bool,
+    /// Restrict decorations (borders, bullets, caps) to ASCII characters.
+    pub ascii_only: bool,
+    /// Handling of right-to-left text; `None` leaves it to each component.
+    pub bidi_policy: Option<BidiPolicy>,
+    /// Marker used by components that truncate text to fit.
+    pub ellipsis: EllipsisConfig,
+    /// Parse plain strings that are printed or rendered as markup.
+    pub markup: bool,
+    /// Named styles for markup tags. See [`RenderOptions::with_theme`].
+    pub theme: Option<Arc<Theme>>,
+    /// Highlight plain strings that are printed or rendered. See
+    /// [`RenderOptions::with_highlight`].
+    pub highlight: bool,
+    /// Sanitizing of control characters in text without a policy of its
+    /// own. See [`RenderOptions::with_sanitize`].
+    pub sanitize: Policy,
+    /// Focus, selection and search for interactive views.
+    pub state: RenderState,
+    /// Flag that stops a long render with [`LuxorError::Cancelled`] once
+    /// set. See [`RenderOptions::with_cancel`].
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// How many containers deep the current measure call is.
+    pub measure_depth: usize,
+    /// Which measure call of the pass these options were made for.
+    pub measure_call: usize,
+    /// Measure calls made through [`RenderOptions::measure_child`] in the
+    /// current pass, shared by the options of every nested call.
+    pub measure_pass: Option<Arc<MeasurePass>>,
+    /// Measure calls in one pass after which containers estimate instead.
+    /// See [`RenderOptions::with_measure_limit`].
+    pub measure_limit: usize,
+    /// Collector of render timings. See [`RenderOptions::with_profiler`].
+    pub profiler: Option<Profiler>,
+}
+
+impl RenderOptions {
+    /// Create new render options with default settings.
+    ///
+    /// ASCII-only and accessibility defaults come from
+    /// [`Capabilities::probe_once`].
+    pub fn new() -> Self {
+        let probed = Capabilities::probe_once();
+        Self {
+            max_width: None,
+            min_width: 0,
+            max_height: None,
+            accessible: probed.accessible,
+            fallback_width: DEFAULT_FALLBACK_WIDTH,
+            soft_wrap: false,
+            ascii_only: !probed.unicode,
+            bidi_policy: None,
+            ellipsis: EllipsisConfig::default(),
+            markup: true,
+            theme: None,
+            highlight: false,
+            sanitize: Policy::Off,
+            state: RenderState::default(),
+            cancel: None,
+            measure_depth: 0,
+            measure_call: 0,
+            measure_pass: None,
+            measure_limit: DEFAULT_MEASURE_LIMIT,
+            profiler: None,
+        }
+    }
+
+    /// Set the maximum width.
+    pub fn with_max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Set the minimum width.
+    pub fn with_min_width(mut self, width: usize) -> Self {
+        self.min_width = width;
+        self
+    }
+
+    /// Set the maximum height, for components that fill the height
+    /// available to them.
+    pub fn with_max_height(mut self, height: usize) -> Self {
+        self.max_height = Some(height);
+        self
+    }
+
+    /// Get the effective maximum height.
+    ///
+    /// The height comes from an explicit `max_height`, then the terminal
+    /// size, and is otherwise 24 lines. A console sets it from the height
+    /// it knows when it prints, so it matches [`Console::height`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::RenderOptions;
+    ///
+    /// assert_eq!(RenderOptions::new().with_max_height(3).get_max_height(), 3);
+    /// assert!(RenderOptions::new().get_max_height() > 0);
+    /// ```
+    pub fn get_max_height(&self) -> usize {
+        self.max_height.unwrap_or_else(|| {
+            capabilities::terminal_size()
+                .map(|(_, height)| height)
+                .filter(|&height| height > 0)
+                .unwrap_or(DEFAULT_FALLBACK_HEIGHT)
+        })
+    }
+
+    /// Enable or disable accessible output.
+    ///
+    /// In accessible mode, built-in components never convey meaning through
+    /// color alone and add a textual marker as well, and theme styles are
+    /// looked up with the theme's accessible overrides applied; see
+    /// [`Theme::with_accessible_override`]. It is enabled by
+    /// default when the `LUXOR_ACCESSIBLE` environment variable is set to
+    /// anything other than an empty string or `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::RenderOptions;
+    ///
+    /// let options = RenderOptions::new().with_accessible(true);
+    /// assert!(options.accessible);
+    /// ```
+    pub fn with_accessible(mut self, accessible: bool) -> Self {
+        self.accessible = accessible;
+        self
+    }
+
+    /// Set the width used when no other width source is available.
+    pub fn with_fallback_width(mut self, width: usize) -> Self {
+        self.fallback_width = width;
+        self
+    }
+
+    /// Enable or disable soft wrapping.
+    ///
+    /// With soft wrapping on, text is emitted as-is without width-dependent
+    /// padding or wrapping, leaving line breaking to whatever displays it.
+    /// This suits output that is piped rather than shown in a terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, RenderOptions, Justify, Renderable, Text};
+    ///
+    /// let options = RenderOptions::new().with_max_width(10).with_soft_wrap(true);
+    /// let text = Text::new("hi").with_justify(Justify::Right);
+    /// let segments = text.render(&Console::new(), &options).unwrap();
+    /// assert_eq!(segments[0].text(), "hi");
+    /// ```
+    pub fn with_soft_wrap(mut self, soft_wrap: bool) -> Self {
+        self.soft_wrap = soft_wrap;
+        self
+    }
+
+    /// Enable or disable ASCII-only decorations.
+    ///
+    /// Components fall back to ASCII look-alikes for box drawing and other
+    /// decorative characters, for terminals and fonts without them. Text
+    /// content is never altered.
+    pub fn with_ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// Set how right-to-left text is handled.
+    ///
+    /// Without an explicit policy, plain text uses [`BidiPolicy::Ignore`]
+    /// and bordered components use [`BidiPolicy::Isolate`], since borders
+    /// are where reordering by the terminal is visible. See [`BidiPolicy`]
+    /// for what each policy can and cannot fix.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{BidiPolicy, Console, RenderOptions, Renderable, Text};
+    ///
+    /// let options = RenderOptions::new()
+    ///     .with_max_width(20)
+    ///     .with_bidi_policy(BidiPolicy::Isolate);
+    /// let segments = Text::new("שלום").render(&Console::new(), &options).unwrap();
+    /// assert_eq!(segments[0].text(), "\u{2068}שלום\u{2069}");
+    /// ```
+    pub fn with_bidi_policy(mut self, policy: BidiPolicy) -> Self {
+        self.bidi_policy = Some(policy);
+        self
+    }
+
+    /// Set the marker components use when truncating text, unless they are
+    /// configured with their own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{RenderOptions, EllipsisConfig};
+    ///
+    /// let options = RenderOptions::new().with_ellipsis(EllipsisConfig::new("..."));
+    /// assert_eq!(options.ellipsis.marker, "...");
+    /// ```
+    pub fn with_ellipsis(mut self, ellipsis: EllipsisConfig) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Enable or disable markup in plain strings.
+    ///
+    /// When enabled, the default, a `&str` or `String` that is printed is
+    /// parsed as markup, and printed literally with a warning if that
+    /// fails. [`Text`] values are not affected. See the
+    /// [`markup`](crate::markup) module for the full rules.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, Renderable, RenderOptions};
+    ///
+    /// let options = RenderOptions::new().with_markup(false);
+    /// let segments = "[red]x[/red]".render(&Console::new(), &options).unwrap();
+    /// assert_eq!(segments[0].text(), "[red]x[/red]");
+    /// ```
+    pub fn with_markup(mut self, markup: bool) -> Self {
+        self.markup = markup;
+        self
+    }
+
+    /// Look up markup tags that are not style definitions in `theme`, so
+    /// a plain string can use names such as `[warning]`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, Renderable, RenderOptions, Theme};
+    ///
+    /// let options = RenderOptions::new().with_theme(Theme::new());
+    /// let segments = "[danger]x[/danger]".render(&Console::new(), &options).unwrap();
+    /// assert_eq!(segments[0].text(), "x");
+    /// assert_eq!(segments[0].style().bold, Some(true));
+    /// ```
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(Arc::new(theme));
+        self
+    }
+
+    /// Get the theme to look styles up in: the theme, with its accessible
+    /// overrides applied in accessible mode.
+    pub(crate) fn resolved_theme(&self) -> Option<Cow<'_, Theme>> {
+        let theme = self.theme.as_deref()?;
+        Some(match self.accessible {
+            true => theme.for_accessible(),
+            false => Cow::Borrowed(theme),
+        })
+    }
+
+    /// Enable or disable highlighting of plain strings.
+    ///
+    /// When enabled, a `&str` or `String` that is printed is styled by the
+    /// console's highlighter, after any markup in it is parsed; see the
+    /// [`highlighter`](crate::highlighter) module. Off by default.
+    /// [`Text`] values are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, Renderable, RenderOptions};
+    ///
+    /// let options = RenderOptions::new().with_highlight(true);
+    /// let segments = "x=1".render(&Console::new(), &options).unwrap();
+    /// assert_eq!(segments[1].text(), "1");
+    /// assert_eq!(segments[1].style().bold, Some(true));
+    /// ```
+    pub fn with_highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Sanitize text under `policy` before it is measured or rendered,
+    /// unless the text sets a policy of its own with
+    /// [`Text::with_sanitize`]. Off by default. See the
+    /// [`sanitize`](crate::sanitize) module.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, Renderable, RenderOptions, sanitize::Policy};
+    ///
+    /// let options = RenderOptions::new().with_sanitize(Policy::Strict);
+    /// let segments = "\x1b[200~pasted".render(&Console::new(), &options).unwrap();
+    /// assert_eq!(segments[0].text(), "pasted");
+    /// ```
+    pub fn with_sanitize(mut self, policy: Policy) -> Self {
+        self.sanitize = policy;
+        self
+    }
+
+    /// Set the focus, selection and search state. See [`RenderState`].
+    pub fn with_state(mut self, state: RenderState) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Stop rendering when `flag` is set, e.g. from a Ctrl-C handler.
+    ///
+    /// Cancellation is cooperative: loops that can run for a long time,
+    /// such as the rows of a [`Tree`](crate::Tree) and the lines of a
+    /// [`Text`], call [`RenderOptions::check_cancelled`] every
+    /// [`CANCEL_CHECK_INTERVAL`] rows, and the render fails with
+    /// [`LuxorError::Cancelled`]. Custom components with loops of their own
+    /// should do the same.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, LuxorError, RenderOptions, Renderable, Text};
+    /// use std::sync::{Arc, atomic::AtomicBool};
+    ///
+    /// let options = RenderOptions::new().with_cancel(Arc::new(AtomicBool::new(true)));
+    /// let result = Text::new("huge\noutput").render(&Console::new(), &options);
+    /// assert!(matches!(result, Err(LuxorError::Cancelled)));
+    /// ```
+    pub fn with_cancel(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(flag);
+        self
+    }
+
+    /// Fail with [`LuxorError::Cancelled`] if the cancel flag is set.
+    pub fn check_cancelled(&self) -> Result<()> {
+        match &self.cancel {
+            Some(flag) if flag.load(Ordering::Relaxed) => Err(LuxorError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    /// Set the number of measure calls in one pass after which containers
+    /// stop measuring their children and estimate instead.
+    ///
+    /// A container measuring children through
+    /// [`RenderOptions::measure_child`] counts the calls, and past the limit
+    /// uses an estimate, the last measurement of the child in the pass if
+    /// there is one, and reports a [`Warning::MeasureBudget`]. Each print
+    /// is one pass, as is each render or measure of a child with options
+    /// that are not in a pass yet.
+    pub fn with_measure_limit(mut self, limit: usize) -> Self {
+        self.measure_limit = limit;
+        self
+    }
+
+    /// Get the number of measure calls made so far in the current pass.
+    pub fn measure_calls(&self) -> usize {
+        self.measure_pass.as_ref().map_or(0, |pass| pass.calls())
+    }
+
+    /// Get these options in a measure pass, starting one if they are not
+    /// in one yet.
+    pub(crate) fn in_measure_pass(&self) -> Cow<'_, RenderOptions> {
+        if self.measure_pass.is_some() {
+            return Cow::Borrowed(self);
+        }
+        let mut options = self.clone();
+        options.measure_pass = Some(Arc::default());
+        Cow::Owned(options)
+    }
+
+    /// Measure `child` of a container, one level deeper, or estimate its
+    /// measurement once the pass has made more than
+    /// [`RenderOptions::measure_limit`] calls.
+    ///
+    /// Containers should measure their children with this rather than
+    /// [`Measurable::measure`], so the calls are counted. Within one
+    /// measure call of a container, a child measured again at the same
+    /// widths gets its first measurement, so measuring each child for a
+    /// minimum and then for a fit takes time linear in the size of the
+    /// tree rather than exponential in its depth. Children are told apart
+    /// by address and type; measure temporaries, which may share an
+    /// address, with [`Measurable::measure`].
+    ///
+    /// Past the limit, the estimate is the last measurement of the child
+    /// in the pass, clamped to the width, or else `estimate()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, Measurement, RenderOptions, Text};
+    ///
+    /// let console = Console::new();
+    /// let options = RenderOptions::new().with_max_width(80).with_measure_limit(1);
+    /// let child = Text::new("child");
+    /// let estimate = || Measurement::span(80);
+    /// let first = options.measure_child(&child, &console, estimate).unwrap();
+    /// assert_eq!(first, Measurement::new(5, 5));
+    /// ```
+    pub fn measure_child<M: Measurable + ?Sized>(
+        &self,
+        child: &M,
+        console: &Console,
+        estimate: impl FnOnce() -> Measurement,
+    ) -> Result<Measurement> {
+        let pass = self.measure_pass.clone().unwrap_or_default();
+        let key = (
+            child as *const M as *const () as usize,
+            std::any::type_name::<M>(),
+        );
+        let max_width = self.get_max_width();
+        let last = pass.lock().get(&key).copied();
+        if let Some(last) = last {
+            let same_call = self.measure_depth > 0 && last.call == self.measure_call;
+            if same_call && last.max_width == max_width && last.min_width == self.min_width {
+                return Ok(last.measurement);
+            }
+        }
+
+        let calls = pass.calls.fetch_add(1, Ordering::Relaxed) + 1;
+        if calls > self.measure_limit {
+            if calls == self.measure_limit + 1 {
+                diagnostics::warn(|| Warning::MeasureBudget {
+                    depth: self.measure_depth,
+                    limit: self.measure_limit,
+                });
+            }
+            return Ok(match last {
+                Some(last) => last.measurement.clamp(0, max_width),
+                None => estimate(),
+            });
+        }
+        let mut nested = self.clone();
+        nested.measure_depth += 1;
+        nested.measure_call = calls;
+        nested.measure_pass = Some(Arc::clone(&pass));
+        let measurement = match &self.profiler {
+            Some(profiler) => {
+                let name = format!("{} (measure)", profile::component_name::<M>());
+                profiler.time(&name, || child.measure(console, &nested))
+            }
+            None => child.measure(console, &nested),
+        }?;
+        let measured = Measured {
+            call: self.measure_call,
+            max_width,
+            min_width: self.min_width,
+            measurement,
+        };
+        pass.lock().insert(key, measured);
+        Ok(measurement)
+    }
+
+    /// Time renders and measures made through
+    /// [`RenderOptions::render_child`] and [`RenderOptions::measure_child`]
+    /// with `profiler`. See the [`profile`] module.
+    pub fn with_profiler(mut self, profiler: Profiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    /// Render `child` of a container, timing it under its
+    /// [`Renderable::component_name`] when profiling.
+    ///
+    /// Containers should render their children with this rather than
+    /// [`Renderable::render`], so the time is put down to the child. The
+    /// child renders in the measure pass of these options, or in a pass
+    /// of its own if they are not in one.
+    pub fn render_child<R: Renderable + ?Sized>(
+        &self,
+        child: &R,
+        console: &Console,
+    ) -> Result<Vec<Segment>> {
+        let options = self.in_measure_pass();
+        match &self.profiler {
+            Some(profiler) => {
+                profiler.time(&child.component_name(), || child.render(console, &options))
+            }
+            None => child.render(console, &options),
+        }
+    }
+
+    /// Render `child` like [`RenderOptions::render_child`], timing it
+    /// under `label` instead of its type, to tell apart children of the
+    /// same type.
+    pub fn render_child_as<R: Renderable + ?Sized>(
+        &self,
+        label: &str,
+        child: &R,
+        console: &Console,
+    ) -> Result<Vec<Segment>> {
+        let options = self.in_measure_pass();
+        match &self.profiler {
+            Some(profiler) => profiler.time(label, || child.render(console, &options)),
+            None => child.render(console, &options),
+        }
+    }
+
+    /// Get the effective maximum width.
+    ///
+    /// The width is resolved in order from an explicit `max_width`, the
+    /// `COLUMNS` environment variable as seen by
+    /// [`Capabilities::probe_once`], the terminal size, and finally
+    /// `fallback_width`. Options in a [`MeasurePass`], such as those of a
+    /// print, detect the width once for the whole pass.
+    pub fn get_max_width(&self) -> usize {
+        self.resolve_width().0
+    }
+
+    /// Get where the effective maximum width comes from.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{RenderOptions, WidthSource};
+    ///
+    /// let options = RenderOptions::new().with_max_width(100);
+    /// assert_eq!(options.width_source(), WidthSource::Explicit);
+    /// ```
+    pub fn width_source(&self) -> WidthSource {
+        self.resolve_width().1
+    }
+
+    fn resolve_width(&self) -> (usize, WidthSource) {
+        if let Some(width) = self.max_width {
+            return (width, WidthSource::Explicit);
+        }
+        let detected = match &self.measure_pass {
+            Some(pass) => *pass.detected_width.get_or_init(detect_width),
+            None => detect_width(),
+        };
+        detected.unwrap_or((self.fallback_width, WidthSource::Fallback))
+    }
+
+    /// Clamp a measured width to the bounds of these options.
+    ///
+    /// The result is at least `min_width` and at most the effective maximum
+    /// width. When the two conflict the maximum wins, so output never
+    /// overflows the available space.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::RenderOptions;
+    ///
+    /// let options = RenderOptions::new().with_max_width(80).with_min_width(20);
+    /// assert_eq!(options.clamp_width(5), 20);
+    /// assert_eq!(options.clamp_width(40), 40);
+    /// assert_eq!(options.clamp_width(100), 80);
+    /// ```
+    pub fn clamp_width(&self, measured: usize) -> usize {
+        measured.max(self.min_width).min(self.get_max_width())
+    }
+
+    /// Check that these options are consistent.
+    ///
+    /// Fails if `min_width` exceeds an explicitly set `max_width`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::RenderOptions;
+    ///
+    /// assert!(RenderOptions::new().with_max_width(10).validate().is_ok());
+    /// assert!(RenderOptions::new().with_max_width(10).with_min_width(20).validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        match self.max_width {
+            Some(max_width) if self.min_width > max_width => Err(LuxorError::measurement(format!(
+                "min_width ({}) exceeds max_width ({})",
+                self.min_width, max_width
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<ConsoleOptions> for RenderOptions {
+    fn from(options: ConsoleOptions) -> Self {
+        options.synced().render
+    }
+}
+
+/// Focus, selection and search, for building interactive views.
+///
+/// A picker or file browser redraws the same component as the user moves
+/// through it. Rather than rebuilding the component with different styles
+/// each time, it sets this state on the [`RenderOptions`] it renders with,
+/// and list-like components emphasize what it names. Components without
+/// rows ignore it.
+///
+/// # Contract
+///
+/// A component that takes part:
+///
+/// - counts its rows from 0 in the order they are drawn, and styles the
+///   rows in [`RenderState::selected`] with
+///   [`RenderState::selection_style`] over their own style;
+/// - highlights the [`RenderState::search`] term in each row with
+///   [`RenderState::highlight`];
+/// - renders its children with the state cleared, so a nested component
+///   does not apply it a second time with its own row numbers.
+///
+/// [`Tree`](crate::Tree) takes part, counting its visible nodes.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, RenderState, Renderable, Tree};
+///
+/// let mut tree = Tree::new("src");
+/// tree.add("lib.rs");
+/// tree.add("main.rs");
+///
+/// let state = RenderState::new().with_selected([2]).with_search("rs");
+/// let options = RenderOptions::new().with_max_width(40).with_state(state);
+/// let segments = tree.render(&Console::new(), &options).unwrap();
+/// let main = segments.iter().find(|s| s.text() == "main.").unwrap();
+/// assert_eq!(main.style().reverse, Some(true));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderState {
+    /// Whether the view has input focus. Selected rows are dimmed while it
+    /// does not.
+    pub focused: bool,
+    /// The rows that are selected.
+    pub selected: Vec<usize>,
+    /// Text to highlight wherever it appears, ignoring case.
+    pub search: Option<String>,
+    /// The style of search matches.
+    pub match_style: Style,
+}
+
+impl RenderState {
+    /// Create a focused state with nothing selected or searched for.
+    pub fn new() -> Self {
+        Self {
+            focused: true,
+            selected: Vec::new(),
+            search: None,
+            match_style: Style::new().bold().underline(),
+        }
+    }
+
+    /// Set whether the view has input focus.
+    pub fn with_focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    /// Set the selected rows.
+    pub fn with_selected(mut self, rows: impl IntoIterator<Item = usize>) -> Self {
+        self.selected = rows.into_iter().collect();
+        self
+    }
+
+    /// Set the text to highlight. An empty string highlights nothing.
+    pub fn with_search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into()).filter(|search| !search.is_empty());
+        self
+    }
+
+    /// Set the style of search matches.
+    pub fn with_match_style(mut self, style: Style) -> Self {
+        self.match_style = style;
+        self
+    }
+
+    /// Check whether the state asks for nothing, so components can skip
+    /// consulting it.
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty() && self.search.is_none()
+    }
+
+    /// Check whether `row` is selected.
+    pub fn is_selected(&self, row: usize) -> bool {
+        self.selected.contains(&row)
+    }
+
+    /// Get the style of a selected row whose own style is `base`: inverted
+    /// with [`Style::on_selection`], and dimmed without focus.
+    pub fn selection_style(&self, base: Style) -> Style {
+        let selected = Style::on_selection(base, Style::new());
+        if self.focused {
+            selected
+        } else {
+            selected.dim()
+        }
+    }
+
+    /// Highlight the search term in `text`, returning how many times it
+    /// was found. See [`Text::highlight_words`].
+    pub fn highlight(&self, text: &mut Text) -> usize {
+        match &self.search {
+            Some(search) => text.highlight_words(&[search], self.match_style.clone(), false),
+            None => 0,
+        }
+    }
+}
+
+impl Default for RenderState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How many rows or lines long render loops produce between checks of
+/// [`RenderOptions::cancel`].
+pub const CANCEL_CHECK_INTERVAL: usize = 256;
+
+/// Install a Ctrl-C handler that sets the returned flag, for
+/// [`RenderOptions::with_cancel`].
+///
+/// The handler replaces the default action of Ctrl-C, so the process no
+/// longer exits on it: the program is expected to check the flag, or to
+/// let a cancelled render return, and exit itself. Each call installs
+/// another handler with a flag of its own.
+///
+/// # Errors
+///
+/// Returns an error if the handler cannot be installed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use luxor::{Console, RenderOptions, ctrlc_cancel_flag};
+///
+/// let console = Console::new();
+/// let options = RenderOptions::new().with_cancel(ctrlc_cancel_flag().unwrap());
+/// let segments = console.render("a very long report", &options);
+/// ```
+#[cfg(feature = "terminal")]
+pub fn ctrlc_cancel_flag() -> Result<Arc<AtomicBool>> {
+    let flag = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register(signal_hook::consts::SIGINT, Arc::clone(&flag))?;
+    Ok(flag)
+}
+
+/// Width used when nothing better is known, matching common terminal defaults.
+pub(crate) const DEFAULT_FALLBACK_WIDTH: usize = 80;
+
+/// Height used when the terminal size is not known.
+pub(crate) const DEFAULT_FALLBACK_HEIGHT: usize = 24;
+
+/// Detect the width from the probed `COLUMNS` or else the terminal size.
+fn detect_width() -> Option<DetectedWidth> {
+    let columns = capabilities::probed_columns();
+    let terminal = || capabilities::terminal_size().map(|(width, _)| width);
+    match resolve_width(None, columns.as_deref(), terminal, 0) {
+        (_, WidthSource::Fallback) => None,
+        detected => Some(detected),
+    }
+}
+
+/// Resolve the render width from each source in priority order.
+///
+/// Unparseable or zero `COLUMNS` values and a zero terminal width are
+/// ignored.
+pub(crate) fn resolve_width(
+    explicit: Option<usize>,
+    columns: Option<&str>,
+    terminal_width: impl FnOnce() -> Option<usize>,
+    fallback: usize,
+) -> (usize, WidthSource) {
+    if let Some(width) = explicit {
+        return (width, WidthSource::Explicit);
+    }
+    if let Some(width) = columns
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|&width| width > 0)
+    {
+        return (width, WidthSource::Environment);
+    }
+    match terminal_width().filter(|&width| width > 0) {
+        Some(width) => (width, WidthSource::Terminal),
+        None => (fallback, WidthSource::Fallback),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Console;
+
+    #[test]
+    fn test_render_options_get_max_width() {
+        let options = RenderOptions::new().with_max_width(100);
+        assert_eq!(options.get_max_width(), 100);
+
+        let options = RenderOptions::new();
+        assert!(options.get_max_width() > 0); // Should use terminal width
+    }
+
+    #[test]
+    fn test_render_options_clamp_width() {
+        let options = RenderOptions::new().with_max_width(80).with_min_width(20);
+        assert_eq!(options.clamp_width(5), 20);
+        assert_eq!(options.clamp_width(50), 50);
+        assert_eq!(options.clamp_width(120), 80);
+
+        // When the bounds conflict the maximum wins
+        let options = RenderOptions::new().with_max_width(10).with_min_width(20);
+        assert_eq!(options.clamp_width(5), 10);
+    }
+
+    #[test]
+    fn test_render_options_validate() {
+        assert!(RenderOptions::new().with_min_width(20).validate().is_ok());
+        assert!(
+            RenderOptions::new()
+                .with_max_width(20)
+                .with_min_width(20)
+                .validate()
+                .is_ok()
+        );
+
+        let options = RenderOptions::new().with_max_width(10).with_min_width(20);
+        assert!(matches!(
+            options.validate(),
+            Err(LuxorError::Measurement { .. })
+        ));
+        assert!(Console::new().render("Hello", &options).is_err());
+    }
+
+    #[test]
+    fn test_cancel_flag_stops_endless_render() {
+        /// Produces rows until it is cancelled.
+        struct Endless;
+
+        impl crate::Renderable for Endless {
+            fn render(
+                &self,
+                _console: &Console,
+                options: &RenderOptions,
+            ) -> Result<Vec<crate::Segment>> {
+                let mut segments = Vec::new();
+                for row in 0.. {
+                    if row % CANCEL_CHECK_INTERVAL == 0 {
+                        options.check_cancelled()?;
+                    }
+                    segments.push(crate::Segment::new(format!("row {}", row), Style::new()));
+                    segments.push(crate::Segment::line());
+                }
+                unreachable!()
+            }
+        }
+
+        let flag = Arc::new(AtomicBool::new(false));
+        let setter = {
+            let flag = Arc::clone(&flag);
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                flag.store(true, Ordering::Relaxed);
+            })
+        };
+        let started = std::time::Instant::now();
+        let options = RenderOptions::new().with_cancel(flag);
+        let result = Console::new().render(Endless, &options);
+        setter.join().unwrap();
+        assert!(matches!(result, Err(LuxorError::Cancelled)));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+        // Built-in loops check the flag too
+        let mut tree = crate::Tree::new("root");
+        for index in 0..1000 {
+            tree.add(format!("leaf {}", index));
+        }
+        assert!(matches!(
+            Console::new().render(tree, &options),
+            Err(LuxorError::Cancelled)
+        ));
+        assert!(
+            Console::new()
+                .render(Text::new("fine"), &RenderOptions::new())
+                .is_ok()
+        );
+    }
+
+    /// A leaf counting how often it is measured.
+    #[derive(Default)]
+    struct CountingLeaf(AtomicUsize);
+
+    impl crate::Renderable for CountingLeaf {
+        fn render(
+            &self,
+            _console: &Console,
+            _options: &RenderOptions,
+        ) -> Result<Vec<crate::Segment>> {
+            Ok(vec![crate::Segment::new("leaf", Style::new())])
+        }
+    }
+
+    impl Measurable for CountingLeaf {
+        fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Ok(Measurement::new(2, 4))
+        }
+    }
+
+    /// A container measuring its child twice, for its minimum and then for
+    /// a fit.
+    struct Nested(Box<dyn Measurable>);
+
+    impl Measurable for Nested {
+        fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+            let estimate = || Measurement::span(options.get_max_width());
+            let minimum = options.measure_child(&*self.0, console, estimate)?;
+            let fit = options.measure_child(&*self.0, console, estimate)?;
+            Ok(Measurement::new(
+                minimum.minimum() + 2,
+                fit.maximum().max(minimum.minimum()) + 2,
+            ))
+        }
+    }
+
+    /// A stack of `depth` containers around a counting leaf.
+    fn nested(depth: usize, leaf: Arc<CountingLeaf>) -> Nested {
+        /// The shared leaf, measured through its counter.
+        struct Shared(Arc<CountingLeaf>);
+
+        impl Measurable for Shared {
+            fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+                self.0.measure(console, options)
+            }
+        }
+
+        let mut node = Nested(Box::new(Shared(leaf)));
+        for _ in 1..depth {
+            node = Nested(Box::new(node));
+        }
+        node
+    }
+
+    /// Options for `width` cells in a measure pass of their own.
+    fn in_pass(width: usize) -> RenderOptions {
+        let mut options = RenderOptions::new().with_max_width(width);
+        options.measure_pass = Some(Arc::new(MeasurePass::new()));
+        options
+    }
+
+    #[test]
+    fn test_measure_keeps_deep_nesting_linear() {
+        let console = Console::new();
+        let leaf = Arc::new(CountingLeaf::default());
+        let tree = nested(12, Arc::clone(&leaf));
+        let nodes = 13;
+
+        // Measured again in the same call, a child is not measured twice,
+        // so the leaf 12 deep is not measured 2^12 times
+        let options = in_pass(80);
+        let measurement = tree.measure(&console, &options).unwrap();
+        assert_eq!(measurement, Measurement::new(26, 28));
+        assert_eq!(leaf.0.load(Ordering::Relaxed), 2);
+        assert!(options.measure_calls() <= 2 * nodes);
+        assert!(options.measure_calls() < DEFAULT_MEASURE_LIMIT);
+    }
+
+    #[test]
+    fn test_measure_limit_estimates_from_last_measurement() {
+        let console = Console::new();
+        let leaf = Arc::new(CountingLeaf::default());
+        let tree = nested(12, Arc::clone(&leaf));
+
+        // The second walk down the tree runs out of calls, and estimates
+        // from the measurements of the first
+        let warnings = diagnostics::collect_warnings(|| {
+            let options = in_pass(80).with_measure_limit(20);
+            let measurement = tree.measure(&console, &options).unwrap();
+            assert_eq!(measurement, Measurement::new(26, 28));
+            assert_eq!(leaf.0.load(Ordering::Relaxed), 1);
+        });
+        assert!(warnings.contains(&Warning::MeasureBudget {
+            depth: 8,
+            limit: 20
+        }));
+    }
+
+    #[test]
+    fn test_print_is_one_measure_pass() {
+        use crate::{ConsoleOptions, Group, Panel};
+
+        // Each level measures what is below it, then renders it
+        let mut tree = Group::new().with_fit(true).with_item(Text::new("leaf"));
+        for _ in 0..12 {
+            tree = Group::new().with_fit(true).with_item(Panel::fit(tree));
+        }
+        let console = Console::with_options(
+            ConsoleOptions::new()
+                .with_max_width(80)
+                .with_color(false)
+                .with_measure_limit(15),
+        );
+        let warnings = diagnostics::collect_warnings(|| {
+            console.capture(|console| console.print(tree)).unwrap();
+        });
+        let reported = warnings
+            .iter()
+            .filter(|warning| matches!(warning, Warning::MeasureBudget { limit: 15, .. }))
+            .count();
+        assert_eq!(reported, 1);
+    }
+
+    #[test]
+    fn test_measure_limit_leaves_normal_depths_alone() {
+        use crate::Renderable;
+
+        let console = Console::new();
+        let leaf = Arc::new(CountingLeaf::default());
+        let tree = nested(3, Arc::clone(&leaf));
+        let options = RenderOptions::new().with_max_width(80);
+        let measurement = tree.measure(&console, &options).unwrap();
+        assert_eq!(measurement, Measurement::new(8, 10));
+        assert_eq!(leaf.0.load(Ordering::Relaxed), 2);
+
+        let text = Text::new("cached text");
+        let cached = crate::Cached::new(text.clone());
+        let exact = cached.measure(&console, &options).unwrap();
+        assert_eq!(exact, text.measure(&console, &options).unwrap());
+        cached.render(&console, &options).unwrap();
+        let over = RenderOptions::new()
+            .with_max_width(80)
+            .with_measure_limit(0);
+        assert_eq!(
+            cached.measure(&console, &over).unwrap(),
+            Measurement::fixed(11)
+        );
+    }
+
+    #[test]
+    fn test_pass_detects_width_once() {
+        let pass = Arc::new(MeasurePass::new());
+        pass.detected_width
+            .set(Some((33, WidthSource::Environment)))
+            .unwrap();
+        let mut options = RenderOptions::new();
+        options.measure_pass = Some(Arc::clone(&pass));
+        assert_eq!(options.get_max_width(), 33);
+        assert_eq!(options.width_source(), WidthSource::Environment);
+        // Nested options share the pass, and an explicit width still wins
+        let nested = options.clone().with_fallback_width(10);
+        assert_eq!(nested.get_max_width(), 33);
+        assert_eq!(options.with_max_width(5).get_max_width(), 5);
+
+        // Nothing detected falls back, per options
+        let pass = Arc::new(MeasurePass::new());
+        pass.detected_width.set(None).unwrap();
+        let mut options = RenderOptions::new().with_fallback_width(50);
+        options.measure_pass = Some(pass);
+        assert_eq!(options.get_max_width(), 50);
+        assert_eq!(options.width_source(), WidthSource::Fallback);
+    }
+
+    #[test]
+    fn test_resolve_width_chain() {
+        let no_terminal = || None;
+
+        // Explicit width beats everything
+        assert_eq!(
+            resolve_width(Some(50), Some("120"), || Some(100), 80),
+            (50, WidthSource::Explicit)
+        );
+        // COLUMNS beats the terminal
+        assert_eq!(
+            resolve_width(None, Some(" 120 "), || Some(100), 80),
+            (120, WidthSource::Environment)
+        );
+        // Terminal size when COLUMNS is absent or garbage
+        assert_eq!(
+            resolve_width(None, None, || Some(100), 80),
+            (100, WidthSource::Terminal)
+        );
+        assert_eq!(
+            resolve_width(None, Some("wide"), || Some(100), 80),
+            (100, WidthSource::Terminal)
+        );
+        assert_eq!(
+            resolve_width(None, Some("0"), || Some(100), 80),
+            (100, WidthSource::Terminal)
+        );
+        // Configurable fallback when nothing is known
+        assert_eq!(
+            resolve_width(None, None, no_terminal, 132),
+            (132, WidthSource::Fallback)
+        );
+        assert_eq!(
+            resolve_width(None, Some("-5"), || Some(0), 80),
+            (80, WidthSource::Fallback)
+        );
+    }
+}
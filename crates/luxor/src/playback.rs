@@ -0,0 +1,558 @@
+//! Playback - recording console frames and playing them back.
+//!
+//! A display that redraws itself is gone once it has been redrawn, which
+//! makes a glitch seen only on someone else's terminal hard to chase. A
+//! [`FrameRecorder`] is a [`FrameSink`] that appends every frame to a
+//! file, with the time and the display size, and can pass the frames on
+//! to another sink so the display keeps working. A [`FramePlayer`] loads
+//! the recording and writes the frames to a console again, at the
+//! original pace or one at a time, and the loaded frames double as
+//! fixtures for tests.
+//!
+//! # Format
+//!
+//! Version 1 of the format is a header followed by one record per frame,
+//! with every number little-endian:
+//!
+//! | Field | Size | Contents |
+//! |-------|------|----------|
+//! | magic | 4 | `LXRF` |
+//! | version | 2 | `1` |
+//!
+//! Each record is:
+//!
+//! | Field | Size | Contents |
+//! |-------|------|----------|
+//! | flags | 1 | bit 0: a delta frame; bit 1: the size is known |
+//! | time | 8 | microseconds since recording started |
+//! | width, height | 4 + 4 | the display size, zero when unknown |
+//! | prefix, suffix | 4 + 4 | delta frames only: bytes kept from the start and end of the previous frame |
+//! | length | 4 | the number of bytes that follow |
+//! | bytes | length | the frame, or for a delta frame what replaces the rest of the previous one |
+//!
+//! A full frame, a keyframe, is written first and then every
+//! [`FrameRecorder::with_keyframe_interval`] frames, so a damaged file
+//! loses at most that many frames.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use luxor::{Console, ConsoleOptions, playback::{FramePlayer, FrameRecorder}};
+//!
+//! let console = Console::with_options(ConsoleOptions::new().with_color(false));
+//! console.set_sink(FrameRecorder::new(std::io::sink()).unwrap());
+//! console.println("recorded").unwrap();
+//!
+//! // With a real file, load what was written to it:
+//! let mut file = Vec::new();
+//! let mut recorder = FrameRecorder::new(&mut file).unwrap();
+//! luxor::FrameSink::frame(&mut recorder, b"hello");
+//! drop(recorder);
+//! let player = FramePlayer::load(file.as_slice()).unwrap();
+//! assert_eq!(player.frames()[0].bytes, b"hello");
+//! ```
+
+use crate::{
+    Console, LuxorError, Result,
+    clock::{SharedClock, SystemClock, Timer},
+    sink::{BoxedSink, FrameSink, FrameSize},
+};
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    time::Duration,
+};
+
+/// The first bytes of every recording.
+const MAGIC: &[u8; 4] = b"LXRF";
+/// The version of the format written.
+pub const FORMAT_VERSION: u16 = 1;
+/// The keyframe interval of a new recorder.
+const DEFAULT_KEYFRAME_INTERVAL: usize = 30;
+
+/// Flag of a record holding a delta frame.
+const FLAG_DELTA: u8 = 1;
+/// Flag of a record with a known display size.
+const FLAG_SIZE: u8 = 2;
+
+/// A frame read back from a recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedFrame {
+    /// The time since recording started.
+    pub at: Duration,
+    /// The display size when the frame was written, if known.
+    pub size: Option<FrameSize>,
+    /// The frame, escape codes included.
+    pub bytes: Vec<u8>,
+}
+
+/// A sink appending frames to a recording.
+///
+/// Write errors are kept as [`WriterSink`](crate::sink::WriterSink) keeps
+/// them: the first one stops the recording until it is taken with
+/// [`FrameRecorder::take_error`]. Frames are still passed on to the inner
+/// sink.
+pub struct FrameRecorder<W> {
+    writer: W,
+    inner: Option<BoxedSink>,
+    timer: Timer,
+    size: Option<FrameSize>,
+    every: usize,
+    keyframe_interval: usize,
+    seen: usize,
+    recorded: usize,
+    previous: Vec<u8>,
+    error: Option<io::Error>,
+}
+
+impl<W: Write + Send> FrameRecorder<W> {
+    /// Start a recording in `writer`, writing its header.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header cannot be written.
+    pub fn new(mut writer: W) -> Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            inner: None,
+            timer: Timer::new(SystemClock::shared()),
+            size: None,
+            every: 1,
+            keyframe_interval: DEFAULT_KEYFRAME_INTERVAL,
+            seen: 0,
+            recorded: 0,
+            previous: Vec::new(),
+            error: None,
+        })
+    }
+
+    /// Pass every frame on to `sink` as well, which also provides the size.
+    pub fn with_inner(mut self, sink: impl FrameSink + 'static) -> Self {
+        self.inner = Some(Box::new(sink));
+        self
+    }
+
+    /// Time the frames with `clock`, from now.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.timer = Timer::new(clock);
+        self
+    }
+
+    /// Record only every `n`th frame, starting with the first. Zero is
+    /// taken as one.
+    pub fn with_every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// Write a full frame every `n` recorded frames and deltas from the
+    /// previous frame in between, thirty by default. One or zero records
+    /// only full frames.
+    pub fn with_keyframe_interval(mut self, n: usize) -> Self {
+        self.keyframe_interval = n.max(1);
+        self
+    }
+
+    /// Take the write error that stopped the recording, if any, so it
+    /// records again.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Flush the recording and get the writer back.
+    pub fn into_inner(mut self) -> W {
+        // Nothing left to report the error to
+        let _ = self.writer.flush();
+        self.writer
+    }
+
+    fn record(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let size = self.size();
+        let mut flags = 0;
+        if size.is_some() {
+            flags |= FLAG_SIZE;
+        }
+        let keyframe = self.recorded % self.keyframe_interval == 0;
+        if !keyframe {
+            flags |= FLAG_DELTA;
+        }
+
+        let micros = u64::try_from(self.timer.elapsed().as_micros()).unwrap_or(u64::MAX);
+        let (width, height) = size.unwrap_or((0, 0));
+        let mut record = vec![flags];
+        record.extend_from_slice(&micros.to_le_bytes());
+        record.extend_from_slice(&to_u32(width).to_le_bytes());
+        record.extend_from_slice(&to_u32(height).to_le_bytes());
+        let body = if keyframe {
+            bytes
+        } else {
+            let (prefix, suffix) = shared_ends(&self.previous, bytes);
+            record.extend_from_slice(&to_u32(prefix).to_le_bytes());
+            record.extend_from_slice(&to_u32(suffix).to_le_bytes());
+            &bytes[prefix..bytes.len() - suffix]
+        };
+        record.extend_from_slice(&to_u32(body.len()).to_le_bytes());
+        record.extend_from_slice(body);
+        self.writer.write_all(&record)?;
+        self.writer.flush()?;
+
+        self.recorded += 1;
+        self.previous = bytes.to_vec();
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> FrameSink for FrameRecorder<W> {
+    fn frame(&mut self, bytes: &[u8]) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.frame(bytes);
+        }
+        let seen = self.seen;
+        self.seen += 1;
+        if self.error.is_some() || seen % self.every != 0 {
+            return;
+        }
+        self.error = self.record(bytes).err();
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.resize(width, height);
+        }
+        self.size = Some((width, height));
+    }
+
+    fn size(&self) -> Option<FrameSize> {
+        self.inner
+            .as_ref()
+            .and_then(|inner| inner.size())
+            .or(self.size)
+    }
+}
+
+impl<W> fmt::Debug for FrameRecorder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameRecorder")
+            .field("size", &self.size)
+            .field("every", &self.every)
+            .field("keyframe_interval", &self.keyframe_interval)
+            .field("recorded", &self.recorded)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+/// A loaded recording, for playing back or inspecting frame by frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FramePlayer {
+    frames: Vec<RecordedFrame>,
+}
+
+impl FramePlayer {
+    /// Load a recording written by a [`FrameRecorder`].
+    ///
+    /// A recording cut short in the middle of a record, as happens when
+    /// the program being recorded is killed, loads up to the last complete
+    /// frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LuxorError::Serialization`] if `reader` does not hold a
+    /// recording of a known version or a delta does not fit its frame, and
+    /// an I/O error if it cannot be read.
+    pub fn load(mut reader: impl Read) -> Result<Self> {
+        let mut header = [0; 6];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(LuxorError::serialization("not a luxor frame recording"));
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != FORMAT_VERSION {
+            return Err(LuxorError::serialization(format!(
+                "unsupported frame recording version {version}"
+            )));
+        }
+
+        let mut frames: Vec<RecordedFrame> = Vec::new();
+        while let Some(frame) = read_record(&mut reader, frames.last())? {
+            frames.push(frame);
+        }
+        Ok(Self { frames })
+    }
+
+    /// Get the frames, in the order they were recorded.
+    pub fn frames(&self) -> &[RecordedFrame] {
+        &self.frames
+    }
+
+    /// Get the time from the start of the recording to its last frame.
+    pub fn duration(&self) -> Duration {
+        self.frames.last().map_or(Duration::ZERO, |frame| frame.at)
+    }
+
+    /// Write the frame `index` to `console`, to step through the
+    /// recording. Out of range indexes write nothing.
+    pub fn play_frame(&self, console: &Console, index: usize) -> Result<()> {
+        match self.frames.get(index) {
+            Some(frame) => console.write_output(&String::from_utf8_lossy(&frame.bytes)),
+            None => Ok(()),
+        }
+    }
+
+    /// Write every frame to `console` at its recorded time, divided by
+    /// `speed`: 2.0 plays twice as fast. An infinite speed writes the
+    /// frames back to back, and a speed that is not positive is taken as
+    /// real time.
+    pub fn play(&self, console: &Console, speed: f64) -> Result<()> {
+        let speed = if speed > 0.0 { speed } else { 1.0 };
+        let mut previous = Duration::ZERO;
+        for index in 0..self.frames.len() {
+            let at = self.frames[index].at;
+            if speed.is_finite() {
+                let gap = at.saturating_sub(previous).as_secs_f64() / speed;
+                std::thread::sleep(Duration::from_secs_f64(gap));
+            }
+            previous = at;
+            self.play_frame(console, index)?;
+        }
+        Ok(())
+    }
+}
+
+/// Read the record after `previous`, or `None` at the end of the
+/// recording.
+fn read_record(
+    reader: &mut impl Read,
+    previous: Option<&RecordedFrame>,
+) -> Result<Option<RecordedFrame>> {
+    let mut fixed = [0; 17];
+    if !read_or_end(reader, &mut fixed)? {
+        return Ok(None);
+    }
+    let flags = fixed[0];
+    let micros = u64::from_le_bytes(fixed[1..9].try_into().expect("eight bytes"));
+    let width = read_u32(&fixed[9..13]);
+    let height = read_u32(&fixed[13..17]);
+    let size = (flags & FLAG_SIZE != 0).then_some((width, height));
+
+    let ends = if flags & FLAG_DELTA != 0 {
+        let mut ends = [0; 8];
+        if !read_or_end(reader, &mut ends)? {
+            return Ok(None);
+        }
+        Some((read_u32(&ends[..4]), read_u32(&ends[4..])))
+    } else {
+        None
+    };
+    let mut length = [0; 4];
+    if !read_or_end(reader, &mut length)? {
+        return Ok(None);
+    }
+    let length = read_u32(&length);
+    let mut body = Vec::new();
+    reader.take(length as u64).read_to_end(&mut body)?;
+    if body.len() < length {
+        return Ok(None);
+    }
+
+    let bytes = match ends {
+        None => body,
+        Some((prefix, suffix)) => {
+            let previous = previous.map_or(&[][..], |frame| &frame.bytes);
+            if prefix + suffix > previous.len() {
+                return Err(LuxorError::serialization(
+                    "delta frame does not fit the frame before it",
+                ));
+            }
+            let mut bytes = previous[..prefix].to_vec();
+            bytes.extend_from_slice(&body);
+            bytes.extend_from_slice(&previous[previous.len() - suffix..]);
+            bytes
+        }
+    };
+    Ok(Some(RecordedFrame {
+        at: Duration::from_micros(micros),
+        size,
+        bytes,
+    }))
+}
+
+/// Fill `buffer`, or get `false` if the input ends first.
+fn read_or_end(reader: &mut impl Read, buffer: &mut [u8]) -> Result<bool> {
+    match reader.read_exact(buffer) {
+        Ok(()) => Ok(true),
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> usize {
+    u32::from_le_bytes(bytes.try_into().expect("four bytes")) as usize
+}
+
+fn to_u32(value: usize) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+/// Get the lengths of the common start and end of `previous` and `next`,
+/// not overlapping in either.
+fn shared_ends(previous: &[u8], next: &[u8]) -> (usize, usize) {
+    let prefix = previous
+        .iter()
+        .zip(next)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let room = previous.len().min(next.len()) - prefix;
+    let suffix = previous
+        .iter()
+        .rev()
+        .zip(next.iter().rev())
+        .take(room)
+        .take_while(|(a, b)| a == b)
+        .count();
+    (prefix, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConsoleOptions, ManualClock, Text};
+    use std::sync::{Arc, Mutex};
+
+    type Buffer = Arc<Mutex<Vec<u8>>>;
+
+    /// A writer whose bytes stay readable after it is handed to a console.
+    #[derive(Debug, Clone, Default)]
+    struct SharedBuffer(Buffer);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn console() -> Console {
+        Console::with_options(ConsoleOptions::new().with_color(false))
+    }
+
+    #[test]
+    fn test_scripted_session_round_trips() {
+        let clock = ManualClock::new();
+        let buffer = SharedBuffer::default();
+        let recorder = FrameRecorder::new(buffer.clone())
+            .unwrap()
+            .with_clock(clock.shared())
+            .with_keyframe_interval(2);
+        let mut console = console();
+        console.set_sink(recorder);
+        console.resize(20, 4);
+
+        let steps = [
+            "building 1/3",
+            "building 2/3",
+            "building 3/3",
+            "linking",
+            "done",
+        ];
+        for (index, step) in steps.iter().enumerate() {
+            clock.advance(Duration::from_millis(100 * index as u64));
+            console.println(Text::new(step)).unwrap();
+        }
+
+        let player = FramePlayer::load(buffer.0.lock().unwrap().as_slice()).unwrap();
+        let frames = player.frames();
+        assert_eq!(frames.len(), 5);
+        let times: Vec<u128> = frames.iter().map(|frame| frame.at.as_millis()).collect();
+        assert_eq!(times, [0, 100, 300, 600, 1000]);
+        for (frame, step) in frames.iter().zip(steps) {
+            assert_eq!(frame.bytes, format!("{step}\n").as_bytes());
+            assert_eq!(frame.size, Some((20, 4)));
+        }
+        assert_eq!(player.duration(), Duration::from_secs(1));
+
+        let replay = console
+            .capture(|console| player.play(console, f64::INFINITY))
+            .unwrap();
+        assert_eq!(
+            replay,
+            "building 1/3\nbuilding 2/3\nbuilding 3/3\nlinking\ndone\n"
+        );
+        let step = console
+            .capture(|console| player.play_frame(console, 3))
+            .unwrap();
+        assert_eq!(step, "linking\n");
+    }
+
+    #[test]
+    fn test_every_nth_frame_and_deltas() {
+        let mut recorder = FrameRecorder::new(Vec::new())
+            .unwrap()
+            .with_every(2)
+            .with_keyframe_interval(3);
+        let frames: Vec<String> = (0..9)
+            .map(|n| format!("[{:<8}] compiling crates, {n} of 8 done", "#".repeat(n)))
+            .collect();
+        for frame in &frames {
+            recorder.frame(frame.as_bytes());
+        }
+        assert!(recorder.take_error().is_none());
+        let file = recorder.into_inner();
+        let full: usize = frames
+            .iter()
+            .step_by(2)
+            .map(|frame| frame.len() + 17 + 4)
+            .sum();
+        assert!(
+            file.len() < full,
+            "deltas should be smaller than full frames"
+        );
+
+        let player = FramePlayer::load(file.as_slice()).unwrap();
+        let loaded: Vec<String> = player
+            .frames()
+            .iter()
+            .map(|frame| String::from_utf8_lossy(&frame.bytes).into_owned())
+            .collect();
+        let expected: Vec<String> = frames.iter().step_by(2).cloned().collect();
+        assert_eq!(loaded, expected);
+        assert_eq!(player.frames()[0].size, None);
+    }
+
+    #[test]
+    fn test_load_rejects_and_truncates() {
+        assert!(matches!(
+            FramePlayer::load(&b"GIF89a"[..]),
+            Err(LuxorError::Serialization { .. })
+        ));
+        let mut future = MAGIC.to_vec();
+        future.extend_from_slice(&2u16.to_le_bytes());
+        assert!(FramePlayer::load(future.as_slice()).is_err());
+
+        let mut recorder = FrameRecorder::new(Vec::new()).unwrap();
+        recorder.frame(b"first");
+        recorder.frame(b"second");
+        let mut file = recorder.into_inner();
+        file.truncate(file.len() - 2);
+        let player = FramePlayer::load(file.as_slice()).unwrap();
+        assert_eq!(player.frames().len(), 1);
+    }
+
+    #[test]
+    fn test_recorder_passes_frames_on() {
+        let (inner, received) = crate::sink::ChannelSink::new();
+        let mut recorder = FrameRecorder::new(io::sink()).unwrap().with_inner(inner);
+        recorder.resize(30, 2);
+        recorder.frame(b"shown");
+        assert_eq!(received.recv().unwrap(), b"shown");
+        assert_eq!(recorder.size(), Some((30, 2)));
+        assert_eq!(shared_ends(b"abcXdef", b"abcYYdef"), (3, 3));
+        assert_eq!(shared_ends(b"aaa", b"aaaa"), (3, 0));
+    }
+}
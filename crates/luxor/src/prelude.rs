@@ -0,0 +1,49 @@
+//! Commonly used types and traits, importable in one line.
+//!
+//! ```rust
+//! use luxor::prelude::*;
+//!
+//! let console = Console::new();
+//! let text = Text::new("Hello").with_style(Style::new().bold().with_color(Color::rgb(255, 0, 0)));
+//! let segments = text.render(&console, &console.get_render_options()).unwrap();
+//! assert_eq!(segments[0].text(), "Hello");
+//! ```
+//!
+//! It includes the types builders take, so a component can be configured
+//! without further imports:
+//!
+//! ```rust
+//! use luxor::prelude::*;
+//!
+//! let panel = Panel::fit(Text::new("ok"))
+//!     .with_box(BorderStyle::Ascii)
+//!     .with_title("t")
+//!     .with_title_align(Align::Left)
+//!     .with_padding(Padding::new(0, 1, 0, 1));
+//! let options = RenderOptions::new().with_max_width(20);
+//! let segments = panel.render(&Console::new(), &options).unwrap();
+//! let plain: String = segments.iter().map(|s| s.text()).collect();
+//! assert_eq!(plain, "+- t +\n| ok |\n+----+");
+//! ```
+//!
+//! # Builder conventions
+//!
+//! Builders that take a value are named `with_*` (`Text::with_style`,
+//! `Style::with_color`, `RenderOptions::with_max_width`). Builders that switch
+//! a boolean attribute on are bare verbs (`Style::bold`, `Style::italic`), with
+//! `*_off` counterparts to switch them off explicitly.
+
+pub use crate::{
+    box_drawing::BorderStyle,
+    color::{Color, ColorSystem, StandardColor},
+    components::*,
+    console::{Console, ConsoleOptions},
+    error::{LuxorError, Result},
+    layout::{Align, Padding},
+    measure::Measurement,
+    options::{RenderOptions, WidthSource},
+    protocol::{Measurable, Renderable},
+    segment::{ControlCode, Segment, Segments},
+    style::Style,
+    text::{Justify, Text},
+};
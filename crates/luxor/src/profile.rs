@@ -0,0 +1,395 @@
+//! Profile - which components a slow frame spends its time in.
+//!
+//! A [`Profiler`] set with [`ConsoleOptions::with_profiling`] or
+//! [`RenderOptions::with_profiler`] times every render and measure that
+//! goes through [`RenderOptions::render_child`] and
+//! [`RenderOptions::measure_child`]: the top-level renderable of each
+//! print, and the children of the built-in containers. Times are kept per
+//! component type, or per label given with
+//! [`RenderOptions::render_child_as`], and exclude the time spent in
+//! children timed the same way, so a slow leaf shows up as itself rather
+//! than in every container above it.
+//!
+//! Without a profiler, a hook costs one check of an `Option`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use luxor::{Console, ConsoleOptions, Text};
+//!
+//! let console = Console::with_options(ConsoleOptions::new().with_profiling(true));
+//! console.capture(|console| console.print(Text::new("frame"))).unwrap();
+//! let profile = console.render_profile().unwrap();
+//! assert_eq!(profile.entries()[0].component, "Text");
+//! assert_eq!(profile.entries()[0].calls, 1);
+//! ```
+//!
+//! [`ConsoleOptions::with_profiling`]: crate::ConsoleOptions::with_profiling
+//! [`RenderOptions::with_profiler`]: crate::RenderOptions::with_profiler
+//! [`RenderOptions::render_child`]: crate::RenderOptions::render_child
+//! [`RenderOptions::measure_child`]: crate::RenderOptions::measure_child
+//! [`RenderOptions::render_child_as`]: crate::RenderOptions::render_child_as
+
+use crate::{
+    Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style,
+    render_utils,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    /// Time spent in timed children of each timed call in progress on this
+    /// thread, innermost last.
+    static CHILD_TIME: RefCell<Vec<Duration>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The time and calls of one component in a [`RenderProfile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileEntry {
+    /// The component's type name without module paths, or its label.
+    pub component: String,
+    /// How often it was rendered or measured.
+    pub calls: usize,
+    /// The time spent in it, not counting timed children.
+    pub total: Duration,
+    /// The longest of the calls.
+    pub max: Duration,
+}
+
+/// Entries by component name.
+type Entries = HashMap<String, ProfileEntry>;
+
+/// A collector of render timings, shared by the options of every render
+/// it times.
+#[derive(Clone, Default)]
+pub struct Profiler {
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl Profiler {
+    /// Create a profiler with nothing recorded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Discard what has been recorded, to start a new frame.
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+
+    /// Get what has been recorded so far.
+    pub fn snapshot(&self) -> RenderProfile {
+        let mut entries: Vec<ProfileEntry> = self.lock().values().cloned().collect();
+        entries.sort_by(|a, b| {
+            b.total
+                .cmp(&a.total)
+                .then_with(|| a.component.cmp(&b.component))
+        });
+        RenderProfile { entries }
+    }
+
+    /// Run `call`, recording its time under `component`.
+    pub(crate) fn time<T>(&self, component: &str, call: impl FnOnce() -> T) -> T {
+        CHILD_TIME.with(|stack| stack.borrow_mut().push(Duration::ZERO));
+        let started = Instant::now();
+        let result = call();
+        let elapsed = started.elapsed();
+        let children = CHILD_TIME.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            let children = stack.pop().unwrap_or_default();
+            if let Some(parent) = stack.last_mut() {
+                *parent += elapsed;
+            }
+            children
+        });
+
+        let own = elapsed.saturating_sub(children);
+        let mut entries = self.lock();
+        let entry = entries
+            .entry(component.to_string())
+            .or_insert_with(|| ProfileEntry {
+                component: component.to_string(),
+                calls: 0,
+                total: Duration::ZERO,
+                max: Duration::ZERO,
+            });
+        entry.calls += 1;
+        entry.total += own;
+        entry.max = entry.max.max(own);
+        result
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl fmt::Debug for Profiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profiler")
+            .field("components", &self.lock().len())
+            .finish()
+    }
+}
+
+/// The timings of one frame, slowest component first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderProfile {
+    entries: Vec<ProfileEntry>,
+}
+
+impl RenderProfile {
+    /// Get the entries, by total time from the slowest.
+    pub fn entries(&self) -> &[ProfileEntry] {
+        &self.entries
+    }
+
+    /// Get the entry of `component`, if it was timed.
+    pub fn get(&self, component: &str) -> Option<&ProfileEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.component == component)
+    }
+
+    /// Check whether nothing was timed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get a renderable table of the entries: component, calls, total and
+    /// maximum time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, ConsoleOptions, Text};
+    ///
+    /// let console = Console::with_options(ConsoleOptions::new().with_profiling(true));
+    /// console.capture(|console| console.print(Text::new("frame"))).unwrap();
+    /// let report = console.render_profile().unwrap().report();
+    /// let table = console.capture(|console| console.print(report)).unwrap();
+    /// assert!(table.contains("component"));
+    /// ```
+    pub fn report(&self) -> ProfileReport {
+        ProfileReport {
+            profile: self.clone(),
+        }
+    }
+}
+
+/// A [`RenderProfile`] drawn as a table. See [`RenderProfile::report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileReport {
+    profile: RenderProfile,
+}
+
+impl ProfileReport {
+    /// Get the rows of the table as text, the header first, with the
+    /// numbers right-aligned.
+    fn rows(&self) -> Vec<String> {
+        let mut cells = vec![[
+            "component".to_string(),
+            "calls".to_string(),
+            "total".to_string(),
+            "max".to_string(),
+        ]];
+        cells.extend(self.profile.entries.iter().map(|entry| {
+            [
+                entry.component.clone(),
+                entry.calls.to_string(),
+                format_duration(entry.total),
+                format_duration(entry.max),
+            ]
+        }));
+
+        let mut widths = [0; 4];
+        for row in &cells {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(render_utils::visible_width(cell));
+            }
+        }
+        cells
+            .iter()
+            .map(|[component, calls, total, max]| {
+                let padding = widths[0] - render_utils::visible_width(component);
+                format!(
+                    "{component}{:padding$}  {calls:>calls_width$}  {total:>total_width$}  {max:>max_width$}",
+                    "",
+                    calls_width = widths[1],
+                    total_width = widths[2],
+                    max_width = widths[3],
+                )
+            })
+            .collect()
+    }
+}
+
+impl Renderable for ProfileReport {
+    fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let width = options.get_max_width();
+        let mut segments = Vec::new();
+        for (index, row) in self.rows().into_iter().enumerate() {
+            if index > 0 {
+                segments.push(Segment::line());
+            }
+            let style = if index == 0 {
+                Style::new().bold()
+            } else {
+                Style::new()
+            };
+            let row_width = render_utils::visible_width(&row).min(width);
+            segments.extend(render_utils::adjust_line_length(
+                vec![Segment::new(row, style)],
+                row_width,
+                Style::new(),
+            ));
+        }
+        Ok(segments)
+    }
+}
+
+impl Measurable for ProfileReport {
+    fn measure(&self, _console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        let widest = self
+            .rows()
+            .iter()
+            .map(|row| render_utils::visible_width(row))
+            .max()
+            .unwrap_or(0);
+        Ok(Measurement::fixed(widest.min(options.get_max_width())))
+    }
+}
+
+/// Format a duration in milliseconds with two decimals.
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Get the name of `T` without module paths, as in `Cached<Text>`.
+pub(crate) fn component_name<T: ?Sized>() -> String {
+    let mut name = String::new();
+    let mut token = String::new();
+    for ch in std::any::type_name::<T>().chars() {
+        if ch.is_alphanumeric() || ch == '_' || ch == ':' {
+            token.push(ch);
+        } else {
+            name.push_str(token.rsplit("::").next().unwrap_or_default());
+            token.clear();
+            name.push(ch);
+        }
+    }
+    name.push_str(token.rsplit("::").next().unwrap_or_default());
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConsoleOptions, Text, components::Cached};
+
+    /// A leaf that takes a while to render.
+    struct Slow;
+
+    impl Renderable for Slow {
+        fn render(&self, _console: &Console, _options: &RenderOptions) -> Result<Vec<Segment>> {
+            std::thread::sleep(Duration::from_millis(30));
+            Ok(vec![Segment::new("slow", Style::new())])
+        }
+    }
+
+    type Child = Box<dyn Renderable>;
+
+    /// A container rendering its children one per line.
+    struct Stack(Vec<Child>);
+
+    impl Renderable for Stack {
+        fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+            let mut segments = Vec::new();
+            for (index, child) in self.0.iter().enumerate() {
+                if index > 0 {
+                    segments.push(Segment::line());
+                }
+                segments.extend(options.render_child(child.as_ref(), console)?);
+            }
+            Ok(segments)
+        }
+    }
+
+    fn scene() -> Stack {
+        Stack(vec![
+            Box::new(Text::new("title")),
+            Box::new(Stack(vec![
+                Box::new(Cached::new(Text::new("cached"))),
+                Box::new(Text::new("status")),
+            ])),
+            Box::new(Slow),
+            Box::new(Text::new("footer")),
+        ])
+    }
+
+    #[test]
+    fn test_slow_component_tops_the_report() {
+        let console = Console::with_options(
+            ConsoleOptions::new()
+                .with_color(false)
+                .with_max_width(60)
+                .with_profiling(true),
+        );
+        let output = console.capture(|console| console.print(scene())).unwrap();
+        assert_eq!(output, "title\ncached\nstatus\nslow\nfooter");
+
+        let profile = console.render_profile().unwrap();
+        let top = &profile.entries()[0];
+        assert_eq!(top.component, "Slow");
+        assert_eq!(top.calls, 1);
+        assert!(top.total >= Duration::from_millis(30));
+        // Containers do not count the time of their children
+        let stacks = profile.get("Stack").unwrap();
+        assert_eq!(stacks.calls, 2);
+        assert!(stacks.total < Duration::from_millis(30));
+        assert_eq!(profile.get("Text").unwrap().calls, 4);
+        assert_eq!(profile.get("Cached<Text>").unwrap().calls, 1);
+
+        let segments = console
+            .render(profile.report(), &RenderOptions::new().with_max_width(60))
+            .unwrap();
+        let lines = crate::testing::render_lines(&segments);
+        let report = lines.join("\n");
+        assert!(lines[0].starts_with("component"), "{}", report);
+        assert!(lines[1].starts_with("Slow "), "{}", report);
+        assert_eq!(lines.len(), profile.entries().len() + 1);
+    }
+
+    #[test]
+    fn test_each_print_is_a_frame() {
+        let console = Console::with_options(ConsoleOptions::new().with_profiling(true));
+        console.capture(|console| console.print(scene())).unwrap();
+        console
+            .capture(|console| console.print(Text::new("next")))
+            .unwrap();
+        let profile = console.render_profile().unwrap();
+        assert_eq!(profile.entries().len(), 1);
+        assert_eq!(profile.get("Text").unwrap().calls, 1);
+
+        assert!(Console::new().render_profile().is_none());
+        let options = ConsoleOptions::new()
+            .with_profiling(true)
+            .with_profiling(false);
+        assert!(options.render.profiler.is_none());
+    }
+
+    #[test]
+    fn test_component_names() {
+        assert_eq!(component_name::<Text>(), "Text");
+        assert_eq!(component_name::<Cached<Text>>(), "Cached<Text>");
+        assert_eq!(component_name::<dyn Renderable>(), "dyn Renderable");
+        assert_eq!(component_name::<[&str]>(), "[&str]");
+    }
+}
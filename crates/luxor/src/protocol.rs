@@ -1,6 +1,32 @@
 //! Core traits and protocols for rendering and measurement.
+//!
+//! Besides the components, these types implement both [`Renderable`] and
+//! [`Measurable`], so they can be printed or laid out directly:
+//!
+//! - `&str`, `String` and `Cow<str>`, rendered as a
+//!   [`Text`](crate::Text): parsed as markup when
+//!   [`RenderOptions::markup`] is set, with embedded newlines breaking
+//!   lines and long lines folded to the available width.
+//! - `Box<dyn Renderable>` and `Box<dyn Measurable>` (one trait each),
+//!   forwarding to what they hold.
+//! - [`Segments`](crate::Segments) (`Renderable` only), printed as they
+//!   are.
+//!
+//! Any other [`Display`](std::fmt::Display) value, such as a number, can
+//! be printed with [`Console::print_display`].
+//!
+//! ```rust
+//! use luxor::{Console, ConsoleOptions, Measurable};
+//!
+//! let console = Console::with_options(ConsoleOptions::new().with_max_width(20));
+//! let measurement = "one\nthree".measure(&console, console.render_options()).unwrap();
+//! assert_eq!((measurement.minimum(), measurement.maximum()), (5, 5));
+//! let output = console.capture(|console| console.print_display(42)).unwrap();
+//! assert_eq!(output, "42");
+//! ```
 
-use crate::{Console, ConsoleOptions, Measurement, Result, Segment};
+use crate::{Console, Measurement, RenderOptions, Result, Segment};
+use std::borrow::Cow;
 
 /// The result of a rendering operation - a vector of segments.
 pub type RenderResult = Result<Vec<Segment>>;
@@ -13,14 +39,14 @@ pub type RenderResult = Result<Vec<Segment>>;
 /// # Examples
 ///
 /// ```rust
-/// use luxor::{Renderable, Console, ConsoleOptions, Segment, Style};
+/// use luxor::{Renderable, Console, RenderOptions, Segment, Style};
 ///
 /// struct SimpleText {
 ///     content: String,
 /// }
 ///
 /// impl Renderable for SimpleText {
-///     fn render(&self, _console: &Console, _options: &ConsoleOptions) -> luxor::Result<Vec<Segment>> {
+///     fn render(&self, _console: &Console, _options: &RenderOptions) -> luxor::Result<Vec<Segment>> {
 ///         Ok(vec![Segment::new(self.content.clone(), Style::default())])
 ///     }
 /// }
@@ -37,7 +63,16 @@ pub trait Renderable: Send + Sync {
     ///
     /// A `Result` containing a vector of `Segment`s that represent the rendered output,
     /// or an error if rendering fails.
-    fn render(&self, console: &Console, options: &ConsoleOptions) -> RenderResult;
+    fn render(&self, console: &Console, options: &RenderOptions) -> RenderResult;
+
+    /// Get the name this object's renders are timed under when profiling.
+    /// See the [`profile`](crate::profile) module.
+    ///
+    /// The default is the type name without module paths, such as
+    /// `Cached<Text>`; wrappers forward to what they wrap.
+    fn component_name(&self) -> String {
+        crate::profile::component_name::<Self>()
+    }
 }
 
 /// A trait for objects that can be measured for layout purposes.
@@ -48,14 +83,14 @@ pub trait Renderable: Send + Sync {
 /// # Examples
 ///
 /// ```rust
-/// use luxor::{Measurable, Console, ConsoleOptions, Measurement};
+/// use luxor::{Measurable, Console, RenderOptions, Measurement};
 ///
 /// struct FixedWidth {
 ///     width: usize,
 /// }
 ///
 /// impl Measurable for FixedWidth {
-///     fn measure(&self, _console: &Console, _options: &ConsoleOptions) -> luxor::Result<Measurement> {
+///     fn measure(&self, _console: &Console, _options: &RenderOptions) -> luxor::Result<Measurement> {
 ///         Ok(Measurement::new(self.width, self.width))
 ///     }
 /// }
@@ -72,53 +107,101 @@ pub trait Measurable: Send + Sync {
     ///
     /// A `Result` containing a `Measurement` that describes the minimum and maximum
     /// width requirements, or an error if measurement fails.
-    fn measure(&self, console: &Console, options: &ConsoleOptions) -> Result<Measurement>;
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement>;
 }
 
 /// Implement `Renderable` for `String` to enable direct rendering of strings.
 impl Renderable for String {
-    fn render(&self, _console: &Console, _options: &ConsoleOptions) -> RenderResult {
-        use crate::Style;
-        Ok(vec![Segment::new(self.clone(), Style::default())])
+    fn render(&self, console: &Console, options: &RenderOptions) -> RenderResult {
+        self.as_str().render(console, options)
     }
 }
 
 /// Implement `Renderable` for `&str` to enable direct rendering of string slices.
+///
+/// The string is parsed as markup when [`RenderOptions::markup`] is set;
+/// see the [`markup`](crate::markup) module, and styled by the console's
+/// highlighter when [`RenderOptions::highlight`] is set. Either way it
+/// renders as a [`Text`](crate::Text), so lines wider than the available
+/// width are folded onto the next line.
 impl Renderable for &str {
-    fn render(&self, _console: &Console, _options: &ConsoleOptions) -> RenderResult {
-        use crate::Style;
-        Ok(vec![Segment::new((*self).to_string(), Style::default())])
+    fn render(&self, console: &Console, options: &RenderOptions) -> RenderResult {
+        let parsed = match options.markup && self.contains(['[', '\\']) {
+            true => crate::markup::render_or_warn(self, options.resolved_theme().as_deref()),
+            false => None,
+        };
+        let mut text = parsed.unwrap_or_else(|| crate::Text::new(self));
+        if let Some(highlighter) = console.highlighter().filter(|_| options.highlight) {
+            highlighter.highlight(&mut text);
+        }
+        text.render(console, options)
+    }
+}
+
+/// Implement `Renderable` for `Cow<str>`, rendering like `&str`.
+impl Renderable for Cow<'_, str> {
+    fn render(&self, console: &Console, options: &RenderOptions) -> RenderResult {
+        self.as_ref().render(console, options)
     }
 }
 
-/// Implement `Measurable` for `String` using Unicode width calculation.
+/// Implement `Measurable` for `String`, measuring like `&str`.
 impl Measurable for String {
-    fn measure(&self, _console: &Console, _options: &ConsoleOptions) -> Result<Measurement> {
-        use unicode_width::UnicodeWidthStr;
-        let width = self.width();
-        Ok(Measurement::new(width, width))
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        self.as_str().measure(console, options)
     }
 }
 
-/// Implement `Measurable` for `&str` using Unicode width calculation.
+/// Implement `Measurable` for `&str` as the [`Text`](crate::Text) it
+/// renders as: the maximum is the widest line, the minimum the longest
+/// word, both at most the available width.
+///
+/// With [`RenderOptions::markup`] set, valid markup tags take no width.
 impl Measurable for &str {
-    fn measure(&self, _console: &Console, _options: &ConsoleOptions) -> Result<Measurement> {
-        use unicode_width::UnicodeWidthStr;
-        let width = self.width();
-        Ok(Measurement::new(width, width))
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        let parsed = match options.markup && self.contains(['[', '\\']) {
+            true => match options.resolved_theme().as_deref() {
+                Some(theme) => crate::markup::render_with_theme(self, None, theme),
+                None => crate::markup::render(self, None),
+            }
+            .ok(),
+            false => None,
+        };
+        let text = parsed.unwrap_or_else(|| crate::Text::new(self));
+        Ok(text
+            .measure(console, options)?
+            .clamp(0, options.get_max_width()))
+    }
+}
+
+/// Implement `Measurable` for `Cow<str>`, measuring like `&str`.
+impl Measurable for Cow<'_, str> {
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        self.as_ref().measure(console, options)
+    }
+}
+
+/// Implement `Renderable` for `Segments` so pre-rendered output can be printed.
+impl Renderable for crate::Segments {
+    fn render(&self, _console: &Console, _options: &RenderOptions) -> RenderResult {
+        Ok(self.as_slice().to_vec())
     }
 }
 
 /// Implement `Renderable` for `Box<dyn Renderable>` to enable trait object rendering.
 impl Renderable for Box<dyn Renderable> {
-    fn render(&self, console: &Console, options: &ConsoleOptions) -> RenderResult {
+    fn render(&self, console: &Console, options: &RenderOptions) -> RenderResult {
         (**self).render(console, options)
     }
+
+    fn component_name(&self) -> String {
+        (**self).component_name()
+    }
 }
 
 /// Implement `Measurable` for `Box<dyn Measurable>` to enable trait object measurement.
 impl Measurable for Box<dyn Measurable> {
-    fn measure(&self, console: &Console, options: &ConsoleOptions) -> Result<Measurement> {
+    fn measure(&self, console: &Console, options: &RenderOptions) -> Result<Measurement> {
         (**self).measure(console, options)
     }
 }
@@ -134,12 +217,12 @@ impl<T> RenderableMeasurable for T where T: Renderable + Measurable {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Console, ConsoleOptions};
+    use crate::{Console, ConsoleOptions, RenderOptions, diagnostics::Warning};
 
     #[test]
     fn test_string_renderable() {
         let console = Console::new();
-        let options = ConsoleOptions::default();
+        let options = RenderOptions::default();
         let text = "Hello, world!".to_string();
 
         let result = text.render(&console, &options);
@@ -152,7 +235,7 @@ mod tests {
     #[test]
     fn test_str_renderable() {
         let console = Console::new();
-        let options = ConsoleOptions::default();
+        let options = RenderOptions::default();
         let text = "Hello, world!";
 
         let result = text.render(&console, &options);
@@ -162,10 +245,106 @@ mod tests {
         assert_eq!(segments[0].text(), "Hello, world!");
     }
 
+    /// Printed output and the invalid markup warnings about it.
+    type Printed = (String, Vec<String>);
+
+    /// Print `string` with markup on or off, returning the output and the
+    /// messages of any invalid markup warnings about `string`.
+    fn print_string(string: &str, markup: bool) -> Printed {
+        let options = ConsoleOptions::new()
+            .with_max_width(40)
+            .with_color_system(crate::ColorSystem::Standard)
+            .with_markup(markup);
+        let console = Console::with_options(options);
+        let mut output = String::new();
+        let warnings = crate::diagnostics::collect_warnings(|| {
+            output = console.capture(|console| console.print(string)).unwrap();
+        });
+        let messages = warnings
+            .into_iter()
+            .filter_map(|warning| match warning {
+                Warning::InvalidMarkup { markup, message } if markup == string => Some(message),
+                _ => None,
+            })
+            .collect();
+        (output, messages)
+    }
+
+    #[test]
+    fn test_str_markup_on_valid() {
+        let (output, warnings) = print_string("[bold]x[/bold] y", true);
+        assert_eq!(output, "\x1b[1mx\x1b[0m y");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_str_markup_on_invalid_falls_back_to_literal() {
+        let (output, warnings) = print_string("[bold]x[/italic]", true);
+        assert_eq!(output, "[bold]x[/italic]");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("[/italic]"), "{}", warnings[0]);
+    }
+
+    #[test]
+    fn test_str_markup_off_valid_is_literal() {
+        let (output, warnings) = print_string("[bold]x[/bold] y", false);
+        assert_eq!(output, "[bold]x[/bold] y");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_str_markup_off_invalid_is_literal() {
+        let (output, warnings) = print_string("[bold]x[/italic]", false);
+        assert_eq!(output, "[bold]x[/italic]");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_str_theme_accessible_overrides() {
+        let theme = crate::Theme::new();
+        let options = RenderOptions::new().with_theme(theme);
+        let warning = |options: &RenderOptions| {
+            let segments = "[warning]x[/]".render(&Console::new(), options).unwrap();
+            segments[0].style().clone()
+        };
+        assert_eq!(warning(&options).bold, None);
+        let accessible = options.clone().with_accessible(true);
+        assert_eq!(warning(&accessible).bold, Some(true));
+        assert_eq!(warning(&accessible).color, warning(&options).color);
+    }
+
+    #[test]
+    fn test_text_never_parses_markup() {
+        let console = Console::with_options(ConsoleOptions::new().with_max_width(40));
+        let segments = crate::Text::from("a [bold]b[/bold]")
+            .render(&console, console.render_options())
+            .unwrap();
+        let plain: String = segments.iter().map(|s| s.text()).collect();
+        assert_eq!(plain, "a [bold]b[/bold]");
+
+        let measurement = "[bold]ab[/bold]"
+            .measure(&console, console.render_options())
+            .unwrap();
+        assert_eq!(measurement.maximum(), 2);
+    }
+
+    #[test]
+    fn test_segments_renderable() {
+        let console = Console::new();
+        let options = RenderOptions::default();
+        let segments = crate::Segments::from_vec(vec![
+            Segment::new("a".to_string(), crate::Style::new().bold()),
+            Segment::line(),
+        ]);
+
+        let rendered = Renderable::render(&segments, &console, &options).unwrap();
+        assert_eq!(rendered, segments.into_vec());
+    }
+
     #[test]
     fn test_string_measurable() {
         let console = Console::new();
-        let options = ConsoleOptions::default();
+        let options = RenderOptions::default();
         let text = "Hello".to_string();
 
         let result = text.measure(&console, &options);
@@ -175,10 +354,56 @@ mod tests {
         assert_eq!(measurement.maximum(), 5);
     }
 
+    #[test]
+    fn test_cow_renders_and_measures_like_str() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(20);
+        for cow in [Cow::Borrowed("a b"), Cow::Owned("a b".to_string())] {
+            assert_eq!(
+                cow.render(&console, &options).unwrap(),
+                "a b".render(&console, &options).unwrap()
+            );
+            assert_eq!(
+                cow.measure(&console, &options).unwrap(),
+                Measurement::new(1, 3)
+            );
+        }
+    }
+
+    #[test]
+    fn test_str_newlines_break_lines() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(20);
+        let text = "first line\nsecond\nthe third line";
+        let segments = text.render(&console, &options).unwrap();
+        assert_eq!(
+            crate::testing::render_lines(&segments),
+            ["first line", "second", "the third line"]
+        );
+        assert_eq!(
+            text.measure(&console, &options).unwrap(),
+            Measurement::new(6, 14)
+        );
+    }
+
+    #[test]
+    fn test_str_keeps_to_max_width() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(30);
+        let text = "word ".repeat(20_000) + &"x".repeat(50_000);
+        assert_eq!(
+            text.measure(&console, &options).unwrap(),
+            Measurement::fixed(30)
+        );
+        let segments = text.render(&console, &options).unwrap();
+        let lines = crate::testing::render_lines(&segments);
+        assert!(lines.iter().all(|line| line.chars().count() <= 30));
+    }
+
     #[test]
     fn test_str_measurable() {
         let console = Console::new();
-        let options = ConsoleOptions::default();
+        let options = RenderOptions::default();
         let text = "Hello";
 
         let result = text.measure(&console, &options);
@@ -0,0 +1,140 @@
+//! Render to string - one call from a renderable to its output.
+//!
+//! Rendering to a string otherwise takes a console, options, a render and
+//! a conversion of the segments. These functions do all four with a
+//! throwaway console that never touches the terminal and never reads the
+//! environment: `NO_COLOR`, `COLUMNS`, `LUXOR_ACCESSIBLE` and terminal
+//! detection are all ignored, so the output depends only on the arguments.
+//! That makes them the entry point for tests and snapshots, in this crate
+//! and downstream.
+
+use crate::{
+    ColorSystem, Console, ConsoleOptions, EllipsisConfig, RenderOptions, RenderState, Renderable,
+    Result, Segment, Segments, Text, segment,
+};
+
+/// Render `renderable` at `width` cells to a string with ANSI styles for
+/// `color`.
+///
+/// Lines are separated by newlines, with no newline added at the end.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{ColorSystem, Style, Text, render_to_string};
+///
+/// let text = Text::new("Hi").with_style(Style::new().bold());
+/// let output = render_to_string(text, 80, ColorSystem::Standard).unwrap();
+/// assert_eq!(output, "\x1b[1mHi\x1b[0m");
+/// ```
+pub fn render_to_string<R: Renderable>(
+    renderable: R,
+    width: usize,
+    color: ColorSystem,
+) -> Result<String> {
+    let segments = render_detached(renderable, width, color)?;
+    Ok(segment::render_segments(&segments, color))
+}
+
+/// Render `renderable` at `width` cells to plain text, without styles or
+/// control codes.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Text, render_plain};
+///
+/// let text = Text::from_markup("[bold]name[/bold]: luxor\n[dim]v0.1[/dim]").unwrap();
+/// assert_eq!(render_plain(text, 80).unwrap(), "name: luxor\nv0.1");
+/// ```
+pub fn render_plain<R: Renderable>(renderable: R, width: usize) -> Result<String> {
+    let segments = render_detached(renderable, width, ColorSystem::Standard)?;
+    Ok(Segments::from_vec(segments).plain_text())
+}
+
+/// Render `renderable` to a block of plain text with every line exactly
+/// `width` cells, for chat code blocks and log files.
+///
+/// This is [`Console::render_block`] on a [`Console::plain_fixed`]
+/// console: no styles, no control characters, no dependence on the
+/// environment.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::render_block;
+///
+/// assert_eq!(render_block("ok\nfailed", 4).unwrap(), "ok  \nfail");
+/// ```
+pub fn render_block<R: Renderable>(renderable: R, width: usize) -> Result<String> {
+    Console::plain_fixed(width).render_block(renderable, false)
+}
+
+/// Parse `markup` and render it at `width` cells to a string with 24-bit
+/// ANSI styles.
+///
+/// This is [`render_to_string`] of [`Text::from_markup`]; use those for
+/// another color system. It is not [`render_markup`](crate::render_markup),
+/// which parses markup into [`Text`] without rendering it.
+///
+/// # Errors
+///
+/// Returns an error if the markup is malformed.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::render_markup_to_string;
+///
+/// let output = render_markup_to_string("[red]no[/red]", 80).unwrap();
+/// assert_eq!(output, "\x1b[31mno\x1b[0m");
+/// ```
+pub fn render_markup_to_string(markup: &str, width: usize) -> Result<String> {
+    render_to_string(Text::from_markup(markup)?, width, ColorSystem::TrueColor)
+}
+
+/// Render with a console built from the arguments alone.
+fn render_detached<R: Renderable>(
+    renderable: R,
+    width: usize,
+    color: ColorSystem,
+) -> Result<Vec<Segment>> {
+    // Every field is set here, since the defaults come from the environment
+    let render = RenderOptions {
+        max_width: Some(width),
+        min_width: 0,
+        max_height: None,
+        accessible: false,
+        fallback_width: width,
+        soft_wrap: false,
+        ascii_only: false,
+        bidi_policy: None,
+        ellipsis: EllipsisConfig::default(),
+        markup: true,
+        theme: None,
+        highlight: false,
+        sanitize: crate::sanitize::Policy::Off,
+        state: RenderState::default(),
+        cancel: None,
+        measure_depth: 0,
+        measure_call: 0,
+        measure_pass: None,
+        measure_limit: crate::options::DEFAULT_MEASURE_LIMIT,
+        profiler: None,
+    };
+    #[allow(deprecated)]
+    let options = ConsoleOptions {
+        max_width: render.max_width,
+        min_width: render.min_width,
+        render: render.clone(),
+        enable_color: true,
+        color_system: color,
+        alt_screen: false,
+        legacy_windows: false,
+        debug_whitespace: false,
+        force_terminal: Some(false),
+        plain_fixed: false,
+    };
+    let console = Console::with_options(options);
+    console.render(renderable, &render)
+}
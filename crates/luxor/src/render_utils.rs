@@ -0,0 +1,813 @@
+//! Render utilities - the supported building blocks for custom components.
+//!
+//! Built-in components are implemented with the functions in this module,
+//! and third-party [`Renderable`] implementations are
+//! encouraged to use them too. They are part of the stable public API:
+//! their signatures and documented behavior only change in a semver-major
+//! release, while everything not documented here (such as the exact
+//! grouping of segments returned) may be refined at any time.
+//!
+//! The usual shape of a render is:
+//!
+//! 1. [`narrow`] the options by the space taken up by borders and padding
+//!    and render the content with them.
+//! 2. [`split_lines`] the result and fit every line to the inner width with
+//!    [`adjust_line_length`].
+//! 3. Decorate each line and join them back up with [`frame_lines`].
+//!
+//! # Cell widths
+//!
+//! Every helper here and on [`Segment`] and
+//! [`Segments`](crate::Segments) cuts at character boundaries, and combining
+//! marks always stay with the character before them. A double-width
+//! character straddling a cut is never split: it goes to the far side of the
+//! cut, and helpers that promise an exact width ([`adjust_line_length`],
+//! `Segment::truncate_cells` with padding) put a space in the freed cell.
+//! The rest only promise not to exceed the width, so they may come up one
+//! cell short.
+//!
+//! # Examples
+//!
+//! A "badge" pill with rounded ends, written purely against the public API:
+//!
+//! ```rust
+//! use luxor::{
+//!     Color, Console, RenderOptions, Renderable, Result, Segment, Style,
+//!     render_utils::{self, visible_width},
+//! };
+//!
+//! struct Badge {
+//!     label: String,
+//!     color: Color,
+//! }
+//!
+//! impl Renderable for Badge {
+//!     fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+//!         let body = Style::new().with_background(self.color);
+//!         let cap = Style::new().with_color(self.color);
+//!
+//!         // One cell of padding on each side of the label, plus the two caps
+//!         let inner = render_utils::narrow(options, 4).get_max_width();
+//!         let label = Segment::new(self.label.as_str(), body.clone());
+//!         let width = visible_width(&self.label).min(inner);
+//!         let label = render_utils::adjust_line_length(vec![label], width, body.clone());
+//!
+//!         let mut line = vec![Segment::new("▐", cap.clone())];
+//!         line.extend(render_utils::fill(1, body.clone()));
+//!         line.extend(label);
+//!         line.extend(render_utils::fill(1, body));
+//!         line.push(Segment::new("▌", cap));
+//!         Ok(render_utils::frame_lines(vec![line]))
+//!     }
+//! }
+//!
+//! let badge = Badge { label: "PASS".to_string(), color: Color::rgb(0, 160, 0) };
+//! let options = RenderOptions::new().with_max_width(20);
+//! let segments = badge.render(&Console::new(), &options).unwrap();
+//! let plain: String = segments.iter().map(|s| s.text()).collect();
+//! assert_eq!(plain, "▐ PASS ▌");
+//! ```
+
+use crate::{
+    BidiPolicy, Console, Measurable, RenderOptions, Renderable, Result, Segment, Style,
+    diagnostics::{self, Warning},
+    text,
+};
+use std::borrow::Cow;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+pub use crate::ansi::text_width as visible_width;
+
+/// Split segments into lines at embedded newline characters.
+///
+/// Newlines are removed and empty segments are dropped, while control
+/// segments stay where they were. The pieces of a split segment keep its
+/// style and padding mark. The result always contains at least one
+/// (possibly empty) line; text ending in a newline yields a trailing empty
+/// line.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Segment, Style, render_utils::split_lines};
+///
+/// let lines = split_lines(vec![Segment::new("ab\ncd", Style::new())]);
+/// assert_eq!(lines.len(), 2);
+/// assert_eq!(lines[1][0].text(), "cd");
+/// ```
+pub fn split_lines(segments: Vec<Segment>) -> Vec<Vec<Segment>> {
+    let mut lines = vec![Vec::new()];
+    for segment in segments {
+        if !segment.text().contains('\n') {
+            if !segment.is_empty() {
+                lines
+                    .last_mut()
+                    .expect("lines is never empty")
+                    .push(segment);
+            }
+            continue;
+        }
+        let style = segment.style().clone();
+        let mut parts = segment.text().split('\n').peekable();
+        while let Some(part) = parts.next() {
+            if !part.is_empty() {
+                let piece = match segment.is_padding() {
+                    true => Segment::pad(part, style.clone()),
+                    false => Segment::new(part, style.clone()),
+                };
+                lines.last_mut().expect("lines is never empty").push(piece);
+            }
+            if parts.peek().is_some() {
+                lines.push(Vec::new());
+            }
+        }
+    }
+    lines
+}
+
+/// Get the display width of a line of segments in terminal cells.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Segment, Style, render_utils::line_width};
+///
+/// let line = vec![Segment::new("ab", Style::new()), Segment::new("漢", Style::new())];
+/// assert_eq!(line_width(&line), 4);
+/// ```
+pub fn line_width(line: &[Segment]) -> usize {
+    line.iter().map(Segment::cell_length).sum()
+}
+
+/// Create a run of `width` spaces in the given style, marked as padding
+/// (see [`Segment::pad`]).
+///
+/// Returns `None` for a zero width, so the result can be passed straight to
+/// [`Vec::extend`] without producing empty segments.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Style, render_utils::fill};
+///
+/// assert_eq!(fill(3, Style::new()).unwrap().text(), "   ");
+/// assert!(fill(0, Style::new()).is_none());
+/// ```
+pub fn fill(width: usize, style: Style) -> Option<Segment> {
+    (width > 0).then(|| Segment::pad(" ".repeat(width), style))
+}
+
+/// Fit a line to exactly `width` cells.
+///
+/// Short lines are padded with spaces in `pad_style`; long lines are cropped.
+/// A double-width character cut by the crop is replaced with a space so the
+/// result is never narrower than `width`.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Segment, Style, render_utils::adjust_line_length};
+///
+/// let line = vec![Segment::new("ab漢字", Style::new())];
+/// let cropped = adjust_line_length(line, 3, Style::new());
+/// assert_eq!(cropped[0].text(), "ab ");
+///
+/// let line = vec![Segment::new("ab", Style::new())];
+/// let padded = adjust_line_length(line, 4, Style::new());
+/// assert_eq!(padded[1].text(), "  ");
+/// ```
+pub fn adjust_line_length(line: Vec<Segment>, width: usize, pad_style: Style) -> Vec<Segment> {
+    let mut output = Vec::with_capacity(line.len() + 1);
+    let mut remaining = width;
+
+    for segment in line {
+        let cells = segment.cell_length();
+        if cells <= remaining {
+            // Zero-width segments up to the cut are kept, like in a full line
+            remaining -= cells;
+            output.push(segment);
+        } else {
+            output.extend((remaining > 0).then(|| segment.truncate_cells(remaining, true)));
+            remaining = 0;
+            break;
+        }
+    }
+
+    output.extend(fill(remaining, pad_style));
+    output
+}
+
+/// Join lines back into a flat segment list separated by newlines.
+///
+/// No newline is added after the last line.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Segment, Style, render_utils::frame_lines};
+///
+/// let lines = vec![
+///     vec![Segment::new("a", Style::new())],
+///     vec![Segment::new("b", Style::new())],
+/// ];
+/// let plain: String = frame_lines(lines).iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, "a\nb");
+/// ```
+pub fn frame_lines(lines: Vec<Vec<Segment>>) -> Vec<Segment> {
+    let mut output = Vec::new();
+    for (index, line) in lines.into_iter().enumerate() {
+        if index > 0 {
+            output.push(Segment::line());
+        }
+        output.extend(line);
+    }
+    output
+}
+
+/// Break a line into pieces at most `width` cells wide.
+///
+/// Lines are broken at the exact cell where they overflow, not at word
+/// boundaries. A character wider than `width` is kept whole on its own line.
+pub(crate) fn fold_line(line: Vec<Segment>, width: usize) -> Vec<Vec<Segment>> {
+    let mut lines = vec![Vec::new()];
+    let mut remaining = width;
+
+    for segment in line {
+        let cells = segment.cell_length();
+        if cells <= remaining {
+            remaining -= cells;
+            lines
+                .last_mut()
+                .expect("lines is never empty")
+                .push(segment);
+            continue;
+        }
+
+        // Walk the text by offset, so a long segment is not copied or
+        // measured again for every line it is folded onto
+        let text = segment.text();
+        let mut start = 0;
+        loop {
+            let rest = &text[start..];
+            let current = lines.last_mut().expect("lines is never empty");
+            let mut end = prefix_within(rest, remaining);
+            if end == 0 && current.is_empty() {
+                // Nothing fits on an empty line, so overflow by one character
+                end = rest.chars().next().map_or(0, char::len_utf8);
+            }
+            if end > 0 {
+                current.push(Segment::new(&rest[..end], segment.style().clone()));
+            }
+            start += end;
+            let rest = &text[start..];
+            if rest.is_empty() {
+                remaining = 0;
+                break;
+            }
+            lines.push(vec![]);
+            remaining = width;
+            if fits(rest, remaining) {
+                remaining -= rest.width();
+                lines
+                    .last_mut()
+                    .expect("lines is never empty")
+                    .push(Segment::new(rest, segment.style().clone()));
+                break;
+            }
+        }
+    }
+
+    lines
+}
+
+/// Get the byte length of the longest prefix of `text` at most `width`
+/// cells wide.
+fn prefix_within(text: &str, width: usize) -> usize {
+    let mut used = 0;
+    for (index, c) in text.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            return index;
+        }
+    }
+    text.len()
+}
+
+/// Check whether `text` is at most `width` cells wide.
+///
+/// Long text is rejected from a prefix first, so folding or wrapping a long
+/// line does not measure the whole remainder once per output line. The
+/// slack allows for a character sequence cut by the prefix changing width.
+pub(crate) fn fits(text: &str, width: usize) -> bool {
+    const SLACK: usize = 2;
+    let limit = width.saturating_add(SLACK + 1);
+    if let Some((cut, _)) = text.char_indices().nth(limit) {
+        if text[..cut].width() > width.saturating_add(SLACK) {
+            return false;
+        }
+    }
+    text.width() <= width
+}
+
+/// Fold every line of `segments` to at most `width` cells.
+pub(crate) fn fold(segments: Vec<Segment>, width: usize) -> Vec<Segment> {
+    let lines = split_lines(segments)
+        .into_iter()
+        .flat_map(|line| fold_line(line, width))
+        .collect();
+    frame_lines(lines)
+}
+
+/// Render `renderable`, checking that the output keeps to its measurement.
+///
+/// Containers lay out children from their
+/// [`Measurement`](crate::Measurement), so a child that renders wider than
+/// it measured, or wider than the available width, breaks the layout of
+/// everything around it. In debug builds this panics
+/// with the offending type's name when either happens; in release builds it
+/// is equivalent to calling [`Renderable::render`]. The available-width
+/// check is skipped with [`RenderOptions::soft_wrap`], which opts out of
+/// width-dependent layout.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Text, render_utils::checked_render};
+///
+/// let options = RenderOptions::new().with_max_width(5);
+/// let segments = checked_render(&Text::new("hello world"), &Console::new(), &options).unwrap();
+/// let plain: String = segments.iter().map(|s| s.text()).collect();
+/// assert_eq!(plain, "hello\nworld");
+/// ```
+pub fn checked_render<R>(
+    renderable: &R,
+    console: &Console,
+    options: &RenderOptions,
+) -> Result<Vec<Segment>>
+where
+    R: Renderable + Measurable + ?Sized,
+{
+    let segments = renderable.render(console, options)?;
+
+    #[cfg(debug_assertions)]
+    {
+        let promised = renderable.measure(console, options)?.maximum();
+        let actual = split_lines(segments.clone())
+            .iter()
+            .map(|line| line_width(line))
+            .max()
+            .unwrap_or(0);
+        let name = std::any::type_name::<R>();
+        assert!(
+            actual <= promised,
+            "{} rendered {} cells wide but measured at most {}",
+            name,
+            actual,
+            promised
+        );
+        let available = options.get_max_width();
+        assert!(
+            options.soft_wrap || actual <= available,
+            "{} rendered {} cells wide with only {} available",
+            name,
+            actual,
+            available
+        );
+    }
+
+    Ok(segments)
+}
+
+/// Apply a [`BidiPolicy`] to rendered segments.
+///
+/// With [`BidiPolicy::Isolate`] the right-to-left runs of every segment are
+/// wrapped in directional isolates; a run spanning several differently
+/// styled segments is isolated piecewise. Isolates are zero width, so line
+/// widths are unchanged. Bordered components call this on their content
+/// with [`BidiPolicy::Isolate`] unless the options set a policy.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{BidiPolicy, Segment, Style, render_utils::{apply_bidi_policy, line_width}};
+///
+/// let line = vec![Segment::new("שלום", Style::new())];
+/// let isolated = apply_bidi_policy(line, BidiPolicy::Isolate);
+/// assert_eq!(isolated[0].text(), "\u{2068}שלום\u{2069}");
+/// assert_eq!(line_width(&isolated), 4);
+/// ```
+pub fn apply_bidi_policy(segments: Vec<Segment>, policy: BidiPolicy) -> Vec<Segment> {
+    match policy {
+        BidiPolicy::Ignore => segments,
+        BidiPolicy::Warn => {
+            if segments
+                .iter()
+                .any(|segment| text::contains_rtl(segment.text()))
+            {
+                diagnostics::warn(|| Warning::RightToLeft {
+                    text: segments.iter().map(|segment| segment.text()).collect(),
+                });
+            }
+            segments
+        }
+        BidiPolicy::Isolate => segments
+            .into_iter()
+            .map(|segment| {
+                if segment.is_control() || !text::contains_rtl(segment.text()) {
+                    segment
+                } else {
+                    Segment::new(text::isolate_rtl(segment.text()), segment.style().clone())
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Make whitespace visible, for finding alignment problems.
+///
+/// Spaces are drawn as `·` and tabs as `→`. Padding inserted by luxor (see
+/// [`Segment::pad`]) is dimmed so it stands out from spaces in the content,
+/// the end of every line is marked with a dim `¶`, and double-width
+/// characters are underlined. Control segments pass through unchanged.
+///
+/// The result is for looking at: the extra `¶` makes lines one cell wider
+/// than they were laid out.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Segment, Style, render_utils::show_whitespace};
+///
+/// let line = vec![
+///     Segment::new("a b\t", Style::new()),
+///     Segment::pad("  ", Style::new()),
+/// ];
+/// let shown: String = show_whitespace(line).iter().map(|s| s.text()).collect();
+/// assert_eq!(shown, "a·b→··¶");
+/// ```
+pub fn show_whitespace(segments: Vec<Segment>) -> Vec<Segment> {
+    let marker = Style::new().dim();
+    let mut output = Vec::with_capacity(segments.len() + 1);
+    let mut at_line_start = true;
+    for segment in segments {
+        if segment.is_control() || segment.text().is_empty() {
+            output.push(segment);
+            continue;
+        }
+        let (style, padding) = if segment.is_padding() {
+            (segment.style().clone().combine(marker.clone()), true)
+        } else {
+            (segment.style().clone(), false)
+        };
+        let wide_style = style.clone().underline();
+        let flush = |run: &mut String, wide: bool, output: &mut Vec<Segment>| {
+            if run.is_empty() {
+                return;
+            }
+            let style = if wide { &wide_style } else { &style };
+            let text = std::mem::take(run);
+            output.push(if padding {
+                Segment::pad(text, style.clone())
+            } else {
+                Segment::new(text, style.clone())
+            });
+        };
+
+        let mut run = String::new();
+        let mut wide = false;
+        for ch in segment.text().chars() {
+            if ch == '\n' {
+                flush(&mut run, wide, &mut output);
+                output.push(Segment::new("¶", marker.clone()));
+                output.push(Segment::line());
+                at_line_start = true;
+                continue;
+            }
+            at_line_start = false;
+            let ch_wide = ch.width().unwrap_or(0) > 1;
+            if ch_wide != wide {
+                flush(&mut run, wide, &mut output);
+                wide = ch_wide;
+            }
+            run.push(match ch {
+                ' ' => '·',
+                '\t' => '→',
+                ch => ch,
+            });
+        }
+        flush(&mut run, wide, &mut output);
+    }
+    if !at_line_start {
+        output.push(Segment::new("¶", marker));
+    }
+    output
+}
+
+/// Replace box-drawing characters with ASCII: horizontal lines become `-`,
+/// vertical lines `|`, and corners and junctions `+`. Diagonals become
+/// `/`, `\\` and `X`. Other characters are kept, and text without
+/// box-drawing characters is borrowed.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::render_utils::ascii_box_drawing;
+///
+/// assert_eq!(ascii_box_drawing("╭─┬─╮\n│a│b│"), "+-+-+\n|a|b|");
+/// ```
+pub fn ascii_box_drawing(text: &str) -> Cow<'_, str> {
+    let is_box = |ch: char| ('\u{2500}'..='\u{257f}').contains(&ch);
+    if !text.chars().any(is_box) {
+        return Cow::Borrowed(text);
+    }
+    let ascii = text
+        .chars()
+        .map(|ch| match ch {
+            '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '═' | '╴' | '╶' | '╸' | '╺' | '╼'
+            | '╾' => '-',
+            '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '║' | '╵' | '╷' | '╹' | '╻' | '╽'
+            | '╿' => '|',
+            '╱' => '/',
+            '╲' => '\\',
+            '╳' => 'X',
+            ch if is_box(ch) => '+',
+            ch => ch,
+        })
+        .collect();
+    Cow::Owned(ascii)
+}
+
+/// Get options for rendering content inside `cells` columns of decoration.
+///
+/// The maximum width is reduced by `cells` (saturating at zero) and made
+/// explicit, so the content resolves the same width as its container. The
+/// minimum width is lowered to fit if necessary.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{RenderOptions, render_utils::narrow};
+///
+/// let options = RenderOptions::new().with_max_width(40).with_min_width(39);
+/// let inner = narrow(&options, 4);
+/// assert_eq!(inner.get_max_width(), 36);
+/// assert_eq!(inner.min_width, 36);
+/// ```
+pub fn narrow(options: &RenderOptions, cells: usize) -> RenderOptions {
+    let width = options.get_max_width().saturating_sub(cells);
+    let min_width = options.min_width.min(width);
+    options
+        .clone()
+        .with_max_width(width)
+        .with_min_width(min_width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Color, ControlCode, Justify, Measurement, Text,
+        components::{Badge, Blank, Cached, Columns, Group, Spacer},
+        protocol::RenderableMeasurable,
+        testing::render_lines,
+    };
+
+    fn plain(text: &str) -> Segment {
+        Segment::new(text, Style::new())
+    }
+
+    #[test]
+    fn test_split_lines_trailing_newline() {
+        let lines = split_lines(vec![plain("a\n"), plain("b"), plain("\n")]);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], vec![plain("a")]);
+        assert_eq!(lines[1], vec![plain("b")]);
+        assert!(lines[2].is_empty());
+    }
+
+    #[test]
+    fn test_adjust_line_length_across_segments() {
+        let line = vec![plain("ab"), plain("cd"), plain("ef")];
+        let adjusted = adjust_line_length(line, 3, Style::new());
+        assert_eq!(adjusted, vec![plain("ab"), plain("c")]);
+
+        let adjusted = adjust_line_length(vec![plain("漢")], 1, Style::new());
+        assert_eq!(line_width(&adjusted), 1);
+    }
+
+    #[test]
+    fn test_split_lines_keeps_controls_and_styles() {
+        let bold = Style::new().bold();
+        let bell = Segment::control(ControlCode::Bell);
+        let segments = vec![
+            Segment::new("ab\ncd", bold.clone()),
+            bell.clone(),
+            Segment::pad("  \n", Style::new()),
+            Segment::line(),
+            Segment::new("漢\n", Style::new()),
+        ];
+        let lines = split_lines(segments);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], vec![Segment::new("ab", bold.clone())]);
+        assert_eq!(lines[1], vec![Segment::new("cd", bold), bell, plain("  ")]);
+        assert!(lines[1][2].is_padding());
+        assert!(lines[2].is_empty());
+        assert_eq!(lines[3], vec![plain("漢")]);
+        assert!(lines[4].is_empty());
+    }
+
+    #[test]
+    fn test_adjust_line_length_wide_boundary_and_controls() {
+        let bell = Segment::control(ControlCode::Bell);
+        let line = vec![plain("a"), bell.clone(), plain("漢字"), bell.clone()];
+        // The cut falls inside 字, which becomes a space
+        let adjusted = adjust_line_length(line.clone(), 4, Style::new());
+        assert_eq!(adjusted, vec![plain("a"), bell.clone(), plain("漢 ")]);
+        assert_eq!(line_width(&adjusted), 4);
+        let adjusted = adjust_line_length(line.clone(), 5, Style::new());
+        assert_eq!(adjusted, line);
+        let adjusted = adjust_line_length(line, 7, Style::new());
+        assert_eq!(adjusted.last(), Some(&plain("  ")));
+        assert_eq!(line_width(&adjusted), 7);
+    }
+
+    #[test]
+    fn test_adjust_line_length_pads_with_style() {
+        let pad = Style::new().bold();
+        let adjusted = adjust_line_length(vec![plain("ab")], 4, pad.clone());
+        assert_eq!(adjusted[1], Segment::new("  ", pad));
+    }
+
+    #[test]
+    fn test_show_whitespace_marks_padding() {
+        let text = Text::new("a b\n漢 c").with_justify(Justify::Center);
+        let options = RenderOptions::new().with_max_width(8);
+        let segments = text.render(&Console::new(), &options).unwrap();
+        let shown = show_whitespace(segments);
+        crate::assert_render_eq!(render_lines(&shown), "··a·b···¶\n··漢·c··¶");
+
+        // Only the spaces added by centering are dimmed padding
+        let dimmed: Vec<&str> = shown
+            .iter()
+            .filter(|segment| segment.style().dim == Some(true) && segment.text() != "¶")
+            .map(Segment::text)
+            .collect();
+        assert_eq!(dimmed, ["··", "···", "··", "··"]);
+        assert!(
+            shown
+                .iter()
+                .filter(|segment| segment.style().dim == Some(true) && segment.text() != "¶")
+                .all(Segment::is_padding)
+        );
+        let wide: Vec<&str> = shown
+            .iter()
+            .filter(|segment| segment.style().underline == Some(true))
+            .map(Segment::text)
+            .collect();
+        assert_eq!(wide, ["漢"]);
+    }
+
+    #[test]
+    fn test_frame_lines_round_trip() {
+        let segments = vec![plain("ab\n"), plain("cd")];
+        let framed = frame_lines(split_lines(segments));
+        crate::assert_render_eq!(render_lines(&framed), "ab\ncd");
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let folded = fold(vec![plain("abcde"), plain("fg\nh")], 3);
+        crate::assert_render_eq!(render_lines(&folded), "abc\ndef\ng\nh");
+
+        // Wide characters never straddle a break, and one that cannot fit
+        // at all still makes progress
+        let folded = fold(vec![plain("a漢字")], 2);
+        crate::assert_render_eq!(render_lines(&folded), "a\n漢\n字");
+        let folded = fold(vec![plain("漢字")], 1);
+        crate::assert_render_eq!(render_lines(&folded), "漢\n字");
+    }
+
+    /// A renderable that ignores the available width and reports a fixed
+    /// measurement, honest or not.
+    struct Lying(usize);
+
+    impl Renderable for Lying {
+        fn render(&self, _console: &Console, _options: &RenderOptions) -> Result<Vec<Segment>> {
+            Ok(vec![plain("twelve cells")])
+        }
+    }
+
+    impl Measurable for Lying {
+        fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+            Ok(Measurement::fixed(self.0))
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Lying rendered 12 cells wide but measured at most 4")]
+    fn test_checked_render_catches_lying_measure() {
+        let options = RenderOptions::new().with_max_width(40);
+        let _ = checked_render(&Lying(4), &Console::new(), &options);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "rendered 12 cells wide with only 10 available")]
+    fn test_checked_render_catches_overflow() {
+        let options = RenderOptions::new().with_max_width(10);
+        let _ = checked_render(&Lying(12), &Console::new(), &options);
+    }
+
+    type Component = Box<dyn RenderableMeasurable>;
+
+    #[test]
+    fn test_builtin_components_keep_to_width() {
+        let console = Console::new();
+        let text = "The quick brown fox\njumps over the lazy dog, 漢字 included";
+        let components: Vec<Component> = vec![
+            Box::new(text),
+            Box::new(text.to_string()),
+            Box::new(Text::new(text)),
+            Box::new(Text::new(text).with_justify(Justify::Center)),
+            Box::new(Text::new(text).with_justify(Justify::Right)),
+            Box::new(
+                Text::new(text)
+                    .with_style(Style::new().with_background(Color::rgb(0, 0, 128)))
+                    .with_full_width_background(true),
+            ),
+            Box::new(Text::from_markup("[bold]quick[/bold] [red]brown fox[/red]").unwrap()),
+            Box::new(console.styled(text, Style::new().bold())),
+            Box::new(Badge::new("PASS")),
+            Box::new(Badge::error("漢字 FAIL").with_pill(true)),
+            Box::new(Spacer::width(12)),
+            Box::new(Spacer::fill()),
+            Box::new(Spacer::lines(2)),
+            Box::new(Blank),
+            Box::new(Cached::new(Text::new(text))),
+            Box::new(Columns::from_items(text.split(' '))),
+            Box::new(Group::from_items(text.split('\n')).with_fit(true)),
+        ];
+
+        for width in 2..=50 {
+            let options = RenderOptions::new().with_max_width(width);
+            for component in &components {
+                checked_render(component.as_ref(), &console, &options).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_narrow_saturates() {
+        let options = RenderOptions::new().with_max_width(3);
+        assert_eq!(narrow(&options, 5).get_max_width(), 0);
+    }
+
+    #[test]
+    fn test_bidi_isolate_keeps_border_columns() {
+        // A bordered box as a container would draw it, resolving the policy
+        // to Isolate since the options leave it unset
+        let options = RenderOptions::new().with_max_width(14);
+        let policy = options.bidi_policy.unwrap_or(BidiPolicy::Isolate);
+        let inner = narrow(&options, 4);
+        let content = Text::new("שלום עולם\nabc");
+        let lines = split_lines(content.render(&Console::new(), &inner).unwrap())
+            .into_iter()
+            .map(|line| {
+                let line = adjust_line_length(apply_bidi_policy(line, policy), 10, Style::new());
+                let mut framed = vec![plain("│ ")];
+                framed.extend(line);
+                framed.push(plain(" │"));
+                framed
+            })
+            .collect();
+        let rendered = render_lines(&frame_lines(lines));
+        crate::assert_render_eq!(&rendered, "│ \u{2068}שלום עולם\u{2069}  │\n│ abc        │");
+        for line in &rendered {
+            let right = line.rfind('│').unwrap();
+            assert_eq!(visible_width(&line[..right]), 13);
+        }
+    }
+
+    #[test]
+    fn test_bidi_ignore_and_warn_leave_text() {
+        let line = vec![plain("שלום"), plain(" bidi warn")];
+        let warnings = diagnostics::collect_warnings(|| {
+            assert_eq!(apply_bidi_policy(line.clone(), BidiPolicy::Ignore), line);
+            assert_eq!(apply_bidi_policy(line.clone(), BidiPolicy::Warn), line);
+            apply_bidi_policy(vec![plain("abc bidi warn")], BidiPolicy::Warn);
+        });
+        let reported: Vec<_> = warnings
+            .iter()
+            .filter(|warning| {
+                matches!(warning, Warning::RightToLeft { text } if text.ends_with("bidi warn"))
+            })
+            .collect();
+        assert_eq!(
+            reported,
+            [&Warning::RightToLeft {
+                text: "שלום bidi warn".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Rendered - lines of a renderable kept for re-layout at other widths.
+//!
+//! Embedders that lay content out themselves, such as a TUI splitting the
+//! screen into resizable panes, render the same value again and again and
+//! need its size between renders. [`Console::render_lines`] returns a
+//! [`Rendered`] holding the value with its lines, which re-renders only
+//! when the width or the value's [`CacheKey`] changes.
+
+use crate::{Console, Renderable, Result, Segment, components::CacheKey, render_utils};
+
+/// A rendered line of segments, without its newline.
+pub type Line = Vec<Segment>;
+
+/// A renderable together with its lines at the last rendered width.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, ConsoleOptions, Text};
+///
+/// let console = Console::with_options(ConsoleOptions::new().with_max_width(80));
+/// let mut rendered = console.render_lines(Text::new("one two three"), 7).unwrap();
+/// assert_eq!(rendered.height(), 2);
+///
+/// // Same width: the lines are reused
+/// assert!(!rendered.rerender_if_needed(&console, 7).unwrap());
+/// // New width: the value is rendered again
+/// assert!(rendered.rerender_if_needed(&console, 20).unwrap());
+/// assert_eq!(rendered.height(), 1);
+/// assert_eq!(rendered.max_line_width(), 13);
+/// ```
+#[derive(Debug)]
+pub struct Rendered<R> {
+    renderable: R,
+    key: u64,
+    width: usize,
+    lines: Vec<Line>,
+}
+
+impl<R: Renderable + CacheKey> Rendered<R> {
+    /// Render `renderable` at `width` with the console's options.
+    pub(crate) fn new(renderable: R, console: &Console, width: usize) -> Result<Self> {
+        let mut rendered = Self {
+            key: renderable.cache_key(),
+            renderable,
+            width,
+            lines: Vec::new(),
+        };
+        rendered.render(console)?;
+        Ok(rendered)
+    }
+
+    /// Render again at `width` unless the lines are already up to date.
+    ///
+    /// The lines are up to date when the width and the renderable's
+    /// [`CacheKey`] are both unchanged. Returns whether a render happened.
+    pub fn rerender_if_needed(&mut self, console: &Console, width: usize) -> Result<bool> {
+        let key = self.renderable.cache_key();
+        if key == self.key && width == self.width {
+            return Ok(false);
+        }
+        self.key = key;
+        self.width = width;
+        self.render(console)?;
+        Ok(true)
+    }
+
+    fn render(&mut self, console: &Console) -> Result<()> {
+        let options = console.render_options().clone().with_max_width(self.width);
+        options.validate()?;
+        let segments = self.renderable.render(console, &options)?;
+        self.lines = render_utils::split_lines(segments);
+        Ok(())
+    }
+}
+
+impl<R> Rendered<R> {
+    /// Get the renderable.
+    pub fn get(&self) -> &R {
+        &self.renderable
+    }
+
+    /// Get the renderable for modification.
+    ///
+    /// The lines are re-rendered by the next
+    /// [`Rendered::rerender_if_needed`] if the change alters its
+    /// [`CacheKey`].
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.renderable
+    }
+
+    /// Unwrap the renderable.
+    pub fn into_inner(self) -> R {
+        self.renderable
+    }
+
+    /// Get the width the lines were rendered at.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the rendered lines, without newlines.
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    /// Get the number of lines.
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Get the width of the widest line in cells.
+    pub fn max_line_width(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| render_utils::line_width(line))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get the lines joined back into segments with newlines.
+    pub fn to_segments(&self) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            if index > 0 {
+                segments.push(Segment::line());
+            }
+            segments.extend_from_slice(line);
+        }
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConsoleOptions, RenderOptions, Style};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A renderable that counts how often it is actually rendered.
+    struct Counting {
+        version: u64,
+        renders: AtomicUsize,
+    }
+
+    impl Renderable for Counting {
+        fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+            self.renders.fetch_add(1, Ordering::SeqCst);
+            let width = options.get_max_width();
+            let text = format!("v{}\n{}", self.version, "-".repeat(width));
+            Ok(vec![Segment::new(text, Style::default())])
+        }
+    }
+
+    impl CacheKey for Counting {
+        fn cache_key(&self) -> u64 {
+            self.version
+        }
+    }
+
+    fn render_count(rendered: &Rendered<Counting>) -> usize {
+        rendered.get().renders.load(Ordering::SeqCst)
+    }
+
+    fn console() -> Console {
+        Console::with_options(ConsoleOptions::new().with_max_width(80))
+    }
+
+    fn rendered(console: &Console, width: usize) -> Rendered<Counting> {
+        let counting = Counting {
+            version: 0,
+            renders: AtomicUsize::new(0),
+        };
+        console.render_lines(counting, width).unwrap()
+    }
+
+    #[test]
+    fn test_rendered_reuses_lines_at_same_width() {
+        let console = console();
+        let mut rendered = rendered(&console, 10);
+        assert_eq!(render_count(&rendered), 1);
+        assert!(!rendered.rerender_if_needed(&console, 10).unwrap());
+        assert!(!rendered.rerender_if_needed(&console, 10).unwrap());
+        assert_eq!(render_count(&rendered), 1);
+        assert_eq!(rendered.height(), 2);
+        assert_eq!(rendered.max_line_width(), 10);
+    }
+
+    #[test]
+    fn test_rendered_rerenders_on_width_change() {
+        let console = console();
+        let mut rendered = rendered(&console, 10);
+        assert!(rendered.rerender_if_needed(&console, 4).unwrap());
+        assert_eq!(render_count(&rendered), 2);
+        assert_eq!(rendered.width(), 4);
+        assert_eq!(rendered.max_line_width(), 4);
+
+        let plain: String = rendered.to_segments().iter().map(|s| s.text()).collect();
+        assert_eq!(plain, "v0\n----");
+    }
+
+    #[test]
+    fn test_rendered_rerenders_on_key_change() {
+        let console = console();
+        let mut rendered = rendered(&console, 10);
+        rendered.get_mut().version = 1;
+        assert!(rendered.rerender_if_needed(&console, 10).unwrap());
+        assert_eq!(rendered.lines()[0][0].text(), "v1");
+        assert_eq!(render_count(&rendered), 2);
+    }
+}
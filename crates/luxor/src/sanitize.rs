@@ -0,0 +1,330 @@
+//! Sanitize - keeping untrusted text from controlling the terminal.
+//!
+//! Text from outside the program, such as log lines, file names or
+//! messages from other users, can carry escape sequences and control
+//! characters. A terminal obeys them: an OSC 52 sequence writes to the
+//! clipboard, a DCS string talks to the terminal itself, a bracketed-paste
+//! end marker makes the shell treat what follows as typed, and the C1 bytes
+//! `0x80`-`0x9F` start sequences of their own in some terminals, even in
+//! UTF-8 text. A [`Policy`] set with [`Text::with_sanitize`] or
+//! [`RenderOptions::with_sanitize`] deals with them before the text is
+//! measured or rendered, so widths match what is shown.
+//!
+//! Only [`Text`], and strings printed through it, are sanitized. Segments
+//! built by hand are written as they are.
+//!
+//! [`Text`]: crate::Text
+//! [`Text::with_sanitize`]: crate::Text::with_sanitize
+//! [`RenderOptions::with_sanitize`]: crate::RenderOptions::with_sanitize
+
+use std::{borrow::Cow, ops::Range};
+
+/// How to deal with escape sequences and control characters in text.
+///
+/// The default is [`Policy::Strict`], meant for untrusted text; render
+/// options leave text alone, with [`Policy::Off`], unless told otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Policy {
+    /// Remove every escape sequence, with its parameters or payload, and
+    /// every control character except newline and tab.
+    #[default]
+    Strict,
+    /// Show control characters rather than obey them: each is replaced by
+    /// its Unicode control picture, such as `␛` for escape, so a stray
+    /// sequence stays visible as text. C1 controls, which have no picture,
+    /// become `�`. Newline and tab are kept.
+    Standard,
+    /// Leave text as it is.
+    Off,
+}
+
+/// Sanitize `text` under `policy`, borrowing it when nothing changes.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::sanitize::{Policy, sanitize};
+///
+/// let hostile = "ok\x1b]52;c;cm0gLXJmIH4=\x07 done";
+/// assert_eq!(sanitize(hostile, Policy::Strict), "ok done");
+/// assert_eq!(sanitize("\x1b[2J", Policy::Standard), "␛[2J");
+/// assert_eq!(sanitize("漢字\t😀", Policy::Strict), "漢字\t😀");
+/// ```
+pub fn sanitize(text: &str, policy: Policy) -> Cow<'_, str> {
+    match apply(text, policy) {
+        Some(sanitized) => Cow::Owned(sanitized.content),
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// Make `title` safe to place inside an OSC sequence, such as a window
+/// title: controls are removed as by [`Policy::Strict`], and newlines and
+/// tabs become spaces, so nothing in it can end the sequence early.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::sanitize::title_safe;
+///
+/// assert_eq!(title_safe("build\x07\x1b]52;c;eA==\x07\nok"), "build ok");
+/// ```
+pub fn title_safe(title: &str) -> String {
+    sanitize(title, Policy::Strict)
+        .chars()
+        .map(|ch| match ch {
+            '\n' | '\t' => ' ',
+            ch => ch,
+        })
+        .collect()
+}
+
+/// Text after sanitizing.
+#[derive(Debug)]
+pub(crate) struct Sanitized {
+    /// The sanitized content.
+    pub(crate) content: String,
+    /// The character position in `content` of each character position in
+    /// the original, up to and including its length. Removed characters map
+    /// to where they would have been.
+    pub(crate) positions: Vec<usize>,
+}
+
+/// Sanitize `text` under `policy`, or get `None` if nothing changes.
+pub(crate) fn apply(text: &str, policy: Policy) -> Option<Sanitized> {
+    let edits = edits(text, policy);
+    if edits.is_empty() {
+        return None;
+    }
+
+    let mut content = String::with_capacity(text.len());
+    let mut positions = Vec::with_capacity(text.len() + 1);
+    let mut edits = edits.into_iter().peekable();
+    let mut output = 0;
+    for (index, ch) in text.chars().enumerate() {
+        positions.push(output);
+        match edits.peek() {
+            Some(edit) if edit.range.contains(&index) => {
+                if index == edit.range.start {
+                    if let Some(replacement) = edit.replacement {
+                        content.push(replacement);
+                        output += 1;
+                    }
+                }
+                if index + 1 == edit.range.end {
+                    edits.next();
+                }
+            }
+            _ => {
+                content.push(ch);
+                output += 1;
+            }
+        }
+    }
+    positions.push(output);
+    Some(Sanitized { content, positions })
+}
+
+/// A run of characters to remove or replace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Edit {
+    /// The characters, by position.
+    range: Range<usize>,
+    /// The character shown in their place, if any.
+    replacement: Option<char>,
+}
+
+/// Find the edits `policy` makes to `text`, in order.
+fn edits(text: &str, policy: Policy) -> Vec<Edit> {
+    match policy {
+        Policy::Off => Vec::new(),
+        Policy::Standard => text
+            .chars()
+            .enumerate()
+            .filter(|&(_, ch)| is_unsafe(ch))
+            .map(|(index, ch)| Edit {
+                range: index..index + 1,
+                replacement: Some(picture(ch)),
+            })
+            .collect(),
+        Policy::Strict => {
+            let chars: Vec<char> = text.chars().collect();
+            let mut edits = Vec::new();
+            let mut index = 0;
+            while index < chars.len() {
+                let end = match sequence_end(&chars, index) {
+                    Some(end) => end,
+                    None if is_unsafe(chars[index]) => index + 1,
+                    None => {
+                        index += 1;
+                        continue;
+                    }
+                };
+                edits.push(Edit {
+                    range: index..end,
+                    replacement: None,
+                });
+                index = end;
+            }
+            edits
+        }
+    }
+}
+
+/// Check whether `ch` is a control character other than newline and tab.
+fn is_unsafe(ch: char) -> bool {
+    ch.is_control() && ch != '\n' && ch != '\t'
+}
+
+/// Get the visible stand-in for the control character `ch`.
+fn picture(ch: char) -> char {
+    match ch as u32 {
+        code @ 0x00..=0x1f => char::from_u32(0x2400 + code).unwrap_or('\u{fffd}'),
+        0x7f => '\u{2421}',
+        _ => '\u{fffd}',
+    }
+}
+
+/// What follows a sequence introducer.
+enum Introducer {
+    /// Parameters and a final character, as in CSI.
+    Control,
+    /// A payload up to a string terminator, as in OSC and DCS.
+    String,
+    /// Intermediate characters and a final character, after a bare escape.
+    Escape,
+}
+
+/// Find the end of the escape sequence starting at `start`, if one does.
+///
+/// Sequences cut short by the end of the text run to the end, as a
+/// terminal would swallow them. A string sequence ends at BEL or a string
+/// terminator, or just before another escape.
+fn sequence_end(chars: &[char], start: usize) -> Option<usize> {
+    let (introducer, mut index) = match (chars[start], chars.get(start + 1)) {
+        ('\x1b', Some('[')) => (Introducer::Control, start + 2),
+        ('\u{9b}', _) => (Introducer::Control, start + 1),
+        ('\x1b', Some(']' | 'P' | 'X' | '^' | '_')) => (Introducer::String, start + 2),
+        ('\u{90}' | '\u{98}' | '\u{9d}' | '\u{9e}' | '\u{9f}', _) => {
+            (Introducer::String, start + 1)
+        }
+        ('\x1b', _) => (Introducer::Escape, start + 1),
+        _ => return None,
+    };
+    match introducer {
+        Introducer::Control => {
+            while chars
+                .get(index)
+                .is_some_and(|ch| ('\x20'..='\x3f').contains(ch))
+            {
+                index += 1;
+            }
+            if chars
+                .get(index)
+                .is_some_and(|ch| ('\x40'..='\x7e').contains(ch))
+            {
+                index += 1;
+            }
+        }
+        Introducer::String => {
+            while let Some(&ch) = chars.get(index) {
+                match ch {
+                    '\x07' | '\u{9c}' => return Some(index + 1),
+                    '\x1b' if chars.get(index + 1) == Some(&'\\') => return Some(index + 2),
+                    '\x1b' => return Some(index),
+                    _ => index += 1,
+                }
+            }
+        }
+        Introducer::Escape => {
+            while chars
+                .get(index)
+                .is_some_and(|ch| ('\x20'..='\x2f').contains(ch))
+            {
+                index += 1;
+            }
+            if chars
+                .get(index)
+                .is_some_and(|ch| ('\x30'..='\x7e').contains(ch))
+            {
+                index += 1;
+            }
+        }
+    }
+    Some(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Console, Measurable, RenderOptions, Renderable, Style, Text, assert_render_eq,
+        testing::render_lines,
+    };
+
+    #[test]
+    fn test_strict_removes_whole_sequences() {
+        let cases = [
+            ("a\x1b[31mb\x1b[0mc", "abc"),
+            ("a\x1b]0;title\x1b\\b", "ab"),
+            ("a\x1bPq#0;2;0;0;0\x1b\\b", "ab"),
+            ("a\x1b(Bb", "ab"),
+            ("a\u{9b}2Jb", "ab"),
+            ("a\x1b]52;c;unterminated", "a"),
+            ("a\x1b]8;;x\x1b[1mb", "ab"),
+            ("bell\x07 cr\r del\x7f", "bell cr del"),
+            ("trailing\x1b", "trailing"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(sanitize(input, Policy::Strict), expected, "{input:?}");
+        }
+        assert!(matches!(
+            sanitize("plain", Policy::Strict),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(sanitize("a\x1b[1mb", Policy::Off), "a\x1b[1mb");
+    }
+
+    #[test]
+    fn test_standard_shows_controls() {
+        assert_eq!(
+            sanitize("a\x1b[1m\u{9b}\x7f\x00\tb", Policy::Standard),
+            "a␛[1m�␡␀\tb"
+        );
+    }
+
+    #[test]
+    fn test_text_keeps_spans_and_measures_after_sanitizing() {
+        let mut text = Text::new("ab\x1b[2Jcd\x1b]52;c;eA==\x07ef").with_sanitize(Policy::Strict);
+        text.stylize_range(1..8, Style::new().bold()).unwrap();
+        assert_eq!(text.width(), 6);
+
+        let options = RenderOptions::new().with_max_width(4);
+        let segments = text.render(&Console::new(), &options).unwrap();
+        assert_render_eq!(render_lines(&segments), "abcd\nef");
+        let bold: String = segments
+            .iter()
+            .filter(|segment| segment.style().bold == Some(true))
+            .map(|segment| segment.text())
+            .collect();
+        assert_eq!(bold, "bcd");
+    }
+
+    #[test]
+    fn test_text_policy_overrides_render_options() {
+        let console = Console::new();
+        let options = RenderOptions::new()
+            .with_max_width(20)
+            .with_sanitize(Policy::Strict);
+        let plain = |text: &Text, options: &RenderOptions| {
+            render_lines(&text.render(&console, options).unwrap()).join("\n")
+        };
+        let text = Text::new("x\x1b[1my");
+        assert_eq!(plain(&text, &options), "xy");
+        assert_eq!(
+            plain(&text.clone().with_sanitize(Policy::Standard), &options),
+            "x␛[1my"
+        );
+        let measured = text.measure(&console, &options).unwrap();
+        assert_eq!(measured.maximum(), 2);
+    }
+}
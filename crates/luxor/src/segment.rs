@@ -1,10 +1,156 @@
 //! Segment - the fundamental rendering unit for rich text.
 
-use crate::{ColorSystem, Style, ansi};
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use crate::{
+    ColorSystem, Style, ansi,
+    render_utils::line_width,
+    units::{Cells, WidthPolicy},
+};
+use std::{
+    collections::{HashMap, HashSet, hash_map::DefaultHasher},
+    fmt,
+    hash::{Hash, Hasher},
+    io,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Number of renders so far, mixed into hyperlink ids so that separate
+/// renders of the same URL stay separate links.
+static LINK_RENDERS: AtomicU64 = AtomicU64::new(0);
+
+/// Render segments to a string with ANSI escape sequences.
+///
+/// A URL linked on more than one line, such as a link that was wrapped,
+/// gets an OSC 8 `id` derived from the URL and this render, so terminals
+/// treat its parts as one link.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{ColorSystem, Segment, Style, segment::render_segments};
+///
+/// let link = Style::new().with_link("https://example.com");
+/// let segments = vec![
+///     Segment::new("exam", link.clone()),
+///     Segment::line(),
+///     Segment::new("ple", link),
+/// ];
+/// let output = render_segments(&segments, ColorSystem::TrueColor);
+/// assert_eq!(output.matches("\x1b]8;id=").count(), 2);
+/// ```
+pub fn render_segments(segments: &[Segment], color_system: ColorSystem) -> String {
+    let mut output = String::new();
+    render_segments_to(segments, &mut output, color_system)
+        .expect("writing to a String cannot fail");
+    output
+}
+
+/// Render segments into `out`, without building an intermediate string.
+///
+/// The output is identical to [`render_segments`].
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{ColorSystem, Segment, Style, segment::{render_segments, render_segments_to}};
+///
+/// let segments = vec![Segment::new("Hi", Style::new().bold()), Segment::line()];
+/// let mut output = String::new();
+/// render_segments_to(&segments, &mut output, ColorSystem::Standard).unwrap();
+/// assert_eq!(output, render_segments(&segments, ColorSystem::Standard));
+/// ```
+pub fn render_segments_to(
+    segments: &[Segment],
+    out: &mut impl fmt::Write,
+    color_system: ColorSystem,
+) -> fmt::Result {
+    let wrapped = wrapped_links(segments);
+    if wrapped.is_empty() {
+        return segments
+            .iter()
+            .try_for_each(|segment| segment.write_with_link_id(out, color_system, None));
+    }
+
+    let render = LINK_RENDERS.fetch_add(1, Ordering::Relaxed);
+    let ids: HashMap<&str, String> = wrapped
+        .into_iter()
+        .map(|url| {
+            let mut hasher = DefaultHasher::new();
+            (url, render).hash(&mut hasher);
+            (url, format!("{:x}", hasher.finish()))
+        })
+        .collect();
+    segments.iter().try_for_each(|segment| {
+        let id = segment.style.link_url().and_then(|url| ids.get(url));
+        segment.write_with_link_id(out, color_system, id.map(String::as_str))
+    })
+}
+
+/// Render segments to a byte sink, such as a file or a locked stdout.
+///
+/// The output is identical to [`render_segments`]. Writes go straight to
+/// `out`, so wrap unbuffered sinks in a [`std::io::BufWriter`].
+pub fn write_segments_to(
+    segments: &[Segment],
+    out: &mut impl io::Write,
+    color_system: ColorSystem,
+) -> io::Result<()> {
+    let mut adapter = IoAdapter {
+        inner: out,
+        error: None,
+    };
+    render_segments_to(segments, &mut adapter, color_system).map_err(|_| {
+        adapter
+            .error
+            .unwrap_or_else(|| io::Error::new(io::ErrorKind::Other, "formatting failed"))
+    })
+}
+
+/// Forwards formatted output to a byte sink, keeping the I/O error that
+/// [`fmt::Write`] cannot carry.
+struct IoAdapter<'a, W: io::Write> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Find the URLs linked on more than one line.
+fn wrapped_links(segments: &[Segment]) -> HashSet<&str> {
+    let mut first_lines = HashMap::new();
+    let mut wrapped = HashSet::new();
+    let mut line = 0;
+    for segment in segments {
+        let newlines = segment.text.matches('\n').count();
+        if let Some(url) = segment.style.link_url() {
+            let first = *first_lines.entry(url).or_insert(line);
+            if first != line || newlines > 0 {
+                wrapped.insert(url);
+            }
+        }
+        line += newlines;
+    }
+    wrapped
+}
 
 /// Control codes for terminal operations.
+///
+/// With the `serde` feature, control codes serialize as a tagged object:
+/// `{"type": "bell"}`, `{"type": "cursor_up", "value": 3}` or
+/// `{"type": "cursor_move_to", "value": {"row": 1, "col": 5}}`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum ControlCode {
     /// Ring the terminal bell.
     Bell,
@@ -62,35 +208,124 @@ impl ControlCode {
 ///
 /// Segments are the fundamental rendering units in Luxor. They contain text,
 /// style information, and optional control codes for terminal operations.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// With the `serde` feature, a segment serializes as an object with `text`,
+/// `style` as a canonical style string and `control` as a [`ControlCode`];
+/// `style` and `control` are omitted when empty. This representation is
+/// stable and suitable for golden files.
+///
+/// Padding inserted by luxor, as opposed to text from the content, is
+/// marked (see [`Segment::pad`]) so debugging output can tell them apart.
+/// The mark is not serialized and does not affect equality.
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
 pub struct Segment {
     /// The text content of this segment.
     text: String,
     /// The style to apply to this segment.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Style::is_empty")
+    )]
     style: Style,
     /// Optional control code for terminal operations.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     control: Option<ControlCode>,
+    /// Whether luxor inserted this segment as padding.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    padding: bool,
+}
+
+impl PartialEq for Segment {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text && self.style == other.style && self.control == other.control
+    }
+}
+
+impl Eq for Segment {}
+
+/// Shows the text followed by the style and control code only when set,
+/// e.g. `Segment("Hello", bold red)`.
+impl fmt::Debug for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tuple = f.debug_tuple("Segment");
+        tuple.field(&self.text);
+        if !self.style.is_empty() {
+            tuple.field(&format_args!("{}", self.style));
+        }
+        if let Some(control) = &self.control {
+            tuple.field(control);
+        }
+        tuple.finish()
+    }
 }
 
 impl Segment {
     /// Create a new text segment with the given content and style.
     ///
+    /// The text may be a `String` or anything that converts into one, such
+    /// as a `&str`.
+    ///
     /// # Examples
     ///
     /// ```rust
     /// use luxor::{Segment, Style, Color};
     ///
-    /// let segment = Segment::new("Hello".to_string(), Style::new().color(Color::rgb(255, 0, 0)));
+    /// let segment = Segment::new("Hello", Style::new().with_color(Color::rgb(255, 0, 0)));
     /// assert_eq!(segment.text(), "Hello");
     /// ```
-    pub fn new(text: String, style: Style) -> Self {
+    pub fn new(text: impl Into<String>, style: Style) -> Self {
         Self {
-            text,
+            text: text.into(),
             style,
             control: None,
+            padding: false,
+        }
+    }
+
+    /// Create a segment of padding inserted by a component rather than
+    /// taken from its content.
+    ///
+    /// Padding renders like any other segment; the mark is only read by
+    /// [`render_utils::show_whitespace`](crate::render_utils::show_whitespace).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Segment, Style};
+    ///
+    /// let padding = Segment::pad("   ", Style::new());
+    /// assert!(padding.is_padding());
+    /// assert_eq!(padding, Segment::new("   ", Style::new()));
+    /// ```
+    pub fn pad(text: impl Into<String>, style: Style) -> Self {
+        Self {
+            padding: true,
+            ..Self::new(text, style)
         }
     }
 
+    /// Create a segment containing a single newline.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Segment;
+    ///
+    /// let segment = Segment::line();
+    /// assert_eq!(segment.text(), "\n");
+    /// ```
+    pub fn line() -> Self {
+        Self::new("\n".to_string(), Style::default())
+    }
+
     /// Create a new control segment with a control code.
     ///
     /// # Examples
@@ -106,6 +341,7 @@ impl Segment {
             text: String::new(),
             style: Style::default(),
             control: Some(control),
+            padding: false,
         }
     }
 
@@ -127,6 +363,7 @@ impl Segment {
             text,
             style,
             control: Some(control),
+            padding: false,
         }
     }
 
@@ -145,6 +382,12 @@ impl Segment {
         self.control
     }
 
+    /// Check if this segment is padding inserted by luxor. See
+    /// [`Segment::pad`].
+    pub fn is_padding(&self) -> bool {
+        self.padding
+    }
+
     /// Check if this segment contains only a control code (no text).
     pub fn is_control(&self) -> bool {
         self.control.is_some() && self.text.is_empty()
@@ -181,10 +424,17 @@ impl Segment {
         }
     }
 
+    /// Get the display width of this segment, as [`Cells`]. The typed
+    /// form of [`Segment::cell_length`].
+    pub fn cells(&self) -> Cells {
+        Cells(self.cell_length())
+    }
+
     /// Split this segment at the given character position.
     ///
-    /// Returns a tuple of (left_segment, right_segment). If the position is
-    /// out of bounds, returns the original segment and an empty segment.
+    /// The position counts characters, not bytes. Returns a tuple of
+    /// (left_segment, right_segment). If the position is at or past the
+    /// end, returns the original segment and an empty segment.
     ///
     /// # Examples
     ///
@@ -197,49 +447,42 @@ impl Segment {
     /// assert_eq!(right.text(), " World");
     /// ```
     pub fn split_at_char(self, pos: usize) -> (Self, Self) {
-        if pos >= self.text.len() {
-            return (self, Segment::new(String::new(), Style::default()));
-        }
-
         // Handle control codes - they stay with the left segment
         if self.is_control() {
             return (self, Segment::new(String::new(), Style::default()));
         }
 
-        // Find the byte position for the character position
-        let mut byte_pos = 0;
-        let mut char_count = 0;
-
-        for (byte_idx, _) in self.text.char_indices() {
-            if char_count == pos {
-                byte_pos = byte_idx;
-                break;
-            }
-            char_count += 1;
+        match self.text.char_indices().nth(pos) {
+            Some((byte_pos, _)) => self.split_at_byte(byte_pos),
+            None => (self, Segment::new(String::new(), Style::default())),
         }
+    }
 
-        if char_count == pos {
-            let (left_text, right_text) = self.text.split_at(byte_pos);
-            let left = Self {
-                text: left_text.to_string(),
-                style: self.style.clone(),
-                control: self.control,
-            };
-            let right = Self {
-                text: right_text.to_string(),
-                style: self.style,
-                control: None,
-            };
-            (left, right)
-        } else {
-            (self, Segment::new(String::new(), Style::default()))
-        }
+    /// Split the text at a byte offset on a character boundary, keeping
+    /// the style and padding mark on both sides and any control code on
+    /// the left.
+    fn split_at_byte(self, byte_pos: usize) -> (Self, Self) {
+        let (left_text, right_text) = self.text.split_at(byte_pos);
+        let left = Self {
+            text: left_text.to_string(),
+            style: self.style.clone(),
+            control: self.control,
+            padding: self.padding,
+        };
+        let right = Self {
+            text: right_text.to_string(),
+            style: self.style,
+            control: None,
+            padding: self.padding,
+        };
+        (left, right)
     }
 
     /// Split this segment to fit within the given display width.
     ///
     /// Returns a tuple of (left_segment, right_segment) where the left segment
     /// has a display width <= max_width. Uses Unicode-aware width calculation.
+    /// See [`Segment::split_at_width_padded`] for details about the split.
     ///
     /// # Examples
     ///
@@ -252,15 +495,54 @@ impl Segment {
     /// assert_eq!(right.text(), " World");
     /// ```
     pub fn split_at_width(self, max_width: usize) -> (Self, Self) {
+        let split = self.split_at_width_padded(max_width);
+        (split.left, split.right)
+    }
+
+    /// Split this segment to fit within the given display width, reporting
+    /// how many cells the left side occupies.
+    ///
+    /// When a double-width character straddles `max_width` it moves to the
+    /// right side, leaving the left one cell short; `split_wide_char` is set
+    /// so callers can pad the gap (see [`Segment::truncate_cells`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Segment, Style};
+    ///
+    /// let segment = Segment::new("ab漢字".to_string(), Style::new());
+    /// let split = segment.split_at_width_padded(3);
+    /// assert_eq!(split.left.text(), "ab");
+    /// assert_eq!(split.right.text(), "漢字");
+    /// assert_eq!(split.left_cells, 2);
+    /// assert!(split.split_wide_char);
+    /// ```
+    pub fn split_at_width_padded(self, max_width: usize) -> WidthSplit {
         if self.is_control() {
-            return (self, Segment::new(String::new(), Style::default()));
+            return WidthSplit {
+                left: self,
+                right: Segment::new(String::new(), Style::default()),
+                left_cells: 0,
+                split_wide_char: false,
+            };
         }
 
-        let mut current_width = 0;
-        let mut split_pos = 0;
+        let max_width = Cells(max_width);
+        if self.cells() <= max_width {
+            // Entire segment fits
+            return WidthSplit {
+                left_cells: self.cell_length(),
+                left: self,
+                right: Segment::new(String::new(), Style::default()),
+                split_wide_char: false,
+            };
+        }
 
+        let mut current_width = Cells::ZERO;
+        let mut split_pos = 0;
         for (char_idx, ch) in self.text.char_indices() {
-            let char_width = ch.width().unwrap_or(0);
+            let char_width = Cells::of_char(ch, WidthPolicy::Standard);
             if current_width + char_width > max_width {
                 break;
             }
@@ -268,28 +550,61 @@ impl Segment {
             split_pos = char_idx + ch.len_utf8();
         }
 
-        if split_pos == 0 {
+        // Character widths can undercount a sequence, as when a variation
+        // selector makes the character before it wide, so back off until
+        // the left side measures within the limit as a whole, taking any
+        // zero-width characters along with the character they follow
+        let mut left_cells = Cells::of_str(&self.text[..split_pos], WidthPolicy::Standard);
+        while left_cells > max_width {
+            while let Some((char_idx, ch)) = self.text[..split_pos].char_indices().next_back() {
+                split_pos = char_idx;
+                if Cells::of_char(ch, WidthPolicy::Standard) > Cells::ZERO {
+                    break;
+                }
+            }
+            left_cells = Cells::of_str(&self.text[..split_pos], WidthPolicy::Standard);
+        }
+
+        let (left, right) = if split_pos == 0 {
             // Can't fit any characters
             (Segment::new(String::new(), self.style.clone()), self)
-        } else if split_pos >= self.text.len() {
-            // Entire segment fits
-            (self, Segment::new(String::new(), Style::default()))
         } else {
-            let (left_text, right_text) = self.text.split_at(split_pos);
-            let left = Self {
-                text: left_text.to_string(),
-                style: self.style.clone(),
-                control: self.control,
-            };
-            let right = Self {
-                text: right_text.to_string(),
-                style: self.style,
-                control: None,
-            };
-            (left, right)
+            self.split_at_byte(split_pos)
+        };
+
+        WidthSplit {
+            left,
+            right,
+            left_cells: left_cells.get(),
+            split_wide_char: left_cells < max_width,
         }
     }
 
+    /// Truncate this segment to at most `max_width` cells.
+    ///
+    /// With `pad` set, a double-width character cut at the boundary is
+    /// replaced by a plain space so the result is exactly `max_width` cells
+    /// whenever the segment was at least that wide.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Segment, Style};
+    ///
+    /// let segment = Segment::new("ab漢字".to_string(), Style::new());
+    /// assert_eq!(segment.clone().truncate_cells(3, true).text(), "ab ");
+    /// assert_eq!(segment.truncate_cells(3, false).text(), "ab");
+    /// ```
+    pub fn truncate_cells(self, max_width: usize, pad: bool) -> Self {
+        let split = self.split_at_width_padded(max_width);
+        let mut left = split.left;
+        if pad && split.split_wide_char {
+            let gap = max_width - split.left_cells;
+            left.text.extend(std::iter::repeat(' ').take(gap));
+        }
+        left
+    }
+
     /// Apply a style to this segment by combining it with the existing style.
     ///
     /// The provided style will override any conflicting attributes in the
@@ -301,7 +616,7 @@ impl Segment {
     /// use luxor::{Segment, Style, Color};
     ///
     /// let mut segment = Segment::new("Hello".to_string(), Style::new().bold());
-    /// segment.apply_style(Style::new().color(Color::rgb(255, 0, 0)));
+    /// segment.apply_style(Style::new().with_color(Color::rgb(255, 0, 0)));
     /// // Segment now has both bold and red color
     /// ```
     pub fn apply_style(&mut self, style: Style) {
@@ -317,32 +632,51 @@ impl Segment {
     ///
     /// let segment = Segment::new(
     ///     "Hello".to_string(),
-    ///     Style::new().bold().color(Color::rgb(255, 0, 0))
+    ///     Style::new().bold().with_color(Color::rgb(255, 0, 0))
     /// );
     /// let output = segment.render(ColorSystem::TrueColor);
     /// // Output includes ANSI codes for bold red text
     /// ```
     pub fn render(&self, color_system: ColorSystem) -> String {
         let mut output = String::new();
+        self.write_with_link_id(&mut output, color_system, None)
+            .expect("writing to a String cannot fail");
+        output
+    }
 
+    /// Write this segment to `out`, marking its link with `link_id` if it
+    /// has one.
+    fn write_with_link_id(
+        &self,
+        out: &mut impl fmt::Write,
+        color_system: ColorSystem,
+        link_id: Option<&str>,
+    ) -> fmt::Result {
         // Add control code if present
         if let Some(control) = self.control {
-            output.push_str(&control.to_ansi());
+            out.write_str(&control.to_ansi())?;
         }
 
         // Add styled text if present
         if !self.text.is_empty() {
+            let link = self.style.link_url();
+            if let Some(url) = link {
+                out.write_str(&ansi::link::open(url, link_id))?;
+            }
             let style_ansi = ansi::style_to_ansi(&self.style, color_system);
             if !style_ansi.is_empty() {
-                output.push_str(&style_ansi);
-                output.push_str(&self.text);
-                output.push_str(ansi::RESET);
+                out.write_str(&style_ansi)?;
+                out.write_str(&self.text)?;
+                out.write_str(ansi::RESET)?;
             } else {
-                output.push_str(&self.text);
+                out.write_str(&self.text)?;
+            }
+            if link.is_some() {
+                out.write_str(ansi::link::CLOSE)?;
             }
         }
 
-        output
+        Ok(())
     }
 
     /// Get the plain text content without any styling or control codes.
@@ -364,64 +698,300 @@ impl Segment {
     }
 }
 
-/// A collection of segments that can be efficiently joined and manipulated.
+/// The result of [`Segment::split_at_width_padded`].
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Segments(Vec<Segment>);
+pub struct WidthSplit {
+    /// The part that fits within the requested width.
+    pub left: Segment,
+    /// The remainder that did not fit.
+    pub right: Segment,
+    /// Display width of `left` in terminal cells.
+    pub left_cells: usize,
+    /// Whether a double-width character straddled the boundary, leaving
+    /// `left` short of the requested width, usually by one cell.
+    pub split_wide_char: bool,
+}
+
+/// A collection of segments that can be efficiently joined and manipulated.
+///
+/// The total width is kept up to date as segments are added and removed,
+/// so [`Segments::width`] does not recount every segment. The one
+/// exception is [`Segments::as_mut_slice`], after which the width is
+/// recounted until the next change made through `Segments` itself.
+///
+/// With the `serde` feature, segments serialize as a plain list of
+/// [`Segment`]s.
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Vec<Segment>", into = "Vec<Segment>")
+)]
+pub struct Segments {
+    segments: Vec<Segment>,
+    /// Total cell width of `segments`, or `None` when it may be stale.
+    width: Option<usize>,
+}
 
 impl Segments {
     /// Create a new empty segments collection.
     pub fn new() -> Self {
-        Self(Vec::new())
+        Self::from_vec(Vec::new())
     }
 
     /// Create segments from a vector of segments.
     pub fn from_vec(segments: Vec<Segment>) -> Self {
-        Self(segments)
+        let width = Some(line_width(&segments));
+        Self { segments, width }
     }
 
     /// Add a segment to the collection.
     pub fn push(&mut self, segment: Segment) {
-        self.0.push(segment);
+        let width = self.width() + segment.cell_length();
+        self.segments.push(segment);
+        self.width = Some(width);
     }
 
     /// Get the segments as a slice.
     pub fn as_slice(&self) -> &[Segment] {
-        &self.0
+        &self.segments
+    }
+
+    /// Iterate over the segments without consuming the collection.
+    pub fn iter(&self) -> std::slice::Iter<'_, Segment> {
+        self.segments.iter()
     }
 
     /// Get a mutable slice of segments.
+    ///
+    /// The segments may change width through it, so [`Segments::width`]
+    /// counts them again until the next change made through `Segments`.
     pub fn as_mut_slice(&mut self) -> &mut [Segment] {
-        &mut self.0
+        self.width = None;
+        &mut self.segments
     }
 
     /// Convert to a vector of segments.
     pub fn into_vec(self) -> Vec<Segment> {
-        self.0
+        self.segments
     }
 
-    /// Get the total display width of all segments.
+    /// Get the total display width of all segments, in cells.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Segment, Segments, Style};
+    ///
+    /// let mut segments = Segments::from_vec(vec![Segment::new("ab", Style::new())]);
+    /// segments.push(Segment::new("漢", Style::new().bold()));
+    /// segments.push(Segment::line());
+    /// assert_eq!(segments.width(), 4);
+    /// ```
+    pub fn width(&self) -> usize {
+        match self.width {
+            Some(width) => {
+                debug_assert_eq!(width, line_width(&self.segments), "stale Segments width");
+                width
+            }
+            None => line_width(&self.segments),
+        }
+    }
+
+    /// Get the total display width of all segments. The same as
+    /// [`Segments::width`].
     pub fn cell_length(&self) -> usize {
-        self.0.iter().map(|s| s.cell_length()).sum()
+        self.width()
+    }
+
+    /// Get the total display width of all segments, as [`Cells`].
+    pub fn cells(&self) -> Cells {
+        Cells(self.width())
     }
 
     /// Check if the segments collection is empty.
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.segments.is_empty()
     }
 
     /// Get the number of segments.
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.segments.len()
     }
 
     /// Render all segments to a string with ANSI escape sequences.
+    ///
+    /// See [`render_segments`].
     pub fn render(&self, color_system: ColorSystem) -> String {
-        self.0.iter().map(|s| s.render(color_system)).collect()
+        render_segments(&self.segments, color_system)
+    }
+
+    /// Render all segments into `out`, without building an intermediate
+    /// string. See [`render_segments_to`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{ColorSystem, Segment, Segments, Style};
+    ///
+    /// let segments = Segments::from_vec(vec![Segment::new("Hi", Style::new().italic())]);
+    /// let mut output = String::new();
+    /// segments.render_to(&mut output, ColorSystem::TrueColor).unwrap();
+    /// assert_eq!(output, segments.render(ColorSystem::TrueColor));
+    /// ```
+    pub fn render_to(&self, out: &mut impl fmt::Write, color_system: ColorSystem) -> fmt::Result {
+        render_segments_to(&self.segments, out, color_system)
+    }
+
+    /// Render all segments to a byte sink. See [`write_segments_to`].
+    pub fn write_to(&self, out: &mut impl io::Write, color_system: ColorSystem) -> io::Result<()> {
+        write_segments_to(&self.segments, out, color_system)
+    }
+
+    /// Shorten the segments in place to at most `max_width` cells.
+    ///
+    /// Segments past the limit are removed, and the one crossing it is cut
+    /// with [`Segment::truncate_cells`], without padding. Zero-width
+    /// segments up to the limit, such as control codes, are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Segment, Segments, Style};
+    ///
+    /// let mut segments = Segments::from_vec(vec![
+    ///     Segment::new("abc", Style::new()),
+    ///     Segment::new("def", Style::new().bold()),
+    /// ]);
+    /// segments.truncate_cells(4);
+    /// assert_eq!(segments.plain_text(), "abcd");
+    /// assert_eq!(segments.len(), 2);
+    /// ```
+    pub fn truncate_cells(&mut self, max_width: usize) {
+        let mut remaining = max_width;
+        let Some(cut) = self.segments.iter().position(|segment| {
+            let width = segment.cell_length();
+            if width > remaining {
+                return true;
+            }
+            remaining -= width;
+            false
+        }) else {
+            return;
+        };
+        self.segments.truncate(cut + 1);
+        let mut width = max_width - remaining;
+        if let Some(last) = self.segments.pop() {
+            let last = last.truncate_cells(remaining, false);
+            if !last.text.is_empty() || last.control.is_some() {
+                width += last.cell_length();
+                self.segments.push(last);
+            }
+        }
+        self.width = Some(width);
+    }
+
+    /// Split the segments into lines at embedded newlines, keeping styles
+    /// and control segments. See [`render_utils::split_lines`]; lines can
+    /// be fitted to a width with [`render_utils::adjust_line_length`].
+    ///
+    /// [`render_utils::split_lines`]: crate::render_utils::split_lines
+    /// [`render_utils::adjust_line_length`]: crate::render_utils::adjust_line_length
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Segment, Segments, Style};
+    ///
+    /// let segments = Segments::from_vec(vec![Segment::new("ab\ncd", Style::new().bold())]);
+    /// let lines = segments.split_lines();
+    /// assert_eq!(lines[1], vec![Segment::new("cd", Style::new().bold())]);
+    /// ```
+    pub fn split_lines(&self) -> Vec<Vec<Segment>> {
+        crate::render_utils::split_lines(self.segments.clone())
+    }
+
+    /// Combine `style` onto the style of every segment, overriding the
+    /// attributes it sets. See [`Segment::apply_style`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Segment, Segments, Style};
+    ///
+    /// let mut segments = Segments::from_vec(vec![
+    ///     Segment::new("a", Style::new().italic()),
+    ///     Segment::new("b", Style::new()),
+    /// ]);
+    /// segments.apply_style(Style::new().bold());
+    /// assert!(segments.iter().all(|segment| segment.style().bold == Some(true)));
+    /// assert_eq!(segments.as_slice()[0].style().italic, Some(true));
+    /// ```
+    pub fn apply_style(&mut self, style: Style) {
+        // Styles take no cells, so the width is unchanged
+        for segment in &mut self.segments {
+            segment.apply_style(style.clone());
+        }
     }
 
     /// Get the plain text content of all segments combined.
     pub fn plain_text(&self) -> String {
-        self.0.iter().map(|s| s.plain_text()).collect()
+        self.segments.iter().map(|s| s.plain_text()).collect()
+    }
+
+    /// Serialize the segments to pretty-printed JSON, e.g. for a golden file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Segment, Segments, Style};
+    ///
+    /// let segments = Segments::from_vec(vec![Segment::new("Hi", Style::new().bold())]);
+    /// let json = segments.to_json().unwrap();
+    /// assert!(json.contains(r#""style": "bold""#));
+    /// assert_eq!(Segments::from_json(&json).unwrap(), segments);
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| crate::LuxorError::serialization(e.to_string()))
+    }
+
+    /// Deserialize segments from JSON produced by [`Segments::to_json`].
+    ///
+    /// Fails with a [`LuxorError::Serialization`](crate::LuxorError::Serialization)
+    /// error naming the problem, such as an unknown control code or an
+    /// unparseable style.
+    #[cfg(feature = "serde_json")]
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        serde_json::from_str(json).map_err(|e| crate::LuxorError::serialization(e.to_string()))
+    }
+}
+
+impl fmt::Debug for Segments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Segments").field(&self.segments).finish()
+    }
+}
+
+/// Segments are equal when they hold equal segments.
+impl PartialEq for Segments {
+    fn eq(&self, other: &Self) -> bool {
+        self.segments == other.segments
+    }
+}
+
+impl Eq for Segments {}
+
+impl From<Vec<Segment>> for Segments {
+    fn from(segments: Vec<Segment>) -> Self {
+        Self::from_vec(segments)
+    }
+}
+
+impl From<Segments> for Vec<Segment> {
+    fn from(segments: Segments) -> Self {
+        segments.segments
     }
 }
 
@@ -433,7 +1003,17 @@ impl Default for Segments {
 
 impl FromIterator<Segment> for Segments {
     fn from_iter<T: IntoIterator<Item = Segment>>(iter: T) -> Self {
-        Self(iter.into_iter().collect())
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl Extend<Segment> for Segments {
+    fn extend<I: IntoIterator<Item = Segment>>(&mut self, iter: I) {
+        let mut width = self.width();
+        self.segments.extend(iter.into_iter().inspect(|segment| {
+            width += segment.cell_length();
+        }));
+        self.width = Some(width);
     }
 }
 
@@ -442,7 +1022,16 @@ impl IntoIterator for Segments {
     type IntoIter = std::vec::IntoIter<Segment>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.segments.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Segments {
+    type Item = &'a Segment;
+    type IntoIter = std::slice::Iter<'a, Segment>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.segments.iter()
     }
 }
 
@@ -464,6 +1053,14 @@ mod tests {
         assert!(!segment.is_empty());
     }
 
+    #[test]
+    fn test_segment_line() {
+        let segment = Segment::line();
+        assert_eq!(segment.text(), "\n");
+        assert!(segment.style().is_empty());
+        assert!(segment.is_text());
+    }
+
     #[test]
     fn test_segment_control() {
         let segment = Segment::control(ControlCode::Clear);
@@ -504,10 +1101,93 @@ mod tests {
         assert_eq!(left.cell_length(), 5);
     }
 
+    #[test]
+    fn test_segment_split_at_width_wide_char() {
+        let split = Segment::new("ab漢字".to_string(), Style::new()).split_at_width_padded(3);
+        assert_eq!(split.left.text(), "ab");
+        assert_eq!(split.right.text(), "漢字");
+        assert_eq!(split.left_cells, 2);
+        assert!(split.split_wide_char);
+
+        let split = Segment::new("ab漢字".to_string(), Style::new()).split_at_width_padded(4);
+        assert_eq!(split.left.text(), "ab漢");
+        assert_eq!(split.left_cells, 4);
+        assert!(!split.split_wide_char);
+    }
+
+    /// Multi-byte samples: combining marks, emoji with a skin tone, a ZWJ
+    /// family, a variation selector, a flag, and CJK.
+    const UNICODE_SAMPLES: &[&str] = &[
+        "héllo",
+        "e\u{301}e\u{301}x",
+        "hi 👍🏽 there",
+        "👨\u{200d}👩\u{200d}👧 family",
+        "a❤\u{fe0f}b",
+        "🇯🇵 flag",
+        "漢字かなabc",
+    ];
+
+    #[test]
+    fn test_segment_split_at_char_counts_characters() {
+        let (left, right) = Segment::new("héllo".to_string(), Style::new()).split_at_char(4);
+        assert_eq!((left.text(), right.text()), ("héll", "o"));
+        let (left, right) = Segment::new("héllo".to_string(), Style::new()).split_at_char(5);
+        assert_eq!((left.text(), right.text()), ("héllo", ""));
+        let (left, right) = Segment::new("漢字".to_string(), Style::new()).split_at_char(1);
+        assert_eq!((left.text(), right.text()), ("漢", "字"));
+
+        for text in UNICODE_SAMPLES {
+            let count = text.chars().count();
+            for pos in 0..=count + 1 {
+                let (left, right) = Segment::new(text.to_string(), Style::new()).split_at_char(pos);
+                assert_eq!(format!("{}{}", left.text(), right.text()), *text);
+                assert_eq!(left.text().chars().count(), pos.min(count));
+            }
+        }
+    }
+
+    #[test]
+    fn test_segment_split_at_width_unicode() {
+        for text in UNICODE_SAMPLES {
+            for width in 0..=text.width() + 1 {
+                let segment = Segment::new(text.to_string(), Style::new());
+                let split = segment.split_at_width_padded(width);
+                assert_eq!(
+                    format!("{}{}", split.left.text(), split.right.text()),
+                    *text
+                );
+                assert!(split.left.cell_length() <= width, "{text:?} at {width}");
+                assert_eq!(split.left_cells, split.left.cell_length());
+            }
+        }
+
+        // A variation selector widens the heart, which then no longer fits
+        let split = Segment::new("❤\u{fe0f}".to_string(), Style::new()).split_at_width_padded(1);
+        assert_eq!((split.left.text(), split.right.text()), ("", "❤\u{fe0f}"));
+        assert!(split.split_wide_char);
+        // Combining marks stay with the character they follow
+        let (left, right) = Segment::new("e\u{301}x".to_string(), Style::new()).split_at_width(1);
+        assert_eq!((left.text(), right.text()), ("e\u{301}", "x"));
+    }
+
+    #[test]
+    fn test_segment_truncate_cells() {
+        let segment = Segment::new("ab漢字".to_string(), Style::new().bold());
+
+        let padded = segment.clone().truncate_cells(3, true);
+        assert_eq!(padded.text(), "ab ");
+        assert_eq!(padded.cell_length(), 3);
+        assert_eq!(padded.style().bold, Some(true));
+
+        assert_eq!(segment.clone().truncate_cells(3, false).text(), "ab");
+        assert_eq!(segment.clone().truncate_cells(1, true).text(), "a");
+        assert_eq!(segment.truncate_cells(10, true).text(), "ab漢字");
+    }
+
     #[test]
     fn test_segment_apply_style() {
         let mut segment = Segment::new("Hello".to_string(), Style::new().bold());
-        segment.apply_style(Style::new().color(Color::rgb(255, 0, 0)));
+        segment.apply_style(Style::new().with_color(Color::rgb(255, 0, 0)));
 
         assert_eq!(segment.style().bold, Some(true));
         assert_eq!(segment.style().color, Some(Color::rgb(255, 0, 0)));
@@ -517,7 +1197,7 @@ mod tests {
     fn test_segment_render() {
         let segment = Segment::new(
             "Hello".to_string(),
-            Style::new().bold().color(Color::rgb(255, 0, 0)),
+            Style::new().bold().with_color(Color::rgb(255, 0, 0)),
         );
         let output = segment.render(ColorSystem::TrueColor);
 
@@ -555,4 +1235,163 @@ mod tests {
         assert_eq!(segments.len(), 2);
         assert_eq!(segments.plain_text(), "Hello World");
     }
+
+    #[test]
+    fn test_segment_link_is_percent_encoded() {
+        let segment = Segment::new("docs", Style::new().with_link("https://a.b/my docs\x07"));
+        assert_eq!(
+            segment.render(ColorSystem::TrueColor),
+            "\x1b]8;;https://a.b/my%20docs%07\x1b\\docs\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_render_segments_link_ids() {
+        let link = Style::new().with_link("https://example.com");
+        let other = Style::new().with_link("https://other.org");
+        let segments = vec![
+            Segment::new("one", link.clone()),
+            Segment::new(" ", Style::new()),
+            Segment::new("two", other),
+            Segment::line(),
+            Segment::new("three", link),
+        ];
+        let output = render_segments(&segments, ColorSystem::TrueColor);
+        // Only the link on both lines gets an id
+        assert_eq!(output.matches("\x1b]8;;https://other.org").count(), 1);
+        let ids: Vec<&str> = output
+            .split("\x1b]8;id=")
+            .skip(1)
+            .map(|rest| rest.split(';').next().unwrap())
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], ids[1]);
+
+        // A separate render is a separate link
+        let again = render_segments(&segments, ColorSystem::TrueColor);
+        assert!(!again.contains(&format!("id={};", ids[0])));
+    }
+
+    #[test]
+    fn test_segment_debug_compact() {
+        let plain = Segment::new("ab", Style::new());
+        let styled = Segment::new("ab", Style::parse("bold red").unwrap());
+        let control = Segment::control(ControlCode::CursorUp(2));
+        assert_eq!(format!("{:?}", plain), r#"Segment("ab")"#);
+        assert_eq!(format!("{:?}", styled), r#"Segment("ab", bold red)"#);
+        assert_eq!(format!("{:?}", control), r#"Segment("", CursorUp(2))"#);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_segments_json_round_trip() {
+        let segments = Segments::from_vec(vec![
+            Segment::new("Hello", Style::parse("bold red on color(208)").unwrap()),
+            Segment::line(),
+            Segment::control(ControlCode::Bell),
+            Segment::control(ControlCode::CursorMoveTo { row: 2, col: 5 }),
+            Segment::with_control("x".to_string(), Style::new(), ControlCode::CursorUp(1)),
+        ]);
+        let json = segments.to_json().unwrap();
+        assert_eq!(Segments::from_json(&json).unwrap(), segments);
+
+        let compact: String = json.split_whitespace().collect();
+        assert!(
+            compact.starts_with(r#"[{"text":"Hello","style":"boldredoncolor(208)"},{"text":"\n"}"#)
+        );
+        assert!(compact.contains(r#"{"text":"","control":{"type":"bell"}}"#));
+        assert!(
+            compact.contains(r#""control":{"type":"cursor_move_to","value":{"row":2,"col":5}}"#)
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_segments_json_errors() {
+        let error = Segments::from_json(r#"[{"text":"","control":{"type":"blink_twice"}}]"#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("unknown variant `blink_twice`"), "{}", error);
+
+        let error = Segments::from_json(r#"[{"text":"a","style":"bold nonsense"}]"#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("nonsense"), "{}", error);
+    }
+
+    #[test]
+    fn test_segments_render_to_matches_render() {
+        let link = Style::new().with_link("https://example.com");
+        let segments = Segments::from_vec(vec![
+            Segment::new(
+                "Hello",
+                Style::new().bold().with_color(Color::rgb(255, 0, 0)),
+            ),
+            Segment::control(ControlCode::Bell),
+            Segment::new("exam", link.clone()),
+            Segment::line(),
+            Segment::new("ple", link),
+        ]);
+        for color_system in [ColorSystem::Standard, ColorSystem::TrueColor] {
+            let rendered = segments.render(color_system);
+            let mut output = String::new();
+            segments.render_to(&mut output, color_system).unwrap();
+            // Link ids differ between renders, so compare around them
+            let strip_ids = |s: &str| s.split("id=").map(|part| part.len()).sum::<usize>();
+            assert_eq!(strip_ids(&output), strip_ids(&rendered));
+            assert_eq!(crate::ansi::strip_ansi(&output), "Hello\u{7}exam\nple");
+
+            let plain = Segments::from_vec(segments.as_slice()[..2].to_vec());
+            let mut bytes = Vec::new();
+            plain.write_to(&mut bytes, color_system).unwrap();
+            assert_eq!(
+                String::from_utf8(bytes).unwrap(),
+                plain.render(color_system)
+            );
+        }
+    }
+
+    #[test]
+    fn test_segments_iter_borrows() {
+        let segments = Segments::from_vec(vec![
+            Segment::new("a", Style::new()),
+            Segment::new("bc", Style::new()),
+        ]);
+        let mut widths = Vec::new();
+        for segment in &segments {
+            widths.push(segment.cell_length());
+        }
+        assert_eq!(widths, vec![1, 2]);
+        assert_eq!(segments.iter().count(), segments.len());
+    }
+
+    #[test]
+    fn test_segments_truncate_cells() {
+        let frame = Segments::from_vec(vec![
+            Segment::new("ab", Style::new()),
+            Segment::control(ControlCode::Bell),
+            Segment::new("漢字", Style::new().bold()),
+            Segment::new("c", Style::new()),
+        ]);
+
+        let mut segments = frame.clone();
+        segments.truncate_cells(10);
+        assert_eq!(segments, frame);
+
+        // The control code at the limit is kept
+        let mut segments = frame.clone();
+        segments.truncate_cells(2);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments.plain_text(), "ab");
+
+        // A cut double-width character is dropped, not padded
+        let mut segments = frame.clone();
+        segments.truncate_cells(5);
+        assert_eq!(segments.plain_text(), "ab漢");
+        assert_eq!(segments.cell_length(), 4);
+
+        let mut segments = frame;
+        segments.truncate_cells(0);
+        assert!(segments.is_empty());
+    }
 }
@@ -0,0 +1,593 @@
+//! Select - pick one or several items from a list with the keyboard.
+//!
+//! [`SelectState`] holds everything about the list (the cursor, the
+//! filter, the chosen items and the scrolled viewport) and renders it; keys
+//! are fed to it with [`SelectState::handle`], so it can be driven and
+//! checked without a terminal. `SelectPrompt`, behind the `terminal`
+//! feature, runs the state in raw mode, redrawing it in place after every
+//! key.
+//!
+//! The keys are:
+//!
+//! - Up/Down or `k`/`j` move the cursor; PageUp/PageDown move a page, and
+//!   Home/End go to the first and last item.
+//! - Space toggles the item under the cursor when several may be chosen.
+//! - `/` starts filtering: typed characters narrow the list to the items
+//!   containing them, ignoring case, and Esc drops the filter again.
+//! - Enter confirms, and Esc (outside the filter) or Ctrl-C cancels.
+
+use crate::{Console, RenderOptions, Renderable, Result, Segment, Style, Text};
+use std::collections::BTreeSet;
+
+/// The number of rows shown by default.
+const DEFAULT_HEIGHT: usize = 10;
+
+/// A key fed to a [`SelectState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectKey {
+    /// Move the cursor up.
+    Up,
+    /// Move the cursor down.
+    Down,
+    /// Move the cursor up a page.
+    PageUp,
+    /// Move the cursor down a page.
+    PageDown,
+    /// Move the cursor to the first item.
+    Home,
+    /// Move the cursor to the last item.
+    End,
+    /// A typed character.
+    Char(char),
+    /// Delete the last character of the filter.
+    Backspace,
+    /// Confirm the selection.
+    Enter,
+    /// Drop the filter, or cancel without one.
+    Escape,
+    /// Ctrl-C.
+    Interrupt,
+}
+
+/// What a [`SelectState`] made of a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectOutcome {
+    /// The selection goes on.
+    Pending,
+    /// The selection was confirmed with the indices of the chosen items,
+    /// in ascending order. A single select has exactly one.
+    Confirmed(Vec<usize>),
+    /// The selection was cancelled with Esc.
+    Cancelled,
+    /// The selection was interrupted with Ctrl-C.
+    Interrupted,
+}
+
+/// The state of a selection list.
+///
+/// Rows are drawn below an optional prompt, with the cursor row in the
+/// selection style of [`RenderState`](crate::RenderState) and the filter
+/// highlighted in its match style. When there are more items than rows,
+/// the view scrolls to keep the cursor in it.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{SelectKey, SelectOutcome, SelectState};
+///
+/// let mut state = SelectState::new(["red", "green", "blue"]);
+/// state.handle(SelectKey::Char('/'));
+/// state.handle(SelectKey::Char('b'));
+/// assert_eq!(state.visible_items().collect::<Vec<_>>(), [2]);
+/// assert_eq!(state.handle(SelectKey::Enter), SelectOutcome::Confirmed(vec![2]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SelectState {
+    items: Vec<String>,
+    prompt: Option<Text>,
+    multi: bool,
+    height: usize,
+    /// The indices of the items passing the filter.
+    matches: Vec<usize>,
+    /// The position of the cursor in `matches`.
+    cursor: usize,
+    /// The position in `matches` of the first row shown.
+    offset: usize,
+    filter: Option<String>,
+    chosen: BTreeSet<usize>,
+}
+
+impl SelectState {
+    /// Create a single select over `items`, with the cursor on the first.
+    pub fn new<I, S>(items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let items: Vec<String> = items.into_iter().map(Into::into).collect();
+        Self {
+            matches: (0..items.len()).collect(),
+            items,
+            prompt: None,
+            multi: false,
+            height: DEFAULT_HEIGHT,
+            cursor: 0,
+            offset: 0,
+            filter: None,
+            chosen: BTreeSet::new(),
+        }
+    }
+
+    /// Set the line drawn above the items.
+    pub fn with_prompt(mut self, prompt: impl Into<Text>) -> Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Set whether several items may be chosen.
+    pub fn with_multi(mut self, multi: bool) -> Self {
+        self.multi = multi;
+        self
+    }
+
+    /// Set the number of rows shown, at least one.
+    pub fn with_height(mut self, height: usize) -> Self {
+        self.height = height.max(1);
+        self.scroll_to_cursor();
+        self
+    }
+
+    /// Set the items chosen to begin with, for a multi-select.
+    pub fn with_chosen(mut self, chosen: impl IntoIterator<Item = usize>) -> Self {
+        let len = self.items.len();
+        self.chosen = chosen.into_iter().filter(|&index| index < len).collect();
+        self
+    }
+
+    /// Get the index of the item under the cursor, if any item passes the
+    /// filter.
+    pub fn cursor(&self) -> Option<usize> {
+        self.matches.get(self.cursor).copied()
+    }
+
+    /// Get the indices of the chosen items, in ascending order.
+    pub fn chosen(&self) -> impl Iterator<Item = usize> + '_ {
+        self.chosen.iter().copied()
+    }
+
+    /// Get the filter being typed, if filtering.
+    pub fn filter(&self) -> Option<&str> {
+        self.filter.as_deref()
+    }
+
+    /// Get the indices of the items in the rows shown, top to bottom.
+    pub fn visible_items(&self) -> impl Iterator<Item = usize> + '_ {
+        self.matches
+            .iter()
+            .skip(self.offset)
+            .take(self.height)
+            .copied()
+    }
+
+    /// Apply `key`, returning whether the selection is over.
+    pub fn handle(&mut self, key: SelectKey) -> SelectOutcome {
+        match key {
+            SelectKey::Interrupt => return SelectOutcome::Interrupted,
+            SelectKey::Escape if self.filter.is_some() => self.set_filter(None),
+            SelectKey::Escape => return SelectOutcome::Cancelled,
+            SelectKey::Enter => return self.confirm(),
+            SelectKey::Up => self.move_cursor(-1),
+            SelectKey::Down => self.move_cursor(1),
+            SelectKey::PageUp => self.move_cursor(-(self.height as isize)),
+            SelectKey::PageDown => self.move_cursor(self.height as isize),
+            SelectKey::Home => self.move_cursor(isize::MIN),
+            SelectKey::End => self.move_cursor(isize::MAX),
+            SelectKey::Backspace => {
+                if let Some(mut filter) = self.filter.clone() {
+                    filter.pop();
+                    self.set_filter(Some(filter));
+                }
+            }
+            SelectKey::Char(c) if self.filter.is_some() => {
+                let mut filter = self.filter.clone().unwrap_or_default();
+                filter.push(c);
+                self.set_filter(Some(filter));
+            }
+            SelectKey::Char('/') => self.set_filter(Some(String::new())),
+            SelectKey::Char('k') => self.move_cursor(-1),
+            SelectKey::Char('j') => self.move_cursor(1),
+            SelectKey::Char(' ') if self.multi => {
+                if let Some(index) = self.cursor() {
+                    if !self.chosen.remove(&index) {
+                        self.chosen.insert(index);
+                    }
+                }
+            }
+            SelectKey::Char(_) => {}
+        }
+        SelectOutcome::Pending
+    }
+
+    fn confirm(&self) -> SelectOutcome {
+        if self.multi {
+            return SelectOutcome::Confirmed(self.chosen().collect());
+        }
+        match self.cursor() {
+            Some(index) => SelectOutcome::Confirmed(vec![index]),
+            // Nothing passes the filter, so there is nothing to confirm
+            None => SelectOutcome::Pending,
+        }
+    }
+
+    /// Move the cursor by `rows`, stopping at either end.
+    fn move_cursor(&mut self, rows: isize) {
+        let last = self.matches.len().saturating_sub(1);
+        self.cursor = match rows < 0 {
+            true => self.cursor.saturating_sub(rows.unsigned_abs()),
+            false => self.cursor.saturating_add(rows as usize).min(last),
+        };
+        self.scroll_to_cursor();
+    }
+
+    /// Replace the filter, keeping the cursor on the same item if it still
+    /// passes.
+    fn set_filter(&mut self, filter: Option<String>) {
+        let current = self.cursor();
+        let needle = filter.as_deref().unwrap_or_default().to_lowercase();
+        self.matches = (0..self.items.len())
+            .filter(|&index| self.items[index].to_lowercase().contains(&needle))
+            .collect();
+        self.filter = filter;
+        self.cursor = current
+            .and_then(|index| self.matches.iter().position(|&m| m == index))
+            .unwrap_or(0);
+        self.scroll_to_cursor();
+    }
+
+    /// Scroll the least needed to show the cursor, without leaving rows
+    /// empty past the end.
+    fn scroll_to_cursor(&mut self) {
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + self.height {
+            self.offset = self.cursor + 1 - self.height;
+        }
+        self.offset = self
+            .offset
+            .min(self.matches.len().saturating_sub(self.height));
+    }
+
+    /// Render the row of the item at `position` in `matches`.
+    fn render_row(&self, position: usize, options: &RenderOptions) -> Vec<Segment> {
+        let index = self.matches[position];
+        let (pointer, marks) = match options.ascii_only {
+            true => ("> ", ["[ ] ", "[x] "]),
+            false => ("❯ ", ["○ ", "◉ "]),
+        };
+        let on_cursor = position == self.cursor;
+        let mut prefix = String::from(if on_cursor { pointer } else { "  " });
+        if self.multi {
+            prefix.push_str(marks[usize::from(self.chosen.contains(&index))]);
+        }
+
+        let mut state = options.state.clone().with_selected([position]);
+        state.search = self.filter.clone().filter(|filter| !filter.is_empty());
+        let mut label = Text::new(self.items[index].as_str());
+        state.highlight(&mut label);
+        let mut row = Text::new(&prefix);
+        row.append_text(label);
+        row.truncate(options.get_max_width(), &options.ellipsis);
+
+        let segments = row.to_segments();
+        if !on_cursor {
+            return segments;
+        }
+        segments
+            .into_iter()
+            .map(|segment| {
+                let style = state.selection_style(segment.style().clone());
+                Segment::new(segment.text(), style)
+            })
+            .collect()
+    }
+}
+
+impl Renderable for SelectState {
+    fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let width = options.get_max_width();
+        let mut lines = Vec::new();
+        if self.prompt.is_some() || self.filter.is_some() {
+            let mut header = self.prompt.clone().unwrap_or_else(|| Text::new(""));
+            if let Some(filter) = &self.filter {
+                if !header.plain().is_empty() {
+                    header.append(" ");
+                }
+                header.append_text(Text::new(&format!("/{filter}")).with_style(Style::new().dim()));
+            }
+            header.truncate(width, &options.ellipsis);
+            lines.push(header.to_segments());
+        }
+        if self.matches.is_empty() {
+            let mut empty = Text::new("  no matches").with_style(Style::new().dim());
+            empty.truncate(width, &options.ellipsis);
+            lines.push(empty.to_segments());
+        }
+        let end = (self.offset + self.height).min(self.matches.len());
+        for position in self.offset..end {
+            options.check_cancelled()?;
+            lines.push(self.render_row(position, options));
+        }
+        Ok(crate::render_utils::frame_lines(lines))
+    }
+}
+
+#[cfg(feature = "terminal")]
+pub use prompt::SelectPrompt;
+
+#[cfg(feature = "terminal")]
+mod prompt {
+    use super::{SelectKey, SelectOutcome, SelectState};
+    use crate::{Console, ControlCode, LuxorError, Result, Segment, Segments, Style, render_utils};
+    use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+    /// The indices of the chosen items, or `None` when cancelled.
+    type Chosen = Option<Vec<usize>>;
+
+    /// An interactive selection list, run in the terminal.
+    ///
+    /// The list is drawn where the cursor is and redrawn in place after
+    /// every key, then erased when the selection is over. The terminal is
+    /// in raw mode meanwhile, and is restored however the prompt ends.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use luxor::{Console, SelectPrompt, SelectState};
+    ///
+    /// let state = SelectState::new(["apple", "pear", "plum"]).with_prompt("Fruit?");
+    /// match SelectPrompt::new(state).run(&Console::new()).unwrap() {
+    ///     Some(chosen) => println!("picked {chosen:?}"),
+    ///     None => println!("cancelled"),
+    /// }
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct SelectPrompt {
+        state: SelectState,
+    }
+
+    impl SelectPrompt {
+        /// Create a prompt running `state`.
+        pub fn new(state: SelectState) -> Self {
+            Self { state }
+        }
+
+        /// Run the prompt until it is confirmed or cancelled.
+        ///
+        /// Returns the indices of the chosen items, or `None` when cancelled
+        /// with Esc.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`LuxorError::Cancelled`] on Ctrl-C, and an error if the
+        /// terminal cannot be put in raw mode or read from.
+        pub fn run(mut self, console: &Console) -> Result<Chosen> {
+            let _raw = RawMode::enable()?;
+            console.hide_cursor()?;
+            let outcome = self.event_loop(console);
+            console.show_cursor()?;
+            match outcome? {
+                SelectOutcome::Confirmed(chosen) => Ok(Some(chosen)),
+                SelectOutcome::Interrupted => Err(LuxorError::Cancelled),
+                _ => Ok(None),
+            }
+        }
+
+        fn event_loop(&mut self, console: &Console) -> Result<SelectOutcome> {
+            let mut drawn = draw(console, Some(&self.state), 0)?;
+            let outcome = loop {
+                let Event::Key(key) = event::read()? else {
+                    continue;
+                };
+                let Some(key) = select_key(key) else {
+                    continue;
+                };
+                match self.state.handle(key) {
+                    SelectOutcome::Pending => drawn = draw(console, Some(&self.state), drawn)?,
+                    outcome => break outcome,
+                }
+            };
+            draw(console, None, drawn)?;
+            Ok(outcome)
+        }
+    }
+
+    /// Draw `state` over the `drawn` lines drawn before, returning how many
+    /// lines are drawn now. Without a state the old lines are blanked and
+    /// the cursor left at the first.
+    fn draw(console: &Console, state: Option<&SelectState>, drawn: usize) -> Result<usize> {
+        // One cell spare, so a full line never wraps
+        let width = console.width().saturating_sub(1);
+        let options = console.render_options().clone().with_max_width(width);
+        let lines = match state {
+            Some(state) => render_utils::split_lines(console.render(state.clone(), &options)?),
+            None => Vec::new(),
+        };
+        let height = lines.len().max(drawn);
+
+        let mut frame = Vec::new();
+        if drawn > 1 {
+            frame.push(Segment::control(ControlCode::CursorUp(drawn - 1)));
+        }
+        frame.push(Segment::control(ControlCode::CarriageReturn));
+        let blank = std::iter::repeat_with(Vec::new);
+        for (index, line) in lines.into_iter().chain(blank).take(height).enumerate() {
+            if index > 0 {
+                // Raw mode does not turn a newline into a carriage return
+                frame.push(Segment::new("\r\n", Style::new()));
+            }
+            frame.extend(render_utils::adjust_line_length(line, width, Style::new()));
+        }
+        if state.is_none() && height > 1 {
+            frame.push(Segment::control(ControlCode::CursorUp(height - 1)));
+            frame.push(Segment::control(ControlCode::CarriageReturn));
+        }
+        console.print(Segments::from_vec(frame))?;
+        Ok(height)
+    }
+
+    /// Map a terminal key press to a select key.
+    fn select_key(key: KeyEvent) -> Option<SelectKey> {
+        if key.kind == KeyEventKind::Release {
+            return None;
+        }
+        Some(match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                SelectKey::Interrupt
+            }
+            KeyCode::Char(c) => SelectKey::Char(c),
+            KeyCode::Up => SelectKey::Up,
+            KeyCode::Down => SelectKey::Down,
+            KeyCode::PageUp => SelectKey::PageUp,
+            KeyCode::PageDown => SelectKey::PageDown,
+            KeyCode::Home => SelectKey::Home,
+            KeyCode::End => SelectKey::End,
+            KeyCode::Backspace => SelectKey::Backspace,
+            KeyCode::Enter => SelectKey::Enter,
+            KeyCode::Esc => SelectKey::Escape,
+            _ => return None,
+        })
+    }
+
+    /// Raw mode for as long as the guard lives.
+    struct RawMode;
+
+    impl RawMode {
+        fn enable() -> Result<Self> {
+            crossterm::terminal::enable_raw_mode()?;
+            Ok(Self)
+        }
+    }
+
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            let _ = crossterm::terminal::disable_raw_mode();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConsoleOptions, assert_render_eq, testing::render_lines};
+
+    /// Capture `state` as a console of `width` cells prints it, without
+    /// styles.
+    fn frame(state: &SelectState, width: usize) -> String {
+        let options = ConsoleOptions::new()
+            .with_max_width(width)
+            .with_ascii_only(true);
+        let console = Console::with_options(options);
+        let output = console
+            .capture(|console| console.print(state.clone()))
+            .unwrap();
+        crate::ansi::strip_ansi(&output)
+    }
+
+    fn press(state: &mut SelectState, keys: &str) -> SelectOutcome {
+        let mut outcome = SelectOutcome::Pending;
+        for c in keys.chars() {
+            outcome = state.handle(SelectKey::Char(c));
+        }
+        outcome
+    }
+
+    #[test]
+    fn test_navigation_scrolls_viewport() {
+        let items = ["one", "two", "three", "four", "five"];
+        let mut state = SelectState::new(items).with_prompt("Pick").with_height(3);
+        assert_eq!(frame(&state, 20), "Pick\n> one\n  two\n  three");
+
+        press(&mut state, "jjj");
+        assert_eq!(state.cursor(), Some(3));
+        assert_eq!(frame(&state, 20), "Pick\n  two\n  three\n> four");
+
+        state.handle(SelectKey::End);
+        state.handle(SelectKey::Down);
+        assert_eq!(state.cursor(), Some(4));
+        assert_eq!(state.visible_items().collect::<Vec<_>>(), [2, 3, 4]);
+
+        state.handle(SelectKey::PageUp);
+        state.handle(SelectKey::Up);
+        assert_eq!(state.cursor(), Some(0));
+        assert_eq!(frame(&state, 20), "Pick\n> one\n  two\n  three");
+        assert_eq!(
+            state.handle(SelectKey::Enter),
+            SelectOutcome::Confirmed(vec![0])
+        );
+    }
+
+    #[test]
+    fn test_filter_narrows_and_clamps() {
+        let items = ["alpha", "beta", "gamma", "delta"];
+        let mut state = SelectState::new(items).with_height(2);
+        state.handle(SelectKey::End);
+        assert_eq!(state.cursor(), Some(3));
+
+        // The cursor stays on delta, which still passes
+        press(&mut state, "/ta");
+        assert_eq!(frame(&state, 20), "/ta\n  beta\n> delta");
+
+        press(&mut state, "x");
+        assert_eq!(state.cursor(), None);
+        assert_eq!(frame(&state, 20), "/tax\n  no matches");
+        assert_eq!(state.handle(SelectKey::Enter), SelectOutcome::Pending);
+
+        // With nothing under it, the cursor goes back to the top
+        state.handle(SelectKey::Backspace);
+        assert_eq!(state.cursor(), Some(1));
+        state.handle(SelectKey::Backspace);
+        state.handle(SelectKey::Backspace);
+        assert_eq!(state.filter(), Some(""));
+        assert_eq!(state.cursor(), Some(1));
+
+        // Esc drops the filter first, then cancels
+        assert_eq!(state.handle(SelectKey::Escape), SelectOutcome::Pending);
+        assert_eq!(state.filter(), None);
+        assert_eq!(state.handle(SelectKey::Escape), SelectOutcome::Cancelled);
+    }
+
+    #[test]
+    fn test_multi_select_toggles() {
+        let mut state = SelectState::new(["a", "b", "c"])
+            .with_multi(true)
+            .with_chosen([2, 7]);
+        press(&mut state, " jj ");
+        assert_eq!(frame(&state, 20), "  [x] a\n  [ ] b\n> [ ] c");
+        assert_eq!(
+            state.handle(SelectKey::Enter),
+            SelectOutcome::Confirmed(vec![0])
+        );
+        assert_eq!(
+            state.handle(SelectKey::Interrupt),
+            SelectOutcome::Interrupted
+        );
+    }
+
+    #[test]
+    fn test_cursor_row_styles() {
+        let mut state = SelectState::new(["first", "second"]);
+        press(&mut state, "/sec");
+        let options = RenderOptions::new().with_max_width(6).with_ascii_only(true);
+        let segments = state.render(&Console::new(), &options).unwrap();
+        assert_render_eq!(render_lines(&segments), "/sec\n> sec…");
+        let reversed = Style::on_selection(Style::new(), Style::new());
+        let cursor_row: Vec<_> = segments.iter().skip_while(|s| s.text() != "\n").collect();
+        assert!(
+            cursor_row[1..]
+                .iter()
+                .all(|s| s.style().reverse == reversed.reverse)
+        );
+        let matched = cursor_row.iter().find(|s| s.text() == "sec").unwrap();
+        assert_eq!(matched.style().bold, Some(true));
+    }
+}
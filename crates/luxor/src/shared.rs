@@ -0,0 +1,195 @@
+//! Shared - a console that several threads print to.
+//!
+//! [`Console`] is `Send` and `Sync`, and each print reaches the output in a
+//! single write, so `&Console` can already be used from several threads.
+//! [`SharedConsole`] is a cheap, cloneable handle for that case. It also
+//! gives threads access to the methods that need `&mut Console`, and lets
+//! a thread hold the console across several prints that must stay together.
+
+use crate::{Console, Renderable, Result};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A console shared between threads.
+///
+/// Clones are handles to the same console. Each print locks the console
+/// for the whole render and write, so the lines of one print are never
+/// interleaved with those of another thread.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, ConsoleOptions, SharedConsole};
+/// use std::thread;
+///
+/// let console = SharedConsole::new(Console::with_options(
+///     ConsoleOptions::new().with_color(false),
+/// ));
+/// console.begin_capture();
+/// let workers: Vec<_> = (0..2)
+///     .map(|index| {
+///         let console = console.clone();
+///         thread::spawn(move || console.println(format!("worker {}", index).as_str()))
+///     })
+///     .collect();
+/// for worker in workers {
+///     worker.join().unwrap().unwrap();
+/// }
+/// assert_eq!(console.end_capture().lines().count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedConsole {
+    console: Arc<Mutex<Console>>,
+}
+
+impl SharedConsole {
+    /// Share `console` between threads.
+    pub fn new(console: Console) -> Self {
+        Self {
+            console: Arc::new(Mutex::new(console)),
+        }
+    }
+
+    /// Lock the console for exclusive use.
+    ///
+    /// Output from other handles waits until the guard is dropped, so
+    /// several prints made through the guard appear together.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Console, SharedConsole};
+    ///
+    /// let console = SharedConsole::new(Console::new());
+    /// let console = console.lock();
+    /// console.begin_capture();
+    /// console.println("header").unwrap();
+    /// console.println("body").unwrap();
+    /// assert_eq!(console.end_capture(), "header\nbody\n");
+    /// ```
+    pub fn lock(&self) -> MutexGuard<'_, Console> {
+        self.console
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Print a renderable. See [`Console::print`].
+    pub fn print<R: Renderable>(&self, renderable: R) -> Result<()> {
+        self.lock().print(renderable)
+    }
+
+    /// Print a renderable followed by a newline. See [`Console::println`].
+    pub fn println<R: Renderable>(&self, renderable: R) -> Result<()> {
+        self.lock().println(renderable)
+    }
+
+    /// Start capturing output. See [`Console::begin_capture`].
+    pub fn begin_capture(&self) {
+        self.lock().begin_capture();
+    }
+
+    /// Stop capturing and return the captured output. See
+    /// [`Console::end_capture`].
+    pub fn end_capture(&self) -> String {
+        self.lock().end_capture()
+    }
+
+    /// Get the console width. See [`Console::width`].
+    pub fn width(&self) -> usize {
+        self.lock().width()
+    }
+}
+
+impl From<Console> for SharedConsole {
+    fn from(console: Console) -> Self {
+        Self::new(console)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Capabilities, ColorSystem, ConsoleOptions, Measurement, ProgressBar, Rendered, Segment,
+        Style, Text, Tree,
+    };
+    use std::{collections::HashSet, thread};
+
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn test_core_types_are_send_and_sync() {
+        assert_send::<Console>();
+        assert_sync::<Console>();
+        assert_send::<SharedConsole>();
+        assert_sync::<SharedConsole>();
+        assert_send::<ConsoleOptions>();
+        assert_sync::<ConsoleOptions>();
+        assert_send::<Capabilities>();
+        assert_sync::<Capabilities>();
+        assert_send::<Segment>();
+        assert_sync::<Segment>();
+        assert_send::<Style>();
+        assert_sync::<Style>();
+        assert_send::<Text>();
+        assert_sync::<Text>();
+        assert_send::<Measurement>();
+        assert_sync::<Measurement>();
+        assert_send::<Rendered<Text>>();
+        assert_sync::<Rendered<Text>>();
+        assert_send::<ProgressBar>();
+        assert_sync::<ProgressBar>();
+        assert_send::<Tree>();
+        assert_sync::<Tree>();
+    }
+
+    #[test]
+    fn test_concurrent_prints_are_not_interleaved() {
+        const THREADS: usize = 8;
+        const PRINTS: usize = 50;
+
+        let options = ConsoleOptions::new()
+            .with_max_width(80)
+            .with_color(true)
+            .with_color_system(ColorSystem::TrueColor);
+        let console = SharedConsole::new(Console::with_options(options));
+        let markup = |index: usize| {
+            format!(
+                "[bold red]thread {index}[/bold red] [italic]first[/italic]\n\
+                 [on blue]thread {index}[/on blue] [underline]second[/underline]"
+            )
+        };
+
+        // Every line any thread may print, rendered on its own
+        let mut expected = HashSet::new();
+        for index in 0..THREADS {
+            let text = Text::from_markup(&markup(index)).unwrap();
+            let output = console.lock().capture(|c| c.println(text)).unwrap();
+            expected.extend(output.lines().map(str::to_string));
+        }
+
+        console.begin_capture();
+        let workers: Vec<_> = (0..THREADS)
+            .map(|index| {
+                let console = console.clone();
+                let markup = markup(index);
+                thread::spawn(move || {
+                    for _ in 0..PRINTS {
+                        console
+                            .println(Text::from_markup(&markup).unwrap())
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        let output = console.end_capture();
+
+        assert_eq!(output.lines().count(), THREADS * PRINTS * 2);
+        for line in output.lines() {
+            assert!(expected.contains(line), "interleaved line: {:?}", line);
+        }
+    }
+}
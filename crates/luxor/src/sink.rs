@@ -0,0 +1,251 @@
+//! Frame sinks - console output delivered as whole frames.
+//!
+//! A console normally writes to stdout, which is fine for a terminal but
+//! not for a terminal widget in a GUI, or a web terminal: those want each
+//! update in one piece, and know their own size. With a [`FrameSink`] set
+//! by [`Console::set_sink`](crate::Console::set_sink), every print arrives
+//! as one [`FrameSink::frame`] call, and the console takes its size from
+//! the sink rather than from the terminal.
+
+use std::{
+    fmt,
+    io::{self, Write},
+    sync::mpsc::{self, Receiver, Sender},
+};
+
+/// The size of a display as (width, height), in cells.
+pub type FrameSize = (usize, usize);
+
+/// The receiving end of a [`ChannelSink`].
+pub type FrameReceiver = Receiver<Vec<u8>>;
+
+/// A display receiving console output one frame at a time.
+///
+/// A frame is everything one print produces, escape codes included, and is
+/// never split across calls.
+pub trait FrameSink: Send {
+    /// Receive a complete frame.
+    fn frame(&mut self, bytes: &[u8]);
+
+    /// Be told the display is now `width` by `height` cells, by
+    /// [`Console::resize`](crate::Console::resize).
+    fn resize(&mut self, width: usize, height: usize);
+
+    /// Get the size of the display as (width, height), if known. The
+    /// console uses it in place of the terminal size.
+    fn size(&self) -> Option<FrameSize> {
+        None
+    }
+}
+
+/// A sink writing frames to any writer, as the console does to stdout.
+///
+/// Each frame is written and flushed. The first write error is kept, and
+/// later frames are dropped, until it is taken with
+/// [`WriterSink::take_error`].
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, ConsoleOptions, sink::WriterSink};
+///
+/// let console = Console::with_options(ConsoleOptions::new().with_color(false));
+/// console.set_sink(WriterSink::new(Vec::new()));
+/// console.println("hello").unwrap();
+/// ```
+pub struct WriterSink<W> {
+    writer: W,
+    size: Option<FrameSize>,
+    error: Option<io::Error>,
+}
+
+impl<W: Write + Send> WriterSink<W> {
+    /// Create a sink writing to `writer`, of unknown size.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            size: None,
+            error: None,
+        }
+    }
+
+    /// Take the write error that stopped the sink, if any, so it writes
+    /// again.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Get the writer back.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write + Send> FrameSink for WriterSink<W> {
+    fn frame(&mut self, bytes: &[u8]) {
+        if self.error.is_some() {
+            return;
+        }
+        let written = self
+            .writer
+            .write_all(bytes)
+            .and_then(|_| self.writer.flush());
+        self.error = written.err();
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        self.size = Some((width, height));
+    }
+
+    fn size(&self) -> Option<FrameSize> {
+        self.size
+    }
+}
+
+impl<W> fmt::Debug for WriterSink<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriterSink")
+            .field("size", &self.size)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+/// A sink sending frames over a channel, to draw them on another thread.
+///
+/// Frames sent after the receiver is dropped are discarded.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, ConsoleOptions, sink::ChannelSink};
+///
+/// let mut console = Console::with_options(ConsoleOptions::new().with_color(false));
+/// let (sink, frames) = ChannelSink::new();
+/// console.set_sink(sink);
+/// console.resize(20, 5);
+/// console.println("hello").unwrap();
+/// assert_eq!(frames.recv().unwrap(), b"hello\n");
+/// assert_eq!(console.width(), 20);
+/// ```
+#[derive(Debug)]
+pub struct ChannelSink {
+    sender: Sender<Vec<u8>>,
+    size: Option<FrameSize>,
+}
+
+impl ChannelSink {
+    /// Create a sink of unknown size and the receiving end of its frames.
+    pub fn new() -> (Self, FrameReceiver) {
+        let (sender, receiver) = mpsc::channel();
+        (Self::with_sender(sender), receiver)
+    }
+
+    /// Create a sink sending frames to `sender`.
+    pub fn with_sender(sender: Sender<Vec<u8>>) -> Self {
+        Self { sender, size: None }
+    }
+}
+
+impl FrameSink for ChannelSink {
+    fn frame(&mut self, bytes: &[u8]) {
+        // Nobody is drawing any more, which is not the printer's problem
+        let _ = self.sender.send(bytes.to_vec());
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        self.size = Some((width, height));
+    }
+
+    fn size(&self) -> Option<FrameSize> {
+        self.size
+    }
+}
+
+/// A sink of any type.
+pub type BoxedSink = Box<dyn FrameSink>;
+
+/// The sink of a console.
+pub(crate) struct ConsoleSink(pub(crate) BoxedSink);
+
+impl fmt::Debug for ConsoleSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConsoleSink")
+            .field("size", &self.0.size())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Console, ConsoleOptions, Style, Text};
+
+    fn console() -> Console {
+        Console::with_options(ConsoleOptions::new().with_color(false))
+    }
+
+    #[test]
+    fn test_channel_sink_gets_one_frame_per_print() {
+        let mut console = console();
+        let (sink, frames) = ChannelSink::new();
+        console.set_sink(sink);
+        console.resize(8, 4);
+
+        console.print(Text::new("first line\nsecond")).unwrap();
+        console.println("done").unwrap();
+        console.hide_cursor().unwrap();
+        let received: Vec<Vec<u8>> = frames.try_iter().collect();
+        assert_eq!(
+            received,
+            [
+                b"first\nline\nsecond".to_vec(),
+                b"done\n".to_vec(),
+                b"\x1b[?25l".to_vec(),
+            ]
+        );
+
+        // Capturing still wins over the sink
+        let output = console.capture(|console| console.print("x")).unwrap();
+        assert_eq!(output, "x");
+        assert!(frames.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_resize_sets_render_width() {
+        let mut console = console();
+        let (sink, frames) = ChannelSink::new();
+        console.set_sink(sink);
+        console.resize(4, 2);
+        assert_eq!(console.size(), (4, 2));
+        assert_eq!(console.get_render_options().get_max_width(), 4);
+        console.print("abcdef").unwrap();
+        console.resize(6, 2);
+        console.print("abcdef").unwrap();
+        assert_eq!(frames.recv().unwrap(), b"abcd\nef");
+        assert_eq!(frames.recv().unwrap(), b"abcdef");
+
+        let mut sink = console.take_sink().unwrap();
+        assert_eq!(sink.size(), Some((6, 2)));
+        sink.resize(3, 1);
+        assert_eq!(sink.size(), Some((3, 1)));
+    }
+
+    #[test]
+    fn test_writer_sink_writes_frames() {
+        let mut sink = WriterSink::new(Vec::new());
+        sink.frame(b"one");
+        sink.frame(b"two");
+        assert_eq!(sink.size(), None);
+        assert!(sink.take_error().is_none());
+        assert_eq!(sink.into_inner(), b"onetwo");
+
+        let console = console();
+        console.set_sink(WriterSink::new(io::sink()));
+        console
+            .println(console.styled("styled", Style::new().bold()))
+            .unwrap();
+        assert!(console.take_sink().is_some());
+        assert!(console.take_sink().is_none());
+    }
+}
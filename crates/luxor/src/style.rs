@@ -1,13 +1,37 @@
 //! Style system for text formatting and appearance.
 
-use crate::{Color, LuxorError, Result};
+pub mod transitions;
+
+use crate::{Color, LuxorError, Result, StyleLayers};
 use std::fmt;
 
+/// A single on/off text attribute; `None` inherits from the parent style.
+type Attribute = Option<bool>;
+
+/// Every attribute of a style paired with its name.
+type NamedAttributes = [(&'static str, Attribute); 12];
+
 /// Text style attributes.
 ///
 /// A style defines the appearance of text including colors, font attributes,
 /// and other formatting options. Styles can be composed and inherited.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// With the `serde` feature, styles serialize as their canonical string
+/// (see [`Style::canonicalize`]), e.g. `"bold red on blue"`.
+///
+/// [`Style::new`] and the builder methods that set a color or attribute are
+/// `const fn`, so styles can be defined as statics. This works on the
+/// crate's minimum supported Rust version (1.70); methods that combine or
+/// parse styles are not const.
+///
+/// ```rust
+/// use luxor::{Color, Style};
+///
+/// static HEADER: Style = Style::new().bold().with_color(Color::rgb(0, 0, 0));
+///
+/// assert_eq!(HEADER.bold, Some(true));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Style {
     /// Foreground color.
     pub color: Option<Color>,
@@ -29,6 +53,16 @@ pub struct Style {
     pub blink: Option<bool>,
     /// Hidden/invisible text.
     pub hidden: Option<bool>,
+    /// Double underlined text.
+    pub underline2: Option<bool>,
+    /// Overlined text.
+    pub overline: Option<bool>,
+    /// Framed text.
+    pub frame: Option<bool>,
+    /// Encircled text.
+    pub encircle: Option<bool>,
+    /// Hyperlink target. An empty string removes an inherited link.
+    pub link: Option<String>,
 }
 
 impl Style {
@@ -42,7 +76,7 @@ impl Style {
     /// let style = Style::new();
     /// assert!(style.is_empty());
     /// ```
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
             color: None,
             background: None,
@@ -54,6 +88,11 @@ impl Style {
             reverse: None,
             blink: None,
             hidden: None,
+            underline2: None,
+            overline: None,
+            frame: None,
+            encircle: None,
+            link: None,
         }
     }
 
@@ -64,13 +103,19 @@ impl Style {
     /// ```rust
     /// use luxor::{Style, Color};
     ///
-    /// let style = Style::new().color(Color::rgb(255, 0, 0));
+    /// let style = Style::new().with_color(Color::rgb(255, 0, 0));
     /// ```
-    pub fn color(mut self, color: Color) -> Self {
+    pub const fn with_color(mut self, color: Color) -> Self {
         self.color = Some(color);
         self
     }
 
+    /// Set the foreground color.
+    #[deprecated(since = "0.1.0", note = "use `Style::with_color` instead")]
+    pub const fn color(self, color: Color) -> Self {
+        self.with_color(color)
+    }
+
     /// Set the background color.
     ///
     /// # Examples
@@ -78,13 +123,19 @@ impl Style {
     /// ```rust
     /// use luxor::{Style, Color};
     ///
-    /// let style = Style::new().background(Color::rgb(0, 255, 0));
+    /// let style = Style::new().with_background(Color::rgb(0, 255, 0));
     /// ```
-    pub fn background(mut self, color: Color) -> Self {
+    pub const fn with_background(mut self, color: Color) -> Self {
         self.background = Some(color);
         self
     }
 
+    /// Set the background color.
+    #[deprecated(since = "0.1.0", note = "use `Style::with_background` instead")]
+    pub const fn background(self, color: Color) -> Self {
+        self.with_background(color)
+    }
+
     /// Set bold formatting.
     ///
     /// # Examples
@@ -95,13 +146,13 @@ impl Style {
     /// let style = Style::new().bold();
     /// let style_not_bold = Style::new().bold_off();
     /// ```
-    pub fn bold(mut self) -> Self {
+    pub const fn bold(mut self) -> Self {
         self.bold = Some(true);
         self
     }
 
     /// Explicitly turn off bold formatting.
-    pub fn bold_off(mut self) -> Self {
+    pub const fn bold_off(mut self) -> Self {
         self.bold = Some(false);
         self
     }
@@ -115,13 +166,13 @@ impl Style {
     ///
     /// let style = Style::new().italic();
     /// ```
-    pub fn italic(mut self) -> Self {
+    pub const fn italic(mut self) -> Self {
         self.italic = Some(true);
         self
     }
 
     /// Explicitly turn off italic formatting.
-    pub fn italic_off(mut self) -> Self {
+    pub const fn italic_off(mut self) -> Self {
         self.italic = Some(false);
         self
     }
@@ -135,13 +186,13 @@ impl Style {
     ///
     /// let style = Style::new().underline();
     /// ```
-    pub fn underline(mut self) -> Self {
+    pub const fn underline(mut self) -> Self {
         self.underline = Some(true);
         self
     }
 
     /// Explicitly turn off underline formatting.
-    pub fn underline_off(mut self) -> Self {
+    pub const fn underline_off(mut self) -> Self {
         self.underline = Some(false);
         self
     }
@@ -155,13 +206,13 @@ impl Style {
     ///
     /// let style = Style::new().strikethrough();
     /// ```
-    pub fn strikethrough(mut self) -> Self {
+    pub const fn strikethrough(mut self) -> Self {
         self.strikethrough = Some(true);
         self
     }
 
     /// Explicitly turn off strikethrough formatting.
-    pub fn strikethrough_off(mut self) -> Self {
+    pub const fn strikethrough_off(mut self) -> Self {
         self.strikethrough = Some(false);
         self
     }
@@ -175,13 +226,13 @@ impl Style {
     ///
     /// let style = Style::new().dim();
     /// ```
-    pub fn dim(mut self) -> Self {
+    pub const fn dim(mut self) -> Self {
         self.dim = Some(true);
         self
     }
 
     /// Explicitly turn off dim formatting.
-    pub fn dim_off(mut self) -> Self {
+    pub const fn dim_off(mut self) -> Self {
         self.dim = Some(false);
         self
     }
@@ -195,13 +246,13 @@ impl Style {
     ///
     /// let style = Style::new().reverse();
     /// ```
-    pub fn reverse(mut self) -> Self {
+    pub const fn reverse(mut self) -> Self {
         self.reverse = Some(true);
         self
     }
 
     /// Explicitly turn off reverse formatting.
-    pub fn reverse_off(mut self) -> Self {
+    pub const fn reverse_off(mut self) -> Self {
         self.reverse = Some(false);
         self
     }
@@ -215,13 +266,13 @@ impl Style {
     ///
     /// let style = Style::new().blink();
     /// ```
-    pub fn blink(mut self) -> Self {
+    pub const fn blink(mut self) -> Self {
         self.blink = Some(true);
         self
     }
 
     /// Explicitly turn off blinking.
-    pub fn blink_off(mut self) -> Self {
+    pub const fn blink_off(mut self) -> Self {
         self.blink = Some(false);
         self
     }
@@ -235,17 +286,137 @@ impl Style {
     ///
     /// let style = Style::new().hidden();
     /// ```
-    pub fn hidden(mut self) -> Self {
+    pub const fn hidden(mut self) -> Self {
         self.hidden = Some(true);
         self
     }
 
     /// Explicitly turn off hidden formatting.
-    pub fn hidden_off(mut self) -> Self {
+    pub const fn hidden_off(mut self) -> Self {
         self.hidden = Some(false);
         self
     }
 
+    /// Set double underline formatting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Style;
+    ///
+    /// let style = Style::new().underline2();
+    /// ```
+    pub const fn underline2(mut self) -> Self {
+        self.underline2 = Some(true);
+        self
+    }
+
+    /// Explicitly turn off double underline formatting.
+    pub const fn underline2_off(mut self) -> Self {
+        self.underline2 = Some(false);
+        self
+    }
+
+    /// Set overline formatting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Style;
+    ///
+    /// let style = Style::new().overline();
+    /// ```
+    pub const fn overline(mut self) -> Self {
+        self.overline = Some(true);
+        self
+    }
+
+    /// Explicitly turn off overline formatting.
+    pub const fn overline_off(mut self) -> Self {
+        self.overline = Some(false);
+        self
+    }
+
+    /// Set framed formatting. Few terminals draw it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Style;
+    ///
+    /// let style = Style::new().frame();
+    /// ```
+    pub const fn frame(mut self) -> Self {
+        self.frame = Some(true);
+        self
+    }
+
+    /// Explicitly turn off framed formatting.
+    pub const fn frame_off(mut self) -> Self {
+        self.frame = Some(false);
+        self
+    }
+
+    /// Set encircled formatting. Few terminals draw it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Style;
+    ///
+    /// let style = Style::new().encircle();
+    /// ```
+    pub const fn encircle(mut self) -> Self {
+        self.encircle = Some(true);
+        self
+    }
+
+    /// Explicitly turn off encircled formatting.
+    pub const fn encircle_off(mut self) -> Self {
+        self.encircle = Some(false);
+        self
+    }
+
+    /// Make the text a hyperlink to `url`.
+    ///
+    /// Links are written as OSC 8 escape sequences, which terminals without
+    /// support ignore. A link wrapped across lines is marked as one link.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Style;
+    ///
+    /// let style = Style::new().underline().with_link("https://example.com");
+    /// assert_eq!(style.link_url(), Some("https://example.com"));
+    /// ```
+    pub fn with_link(mut self, url: impl Into<String>) -> Self {
+        self.link = Some(url.into());
+        self
+    }
+
+    /// Explicitly remove any link, including one inherited through
+    /// [`Style::combine`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Style;
+    ///
+    /// let linked = Style::new().with_link("https://example.com");
+    /// assert_eq!(linked.clone().combine(Style::new()).link_url(), Some("https://example.com"));
+    /// assert_eq!(linked.combine(Style::new().clear_link()).link_url(), None);
+    /// ```
+    pub fn clear_link(mut self) -> Self {
+        self.link = Some(String::new());
+        self
+    }
+
+    /// Get the hyperlink target, if the style links anywhere.
+    pub fn link_url(&self) -> Option<&str> {
+        self.link.as_deref().filter(|url| !url.is_empty())
+    }
+
     /// Check if this style has no attributes set.
     ///
     /// # Examples
@@ -254,9 +425,9 @@ impl Style {
     /// use luxor::{Style, Color};
     ///
     /// assert!(Style::new().is_empty());
-    /// assert!(!Style::new().color(Color::rgb(255, 0, 0)).is_empty());
+    /// assert!(!Style::new().with_color(Color::rgb(255, 0, 0)).is_empty());
     /// ```
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.color.is_none()
             && self.background.is_none()
             && self.bold.is_none()
@@ -267,6 +438,11 @@ impl Style {
             && self.reverse.is_none()
             && self.blink.is_none()
             && self.hidden.is_none()
+            && self.underline2.is_none()
+            && self.overline.is_none()
+            && self.frame.is_none()
+            && self.encircle.is_none()
+            && self.link.is_none()
     }
 
     /// Combine this style with another style.
@@ -279,8 +455,8 @@ impl Style {
     /// ```rust
     /// use luxor::{Style, Color};
     ///
-    /// let base = Style::new().color(Color::rgb(255, 0, 0)).bold();
-    /// let overlay = Style::new().color(Color::rgb(0, 255, 0)).italic();
+    /// let base = Style::new().with_color(Color::rgb(255, 0, 0)).bold();
+    /// let overlay = Style::new().with_color(Color::rgb(0, 255, 0)).italic();
     /// let combined = base.combine(overlay);
     ///
     /// // Result has green color (from overlay), bold (from base), and italic (from overlay)
@@ -297,58 +473,226 @@ impl Style {
             reverse: other.reverse.or(self.reverse),
             blink: other.blink.or(self.blink),
             hidden: other.hidden.or(self.hidden),
+            underline2: other.underline2.or(self.underline2),
+            overline: other.overline.or(self.overline),
+            frame: other.frame.or(self.frame),
+            encircle: other.encircle.or(self.encircle),
+            link: other.link.or(self.link),
+        }
+    }
+
+    /// The minimum contrast ratio [`Style::ensure_contrast`] enforces (WCAG AA).
+    pub const MIN_CONTRAST_RATIO: f64 = 4.5;
+
+    /// Make the foreground readable against the given background.
+    ///
+    /// If the foreground color's contrast ratio against `background` is
+    /// below [`Style::MIN_CONTRAST_RATIO`], it is replaced with black or white,
+    /// whichever contrasts more. Styles without a foreground color are
+    /// returned unchanged, since the terminal's default color is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Color, Style};
+    ///
+    /// let style = Style::new().with_color(Color::rgb(250, 250, 100));
+    /// let readable = style.ensure_contrast(Color::rgb(255, 255, 255));
+    /// assert_eq!(readable.color, Some(Color::rgb(0, 0, 0)));
+    /// ```
+    pub fn ensure_contrast(mut self, background: Color) -> Self {
+        if let Some(color) = self.color {
+            if color.contrast_ratio(background) < Self::MIN_CONTRAST_RATIO {
+                let black = Color::rgb(0, 0, 0);
+                let white = Color::rgb(255, 255, 255);
+                self.color = if black.contrast_ratio(background) >= white.contrast_ratio(background)
+                {
+                    Some(black)
+                } else {
+                    Some(white)
+                };
+            }
         }
+        self
+    }
+
+    /// Swap foreground and background, as for a selected row.
+    ///
+    /// When both colors are concrete they are swapped, so the result looks
+    /// the same wherever it is rendered. When either is unset or
+    /// [`Color::Default`], the terminal's own colors are unknown and cannot
+    /// be swapped, so reverse video is switched on instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Color, Style};
+    ///
+    /// let style = Style::new().with_color(Color::rgb(255, 255, 255)).with_background(Color::rgb(0, 0, 128));
+    /// let inverted = style.invert();
+    /// assert_eq!(inverted.color, Some(Color::rgb(0, 0, 128)));
+    /// assert_eq!(inverted.background, Some(Color::rgb(255, 255, 255)));
+    ///
+    /// assert_eq!(Style::new().bold().invert(), Style::new().bold().reverse());
+    /// ```
+    pub fn invert(mut self) -> Self {
+        match (self.color, self.background) {
+            (Some(color), Some(background)) if !color.is_default() && !background.is_default() => {
+                self.color = Some(background);
+                self.background = Some(color);
+            }
+            _ => self.reverse = Some(true),
+        }
+        self
+    }
+
+    /// Style a selected item: `selection` over `base`, kept readable.
+    ///
+    /// The styles are combined, and if the result has a concrete background
+    /// its foreground is adjusted with [`Style::ensure_contrast`]. An empty
+    /// `selection` means "highlight however works", and inverts `base`
+    /// with [`Style::invert`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Color, Style};
+    ///
+    /// let base = Style::new().with_color(Color::rgb(0, 0, 200));
+    /// let selected = Style::on_selection(base.clone(), Style::new().with_background(Color::rgb(0, 0, 80)));
+    /// assert_eq!(selected.color, Some(Color::rgb(255, 255, 255)));
+    ///
+    /// assert_eq!(Style::on_selection(base.clone(), Style::new()), base.invert());
+    /// ```
+    pub fn on_selection(base: Style, selection: Style) -> Self {
+        if selection.is_empty() {
+            return base.invert();
+        }
+        let combined = StyleLayers::new()
+            .layer("base", base)
+            .layer("selection", selection)
+            .resolve();
+        match combined.background {
+            Some(background) if !background.is_default() => combined.ensure_contrast(background),
+            _ => combined,
+        }
+    }
+
+    /// Get the canonical representation of this style.
+    ///
+    /// Two styles that render identically in every context have the same
+    /// canonical form, so `a.canonicalize() == b.canonicalize()` (and their
+    /// hashes) is the right equality for cache and theme keys. Canonical
+    /// styles also round trip through [`Display`](fmt::Display) and
+    /// [`Style::parse`].
+    ///
+    /// Only colors and links are normalized (see [`Color::canonicalize`]
+    /// and [`encode_url`](crate::ansi::link::encode_url)). Unset and
+    /// explicitly set values stay distinct because they behave differently
+    /// under [`Style::combine`]: `None` inherits from the parent style while
+    /// `Some(false)` and `Some(Color::Default)` override it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Color, Style};
+    ///
+    /// let style = Style::new().with_color(Color::EightBit(1));
+    /// assert_eq!(style.canonicalize(), Style::parse("red").unwrap());
+    /// ```
+    pub fn canonicalize(mut self) -> Self {
+        self.color = self.color.map(Color::canonicalize);
+        self.background = self.background.map(Color::canonicalize);
+        self.link = self
+            .link
+            .map(|url| crate::ansi::link::encode_url(&url).into_owned());
+        self
     }
 
     /// Parse a style from a string representation.
     ///
     /// Supports various formats:
-    /// - Color names: "red", "green", "blue"
+    /// - Color names: "red", "green", "blue", "default"
     /// - Hex colors: "#FF0000", "#F00"
-    /// - Style attributes: "bold", "italic", "underline"
+    /// - 8-bit colors: "color(208)", or a bare palette index "208"
+    /// - RGB colors: "rgb(255,128,0)", spaces allowed inside the parentheses
+    /// - Style attributes: "bold", "italic", "underline", "underline2",
+    ///   "overline", "frame", "encircle"
+    /// - Explicitly disabled attributes: "not bold"
+    /// - Hyperlinks: "link URL", or "not link" to remove one
     /// - Combined: "bold red on blue", "italic #FF0000"
+    /// - The empty style: "none" or ""
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use luxor::Style;
+    /// use luxor::{Color, Style};
     ///
     /// let style1 = Style::parse("bold red").unwrap();
     /// let style2 = Style::parse("italic #FF0000 on #0000FF").unwrap();
+    /// let style3 = Style::parse("not dim color(208)").unwrap();
+    /// assert_eq!(style3.dim, Some(false));
+    /// let style4 = Style::parse("rgb(255, 128, 0) on 17").unwrap();
+    /// assert_eq!(style4.color, Some(Color::rgb(255, 128, 0)));
+    /// assert_eq!(style4.background, Some(Color::EightBit(17)));
     /// ```
     pub fn parse(style_str: &str) -> Result<Self> {
         let mut style = Style::new();
-        let mut tokens = style_str.split_whitespace().peekable();
+        let mut tokens = style_tokens(style_str).into_iter().peekable();
 
         while let Some(token) = tokens.next() {
-            match token.to_lowercase().as_str() {
-                "bold" => style.bold = Some(true),
-                "italic" => style.italic = Some(true),
-                "underline" => style.underline = Some(true),
-                "strikethrough" => style.strikethrough = Some(true),
-                "dim" => style.dim = Some(true),
-                "reverse" => style.reverse = Some(true),
-                "blink" => style.blink = Some(true),
-                "hidden" => style.hidden = Some(true),
+            let token = token.to_lowercase();
+            if let Some(attribute) = style.attribute_mut(&token) {
+                *attribute = Some(true);
+                continue;
+            }
+            match token.as_str() {
+                "none" => {}
+                "not" => {
+                    let Some(name) = tokens.next() else {
+                        return Err(LuxorError::style("Expected attribute after 'not'"));
+                    };
+                    if name.eq_ignore_ascii_case("link") {
+                        style.link = Some(String::new());
+                        continue;
+                    }
+                    match style.attribute_mut(&name.to_lowercase()) {
+                        Some(attribute) => *attribute = Some(false),
+                        None => {
+                            return Err(LuxorError::style(format!(
+                                "Unknown style attribute: {}",
+                                name
+                            )));
+                        }
+                    }
+                }
+                "link" => match tokens.next() {
+                    Some(url) => style.link = Some(url.to_string()),
+                    None => return Err(LuxorError::style("Expected URL after 'link'")),
+                },
                 "on" => {
                     // Next token should be background color
                     if let Some(bg_token) = tokens.next() {
-                        style.background = Some(parse_color_token(bg_token)?);
+                        style.background = Some(parse_color_token(&bg_token)?);
                     } else {
                         return Err(LuxorError::style("Expected color after 'on'"));
                     }
                 }
                 color_token => {
-                    // Try to parse as color
-                    if let Ok(color) = parse_color_token(color_token) {
-                        if style.color.is_none() {
-                            style.color = Some(color);
+                    // Try to parse as color. Tokens in color syntax report
+                    // why they are invalid; anything else is unknown
+                    let color = match parse_color_token(color_token) {
+                        Ok(color) => color,
+                        Err(error) if is_color_syntax(color_token) => return Err(error),
+                        Err(_) => {
+                            return Err(LuxorError::style(format!(
+                                "Unknown style token: {}",
+                                color_token
+                            )));
                         }
-                    } else {
-                        return Err(LuxorError::style(format!(
-                            "Unknown style token: {}",
-                            color_token
-                        )));
+                    };
+                    if style.color.is_none() {
+                        style.color = Some(color);
                     }
                 }
             }
@@ -356,6 +700,43 @@ impl Style {
 
         Ok(style)
     }
+
+    /// Get the attribute field with the given name, as used by [`Style::parse`].
+    fn attribute_mut(&mut self, name: &str) -> Option<&mut Attribute> {
+        match name {
+            "bold" => Some(&mut self.bold),
+            "italic" => Some(&mut self.italic),
+            "underline" => Some(&mut self.underline),
+            "strikethrough" => Some(&mut self.strikethrough),
+            "dim" => Some(&mut self.dim),
+            "reverse" => Some(&mut self.reverse),
+            "blink" => Some(&mut self.blink),
+            "hidden" => Some(&mut self.hidden),
+            "underline2" => Some(&mut self.underline2),
+            "overline" => Some(&mut self.overline),
+            "frame" => Some(&mut self.frame),
+            "encircle" => Some(&mut self.encircle),
+            _ => None,
+        }
+    }
+
+    /// Get the attributes with their names, in canonical order.
+    fn attributes(&self) -> NamedAttributes {
+        [
+            ("bold", self.bold),
+            ("italic", self.italic),
+            ("underline", self.underline),
+            ("strikethrough", self.strikethrough),
+            ("dim", self.dim),
+            ("reverse", self.reverse),
+            ("blink", self.blink),
+            ("hidden", self.hidden),
+            ("underline2", self.underline2),
+            ("overline", self.overline),
+            ("frame", self.frame),
+            ("encircle", self.encircle),
+        ]
+    }
 }
 
 impl Default for Style {
@@ -364,54 +745,133 @@ impl Default for Style {
     }
 }
 
+/// Formats the style in canonical form, which [`Style::parse`] reads back
+/// as [`Style::canonicalize`] of the original.
+///
+/// Attributes come first in a fixed order (disabled ones prefixed with
+/// `not`), then the foreground color, then `on` and the background, then
+/// `link` and the percent-encoded URL. The empty style is written as `none`.
 impl fmt::Display for Style {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut parts = Vec::new();
 
-        if let Some(true) = self.bold {
-            parts.push("bold".to_string());
-        }
-        if let Some(true) = self.italic {
-            parts.push("italic".to_string());
-        }
-        if let Some(true) = self.underline {
-            parts.push("underline".to_string());
-        }
-        if let Some(true) = self.strikethrough {
-            parts.push("strikethrough".to_string());
-        }
-        if let Some(true) = self.dim {
-            parts.push("dim".to_string());
-        }
-        if let Some(true) = self.reverse {
-            parts.push("reverse".to_string());
-        }
-        if let Some(true) = self.blink {
-            parts.push("blink".to_string());
-        }
-        if let Some(true) = self.hidden {
-            parts.push("hidden".to_string());
+        for (name, value) in self.attributes() {
+            match value {
+                Some(true) => parts.push(name.to_string()),
+                Some(false) => parts.push(format!("not {}", name)),
+                None => {}
+            }
         }
 
         if let Some(color) = self.color {
-            parts.push(format!("color:{:?}", color));
+            parts.push(color.to_string());
         }
 
         if let Some(background) = self.background {
-            parts.push(format!("bg:{:?}", background));
+            parts.push(format!("on {}", background));
+        }
+
+        match self.link.as_deref() {
+            Some("") => parts.push("not link".to_string()),
+            Some(url) => parts.push(format!("link {}", crate::ansi::link::encode_url(url))),
+            None => {}
         }
 
         if parts.is_empty() {
-            write!(f, "Style::new()")
+            write!(f, "none")
         } else {
-            write!(f, "Style({})", parts.join(" "))
+            write!(f, "{}", parts.join(" "))
+        }
+    }
+}
+
+/// Shows only the attributes that are set, e.g. `Style(bold red)`.
+impl fmt::Debug for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Style({})", self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Style {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Style {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let style = String::deserialize(deserializer)?;
+        Style::parse(&style).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Split a style definition into tokens at whitespace, keeping a
+/// `color(...)` or `rgb(...)` token whole when it has spaces inside.
+fn style_tokens(style_str: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut open = false;
+    for word in style_str.split_whitespace() {
+        match tokens.last_mut() {
+            Some(token) if open => token.push_str(word),
+            _ => {
+                let lower = word.to_lowercase();
+                open = lower.starts_with("color(") || lower.starts_with("rgb(");
+                tokens.push(word.to_string());
+            }
+        }
+        if word.contains(')') {
+            open = false;
         }
     }
+    tokens
+}
+
+/// Check whether `token` is written as a color rather than a name: a
+/// palette index, a hex code or a color function.
+fn is_color_syntax(token: &str) -> bool {
+    let lower = token.to_lowercase();
+    lower.starts_with('#')
+        || lower.starts_with("color(")
+        || lower.starts_with("rgb(")
+        || lower.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Parse the parenthesized arguments of `function(...)` in `token`.
+fn color_arguments<'a>(lower: &'a str, function: &str) -> Option<&'a str> {
+    lower
+        .strip_prefix(function)
+        .and_then(|rest| rest.strip_prefix('('))
+        .and_then(|rest| rest.strip_suffix(')'))
+}
+
+/// Parse a palette index from 0 to 255 written in `token`.
+fn palette_index(index: &str, token: &str) -> Result<Color> {
+    index
+        .trim()
+        .parse::<u8>()
+        .map(Color::EightBit)
+        .map_err(|_| {
+            LuxorError::color(format!(
+                "Invalid color '{}': palette index must be 0 to 255",
+                token
+            ))
+        })
 }
 
 /// Parse a color token from a string.
+///
+/// Accepts `default`, standard color names, palette indices written
+/// `color(N)` or bare, `rgb(r,g,b)` and hex codes.
 fn parse_color_token(token: &str) -> Result<Color> {
     match token.to_lowercase().as_str() {
+        "default" => Ok(Color::Default),
         "black" => Ok(Color::Standard(crate::StandardColor::Black)),
         "red" => Ok(Color::Standard(crate::StandardColor::Red)),
         "green" => Ok(Color::Standard(crate::StandardColor::Green)),
@@ -428,7 +888,26 @@ fn parse_color_token(token: &str) -> Result<Color> {
         "bright_magenta" => Ok(Color::Standard(crate::StandardColor::BrightMagenta)),
         "bright_cyan" => Ok(Color::Standard(crate::StandardColor::BrightCyan)),
         "bright_white" => Ok(Color::Standard(crate::StandardColor::BrightWhite)),
-        _ => {
+        lower => {
+            if let Some(index) = color_arguments(lower, "color") {
+                return palette_index(index, token);
+            }
+            if let Some(arguments) = color_arguments(lower, "rgb") {
+                let channels = arguments
+                    .split(',')
+                    .map(|channel| channel.trim().parse::<u8>().ok())
+                    .collect::<Option<Vec<_>>>();
+                return match channels.as_deref() {
+                    Some(&[r, g, b]) => Ok(Color::rgb(r, g, b)),
+                    _ => Err(LuxorError::color(format!(
+                        "Invalid color '{}': expected rgb(r,g,b) with each channel 0 to 255",
+                        token
+                    ))),
+                };
+            }
+            if !lower.is_empty() && lower.len() <= 3 && lower.bytes().all(|b| b.is_ascii_digit()) {
+                return palette_index(lower, token);
+            }
             // Try to parse as hex color
             if token.starts_with('#') || token.len() == 3 || token.len() == 6 {
                 Color::from_hex(token)
@@ -453,8 +932,8 @@ mod tests {
     #[test]
     fn test_style_builder() {
         let style = Style::new()
-            .color(Color::rgb(255, 0, 0))
-            .background(Color::rgb(0, 255, 0))
+            .with_color(Color::rgb(255, 0, 0))
+            .with_background(Color::rgb(0, 255, 0))
             .bold()
             .italic();
 
@@ -465,11 +944,23 @@ mod tests {
         assert!(!style.is_empty());
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_style_deprecated_builders() {
+        let old = Style::new()
+            .color(Color::rgb(255, 0, 0))
+            .background(Color::rgb(0, 0, 255));
+        let new = Style::new()
+            .with_color(Color::rgb(255, 0, 0))
+            .with_background(Color::rgb(0, 0, 255));
+        assert_eq!(old, new);
+    }
+
     #[test]
     fn test_style_combine() {
-        let base = Style::new().color(Color::rgb(255, 0, 0)).bold();
+        let base = Style::new().with_color(Color::rgb(255, 0, 0)).bold();
 
-        let overlay = Style::new().color(Color::rgb(0, 255, 0)).italic();
+        let overlay = Style::new().with_color(Color::rgb(0, 255, 0)).italic();
 
         let combined = base.combine(overlay);
 
@@ -512,10 +1003,79 @@ mod tests {
 
     #[test]
     fn test_style_display() {
-        let style = Style::new().bold().color(Color::rgb(255, 0, 0));
+        let style = Style::new().bold().with_color(Color::rgb(255, 0, 0));
         let string_repr = format!("{}", style);
         assert!(string_repr.contains("bold"));
-        assert!(string_repr.contains("color"));
+        assert!(string_repr.contains("#ff0000"));
+    }
+
+    #[test]
+    fn test_style_display_canonical() {
+        let style = Style::parse("on color(4) red italic not blink bold").unwrap();
+        assert_eq!(style.to_string(), "bold italic not blink red on blue");
+        assert_eq!(Style::new().to_string(), "none");
+        assert_eq!(Style::parse("none").unwrap(), Style::new());
+    }
+
+    #[test]
+    fn test_style_parse_explicit_values() {
+        let style = Style::parse("not bold default on default").unwrap();
+        assert_eq!(style.bold, Some(false));
+        assert_eq!(style.color, Some(Color::Default));
+        assert_eq!(style.background, Some(Color::Default));
+        assert_eq!(Style::parse(&style.to_string()).unwrap(), style);
+
+        assert!(Style::parse("not").is_err());
+        assert!(Style::parse("not red").is_err());
+        assert!(Style::parse("color(256)").is_err());
+    }
+
+    #[test]
+    fn test_style_debug_compact() {
+        assert_eq!(format!("{:?}", Style::new()), "Style(none)");
+        assert_eq!(
+            format!("{:?}", Style::parse("italic red on #00ff00").unwrap()),
+            "Style(italic red on #00ff00)"
+        );
+    }
+
+    #[test]
+    fn test_style_canonicalize() {
+        let eight_bit = Style::new().with_background(Color::EightBit(9));
+        let standard = Style::new().with_background(Color::Standard(StandardColor::BrightRed));
+        assert_ne!(eight_bit, standard);
+        assert_eq!(eight_bit.canonicalize(), standard);
+
+        // Unset and explicitly off are different styles
+        assert_ne!(
+            Style::new().canonicalize(),
+            Style::parse("not bold").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_style_ensure_contrast() {
+        let white = Color::rgb(255, 255, 255);
+        let black = Color::rgb(0, 0, 0);
+
+        // Light yellow on white is unreadable and becomes black
+        let style = Style::new()
+            .bold()
+            .with_color(Color::rgb(250, 250, 100))
+            .ensure_contrast(white);
+        assert_eq!(style.color, Some(black));
+        assert_eq!(style.bold, Some(true));
+
+        // Dark blue on black becomes white
+        let style = Style::new()
+            .with_color(Color::rgb(0, 0, 80))
+            .ensure_contrast(black);
+        assert_eq!(style.color, Some(white));
+
+        // Readable colors and styles without a foreground are untouched
+        let readable = Style::new().with_color(Color::rgb(0, 0, 160));
+        assert_eq!(readable.clone().ensure_contrast(white), readable);
+        assert_eq!(Style::new().ensure_contrast(white), Style::new());
     }
 
     #[test]
@@ -531,4 +1091,160 @@ mod tests {
         assert_eq!(parse_color_token("#FF0000").unwrap(), Color::rgb(255, 0, 0));
         assert!(parse_color_token("invalid").is_err());
     }
+
+    #[test]
+    fn test_parse_palette_and_rgb_colors() {
+        assert_eq!(
+            parse_color_token("color(196)").unwrap(),
+            Color::EightBit(196)
+        );
+        assert_eq!(parse_color_token("196").unwrap(), Color::EightBit(196));
+        assert_eq!(parse_color_token("0").unwrap(), Color::EightBit(0));
+        assert_eq!(parse_color_token("DEFAULT").unwrap(), Color::Default);
+        assert_eq!(
+            parse_color_token("RGB(255,0,10)").unwrap(),
+            Color::rgb(255, 0, 10)
+        );
+        assert_eq!(
+            Style::parse("bold rgb( 1, 2 ,3 ) on color( 9 )").unwrap(),
+            Style::new()
+                .bold()
+                .with_color(Color::rgb(1, 2, 3))
+                .with_background(Color::EightBit(9))
+        );
+        // Six digits are still a hex code
+        assert_eq!(parse_color_token("102030").unwrap(), Color::rgb(16, 32, 48));
+
+        for (definition, token) in [
+            ("color(256)", "color(256)"),
+            ("300", "300"),
+            ("bold rgb(1,2)", "rgb(1,2)"),
+            ("rgb(0, 0, 256) on red", "rgb(0,0,256)"),
+            ("on color(-1)", "color(-1)"),
+        ] {
+            let error = Style::parse(definition).unwrap_err().to_string();
+            assert!(error.contains(&format!("'{}'", token)), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_style_link_inheritance() {
+        let linked = Style::new().bold().with_link("https://example.com");
+        // An unset link inherits, an empty one clears
+        let inherited = linked.clone().combine(Style::new().italic());
+        assert_eq!(inherited.link_url(), Some("https://example.com"));
+        let cleared = linked.combine(Style::new().clear_link());
+        assert_eq!(cleared.link_url(), None);
+        assert_eq!(cleared.bold, Some(true));
+        let relinked = cleared.combine(Style::new().with_link("https://other.org"));
+        assert_eq!(relinked.link_url(), Some("https://other.org"));
+    }
+
+    #[test]
+    fn test_style_link_round_trip() {
+        let style = Style::new()
+            .underline()
+            .with_link("https://example.com/a b");
+        assert_eq!(
+            style.to_string(),
+            "underline link https://example.com/a%20b"
+        );
+        assert_eq!(
+            Style::parse(&style.to_string()).unwrap(),
+            style.canonicalize()
+        );
+
+        let cleared = Style::new().clear_link();
+        assert_eq!(cleared.to_string(), "not link");
+        assert_eq!(Style::parse("not link").unwrap(), cleared);
+        assert!(Style::parse("bold link").is_err());
+    }
+
+    #[test]
+    fn test_style_extra_decorations_round_trip() {
+        let style = Style::parse("underline2 not overline frame encircle").unwrap();
+        assert_eq!(style.underline2, Some(true));
+        assert_eq!(style.overline, Some(false));
+        assert_eq!(
+            style,
+            Style::new().underline2().overline_off().frame().encircle()
+        );
+        assert_eq!(style.to_string(), "underline2 not overline frame encircle");
+        assert_eq!(Style::parse(&style.to_string()).unwrap(), style);
+
+        // "not" overrides an inherited attribute when combined
+        let combined = Style::parse("bold overline")
+            .unwrap()
+            .combine(Style::parse("not bold not overline").unwrap());
+        assert_eq!(
+            (combined.bold, combined.overline),
+            (Some(false), Some(false))
+        );
+        assert!(!Style::new().encircle_off().is_empty());
+    }
+
+    #[test]
+    fn test_style_in_static() {
+        static HEADER: Style = Style::new()
+            .bold()
+            .underline_off()
+            .with_color(Color::rgb(0, 0, 0))
+            .with_background(Color::Standard(StandardColor::White));
+        const PLAIN: &Style = &Style::new();
+        const _: () = assert!(PLAIN.is_empty());
+
+        assert_eq!(
+            HEADER,
+            Style::parse("bold not underline #000000 on white").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_style_invert() {
+        let red = Color::rgb(255, 0, 0);
+        let blue = Color::rgb(0, 0, 255);
+
+        // Both concrete: colors are swapped, reverse is left alone
+        let inverted = Style::new().with_color(red).with_background(blue).invert();
+        assert_eq!(inverted.color, Some(blue));
+        assert_eq!(inverted.background, Some(red));
+        assert_eq!(inverted.reverse, None);
+
+        // Either side default or unset: reverse video, colors untouched
+        for style in [
+            Style::new().with_color(red),
+            Style::new().with_background(blue),
+            Style::new()
+                .with_color(Color::Default)
+                .with_background(blue),
+            Style::new().with_color(red).with_background(Color::Default),
+            Style::new(),
+        ] {
+            let inverted = style.clone().invert();
+            assert_eq!(inverted.reverse, Some(true), "{}", style);
+            assert_eq!(inverted.color, style.color);
+            assert_eq!(inverted.background, style.background);
+        }
+    }
+
+    #[test]
+    fn test_style_on_selection() {
+        let base = Style::new().bold().with_color(Color::rgb(250, 250, 100));
+        let selected = Style::on_selection(
+            base.clone(),
+            Style::new().with_background(Color::rgb(255, 255, 255)),
+        );
+        assert_eq!(selected.color, Some(Color::rgb(0, 0, 0)));
+        assert_eq!(selected.bold, Some(true));
+
+        // Without a concrete background there is nothing to contrast with
+        let selected = Style::on_selection(base.clone(), Style::new().underline());
+        assert_eq!(selected.color, base.color);
+        assert_eq!(selected.underline, Some(true));
+
+        assert_eq!(
+            Style::on_selection(base.clone(), Style::new()),
+            base.invert()
+        );
+    }
 }
@@ -0,0 +1,494 @@
+//! Style transitions - what changes between one style and the next.
+//!
+//! Writers that walk segments, such as the ANSI writer, only need to emit
+//! what differs from the style before. [`StyleDelta`] works that out once,
+//! including the awkward cases where one SGR code turns off two
+//! attributes: 22 turns off bold and dim, 24 both underlines, and 54 frame
+//! and encircle, so turning off one of a pair has to turn the other back on.
+
+use crate::{
+    Color, ColorSystem, Style,
+    diagnostics::{self, Warning},
+};
+
+/// A CSS property and its value.
+pub type CssDeclaration = (&'static str, String);
+
+/// An on/off text attribute of a [`Style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextAttribute {
+    /// Bold text.
+    Bold,
+    /// Dim/faint text.
+    Dim,
+    /// Italic text.
+    Italic,
+    /// Underlined text.
+    Underline,
+    /// Blinking text.
+    Blink,
+    /// Reverse/inverse colors.
+    Reverse,
+    /// Hidden/invisible text.
+    Hidden,
+    /// Strikethrough text.
+    Strikethrough,
+    /// Double underlined text.
+    Underline2,
+    /// Framed text.
+    Frame,
+    /// Encircled text.
+    Encircle,
+    /// Overlined text.
+    Overline,
+}
+
+impl TextAttribute {
+    /// Every attribute, in the order their SGR codes are written.
+    pub const ALL: [TextAttribute; 12] = [
+        TextAttribute::Bold,
+        TextAttribute::Dim,
+        TextAttribute::Italic,
+        TextAttribute::Underline,
+        TextAttribute::Blink,
+        TextAttribute::Reverse,
+        TextAttribute::Hidden,
+        TextAttribute::Strikethrough,
+        TextAttribute::Underline2,
+        TextAttribute::Frame,
+        TextAttribute::Encircle,
+        TextAttribute::Overline,
+    ];
+
+    /// Get the setting of this attribute in `style`.
+    pub fn get(self, style: &Style) -> Option<bool> {
+        match self {
+            TextAttribute::Bold => style.bold,
+            TextAttribute::Dim => style.dim,
+            TextAttribute::Italic => style.italic,
+            TextAttribute::Underline => style.underline,
+            TextAttribute::Blink => style.blink,
+            TextAttribute::Reverse => style.reverse,
+            TextAttribute::Hidden => style.hidden,
+            TextAttribute::Strikethrough => style.strikethrough,
+            TextAttribute::Underline2 => style.underline2,
+            TextAttribute::Frame => style.frame,
+            TextAttribute::Encircle => style.encircle,
+            TextAttribute::Overline => style.overline,
+        }
+    }
+
+    /// Get the SGR code turning this attribute on.
+    pub fn sgr_on(self) -> u8 {
+        match self {
+            TextAttribute::Bold => 1,
+            TextAttribute::Dim => 2,
+            TextAttribute::Italic => 3,
+            TextAttribute::Underline => 4,
+            TextAttribute::Blink => 5,
+            TextAttribute::Reverse => 7,
+            TextAttribute::Hidden => 8,
+            TextAttribute::Strikethrough => 9,
+            TextAttribute::Underline2 => 21,
+            TextAttribute::Frame => 51,
+            TextAttribute::Encircle => 52,
+            TextAttribute::Overline => 53,
+        }
+    }
+
+    /// Get the SGR code turning this attribute off. Bold and dim share 22,
+    /// the two underlines 24, and frame and encircle 54.
+    pub fn sgr_off(self) -> u8 {
+        match self {
+            TextAttribute::Bold | TextAttribute::Dim => 22,
+            TextAttribute::Underline | TextAttribute::Underline2 => 24,
+            TextAttribute::Frame | TextAttribute::Encircle => 54,
+            TextAttribute::Overline => 55,
+            attribute => attribute.sgr_on() + 20,
+        }
+    }
+
+    /// Check whether the code turning this attribute off turns off another.
+    fn shares_off(self) -> bool {
+        TextAttribute::ALL
+            .iter()
+            .any(|&other| other != self && other.sgr_off() == self.sgr_off())
+    }
+}
+
+/// The difference between two styles.
+///
+/// An attribute is turned on when the next style sets it and the previous
+/// did not, and turned off when the next style clears it (or leaves unset
+/// one the previous style set). Colors and links are reported as the new
+/// value: [`Color::Default`] when a color is removed, and an empty string
+/// when a link is.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Color, ColorSystem, Style, StyleDelta};
+///
+/// let prev = Style::new().bold().dim();
+/// let next = Style::new().dim().with_color(Color::rgb(255, 0, 0));
+/// let delta = StyleDelta::between(&prev, &next);
+/// // 22 turns off dim as well, so dim is turned back on
+/// assert_eq!(delta.apply_to_sgr(ColorSystem::TrueColor), [38, 2, 255, 0, 0, 22, 2]);
+/// assert!(StyleDelta::between(&next, &next).is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleDelta {
+    /// Attributes turned on, in [`TextAttribute::ALL`] order.
+    pub on: Vec<TextAttribute>,
+    /// Attributes turned off, in [`TextAttribute::ALL`] order.
+    pub off: Vec<TextAttribute>,
+    /// The new foreground color, if it changed.
+    pub color: Option<Color>,
+    /// The new background color, if it changed.
+    pub background: Option<Color>,
+    /// The new link, if it changed.
+    pub link: Option<String>,
+    /// Attributes staying on, for restoring them after a shared off code.
+    kept: Vec<TextAttribute>,
+    /// The decorations of the next style, if any of them changed.
+    decorations: Option<Vec<TextAttribute>>,
+}
+
+impl StyleDelta {
+    /// Work out what changes from `prev` to `next`.
+    pub fn between(prev: &Style, next: &Style) -> Self {
+        let is_on = |style: &Style, attribute: TextAttribute| attribute.get(style) == Some(true);
+        let mut on = Vec::new();
+        let mut off = Vec::new();
+        for attribute in TextAttribute::ALL {
+            let (before, after) = (attribute.get(prev), attribute.get(next));
+            match after {
+                Some(true) if before != Some(true) => on.push(attribute),
+                Some(false) if before != Some(false) => off.push(attribute),
+                None if before == Some(true) => off.push(attribute),
+                _ => {}
+            }
+        }
+        let changed = |attribute| on.contains(&attribute) || off.contains(&attribute);
+        let decorations = [
+            TextAttribute::Underline,
+            TextAttribute::Underline2,
+            TextAttribute::Overline,
+            TextAttribute::Strikethrough,
+            TextAttribute::Blink,
+        ];
+        let decorations = decorations
+            .iter()
+            .any(|&attribute| changed(attribute))
+            .then(|| {
+                decorations
+                    .into_iter()
+                    .filter(|&attribute| is_on(next, attribute))
+                    .collect()
+            });
+        let new_color = |before: Option<Color>, after: Option<Color>| {
+            (before != after).then(|| after.unwrap_or(Color::Default))
+        };
+        let kept = TextAttribute::ALL
+            .into_iter()
+            .filter(|&attribute| is_on(next, attribute) && !changed(attribute))
+            .collect();
+        Self {
+            kept,
+            on,
+            off,
+            color: new_color(prev.color, next.color),
+            background: new_color(prev.background, next.background),
+            link: (prev.link != next.link).then(|| next.link.clone().unwrap_or_default()),
+            decorations,
+        }
+    }
+
+    /// Check whether nothing changes.
+    pub fn is_empty(&self) -> bool {
+        self.on.is_empty()
+            && self.off.is_empty()
+            && self.color.is_none()
+            && self.background.is_none()
+            && self.link.is_none()
+    }
+
+    /// Get the SGR parameters making the change, downgrading colors to
+    /// `color_system`.
+    ///
+    /// Colors come first, then attributes turned off, then attributes
+    /// turned on. A code turning off two attributes, such as 22 for bold
+    /// and dim, is written once after the others, and whichever of the two
+    /// stays on is turned back on. Links are not SGR parameters, so they
+    /// are left out.
+    pub fn apply_to_sgr(&self, color_system: ColorSystem) -> Vec<u8> {
+        let mut codes = Vec::new();
+        if let Some(color) = self.color {
+            push_color(&mut codes, color, false, color_system);
+        }
+        if let Some(color) = self.background {
+            push_color(&mut codes, color, true, color_system);
+        }
+        let mut on = self.on.clone();
+        let mut shared = Vec::new();
+        for &attribute in &self.off {
+            if !attribute.shares_off() {
+                codes.push(attribute.sgr_off());
+            } else if !shared.contains(&attribute.sgr_off()) {
+                shared.push(attribute.sgr_off());
+            }
+        }
+        if !shared.is_empty() {
+            codes.extend(&shared);
+            for &attribute in &self.kept {
+                if shared.contains(&attribute.sgr_off()) && !on.contains(&attribute) {
+                    on.push(attribute);
+                }
+            }
+            on.sort_by_key(|attribute| attribute.sgr_on());
+        }
+        codes.extend(on.iter().map(|attribute| attribute.sgr_on()));
+        codes
+    }
+
+    /// Get the CSS declarations making the change, as property and value.
+    ///
+    /// The underlines, overline, strikethrough and blink share
+    /// `text-decoration`, which is written with all of them whenever one
+    /// changes; a double underline is a plain underline there. Reverse,
+    /// frame, encircle and links have no CSS property, so they are left out.
+    pub fn apply_to_css(&self) -> Vec<CssDeclaration> {
+        let mut declarations = Vec::new();
+        if let Some(color) = self.color {
+            declarations.push(("color", css_color(color, "inherit")));
+        }
+        if let Some(color) = self.background {
+            declarations.push(("background-color", css_color(color, "transparent")));
+        }
+        let states = self
+            .on
+            .iter()
+            .map(|&attribute| (attribute, true))
+            .chain(self.off.iter().map(|&attribute| (attribute, false)));
+        for (attribute, on) in states {
+            let declaration = match attribute {
+                TextAttribute::Bold => ("font-weight", if on { "bold" } else { "normal" }),
+                TextAttribute::Dim => ("opacity", if on { "0.5" } else { "1" }),
+                TextAttribute::Italic => ("font-style", if on { "italic" } else { "normal" }),
+                TextAttribute::Hidden => ("visibility", if on { "hidden" } else { "visible" }),
+                _ => continue,
+            };
+            declarations.push((declaration.0, declaration.1.to_string()));
+        }
+        if let Some(decorations) = &self.decorations {
+            let mut value: Vec<&str> = decorations
+                .iter()
+                .map(|attribute| match attribute {
+                    TextAttribute::Underline | TextAttribute::Underline2 => "underline",
+                    TextAttribute::Overline => "overline",
+                    TextAttribute::Strikethrough => "line-through",
+                    _ => "blink",
+                })
+                .collect();
+            value.dedup();
+            let value = if value.is_empty() {
+                "none".to_string()
+            } else {
+                value.join(" ")
+            };
+            declarations.push(("text-decoration", value));
+        }
+        declarations
+    }
+}
+
+/// Push the SGR parameters setting a foreground or background `color`.
+fn push_color(codes: &mut Vec<u8>, color: Color, background: bool, color_system: ColorSystem) {
+    let downgraded = color.downgrade(color_system);
+    if downgraded != color {
+        diagnostics::warn(|| Warning::ColorDowngrade {
+            color,
+            rendered: downgraded,
+            color_system,
+        });
+    }
+    let base = if background { 48 } else { 38 };
+    match downgraded {
+        Color::Default => codes.push(base + 1),
+        Color::Standard(standard) if background => codes.push(standard.ansi_bg_code()),
+        Color::Standard(standard) => codes.push(standard.ansi_fg_code()),
+        Color::EightBit(index) => codes.extend([base, 5, index]),
+        Color::TrueColor { r, g, b } => codes.extend([base, 2, r, g, b]),
+    }
+}
+
+/// Write `color` as a CSS color, with `default` for the default color.
+fn css_color(color: Color, default: &str) -> String {
+    match color {
+        Color::Default => default.to_string(),
+        color => {
+            let (r, g, b) = color.to_rgb();
+            format!("#{r:02x}{g:02x}{b:02x}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with(attribute: TextAttribute, value: Option<bool>) -> Style {
+        let mut style = Style::new();
+        let field = match attribute {
+            TextAttribute::Bold => &mut style.bold,
+            TextAttribute::Dim => &mut style.dim,
+            TextAttribute::Italic => &mut style.italic,
+            TextAttribute::Underline => &mut style.underline,
+            TextAttribute::Blink => &mut style.blink,
+            TextAttribute::Reverse => &mut style.reverse,
+            TextAttribute::Hidden => &mut style.hidden,
+            TextAttribute::Strikethrough => &mut style.strikethrough,
+            TextAttribute::Underline2 => &mut style.underline2,
+            TextAttribute::Frame => &mut style.frame,
+            TextAttribute::Encircle => &mut style.encircle,
+            TextAttribute::Overline => &mut style.overline,
+        };
+        *field = value;
+        style
+    }
+
+    #[test]
+    fn test_single_attribute_transitions() {
+        let sgr = |prev: &Style, next: &Style| {
+            StyleDelta::between(prev, next).apply_to_sgr(ColorSystem::TrueColor)
+        };
+        for attribute in TextAttribute::ALL {
+            let unset = Style::new();
+            let on = with(attribute, Some(true));
+            let off = with(attribute, Some(false));
+            assert_eq!(attribute.get(&on), Some(true));
+
+            assert_eq!(sgr(&unset, &on), [attribute.sgr_on()], "{attribute:?} on");
+            assert_eq!(sgr(&off, &on), [attribute.sgr_on()], "{attribute:?} on");
+            assert_eq!(sgr(&on, &off), [attribute.sgr_off()], "{attribute:?} off");
+            assert_eq!(sgr(&on, &unset), [attribute.sgr_off()], "{attribute:?} off");
+            assert_eq!(
+                sgr(&unset, &off),
+                [attribute.sgr_off()],
+                "{attribute:?} off"
+            );
+            assert!(StyleDelta::between(&on, &on).is_empty());
+            assert!(StyleDelta::between(&off, &unset).is_empty());
+
+            let delta = StyleDelta::between(&unset, &on);
+            assert_eq!((delta.on, delta.off), (vec![attribute], vec![]));
+            let delta = StyleDelta::between(&on, &unset);
+            assert_eq!((delta.on, delta.off), (vec![], vec![attribute]));
+        }
+    }
+
+    #[test]
+    fn test_bold_dim_matrix() {
+        let states = [None, Some(false), Some(true)];
+        for bold_before in states {
+            for dim_before in states {
+                for bold_after in states {
+                    for dim_after in states {
+                        let mut prev = with(TextAttribute::Bold, bold_before);
+                        prev.dim = dim_before;
+                        let mut next = with(TextAttribute::Bold, bold_after);
+                        next.dim = dim_after;
+                        let codes =
+                            StyleDelta::between(&prev, &next).apply_to_sgr(ColorSystem::TrueColor);
+
+                        // Replay the codes on the terminal's intensity state
+                        let (mut bold, mut dim) =
+                            (bold_before == Some(true), dim_before == Some(true));
+                        for code in &codes {
+                            match code {
+                                1 => bold = true,
+                                2 => dim = true,
+                                22 => (bold, dim) = (false, false),
+                                _ => unreachable!("{code}"),
+                            }
+                        }
+                        let context = format!("{prev:?} -> {next:?}: {codes:?}");
+                        assert_eq!(bold, bold_after == Some(true), "{context}");
+                        assert_eq!(dim, dim_after == Some(true), "{context}");
+                        assert!(codes.iter().filter(|&&code| code == 22).count() <= 1);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shared_off_codes() {
+        let sgr = |prev: &Style, next: &Style| {
+            StyleDelta::between(prev, next).apply_to_sgr(ColorSystem::TrueColor)
+        };
+        let both = Style::new().underline().underline2();
+        assert_eq!(sgr(&both, &Style::new().underline()), [24, 4]);
+        assert_eq!(sgr(&both, &Style::new().underline2()), [24, 21]);
+        assert_eq!(sgr(&both, &Style::new()), [24]);
+        let circled = Style::new().frame().encircle().bold().dim();
+        assert_eq!(
+            sgr(&circled, &Style::new().encircle().dim()),
+            [22, 54, 2, 52]
+        );
+        assert_eq!(sgr(&Style::new().overline(), &Style::new()), [55]);
+    }
+
+    #[test]
+    fn test_color_and_link_changes() {
+        let red = Style::new().with_color(Color::rgb(255, 0, 0));
+        let on_blue = Style::new().with_background(Color::EightBit(21));
+        let delta = StyleDelta::between(&red, &on_blue);
+        assert_eq!(delta.color, Some(Color::Default));
+        assert_eq!(delta.background, Some(Color::EightBit(21)));
+        assert_eq!(delta.apply_to_sgr(ColorSystem::EightBit), [39, 48, 5, 21]);
+        assert_eq!(
+            delta.apply_to_css(),
+            [
+                ("color", "inherit".to_string()),
+                ("background-color", "#0000ff".to_string())
+            ]
+        );
+
+        let link = Style::new().with_link("https://example.com");
+        let delta = StyleDelta::between(&link, &Style::new());
+        assert_eq!(delta.link.as_deref(), Some(""));
+        assert!(!delta.is_empty());
+        assert!(delta.apply_to_sgr(ColorSystem::TrueColor).is_empty());
+    }
+
+    #[test]
+    fn test_css_declarations() {
+        let prev = Style::new().underline().dim();
+        let next = Style::new().underline().strikethrough().bold().italic();
+        assert_eq!(
+            StyleDelta::between(&prev, &next).apply_to_css(),
+            [
+                ("font-weight", "bold".to_string()),
+                ("font-style", "italic".to_string()),
+                ("opacity", "1".to_string()),
+                ("text-decoration", "underline line-through".to_string()),
+            ]
+        );
+        let delta = StyleDelta::between(&next, &Style::new().hidden());
+        assert_eq!(
+            delta.apply_to_css(),
+            [
+                ("visibility", "hidden".to_string()),
+                ("font-weight", "normal".to_string()),
+                ("font-style", "normal".to_string()),
+                ("text-decoration", "none".to_string()),
+            ]
+        );
+        let next = Style::new().underline().underline2().overline();
+        assert_eq!(
+            StyleDelta::between(&Style::new(), &next).apply_to_css(),
+            [("text-decoration", "underline overline".to_string())]
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! Style layers - combining named styles in a fixed order of precedence.
+//!
+//! A component usually draws with several styles at once: its own
+//! defaults, a theme, what the user set, and a state such as selected or
+//! disabled. [`StyleLayers`] combines them in the order they are declared,
+//! later layers winning, and remembers which layer supplied each attribute
+//! of the result, so "why is this cell not red" has an answer.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use luxor::{Color, Style, StyleLayers};
+//!
+//! let theme = Style::new().bold().with_color(Color::rgb(200, 200, 200));
+//! let user = Style::new().with_color(Color::rgb(255, 0, 0));
+//! let layers = StyleLayers::new()
+//!     .layer("theme", theme)
+//!     .layer("user", user)
+//!     .layer_if(false, "disabled", Style::new().dim());
+//!
+//! let style = layers.resolve();
+//! assert_eq!(style.color, Some(Color::rgb(255, 0, 0)));
+//! assert_eq!(layers.source("color"), Some("user"));
+//! assert_eq!(layers.source("bold"), Some("theme"));
+//! assert_eq!(layers.source("dim"), None);
+//! ```
+
+use crate::Style;
+use std::{borrow::Cow, fmt};
+
+/// The name of a layer.
+type LayerName = Cow<'static, str>;
+
+/// Names of the attributes of a [`Style`], in the order they are tracked.
+const ATTRIBUTES: [&str; 15] = [
+    "color",
+    "background",
+    "bold",
+    "italic",
+    "underline",
+    "strikethrough",
+    "dim",
+    "reverse",
+    "blink",
+    "hidden",
+    "underline2",
+    "overline",
+    "frame",
+    "encircle",
+    "link",
+];
+
+/// Which of [`ATTRIBUTES`] `style` sets.
+fn set_attributes(style: &Style) -> [bool; 15] {
+    [
+        style.color.is_some(),
+        style.background.is_some(),
+        style.bold.is_some(),
+        style.italic.is_some(),
+        style.underline.is_some(),
+        style.strikethrough.is_some(),
+        style.dim.is_some(),
+        style.reverse.is_some(),
+        style.blink.is_some(),
+        style.hidden.is_some(),
+        style.underline2.is_some(),
+        style.overline.is_some(),
+        style.frame.is_some(),
+        style.encircle.is_some(),
+        style.link.is_some(),
+    ]
+}
+
+/// A style with only the attribute at `index` of [`ATTRIBUTES`] kept.
+fn only(style: &Style, index: usize) -> Style {
+    let mut single = Style::new();
+    match index {
+        0 => single.color = style.color,
+        1 => single.background = style.background,
+        2 => single.bold = style.bold,
+        3 => single.italic = style.italic,
+        4 => single.underline = style.underline,
+        5 => single.strikethrough = style.strikethrough,
+        6 => single.dim = style.dim,
+        7 => single.reverse = style.reverse,
+        8 => single.blink = style.blink,
+        9 => single.hidden = style.hidden,
+        10 => single.underline2 = style.underline2,
+        11 => single.overline = style.overline,
+        12 => single.frame = style.frame,
+        13 => single.encircle = style.encircle,
+        _ => single.link = style.link.clone(),
+    }
+    single
+}
+
+/// Named styles combined in declaration order, later layers winning.
+///
+/// Layers are folded in as they are added, so [`StyleLayers::resolve`] is
+/// a clone. A partly built stack can be kept and cloned to add per-item
+/// layers on top, such as a cached row style extended by the state of one
+/// cell.
+///
+/// The `Debug` output lists the layers and, for each attribute of the
+/// result, its value and the layer that supplied it.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct StyleLayers {
+    names: Vec<LayerName>,
+    resolved: Style,
+    sources: [Option<LayerName>; 15],
+}
+
+impl StyleLayers {
+    /// Create an empty stack, which resolves to the empty style.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layer above the existing ones.
+    pub fn layer(mut self, name: impl Into<Cow<'static, str>>, style: Style) -> Self {
+        self.push(name, style);
+        self
+    }
+
+    /// Add a layer above the existing ones if `condition` holds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Style, StyleLayers};
+    ///
+    /// let selected = true;
+    /// let layers = StyleLayers::new()
+    ///     .layer("base", Style::new().italic())
+    ///     .layer_if(selected, "selected", Style::new().reverse());
+    /// assert_eq!(layers.resolve(), Style::new().italic().reverse());
+    /// ```
+    pub fn layer_if(
+        self,
+        condition: bool,
+        name: impl Into<Cow<'static, str>>,
+        style: Style,
+    ) -> Self {
+        if condition {
+            self.layer(name, style)
+        } else {
+            self
+        }
+    }
+
+    /// Add a layer above the existing ones in place.
+    pub fn push(&mut self, name: impl Into<Cow<'static, str>>, style: Style) {
+        let name = name.into();
+        for (source, set) in self.sources.iter_mut().zip(set_attributes(&style)) {
+            if set {
+                *source = Some(name.clone());
+            }
+        }
+        self.resolved = std::mem::take(&mut self.resolved).combine(style);
+        self.names.push(name);
+    }
+
+    /// Get the combined style.
+    pub fn resolve(&self) -> Style {
+        self.resolved.clone()
+    }
+
+    /// Get the combined style over `base`, which every layer overrides.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Style, StyleLayers};
+    ///
+    /// let layers = StyleLayers::new().layer("state", Style::new().bold_off());
+    /// let style = layers.resolve_with_base(Style::new().bold().italic());
+    /// assert_eq!(style, Style::new().bold_off().italic());
+    /// ```
+    pub fn resolve_with_base(&self, base: Style) -> Style {
+        base.combine(self.resolved.clone())
+    }
+
+    /// Get the name of the layer that supplied `attribute` of the combined
+    /// style, or `None` if no layer sets it.
+    ///
+    /// Attributes are named as the fields of [`Style`]: `"color"`,
+    /// `"background"`, `"bold"`, `"link"` and so on.
+    pub fn source(&self, attribute: &str) -> Option<&str> {
+        let index = ATTRIBUTES.iter().position(|name| *name == attribute)?;
+        self.sources[index].as_deref()
+    }
+
+    /// Get the names of the layers, bottom first.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|name| name.as_ref())
+    }
+
+    /// Check whether the stack has no layers.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Shows each attribute of the combined style with the layer that set it,
+/// e.g. `StyleLayers { layers: ["theme", "user"], color: red from "user" }`.
+impl fmt::Debug for StyleLayers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("StyleLayers");
+        debug.field("layers", &self.names);
+        for (index, source) in self.sources.iter().enumerate() {
+            if let Some(source) = source {
+                debug.field(
+                    ATTRIBUTES[index],
+                    &format_args!("{} from {:?}", only(&self.resolved, index), source),
+                );
+            }
+        }
+        debug.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    #[test]
+    fn test_three_layer_provenance() {
+        let red = Color::rgb(255, 0, 0);
+        let layers = StyleLayers::new()
+            .layer(
+                "theme",
+                Style::new()
+                    .bold()
+                    .italic()
+                    .with_color(Color::rgb(0, 0, 255)),
+            )
+            .layer("user", Style::new().with_color(red).italic_off())
+            .layer(
+                "state",
+                Style::new()
+                    .italic()
+                    .with_background(Color::rgb(40, 40, 40)),
+            );
+
+        let style = layers.resolve();
+        assert_eq!(style.color, Some(red));
+        assert_eq!(style.italic, Some(true));
+        assert_eq!(style.bold, Some(true));
+        assert_eq!(layers.source("color"), Some("user"));
+        assert_eq!(layers.source("italic"), Some("state"));
+        assert_eq!(layers.source("bold"), Some("theme"));
+        assert_eq!(layers.source("background"), Some("state"));
+        assert_eq!(layers.source("underline"), None);
+        assert_eq!(layers.source("no such attribute"), None);
+
+        assert_eq!(
+            format!("{:?}", layers),
+            "StyleLayers { layers: [\"theme\", \"user\", \"state\"], \
+             color: #ff0000 from \"user\", background: on #282828 from \"state\", \
+             bold: bold from \"theme\", italic: italic from \"state\" }"
+        );
+    }
+
+    #[test]
+    fn test_cached_base_extended_per_item() {
+        let row = StyleLayers::new()
+            .layer("table", Style::new().dim())
+            .layer("row", Style::new().with_color(Color::rgb(0, 128, 0)));
+
+        let plain = row.clone().layer_if(false, "selected", Style::new().bold());
+        let selected = row
+            .clone()
+            .layer_if(true, "selected", Style::new().dim_off());
+        assert_eq!(plain, row);
+        assert_eq!(plain.resolve().dim, Some(true));
+        assert_eq!(selected.resolve().dim, Some(false));
+        assert_eq!(selected.source("dim"), Some("selected"));
+        assert_eq!(selected.source("color"), Some("row"));
+        assert_eq!(
+            selected.names().collect::<Vec<_>>(),
+            ["table", "row", "selected"]
+        );
+        assert!(StyleLayers::new().is_empty());
+        assert_eq!(StyleLayers::new().resolve(), Style::new());
+    }
+}
@@ -0,0 +1,188 @@
+//! Tee - a plain-text transcript of console output.
+//!
+//! [`Console::tee_plain`](crate::Console::tee_plain) duplicates everything
+//! the console prints to a second writer, without styles or escape codes.
+//! Cursor movement is resolved rather than copied, so a progress display
+//! that redraws itself in place shows up in the transcript according to the
+//! [`TeeMode`].
+
+use crate::{
+    ControlCode, Segment,
+    diagnostics::{self, Warning},
+};
+use std::{fmt, io::Write};
+
+/// A writer receiving a plain transcript.
+pub type TeeWriter = Box<dyn Write + Send>;
+
+/// How in-place rewrites appear in a plain transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TeeMode {
+    /// Record only the final state of lines that are redrawn, so a live
+    /// display contributes its last frame. A line is written once later
+    /// output shows it is no longer being rewritten, or when the tee is
+    /// detached.
+    #[default]
+    Final,
+    /// Record every state of redrawn lines one after another, as it would
+    /// look scrolling past. Lines are written as soon as they end.
+    Sequential,
+}
+
+/// The secondary writer of a console and the lines not yet written to it.
+pub(crate) struct PlainTee {
+    writer: Option<TeeWriter>,
+    mode: TeeMode,
+    /// Lines that may still be rewritten, the last one being the cursor line.
+    lines: Vec<String>,
+}
+
+impl PlainTee {
+    pub(crate) fn new(writer: TeeWriter, mode: TeeMode) -> Self {
+        Self {
+            writer: Some(writer),
+            mode,
+            lines: vec![String::new()],
+        }
+    }
+
+    /// Record one write of the console.
+    pub(crate) fn write_segments(&mut self, segments: &[Segment]) {
+        let Some(first) = segments.first() else {
+            return;
+        };
+        // Output that does not start by moving back over earlier lines
+        // settles everything above the cursor
+        if self.mode == TeeMode::Final && !first.get_control().is_some_and(is_rewrite) {
+            let settled = self.lines.len() - 1;
+            self.commit(settled);
+        }
+
+        for segment in segments {
+            match segment.get_control() {
+                Some(control) => self.control(control),
+                None => self.text(segment.text()),
+            }
+        }
+    }
+
+    /// Record text, starting a new line at each newline.
+    fn text(&mut self, text: &str) {
+        let mut parts = text.split('\n');
+        if let Some(part) = parts.next() {
+            self.current().push_str(part);
+        }
+        for part in parts {
+            self.lines.push(part.to_string());
+            if self.mode == TeeMode::Sequential {
+                self.commit(1);
+            }
+        }
+    }
+
+    /// Apply cursor movement to the pending lines.
+    fn control(&mut self, control: ControlCode) {
+        match (self.mode, control) {
+            (
+                TeeMode::Sequential,
+                ControlCode::CarriageReturn
+                | ControlCode::CursorMoveToColumn(_)
+                | ControlCode::CursorBackward(_),
+            ) => {
+                if !self.current().is_empty() {
+                    self.lines.push(String::new());
+                    self.commit(1);
+                }
+            }
+            (TeeMode::Sequential, _) => {}
+            (
+                TeeMode::Final,
+                ControlCode::CarriageReturn
+                | ControlCode::CursorMoveToColumn(_)
+                | ControlCode::CursorBackward(_),
+            ) => self.current().clear(),
+            (TeeMode::Final, ControlCode::CursorUp(count)) => {
+                let row = self.lines.len() - 1;
+                self.lines.truncate(row.saturating_sub(count) + 1);
+                self.current().clear();
+            }
+            (TeeMode::Final, ControlCode::CursorDown(count)) => {
+                let rows = self.lines.len() + count;
+                self.lines.resize(rows, String::new());
+            }
+            (TeeMode::Final, ControlCode::Clear | ControlCode::Home) => {
+                self.lines = vec![String::new()];
+            }
+            (TeeMode::Final, _) => {}
+        }
+    }
+
+    fn current(&mut self) -> &mut String {
+        self.lines
+            .last_mut()
+            .expect("a tee always has a cursor line")
+    }
+
+    /// Write the first `count` pending lines, which can no longer change.
+    fn commit(&mut self, count: usize) {
+        let lines: Vec<String> = self.lines.drain(..count).collect();
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+        for line in lines {
+            let result = writeln!(writer, "{}", line).and_then(|_| writer.flush());
+            if let Err(error) = result {
+                // The transcript is secondary; keep the console working
+                diagnostics::warn(|| Warning::TeeDetached {
+                    error: error.to_string(),
+                });
+                self.writer = None;
+                return;
+            }
+        }
+    }
+
+    /// Write all pending lines and hand back the writer.
+    pub(crate) fn finish(mut self) -> Option<TeeWriter> {
+        self.flush_pending();
+        self.writer.take()
+    }
+
+    fn flush_pending(&mut self) {
+        if self.current().is_empty() {
+            self.lines.pop();
+        }
+        let pending = self.lines.len();
+        self.commit(pending);
+        self.lines.push(String::new());
+    }
+}
+
+impl Drop for PlainTee {
+    fn drop(&mut self) {
+        self.flush_pending();
+    }
+}
+
+impl fmt::Debug for PlainTee {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PlainTee")
+            .field("mode", &self.mode)
+            .field("attached", &self.writer.is_some())
+            .field("pending_lines", &self.lines.len())
+            .finish()
+    }
+}
+
+/// Check if a control code moves back over output to redraw it.
+fn is_rewrite(control: ControlCode) -> bool {
+    matches!(
+        control,
+        ControlCode::CarriageReturn
+            | ControlCode::CursorUp(_)
+            | ControlCode::CursorBackward(_)
+            | ControlCode::CursorMoveToColumn(_)
+            | ControlCode::Home
+            | ControlCode::Clear
+    )
+}
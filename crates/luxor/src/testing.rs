@@ -0,0 +1,226 @@
+//! Test utilities for asserting on rendered output.
+//!
+//! Enabled with the `test-util` feature. [`assert_render_eq!`] compares
+//! rendered lines against an expected string and, on failure, prints a
+//! cell-precise diff instead of two walls of text:
+//!
+//! ```text
+//!   1 │ ab····
+//! - 2 │ cdef··
+//! + 2 │ cdxf··
+//!         ^
+//! ```
+//!
+//! Trailing spaces show as `·` and control characters as their Unicode
+//! control pictures (`␛` for escape), so invisible differences stand out.
+//!
+//! [`assert_render_eq!`]: crate::assert_render_eq
+
+use crate::{Color, ColorSystem, Segment, StandardColor, Style};
+use std::io::IsTerminal;
+use unicode_width::UnicodeWidthStr;
+
+/// Split rendered segments into plain text lines.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Segment, Style, testing::render_lines};
+///
+/// let segments = vec![
+///     Segment::new("ab".to_string(), Style::new()),
+///     Segment::line(),
+///     Segment::new("cd".to_string(), Style::new()),
+/// ];
+/// assert_eq!(render_lines(&segments), vec!["ab", "cd"]);
+/// ```
+pub fn render_lines(segments: &[Segment]) -> Vec<String> {
+    let plain: String = segments.iter().map(|s| s.text()).collect();
+    plain.split('\n').map(String::from).collect()
+}
+
+/// Produce a line-by-line diff of expected and actual rendered lines.
+///
+/// Each differing line is shown as a `-`/`+` pair followed by a `^` marker
+/// under the first differing cell. The diff is colored when stderr is a
+/// terminal.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::testing::render_diff;
+///
+/// let expected = vec!["ab".to_string(), "cd".to_string()];
+/// let actual = vec!["ab".to_string(), "cx".to_string()];
+/// let diff = render_diff(&expected, &actual);
+/// assert!(diff.contains("- 2 │ cd"));
+/// assert!(diff.contains("+ 2 │ cx"));
+/// ```
+pub fn render_diff(expected: &[String], actual: &[String]) -> String {
+    diff_lines(expected, actual, std::io::stderr().is_terminal())
+}
+
+/// Build the diff, optionally styled with ANSI colors.
+fn diff_lines(expected: &[String], actual: &[String], styled: bool) -> String {
+    let line_count = expected.len().max(actual.len());
+    let number_width = line_count.to_string().len();
+    let paint = |text: String, color: StandardColor| {
+        if styled {
+            let style = Style::new().with_color(Color::Standard(color));
+            Segment::new(text, style).render(ColorSystem::Standard)
+        } else {
+            text
+        }
+    };
+
+    let mut output = Vec::new();
+    for index in 0..line_count {
+        let number = index + 1;
+        let expected_line = expected.get(index).map(|line| visible(line));
+        let actual_line = actual.get(index).map(|line| visible(line));
+
+        if expected_line == actual_line {
+            let line = expected_line.unwrap_or_default();
+            output.push(format!("  {:>number_width$} │ {}", number, line));
+            continue;
+        }
+
+        let expected_line = expected_line.unwrap_or_else(|| MISSING.to_string());
+        let actual_line = actual_line.unwrap_or_else(|| MISSING.to_string());
+        let column = first_difference_cell(&expected_line, &actual_line);
+
+        output.push(paint(
+            format!("- {:>number_width$} │ {}", number, expected_line),
+            StandardColor::Red,
+        ));
+        output.push(paint(
+            format!("+ {:>number_width$} │ {}", number, actual_line),
+            StandardColor::Green,
+        ));
+        let indent = " ".repeat(number_width + 5 + column);
+        output.push(paint(format!("{}^", indent), StandardColor::Yellow));
+    }
+
+    output.join("\n")
+}
+
+/// Placeholder shown for a line present on only one side.
+const MISSING: &str = "<no line>";
+
+/// Make trailing whitespace and control characters visible.
+fn visible(line: &str) -> String {
+    let content = line.trim_end_matches(' ');
+    let trailing = line.len() - content.len();
+    let mut output: String = content
+        .chars()
+        .map(|ch| match ch {
+            '\u{0}'..='\u{1f}' => char::from_u32(0x2400 + ch as u32).unwrap_or(ch),
+            '\u{7f}' => '␡',
+            _ => ch,
+        })
+        .collect();
+    output.extend(std::iter::repeat('·').take(trailing));
+    output
+}
+
+/// Get the cell column of the first character that differs.
+fn first_difference_cell(expected: &str, actual: &str) -> usize {
+    let prefix_bytes = expected
+        .char_indices()
+        .zip(actual.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((index, _), _)| index)
+        .unwrap_or_else(|| expected.len().min(actual.len()));
+    expected[..prefix_bytes].width()
+}
+
+/// Assert that rendered lines match an expected multi-line string.
+///
+/// `actual` is anything iterable over lines (e.g. the result of
+/// [`render_lines`]); `expected` is split on `\n`. On mismatch the panic
+/// message contains a cell-precise diff from [`render_diff`].
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Console, RenderOptions, Renderable, Text, assert_render_eq, testing::render_lines};
+///
+/// let options = RenderOptions::new().with_max_width(10);
+/// let segments = Text::new("a\nb").render(&Console::new(), &options).unwrap();
+/// assert_render_eq!(render_lines(&segments), "a\nb");
+/// ```
+#[macro_export]
+macro_rules! assert_render_eq {
+    ($actual:expr, $expected:expr $(,)?) => {{
+        let actual: ::std::vec::Vec<::std::string::String> =
+            ::std::iter::IntoIterator::into_iter($actual)
+                .map(|line| ::std::string::ToString::to_string(&line))
+                .collect();
+        let expected: ::std::vec::Vec<::std::string::String> = $expected
+            .split('\n')
+            .map(::std::string::String::from)
+            .collect();
+        if actual != expected {
+            panic!(
+                "rendered output differs from expected:\n{}",
+                $crate::testing::render_diff(&expected, &actual)
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.split('\n').map(String::from).collect()
+    }
+
+    #[test]
+    fn test_visible_whitespace_and_controls() {
+        assert_eq!(visible("ab  "), "ab··");
+        assert_eq!(visible("a b"), "a b");
+        assert_eq!(visible("\x1b[1m\t"), "␛[1m␉");
+    }
+
+    #[test]
+    fn test_diff_marks_first_differing_cell() {
+        let diff = diff_lines(&lines("ab\ncdef"), &lines("ab\ncdxf"), false);
+        assert_eq!(diff, "  1 │ ab\n- 2 │ cdef\n+ 2 │ cdxf\n        ^");
+    }
+
+    #[test]
+    fn test_diff_counts_cells_not_chars() {
+        let diff = diff_lines(&lines("漢字a"), &lines("漢字b"), false);
+        let marker = diff.lines().last().unwrap();
+        assert_eq!(marker, format!("{}^", " ".repeat(6 + 4)));
+    }
+
+    #[test]
+    fn test_diff_trailing_whitespace_and_missing_lines() {
+        let diff = diff_lines(&lines("ab"), &lines("ab \nc"), false);
+        assert_eq!(
+            diff,
+            "- 1 │ ab\n+ 1 │ ab·\n        ^\n- 2 │ <no line>\n+ 2 │ c\n      ^"
+        );
+    }
+
+    #[test]
+    fn test_diff_styled() {
+        let diff = diff_lines(&lines("a"), &lines("b"), true);
+        assert!(diff.contains("\x1b[31m- 1 │ a"));
+        assert!(diff.contains("\x1b[32m+ 1 │ b"));
+    }
+
+    #[test]
+    fn test_assert_render_eq_passes() {
+        crate::assert_render_eq!(vec!["ab", "cd"], "ab\ncd");
+    }
+
+    #[test]
+    #[should_panic(expected = "- 2 │ cd")]
+    fn test_assert_render_eq_fails_with_diff() {
+        crate::assert_render_eq!(vec!["ab", "cx"], "ab\ncd");
+    }
+}
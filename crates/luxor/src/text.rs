@@ -1,21 +1,283 @@
 //! Rich text implementation with style spans and markup support.
 
 use crate::{
-    console::{Console, ConsoleOptions},
+    console::Console,
     error::{LuxorError, Result},
     markup::Span,
     measure::Measurement,
+    options::{CANCEL_CHECK_INTERVAL, RenderOptions},
     protocol::{Measurable, Renderable},
+    render_utils,
+    sanitize::{self, Policy},
     segment::Segment,
     style::Style,
+    wrap::{WhitespaceBreaks, WrapOptions},
 };
-use std::ops::Range;
-use unicode_width::UnicodeWidthStr;
+use std::{borrow::Cow, ops::Range};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Horizontal justification of text lines within the available width.
+///
+/// In every mode, lines wider than the available width wrap at whitespace;
+/// only a word wider than the width is split between characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Justify {
+    /// Align lines to the left edge (no padding is added).
+    #[default]
+    Left,
+    /// Center lines, padding both sides.
+    Center,
+    /// Align lines to the right edge.
+    Right,
+    /// Stretch every line to the full width, except the last
+    /// line of each paragraph. Lines with a single word are never stretched.
+    ///
+    /// Extra spaces go into the existing gaps between words, leftmost gaps
+    /// first, and take the style of the word before the gap.
+    Full,
+    /// Like [`Justify::Full`], but also stretch the last line of each
+    /// paragraph.
+    FullAll,
+}
+
+/// Where text is cut when it is truncated to fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OverflowPosition {
+    /// Keep the start of the text and mark the cut at the end.
+    #[default]
+    End,
+    /// Keep the end of the text and mark the cut at the start.
+    Start,
+    /// Keep both ends and mark the cut in the middle.
+    Middle,
+}
+
+/// How truncated text marks the cut.
+///
+/// The marker's width always counts against the available width. Its style
+/// is combined over the style of the text it replaces, so a dim marker in a
+/// colored cell stays on the cell's background; the marker style's own
+/// background is ignored so it never hides the marker.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{EllipsisConfig, OverflowPosition, Text};
+///
+/// let mut text = Text::new("src/components/badge.rs");
+/// text.truncate(12, &EllipsisConfig::default().with_position(OverflowPosition::Start));
+/// assert_eq!(text.plain(), "…ts/badge.rs");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EllipsisConfig {
+    /// Text marking the cut.
+    pub marker: String,
+    /// Style of the marker, combined over the style of the cut text.
+    pub style: Option<Style>,
+    /// Where the text is cut.
+    pub position: OverflowPosition,
+}
+
+impl EllipsisConfig {
+    /// Create a config with the given marker, unstyled, cutting at the end.
+    pub fn new(marker: impl Into<String>) -> Self {
+        Self {
+            marker: marker.into(),
+            style: None,
+            position: OverflowPosition::End,
+        }
+    }
+
+    /// Set the style of the marker.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set where the text is cut.
+    pub fn with_position(mut self, position: OverflowPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Get the marker's style over the given surrounding style.
+    fn marker_style(&self, surrounding: Style) -> Style {
+        match &self.style {
+            Some(style) => {
+                let mut style = style.clone();
+                style.background = None;
+                surrounding.combine(style)
+            }
+            None => surrounding,
+        }
+    }
+}
+
+/// A dim "…" at the end.
+impl Default for EllipsisConfig {
+    fn default() -> Self {
+        Self::new("…").with_style(Style::new().dim())
+    }
+}
+
+/// How right-to-left text is handled when rendering.
+///
+/// Luxor lays out text in logical order and leaves reordering to the
+/// terminal, whose bidi algorithm (if any) works on whole lines. An Arabic
+/// or Hebrew run next to a border can then pull the border characters into
+/// its reordering, so the border no longer appears in its column. Full bidi
+/// layout is out of scope; these policies only keep the damage contained.
+/// Widths are always computed with `unicode-width` on the logical text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BidiPolicy {
+    /// Render right-to-left text unchanged.
+    #[default]
+    Ignore,
+    /// Wrap each right-to-left run in FIRST STRONG ISOLATE (U+2068) and POP
+    /// DIRECTIONAL ISOLATE (U+2069), so terminals that implement isolates
+    /// reorder the run without touching what surrounds it. Both characters
+    /// are zero width. Terminals without bidi support show the text in
+    /// logical order either way.
+    Isolate,
+    /// Render unchanged, but report each render of right-to-left text as a
+    /// [`Warning::RightToLeft`](crate::diagnostics::Warning::RightToLeft).
+    Warn,
+}
+
+/// FIRST STRONG ISOLATE, opening a directional isolate.
+const FSI: char = '\u{2068}';
+/// POP DIRECTIONAL ISOLATE, closing a directional isolate.
+const PDI: char = '\u{2069}';
+
+/// Check if a character belongs to a right-to-left script block (Hebrew,
+/// Arabic, Syriac, Thaana, N'Ko and their presentation forms, among others).
+fn is_rtl(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{0590}'..='\u{08FF}'
+            | '\u{FB1D}'..='\u{FDFF}'
+            | '\u{FE70}'..='\u{FEFF}'
+            | '\u{10800}'..='\u{10FFF}'
+            | '\u{1E800}'..='\u{1EFFF}'
+    )
+}
+
+/// Check if text contains characters from a right-to-left script.
+///
+/// Detection is by Unicode block rather than the full bidi character
+/// classes, which is enough to decide whether a [`BidiPolicy`] applies.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::text::contains_rtl;
+///
+/// assert!(contains_rtl("status: שלום"));
+/// assert!(!contains_rtl("status: ok"));
+/// ```
+pub fn contains_rtl(text: &str) -> bool {
+    text.chars().any(is_rtl)
+}
+
+/// Wrap each right-to-left run in `text` in directional isolates.
+///
+/// A run starts and ends at a right-to-left character and includes the
+/// spaces, digits and punctuation between them; it ends before a
+/// left-to-right letter or a newline. Text without right-to-left characters
+/// is returned unchanged, as are runs already isolated, so isolating twice
+/// is the same as isolating once.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::text::isolate_rtl;
+///
+/// assert_eq!(isolate_rtl("a שלום עולם!"), "a \u{2068}שלום עולם\u{2069}!");
+/// assert_eq!(isolate_rtl("plain"), "plain");
+/// ```
+pub fn isolate_rtl(text: &str) -> String {
+    if !contains_rtl(text) {
+        return text.to_string();
+    }
+
+    let mut output = String::with_capacity(text.len() + 6);
+    // Neutral characters after the last RTL character, held back until it
+    // is known whether the run continues past them
+    let mut pending = String::new();
+    let mut in_run = false;
+    // Depth inside isolates already in the text, which are left as they are
+    let mut isolated = 0usize;
+    for ch in text.chars() {
+        if isolated > 0 {
+            match ch {
+                '\u{2066}'..='\u{2068}' => isolated += 1,
+                PDI => isolated -= 1,
+                _ => {}
+            }
+            output.push(ch);
+        } else if matches!(ch, '\u{2066}'..='\u{2068}') {
+            if in_run {
+                output.push(PDI);
+                in_run = false;
+            }
+            output.push_str(&pending);
+            pending.clear();
+            output.push(ch);
+            isolated = 1;
+        } else if is_rtl(ch) {
+            if !in_run {
+                output.push(FSI);
+                in_run = true;
+            }
+            output.push_str(&pending);
+            pending.clear();
+            output.push(ch);
+        } else if in_run && ch != '\n' && !ch.is_alphabetic() {
+            pending.push(ch);
+        } else {
+            if in_run {
+                output.push(PDI);
+                in_run = false;
+            }
+            output.push_str(&pending);
+            pending.clear();
+            output.push(ch);
+        }
+    }
+    if in_run {
+        output.push(PDI);
+    }
+    output.push_str(&pending);
+    output
+}
 
 /// A rich text object that supports styled spans within the text.
 ///
 /// Text can contain multiple style spans that apply different formatting
 /// to different portions of the text content.
+///
+/// # Overlapping spans
+///
+/// Spans are kept in the order they were applied, and where spans overlap
+/// the later one wins: the style of a character is the base style combined
+/// with each span covering it in application order, so attributes set by a
+/// later span override those of earlier ones while unset attributes are
+/// inherited. [`Text::style_at`] and rendering follow the same rule.
+///
+/// Markup applies each tag's span when the tag opens, so nested tags win
+/// over the tags around them. Appended and inserted texts bring their own
+/// spans after the existing ones.
+///
+/// ```rust
+/// use luxor::{Color, Style, Text};
+///
+/// let red = Style::new().with_color(Color::rgb(255, 0, 0));
+/// let blue = Style::new().with_color(Color::rgb(0, 0, 255));
+/// let mut text = Text::new("overlap");
+/// text.stylize_range(2..7, blue).unwrap();
+/// text.stylize_range(0..4, red.clone()).unwrap();
+/// assert_eq!(text.style_at(3), red);
+/// ```
 #[derive(Debug, Clone)]
 pub struct Text {
     /// The plain text content
@@ -24,6 +286,12 @@ pub struct Text {
     base_style: Style,
     /// Style spans that apply to portions of the text
     spans: Vec<Span>,
+    /// Justification of each line within the render width
+    justify: Justify,
+    /// Whether padding to the full render width carries the base style
+    full_width_background: bool,
+    /// Sanitizing of control characters, overriding the render options
+    sanitize: Option<Policy>,
 }
 
 impl Text {
@@ -42,6 +310,9 @@ impl Text {
             content: content.to_string(),
             base_style: Style::default(),
             spans: Vec::new(),
+            justify: Justify::default(),
+            full_width_background: false,
+            sanitize: None,
         }
     }
 
@@ -52,7 +323,7 @@ impl Text {
     /// ```rust
     /// use luxor::{Text, Style, Color};
     ///
-    /// let style = Style::new().color(Color::rgb(255, 0, 0));
+    /// let style = Style::new().with_color(Color::rgb(255, 0, 0));
     /// let text = Text::new("Hello").with_style(style);
     /// ```
     pub fn with_style(mut self, style: Style) -> Self {
@@ -60,6 +331,79 @@ impl Text {
         self
     }
 
+    /// Set the justification used when rendering each line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Justify, Text};
+    ///
+    /// let text = Text::new("Title").with_justify(Justify::Center);
+    /// assert_eq!(text.justify(), Justify::Center);
+    /// ```
+    pub fn with_justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Extend the base style's background across the full render width.
+    ///
+    /// When enabled, every rendered line is padded to the available width with
+    /// spaces carrying the base style, so banners and headers show a solid bar
+    /// of background color. Measurement is unaffected and stays content-based.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Color, Console, RenderOptions, Justify, Renderable, Style, Text};
+    ///
+    /// let banner = Text::new("DEPLOY")
+    ///     .with_style(Style::new().with_background(Color::rgb(255, 0, 0)))
+    ///     .with_justify(Justify::Center)
+    ///     .with_full_width_background(true);
+    /// let options = RenderOptions::new().with_max_width(20);
+    /// let segments = banner.render(&Console::new(), &options).unwrap();
+    /// let width: usize = segments.iter().map(|s| s.cell_length()).sum();
+    /// assert_eq!(width, 20);
+    /// ```
+    pub fn with_full_width_background(mut self, enabled: bool) -> Self {
+        self.full_width_background = enabled;
+        self
+    }
+
+    /// Sanitize this text under `policy` whenever it is measured or
+    /// rendered, whatever the render options say. Use
+    /// [`Policy::Strict`] for text from untrusted sources. See the
+    /// [`sanitize`] module.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Text, sanitize::Policy};
+    ///
+    /// let text = Text::new("name\x1b]52;c;eA==\x07").with_sanitize(Policy::Strict);
+    /// assert_eq!(text.width(), 4);
+    /// ```
+    pub fn with_sanitize(mut self, policy: Policy) -> Self {
+        self.sanitize = Some(policy);
+        self
+    }
+
+    /// Get the sanitize policy set on this text, if any.
+    pub fn sanitize(&self) -> Option<Policy> {
+        self.sanitize
+    }
+
+    /// Get the justification of this text.
+    pub fn justify(&self) -> Justify {
+        self.justify
+    }
+
+    /// Check if the background is extended to the full render width.
+    pub fn full_width_background(&self) -> bool {
+        self.full_width_background
+    }
+
     /// Set the base style for this text.
     pub fn set_style(&mut self, style: Style) {
         self.base_style = style;
@@ -75,12 +419,17 @@ impl Text {
         &self.base_style
     }
 
-    /// Get all style spans in this text.
+    /// Get all style spans in this text, in the order they were applied.
+    ///
+    /// See [the overlap rule](Text#overlapping-spans) for how the order
+    /// affects rendering.
     pub fn spans(&self) -> &[Span] {
         &self.spans
     }
 
     /// Get a mutable reference to the spans.
+    ///
+    /// The position of a span in the vector is its application order.
     pub fn spans_mut(&mut self) -> &mut Vec<Span> {
         &mut self.spans
     }
@@ -96,8 +445,14 @@ impl Text {
     }
 
     /// Get the display width of the text (considering Unicode width).
+    ///
+    /// With a sanitize policy set on the text, this is the width after
+    /// sanitizing.
     pub fn width(&self) -> usize {
-        self.content.width()
+        match self.sanitize {
+            Some(policy) => sanitize::sanitize(&self.content, policy).width(),
+            None => self.content.width(),
+        }
     }
 
     /// Apply a style to a range of characters.
@@ -113,7 +468,7 @@ impl Text {
     /// use luxor::{Text, Style, Color};
     ///
     /// let mut text = Text::new("Hello world");
-    /// let style = Style::new().color(Color::rgb(255, 0, 0)).bold();
+    /// let style = Style::new().with_color(Color::rgb(255, 0, 0)).bold();
     /// text.stylize_range(0..5, style).unwrap(); // Make "Hello" red and bold
     /// ```
     pub fn stylize_range(&mut self, range: Range<usize>, style: Style) -> Result<()> {
@@ -136,16 +491,78 @@ impl Text {
             )));
         }
 
-        // Add the new span
-        let span = Span::new(start, end, style);
-        self.spans.push(span);
-
-        // Sort spans by start position for consistent rendering
-        self.spans.sort_by_key(|s| s.start);
+        // Spans stay in application order, so this one wins where it overlaps
+        self.spans.push(Span::new(start, end, style));
 
         Ok(())
     }
 
+    /// Style every occurrence of each of `words`, returning how many were
+    /// found.
+    ///
+    /// Occurrences of one word do not overlap; empty words are skipped.
+    /// Without `case_sensitive`, characters are compared by their lowercase
+    /// forms.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Style, Text};
+    ///
+    /// let mut text = Text::new("Cargo.toml and cargo.lock");
+    /// let found = text.highlight_words(&["cargo"], Style::new().bold(), false);
+    /// assert_eq!(found, 2);
+    /// assert_eq!(text.style_at(15).bold, Some(true));
+    /// ```
+    pub fn highlight_words(&mut self, words: &[&str], style: Style, case_sensitive: bool) -> usize {
+        let mut found = 0;
+        for word in words {
+            for range in self.find_all(word, !case_sensitive) {
+                self.spans
+                    .push(Span::new(range.start, range.end, style.clone()));
+                found += 1;
+            }
+        }
+        found
+    }
+
+    /// Find every occurrence of `needle`, as character ranges in order.
+    ///
+    /// Occurrences do not overlap, and an empty needle finds nothing. With
+    /// `case_insensitive`, characters are compared by their lowercase forms.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Text;
+    ///
+    /// let text = Text::new("Error: error in errors.rs");
+    /// assert_eq!(text.find_all("error", false), [7..12, 16..21]);
+    /// assert_eq!(text.find_all("error", true), [0..5, 7..12, 16..21]);
+    /// ```
+    pub fn find_all(&self, needle: &str, case_insensitive: bool) -> Vec<Range<usize>> {
+        let content: Vec<char> = self.content.chars().collect();
+        let needle: Vec<char> = needle.chars().collect();
+        let same = |a: char, b: char| {
+            a == b || (case_insensitive && a.to_lowercase().eq(b.to_lowercase()))
+        };
+        let mut ranges = Vec::new();
+        if needle.is_empty() {
+            return ranges;
+        }
+        let mut start = 0;
+        while start + needle.len() <= content.len() {
+            let window = &content[start..start + needle.len()];
+            if window.iter().zip(&needle).all(|(&a, &b)| same(a, b)) {
+                ranges.push(start..start + needle.len());
+                start += needle.len();
+            } else {
+                start += 1;
+            }
+        }
+        ranges
+    }
+
     /// Apply a style to the entire text.
     ///
     /// This is equivalent to calling `stylize_range(0..text.len(), style)`.
@@ -180,7 +597,12 @@ impl Text {
         // No need to adjust spans since we're only adding at the end
     }
 
-    /// Append another Text object to this one, preserving its spans.
+    /// Append another Text object to this one, preserving its appearance.
+    ///
+    /// The other text's spans are moved along with its content, and its base
+    /// style becomes a span over the appended range. The appended text still
+    /// inherits this text's base style for any attribute it leaves unset,
+    /// just as nested markup tags inherit from their parents.
     ///
     /// # Examples
     ///
@@ -189,7 +611,7 @@ impl Text {
     ///
     /// let mut text1 = Text::new("Hello ");
     /// let mut text2 = Text::new("world");
-    /// text2.stylize_all(Style::new().color(Color::rgb(255, 0, 0))).unwrap();
+    /// text2.stylize_all(Style::new().with_color(Color::rgb(255, 0, 0))).unwrap();
     ///
     /// text1.append_text(text2);
     /// assert_eq!(text1.plain(), "Hello world");
@@ -198,16 +620,320 @@ impl Text {
     pub fn append_text(&mut self, other: Text) {
         let offset = self.len(); // Use character count, not byte count
         self.content.push_str(&other.content);
+        self.spans.extend(other.into_spans_at(offset));
+    }
+
+    /// Insert another Text object at a character position.
+    ///
+    /// Like [`Text::append_text`], the inserted text keeps its spans and base
+    /// style. Spans of this text after `position` move along, and a span
+    /// crossing `position` is split so it does not restyle the inserted text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `position` is past the end of the text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Color, Style, Text};
+    ///
+    /// let mut text = Text::from_markup("[bold]Hello world[/bold]").unwrap();
+    /// let red = Style::new().with_color(Color::rgb(255, 0, 0));
+    /// text.insert_text(6, Text::new("big ").with_style(red)).unwrap();
+    /// assert_eq!(text.plain(), "Hello big world");
+    /// assert_eq!(text.style_at(6).bold, None);
+    /// assert_eq!(text.style_at(10).bold, Some(true));
+    /// ```
+    pub fn insert_text(&mut self, position: usize, other: Text) -> Result<()> {
+        if position > self.len() {
+            return Err(LuxorError::InvalidRange(format!(
+                "Insert position {} is out of bounds for text of length {}",
+                position,
+                self.len()
+            )));
+        }
+
+        let inserted = other.len();
+        let byte_index = self
+            .content
+            .char_indices()
+            .nth(position)
+            .map_or(self.content.len(), |(index, _)| index);
+        self.content.insert_str(byte_index, &other.content);
+
+        let mut spans = Vec::with_capacity(self.spans.len() + other.spans.len() + 1);
+        for span in self.spans.drain(..) {
+            if span.start >= position {
+                spans.push(Span::new(
+                    span.start + inserted,
+                    span.end + inserted,
+                    span.style,
+                ));
+            } else if span.end > position {
+                spans.push(Span::new(span.start, position, span.style.clone()));
+                spans.push(Span::new(
+                    position + inserted,
+                    span.end + inserted,
+                    span.style,
+                ));
+            } else {
+                spans.push(span);
+            }
+        }
+        spans.extend(other.into_spans_at(position));
+        self.spans = spans;
+        Ok(())
+    }
+
+    /// Assemble a text from pieces joined by a plain separator.
+    ///
+    /// Each piece keeps its own styling as with [`Text::append_text`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Color, Style, Text};
+    ///
+    /// let key = Text::new("name").with_style(Style::new().bold());
+    /// let value = Text::new("luxor").with_style(Style::new().with_color(Color::rgb(0, 128, 0)));
+    /// let text = Text::assemble([key, value], ": ");
+    /// assert_eq!(text.plain(), "name: luxor");
+    /// assert_eq!(text.style_at(0).bold, Some(true));
+    /// assert_eq!(text.style_at(4), Style::new());
+    /// ```
+    pub fn assemble(pieces: impl IntoIterator<Item = Text>, separator: &str) -> Self {
+        let mut text = Text::new("");
+        for (index, piece) in pieces.into_iter().enumerate() {
+            if index > 0 {
+                text.append(separator);
+            }
+            text.append_text(piece);
+        }
+        text
+    }
+
+    /// Truncate the text to at most `max_width` cells, marking the cut.
+    ///
+    /// Text that fits is left unchanged. The text is treated as a single
+    /// line. If the marker alone is wider than `max_width`, the text is
+    /// cropped without it. Components use [`RenderOptions::ellipsis`]
+    /// unless configured otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{EllipsisConfig, OverflowPosition, Text};
+    ///
+    /// let mut text = Text::new("Hello, world!");
+    /// text.truncate(8, &EllipsisConfig::default());
+    /// assert_eq!(text.plain(), "Hello, …");
+    ///
+    /// let mut text = Text::new("Hello, world!");
+    /// let middle = EllipsisConfig::new("..").with_position(OverflowPosition::Middle);
+    /// text.truncate(8, &middle);
+    /// assert_eq!(text.plain(), "Hel..ld!");
+    /// ```
+    pub fn truncate(&mut self, max_width: usize, ellipsis: &EllipsisConfig) {
+        if self.width() <= max_width {
+            return;
+        }
+
+        let widths: Vec<usize> = self
+            .content
+            .chars()
+            .map(|c| c.width().unwrap_or(0))
+            .collect();
+        let len = widths.len();
+        // Count the characters from the front of `widths` that fit `budget`
+        let fitting = |widths: &mut dyn Iterator<Item = &usize>, budget: usize| {
+            let mut used = 0;
+            widths
+                .take_while(|&&width| {
+                    used += width;
+                    used <= budget
+                })
+                .count()
+        };
+
+        let marker_width = ellipsis.marker.width();
+        if marker_width > max_width {
+            let head = fitting(&mut widths.iter(), max_width);
+            self.replace_range(head, len, &Text::new(""));
+            return;
+        }
+        let budget = max_width - marker_width;
+        let (head, tail) = match ellipsis.position {
+            OverflowPosition::End => (fitting(&mut widths.iter(), budget), len),
+            OverflowPosition::Start => (0, len - fitting(&mut widths.iter().rev(), budget)),
+            OverflowPosition::Middle => (
+                fitting(&mut widths.iter(), budget - budget / 2),
+                len - fitting(&mut widths.iter().rev(), budget / 2),
+            ),
+        };
+
+        let marker_style = ellipsis.marker_style(self.style_at(head));
+        let marker = Text::new(&ellipsis.marker).with_style(marker_style);
+        self.replace_range(head, tail, &marker);
+    }
+
+    /// Split the text at newlines, keeping styles. The newlines are dropped,
+    /// so a trailing newline gives a last line that is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Text;
+    ///
+    /// let text = Text::from_markup("one\n[bold]two[/bold]\n").unwrap();
+    /// let lines = text.split_lines();
+    /// assert_eq!(lines.iter().map(Text::plain).collect::<Vec<_>>(), ["one", "two", ""]);
+    /// assert_eq!(lines[1].style_at(0).bold, Some(true));
+    /// ```
+    pub fn split_lines(&self) -> Vec<Text> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for line in self.content.split('\n') {
+            let end = start + line.chars().count();
+            lines.push(self.slice_with(start, end, line.to_string()));
+            start = end + 1;
+        }
+        lines
+    }
 
-        // Adjust and add spans from the other text
-        for span in other.spans {
-            let adjusted_span =
-                Span::new(span.start + offset, span.end + offset, span.style.clone());
-            self.spans.push(adjusted_span);
+    /// Wrap the text into lines of at most `options.width` cells, keeping
+    /// styles, justification and background settings.
+    ///
+    /// See [`wrap`](crate::wrap::wrap) for where lines break.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Text, wrap::WrapOptions};
+    ///
+    /// let text = Text::from_markup("[bold]wrap[/bold] these words").unwrap();
+    /// let lines = text.wrap(&WrapOptions::new(10));
+    /// assert_eq!(lines[0].plain(), "wrap these");
+    /// assert_eq!(lines[0].style_at(0).bold, Some(true));
+    /// assert_eq!(lines[1].plain(), "words");
+    /// ```
+    pub fn wrap(&self, options: &WrapOptions) -> Vec<Text> {
+        // Lines come in order, so count characters from the previous line
+        // rather than from the start of the text
+        let (mut byte, mut start) = (0, 0);
+        crate::wrap::wrap(&self.content, options)
+            .into_iter()
+            .map(|range| {
+                start += self.content[byte..range.start].chars().count();
+                let line = &self.content[range.clone()];
+                let end = start + line.chars().count();
+                byte = range.start;
+                self.slice_with(start, end, line.to_string())
+            })
+            .collect()
+    }
+
+    /// Get this text sanitized under its own policy, or `fallback` if it has
+    /// none, with spans moved to match.
+    fn sanitized(&self, fallback: Policy) -> Cow<'_, Text> {
+        let policy = self.sanitize.unwrap_or(fallback);
+        let Some(sanitized) = sanitize::apply(&self.content, policy) else {
+            return Cow::Borrowed(self);
+        };
+        let positions = &sanitized.positions;
+        let spans = self
+            .spans
+            .iter()
+            .map(|span| {
+                let start = positions[span.start.min(positions.len() - 1)];
+                let end = positions[span.end.min(positions.len() - 1)];
+                Span::new(start, end, span.style.clone())
+            })
+            .filter(|span| span.start < span.end)
+            .collect();
+        Cow::Owned(Text {
+            content: sanitized.content,
+            spans,
+            // Already done; the result has nothing left to sanitize
+            sanitize: Some(Policy::Off),
+            ..self.clone()
+        })
+    }
+
+    /// Get the characters in `start..end` as text with the same styles.
+    fn slice(&self, start: usize, end: usize) -> Text {
+        self.slice_with(start, end, self.get_char_slice(start, end))
+    }
+
+    /// Get `content`, the characters in `start..end`, as text with the same
+    /// styles.
+    fn slice_with(&self, start: usize, end: usize, content: String) -> Text {
+        let spans = self
+            .spans
+            .iter()
+            .filter(|span| span.start < end && span.end > start)
+            .map(|span| {
+                Span::new(
+                    span.start.max(start) - start,
+                    span.end.min(end) - start,
+                    span.style.clone(),
+                )
+            })
+            .collect();
+        Text {
+            content,
+            base_style: self.base_style.clone(),
+            spans,
+            justify: self.justify,
+            full_width_background: self.full_width_background,
+            sanitize: self.sanitize,
         }
+    }
 
-        // Re-sort spans
-        self.spans.sort_by_key(|s| s.start);
+    /// Replace the characters in `start..end` with `replacement`, keeping
+    /// the styles of the text around it.
+    fn replace_range(&mut self, start: usize, end: usize, replacement: &Text) {
+        let removed = end - start;
+        let inserted = replacement.len();
+        let mut spans = Vec::with_capacity(self.spans.len() + replacement.spans.len() + 1);
+        for span in self.spans.drain(..) {
+            let head = span.start.min(start)..span.end.min(start);
+            let tail = span.start.max(end)..span.end.max(end);
+            if !head.is_empty() {
+                spans.push(Span::new(head.start, head.end, span.style.clone()));
+            }
+            if !tail.is_empty() {
+                spans.push(Span::new(
+                    tail.start - removed + inserted,
+                    tail.end - removed + inserted,
+                    span.style,
+                ));
+            }
+        }
+        spans.extend(replacement.clone().into_spans_at(start));
+        self.spans = spans;
+
+        self.content = format!(
+            "{}{}{}",
+            self.get_char_slice(0, start),
+            replacement.content,
+            self.get_char_slice(end, self.len())
+        );
+    }
+
+    /// Convert this text's base style and spans into spans starting at `offset`.
+    fn into_spans_at(self, offset: usize) -> Vec<Span> {
+        let mut spans = Vec::with_capacity(self.spans.len() + 1);
+        let len = self.len();
+        if len > 0 && !self.base_style.is_empty() {
+            spans.push(Span::new(offset, offset + len, self.base_style));
+        }
+        spans.extend(
+            self.spans
+                .into_iter()
+                .map(|span| Span::new(span.start + offset, span.end + offset, span.style)),
+        );
+        spans
     }
 
     /// Create text from markup string.
@@ -235,16 +961,36 @@ impl Text {
     /// ```rust
     /// use luxor::{Text, Style, Color};
     ///
-    /// let base_style = Style::new().color(Color::rgb(0, 0, 255));
+    /// let base_style = Style::new().with_color(Color::rgb(0, 0, 255));
     /// let text = Text::from_markup_with_style("[bold]Hello[/bold]", base_style).unwrap();
     /// ```
     pub fn from_markup_with_style(markup: &str, base_style: Style) -> Result<Self> {
         crate::markup::render(markup, Some(base_style))
     }
 
+    /// Create text from markup, recovering from malformed tags.
+    ///
+    /// Never fails; problems are returned as warnings alongside the text.
+    /// See [`crate::markup::render_lossy`] for the recovery rules.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::Text;
+    ///
+    /// let (text, warnings) = Text::from_markup_lossy("[bold]Hello[/italic]");
+    /// assert_eq!(text.plain(), "Hello");
+    /// assert!(!warnings.is_empty());
+    /// ```
+    pub fn from_markup_lossy(markup: &str) -> crate::markup::LossyText {
+        crate::markup::render_lossy(markup, None)
+    }
+
     /// Get the style at a specific character position.
     ///
-    /// This combines the base style with any applicable span styles.
+    /// This combines the base style with the styles of the spans covering
+    /// the position, in application order (see
+    /// [the overlap rule](Text#overlapping-spans)).
     pub fn style_at(&self, position: usize) -> Style {
         let mut combined_style = self.base_style.clone();
 
@@ -272,47 +1018,72 @@ impl Text {
             return vec![Segment::new(self.content.clone(), self.base_style.clone())];
         }
 
-        let mut segments = Vec::new();
-        let mut events = Vec::new();
+        let mut events = Vec::with_capacity(self.spans.len() * 2);
 
-        // Create events for span starts and ends
-        for span in &self.spans {
-            events.push((span.start, true, span)); // Start event
-            events.push((span.end, false, span)); // End event
+        // Create events for span starts and ends, identifying each span by
+        // its application order
+        for (order, span) in self.spans.iter().enumerate() {
+            if span.is_empty() {
+                continue;
+            }
+            events.push((span.start, true, order)); // Start event
+            events.push((span.end, false, order)); // End event
         }
 
-        // Sort events by position, with end events before start events at the same position
-        events.sort_by_key(|(pos, is_start, _)| (*pos, *is_start));
-
-        let mut current_position = 0;
-        let mut active_spans: Vec<&Span> = Vec::new();
-
-        for (position, is_start, span) in events {
+        // Sort events by position, with end events before start events at
+        // the same position, then by application order
+        events.sort_unstable();
+
+        let mut segments = Vec::with_capacity(events.len() + 1);
+        // Character and byte offsets of the end of the last segment; events
+        // are sorted, so the content is walked once
+        let mut chars = self.content.char_indices();
+        let (mut current_position, mut current_byte) = (0, 0);
+        // Active spans in application order. `styles[i]` is the base style
+        // combined with `active[..=i]`, valid for the first `valid` entries;
+        // the rest are recomputed only when a segment needs them
+        let mut active: Vec<usize> = Vec::new();
+        let mut styles: Vec<Style> = Vec::new();
+        let mut valid = 0;
+
+        for (position, is_start, order) in events {
             // Create segment for text before this event
             if position > current_position {
-                let text_slice = self.get_char_slice(current_position, position);
-                let style = self.compute_style_for_spans(&active_spans);
-                if !text_slice.is_empty() {
-                    segments.push(Segment::new(text_slice, style));
+                let end_byte = chars
+                    .nth(position - current_position - 1)
+                    .and_then(|_| chars.clone().next())
+                    .map_or(self.content.len(), |(index, _)| index);
+                if end_byte > current_byte {
+                    self.extend_styles(&active, &mut styles, valid);
+                    valid = active.len();
+                    let style = styles.last().unwrap_or(&self.base_style);
+                    segments.push(Segment::new(
+                        &self.content[current_byte..end_byte],
+                        style.clone(),
+                    ));
                 }
-                current_position = position;
+                (current_position, current_byte) = (position, end_byte);
             }
 
             // Update active spans
-            if is_start {
-                active_spans.push(span);
-            } else {
-                active_spans.retain(|s| s != &span);
+            match (active.binary_search(&order), is_start) {
+                (Err(index), true) => {
+                    active.insert(index, order);
+                    valid = valid.min(index);
+                }
+                (Ok(index), false) => {
+                    active.remove(index);
+                    valid = valid.min(index);
+                }
+                _ => {}
             }
         }
 
         // Handle remaining text after last event
-        if current_position < self.len() {
-            let text_slice = self.get_char_slice(current_position, self.len());
-            let style = self.compute_style_for_spans(&active_spans);
-            if !text_slice.is_empty() {
-                segments.push(Segment::new(text_slice, style));
-            }
+        if current_byte < self.content.len() {
+            self.extend_styles(&active, &mut styles, valid);
+            let style = styles.pop().unwrap_or_else(|| self.base_style.clone());
+            segments.push(Segment::new(&self.content[current_byte..], style));
         }
 
         segments
@@ -323,26 +1094,305 @@ impl Text {
         self.content.chars().skip(start).take(end - start).collect()
     }
 
-    /// Compute the combined style for a set of active spans.
-    fn compute_style_for_spans(&self, spans: &[&Span]) -> Style {
-        let mut style = self.base_style.clone();
-        for span in spans {
-            style = style.combine(span.style.clone());
+    /// Render the segments line by line, folding lines longer than `width`
+    /// and padding each line to it unless left-justified without a
+    /// full-width background.
+    fn justified_segments(&self, width: usize, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let pad_style = if self.full_width_background {
+            self.base_style.clone()
+        } else {
+            Style::default()
+        };
+        let pad =
+            matches!(self.justify, Justify::Center | Justify::Right) || self.full_width_background;
+        let lines = self.wrapped_lines(width);
+        let line_count = lines.len();
+        let mut output = Vec::new();
+
+        for (index, line) in lines.into_iter().enumerate() {
+            if index % CANCEL_CHECK_INTERVAL == 0 {
+                options.check_cancelled()?;
+            }
+            let is_last = index + 1 == line_count;
+            if index > 0 {
+                output.push(Segment::line());
+            }
+            // A trailing newline leaves an empty final line that should stay empty
+            if is_last && line_count > 1 && line.iter().all(|s| s.text().is_empty()) {
+                break;
+            }
+
+            let excess = width.saturating_sub(render_utils::line_width(&line));
+            let (left, right) = match self.justify {
+                _ if !pad => (0, 0),
+                Justify::Left | Justify::Full | Justify::FullAll => (0, excess),
+                Justify::Center => (excess / 2, excess - excess / 2),
+                Justify::Right => (excess, 0),
+            };
+            output.extend(render_utils::fill(left, pad_style.clone()));
+            output.extend(line);
+            output.extend(render_utils::fill(right, pad_style.clone()));
+        }
+
+        Ok(output)
+    }
+
+    /// Word-wrap each paragraph at whitespace, as [`Text::measure`]
+    /// assumes, and stretch its lines to `width` as [`Justify::Full`] and
+    /// [`Justify::FullAll`] describe.
+    fn wrapped_lines(&self, width: usize) -> Vec<Vec<Segment>> {
+        let options = WrapOptions::new(width).with_strategy(WhitespaceBreaks);
+        let stretch = matches!(self.justify, Justify::Full | Justify::FullAll);
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for paragraph in self.content.split('\n') {
+            let len = paragraph.chars().count();
+            let wrapped = self.slice(start, start + len).wrap(&options);
+            let count = wrapped.len();
+            for (index, mut line) in wrapped.into_iter().enumerate() {
+                if stretch && (index + 1 < count || self.justify == Justify::FullAll) {
+                    line.stretch_to(width);
+                }
+                lines.push(line.to_segments());
+            }
+            start += len + 1;
+        }
+        lines
+    }
+
+    /// Widen the gaps between words so the text is `width` cells wide.
+    fn stretch_to(&mut self, width: usize) {
+        let chars: Vec<char> = self.content.chars().collect();
+        let gaps: Vec<usize> = (1..chars.len())
+            .filter(|&index| chars[index] == ' ' && chars[index - 1] != ' ')
+            .collect();
+        let extra = width.saturating_sub(self.width());
+        if gaps.is_empty() || extra == 0 {
+            return;
+        }
+
+        let (each, remainder) = (extra / gaps.len(), extra % gaps.len());
+        // Insert from the right so earlier positions stay valid
+        for (index, &position) in gaps.iter().enumerate().rev() {
+            let count = each + usize::from(index < remainder);
+            if count == 0 {
+                continue;
+            }
+            let spaces = Text::new(&" ".repeat(count)).with_style(self.style_at(position - 1));
+            self.insert_text(position, spaces)
+                .expect("gap positions are within the text");
+        }
+    }
+
+    /// Bring `styles` up to date with `active`, keeping the first `valid`
+    /// styles. See [`Text::to_segments`].
+    fn extend_styles(&self, active: &[usize], styles: &mut Vec<Style>, valid: usize) {
+        styles.truncate(valid);
+        for &order in &active[valid..] {
+            let below = styles.last().unwrap_or(&self.base_style).clone();
+            styles.push(below.combine(self.spans[order].style.clone()));
+        }
+    }
+}
+
+/// Snippets of text around search matches, for grep-like output.
+///
+/// [`extract`](MatchContext::extract) cuts the line holding a match down to
+/// a fixed width, centered on the match where the line allows, and marks
+/// each cut side with the ellipsis marker. The match is styled on top of
+/// the text's own styles, which are kept.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{MatchContext, Text};
+///
+/// let text = Text::new("warning: unused variable `count` in main.rs");
+/// let context = MatchContext::new();
+/// let ranges = text.find_all("count", false);
+/// let snippet = context.extract(&text, ranges[0].clone(), 21);
+/// assert_eq!(snippet.plain(), "…iable `count` in ma…");
+/// assert_eq!(snippet.style_at(8).bold, Some(true));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchContext {
+    /// Style of the match, combined over its own styles.
+    pub style: Style,
+    /// Marker for cut text. Its position is ignored: each side that is
+    /// cut gets a marker.
+    pub ellipsis: EllipsisConfig,
+}
+
+impl MatchContext {
+    /// Create a context with bold, underlined matches and the default
+    /// ellipsis.
+    pub fn new() -> Self {
+        Self {
+            style: Style::new().bold().underline(),
+            ellipsis: EllipsisConfig::default(),
+        }
+    }
+
+    /// Set the style of the match.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the marker for cut text.
+    pub fn with_ellipsis(mut self, ellipsis: EllipsisConfig) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Get the match at `range`, in characters, with up to `context_cells`
+    /// cells of the line around it.
+    ///
+    /// The snippet stays within the line holding the start of the match;
+    /// a match running past the end of that line is cut there. Context is
+    /// split evenly between the sides, with any the line cannot fill on one
+    /// side given to the other. A match too wide for `context_cells` is
+    /// kept from its start and cut at the end.
+    pub fn extract(&self, text: &Text, range: Range<usize>, context_cells: usize) -> Text {
+        let chars: Vec<char> = text.content.chars().collect();
+        let start = range.start.min(chars.len());
+        let line_start = chars[..start]
+            .iter()
+            .rposition(|&ch| ch == '\n')
+            .map_or(0, |index| index + 1);
+        let line_end = chars[start..]
+            .iter()
+            .position(|&ch| ch == '\n')
+            .map_or(chars.len(), |index| start + index);
+        let end = range.end.clamp(start, line_end);
+
+        let mut line = text.slice(line_start, line_end);
+        let (start, end) = (start - line_start, end - line_start);
+        if start < end {
+            line.spans.push(Span::new(start, end, self.style.clone()));
+        }
+        if line.width() <= context_cells {
+            return line;
+        }
+
+        let widths: Vec<usize> = chars[line_start..line_end]
+            .iter()
+            .map(|ch| ch.width().unwrap_or(0))
+            .collect();
+        let matched: usize = widths[start..end].iter().sum();
+        let before: usize = widths[..start].iter().sum();
+        let after: usize = widths[end..].iter().sum();
+        let marker_width = self.ellipsis.marker.width();
+
+        // Reserve a marker for each side that turns out to be cut; cutting
+        // only ever shrinks the room, so this settles within two passes
+        let (mut cut_before, mut cut_after) = (false, false);
+        let (left, right) = loop {
+            let markers = marker_width * (usize::from(cut_before) + usize::from(cut_after));
+            let room = context_cells.saturating_sub(matched + markers);
+            let half = room / 2;
+            let (left, right) = if before <= room - half {
+                (before, after.min(room - before))
+            } else if after <= half {
+                (before.min(room - after), after)
+            } else {
+                (room - half, half)
+            };
+            let cuts = (left < before, right < after);
+            if cuts == (cut_before, cut_after) {
+                break (left, right);
+            }
+            (cut_before, cut_after) = cuts;
+        };
+
+        let markers = marker_width * (usize::from(cut_before) + usize::from(cut_after));
+        if matched + markers > context_cells {
+            let mut snippet = line.slice(start, line.len());
+            let ellipsis = self.ellipsis.clone().with_position(OverflowPosition::End);
+            snippet.truncate(context_cells, &ellipsis);
+            return snippet;
+        }
+
+        // Take whole characters out to the cell budget on each side
+        let fitting = |widths: &mut dyn Iterator<Item = &usize>, budget: usize| {
+            let mut used = 0;
+            widths
+                .take_while(|&&width| {
+                    used += width;
+                    used <= budget
+                })
+                .count()
+        };
+        let first = start - fitting(&mut widths[..start].iter().rev(), left);
+        let last = end + fitting(&mut widths[end..].iter(), right);
+
+        let marker = |position: usize| {
+            let style = self.ellipsis.marker_style(line.style_at(position));
+            Text::new(&self.ellipsis.marker).with_style(style)
+        };
+        let mut snippet = line.slice(first, last);
+        if cut_before {
+            snippet
+                .insert_text(0, marker(first))
+                .expect("the start is within the text");
+        }
+        if cut_after {
+            snippet.append_text(marker(last));
         }
-        style
+        snippet
+    }
+}
+
+impl Default for MatchContext {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl Renderable for Text {
-    fn render(&self, _console: &Console, _options: &ConsoleOptions) -> Result<Vec<Segment>> {
-        Ok(self.to_segments())
+    fn render(&self, _console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        self.sanitized(options.sanitize).render_sanitized(options)
+    }
+}
+
+impl Text {
+    /// Render text that has been sanitized already.
+    fn render_sanitized(&self, options: &RenderOptions) -> Result<Vec<Segment>> {
+        // Soft wrapping leaves layout to the display, so skip width padding
+        let policy = options.bidi_policy.unwrap_or_default();
+        if options.soft_wrap {
+            return Ok(render_utils::apply_bidi_policy(self.to_segments(), policy));
+        }
+        options.check_cancelled()?;
+        let width = options.get_max_width();
+        let fits = self.content.split('\n').all(|line| line.width() <= width);
+        let segments = if fits && self.justify == Justify::Left && !self.full_width_background {
+            self.to_segments()
+        } else {
+            self.justified_segments(width, options)?
+        };
+        Ok(render_utils::apply_bidi_policy(segments, policy))
     }
 }
 
 impl Measurable for Text {
-    fn measure(&self, _console: &Console, _options: &ConsoleOptions) -> Result<Measurement> {
-        let width = self.width();
-        Ok(Measurement::fixed(width))
+    /// Measure the widest line as the maximum and the longest word, split
+    /// at whitespace as in Rich, as the minimum. See
+    /// [`wrap::measure`](crate::wrap::measure).
+    ///
+    /// Centered and right-justified text, and text with a full-width
+    /// background, is padded to the whole width, so it measures as taking
+    /// all of it.
+    fn measure(&self, _console: &Console, options: &RenderOptions) -> Result<Measurement> {
+        let text = self.sanitized(options.sanitize);
+        let measurement = crate::wrap::measure(text.plain(), &crate::wrap::WhitespaceBreaks);
+        let fills =
+            matches!(self.justify, Justify::Center | Justify::Right) || self.full_width_background;
+        if !fills {
+            return Ok(measurement);
+        }
+        let width = options.get_max_width();
+        Ok(Measurement::new(measurement.minimum().min(width), width))
     }
 }
 
@@ -367,7 +1417,7 @@ impl From<String> for Text {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Style, color::Color};
+    use crate::{Style, color::Color, testing::render_lines};
 
     #[test]
     fn test_text_creation() {
@@ -388,7 +1438,7 @@ mod tests {
     #[test]
     fn test_stylize_range() {
         let mut text = Text::new("Hello world");
-        let style = Style::new().color(Color::rgb(255, 0, 0));
+        let style = Style::new().with_color(Color::rgb(255, 0, 0));
 
         text.stylize_range(0..5, style.clone()).unwrap();
         assert_eq!(text.spans().len(), 1);
@@ -439,7 +1489,7 @@ mod tests {
         let mut text1 = Text::new("Hello ");
         let mut text2 = Text::new("world");
         text2
-            .stylize_all(Style::new().color(Color::rgb(255, 0, 0)))
+            .stylize_all(Style::new().with_color(Color::rgb(255, 0, 0)))
             .unwrap();
 
         text1.append_text(text2);
@@ -452,10 +1502,44 @@ mod tests {
         assert_eq!(span.style.color, Some(Color::rgb(255, 0, 0)));
     }
 
+    #[test]
+    fn test_append_text_keeps_base_style() {
+        let blue = Style::new().with_color(Color::rgb(0, 0, 255));
+        let mut text = Text::new("a").with_style(Style::new().bold());
+        text.append_text(
+            Text::from_markup("b[italic]c[/italic]")
+                .unwrap()
+                .with_style(blue),
+        );
+
+        assert_eq!(text.style_at(1).color, Some(Color::rgb(0, 0, 255)));
+        assert_eq!(text.style_at(1).bold, Some(true));
+        assert_eq!(text.style_at(2).italic, Some(true));
+        assert_eq!(text.style_at(2).color, Some(Color::rgb(0, 0, 255)));
+    }
+
+    #[test]
+    fn test_insert_text() {
+        let mut text = Text::from_markup("[red]ab[/red]cd").unwrap();
+        text.insert_text(1, Text::from_markup("[bold]X[/bold]").unwrap())
+            .unwrap();
+        text.insert_text(5, Text::new("!")).unwrap();
+        assert_eq!(text.plain(), "aXbcd!");
+
+        let red = Some(Color::Standard(crate::StandardColor::Red));
+        assert_eq!(text.style_at(0).color, red);
+        assert_eq!(text.style_at(1).color, None);
+        assert_eq!(text.style_at(1).bold, Some(true));
+        assert_eq!(text.style_at(2).color, red);
+        assert_eq!(text.style_at(3).color, None);
+
+        assert!(text.insert_text(7, Text::new("?")).is_err());
+    }
+
     #[test]
     fn test_style_at() {
         let mut text = Text::new("Hello world");
-        let base_style = Style::new().color(Color::rgb(0, 0, 255));
+        let base_style = Style::new().with_color(Color::rgb(0, 0, 255));
         text.set_style(base_style.clone());
 
         // Add a bold span for "Hello"
@@ -484,7 +1568,7 @@ mod tests {
     fn test_to_segments_with_spans() {
         let mut text = Text::new("Hello world");
         text.stylize_range(0..5, Style::new().bold()).unwrap();
-        text.stylize_range(6..11, Style::new().color(Color::rgb(255, 0, 0)))
+        text.stylize_range(6..11, Style::new().with_color(Color::rgb(255, 0, 0)))
             .unwrap();
 
         let segments = text.to_segments();
@@ -503,7 +1587,7 @@ mod tests {
     fn test_overlapping_spans() {
         let mut text = Text::new("Hello world");
         text.stylize_range(0..8, Style::new().bold()).unwrap(); // "Hello wo"
-        text.stylize_range(6..11, Style::new().color(Color::rgb(255, 0, 0)))
+        text.stylize_range(6..11, Style::new().with_color(Color::rgb(255, 0, 0)))
             .unwrap(); // "world"
 
         let segments = text.to_segments();
@@ -530,6 +1614,119 @@ mod tests {
         assert_eq!(span.style.bold, Some(true));
     }
 
+    #[test]
+    fn test_full_width_background_centered_banner() {
+        let red = Style::new().with_background(Color::rgb(255, 0, 0));
+        let banner = Text::new("DEPLOY")
+            .with_style(red.clone())
+            .with_justify(Justify::Center)
+            .with_full_width_background(true);
+        let options = RenderOptions::new().with_max_width(20);
+        let segments = banner.render(&Console::new(), &options).unwrap();
+
+        crate::assert_render_eq!(render_lines(&segments), "       DEPLOY       ");
+        let width: usize = segments.iter().map(|s| s.cell_length()).sum();
+        assert_eq!(width, 20);
+        assert!(
+            segments
+                .iter()
+                .all(|s| s.style().background == Some(Color::rgb(255, 0, 0)))
+        );
+    }
+
+    #[test]
+    fn test_full_width_background_multiple_lines() {
+        let style = Style::new().with_background(Color::rgb(0, 0, 255));
+        let text = Text::new("ab\ncdef\n")
+            .with_style(style)
+            .with_full_width_background(true);
+        let options = RenderOptions::new().with_max_width(6);
+        let segments = text.render(&Console::new(), &options).unwrap();
+
+        crate::assert_render_eq!(render_lines(&segments), "ab    \ncdef  \n");
+    }
+
+    #[test]
+    fn test_justify_right_pads_unstyled() {
+        let text = Text::new("abc")
+            .with_style(Style::new().bold())
+            .with_justify(Justify::Right);
+        let options = RenderOptions::new().with_max_width(5);
+        let segments = text.render(&Console::new(), &options).unwrap();
+
+        assert_eq!(segments[0].text(), "  ");
+        assert!(segments[0].style().is_empty());
+        assert_eq!(segments[1].text(), "abc");
+    }
+
+    #[test]
+    fn test_soft_wrap_skips_padding() {
+        let text = Text::new("abc")
+            .with_justify(Justify::Center)
+            .with_full_width_background(true);
+        let options = RenderOptions::new().with_max_width(9).with_soft_wrap(true);
+        let segments = text.render(&Console::new(), &options).unwrap();
+        crate::assert_render_eq!(render_lines(&segments), "abc");
+    }
+
+    #[test]
+    fn test_full_width_background_measures_full_width() {
+        let text = Text::new("DEPLOY").with_full_width_background(true);
+        let options = RenderOptions::new().with_max_width(20);
+        let measurement = text.measure(&Console::new(), &options).unwrap();
+        assert_eq!(measurement, Measurement::new(6, 20));
+        let text = Text::new("DEPLOY").with_justify(Justify::Center);
+        let measurement = text.measure(&Console::new(), &options).unwrap();
+        assert_eq!(measurement, Measurement::new(6, 20));
+    }
+
+    #[test]
+    fn test_measure_multi_line_text() {
+        let console = Console::new();
+        let options = RenderOptions::new().with_max_width(80);
+        let measure = |content: &str| Text::new(content).measure(&console, &options).unwrap();
+        // The longest line, not every line end to end
+        assert_eq!(measure("one\nthree words\ntwo"), Measurement::new(5, 11));
+        assert_eq!(measure("hello world\n"), Measurement::new(5, 11));
+        assert_eq!(measure("\n"), Measurement::new(0, 0));
+        assert_eq!(
+            measure("antidisestablishmentarianism"),
+            Measurement::fixed(28)
+        );
+    }
+
+    #[test]
+    fn test_measured_minimum_keeps_words() {
+        let console = Console::new();
+        for content in ["hello world", "the quick brown fox", "a bb ccc\ndddd e"] {
+            let words: Vec<&str> = content.split_whitespace().collect();
+            for justify in [
+                Justify::Left,
+                Justify::Center,
+                Justify::Right,
+                Justify::Full,
+                Justify::FullAll,
+            ] {
+                let text = Text::new(content).with_justify(justify);
+                let options = RenderOptions::new().with_max_width(40);
+                let minimum = text.measure(&console, &options).unwrap().minimum();
+                let options = RenderOptions::new().with_max_width(minimum);
+                let lines = render_lines(&text.render(&console, &options).unwrap());
+                for word in lines.iter().flat_map(|line| line.split_whitespace()) {
+                    assert!(
+                        words.contains(&word),
+                        "{justify:?} split {content:?}: {lines:?}"
+                    );
+                }
+            }
+        }
+
+        let text = Text::new("the quick brown fox").with_justify(Justify::Center);
+        let options = RenderOptions::new().with_max_width(12);
+        let segments = text.render(&console, &options).unwrap();
+        crate::assert_render_eq!(render_lines(&segments), " the quick  \n brown fox  ");
+    }
+
     #[test]
     fn test_display_trait() {
         let text = Text::new("Hello world");
@@ -547,4 +1744,265 @@ mod tests {
         let text: Text = String::from("Hello world").into();
         assert_eq!(text.plain(), "Hello world");
     }
+
+    #[test]
+    fn test_contains_rtl() {
+        assert!(contains_rtl("שלום"));
+        assert!(contains_rtl("سلام"));
+        assert!(!contains_rtl("hello 漢字"));
+        assert!(!contains_rtl(""));
+    }
+
+    #[test]
+    fn test_isolate_rtl_runs() {
+        // Neutrals inside a run are included, trailing ones are not
+        assert_eq!(
+            isolate_rtl("id 42: שלום 7 עולם. ok"),
+            "id 42: \u{2068}שלום 7 עולם\u{2069}. ok"
+        );
+        // Runs end at LTR letters and newlines
+        assert_eq!(
+            isolate_rtl("א b ב\nג"),
+            "\u{2068}א\u{2069} b \u{2068}ב\u{2069}\n\u{2068}ג\u{2069}"
+        );
+        assert_eq!(isolate_rtl("שלום").width(), "שלום".width());
+        // Isolating again changes nothing
+        let once = isolate_rtl("id 42: שלום 7 עולם. ok");
+        assert_eq!(isolate_rtl(&once), once);
+    }
+
+    #[test]
+    fn test_render_bidi_policy() {
+        let console = Console::new();
+        let text = Text::new("שלום").with_justify(Justify::Right);
+        let options = RenderOptions::new().with_max_width(6);
+        let plain = |options: &RenderOptions| -> String {
+            text.render(&console, options)
+                .unwrap()
+                .iter()
+                .map(|s| s.text())
+                .collect()
+        };
+        assert_eq!(plain(&options), "  שלום");
+        let isolated = options.with_bidi_policy(BidiPolicy::Isolate);
+        assert_eq!(plain(&isolated), "  \u{2068}שלום\u{2069}");
+    }
+
+    #[test]
+    fn test_truncate_ellipsis_keeps_background() {
+        let cell = Style::parse("white on blue").unwrap();
+        let mut text = Text::new("statusreport").with_style(cell.clone());
+        text.truncate(8, &EllipsisConfig::default());
+        assert_eq!(text.plain(), "statusr…");
+
+        let segments = text.to_segments();
+        let marker = segments.last().unwrap();
+        assert_eq!(marker.text(), "…");
+        assert_eq!(marker.style(), &cell.clone().dim());
+        assert_eq!(segments[0].style(), &cell);
+    }
+
+    #[test]
+    fn test_truncate_ignores_marker_background() {
+        let ellipsis = EllipsisConfig::new("~").with_style(Style::parse("red on black").unwrap());
+        let mut text = Text::new("abcdef").with_style(Style::parse("on blue").unwrap());
+        text.truncate(3, &ellipsis);
+        assert_eq!(text.style_at(2), Style::parse("red on blue").unwrap());
+    }
+
+    #[test]
+    fn test_truncate_positions() {
+        let truncated = |position: OverflowPosition, width: usize| {
+            let mut text = Text::from_markup("[bold]abc[/bold]defghij").unwrap();
+            text.truncate(width, &EllipsisConfig::default().with_position(position));
+            text
+        };
+
+        let end = truncated(OverflowPosition::End, 5);
+        assert_eq!(end.plain(), "abcd…");
+        assert_eq!(end.style_at(2).bold, Some(true));
+
+        // The tail survives, restyled spans move with it
+        let start = truncated(OverflowPosition::Start, 5);
+        assert_eq!(start.plain(), "…ghij");
+        assert_eq!(start.style_at(1).bold, None);
+
+        let middle = truncated(OverflowPosition::Middle, 6);
+        assert_eq!(middle.plain(), "abc…ij");
+        assert_eq!(middle.style_at(2).bold, Some(true));
+        assert_eq!(middle.style_at(4).bold, None);
+    }
+
+    #[test]
+    fn test_truncate_tight_budgets() {
+        let mut text = Text::new("abcdef");
+        text.truncate(1, &EllipsisConfig::default());
+        assert_eq!(text.plain(), "…");
+
+        let mut text = Text::new("漢字漢字");
+        text.truncate(4, &EllipsisConfig::default());
+        assert_eq!(text.plain(), "漢…");
+        assert!(text.width() <= 4);
+
+        let mut text = Text::new("abcdef");
+        text.truncate(2, &EllipsisConfig::new("..."));
+        assert_eq!(text.plain(), "ab");
+
+        let mut text = Text::new("fits");
+        text.truncate(4, &EllipsisConfig::default());
+        assert_eq!(text.plain(), "fits");
+    }
+
+    #[test]
+    fn test_match_context_snippets() {
+        let mut text =
+            Text::new("needle at the start, a needle in the middle, and the last needle");
+        text.stylize_range(23..25, Style::new().italic()).unwrap();
+        let context = MatchContext::new();
+        let matches = text.find_all("NEEDLE", true);
+        assert_eq!(matches, [0..6, 23..29, 58..64]);
+
+        let snippets: Vec<Text> = matches
+            .iter()
+            .map(|range| context.extract(&text, range.clone(), 16))
+            .collect();
+        let plain: Vec<&str> = snippets.iter().map(Text::plain).collect();
+        assert_eq!(
+            plain,
+            ["needle at the s…", "…, a needle in …", "…the last needle"]
+        );
+        assert!(snippets.iter().all(|snippet| snippet.width() == 16));
+
+        let middle = &snippets[1];
+        assert_eq!(middle.style_at(0).dim, Some(true));
+        assert_eq!(middle.style_at(5).bold, Some(true));
+        assert_eq!(middle.style_at(10).underline, Some(true));
+        assert_eq!(middle.style_at(5).italic, Some(true));
+        assert_eq!(middle.style_at(11).bold, None);
+        assert_eq!(middle.style_at(15).dim, Some(true));
+
+        // Context stays on the match's line, and wide matches are cut
+        let text = Text::new("first line\nsecond needle line\nthird");
+        let range = text.find_all("needle", false)[0].clone();
+        assert_eq!(
+            context.extract(&text, range.clone(), 40).plain(),
+            "second needle line"
+        );
+        assert_eq!(context.extract(&text, range, 4).plain(), "nee…");
+    }
+
+    #[test]
+    fn test_justify_full_styled_paragraph() {
+        let text = Text::from_markup(
+            "[bold]alpha[/bold] beta [red]gamma delta[/red] epsilon zeta eta theta",
+        )
+        .unwrap()
+        .with_justify(Justify::Full);
+        let options = RenderOptions::new().with_max_width(20);
+        let segments = text.render(&Console::new(), &options).unwrap();
+        let lines = render_utils::split_lines(segments);
+
+        let plain: Vec<String> = lines
+            .iter()
+            .map(|line| line.iter().map(|s| s.text()).collect())
+            .collect();
+        assert_eq!(
+            plain,
+            vec!["alpha   beta   gamma", "delta  epsilon  zeta", "eta theta"]
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        );
+        let widths: Vec<usize> = lines
+            .iter()
+            .map(|line| render_utils::line_width(line))
+            .collect();
+        assert_eq!(widths, vec![20, 20, 9]);
+
+        // Inserted spaces take the style of the word before them
+        let bold = Style::new().bold();
+        let red = Style::new().with_color(crate::Color::Standard(crate::StandardColor::Red));
+        assert_eq!(
+            lines[0],
+            vec![
+                Segment::new("alpha", bold.clone()),
+                Segment::new("  ", bold),
+                Segment::new(" beta   ", Style::new()),
+                Segment::new("gamma", red.clone()),
+            ]
+        );
+        assert_eq!(lines[1][0], Segment::new("delta", red));
+    }
+
+    #[test]
+    fn test_justify_full_all_and_hard_breaks() {
+        let render = |justify: Justify| -> Vec<String> {
+            let text = Text::new("one two three four\nfive six\nseven").with_justify(justify);
+            let options = RenderOptions::new().with_max_width(12);
+            let segments = text.render(&Console::new(), &options).unwrap();
+            let plain: String = segments.iter().map(|s| s.text()).collect();
+            plain.split('\n').map(String::from).collect()
+        };
+
+        // Last lines of paragraphs and single words are left alone
+        assert_eq!(
+            render(Justify::Full),
+            vec!["one      two", "three four", "five six", "seven"]
+        );
+        assert_eq!(
+            render(Justify::FullAll),
+            vec!["one      two", "three   four", "five     six", "seven"]
+        );
+    }
+
+    #[test]
+    fn test_later_span_wins_on_identical_range() {
+        let red = Style::new().with_color(Color::rgb(255, 0, 0));
+        let blue = Style::new().with_color(Color::rgb(0, 0, 255));
+
+        for (first, second) in [(&red, &blue), (&blue, &red)] {
+            let mut text = Text::new("abc");
+            text.stylize_range(0..3, first.clone().bold()).unwrap();
+            text.stylize_range(0..3, second.clone()).unwrap();
+
+            let expected = second.clone().bold();
+            assert_eq!(text.style_at(1), expected);
+            assert_eq!(text.to_segments(), vec![Segment::new("abc", expected)]);
+        }
+    }
+
+    #[test]
+    fn test_later_span_wins_on_partial_overlap() {
+        let red = Style::new().with_color(Color::rgb(255, 0, 0));
+        let blue = Style::new().with_color(Color::rgb(0, 0, 255));
+
+        // The later span starts first, so ordering by start alone would
+        // let the earlier span win in the overlap
+        let mut text = Text::new("abcdef");
+        text.stylize_range(2..6, blue.clone()).unwrap();
+        text.stylize_range(0..4, red.clone()).unwrap();
+        text.stylize_range(3..3, Style::new().italic()).unwrap();
+
+        let styles: Vec<Style> = (0..6).map(|index| text.style_at(index)).collect();
+        assert_eq!(styles[..4], vec![red.clone(); 4][..]);
+        assert_eq!(styles[4..], vec![blue.clone(); 2][..]);
+        assert_eq!(
+            text.to_segments(),
+            vec![
+                Segment::new("ab", red.clone()),
+                Segment::new("cd", red),
+                Segment::new("ef", blue)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_markup_on_same_range() {
+        let text = Text::from_markup("[red][blue]x[/blue][/red] [blue][red]y[/]z[/]").unwrap();
+        let blue = Style::parse("blue").unwrap();
+        let red = Style::parse("red").unwrap();
+        assert_eq!(text.style_at(0), blue);
+        assert_eq!(text.style_at(2), red);
+        assert_eq!(text.style_at(3), blue);
+    }
 }
@@ -0,0 +1,294 @@
+//! Themes - named styles for markup.
+//!
+//! A [`Theme`] maps names such as `warning` to styles, so markup can say
+//! what text is rather than how it looks: `[warning]disk full[/warning]`.
+//! Set one on a console with [`Console::with_theme`] or
+//! [`ConsoleOptions::with_theme`], and tags that are not style
+//! definitions are looked up in it when markup is parsed:
+//!
+//! ```rust
+//! use luxor::{ColorSystem, Console, ConsoleOptions, Theme};
+//!
+//! let options = ConsoleOptions::new()
+//!     .with_color_system(ColorSystem::Standard)
+//!     .with_theme(Theme::new());
+//! let console = Console::with_options(options);
+//! let output = console.capture(|console| console.print("[warning]disk full[/]")).unwrap();
+//! assert_eq!(output, "\x1b[33mdisk full\x1b[0m");
+//! ```
+//!
+//! [`Console::with_theme`]: crate::Console::with_theme
+//! [`ConsoleOptions::with_theme`]: crate::ConsoleOptions::with_theme
+
+use crate::{LuxorError, Result, Style};
+use std::{borrow::Cow, collections::BTreeMap};
+
+/// A style name and its definition.
+type Entry = (&'static str, &'static str);
+
+/// Names and style definitions of the default theme.
+const DEFAULT_STYLES: [Entry; 17] = [
+    ("info", "cyan"),
+    ("success", "green"),
+    ("warning", "yellow"),
+    ("danger", "bold red"),
+    ("error", "bold red"),
+    ("badge.success", "bold black on green"),
+    ("badge.warning", "bold black on yellow"),
+    ("badge.error", "bold bright_white on red"),
+    ("badge.info", "bold bright_white on blue"),
+    ("repr.number", "bold cyan"),
+    ("repr.str", "green"),
+    ("repr.bool_true", "italic bright_green"),
+    ("repr.bool_false", "italic bright_red"),
+    ("repr.none", "italic magenta"),
+    ("repr.url", "underline bright_blue"),
+    ("repr.uuid", "bright_yellow"),
+    ("repr.path", "magenta"),
+];
+
+/// Names and style definitions the default theme uses in accessible mode,
+/// so that status styles differ by more than their color.
+const DEFAULT_ACCESSIBLE_STYLES: [Entry; 3] = [
+    ("info", "bold cyan"),
+    ("success", "bold green"),
+    ("warning", "bold yellow"),
+];
+
+/// A set of named styles.
+///
+/// A theme can also override styles for accessible mode, see
+/// [`Theme::with_accessible_override`]. With the `serde` feature, themes
+/// serialize as a map of names to style strings, e.g.
+/// `{"warning": "yellow"}`; accessible overrides are not serialized.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Style, Theme};
+///
+/// let theme = Theme::new().with_style("warning", Style::parse("bold magenta").unwrap());
+/// assert_eq!(theme.resolve("warning").unwrap(), Style::parse("bold magenta").unwrap());
+/// assert_eq!(theme.resolve("bold red").unwrap(), Style::parse("bold red").unwrap());
+/// assert!(theme.resolve("warnign").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Theme {
+    styles: BTreeMap<String, Style>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    accessible: BTreeMap<String, Style>,
+}
+
+impl Theme {
+    /// Create the default theme, with `info`, `success`, `warning`,
+    /// `danger`, `error`, `badge.*` styles for
+    /// [`Badge`](crate::components::Badge) presets and `repr.*` styles for
+    /// values. In accessible
+    /// mode, `info`, `success` and `warning` are bold, as `danger` and
+    /// `error` always are.
+    pub fn new() -> Self {
+        Self {
+            styles: parse_entries(&DEFAULT_STYLES),
+            accessible: parse_entries(&DEFAULT_ACCESSIBLE_STYLES),
+        }
+    }
+
+    /// Create a theme with no styles.
+    pub fn empty() -> Self {
+        Self {
+            styles: BTreeMap::new(),
+            accessible: BTreeMap::new(),
+        }
+    }
+
+    /// Set the style called `name`, replacing any style of that name.
+    pub fn with_style(mut self, name: impl Into<String>, style: Style) -> Self {
+        self.insert(name, style);
+        self
+    }
+
+    /// Set the style called `name` in place, returning the style it
+    /// replaced.
+    pub fn insert(&mut self, name: impl Into<String>, style: Style) -> Option<Style> {
+        self.styles.insert(name.into(), style)
+    }
+
+    /// Set the style called `name` in accessible mode, used in place of
+    /// its usual style when [`RenderOptions::accessible`] is set.
+    ///
+    /// Use it to give a style that is told apart by color alone another
+    /// signal, such as bold or underline.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Style, Theme};
+    ///
+    /// let style = Style::parse("underline yellow").unwrap();
+    /// let theme = Theme::new().with_accessible_override("warning", style.clone());
+    /// assert_eq!(theme.for_accessible().get("warning"), Some(&style));
+    /// assert_ne!(theme.get("warning"), Some(&style));
+    /// ```
+    ///
+    /// [`RenderOptions::accessible`]: crate::RenderOptions::accessible
+    pub fn with_accessible_override(mut self, name: impl Into<String>, style: Style) -> Self {
+        self.accessible.insert(name.into(), style);
+        self
+    }
+
+    /// Get the names and styles used in accessible mode in place of the
+    /// usual ones, in order of name.
+    pub fn accessible_overrides(&self) -> impl Iterator<Item = (&str, &Style)> + '_ {
+        self.accessible
+            .iter()
+            .map(|(name, style)| (name.as_str(), style))
+    }
+
+    /// Get the theme as used in accessible mode, with its accessible
+    /// overrides applied.
+    pub fn for_accessible(&self) -> Cow<'_, Theme> {
+        if self.accessible.is_empty() {
+            return Cow::Borrowed(self);
+        }
+        let mut styles = self.styles.clone();
+        styles.extend(self.accessible.clone());
+        Cow::Owned(Self {
+            styles,
+            accessible: BTreeMap::new(),
+        })
+    }
+
+    /// Add the styles of `other`, which win over styles of the same name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use luxor::{Style, Theme};
+    ///
+    /// let overrides = Theme::empty().with_style("info", Style::new().italic());
+    /// let theme = Theme::new().merge(overrides);
+    /// assert_eq!(theme.get("info"), Some(&Style::new().italic()));
+    /// assert!(theme.get("warning").is_some());
+    /// ```
+    pub fn merge(mut self, other: Theme) -> Self {
+        self.styles.extend(other.styles);
+        self.accessible.extend(other.accessible);
+        self
+    }
+
+    /// Get the style called `name`.
+    pub fn get(&self, name: &str) -> Option<&Style> {
+        self.styles.get(name)
+    }
+
+    /// Get the names of the styles, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.styles.keys().map(String::as_str)
+    }
+
+    /// Get the style for `definition`: the theme's style of that name, or
+    /// else the definition parsed by [`Style::parse`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LuxorError::Style`] naming the definition if it is neither
+    /// a name in the theme nor a valid style.
+    pub fn resolve(&self, definition: &str) -> Result<Style> {
+        if let Some(style) = self.get(definition.trim()) {
+            return Ok(style.clone());
+        }
+        Style::parse(definition).map_err(|error| {
+            LuxorError::style(format!(
+                "'{}' is not a theme style or a style definition: {}",
+                definition, error
+            ))
+        })
+    }
+}
+
+/// Parse fixed style definitions into named styles.
+fn parse_entries(entries: &[Entry]) -> BTreeMap<String, Style> {
+    entries
+        .iter()
+        .map(|(name, definition)| {
+            // The definitions are fixed and covered by the tests
+            let style = Style::parse(definition).expect("default theme style is valid");
+            (name.to_string(), style)
+        })
+        .collect()
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, StandardColor};
+
+    #[test]
+    fn test_default_theme() {
+        let theme = Theme::new();
+        assert_eq!(theme.names().count(), DEFAULT_STYLES.len());
+        assert_eq!(
+            theme.get("warning"),
+            Some(&Style::new().with_color(Color::Standard(StandardColor::Yellow)))
+        );
+        assert_eq!(theme.get("repr.number").unwrap().bold, Some(true));
+        assert!(Theme::empty().get("warning").is_none());
+    }
+
+    #[test]
+    fn test_merge_and_resolve() {
+        let custom = Theme::empty()
+            .with_style("warning", Style::new().bold())
+            .with_style("note", Style::new().dim());
+        let theme = Theme::new().merge(custom);
+        assert_eq!(theme.resolve("warning").unwrap(), Style::new().bold());
+        assert_eq!(theme.resolve(" note ").unwrap(), Style::new().dim());
+        assert_eq!(theme.resolve("info").unwrap(), Theme::new().styles["info"]);
+        assert_eq!(theme.resolve("not bold").unwrap(), Style::new().bold_off());
+
+        let error = theme.resolve("warnign").unwrap_err().to_string();
+        assert!(error.contains("'warnign'"), "{error}");
+    }
+
+    #[test]
+    fn test_accessible_overrides() {
+        let theme = Theme::new();
+        assert_eq!(
+            theme.accessible_overrides().count(),
+            DEFAULT_ACCESSIBLE_STYLES.len()
+        );
+        let accessible = theme.for_accessible();
+        assert_eq!(accessible.get("warning").unwrap().bold, Some(true));
+        assert_eq!(theme.get("warning").unwrap().bold, None);
+        assert_eq!(accessible.get("repr.str"), theme.get("repr.str"));
+        assert!(matches!(Theme::empty().for_accessible(), Cow::Borrowed(_)));
+
+        // Later overrides win, as styles do
+        let custom = Theme::empty().with_accessible_override("warning", Style::new().italic());
+        let theme = Theme::new().merge(custom);
+        assert_eq!(
+            theme.for_accessible().get("warning"),
+            Some(&Style::new().italic())
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_theme_serde() {
+        let theme = Theme::empty().with_style("warning", Style::parse("bold yellow").unwrap());
+        let json = serde_json::to_string(&theme).unwrap();
+        assert_eq!(json, r#"{"warning":"bold yellow"}"#);
+        assert_eq!(serde_json::from_str::<Theme>(&json).unwrap(), theme);
+    }
+}
@@ -0,0 +1,263 @@
+//! Typed units for widths - terminal cells versus characters.
+//!
+//! A width in this crate is almost always a number of terminal cells, but a
+//! bare `usize` says nothing about that, and a character count or a byte
+//! offset fits in one just as well. Mixing them up goes unnoticed as long
+//! as the text is ASCII, and breaks the first time it meets `漢字` or an
+//! emoji. [`Cells`] and [`Chars`] keep the two apart: there is no
+//! conversion between them, and the only way to get cells from a string is
+//! to measure it with [`Cells::of_str`].
+//!
+//! # Migrating
+//!
+//! The core width-bearing APIs gain typed companions first, such as
+//! [`Segment::cells`](crate::Segment::cells) and
+//! [`Measurement::from_cells`](crate::Measurement::from_cells), while the
+//! `usize` versions stay so component code keeps compiling. Both units
+//! convert to and from `usize` to bridge the two.
+//!
+//! Before, nothing stops a character count passing as a width:
+//!
+//! ```rust
+//! use luxor::Measurement;
+//!
+//! let name = "漢字";
+//! let measurement = Measurement::fixed(name.chars().count());
+//! assert_eq!(measurement.maximum(), 2); // but it takes four cells
+//! ```
+//!
+//! After, the width has to come from a measurement:
+//!
+//! ```rust
+//! use luxor::{Measurement, units::{Cells, Chars, WidthPolicy}};
+//!
+//! let name = "漢字";
+//! let width = Cells::of_str(name, WidthPolicy::Standard);
+//! let measurement = Measurement::from_cells(width, width);
+//! assert_eq!(measurement.maximum_cells(), Cells(4));
+//! assert_eq!(Chars::of_str(name), Chars(2));
+//! ```
+
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, Sub, SubAssign},
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// How to count the cells of characters whose width depends on the
+/// terminal.
+///
+/// East Asian "ambiguous" characters, such as `±` or Greek letters, take
+/// one cell in most terminals but two in ones set up for CJK text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WidthPolicy {
+    /// Ambiguous characters take one cell, as everywhere else in this
+    /// crate.
+    #[default]
+    Standard,
+    /// Ambiguous characters take two cells.
+    Cjk,
+}
+
+/// A width in terminal cells.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::units::{Cells, WidthPolicy};
+///
+/// let width = Cells::of_str("ab漢", WidthPolicy::Standard);
+/// assert_eq!(width, Cells(4));
+/// assert_eq!(width - Cells(1), Cells(3));
+/// assert_eq!(Cells(1).saturating_sub(width), Cells::ZERO);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Cells(pub usize);
+
+/// A count of characters (Unicode scalar values), not of cells or bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::units::Chars;
+///
+/// let text = "ab漢字";
+/// assert_eq!(Chars::of_str(text), Chars(4));
+/// assert_eq!(Chars(3).byte_offset_in(text), 5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Chars(pub usize);
+
+impl Cells {
+    /// No width at all.
+    pub const ZERO: Self = Self(0);
+
+    /// Measure the cells `s` takes up on screen.
+    pub fn of_str(s: &str, policy: WidthPolicy) -> Self {
+        Self(match policy {
+            WidthPolicy::Standard => s.width(),
+            WidthPolicy::Cjk => s.width_cjk(),
+        })
+    }
+
+    /// Measure the cells `c` takes up on screen. Control characters take
+    /// none.
+    pub fn of_char(c: char, policy: WidthPolicy) -> Self {
+        Self(
+            match policy {
+                WidthPolicy::Standard => c.width(),
+                WidthPolicy::Cjk => c.width_cjk(),
+            }
+            .unwrap_or(0),
+        )
+    }
+
+    /// Get the number of cells.
+    pub fn get(self) -> usize {
+        self.0
+    }
+
+    /// Subtract `other`, stopping at zero.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Chars {
+    /// No characters at all.
+    pub const ZERO: Self = Self(0);
+
+    /// Count the characters of `s`.
+    pub fn of_str(s: &str) -> Self {
+        Self(s.chars().count())
+    }
+
+    /// Get the number of characters.
+    pub fn get(self) -> usize {
+        self.0
+    }
+
+    /// Subtract `other`, stopping at zero.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// Get the byte offset in `s` after this many characters, or the
+    /// length of `s` if it is shorter.
+    pub fn byte_offset_in(self, s: &str) -> usize {
+        s.char_indices()
+            .nth(self.0)
+            .map_or(s.len(), |(index, _)| index)
+    }
+}
+
+/// Arithmetic, conversions and formatting shared by both units.
+macro_rules! impl_unit {
+    ($unit:ident) => {
+        impl Add for $unit {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl Sub for $unit {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl Mul<usize> for $unit {
+            type Output = Self;
+
+            fn mul(self, times: usize) -> Self {
+                Self(self.0 * times)
+            }
+        }
+
+        impl AddAssign for $unit {
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl SubAssign for $unit {
+            fn sub_assign(&mut self, other: Self) {
+                self.0 -= other.0;
+            }
+        }
+
+        impl Sum for $unit {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|unit| unit.0).sum())
+            }
+        }
+
+        impl From<usize> for $unit {
+            fn from(count: usize) -> Self {
+                Self(count)
+            }
+        }
+
+        impl From<$unit> for usize {
+            fn from(unit: $unit) -> usize {
+                unit.0
+            }
+        }
+
+        impl fmt::Display for $unit {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+impl_unit!(Cells);
+impl_unit!(Chars);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Measurement, Segment, Segments, Style};
+
+    #[test]
+    fn test_cells_and_chars_differ_for_wide_text() {
+        let text = "a漢😀";
+        assert_eq!(Cells::of_str(text, WidthPolicy::Standard), Cells(5));
+        assert_eq!(Chars::of_str(text), Chars(3));
+        assert_eq!(Cells::of_str("±", WidthPolicy::Standard), Cells(1));
+        assert_eq!(Cells::of_str("±", WidthPolicy::Cjk), Cells(2));
+        assert_eq!(Cells::of_char('\x07', WidthPolicy::Standard), Cells::ZERO);
+        assert_eq!(Chars(1).byte_offset_in(text), 1);
+        assert_eq!(Chars(9).byte_offset_in(text), text.len());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut width = Cells(3) + Cells(4);
+        width -= Cells(2);
+        width += Cells(1) * 2;
+        assert_eq!(width, Cells(7));
+        assert_eq!([Cells(1), Cells(2)].into_iter().sum::<Cells>(), Cells(3));
+        assert_eq!(usize::from(Chars(5)), 5);
+        assert_eq!(Cells::from(5).to_string(), "5");
+    }
+
+    #[test]
+    fn test_typed_accessors_agree_with_usize_ones() {
+        let segment = Segment::new("ab漢字".to_string(), Style::new());
+        assert_eq!(segment.cells(), Cells(segment.cell_length()));
+        let segments = Segments::from(vec![segment, Segment::line()]);
+        assert_eq!(segments.cells(), Cells(6));
+
+        let measurement = Measurement::from_cells(Cells(2), Cells(6));
+        assert_eq!(measurement, Measurement::new(2, 6));
+        assert_eq!(measurement.minimum_cells(), Cells(2));
+        assert_eq!(measurement.maximum_cells(), Cells(6));
+    }
+}
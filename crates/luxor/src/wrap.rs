@@ -0,0 +1,392 @@
+//! Wrap - breaking text into lines at permitted break points.
+//!
+//! Where a line may break depends on the script: words in English are
+//! separated by spaces, while Chinese and Japanese may break between almost
+//! any two characters, and a URL has no spaces at all. A [`BreakStrategy`]
+//! reports the break points for a piece of text, and [`wrap`] fills each line
+//! greedily up to the last break point that fits.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use luxor::wrap::{wrap_str, WrapOptions};
+//!
+//! let lines = wrap_str("the quick brown fox", &WrapOptions::new(10));
+//! assert_eq!(lines, vec!["the quick", "brown fox"]);
+//! ```
+
+use crate::{Measurement, render_utils};
+use std::{fmt, ops::Range, sync::Arc};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// A shared [`BreakStrategy`].
+pub type SharedStrategy = Arc<dyn BreakStrategy>;
+
+/// The byte range of a wrapped line in the original text.
+pub type LineRange = Range<usize>;
+
+/// Adjacent characters as `(offset of second, first, second)`.
+type CharPair = (usize, char, char);
+
+/// A rule for where text may be broken across lines.
+pub trait BreakStrategy: fmt::Debug + Send + Sync {
+    /// Get the byte offsets at which `text` may break, in ascending order.
+    ///
+    /// A break at offset `i` ends a line with `text[..i]` and starts the next
+    /// with `text[i..]`. Offsets are at character boundaries, strictly
+    /// between `0` and `text.len()`. `text` never contains a newline.
+    fn break_points(&self, text: &str) -> Vec<usize>;
+}
+
+/// Break before each word that follows whitespace.
+///
+/// Whitespace at the end of a line is dropped when wrapping.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WhitespaceBreaks;
+
+impl BreakStrategy for WhitespaceBreaks {
+    fn break_points(&self, text: &str) -> Vec<usize> {
+        pairs(text)
+            .filter(|&(_, before, after)| before.is_whitespace() && !after.is_whitespace())
+            .map(|(index, _, _)| index)
+            .collect()
+    }
+}
+
+/// Break between CJK characters, following the common line-breaking rules
+/// for East Asian text.
+///
+/// This is a pragmatic subset of UAX #14: a break is allowed wherever a CJK
+/// character meets another character, except before closing punctuation
+/// such as `。` or `」` and after opening brackets such as `「`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnicodeLineBreaks;
+
+impl BreakStrategy for UnicodeLineBreaks {
+    fn break_points(&self, text: &str) -> Vec<usize> {
+        pairs(text)
+            .filter(|&(_, before, after)| {
+                (is_cjk(before) || is_cjk(after))
+                    && !before.is_whitespace()
+                    && !after.is_whitespace()
+                    && !NO_LINE_START.contains(after)
+                    && !NO_LINE_END.contains(before)
+            })
+            .map(|(index, _, _)| index)
+            .collect()
+    }
+}
+
+/// Break after the separators in URLs and paths: `/`, `?`, `&` and `-`.
+///
+/// Runs of separators such as `//` are kept together.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UrlAwareBreaks;
+
+impl BreakStrategy for UrlAwareBreaks {
+    fn break_points(&self, text: &str) -> Vec<usize> {
+        const SEPARATORS: &str = "/?&-";
+        pairs(text)
+            .filter(|&(_, before, after)| {
+                SEPARATORS.contains(before) && !SEPARATORS.contains(after) && !after.is_whitespace()
+            })
+            .map(|(index, _, _)| index)
+            .collect()
+    }
+}
+
+/// Break wherever any of several strategies allows it.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::wrap::{BreakStrategy, CompositeBreaks, UrlAwareBreaks, WhitespaceBreaks};
+///
+/// let strategy = CompositeBreaks::new()
+///     .with(WhitespaceBreaks)
+///     .with(UrlAwareBreaks);
+/// assert_eq!(strategy.break_points("see a/b"), vec![4, 6]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CompositeBreaks {
+    strategies: Vec<SharedStrategy>,
+}
+
+impl CompositeBreaks {
+    /// Create a composite that allows no breaks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also allow the breaks of `strategy`.
+    pub fn with(mut self, strategy: impl BreakStrategy + 'static) -> Self {
+        self.strategies.push(Arc::new(strategy));
+        self
+    }
+}
+
+impl BreakStrategy for CompositeBreaks {
+    fn break_points(&self, text: &str) -> Vec<usize> {
+        let mut points: Vec<usize> = self
+            .strategies
+            .iter()
+            .flat_map(|strategy| strategy.break_points(text))
+            .collect();
+        points.sort_unstable();
+        points.dedup();
+        points
+    }
+}
+
+/// Options for wrapping text into lines.
+#[derive(Debug, Clone)]
+pub struct WrapOptions {
+    /// Maximum width of a line in cells.
+    pub width: usize,
+    /// Where lines may break. Defaults to whitespace and CJK breaks.
+    pub strategy: SharedStrategy,
+}
+
+impl WrapOptions {
+    /// Create options for wrapping at `width` with the default strategy.
+    pub fn new(width: usize) -> Self {
+        let strategy = CompositeBreaks::new()
+            .with(WhitespaceBreaks)
+            .with(UnicodeLineBreaks);
+        Self {
+            width,
+            strategy: Arc::new(strategy),
+        }
+    }
+
+    /// Set the break strategy.
+    pub fn with_strategy(mut self, strategy: impl BreakStrategy + 'static) -> Self {
+        self.strategy = Arc::new(strategy);
+        self
+    }
+}
+
+/// Wrap text into lines of at most `options.width` cells.
+///
+/// Returns the byte range of each line in `text`. Newlines always break,
+/// and are not part of any range. Each line extends to the last break point
+/// that fits, with whitespace around breaks left out. A stretch without a
+/// fitting break point is split between characters instead; a single
+/// character wider than the width gets a line of its own.
+pub fn wrap(text: &str, options: &WrapOptions) -> Vec<LineRange> {
+    let width = options.width.max(1);
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for paragraph in text.split('\n') {
+        wrap_paragraph(
+            paragraph,
+            offset,
+            width,
+            options.strategy.as_ref(),
+            &mut lines,
+        );
+        offset += paragraph.len() + 1;
+    }
+    lines
+}
+
+/// Measure text as [`wrap`] lays it out with `strategy`.
+///
+/// The maximum is the width of the widest line, so text with newlines
+/// measures by its longest line rather than all of its lines end to end.
+/// The minimum is the widest stretch between break points, such as the
+/// longest word: the narrowest width that wraps without splitting inside
+/// one.
+///
+/// # Examples
+///
+/// ```rust
+/// use luxor::{Measurement, wrap::{WhitespaceBreaks, measure}};
+///
+/// let measurement = measure("a few words\nand a longer line", &WhitespaceBreaks);
+/// assert_eq!(measurement, Measurement::new(6, 17));
+/// ```
+pub fn measure(text: &str, strategy: &dyn BreakStrategy) -> Measurement {
+    let mut minimum = 0;
+    let mut maximum = 0;
+    for line in text.split('\n') {
+        maximum = maximum.max(line.width());
+        let mut start = 0;
+        let points = strategy.break_points(line);
+        for end in points.into_iter().chain([line.len()]) {
+            minimum = minimum.max(line[start..end].trim_end().width());
+            start = end;
+        }
+    }
+    Measurement::new(minimum, maximum)
+}
+
+/// Wrap text into owned lines. See [`wrap`].
+pub fn wrap_str(text: &str, options: &WrapOptions) -> Vec<String> {
+    wrap(text, options)
+        .into_iter()
+        .map(|range| text[range].to_string())
+        .collect()
+}
+
+/// Wrap one line of text, pushing ranges offset by `offset`.
+fn wrap_paragraph(
+    text: &str,
+    offset: usize,
+    width: usize,
+    strategy: &dyn BreakStrategy,
+    lines: &mut Vec<LineRange>,
+) {
+    let points = strategy.break_points(text);
+    let mut start = 0;
+    loop {
+        let rest = &text[start..];
+        if rest.is_empty() && start > 0 {
+            return;
+        }
+        if render_utils::fits(rest.trim_end(), width) {
+            let end = start + rest.trim_end().len();
+            lines.push(offset + start..offset + end);
+            return;
+        }
+
+        let after_start = points.partition_point(|&point| point <= start);
+        let fitting = points[after_start..]
+            .iter()
+            .copied()
+            .filter(|&point| !text[start..point].trim().is_empty())
+            .take_while(|&point| text[start..point].trim_end().width() <= width)
+            .last();
+        let (end, next) = match fitting {
+            Some(point) => (start + text[start..point].trim_end().len(), point),
+            None => {
+                let end = start + split_at_width(rest, width);
+                (end, end)
+            }
+        };
+        lines.push(offset + start..offset + end);
+        start = next + text[next..].len() - text[next..].trim_start().len();
+    }
+}
+
+/// Get the byte length of the longest prefix of `text` that fits `width`
+/// cells, taking at least one character.
+fn split_at_width(text: &str, width: usize) -> usize {
+    let mut used = 0;
+    for (index, c) in text.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            return if index == 0 { c.len_utf8() } else { index };
+        }
+    }
+    text.len()
+}
+
+/// Iterate over adjacent character pairs.
+fn pairs(text: &str) -> impl Iterator<Item = CharPair> + '_ {
+    text.char_indices()
+        .zip(text.chars().skip(1))
+        .map(|((index, before), after)| (index + before.len_utf8(), before, after))
+}
+
+/// Characters that may not start a line.
+const NO_LINE_START: &str = ")]}、。，．：；？！）」』】〉》〕〙〛ー々〜…‥・";
+/// Characters that may not end a line.
+const NO_LINE_END: &str = "([{（「『【〈《〔〘〚";
+
+/// Check if a character is from a CJK script, or CJK punctuation.
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1100}'..='\u{115F}'
+            | '\u{2E80}'..='\u{2FFF}'
+            | '\u{3000}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{A960}'..='\u{A97F}'
+            | '\u{AC00}'..='\u{D7AF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF00}'..='\u{FF60}'
+            | '\u{FFE0}'..='\u{FFE6}'
+            | '\u{20000}'..='\u{3FFFF}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_lines_and_words() {
+        let options = WrapOptions::new(0);
+        let measure = |text| measure(text, options.strategy.as_ref());
+        assert_eq!(measure("one\nthree\ntwo"), Measurement::new(5, 5));
+        assert_eq!(measure("ab cd\n"), Measurement::new(2, 5));
+        assert_eq!(measure(""), Measurement::new(0, 0));
+        assert_eq!(measure("supercalifragilistic"), Measurement::fixed(20));
+        // CJK text breaks between characters
+        assert_eq!(measure("漢字漢字"), Measurement::new(2, 8));
+    }
+
+    #[test]
+    fn test_wrap_whitespace() {
+        let options = WrapOptions::new(10);
+        assert_eq!(
+            wrap_str("the quick  brown fox jumps", &options),
+            vec!["the quick", "brown fox", "jumps"]
+        );
+        assert_eq!(wrap_str("a\n\nb", &options), vec!["a", "", "b"]);
+    }
+
+    #[test]
+    fn test_wrap_chinese_paragraph() {
+        let text = "敏捷的棕色狐狸跳过了懒狗。然后它又跑回来了，非常开心。";
+        let lines = wrap_str(text, &WrapOptions::new(10));
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.width() <= 10), "{:?}", lines);
+        assert!(
+            lines
+                .iter()
+                .take(lines.len() - 1)
+                .all(|line| line.width() >= 8)
+        );
+        assert_eq!(lines.concat(), text);
+        // Closing punctuation stays with the preceding character
+        assert!(lines.iter().all(|line| !line.starts_with(['。', '，'])));
+    }
+
+    #[test]
+    fn test_wrap_url_after_slashes() {
+        let options = WrapOptions::new(20).with_strategy(
+            CompositeBreaks::new()
+                .with(WhitespaceBreaks)
+                .with(UrlAwareBreaks),
+        );
+        let text = "see https://example.com/docs/guides/install-steps for details";
+        let lines = wrap_str(text, &options);
+        assert_eq!(
+            lines,
+            vec![
+                "see https://",
+                "example.com/docs/",
+                "guides/install-steps",
+                "for details",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_unbreakable_falls_back_to_characters() {
+        let options = WrapOptions::new(4);
+        assert_eq!(wrap_str("abcdefghij", &options), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap_str("漢", &WrapOptions::new(1)), vec!["漢"]);
+    }
+
+    #[test]
+    fn test_break_points() {
+        assert_eq!(WhitespaceBreaks.break_points("ab  cd"), vec![4]);
+        assert_eq!(UnicodeLineBreaks.break_points("漢字。"), vec![3]);
+        assert!(UnicodeLineBreaks.break_points("「漢」").is_empty());
+        assert_eq!(UrlAwareBreaks.break_points("a://b?c"), vec![4, 6]);
+    }
+}
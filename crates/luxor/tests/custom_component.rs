@@ -0,0 +1,140 @@
+//! A custom component built only on the public API.
+//!
+//! The badge below is written the way a third-party crate would write it,
+//! using `render_utils` for all layout work. If it stops compiling, the
+//! supported extension surface has regressed.
+
+use luxor::{
+    Color, Console, Measurable, Measurement, RenderOptions, Renderable, Result, Segment, Style,
+    Text, render_utils,
+};
+
+/// A pill-shaped label with rounded ends, e.g. `▐ PASS ▌`.
+struct Badge {
+    content: Text,
+    color: Color,
+}
+
+impl Badge {
+    /// Cells taken up by the caps and the padding inside them.
+    const DECORATION: usize = 4;
+
+    fn new(content: impl Into<Text>, color: Color) -> Self {
+        Self {
+            content: content.into(),
+            color,
+        }
+    }
+}
+
+impl Renderable for Badge {
+    fn render(&self, console: &Console, options: &RenderOptions) -> Result<Vec<Segment>> {
+        let body = Style::new().with_background(self.color);
+        let cap = Style::new().with_color(self.color);
+
+        let inner_options = render_utils::narrow(options, Self::DECORATION);
+        let lines = render_utils::split_lines(self.content.render(console, &inner_options)?);
+        let inner_width = lines
+            .iter()
+            .map(|line| render_utils::line_width(line))
+            .max()
+            .unwrap_or(0)
+            .min(inner_options.get_max_width());
+
+        let framed = lines
+            .into_iter()
+            .map(|line| {
+                let line = line
+                    .into_iter()
+                    .map(|segment| {
+                        let style = body.clone().combine(segment.style().clone());
+                        Segment::new(segment.text(), style)
+                    })
+                    .collect();
+                let mut output = vec![Segment::new("▐", cap.clone())];
+                output.extend(render_utils::fill(1, body.clone()));
+                output.extend(render_utils::adjust_line_length(
+                    line,
+                    inner_width,
+                    body.clone(),
+                ));
+                output.extend(render_utils::fill(1, body.clone()));
+                output.push(Segment::new("▌", cap.clone()));
+                output
+            })
+            .collect();
+        Ok(render_utils::frame_lines(framed))
+    }
+}
+
+impl Measurable for Badge {
+    fn measure(&self, _console: &Console, _options: &RenderOptions) -> Result<Measurement> {
+        let width = self
+            .content
+            .plain()
+            .lines()
+            .map(render_utils::visible_width)
+            .max()
+            .unwrap_or(0);
+        Ok(Measurement::fixed(width + Self::DECORATION))
+    }
+}
+
+fn plain_lines(segments: &[Segment]) -> Vec<String> {
+    let plain: String = segments.iter().map(|s| s.text()).collect();
+    plain.split('\n').map(String::from).collect()
+}
+
+#[test]
+fn test_badge_renders_pill() -> Result<()> {
+    let badge = Badge::new("PASS", Color::rgb(0, 160, 0));
+    let options = RenderOptions::new().with_max_width(40);
+    let segments = badge.render(&Console::new(), &options)?;
+
+    assert_eq!(plain_lines(&segments), vec!["▐ PASS ▌"]);
+    assert!(
+        segments[2]
+            .style()
+            .background
+            .is_some_and(|color| color == Color::rgb(0, 160, 0))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_badge_multiline_lines_up() -> Result<()> {
+    let badge = Badge::new("ok\nfailed", Color::rgb(200, 0, 0));
+    let options = RenderOptions::new().with_max_width(40);
+    let segments = badge.render(&Console::new(), &options)?;
+
+    assert_eq!(plain_lines(&segments), vec!["▐ ok     ▌", "▐ failed ▌"]);
+    Ok(())
+}
+
+#[test]
+fn test_badge_folds_to_width() -> Result<()> {
+    let badge = Badge::new("漢字漢字", Color::rgb(0, 0, 200));
+    let options = RenderOptions::new().with_max_width(9);
+    let segments = badge.render(&Console::new(), &options)?;
+
+    let lines = plain_lines(&segments);
+    assert_eq!(lines, vec!["▐ 漢字 ▌", "▐ 漢字 ▌"]);
+    assert!(
+        lines
+            .iter()
+            .all(|line| render_utils::visible_width(line) <= 9)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_badge_measure_matches_render() -> Result<()> {
+    let console = Console::new();
+    let options = RenderOptions::new().with_max_width(40);
+    let badge = Badge::new("v1.2.0", Color::rgb(80, 80, 80));
+
+    let measurement = badge.measure(&console, &options)?;
+    let segments = badge.render(&console, &options)?;
+    assert_eq!(render_utils::line_width(&segments), measurement.maximum());
+    Ok(())
+}
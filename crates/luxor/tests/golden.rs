@@ -0,0 +1,162 @@
+//! Golden-file test of a composed dashboard.
+//!
+//! The scene combines every component that can share a screen, so a
+//! regression in one primitive (width math, padding, guide characters)
+//! shows up here as a diff against a checked-in rendering. Each width is
+//! stored twice under `tests/golden/`: as plain text, and styled with ANSI
+//! escapes.
+//!
+//! After an intended change to the output, regenerate the files with
+//!
+//! ```text
+//! UPDATE_GOLDENS=1 cargo test -p luxor --features test-util --test golden
+//! ```
+//!
+//! and review the diff before committing it. Extend [`scene`] as new
+//! components land.
+
+use luxor::{
+    Badge, Color, ColorSystem, Console, ConsoleOptions, Justify, LineLayout, ProgressBar,
+    RenderOptions, Renderable, Result, Segment, Segments, Style, Text, Tree,
+    testing::{render_diff, render_lines},
+};
+use std::{fs, path::PathBuf};
+
+/// Widths the dashboard is rendered at.
+const WIDTHS: [usize; 3] = [40, 80, 120];
+
+/// Environment variable that rewrites the golden files instead of
+/// comparing against them.
+const UPDATE_GOLDENS: &str = "UPDATE_GOLDENS";
+
+/// One part of the dashboard.
+type Part = Box<dyn Renderable>;
+
+/// A rendering and the name of the golden file it is compared against.
+type Rendering = (String, String);
+
+/// Build the dashboard, top to bottom.
+fn scene() -> Result<Vec<Part>> {
+    let mut tree = Tree::new(Text::from_markup("[bold]luxor[/bold]")?)
+        .with_guide_style(Style::new().with_color(Color::rgb(100, 100, 100)));
+    tree.add("src")
+        .add(Text::from_markup("[green]console.rs[/green]")?);
+    tree.add("tests").add("golden.rs");
+
+    Ok(vec![
+        Box::new(
+            Text::from_markup("[bold underline]Build dashboard[/bold underline]")?
+                .with_justify(Justify::Center),
+        ),
+        Box::new(
+            LineLayout::new(usize::MAX)
+                .with_left(Badge::success("PASS"))
+                .with_right(Text::from_markup("[dim]12:00[/dim]")?)
+                .with_fill('.', Style::new().dim()),
+        ),
+        Box::new(
+            Text::from_markup(
+                "The [italic]nightly[/italic] build finished with [bold green]all checks \
+                 passing[/bold green], and the release candidate is ready for review by \
+                 the core team.",
+            )?
+            .with_justify(Justify::Full),
+        ),
+        Box::new(tree),
+        Box::new(
+            ProgressBar::new(100.0)
+                .with_completed(62.5)
+                .with_complete_style(Style::new().with_color(Color::rgb(0, 160, 0))),
+        ),
+    ])
+}
+
+/// Render the dashboard at `width`, separating parts with newlines.
+fn render_scene(width: usize) -> Result<Vec<Segment>> {
+    let console = Console::with_options(ConsoleOptions::new().with_max_width(width));
+    let options = RenderOptions::new()
+        .with_max_width(width)
+        .with_ascii_only(false)
+        .with_accessible(false);
+    let mut segments = Vec::new();
+    for (index, part) in scene()?.iter().enumerate() {
+        if index > 0 {
+            segments.push(Segment::line());
+        }
+        segments.extend(part.render(&console, &options)?);
+    }
+    Ok(segments)
+}
+
+/// The plain and styled renderings of the dashboard at `width`.
+fn renderings(width: usize) -> Result<Vec<Rendering>> {
+    let segments = render_scene(width)?;
+    let styled = Segments::from_vec(segments.clone()).render(ColorSystem::TrueColor);
+    Ok(vec![
+        (
+            format!("dashboard_{}.txt", width),
+            render_lines(&segments).join("\n"),
+        ),
+        (format!("dashboard_{}.ansi", width), styled),
+    ])
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(name)
+}
+
+/// Compare a rendering against its golden content, returning the diff.
+fn compare(golden: &str, actual: &str) -> std::result::Result<(), String> {
+    let lines = |text: &str| text.split('\n').map(String::from).collect::<Vec<_>>();
+    if golden == actual {
+        Ok(())
+    } else {
+        Err(render_diff(&lines(golden), &lines(actual)))
+    }
+}
+
+#[test]
+fn test_dashboard_matches_goldens() -> Result<()> {
+    let update = std::env::var_os(UPDATE_GOLDENS).is_some_and(|value| value != "0");
+    for width in WIDTHS {
+        for (name, actual) in renderings(width)? {
+            let path = golden_path(&name);
+            if update {
+                fs::create_dir_all(path.parent().unwrap())?;
+                fs::write(&path, &actual)?;
+                continue;
+            }
+            let golden = fs::read_to_string(&path).unwrap_or_else(|error| {
+                panic!(
+                    "cannot read {}: {}; run with {}=1 to create it",
+                    path.display(),
+                    error,
+                    UPDATE_GOLDENS
+                )
+            });
+            if let Err(diff) = compare(&golden, &actual) {
+                panic!(
+                    "{} differs from the golden file; run with {}=1 to update it:\n{}",
+                    name, UPDATE_GOLDENS, diff
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_goldens_catch_one_cell_padding_bug() -> Result<()> {
+    let (name, plain) = renderings(40)?.swap_remove(0);
+    let golden = fs::read_to_string(golden_path(&name))?;
+
+    // Shift one line right by a cell, as an off-by-one padding bug would
+    let mut lines: Vec<String> = plain.split('\n').map(String::from).collect();
+    lines[3].insert(0, ' ');
+    let diff = compare(&golden, &lines.join("\n")).unwrap_err();
+    assert!(diff.contains("+  4 │  checks"), "{}", diff);
+    Ok(())
+}
@@ -4,19 +4,19 @@
 //! and test the public API as it would be used by consumers.
 
 use luxor::{
-    Color, ColorSystem, Console, ConsoleOptions, Measurable, Renderable, Result, Segment, Style,
-    Text,
+    Color, ColorSystem, Console, ConsoleOptions, Measurable, RenderOptions, Renderable, Result,
+    Segment, Style, Text,
 };
 
 /// Test basic text rendering through the full pipeline.
 #[test]
 fn test_end_to_end_text_rendering() -> Result<()> {
-    let console = Console::new();
-    let options = ConsoleOptions::new().with_color(true);
+    let console = Console::with_options(ConsoleOptions::new().with_color(true));
+    let options = console.get_render_options();
 
     // Create styled text
-    let text =
-        Text::new("Hello, World!").with_style(Style::new().bold().color(Color::rgb(255, 0, 0)));
+    let text = Text::new("Hello, World!")
+        .with_style(Style::new().bold().with_color(Color::rgb(255, 0, 0)));
 
     // Render through the full pipeline
     let segments = text.render(&console, &options)?;
@@ -33,7 +33,6 @@ fn test_end_to_end_text_rendering() -> Result<()> {
 /// Test color downgrading through different color systems.
 #[test]
 fn test_color_system_compatibility() -> Result<()> {
-    let console = Console::new();
     let true_color = Color::rgb(128, 64, 192);
 
     // Test rendering with different color systems
@@ -42,8 +41,9 @@ fn test_color_system_compatibility() -> Result<()> {
         ColorSystem::EightBit,
         ColorSystem::TrueColor,
     ] {
-        let options = ConsoleOptions::new().with_color_system(color_system);
-        let text = Text::new("Test").with_style(Style::new().color(true_color));
+        let console = Console::with_options(ConsoleOptions::new().with_color_system(color_system));
+        let options = console.get_render_options();
+        let text = Text::new("Test").with_style(Style::new().with_color(true_color));
 
         let segments = text.render(&console, &options)?;
         assert_eq!(segments.len(), 1);
@@ -78,18 +78,18 @@ fn test_color_system_compatibility() -> Result<()> {
 #[test]
 fn test_complex_style_composition() -> Result<()> {
     let console = Console::new();
-    let options = ConsoleOptions::new();
+    let options = RenderOptions::new();
 
     // Create base style
     let base_style = Style::new()
         .bold()
-        .color(Color::rgb(255, 0, 0))
-        .background(Color::rgb(0, 0, 255));
+        .with_color(Color::rgb(255, 0, 0))
+        .with_background(Color::rgb(0, 0, 255));
 
     // Create overlay style
     let overlay_style = Style::new()
         .italic()
-        .color(Color::rgb(0, 255, 0)) // Should override base color
+        .with_color(Color::rgb(0, 255, 0)) // Should override base color
         .underline();
 
     // Combine styles
@@ -116,31 +116,31 @@ fn test_complex_style_composition() -> Result<()> {
 #[test]
 fn test_unicode_text_measurement() -> Result<()> {
     let console = Console::new();
-    let options = ConsoleOptions::new();
+    let options = RenderOptions::new();
 
-    // Test different types of text
+    // Test different types of text, as (minimum, maximum) widths: the
+    // longest word and the whole line
     let test_cases = [
-        ("ASCII", "Hello", 5),
-        ("Emoji", "👋🌍", 4),            // Each emoji is 2 width units
-        ("Chinese", "你好", 4),          // Each Chinese character is 2 width units
-        ("Mixed", "Hi 👋", 5),           // 2 + 1 + 2 = 5
-        ("Zero Width", "a\u{200B}b", 2), // Zero-width space should not count
+        ("ASCII", "Hello", (5, 5)),
+        ("Emoji", "👋🌍", (4, 4)),            // Each emoji is 2 width units
+        ("Chinese", "你好", (4, 4)),          // Each Chinese character is 2 width units
+        ("Mixed", "Hi 👋", (2, 5)),           // 2 + 1 + 2 = 5
+        ("Zero Width", "a\u{200B}b", (2, 2)), // Zero-width space should not count
     ];
 
-    for (name, text, expected_width) in test_cases {
+    for (name, text, (minimum, maximum)) in test_cases {
         let text_obj = Text::new(text);
         let measurement = text_obj.measure(&console, &options)?;
 
         assert_eq!(
             measurement.minimum(),
-            expected_width,
+            minimum,
             "Failed for {}: expected {}, got {}",
             name,
-            expected_width,
+            minimum,
             measurement.minimum()
         );
-        assert_eq!(measurement.maximum(), expected_width);
-        assert!(measurement.is_fixed());
+        assert_eq!(measurement.maximum(), maximum, "Failed for {}", name);
     }
 
     Ok(())
@@ -199,18 +199,17 @@ fn test_console_size_management() {
 /// Test console options and their effects.
 #[test]
 fn test_console_options_effects() -> Result<()> {
-    let console = Console::new();
-
     // Test with color disabled
-    let no_color_options = ConsoleOptions::new().with_color(false);
-    assert_eq!(no_color_options.get_color_system(), ColorSystem::Standard);
+    let console = Console::with_options(ConsoleOptions::new().with_color(false));
+    assert_eq!(console.color_system(), ColorSystem::Standard);
+    let no_color_options = console.get_render_options();
 
     // Test with specific width
-    let fixed_width_options = ConsoleOptions::new().with_max_width(80);
+    let fixed_width_options = RenderOptions::new().with_max_width(80);
     assert_eq!(fixed_width_options.get_max_width(), 80);
 
     // Test rendering with disabled color
-    let text = Text::new("Colored").with_style(Style::new().color(Color::rgb(255, 0, 0)));
+    let text = Text::new("Colored").with_style(Style::new().with_color(Color::rgb(255, 0, 0)));
     let segments = text.render(&console, &no_color_options)?;
 
     // Should still render but color system should be limited
@@ -243,7 +242,7 @@ fn test_ansi_processing() {
 #[test]
 fn test_performance_characteristics() -> Result<()> {
     let console = Console::new();
-    let options = ConsoleOptions::new();
+    let options = RenderOptions::new();
 
     // Test that large text can be processed efficiently
     let large_text = "Lorem ipsum ".repeat(1000);
@@ -282,7 +281,7 @@ fn test_thread_safety() {
     use std::thread;
 
     let console = Arc::new(Console::new());
-    let style = Arc::new(Style::new().bold().color(Color::rgb(255, 0, 0)));
+    let style = Arc::new(Style::new().bold().with_color(Color::rgb(255, 0, 0)));
 
     let handles: Vec<_> = (0..4)
         .map(|i| {
@@ -290,7 +289,7 @@ fn test_thread_safety() {
             let style = Arc::clone(&style);
             thread::spawn(move || {
                 let text = Text::new(&format!("Thread {}", i)).with_style((*style).clone());
-                let options = ConsoleOptions::new();
+                let options = RenderOptions::new();
                 text.render(&console, &options).unwrap()
             })
         })
@@ -308,7 +307,7 @@ fn test_thread_safety() {
 #[test]
 fn test_edge_cases() -> Result<()> {
     let console = Console::new();
-    let options = ConsoleOptions::new();
+    let options = RenderOptions::new();
 
     // Test empty text
     let empty_text = Text::new("");
@@ -319,10 +318,18 @@ fn test_edge_cases() -> Result<()> {
     // Test very long lines
     let long_line = "a".repeat(10000);
     let long_text = Text::new(&long_line);
-    let segments = long_text.render(&console, &options)?;
+    let soft_wrap = options.clone().with_soft_wrap(true);
+    let segments = long_text.render(&console, &soft_wrap)?;
     assert_eq!(segments.len(), 1);
     assert_eq!(segments[0].text().len(), 10000);
 
+    // Without soft wrapping the line is folded to the width, losing nothing
+    let narrow = options.clone().with_max_width(80);
+    let segments = long_text.render(&console, &narrow)?;
+    let plain: String = segments.iter().map(|s| s.text()).collect();
+    assert_eq!(plain.replace('\n', ""), long_line);
+    assert!(plain.lines().all(|line| line.len() <= 80));
+
     // Test all possible style combinations
     let all_styles = Style::new()
         .bold()
@@ -333,8 +340,8 @@ fn test_edge_cases() -> Result<()> {
         .reverse()
         .blink()
         .hidden()
-        .color(Color::rgb(255, 128, 64))
-        .background(Color::rgb(64, 128, 255));
+        .with_color(Color::rgb(255, 128, 64))
+        .with_background(Color::rgb(64, 128, 255));
 
     let styled_text = Text::new("All styles").with_style(all_styles);
     let segments = styled_text.render(&console, &options)?;
@@ -0,0 +1,393 @@
+//! Adversarial inputs for the public API.
+//!
+//! Luxor runs inside long-lived services, where a panic while drawing a
+//! status line takes the whole process down. Every check here calls public
+//! functions with hostile input (empty strings, isolated combining marks,
+//! megabyte lines, deeply nested markup, zero and `usize::MAX` sizes) under
+//! [`catch_unwind`] and fails with the list of calls that panicked. Returning
+//! an error is fine; panicking is not.
+
+use luxor::{
+    Badge, BigText, Console, EllipsisConfig, Justify, LineLayout, Measurable, Measurement,
+    NumericColumn, NumericOverflow, Padding, ProgressBar, RenderOptions, Renderable, Segment,
+    Segments, Spacer, Span, Spinner, Style, Text, Tree, ansi,
+    highlighter::{Highlighter, ReprHighlighter},
+    markup, render_utils, wrap,
+};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// Widths at and around the edges.
+const WIDTHS: [usize; 5] = [0, 1, 2, 3, usize::MAX];
+
+/// Widths for output padded to the full width. Padding to `usize::MAX`
+/// cells cannot be allocated, so these stop short of it.
+fn padded_widths() -> impl Iterator<Item = usize> {
+    WIDTHS.into_iter().filter(|&width| width < 1 << 24)
+}
+
+/// Strings that have tripped up terminal libraries.
+fn hostile_strings() -> Vec<String> {
+    vec![
+        String::new(),
+        " ".to_string(),
+        "\u{301}".to_string(),
+        "\u{301}\u{301}\u{301}a\u{301}".to_string(),
+        "\u{200d}\u{fe0f}\u{200b}".to_string(),
+        "👨‍👩‍👧‍👦 日本語 ｗｉｄｅ".to_string(),
+        "שלום עולם hello".to_string(),
+        "\t\r\n\n\r\x00\x07\x1b[31mred\x1b[0m".to_string(),
+        "[".to_string(),
+        "]".to_string(),
+        "[/]".to_string(),
+        "[[bold]]".to_string(),
+        "[bold".to_string(),
+        "\\[bold]".to_string(),
+        "[link=]x[/link]".to_string(),
+        "x".repeat(1 << 20),
+        "ab ".repeat(1 << 15),
+        "日".repeat(1 << 14),
+    ]
+}
+
+/// Markup nested `depth` tags deep, both well-formed and unclosed.
+fn nested_markup(depth: usize) -> Vec<String> {
+    let open = "[bold]".repeat(depth);
+    let close = "[/bold]".repeat(depth);
+    vec![
+        format!("{}x{}", open, close),
+        format!("{}x", open),
+        format!("x{}", close),
+        "[".repeat(depth),
+        "]".repeat(depth),
+    ]
+}
+
+/// A component under test and its name.
+type Component = (&'static str, Box<dyn Renderable>);
+
+/// Collects the names of calls that panicked.
+#[derive(Default)]
+struct Checker {
+    panics: Vec<String>,
+}
+
+impl Checker {
+    fn check<T>(&mut self, name: impl Into<String>, call: impl FnOnce() -> T) {
+        if catch_unwind(AssertUnwindSafe(call)).is_err() {
+            self.panics.push(name.into());
+        }
+    }
+
+    fn assert_none(self) {
+        assert!(
+            self.panics.is_empty(),
+            "{} call(s) panicked:\n  {}",
+            self.panics.len(),
+            self.panics.join("\n  ")
+        );
+    }
+}
+
+fn short(input: &str) -> String {
+    let mut name: String = input.chars().take(12).collect();
+    if input.chars().count() > 12 {
+        name.push_str(&format!("…({} bytes)", input.len()));
+    }
+    format!("{:?}", name)
+}
+
+fn options(width: usize) -> RenderOptions {
+    RenderOptions::new().with_max_width(width)
+}
+
+#[test]
+fn test_text_never_panics() {
+    let console = Console::new();
+    let mut checker = Checker::default();
+    for input in hostile_strings() {
+        let name = short(&input);
+        // Folding a long line at a narrow width makes hundreds of thousands
+        // of lines, so it is only folded at a terminal's width to keep the
+        // suite fast
+        let widths: Vec<usize> = if input.len() > 1 << 16 {
+            vec![80]
+        } else {
+            padded_widths().collect()
+        };
+        for justify in [
+            Justify::Left,
+            Justify::Center,
+            Justify::Right,
+            Justify::Full,
+            Justify::FullAll,
+        ] {
+            let text = Text::new(&input).with_justify(justify);
+            for &width in &widths {
+                checker.check(format!("Text {} {:?} @ {}", name, justify, width), || {
+                    text.render(&console, &options(width))
+                });
+            }
+        }
+        let text = Text::new(&input);
+        checker.check(format!("Text {} @ MAX", name), || {
+            text.render(&console, &options(usize::MAX))
+        });
+        checker.check(format!("Text::measure {}", name), || {
+            text.measure(&console, &options(0))
+        });
+        for width in WIDTHS {
+            checker.check(format!("Text::truncate {} @ {}", name, width), || {
+                let mut text = text.clone();
+                text.truncate(width, &EllipsisConfig::default());
+                text
+            });
+        }
+        for width in widths.iter().copied().chain([usize::MAX]) {
+            checker.check(format!("Text::wrap {} @ {}", name, width), || {
+                text.wrap(&wrap::WrapOptions::new(width))
+            });
+        }
+        for position in [0, 1, input.len(), input.len() + 1, usize::MAX] {
+            checker.check(format!("Text::style_at {} {}", name, position), || {
+                text.style_at(position)
+            });
+            checker.check(format!("Text::insert_text {} {}", name, position), || {
+                let mut text = text.clone();
+                text.insert_text(position, Text::new("é"))
+            });
+            checker.check(format!("Text::stylize_range {} {}", name, position), || {
+                let mut text = text.clone();
+                text.stylize_range(position..usize::MAX, Style::new().bold())?;
+                text.stylize_range(usize::MAX..position, Style::new().bold())?;
+                text.stylize_range(0..position, Style::new().bold())
+            });
+        }
+        checker.check(format!("Text::assemble {}", name), || {
+            Text::assemble([Text::new(&input), Text::new(&input)], &input)
+        });
+        checker.check(format!("ReprHighlighter {}", name), || {
+            ReprHighlighter::new().highlight(&mut text.clone())
+        });
+    }
+    checker.assert_none();
+}
+
+#[test]
+fn test_markup_never_panics() {
+    let console = Console::new();
+    let mut checker = Checker::default();
+    let mut inputs = hostile_strings();
+    inputs.extend(nested_markup(10_000));
+    for input in inputs {
+        let name = short(&input);
+        checker.check(format!("markup::render {}", name), || {
+            markup::render(&input, None)
+        });
+        checker.check(format!("markup::render_lossy {}", name), || {
+            markup::render_lossy(&input, None)
+        });
+        checker.check(format!("markup::escape {}", name), || {
+            markup::escape(&input)
+        });
+        checker.check(format!("render markup {}", name), || {
+            Text::from_markup_lossy(&input)
+                .0
+                .render(&console, &options(40))
+        });
+    }
+    checker.assert_none();
+}
+
+#[test]
+fn test_span_edges_never_panic() {
+    let mut checker = Checker::default();
+    let spans = [
+        Span::new(0, 0, Style::new()),
+        Span::new(5, 5, Style::new()),
+        Span::new(5, 2, Style::new()),
+        Span::new(0, usize::MAX, Style::new()),
+        Span::new(usize::MAX, usize::MAX, Style::new()),
+    ];
+    for span in &spans {
+        for offset in [0, 1, 2, 5, usize::MAX - 1, usize::MAX] {
+            checker.check(format!("{:?}.split({})", span, offset), || {
+                span.split(offset)
+            });
+        }
+        for offset in [0, 1, -1, isize::MAX, isize::MIN] {
+            checker.check(format!("{:?}.move_by({})", span, offset), || {
+                span.move_by(offset)
+            });
+        }
+        checker.check(format!("{:?}.len()", span), || span.len());
+    }
+    checker.assert_none();
+}
+
+#[test]
+fn test_segments_never_panic() {
+    let mut checker = Checker::default();
+    for input in hostile_strings() {
+        let name = short(&input);
+        let segment = Segment::new(input.clone(), Style::new().bold());
+        for position in [0, 1, 2, input.len(), usize::MAX] {
+            checker.check(
+                format!("Segment::split_at_char {} {}", name, position),
+                || segment.clone().split_at_char(position),
+            );
+        }
+        for width in WIDTHS {
+            checker.check(
+                format!("Segment::split_at_width {} {}", name, width),
+                || segment.clone().split_at_width(width),
+            );
+            checker.check(
+                format!("Segment::split_at_width_padded {} {}", name, width),
+                || segment.clone().split_at_width_padded(width),
+            );
+            checker.check(
+                format!("Segment::truncate_cells {} {}", name, width),
+                || segment.clone().truncate_cells(width, false),
+            );
+            checker.check(
+                format!("Segments::truncate_cells {} {}", name, width),
+                || {
+                    let mut segments = Segments::from_vec(vec![segment.clone(), Segment::line()]);
+                    segments.truncate_cells(width);
+                    segments
+                },
+            );
+            checker.check(format!("ansi::truncate_visible {} {}", name, width), || {
+                ansi::truncate_visible(&input, width, Some("…"))
+            });
+        }
+        checker.check(format!("split_lines {}", name), || {
+            render_utils::split_lines(vec![segment.clone(), Segment::line(), segment.clone()])
+        });
+        checker.check(format!("strip_ansi {}", name), || ansi::strip_ansi(&input));
+        checker.check(format!("text_width {}", name), || ansi::text_width(&input));
+        checker.check(format!("wrap_str {}", name), || {
+            wrap::wrap_str(&input, &wrap::WrapOptions::new(usize::MAX))
+        });
+    }
+    checker.assert_none();
+}
+
+#[test]
+fn test_measurement_and_padding_extremes_never_panic() {
+    let mut checker = Checker::default();
+    let sizes = [0, 1, usize::MAX - 1, usize::MAX];
+    for &a in &sizes {
+        for &b in &sizes {
+            // `Measurement::new` documents a panic for a minimum above the
+            // maximum, so only valid measurements are built
+            let measurement = Measurement::new(a.min(b), a.max(b));
+            checker.check(format!("Measurement({}, {})", a, b), || {
+                (
+                    measurement.add_width(b),
+                    measurement.subtract_width(b),
+                    measurement.clamp(a, b),
+                    measurement.add_with(Measurement::fixed(b)),
+                    measurement.max_with(Measurement::fixed(a)),
+                )
+            });
+            checker.check(format!("Padding({}, {})", a, b), || {
+                let padding = Padding::new(a, b, a, b);
+                (
+                    padding.horizontal(),
+                    padding.vertical(),
+                    padding.to_string(),
+                )
+            });
+        }
+    }
+    checker.check("Padding::parse overflow", || {
+        Padding::parse("99999999999999999999999999")
+    });
+    for &size in &sizes {
+        checker.check(format!("ansi::cursor {}", size), || {
+            (ansi::cursor::up(size), ansi::cursor::position(size, size))
+        });
+    }
+    checker.assert_none();
+}
+
+#[test]
+fn test_components_never_panic() {
+    let console = Console::new();
+    let mut checker = Checker::default();
+    for input in hostile_strings().into_iter().take(15) {
+        let name = short(&input);
+        let mut tree = Tree::new(input.as_str());
+        tree.add(input.as_str()).add(input.as_str());
+        let components: Vec<Component> = vec![
+            ("Badge", Box::new(Badge::new(input.as_str()))),
+            (
+                "pill Badge",
+                Box::new(Badge::info(input.as_str()).with_pill(true)),
+            ),
+            ("BigText", Box::new(BigText::new(input.as_str()))),
+            (
+                "Spinner",
+                Box::new(Spinner::new().with_text(input.as_str())),
+            ),
+            ("Tree", Box::new(tree)),
+        ];
+        for (component, renderable) in &components {
+            for width in WIDTHS {
+                checker.check(format!("{} {} @ {}", component, name, width), || {
+                    renderable.render(&console, &options(width))
+                });
+            }
+        }
+        let layout = LineLayout::new(usize::MAX)
+            .with_left(Text::new(&input))
+            .with_right(Badge::new(input.as_str()))
+            .with_min_gap(usize::MAX);
+        for width in padded_widths() {
+            checker.check(format!("LineLayout {} @ {}", name, width), || {
+                layout.render(&console, &options(width))
+            });
+        }
+    }
+    for total in [0.0, -1.0, f64::NAN, f64::INFINITY, f64::MIN_POSITIVE] {
+        for completed in [0.0, -1.0, f64::NAN, f64::INFINITY, f64::MAX] {
+            let bar = ProgressBar::new(total).with_completed(completed);
+            for width in [0, 1, 2, 80] {
+                checker.check(
+                    format!("ProgressBar {}/{} @ {}", completed, total, width),
+                    || bar.render(&console, &options(width)),
+                );
+            }
+        }
+    }
+    let extremes = [
+        f64::MAX,
+        f64::MIN,
+        f64::NAN,
+        -0.0,
+        f64::MIN_POSITIVE,
+        999_999.9,
+    ];
+    for overflow in [
+        NumericOverflow::Scale,
+        NumericOverflow::ClipLeft { marker: '…' },
+    ] {
+        let column = NumericColumn::new(extremes)
+            .with_thousands_separator(',')
+            .with_overflow(overflow);
+        for width in padded_widths() {
+            checker.check(format!("NumericColumn {:?} @ {}", overflow, width), || {
+                column.render(&console, &options(width))
+            });
+        }
+    }
+    for spacer in [Spacer::width(0), Spacer::lines(0), Spacer::fill()] {
+        for width in [0, 1, 80] {
+            checker.check(format!("{:?} @ {}", spacer, width), || {
+                spacer.render(&console, &options(width))
+            });
+        }
+    }
+    checker.assert_none();
+}
@@ -3,7 +3,7 @@
 //! These tests use proptest to generate random inputs and verify
 //! that certain properties always hold true.
 
-use luxor::{Color, ColorSystem, Measurement, Segment, Style};
+use luxor::{Color, ColorSystem, Measurement, Segment, Segments, Style, Text, render_utils};
 use proptest::prelude::*;
 
 // Strategies for generating test data
@@ -26,7 +26,7 @@ fn color_strategy() -> impl Strategy<Value = Color> {
 
 /// Strategy for generating styles with random attributes.
 fn style_strategy() -> impl Strategy<Value = Style> {
-    (
+    let attributes = (
         prop::option::of(color_strategy()), // color
         prop::option::of(color_strategy()), // background
         prop::option::of(any::<bool>()),    // bold
@@ -37,9 +37,30 @@ fn style_strategy() -> impl Strategy<Value = Style> {
         prop::option::of(any::<bool>()),    // reverse
         prop::option::of(any::<bool>()),    // blink
         prop::option::of(any::<bool>()),    // hidden
-    )
-        .prop_map(
-            |(
+    );
+    let decorations = (
+        prop::option::of(any::<bool>()), // underline2
+        prop::option::of(any::<bool>()), // overline
+        prop::option::of(any::<bool>()), // frame
+        prop::option::of(any::<bool>()), // encircle
+    );
+    (attributes, decorations).prop_map(
+        |(
+            (
+                color,
+                background,
+                bold,
+                italic,
+                underline,
+                strikethrough,
+                dim,
+                reverse,
+                blink,
+                hidden,
+            ),
+            (underline2, overline, frame, encircle),
+        )| {
+            Style {
                 color,
                 background,
                 bold,
@@ -50,21 +71,42 @@ fn style_strategy() -> impl Strategy<Value = Style> {
                 reverse,
                 blink,
                 hidden,
-            )| {
-                Style {
-                    color,
-                    background,
-                    bold,
-                    italic,
-                    underline,
-                    strikethrough,
-                    dim,
-                    reverse,
-                    blink,
-                    hidden,
-                }
-            },
-        )
+                underline2,
+                overline,
+                frame,
+                encircle,
+                link: None,
+            }
+        },
+    )
+}
+
+/// Strategy for generating text with a base style and random spans.
+fn text_strategy() -> impl Strategy<Value = Text> {
+    (
+        r"[a-c漢é ]{0,12}",
+        style_strategy(),
+        prop::collection::vec((0usize..13, 0usize..13, style_strategy()), 0..4),
+    )
+        .prop_map(|(content, base_style, spans)| {
+            let mut text = Text::new(&content).with_style(base_style);
+            let len = text.len();
+            for (a, b, style) in spans {
+                let (start, end) = (a.min(b).min(len), a.max(b).min(len));
+                text.stylize_range(start..end, style).unwrap();
+            }
+            text
+        })
+}
+
+/// Strategy for generating a line of styled segments, including wide
+/// characters and combining marks.
+fn line_strategy() -> impl Strategy<Value = Vec<Segment>> {
+    prop::collection::vec(
+        (r"[a-c漢字é \u{301}]{0,6}", style_strategy())
+            .prop_map(|(text, style)| Segment::new(text, style)),
+        0..6,
+    )
 }
 
 /// Helper function to convert index to standard color.
@@ -276,6 +318,51 @@ proptest! {
         }
     }
 
+    /// Test that a style's display form parses back to its canonical form.
+    #[test]
+    fn style_display_round_trip(style in style_strategy()) {
+        use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}};
+
+        let parsed = Style::parse(&style.to_string()).unwrap();
+        let canonical = style.clone().canonicalize();
+        prop_assert_eq!(&parsed, &canonical);
+
+        // Equal canonical styles must hash equal to be usable as cache keys
+        let hash = |style: &Style| {
+            let mut hasher = DefaultHasher::new();
+            style.hash(&mut hasher);
+            hasher.finish()
+        };
+        prop_assert_eq!(hash(&parsed), hash(&canonical));
+        prop_assert_eq!(canonical.clone().canonicalize(), canonical);
+    }
+
+    /// Test that appending text preserves the styles of both pieces.
+    #[test]
+    fn append_text_preserves_styles(left in text_strategy(), right in text_strategy()) {
+        let mut joined = left.clone();
+        joined.append_text(right.clone());
+
+        for position in 0..left.len() {
+            prop_assert_eq!(joined.style_at(position), left.style_at(position));
+        }
+        // The appended piece inherits only what it leaves unset
+        for position in 0..right.len() {
+            let expected = left.base_style().clone().combine(right.style_at(position));
+            prop_assert_eq!(joined.style_at(left.len() + position), expected);
+        }
+    }
+
+    /// Test that truncated ANSI strings fit and keep their visible prefix.
+    #[test]
+    fn truncate_visible_fits(text in r"[\x1b\[;0-9mab漢]{0,24}", max_cells in 0usize..12) {
+        use luxor::ansi::{strip_ansi, text_width, truncate_visible};
+
+        let truncated = truncate_visible(&text, max_cells, None);
+        prop_assert!(text_width(&truncated) <= max_cells);
+        prop_assert!(strip_ansi(&text).starts_with(&strip_ansi(&truncated)));
+    }
+
     /// Test that style parsing handles various input formats gracefully.
     #[test]
     fn style_parsing_robustness(input in r"[a-zA-Z0-9 #_-]{0,50}") {
@@ -326,4 +413,126 @@ proptest! {
         prop_assert!(narrowed.maximum() <= m1.maximum());
         prop_assert!(narrowed.minimum() <= narrowed.maximum());
     }
+
+    /// Test that markup without closing brackets loses no text.
+    #[test]
+    fn markup_without_tags_preserves_text(text in r"[\[a-c漢é /]{0,24}") {
+        use luxor::markup::{render, render_lossy};
+
+        let expected = text.replace("[[", "[");
+        let rendered = render(&text, None).unwrap();
+        prop_assert_eq!(rendered.plain(), expected.as_str());
+
+        let (lossy, warnings) = render_lossy(&text, None);
+        prop_assert_eq!(lossy.plain(), expected.as_str());
+        prop_assert!(warnings.is_empty());
+    }
+
+    /// Test that rendering escaped text gives back the text.
+    #[test]
+    fn markup_escape_round_trips(text in r"[\[\]\\a漢/ ]{0,24}|\PC{0,40}") {
+        use luxor::markup::{escape, render};
+
+        let rendered = render(&escape(&text), None).unwrap();
+        prop_assert_eq!(rendered.plain(), text.as_str());
+    }
+
+    /// Test that lossy markup rendering never panics or grows the text.
+    #[test]
+    fn markup_lossy_never_grows(text in r"\PC{0,40}") {
+        use luxor::markup::render_lossy;
+
+        let (rendered, _) = render_lossy(&text, None);
+        prop_assert!(rendered.plain().chars().count() <= text.chars().count());
+        let _ = Style::parse(&text);
+    }
+
+    /// Test that stripped output never contains escape characters.
+    #[test]
+    fn ansi_stripping_removes_all_escapes(text in r"[\x1b\[;0-9mHa漢]{0,24}") {
+        use luxor::ansi::strip_ansi;
+
+        let stripped = strip_ansi(&text);
+        prop_assert!(!stripped.contains('\x1b'));
+        prop_assert_eq!(strip_ansi(&stripped), stripped);
+    }
+}
+
+// Cell-width invariants of the segment helpers
+
+proptest! {
+    /// Test that fill produces exactly the requested width.
+    #[test]
+    fn fill_is_exact(width in 0usize..40) {
+        let filled = render_utils::fill(width, Style::new());
+        prop_assert_eq!(filled.map_or(0, |segment| segment.cell_length()), width);
+    }
+
+    /// Test that adjusting a line pads or crops it to exactly the width.
+    #[test]
+    fn adjust_line_length_is_exact(line in line_strategy(), width in 0usize..30) {
+        let adjusted = render_utils::adjust_line_length(line.clone(), width, Style::new());
+        prop_assert_eq!(render_utils::line_width(&adjusted), width);
+        if width >= render_utils::line_width(&line) {
+            prop_assert_eq!(&adjusted[..line.len()], &line[..]);
+        }
+    }
+
+    /// Test that truncating a segment fits, and padding makes it exact.
+    #[test]
+    fn segment_truncate_cells_fits(
+        text in r"[a-c漢字é \u{301}]{0,12}",
+        width in 0usize..16,
+    ) {
+        let segment = Segment::new(text, Style::new());
+        let full = segment.cell_length();
+        let cropped = segment.clone().truncate_cells(width, false);
+        prop_assert!(cropped.cell_length() <= width);
+        // Only a wide character straddling the cut is lost
+        prop_assert!(cropped.cell_length() + 1 >= width.min(full));
+        let padded = segment.truncate_cells(width, true);
+        prop_assert_eq!(padded.cell_length(), width.min(full));
+    }
+
+    /// Test that truncating segments fits and keeps the cached width right.
+    #[test]
+    fn segments_truncate_cells_fits(line in line_strategy(), width in 0usize..30) {
+        let mut segments = Segments::from_vec(line.clone());
+        prop_assert_eq!(segments.width(), render_utils::line_width(&line));
+        segments.truncate_cells(width);
+        prop_assert_eq!(segments.width(), render_utils::line_width(segments.as_slice()));
+        prop_assert!(segments.width() <= width);
+        prop_assert!(segments.width() + 1 >= width.min(render_utils::line_width(&line)));
+    }
+
+    /// Test that the cached width follows every change to segments.
+    #[test]
+    fn segments_width_tracks_changes(
+        lines in prop::collection::vec(line_strategy(), 1..5),
+        widths in prop::collection::vec(0usize..20, 1..5),
+    ) {
+        let mut segments = Segments::new();
+        for (index, line) in lines.into_iter().enumerate() {
+            if index % 2 == 0 {
+                segments.extend(line);
+            } else {
+                line.into_iter().for_each(|segment| segments.push(segment));
+            }
+            segments.truncate_cells(widths[index % widths.len()] + 10);
+            prop_assert_eq!(segments.width(), render_utils::line_width(segments.as_slice()));
+        }
+        let collected: Segments = segments.iter().cloned().collect();
+        prop_assert_eq!(collected.width(), segments.width());
+    }
+
+    /// Test that splitting lines keeps the width of every line.
+    #[test]
+    fn split_lines_keeps_widths(lines in prop::collection::vec(line_strategy(), 1..5)) {
+        let joined = render_utils::frame_lines(lines.clone());
+        let split = render_utils::split_lines(joined);
+        prop_assert_eq!(split.len(), lines.len());
+        for (line, original) in split.iter().zip(&lines) {
+            prop_assert_eq!(render_utils::line_width(line), render_utils::line_width(original));
+        }
+    }
 }
